@@ -38,6 +38,8 @@ pub const CLI_CONFIG_HOME_ENV: &str = "RESTATE_CLI_CONFIG_HOME";
 pub const CLI_CONFIG_FILE_ENV: &str = "RESTATE_CLI_CONFIG";
 
 pub const RESTATE_AUTH_TOKEN_ENV: &str = "RESTATE_AUTH_TOKEN";
+pub const RESTATE_ADMIN_AUTH_TOKEN_ENV: &str = "RESTATE_ADMIN_AUTH_TOKEN";
+pub const RESTATE_ADMIN_CLIENT_CERT_ENV: &str = "RESTATE_ADMIN_CLIENT_CERT";
 // TODO: Deprecated, will be removed once this is provided by the admin server
 pub const INGRESS_URL_ENV: &str = "RESTATE_INGRESS_URL";
 pub const ADMIN_URL_ENV: &str = "RESTATE_ADMIN_URL";
@@ -50,6 +52,10 @@ pub struct CliConfig {
     pub ingress_base_url: Option<Url>,
     pub admin_base_url: Option<Url>,
     pub bearer_token: Option<String>,
+    /// Bearer token sent to the admin API only, overriding `bearer_token`.
+    pub admin_bearer_token: Option<String>,
+    /// PEM file containing the client certificate and private key presented to the admin API.
+    pub admin_client_certificate: Option<PathBuf>,
 
     #[cfg(feature = "cloud")]
     pub cloud: crate::commands::cloud::CloudConfig,
@@ -65,6 +71,8 @@ impl CliConfig {
             ingress_base_url: Some(Url::parse("http://localhost:8080/").unwrap()),
             admin_base_url: Some(Url::parse("http://localhost:9070/").unwrap()),
             bearer_token: None,
+            admin_bearer_token: None,
+            admin_client_certificate: None,
 
             #[cfg(feature = "cloud")]
             cloud: crate::commands::cloud::CloudConfig::default(),
@@ -224,6 +232,19 @@ impl CliEnv {
             figment
         };
 
+        let figment = if let Some(admin_bearer_token) = os_env.get(RESTATE_ADMIN_AUTH_TOKEN_ENV) {
+            figment.merge(("admin_bearer_token", admin_bearer_token))
+        } else {
+            figment
+        };
+
+        let figment =
+            if let Some(admin_client_certificate) = os_env.get(RESTATE_ADMIN_CLIENT_CERT_ENV) {
+                figment.merge(("admin_client_certificate", admin_client_certificate))
+            } else {
+                figment
+            };
+
         Ok(figment)
     }
 
@@ -292,6 +313,14 @@ impl CliEnv {
         }
     }
 
+    /// Bearer token sent to the admin API, defaulting to the [`Self::bearer_token`].
+    pub fn admin_bearer_token(&self) -> Result<Option<&str>> {
+        match &self.config.admin_bearer_token {
+            Some(admin_bearer_token) => Ok(Some(admin_bearer_token)),
+            None => self.bearer_token(),
+        }
+    }
+
     pub fn write_environment(&self, environment: &str) -> std::io::Result<()> {
         if let Some(parent) = self.environment_file.parent() {
             std::fs::create_dir_all(parent)?
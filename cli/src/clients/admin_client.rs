@@ -10,7 +10,7 @@
 
 //! A wrapper client for admin HTTP service.
 
-use anyhow::bail;
+use anyhow::{Context, bail};
 use http::StatusCode;
 use restate_admin_rest_model::version::{AdminApiVersion, VersionInformation};
 use restate_cli_util::{CliContext, c_warn};
@@ -119,7 +119,7 @@ pub struct AdminClient {
 
 impl AdminClient {
     pub async fn new(env: &CliEnv) -> anyhow::Result<Self> {
-        let raw_client = reqwest::Client::builder()
+        let mut raw_client = reqwest::Client::builder()
             .user_agent(format!(
                 "{}/{} {}-{}",
                 env!("CARGO_PKG_NAME"),
@@ -128,11 +128,22 @@ impl AdminClient {
                 std::env::consts::ARCH,
             ))
             .connect_timeout(CliContext::get().connect_timeout())
-            .danger_accept_invalid_certs(CliContext::get().insecure_skip_tls_verify())
-            .build()?;
+            .danger_accept_invalid_certs(CliContext::get().insecure_skip_tls_verify());
+
+        if let Some(path) = &env.config.admin_client_certificate {
+            let pem = std::fs::read(path).with_context(|| {
+                format!(
+                    "Failed reading the admin client certificate '{}'",
+                    path.display()
+                )
+            })?;
+            raw_client = raw_client.identity(reqwest::Identity::from_pem(&pem)?);
+        }
+
+        let raw_client = raw_client.build()?;
 
         let base_url = env.admin_base_url()?.clone();
-        let bearer_token = env.bearer_token()?.map(str::to_string);
+        let bearer_token = env.admin_bearer_token()?.map(str::to_string);
 
         let client = Self {
             inner: raw_client,
@@ -12,6 +12,7 @@ use super::AdminClient;
 use super::admin_client::Envelope;
 
 use restate_admin_rest_model::deployments::*;
+use restate_admin_rest_model::invocations::RestartAsNewInvocationResponse;
 use restate_admin_rest_model::services::*;
 use restate_admin_rest_model::version::VersionInformation;
 use restate_types::schema::service::ServiceMetadata;
@@ -42,6 +43,16 @@ pub trait AdminClientInterface {
 
     async fn cancel_invocation(&self, id: &str, kill: bool) -> reqwest::Result<Envelope<()>>;
 
+    async fn pause_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>>;
+
+    async fn resume_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>>;
+
+    async fn restart_as_new_invocation(
+        &self,
+        id: &str,
+        from_entry: u32,
+    ) -> reqwest::Result<Envelope<RestartAsNewInvocationResponse>>;
+
     async fn patch_state(
         &self,
         service: &str,
@@ -122,6 +133,27 @@ impl AdminClientInterface for AdminClient {
         self.run(reqwest::Method::DELETE, url).await
     }
 
+    async fn pause_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>> {
+        let url = self.versioned_url(["invocations", id, "pause"]);
+        self.run(reqwest::Method::PATCH, url).await
+    }
+
+    async fn resume_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>> {
+        let url = self.versioned_url(["invocations", id, "resume"]);
+        self.run(reqwest::Method::PATCH, url).await
+    }
+
+    async fn restart_as_new_invocation(
+        &self,
+        id: &str,
+        from_entry: u32,
+    ) -> reqwest::Result<Envelope<RestartAsNewInvocationResponse>> {
+        let mut url = self.versioned_url(["invocations", id, "restart-as-new"]);
+        url.set_query(Some(&format!("from_entry={from_entry}")));
+
+        self.run(reqwest::Method::PATCH, url).await
+    }
+
     async fn patch_state(
         &self,
         service: &str,
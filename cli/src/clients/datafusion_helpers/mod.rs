@@ -43,6 +43,7 @@ pub enum InvocationState {
     Ready,
     Running,
     Suspended,
+    Paused,
     BackingOff,
     Completed,
 }
@@ -56,6 +57,7 @@ impl FromStr for InvocationState {
             "ready" => Self::Ready,
             "running" => Self::Running,
             "suspended" => Self::Suspended,
+            "paused" => Self::Paused,
             "backing-off" => Self::BackingOff,
             "completed" => Self::Completed,
             _ => Self::Unknown,
@@ -72,6 +74,7 @@ impl Display for InvocationState {
             InvocationState::Ready => write!(f, "ready"),
             InvocationState::Running => write!(f, "running"),
             InvocationState::Suspended => write!(f, "suspended"),
+            InvocationState::Paused => write!(f, "paused"),
             InvocationState::BackingOff => write!(f, "backing-off"),
             InvocationState::Completed => write!(f, "completed"),
         }
@@ -0,0 +1,208 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::clients::AdminClient;
+
+use anyhow::{Result, bail};
+use cling::prelude::*;
+use futures::TryStreamExt;
+use indicatif::ProgressBar;
+use restate_admin_rest_model::invocations::{
+    BulkInvocationOperation, BulkInvocationsEvent, BulkInvocationsFilter, BulkInvocationsRequest,
+    InvocationStatusFilter,
+};
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_println, c_success, c_warn};
+
+/// Ids of the [`BulkFilterOpts`] arguments, which select a bulk operation.
+pub const BULK_FILTER_ARGS: [&str; 8] = [
+    "service",
+    "handler",
+    "status",
+    "created_after",
+    "created_before",
+    "modified_after",
+    "modified_before",
+    "where_clause",
+];
+
+/// Conditions selecting the invocations of a bulk operation.
+#[derive(Args, Collect, Clone, Default)]
+pub struct BulkFilterOpts {
+    /// Select the invocations of this service
+    #[clap(long)]
+    service: Option<String>,
+    /// Select the invocations of this handler
+    #[clap(long)]
+    handler: Option<String>,
+    /// Select the invocations in this status, can be repeated.
+    /// One of `pending`, `scheduled`, `ready`, `running`, `backing-off`, `suspended`, `paused`, `completed`
+    #[clap(long, value_parser = parse_status)]
+    status: Vec<InvocationStatusFilter>,
+    /// Select the invocations created at or after this RFC 3339 timestamp, e.g. `2025-01-01T00:00:00Z`
+    #[clap(long)]
+    created_after: Option<humantime::Timestamp>,
+    /// Select the invocations created before this RFC 3339 timestamp
+    #[clap(long)]
+    created_before: Option<humantime::Timestamp>,
+    /// Select the invocations last modified at or after this RFC 3339 timestamp
+    #[clap(long)]
+    modified_after: Option<humantime::Timestamp>,
+    /// Select the invocations last modified before this RFC 3339 timestamp
+    #[clap(long)]
+    modified_before: Option<humantime::Timestamp>,
+    /// Select the invocations matching this SQL where clause over the `sys_invocation` columns,
+    /// e.g. `target_service_name = 'Greeter' AND retry_count > 10`
+    #[clap(long = "query", value_name = "WHERE_CLAUSE")]
+    where_clause: Option<String>,
+}
+
+impl From<&BulkFilterOpts> for BulkInvocationsFilter {
+    fn from(opts: &BulkFilterOpts) -> Self {
+        BulkInvocationsFilter {
+            service: opts.service.clone(),
+            handler: opts.handler.clone(),
+            status: opts.status.clone(),
+            created_after: opts.created_after.clone(),
+            created_before: opts.created_before.clone(),
+            modified_after: opts.modified_after.clone(),
+            modified_before: opts.modified_before.clone(),
+            where_clause: opts.where_clause.clone(),
+        }
+    }
+}
+
+fn parse_status(status: &str) -> Result<InvocationStatusFilter, String> {
+    serde_json::from_value(serde_json::Value::String(status.to_owned()))
+        .map_err(|_| format!("unknown invocation status '{status}'"))
+}
+
+/// Applies the operation to all the invocations matching the given filter,
+/// asking for confirmation once the number of matching invocations is known.
+pub async fn run_bulk_operation(
+    client: &AdminClient,
+    filter: &BulkFilterOpts,
+    operation: BulkInvocationOperation,
+    dry_run: bool,
+) -> Result<()> {
+    let verb = match operation {
+        BulkInvocationOperation::Cancel => "cancel",
+        BulkInvocationOperation::Kill => "kill",
+        BulkInvocationOperation::Purge => "purge",
+    };
+
+    // First count the matching invocations
+    let filter = BulkInvocationsFilter::from(filter);
+    let mut events = send_bulk_request(client, &filter, operation, true).await?;
+    let Some(BulkInvocationsEvent::Matched { count }) = events.try_next().await? else {
+        bail!("Unexpected response from the server, expected the count of matching invocations");
+    };
+    if count == 0 {
+        bail!("No invocations found matching the filter!");
+    }
+    if dry_run {
+        c_println!("{count} invocations would be affected by {verb}");
+        return Ok(());
+    }
+
+    confirm_or_exit(&format!(
+        "Are you sure you want to {verb} {count} invocations?"
+    ))?;
+
+    let mut events = send_bulk_request(client, &filter, operation, false).await?;
+
+    let progress = ProgressBar::new(0);
+    progress.set_style(
+        indicatif::ProgressStyle::with_template("{spinner} [{elapsed}] {bar:40} {pos}/{len} {msg}")
+            .unwrap(),
+    );
+    progress.set_message(format!("Submitting {verb} commands"));
+
+    let mut submitted = 0;
+    let mut failures = vec![];
+    while let Some(event) = events.try_next().await? {
+        match event {
+            BulkInvocationsEvent::Matched { count } => progress.set_length(count as u64),
+            BulkInvocationsEvent::Submitted { count, .. } => {
+                submitted += count;
+                progress.inc(count as u64);
+            }
+            BulkInvocationsEvent::Failed {
+                partition_id,
+                count,
+                reason,
+            } => {
+                failures.push((partition_id, count, reason));
+                progress.inc(count as u64);
+            }
+        }
+    }
+    progress.finish_and_clear();
+
+    c_println!();
+    for (partition_id, count, reason) in &failures {
+        c_warn!("Failed to submit {count} commands to partition {partition_id}: {reason}");
+    }
+    if !failures.is_empty() {
+        bail!(
+            "Submitted {submitted} {verb} commands, but {} could not be submitted",
+            failures.iter().map(|(_, count, _)| count).sum::<usize>()
+        );
+    }
+    c_success!("Submitted {submitted} {verb} commands successfully");
+
+    Ok(())
+}
+
+async fn send_bulk_request(
+    client: &AdminClient,
+    filter: &BulkInvocationsFilter,
+    operation: BulkInvocationOperation,
+    dry_run: bool,
+) -> Result<impl futures::Stream<Item = Result<BulkInvocationsEvent>> + Unpin> {
+    let response = client
+        .prepare(
+            reqwest::Method::POST,
+            client.versioned_url(["invocations", "bulk"]),
+        )
+        .json(&BulkInvocationsRequest {
+            filter: filter.clone(),
+            operation,
+            dry_run,
+        })
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        bail!("Bulk invocation operation failed ({status}): {body}");
+    }
+
+    // The response is a stream of newline delimited JSON events
+    let mut buffer = Vec::new();
+    let lines = response
+        .bytes_stream()
+        .map_err(anyhow::Error::from)
+        .map_ok(move |chunk| {
+            buffer.extend_from_slice(&chunk);
+            let mut lines = vec![];
+            while let Some(newline) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=newline).collect();
+                lines.push(Ok::<_, anyhow::Error>(line));
+            }
+            futures::stream::iter(lines)
+        })
+        .try_flatten();
+
+    Ok(Box::pin(lines.and_then(|line| async move {
+        Ok(serde_json::from_slice::<BulkInvocationsEvent>(&line)?)
+    })))
+}
@@ -8,12 +8,17 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod bulk;
 mod cancel;
 mod describe;
 mod list;
+mod pause;
 mod purge;
+mod restart;
+mod resume;
 
 use cling::prelude::*;
+use restate_types::identifiers::InvocationId;
 
 #[derive(Run, Subcommand, Clone)]
 pub enum Invocations {
@@ -25,4 +30,52 @@ pub enum Invocations {
     Cancel(cancel::Cancel),
     /// Purge a completed invocation, or a set of invocations. This command affects only completed invocations.
     Purge(purge::Purge),
+    /// Pause a running or suspended invocation, or a set of invocations, until they're resumed
+    Pause(pause::Pause),
+    /// Resume a paused invocation, or a set of invocations
+    Resume(resume::Resume),
+    /// Restart a completed invocation as a new invocation, reusing a prefix of its retained journal
+    Restart(restart::Restart),
+}
+
+/// Builds the `sys_invocation` filter selecting the invocations of a query, which is either an
+/// invocation id or a target string exact match or prefix. The filter is parenthesized, so that
+/// it can be combined with other conditions.
+fn invocation_target_filter(query: &str) -> String {
+    let q = query.trim().replace('\'', "''");
+    if let Ok(id) = q.parse::<InvocationId>() {
+        return format!("(id = '{id}')");
+    }
+
+    match q.matches('/').count() {
+        0 => format!("(target LIKE '{q}/%')"),
+        // If there's one slash, let's add the wildcard depending on the service type,
+        // so we discriminate correctly with serviceName/handlerName with workflowName/workflowKey
+        1 => format!(
+            "((target = '{q}' AND target_service_ty = 'service') OR (target LIKE '{q}/%' AND target_service_ty != 'service'))"
+        ),
+        // Can only be exact match here
+        _ => format!("(target LIKE '{q}')"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_filter_depends_on_the_number_of_slashes() {
+        assert_eq!(
+            "(target LIKE 'Greeter/%')",
+            invocation_target_filter(" Greeter ")
+        );
+        assert_eq!(
+            "((target = 'Greeter/greet' AND target_service_ty = 'service') OR (target LIKE 'Greeter/greet/%' AND target_service_ty != 'service'))",
+            invocation_target_filter("Greeter/greet")
+        );
+        assert_eq!(
+            "(target LIKE 'Counter/my''key/add')",
+            invocation_target_filter("Counter/my'key/add")
+        );
+    }
 }
@@ -0,0 +1,70 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::cli_env::CliEnv;
+use crate::clients::datafusion_helpers::find_active_invocations_simple;
+use crate::clients::{self, AdminClientInterface};
+use crate::commands::invocations::invocation_target_filter;
+use crate::ui::invocations::render_simple_invocation_list;
+
+use anyhow::{Result, bail};
+use cling::prelude::*;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_println, c_success};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_pause")]
+pub struct Pause {
+    /// Either an invocation id, or a target string exact match or prefix, e.g.:
+    /// * `invocationId`
+    /// * `serviceName`
+    /// * `serviceName/handler`
+    /// * `virtualObjectName`
+    /// * `virtualObjectName/key`
+    /// * `virtualObjectName/key/handler`
+    /// * `workflowName`
+    /// * `workflowName/key`
+    /// * `workflowName/key/handler`
+    query: String,
+}
+
+pub async fn run_pause(State(env): State<CliEnv>, opts: &Pause) -> Result<()> {
+    let client = clients::AdminClient::new(&env).await?;
+    let sql_client = clients::DataFusionHttpClient::from(client.clone());
+
+    // Only invocations that started executing can be paused
+    let filter = format!(
+        "{} AND status IN ('ready', 'running', 'backing-off', 'suspended')",
+        invocation_target_filter(&opts.query)
+    );
+
+    let invocations = find_active_invocations_simple(&sql_client, &filter).await?;
+    if invocations.is_empty() {
+        bail!(
+            "No invocations found for query {}! Note that the pause command works only on running, backing-off or suspended invocations.",
+            opts.query
+        );
+    };
+
+    render_simple_invocation_list(&invocations);
+
+    // Get the invocation and confirm
+    confirm_or_exit("Are you sure you want to pause these invocations?")?;
+
+    for inv in invocations {
+        let result = client.pause_invocation(&inv.id).await?;
+        let _ = result.success_or_error()?;
+    }
+
+    c_println!();
+    c_success!("Request was sent successfully");
+
+    Ok(())
+}
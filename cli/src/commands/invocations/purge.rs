@@ -11,13 +11,15 @@
 use crate::cli_env::CliEnv;
 use crate::clients::datafusion_helpers::find_active_invocations_simple;
 use crate::clients::{self, AdminClientInterface};
+use crate::commands::invocations::bulk::{BULK_FILTER_ARGS, BulkFilterOpts, run_bulk_operation};
+use crate::commands::invocations::invocation_target_filter;
 use crate::ui::invocations::render_simple_invocation_list;
 
 use anyhow::{Result, bail};
 use cling::prelude::*;
+use restate_admin_rest_model::invocations::BulkInvocationOperation;
 use restate_cli_util::ui::console::confirm_or_exit;
 use restate_cli_util::{c_println, c_success};
-use restate_types::identifiers::InvocationId;
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_purge")]
@@ -33,36 +35,46 @@ pub struct Purge {
     /// * `workflowName`
     /// * `workflowName/key`
     /// * `workflowName/key/handler`
-    query: String,
+    #[clap(
+        required_unless_present_any = BULK_FILTER_ARGS,
+        conflicts_with_all = BULK_FILTER_ARGS
+    )]
+    query: Option<String>,
+    // Instead of a single query, purge all the invocations matching these conditions
+    #[clap(flatten)]
+    bulk_filter: BulkFilterOpts,
+    /// Only print the number of invocations matching the bulk filter
+    #[clap(long, conflicts_with = "query")]
+    dry_run: bool,
 }
 
 pub async fn run_purge(State(env): State<CliEnv>, opts: &Purge) -> Result<()> {
     let client = clients::AdminClient::new(&env).await?;
+
+    if opts.query.is_none() {
+        return run_bulk_operation(
+            &client,
+            &opts.bulk_filter,
+            BulkInvocationOperation::Purge,
+            opts.dry_run,
+        )
+        .await;
+    }
+
     let sql_client = clients::DataFusionHttpClient::from(client.clone());
 
-    let q = opts.query.trim();
-    let filter = if let Ok(id) = q.parse::<InvocationId>() {
-        format!("id = '{id}'")
-    } else {
-        match q.find('/').unwrap_or_default() {
-            0 => format!("target LIKE '{q}/%'"),
-            // If there's one slash, let's add the wildcard depending on the service type,
-            // so we discriminate correctly with serviceName/handlerName with workflowName/workflowKey
-            1 => format!(
-                "(target = '{q}' AND target_service_ty = 'service') OR (target LIKE '{q}/%' AND target_service_ty != 'service'))"
-            ),
-            // Can only be exact match here
-            _ => format!("target LIKE '{q}'"),
-        }
-    };
+    let query = opts.query.as_deref().unwrap_or_default();
     // Filter only by completed, this command has no effect on non-completed invocations
-    let filter = format!("{filter} AND status = 'completed'");
+    let filter = format!(
+        "{} AND status = 'completed'",
+        invocation_target_filter(query)
+    );
 
     let invocations = find_active_invocations_simple(&sql_client, &filter).await?;
     if invocations.is_empty() {
         bail!(
             "No invocations found for query {}! Note that the purge command works only on completed invocations. If you need to cancel/kill an invocation, consider using the cancel command.",
-            opts.query
+            query
         );
     };
 
@@ -0,0 +1,69 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::cli_env::CliEnv;
+use crate::clients::datafusion_helpers::find_active_invocations_simple;
+use crate::clients::{self, AdminClientInterface};
+use crate::ui::invocations::render_simple_invocation_list;
+
+use anyhow::{Result, bail};
+use cling::prelude::*;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_println, c_success};
+use restate_types::identifiers::InvocationId;
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_restart")]
+pub struct Restart {
+    /// The id of the completed invocation to restart
+    invocation_id: InvocationId,
+
+    /// Index of the last journal entry to reuse in the new invocation.
+    /// Entries up to this index (included) won't be executed again, 0 reuses only the input.
+    #[clap(long, default_value_t = 0)]
+    from_entry: u32,
+}
+
+pub async fn run_restart(State(env): State<CliEnv>, opts: &Restart) -> Result<()> {
+    let client = clients::AdminClient::new(&env).await?;
+    let sql_client = clients::DataFusionHttpClient::from(client.clone());
+
+    // Only completed invocations can be restarted
+    let filter = format!("id = '{}' AND status = 'completed'", opts.invocation_id);
+
+    let invocations = find_active_invocations_simple(&sql_client, &filter).await?;
+    if invocations.is_empty() {
+        bail!(
+            "No completed invocation found with id {}! Note that the restart command works only on completed invocations whose journal was retained.",
+            opts.invocation_id
+        );
+    };
+
+    render_simple_invocation_list(&invocations);
+
+    confirm_or_exit(&format!(
+        "Are you sure you want to restart this invocation as new from journal entry {}?",
+        opts.from_entry
+    ))?;
+
+    let response = client
+        .restart_as_new_invocation(&opts.invocation_id.to_string(), opts.from_entry)
+        .await?
+        .into_body()
+        .await?;
+
+    c_println!();
+    c_success!(
+        "Request was sent successfully, the new invocation id is {}",
+        response.new_invocation_id
+    );
+
+    Ok(())
+}
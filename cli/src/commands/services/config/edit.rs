@@ -85,6 +85,11 @@ fn write_out_edit_toml(w: &mut impl io::Write, service_type: ServiceType) -> Res
         writeln!(w)?;
     }
 
+    write_prefixed_lines(w, "# ", super::patch::JOURNAL_RETENTION_EDIT_DESCRIPTION)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# journal_retention = \"1day\"")?;
+    writeln!(w)?;
+
     write_prefixed_lines(w, "# ", super::patch::INACTIVITY_TIMEOUT_EDIT_DESCRIPTION)?;
     writeln!(w, "# Example:")?;
     writeln!(w, "# inactivity_timeout = \"1min\"")?;
@@ -95,6 +100,36 @@ fn write_out_edit_toml(w: &mut impl io::Write, service_type: ServiceType) -> Res
     writeln!(w, "# abort_timeout = \"10min\"")?;
     writeln!(w)?;
 
+    writeln!(
+        w,
+        "# Retry policy to use for the invocations of this service. This overrides the default retry policy set in invoker options."
+    )?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# [retry_policy]")?;
+    writeln!(w, "# type = \"exponential\"")?;
+    writeln!(w, "# initial-interval = \"100ms\"")?;
+    writeln!(w, "# factor = 2.0")?;
+    writeln!(w, "# max-attempts = 10")?;
+    writeln!(w, "# max-interval = \"30s\"")?;
+    writeln!(w)?;
+
+    write_prefixed_lines(w, "# ", super::view::DEAD_LETTER_QUEUE)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# dead_letter_queue = true")?;
+    writeln!(w)?;
+
+    write_prefixed_lines(w, "# ", super::view::VALIDATE_INPUT)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# validate_input = true")?;
+    writeln!(w)?;
+
+    if service_type.has_state() {
+        write_prefixed_lines(w, "# ", super::patch::STATE_TTL_EDIT_DESCRIPTION)?;
+        writeln!(w, "# Example:")?;
+        writeln!(w, "# state_ttl = \"30days\"")?;
+        writeln!(w)?;
+    }
+
     Ok(())
 }
 
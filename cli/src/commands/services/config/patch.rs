@@ -30,6 +30,11 @@ pub(super) const WORKFLOW_RETENTION_EDIT_DESCRIPTION: &str = concatcp!(
     "\n",
     DURATION_EDIT_DESCRIPTION
 );
+pub(super) const JOURNAL_RETENTION_EDIT_DESCRIPTION: &str = concatcp!(
+    super::view::JOURNAL_RETENTION,
+    "\n",
+    DURATION_EDIT_DESCRIPTION
+);
 pub(super) const INACTIVITY_TIMEOUT_EDIT_DESCRIPTION: &str = concatcp!(
     super::view::INACTIVITY_TIMEOUT,
     "\n",
@@ -37,6 +42,8 @@ pub(super) const INACTIVITY_TIMEOUT_EDIT_DESCRIPTION: &str = concatcp!(
 );
 pub(super) const ABORT_TIMEOUT_EDIT_DESCRIPTION: &str =
     concatcp!(super::view::ABORT_TIMEOUT, "\n", DURATION_EDIT_DESCRIPTION);
+pub(super) const STATE_TTL_EDIT_DESCRIPTION: &str =
+    concatcp!(super::view::STATE_TTL, "\n", DURATION_EDIT_DESCRIPTION);
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_patch")]
@@ -50,12 +57,24 @@ pub struct Patch {
     #[clap(long, alias = "workflow_completion_retention", help = WORKFLOW_RETENTION_EDIT_DESCRIPTION)]
     workflow_completion_retention: Option<String>,
 
+    #[clap(long, alias = "journal_retention", help = JOURNAL_RETENTION_EDIT_DESCRIPTION)]
+    journal_retention: Option<String>,
+
     #[clap(long, alias = "inactivity_retention", help = INACTIVITY_TIMEOUT_EDIT_DESCRIPTION)]
     inactivity_timeout: Option<String>,
 
     #[clap(long, alias = "abort_retention", help = ABORT_TIMEOUT_EDIT_DESCRIPTION)]
     abort_timeout: Option<String>,
 
+    #[clap(long, alias = "dead_letter_queue", help = super::view::DEAD_LETTER_QUEUE)]
+    dead_letter_queue: Option<bool>,
+
+    #[clap(long, alias = "validate_input", help = super::view::VALIDATE_INPUT)]
+    validate_input: Option<bool>,
+
+    #[clap(long, alias = "state_ttl", help = STATE_TTL_EDIT_DESCRIPTION)]
+    state_ttl: Option<String>,
+
     /// Service name
     service: String,
 }
@@ -83,6 +102,11 @@ async fn patch(env: &CliEnv, opts: &Patch) -> Result<()> {
                     .context("Cannot parse workflow_completion_retention")
             })
             .transpose()?,
+        journal_retention: opts
+            .journal_retention
+            .as_ref()
+            .map(|s| DurationString::parse_duration(s).context("Cannot parse journal_retention"))
+            .transpose()?,
         inactivity_timeout: opts
             .inactivity_timeout
             .as_ref()
@@ -93,6 +117,15 @@ async fn patch(env: &CliEnv, opts: &Patch) -> Result<()> {
             .as_ref()
             .map(|s| DurationString::parse_duration(s).context("Cannot parse abort_timeout"))
             .transpose()?,
+        retry_policy: None,
+        dead_letter_queue: opts.dead_letter_queue,
+        cors: None,
+        validate_input: opts.validate_input,
+        state_ttl: opts
+            .state_ttl
+            .as_ref()
+            .map(|s| DurationString::parse_duration(s).context("Cannot parse state_ttl"))
+            .transpose()?,
     };
 
     apply_service_configuration_patch(opts.service.clone(), admin_client, modify_request).await
@@ -107,8 +140,13 @@ pub(super) async fn apply_service_configuration_patch(
     if modify_request.public.is_none()
         && modify_request.workflow_completion_retention.is_none()
         && modify_request.idempotency_retention.is_none()
+        && modify_request.journal_retention.is_none()
         && modify_request.inactivity_timeout.is_none()
         && modify_request.abort_timeout.is_none()
+        && modify_request.retry_policy.is_none()
+        && modify_request.dead_letter_queue.is_none()
+        && modify_request.validate_input.is_none()
+        && modify_request.state_ttl.is_none()
     {
         c_println!("No changes requested");
         return Ok(());
@@ -131,6 +169,12 @@ pub(super) async fn apply_service_configuration_patch(
             humantime::Duration::from(*workflow_completion_retention),
         );
     }
+    if let Some(journal_retention) = &modify_request.journal_retention {
+        table.add_kv_row(
+            "Journal retention:",
+            humantime::Duration::from(*journal_retention),
+        );
+    }
     if let Some(inactivity_timeout) = &modify_request.inactivity_timeout {
         table.add_kv_row(
             "Inactivity timeout:",
@@ -140,6 +184,18 @@ pub(super) async fn apply_service_configuration_patch(
     if let Some(abort_timeout) = &modify_request.abort_timeout {
         table.add_kv_row("Abort timeout:", humantime::Duration::from(*abort_timeout));
     }
+    if let Some(retry_policy) = &modify_request.retry_policy {
+        table.add_kv_row("Retry policy:", format!("{retry_policy:?}"));
+    }
+    if let Some(dead_letter_queue) = &modify_request.dead_letter_queue {
+        table.add_kv_row("Dead letter queue:", dead_letter_queue);
+    }
+    if let Some(validate_input) = &modify_request.validate_input {
+        table.add_kv_row("Input validation:", validate_input);
+    }
+    if let Some(state_ttl) = &modify_request.state_ttl {
+        table.add_kv_row("State TTL:", humantime::Duration::from(*state_ttl));
+    }
     c_println!("{table}");
     confirm_or_exit("Are you sure you want to apply these changes?")?;
 
@@ -34,6 +34,11 @@ pub(super) const WORKFLOW_RETENTION: &str = indoc! {
     The retention period starts once the invocation completes (with either success or failure).
     After the retention period, the invocation response together with the workflow state and promises will be forgotten."
 };
+pub(super) const JOURNAL_RETENTION: &str = indoc! {
+    "The retention duration of the journal of completed invocations.
+    The retention period starts once the invocation completes (with either success or failure).
+    While the journal is retained, the invocation can be restarted from a given journal entry."
+};
 pub(super) const INACTIVITY_TIMEOUT: &str = indoc! {
     "This timer guards against stalled service/handler invocations. Once it expires,
     Restate triggers a graceful termination by asking the service invocation to
@@ -55,6 +60,21 @@ pub(super) const ABORT_TIMEOUT: &str = indoc! {
 
     This overrides the default abort timeout set in invoker options."
 };
+pub(super) const DEAD_LETTER_QUEUE: &str = indoc! {
+    "Whether the requests of terminally failed invocations are stored in the dead letter queue.
+    Dead letters can be inspected through the sys_dead_letter table,
+    and redriven or discarded through the admin API."
+};
+
+pub(super) const VALIDATE_INPUT: &str = indoc! {
+    "Whether the ingress validates the request bodies against the input JSON schemas of the handlers.
+    Handlers can override this through the ingress.validate-input handler metadata."
+};
+
+pub(super) const STATE_TTL: &str = indoc! {
+    "The time to live of the state entries of virtual objects and workflows, counted from their last write.
+    Expired entries are deleted in the background, while no invocation is running for their key."
+};
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_view")]
@@ -105,6 +125,18 @@ async fn view(env: &CliEnv, opts: &View) -> Result<()> {
         c_println!();
     }
 
+    let mut table = Table::new_styled();
+    table.add_kv_row(
+        "Journal retention:",
+        service
+            .journal_retention
+            .map(|d| d.to_string())
+            .unwrap_or("<DISABLED>".to_string()),
+    );
+    c_println!("{table}");
+    c_tip!("{}", JOURNAL_RETENTION);
+    c_println!();
+
     let mut table = Table::new_styled();
     table.add_kv_row(
         "Inactivity timeout:",
@@ -129,5 +161,31 @@ async fn view(env: &CliEnv, opts: &View) -> Result<()> {
     c_tip!("{}", ABORT_TIMEOUT);
     c_println!();
 
+    let mut table = Table::new_styled();
+    table.add_kv_row("Dead letter queue:", service.dead_letter_queue);
+    c_println!("{table}");
+    c_tip!("{}", DEAD_LETTER_QUEUE);
+    c_println!();
+
+    let mut table = Table::new_styled();
+    table.add_kv_row("Input validation:", service.validate_input);
+    c_println!("{table}");
+    c_tip!("{}", VALIDATE_INPUT);
+    c_println!();
+
+    if service.ty.has_state() {
+        let mut table = Table::new_styled();
+        table.add_kv_row(
+            "State TTL:",
+            service
+                .state_ttl
+                .map(|d| d.to_string())
+                .unwrap_or("<DISABLED>".to_string()),
+        );
+        c_println!("{table}");
+        c_tip!("{}", STATE_TTL);
+        c_println!();
+    }
+
     Ok(())
 }
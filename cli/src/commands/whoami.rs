@@ -54,6 +54,17 @@ pub async fn run(State(env): State<CliEnv>) {
         table.add_row(vec!["Authentication Token", "(set)"]);
     }
 
+    if env.config.admin_bearer_token.is_some() {
+        table.add_row(vec!["Admin Authentication Token", "(set)"]);
+    }
+
+    if let Some(admin_client_certificate) = &env.config.admin_client_certificate {
+        table.add_row(vec![
+            "Admin Client Certificate",
+            &admin_client_certificate.display().to_string(),
+        ]);
+    }
+
     c_println!("{}", table);
 
     c_println!();
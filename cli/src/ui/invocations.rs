@@ -120,6 +120,7 @@ pub fn invocation_status_style(status: InvocationState) -> Style {
         InvocationState::Ready => DStyle::new().blue(),
         InvocationState::Running => DStyle::new().green(),
         InvocationState::Suspended => DStyle::new().dim(),
+        InvocationState::Paused => DStyle::new().yellow(),
         InvocationState::BackingOff => DStyle::new().red(),
         InvocationState::Completed => DStyle::new().blue(),
     }
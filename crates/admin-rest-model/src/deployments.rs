@@ -10,6 +10,7 @@
 
 use http::Uri;
 use http::Version;
+use http::header::{HeaderName, HeaderValue};
 use restate_serde_util::SerdeableHeaderHashMap;
 use restate_types::identifiers::ServiceRevision;
 use restate_types::identifiers::{DeploymentId, LambdaARN};
@@ -18,8 +19,12 @@ use restate_types::schema::deployment::{DeploymentMetadata, ProtocolType};
 use restate_types::schema::service::ServiceMetadata;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::HashMap;
 use std::time::SystemTime;
 
+/// Value shown in place of the plain-text values in the deployment headers.
+const REDACTED_HEADER_VALUE: &str = "<redacted>";
+
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(from = "DeploymentShadow")]
@@ -156,6 +161,29 @@ mod tests {
             serialised
         );
     }
+
+    #[test]
+    fn redacts_plain_text_values() {
+        use super::*;
+        use http::header::{AUTHORIZATION, PROXY_AUTHORIZATION};
+
+        let headers: HashMap<HeaderName, HeaderValue> = HashMap::from([
+            (AUTHORIZATION, HeaderValue::from_static("Bearer s3cr3t")),
+            (
+                PROXY_AUTHORIZATION,
+                HeaderValue::from_static("Basic ${secret:proxy}"),
+            ),
+            (
+                HeaderName::from_static("x-tenant"),
+                HeaderValue::from_static("acme"),
+            ),
+        ]);
+
+        let redacted: HashMap<HeaderName, HeaderValue> = redact_headers(headers).into();
+        assert_eq!(redacted[AUTHORIZATION], REDACTED_HEADER_VALUE);
+        assert_eq!(redacted[PROXY_AUTHORIZATION], "Basic ${secret:proxy}");
+        assert_eq!(redacted["x-tenant"], REDACTED_HEADER_VALUE);
+    }
 }
 
 impl From<DeploymentMetadata> for Deployment {
@@ -169,7 +197,7 @@ impl From<DeploymentMetadata> for Deployment {
                 uri: address,
                 protocol_type,
                 http_version,
-                additional_headers: value.delivery_options.additional_headers.into(),
+                additional_headers: redact_headers(value.delivery_options.additional_headers),
                 created_at: SystemTime::from(value.created_at).into(),
                 min_protocol_version: *value.supported_protocol_versions.start(),
                 max_protocol_version: *value.supported_protocol_versions.end(),
@@ -180,7 +208,7 @@ impl From<DeploymentMetadata> for Deployment {
             } => Self::Lambda {
                 arn,
                 assume_role_arn: assume_role_arn.map(Into::into),
-                additional_headers: value.delivery_options.additional_headers.into(),
+                additional_headers: redact_headers(value.delivery_options.additional_headers),
                 created_at: SystemTime::from(value.created_at).into(),
                 min_protocol_version: *value.supported_protocol_versions.start(),
                 max_protocol_version: *value.supported_protocol_versions.end(),
@@ -189,6 +217,18 @@ impl From<DeploymentMetadata> for Deployment {
     }
 }
 
+/// Redacts the plain-text values of the deployment headers, as any header, not only the
+/// well-known ones such as `Authorization`, can carry credentials. Secret references, such as
+/// `Bearer ${secret:token}`, are shown as registered, as they don't contain the secret itself.
+fn redact_headers(mut headers: HashMap<HeaderName, HeaderValue>) -> SerdeableHeaderHashMap {
+    for value in headers.values_mut() {
+        if !value.as_bytes().windows(2).any(|w| w == b"${") {
+            *value = HeaderValue::from_static(REDACTED_HEADER_VALUE);
+        }
+    }
+    headers.into()
+}
+
 // This enum could be a struct with a nested enum to avoid repeating some fields, but serde(flatten) unfortunately breaks the openapi code generation
 #[serde_as]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -213,6 +253,9 @@ pub enum RegisterDeploymentRequest {
         /// # Additional headers
         ///
         /// Additional headers added to the discover/invoke requests to the deployment.
+        /// Values can reference secrets with `${secret:<name>}`, or environment variables with the
+        /// configured secrets prefix with `${env:<name>}`, which are resolved by the Restate
+        /// servers when sending the requests.
         ///
         additional_headers: Option<SerdeableHeaderHashMap>,
 
@@ -265,6 +308,9 @@ pub enum RegisterDeploymentRequest {
         /// # Additional headers
         ///
         /// Additional headers added to the discover/invoke requests to the deployment.
+        /// Values can reference secrets with `${secret:<name>}`, or environment variables with the
+        /// configured secrets prefix with `${env:<name>}`, which are resolved by the Restate
+        /// servers when sending the requests.
         ///
         additional_headers: Option<SerdeableHeaderHashMap>,
         /// # Force
@@ -720,6 +766,9 @@ pub enum UpdateDeploymentRequest {
         /// # Additional headers
         ///
         /// Additional headers added to the discover/invoke requests to the deployment.
+        /// Values can reference secrets with `${secret:<name>}`, or environment variables with the
+        /// configured secrets prefix with `${env:<name>}`, which are resolved by the Restate
+        /// servers when sending the requests.
         ///
         additional_headers: Option<SerdeableHeaderHashMap>,
 
@@ -754,6 +803,9 @@ pub enum UpdateDeploymentRequest {
         /// # Additional headers
         ///
         /// Additional headers added to the discover/invoke requests to the deployment.
+        /// Values can reference secrets with `${secret:<name>}`, or environment variables with the
+        /// configured secrets prefix with `${env:<name>}`, which are resolved by the Restate
+        /// servers when sending the requests.
         ///
         additional_headers: Option<SerdeableHeaderHashMap>,
 
@@ -0,0 +1,147 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use restate_types::identifiers::InvocationId;
+
+#[serde_as]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestartAsNewInvocationResponse {
+    /// # New invocation id
+    ///
+    /// Identifier of the invocation created by the restart.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub new_invocation_id: InvocationId,
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkInvocationOperation {
+    /// Gracefully cancel the matching invocations.
+    Cancel,
+    /// Kill the matching invocations.
+    Kill,
+    /// Purge the matching completed invocations.
+    Purge,
+}
+
+/// Status of an invocation, as reported by the `status` column of the `sys_invocation` table.
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::IntoStaticStr)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum InvocationStatusFilter {
+    Pending,
+    Scheduled,
+    Ready,
+    Running,
+    BackingOff,
+    Suspended,
+    Paused,
+    Completed,
+}
+
+/// Selects the invocations of a bulk operation. All the set conditions must match.
+#[serde_as]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkInvocationsFilter {
+    /// # Service
+    ///
+    /// Name of the target service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    /// # Handler
+    ///
+    /// Name of the target handler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+    /// # Status
+    ///
+    /// Statuses the invocations must be in. If empty, invocations in any status are selected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<InvocationStatusFilter>,
+    /// # Created after
+    ///
+    /// Only invocations created at or after this RFC 3339 timestamp.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub created_after: Option<humantime::Timestamp>,
+    /// # Created before
+    ///
+    /// Only invocations created before this RFC 3339 timestamp.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub created_before: Option<humantime::Timestamp>,
+    /// # Modified after
+    ///
+    /// Only invocations last modified at or after this RFC 3339 timestamp.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub modified_after: Option<humantime::Timestamp>,
+    /// # Modified before
+    ///
+    /// Only invocations last modified before this RFC 3339 timestamp.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub modified_before: Option<humantime::Timestamp>,
+    /// # Where clause
+    ///
+    /// SQL boolean expression over the columns of the `sys_invocation` table,
+    /// e.g. `target_service_name = 'Greeter' AND retry_count > 10`. Subqueries are not allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub where_clause: Option<String>,
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkInvocationsRequest {
+    /// # Filter
+    ///
+    /// Conditions selecting the invocations.
+    pub filter: BulkInvocationsFilter,
+    /// # Operation
+    ///
+    /// Operation to apply to the selected invocations.
+    /// Cancel and kill apply only to invocations that are not completed, purge only to completed invocations.
+    pub operation: BulkInvocationOperation,
+    /// # Dry run
+    ///
+    /// If true, only the number of matching invocations is returned, and no operation is applied.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Progress event of a bulk invocation operation.
+/// The response of a bulk invocation operation is a stream of newline delimited JSON events.
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkInvocationsEvent {
+    /// Number of invocations matching the filter. This is always the first event.
+    Matched { count: usize },
+    /// A batch of commands was submitted to the given partition.
+    Submitted { partition_id: u16, count: usize },
+    /// A batch of commands could not be submitted to the given partition.
+    Failed {
+        partition_id: u16,
+        count: usize,
+        reason: String,
+    },
+}
@@ -11,6 +11,8 @@
 pub mod converters;
 pub mod deployments;
 pub mod handlers;
+pub mod invocations;
+pub mod schedules;
 pub mod services;
 pub mod subscriptions;
 pub mod version;
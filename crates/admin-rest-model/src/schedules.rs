@@ -0,0 +1,76 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+
+use restate_types::identifiers::ScheduleId;
+use restate_types::schedule::{CatchUpPolicy, Schedule};
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateScheduleRequest {
+    /// # Cron expression
+    ///
+    /// Cron expression evaluated in UTC, either in the 5 fields format, e.g. `*/5 * * * *`,
+    /// or in the 6/7 fields format with leading seconds and optional trailing year.
+    pub cron: String,
+    /// # Service
+    ///
+    /// Name of the service to invoke.
+    pub service: String,
+    /// # Handler
+    ///
+    /// Name of the handler to invoke.
+    pub handler: String,
+    /// # Key
+    ///
+    /// Key of the virtual object to invoke. Required when invoking a virtual object.
+    pub key: Option<String>,
+    /// # Payload
+    ///
+    /// JSON payload passed to every run. If not provided, the runs have an empty body.
+    pub payload: Option<serde_json::Value>,
+    /// # Catch up
+    ///
+    /// What to do with the runs missed while the schedule could not fire. Defaults to `run-once`.
+    #[serde(default)]
+    pub catch_up: Option<CatchUpPolicy>,
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScheduleResponse {
+    pub id: ScheduleId,
+    pub cron: String,
+    pub service: String,
+    pub handler: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub catch_up: CatchUpPolicy,
+}
+
+impl From<Schedule> for ScheduleResponse {
+    fn from(value: Schedule) -> Self {
+        Self {
+            id: value.id,
+            cron: value.cron.to_string(),
+            service: value.invocation_target.service_name().to_string(),
+            handler: value.invocation_target.handler_name().to_string(),
+            key: value.invocation_target.key().map(|key| key.to_string()),
+            catch_up: value.catch_up,
+        }
+    }
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListSchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
@@ -13,7 +13,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
-use restate_types::schema::service::ServiceMetadata;
+use restate_types::retries::RetryPolicy;
+use restate_types::schema::service::{ServiceCorsPolicy, ServiceMetadata};
 
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +56,19 @@ pub struct ModifyServiceRequest {
     #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
     pub workflow_completion_retention: Option<Duration>,
 
+    /// # Journal retention
+    ///
+    /// Modify the retention of the journal of completed invocations for this service.
+    /// A retained journal can be used to restart a completed invocation from a given entry.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format or the ISO8601.
+    #[serde(
+        default,
+        with = "serde_with::As::<Option<restate_serde_util::DurationString>>"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub journal_retention: Option<Duration>,
+
     /// # Inactivity timeout
     ///
     /// This timer guards against stalled service/handler invocations. Once it expires,
@@ -93,6 +107,52 @@ pub struct ModifyServiceRequest {
     )]
     #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
     pub abort_timeout: Option<Duration>,
+
+    /// # Retry policy
+    ///
+    /// Retry policy to use for the invocations of this service.
+    /// Handlers declaring their own retry policy in the endpoint manifest keep using it.
+    ///
+    /// This overrides the default retry policy set in invoker options.
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+
+    /// # Dead letter queue
+    ///
+    /// If true, the requests of invocations of this service that terminally fail are stored in the dead letter queue,
+    /// from where they can be redriven or discarded.
+    /// Killed and canceled invocations are not stored, nor are the requests sent to a dead letter handler by the retry policy.
+    #[serde(default)]
+    pub dead_letter_queue: Option<bool>,
+
+    /// # CORS policy
+    ///
+    /// Overrides of the ingress CORS policy for this service. Fields which are not set fall back to the
+    /// CORS policy configured in the ingress options. An empty policy removes the overrides.
+    #[serde(default)]
+    pub cors: Option<ServiceCorsPolicy>,
+
+    /// # Input validation
+    ///
+    /// If true, the ingress validates the request bodies against the input JSON schemas of the handlers,
+    /// and rejects the invalid ones with `400 Bad Request` before creating the invocation.
+    /// Handlers can override this through the `ingress.validate-input` handler metadata.
+    #[serde(default)]
+    pub validate_input: Option<bool>,
+
+    /// # State TTL
+    ///
+    /// Modify the time to live of the state entries of this virtual object or workflow, counted from their last write.
+    /// Expired entries are deleted in the background. A zero TTL removes it.
+    /// Entries written before the TTL was set don't expire until they're written again.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format or the ISO8601.
+    #[serde(
+        default,
+        with = "serde_with::As::<Option<restate_serde_util::DurationString>>"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub state_ttl: Option<Duration>,
 }
 
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -26,6 +26,9 @@ pub struct CreateSubscriptionRequest {
     /// Source uri. Accepted forms:
     ///
     /// * `kafka://<cluster_name>/<topic_name>`, e.g. `kafka://my-cluster/my-topic`
+    /// * `file://localhost/<path>`, e.g. `file://localhost/var/events`, tailing an NDJSON file or a directory of NDJSON files
+    /// * `webhook://<webhook_name>`, e.g. `webhook://github`
+    /// * `service://<service_name>/<handler_name>`, e.g. `service://Counter/count`, the output of the completed invocations of the handler, to publish to a Kafka sink
     #[serde_as(as = "serde_with::DisplayFromStr")]
     #[cfg_attr(feature = "schema", schemars(with = "String"))]
     pub source: Uri,
@@ -34,6 +37,9 @@ pub struct CreateSubscriptionRequest {
     /// Sink uri. Accepted forms:
     ///
     /// * `service://<service_name>/<service_name>`, e.g. `service://Counter/count`
+    /// * `kafka://<cluster_name>/<topic_name>`, e.g. `kafka://my-cluster/my-topic`, only for a service source.
+    ///   The `key_template` option sets the template of the record key, supporting the placeholders
+    ///   `{invocation_id}`, `{service}`, `{handler}` and `{key}`.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     #[cfg_attr(feature = "schema", schemars(with = "String"))]
     pub sink: Uri,
@@ -41,6 +47,12 @@ pub struct CreateSubscriptionRequest {
     ///
     /// Additional options to apply to the subscription.
     pub options: Option<HashMap<String, String>>,
+    /// # Dead letter queue
+    ///
+    /// If true, the events whose invocation terminally fails are stored in the dead letter queue,
+    /// from where they can be redriven or discarded.
+    #[serde(default)]
+    pub dead_letter_queue: Option<bool>,
 }
 
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -50,6 +62,8 @@ pub struct SubscriptionResponse {
     pub source: String,
     pub sink: String,
     pub options: HashMap<String, String>,
+    #[serde(default)]
+    pub dead_letter_queue: bool,
 }
 
 impl From<Subscription> for SubscriptionResponse {
@@ -59,6 +73,7 @@ impl From<Subscription> for SubscriptionResponse {
             source: value.source().to_string(),
             sink: value.sink().to_string(),
             options: value.metadata().clone(),
+            dead_letter_queue: value.dead_letter_queue(),
         }
     }
 }
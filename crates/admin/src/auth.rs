@@ -0,0 +1,323 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Authentication and role-based authorization of the admin API requests.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use http::{Method, StatusCode};
+use restate_core::network::net_util::PeerCertificateSubject;
+use restate_types::config::{AdminAuthOptions, AdminRole};
+use tracing::debug;
+
+/// Principal authenticated by the admin API, available in the request extensions.
+#[derive(Debug, Clone)]
+pub(crate) struct AdminPrincipal {
+    pub(crate) name: String,
+    pub(crate) role: AdminRole,
+}
+
+/// Authenticates the requests using the configured bearer tokens and client certificates, then
+/// checks the role of the principal against the one required by the requested route.
+#[derive(Debug)]
+pub(crate) struct AdminAuth {
+    options: AdminAuthOptions,
+}
+
+impl AdminAuth {
+    pub(crate) fn new(options: AdminAuthOptions) -> Self {
+        Self { options }
+    }
+
+    /// Wraps the routes of the router with the auth middleware. Must be applied before the router
+    /// is nested under the API version prefixes, as the route groups are matched on the
+    /// unversioned paths. Routes merged afterward, like the web UI assets, are not authenticated.
+    pub(crate) fn layer(self: Arc<Self>, router: axum::Router) -> axum::Router {
+        router.layer(axum::middleware::from_fn(
+            move |mut request: Request, next: Next| {
+                let auth = Arc::clone(&self);
+                async move {
+                    match auth.authorize(&request) {
+                        Ok(principal) => {
+                            if let Some(principal) = principal {
+                                request.extensions_mut().insert(principal);
+                            }
+                            next.run(request).await
+                        }
+                        Err(error) => error.into_response(),
+                    }
+                }
+            },
+        ))
+    }
+
+    fn authorize(&self, request: &Request) -> Result<Option<AdminPrincipal>, AuthError> {
+        let Some(required_role) = required_role(request.method(), request.uri().path()) else {
+            return Ok(None);
+        };
+
+        let principal = self.authenticate(request)?;
+        if principal.role < required_role {
+            debug!(
+                "Rejecting admin request {} {} of '{}': role {:?} required, has {:?}",
+                request.method(),
+                request.uri().path(),
+                principal.name,
+                required_role,
+                principal.role
+            );
+            return Err(AuthError::Forbidden {
+                principal: principal.name,
+                required_role,
+            });
+        }
+
+        Ok(Some(principal))
+    }
+
+    fn authenticate(&self, request: &Request) -> Result<AdminPrincipal, AuthError> {
+        if let Some(authorization) = request.headers().get(AUTHORIZATION) {
+            let token = authorization
+                .to_str()
+                .ok()
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .map(str::trim)
+                .ok_or(AuthError::Unauthorized("malformed authorization header"))?;
+
+            return self
+                .options
+                .tokens
+                .iter()
+                .find(|options| constant_time_eq(options.token.as_bytes(), token.as_bytes()))
+                .map(|options| AdminPrincipal {
+                    name: options.principal.clone(),
+                    role: options.role,
+                })
+                .ok_or(AuthError::Unauthorized("unknown token"));
+        }
+
+        if let Some(PeerCertificateSubject(subject)) = request.extensions().get() {
+            return self
+                .options
+                .client_certificates
+                .iter()
+                .find(|options| &options.subject == subject)
+                .map(|options| AdminPrincipal {
+                    name: options.subject.clone(),
+                    role: options.role,
+                })
+                .ok_or(AuthError::Unauthorized("unknown client certificate"));
+        }
+
+        Err(AuthError::Unauthorized("missing credentials"))
+    }
+}
+
+/// Role required by the route groups of the admin API, or `None` for the unauthenticated routes,
+/// which are the health check and the request identity public keys.
+///
+/// * `viewer`: every read-only request, except the SQL query API.
+/// * `operator`: managing invocations and dead letters, and the SQL query API which exposes the
+///   state and the journals of the invocations.
+/// * `admin`: everything else, such as deployments, service changes, state, subscriptions and
+///   the raw metadata store.
+fn required_role(method: &Method, path: &str) -> Option<AdminRole> {
+    let group = path
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or_default();
+
+    Some(match (method, group) {
+        (_, "health" | "keys") => return None,
+        (_, "metadata") => AdminRole::Admin,
+        (&Method::GET | &Method::HEAD, _) => AdminRole::Viewer,
+        (_, "query" | "invocations" | "dead-letters") => AdminRole::Operator,
+        _ => AdminRole::Admin,
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+enum AuthError {
+    #[error("Unauthorized: {0}")]
+    Unauthorized(&'static str),
+    #[error(
+        "The principal '{principal}' is not allowed to perform this operation, role {required_role:?} is required"
+    )]
+    Forbidden {
+        principal: String,
+        required_role: AdminRole,
+    },
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "message": self.to_string() }));
+        match self {
+            AuthError::Unauthorized(_) => (
+                StatusCode::UNAUTHORIZED,
+                [(WWW_AUTHENTICATE, "Bearer")],
+                body,
+            )
+                .into_response(),
+            AuthError::Forbidden { .. } => (StatusCode::FORBIDDEN, body).into_response(),
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use restate_types::config::{AdminClientCertificateOptions, AdminTokenOptions};
+
+    fn auth() -> AdminAuth {
+        AdminAuth::new(AdminAuthOptions {
+            tokens: vec![
+                AdminTokenOptions {
+                    principal: "dashboard".to_owned(),
+                    token: "viewer-token".to_owned(),
+                    role: AdminRole::Viewer,
+                },
+                AdminTokenOptions {
+                    principal: "on-call".to_owned(),
+                    token: "operator-token".to_owned(),
+                    role: AdminRole::Operator,
+                },
+            ],
+            client_certificates: vec![AdminClientCertificateOptions {
+                subject: "CN=deployer,O=Example".to_owned(),
+                role: AdminRole::Admin,
+            }],
+        })
+    }
+
+    fn request(method: Method, path: &str, token: Option<&str>) -> Request {
+        let mut builder = Request::builder().method(method).uri(path);
+        if let Some(token) = token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        builder.body(axum::body::Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn route_groups() {
+        assert_eq!(required_role(&Method::GET, "/health"), None);
+        assert_eq!(required_role(&Method::GET, "/keys"), None);
+        assert_eq!(
+            required_role(&Method::GET, "/deployments"),
+            Some(AdminRole::Viewer)
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/query"),
+            Some(AdminRole::Operator)
+        );
+        assert_eq!(
+            required_role(&Method::PATCH, "/invocations/inv_1/pause"),
+            Some(AdminRole::Operator)
+        );
+        assert_eq!(
+            required_role(&Method::DELETE, "/dead-letters/inv_1"),
+            Some(AdminRole::Operator)
+        );
+        assert_eq!(
+            required_role(&Method::DELETE, "/deployments/dp_1"),
+            Some(AdminRole::Admin)
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/services/Greeter/state"),
+            Some(AdminRole::Admin)
+        );
+        assert_eq!(
+            required_role(&Method::GET, "/metadata/nodes_config"),
+            Some(AdminRole::Admin)
+        );
+    }
+
+    #[test]
+    fn bearer_tokens() {
+        let auth = auth();
+
+        let principal = auth
+            .authorize(&request(Method::GET, "/services", Some("viewer-token")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(principal.name, "dashboard");
+
+        assert!(matches!(
+            auth.authorize(&request(
+                Method::DELETE,
+                "/invocations/inv_1",
+                Some("viewer-token")
+            )),
+            Err(AuthError::Forbidden { .. })
+        ));
+        assert!(
+            auth.authorize(&request(
+                Method::DELETE,
+                "/invocations/inv_1",
+                Some("operator-token")
+            ))
+            .is_ok()
+        );
+        assert!(matches!(
+            auth.authorize(&request(
+                Method::POST,
+                "/deployments",
+                Some("operator-token")
+            )),
+            Err(AuthError::Forbidden { .. })
+        ));
+
+        assert!(matches!(
+            auth.authorize(&request(Method::GET, "/services", Some("wrong"))),
+            Err(AuthError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            auth.authorize(&request(Method::GET, "/services", None)),
+            Err(AuthError::Unauthorized(_))
+        ));
+        assert!(
+            auth.authorize(&request(Method::GET, "/health", None))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn client_certificates() {
+        let auth = auth();
+
+        let mut req = request(Method::POST, "/deployments", None);
+        req.extensions_mut()
+            .insert(PeerCertificateSubject("CN=deployer,O=Example".to_owned()));
+        assert_eq!(
+            auth.authorize(&req).unwrap().unwrap().role,
+            AdminRole::Admin
+        );
+
+        let mut req = request(Method::GET, "/services", None);
+        req.extensions_mut()
+            .insert(PeerCertificateSubject("CN=someone-else".to_owned()));
+        assert!(matches!(
+            auth.authorize(&req),
+            Err(AuthError::Unauthorized(_))
+        ));
+    }
+}
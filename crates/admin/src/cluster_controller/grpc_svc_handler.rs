@@ -21,22 +21,27 @@ use restate_bifrost::loglet::FindTailOptions;
 use restate_bifrost::{Bifrost, Error as BiforstError};
 use restate_core::protobuf::cluster_ctrl_svc::{
     ClusterStateRequest, ClusterStateResponse, CreatePartitionSnapshotRequest,
-    CreatePartitionSnapshotResponse, DescribeLogRequest, DescribeLogResponse, FindTailRequest,
-    FindTailResponse, GetClusterConfigurationRequest, GetClusterConfigurationResponse,
-    ListLogsRequest, ListLogsResponse, QueryRequest, QueryResponse, SealAndExtendChainRequest,
+    CreatePartitionSnapshotResponse, DeletePartitionSnapshotRequest, DescribeLogRequest,
+    DescribeLogResponse, DrainNodeRequest, DrainNodeResponse, FindTailRequest, FindTailResponse,
+    GetClusterConfigurationRequest, GetClusterConfigurationResponse, ListLogsRequest,
+    ListLogsResponse, ListPartitionSnapshotsRequest, ListPartitionSnapshotsResponse,
+    MergePartitionsRequest, MergePartitionsResponse, MoveLeaderRequest, PartitionSnapshot,
+    PinPartitionRequest, QueryRequest, QueryResponse, SealAndExtendChainRequest,
     SealAndExtendChainResponse, SealedSegment, SetClusterConfigurationRequest,
-    SetClusterConfigurationResponse, TailState, TrimLogRequest,
+    SetClusterConfigurationResponse, SplitPartitionRequest, SplitPartitionResponse, TailState,
+    TrimLogRequest, UndrainNodeRequest,
     cluster_ctrl_svc_server::{ClusterCtrlSvc, ClusterCtrlSvcServer},
 };
 use restate_core::{Metadata, MetadataWriter};
 use restate_storage_query_datafusion::context::QueryContext;
-use restate_types::identifiers::PartitionId;
+use restate_types::identifiers::{PartitionId, SnapshotId};
 use restate_types::logs::metadata::SegmentIndex;
 use restate_types::logs::{LogId, Lsn, SequenceNumber};
 use restate_types::metadata_store::keys::NODES_CONFIG_KEY;
 use restate_types::net::partition_processor_manager::Snapshot;
 use restate_types::nodes_config::NodesConfiguration;
 use restate_types::protobuf::cluster::ClusterConfiguration;
+use restate_types::replication::NodeSet;
 use restate_types::storage::{StorageCodec, StorageEncode};
 use restate_types::{PlainNodeId, Version, Versioned};
 
@@ -208,6 +213,209 @@ impl ClusterCtrlSvc for ClusterCtrlSvcHandler {
         }
     }
 
+    async fn list_partition_snapshots(
+        &self,
+        request: Request<ListPartitionSnapshotsRequest>,
+    ) -> Result<Response<ListPartitionSnapshotsResponse>, Status> {
+        let request = request.into_inner();
+        let partition_id = PartitionId::from(
+            u16::try_from(request.partition_id)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+
+        let snapshots = self
+            .controller_handle
+            .list_partition_snapshots(partition_id)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed listing partition snapshots: {err}");
+                Status::internal(err.to_string())
+            })?;
+
+        Ok(Response::new(ListPartitionSnapshotsResponse {
+            snapshots: snapshots
+                .into_iter()
+                .map(|snapshot| PartitionSnapshot {
+                    snapshot_id: snapshot.snapshot_id.to_string(),
+                    min_applied_lsn: snapshot.min_applied_lsn.as_u64(),
+                    created_at_millis: snapshot.created_at.as_u64(),
+                    node_name: snapshot.node_name,
+                    latest: snapshot.latest,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn delete_partition_snapshot(
+        &self,
+        request: Request<DeletePartitionSnapshotRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+        let partition_id = PartitionId::from(
+            u16::try_from(request.partition_id)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+        let snapshot_id: SnapshotId = request.snapshot_id.parse().map_err(|err| {
+            Status::invalid_argument(format!(
+                "Invalid snapshot id {}: {err}",
+                request.snapshot_id
+            ))
+        })?;
+
+        self.controller_handle
+            .delete_partition_snapshot(partition_id, snapshot_id)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed deleting partition snapshot: {err}");
+                Status::failed_precondition(err.to_string())
+            })?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn split_partition(
+        &self,
+        request: Request<SplitPartitionRequest>,
+    ) -> Result<Response<SplitPartitionResponse>, Status> {
+        let request = request.into_inner();
+        let partition_id = PartitionId::from(
+            u16::try_from(request.partition_id)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+
+        let partition_ids = self
+            .controller_handle
+            .split_partition(partition_id, request.split_key)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed splitting partition {partition_id}: {err}");
+                Status::internal(err.to_string())
+            })?;
+
+        Ok(Response::new(SplitPartitionResponse {
+            partition_ids: partition_ids.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn merge_partitions(
+        &self,
+        request: Request<MergePartitionsRequest>,
+    ) -> Result<Response<MergePartitionsResponse>, Status> {
+        let request = request.into_inner();
+        let left = PartitionId::from(
+            u16::try_from(request.left)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+        let right = PartitionId::from(
+            u16::try_from(request.right)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+
+        let partition_id = self
+            .controller_handle
+            .merge_partitions(left, right)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed merging partitions {left} and {right}: {err}");
+                Status::internal(err.to_string())
+            })?
+            .into_iter()
+            .next()
+            .expect("merge creates a partition");
+
+        Ok(Response::new(MergePartitionsResponse {
+            partition_id: partition_id.into(),
+        }))
+    }
+
+    async fn move_leader(
+        &self,
+        request: Request<MoveLeaderRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+        let partition_id = PartitionId::from(
+            u16::try_from(request.partition_id)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+        let node_id = PlainNodeId::from(request.node_id);
+
+        self.controller_handle
+            .move_leader(partition_id, node_id)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed moving leader of partition {partition_id} to {node_id}: {err}");
+                Status::failed_precondition(err.to_string())
+            })?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn pin_partition(
+        &self,
+        request: Request<PinPartitionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+        let partition_id = PartitionId::from(
+            u16::try_from(request.partition_id)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+
+        self.controller_handle
+            .pin_partition(partition_id, NodeSet::from(request.node_ids))
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed pinning partition {partition_id}: {err}");
+                Status::failed_precondition(err.to_string())
+            })?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn drain_node(
+        &self,
+        request: Request<DrainNodeRequest>,
+    ) -> Result<Response<DrainNodeResponse>, Status> {
+        let node_id = PlainNodeId::from(request.into_inner().node_id);
+
+        let partition_ids = self
+            .controller_handle
+            .drain_node(node_id)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed draining node {node_id}: {err}");
+                Status::failed_precondition(err.to_string())
+            })?;
+
+        Ok(Response::new(DrainNodeResponse {
+            partition_ids: partition_ids.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn undrain_node(
+        &self,
+        request: Request<UndrainNodeRequest>,
+    ) -> Result<Response<()>, Status> {
+        let node_id = PlainNodeId::from(request.into_inner().node_id);
+
+        self.controller_handle
+            .undrain_node(node_id)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed undraining node {node_id}: {err}");
+                Status::failed_precondition(err.to_string())
+            })?;
+
+        Ok(Response::new(()))
+    }
+
     async fn seal_and_extend_chain(
         &self,
         request: Request<SealAndExtendChainRequest>,
@@ -932,6 +932,11 @@ impl LogsControllerInner {
         logs: &Logs,
     ) -> Result<(), LogsControllerError> {
         for (log_id, chain) in logs.iter() {
+            if chain.sealed_tail_lsn().is_some() {
+                // permanently sealed logs of split or merged partitions are never reconfigured
+                continue;
+            }
+
             let tail = chain.tail();
 
             if let Some(seal_lsn) = tail.tail_lsn {
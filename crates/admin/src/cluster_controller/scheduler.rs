@@ -8,11 +8,12 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use rand::seq::IteratorRandom;
 use tracing::{Level, debug, enabled, info, instrument, trace, warn};
 
@@ -30,8 +31,9 @@ use restate_types::net::partition_processor_manager::{
 };
 use restate_types::nodes_config::NodesConfiguration;
 use restate_types::partition_table::{
-    Partition, PartitionPlacement, PartitionReplication, PartitionTable,
+    Partition, PartitionPlacement, PartitionReplication, PartitionTable, PlacementConstraints,
 };
+use restate_types::replication::NodeSet;
 use restate_types::{NodeId, PlainNodeId, Version};
 
 use crate::cluster_controller::logs_controller;
@@ -125,7 +127,10 @@ impl<T: TransportConnect> Scheduler<T> {
         let logs = Metadata::with_current(|m| m.logs_ref());
         let partition_table = Metadata::with_current(|m| m.partition_table_ref());
 
-        if logs.num_logs() != partition_table.num_partitions() as usize {
+        if partition_table
+            .partitions()
+            .any(|(_, partition)| logs.chain(&partition.log_id()).is_none())
+        {
             // either the partition table or the logs are not fully initialized
             // hence there is nothing we can do atm.
             // we need to wait until both partitions and logs are created
@@ -137,19 +142,55 @@ impl<T: TransportConnect> Scheduler<T> {
         //  the latest built always available as a field
         let mut builder = partition_table.clone().into_builder();
         let partition_replication = builder.partition_replication().clone();
+        let placement_hints = ConstrainedPlacementHints {
+            partition_table: &partition_table,
+            alive_workers,
+            placement_hints,
+        };
 
         builder.for_each(|partition_id, placement| {
+            let placement_constraints = &partition_table
+                .get_partition(partition_id)
+                .expect("partition exists")
+                .placement_constraints;
+            let candidate_workers = if placement_constraints.pinned_nodes.is_empty() {
+                Cow::Borrowed(alive_workers)
+            } else {
+                Cow::Owned(
+                    alive_workers
+                        .iter()
+                        .copied()
+                        .filter(|node_id| placement_constraints.allows(*node_id))
+                        .collect(),
+                )
+            };
+
             let mut target_state = TargetPartitionPlacementState::new(placement);
+            // the leader chosen by the operator, or the one taking over from a drained node, joins
+            // the node set even if it is fully replicated, ensure_replication then removes one of
+            // the other nodes
+            if let Some(preferred_leader) = placement_hints
+                .leader_override(partition_id)
+                .filter(|node_id| candidate_workers.contains(node_id))
+            {
+                target_state.node_set.insert(preferred_leader);
+            }
+
             self.ensure_replication(
                 partition_id,
                 &mut target_state,
-                alive_workers,
+                &candidate_workers,
                 &partition_replication,
                 nodes_config,
                 &placement_hints,
             );
 
-            self.ensure_leadership(partition_id, &mut target_state, &placement_hints);
+            self.ensure_leadership(
+                partition_id,
+                &mut target_state,
+                &placement_hints,
+                partition_table.drained_nodes(),
+            );
         });
 
         if let Some(partition_table) = builder.build_if_modified() {
@@ -346,11 +387,13 @@ impl<T: TransportConnect> Scheduler<T> {
         partition_id: &PartitionId,
         target_state: &mut TargetPartitionPlacementState,
         placement_hints: &H,
+        drained_nodes: &NodeSet,
     ) {
         let preferred_leader = placement_hints.preferred_leader(partition_id);
 
         if target_state.leader.is_none() {
-            target_state.leader = self.select_leader_from(target_state, preferred_leader);
+            target_state.leader =
+                self.select_leader_from(target_state, preferred_leader, drained_nodes);
         } else if preferred_leader
             .is_some_and(|preferred_leader| target_state.node_set.contains(preferred_leader))
         {
@@ -362,22 +405,36 @@ impl<T: TransportConnect> Scheduler<T> {
         &self,
         leader_candidates: &TargetPartitionPlacementState,
         preferred_leader: Option<PlainNodeId>,
+        drained_nodes: &NodeSet,
     ) -> Option<PlainNodeId> {
         // todo: Implement leader balancing between nodes
         preferred_leader
             .filter(|leader| leader_candidates.contains(*leader))
             .or_else(|| {
                 let mut rng = rand::rng();
-                leader_candidates.node_set.iter().choose(&mut rng).cloned()
+                // drained nodes only lead if no other node can
+                leader_candidates
+                    .node_set
+                    .iter()
+                    .filter(|node_id| !drained_nodes.contains(**node_id))
+                    .choose(&mut rng)
+                    .or_else(|| leader_candidates.node_set.iter().choose(&mut rng))
+                    .cloned()
             })
     }
 
     fn instruct_nodes(&self, observed_cluster_state: &ObservedClusterState) -> Result<(), Error> {
         let partition_table = Metadata::with_current(|m| m.partition_table_ref());
+        let logs = Metadata::with_current(|m| m.logs_ref());
 
         let mut commands = BTreeMap::default();
 
         for (partition_id, partition) in partition_table.partitions() {
+            if logs.chain(&partition.log_id()).is_none() {
+                // the log of a newly split or merged partition is not provisioned yet
+                continue;
+            }
+
             self.generate_instructions_for_partition(
                 partition_id,
                 partition,
@@ -386,6 +443,23 @@ impl<T: TransportConnect> Scheduler<T> {
             );
         }
 
+        // stop the processors of partitions which have been split or merged
+        for (partition_id, state) in &observed_cluster_state.partitions {
+            if partition_table.contains_partition(partition_id) {
+                continue;
+            }
+
+            for node_id in state.partition_processors.keys() {
+                commands
+                    .entry(*node_id)
+                    .or_default()
+                    .push(ControlProcessor {
+                        partition_id: *partition_id,
+                        command: ProcessorCommand::Stop,
+                    });
+            }
+        }
+
         let (cur_partition_table_version, cur_logs_version) =
             Metadata::with_current(|m| (m.partition_table_version(), m.logs_version()));
         for (node_id, commands) in commands.into_iter() {
@@ -480,6 +554,87 @@ impl<T: TransportConnect> Scheduler<T> {
     }
 }
 
+/// Placement hints which give precedence to the [`PlacementConstraints`] of the partitions and to
+/// the drained nodes over the wrapped hints.
+struct ConstrainedPlacementHints<'a, H> {
+    partition_table: &'a PartitionTable,
+    alive_workers: &'a HashSet<PlainNodeId>,
+    placement_hints: H,
+}
+
+impl<H> ConstrainedPlacementHints<'_, H> {
+    fn placement_constraints(&self, partition_id: &PartitionId) -> Option<&PlacementConstraints> {
+        self.partition_table
+            .get_partition(partition_id)
+            .map(|partition| &partition.placement_constraints)
+    }
+
+    /// Whether the node is not drained and allowed to run the partition.
+    fn can_lead(&self, partition_id: &PartitionId, node_id: PlainNodeId) -> bool {
+        !self.partition_table.drained_nodes().contains(node_id)
+            && self
+                .placement_constraints(partition_id)
+                .is_none_or(|constraints| constraints.allows(node_id))
+    }
+
+    /// Leader which is placed regardless of the wrapped hints: the alive preferred leader chosen
+    /// by the operator or, if the current leader is drained, the node taking over its leadership.
+    fn leader_override(&self, partition_id: &PartitionId) -> Option<PlainNodeId> {
+        let partition = self.partition_table.get_partition(partition_id)?;
+        let can_take_over = |node_id: &PlainNodeId| {
+            self.alive_workers.contains(node_id) && self.can_lead(partition_id, *node_id)
+        };
+
+        if let Some(preferred_leader) = partition
+            .placement_constraints
+            .preferred_leader
+            .filter(can_take_over)
+        {
+            return Some(preferred_leader);
+        }
+
+        let leader = partition.placement.leader()?;
+        if !self.partition_table.drained_nodes().contains(leader) {
+            return None;
+        }
+
+        // prefer the followers of the partition, which are already caught up
+        partition
+            .placement
+            .iter()
+            .copied()
+            .find(can_take_over)
+            .or_else(|| {
+                self.alive_workers
+                    .iter()
+                    .copied()
+                    .filter(can_take_over)
+                    .min()
+            })
+    }
+}
+
+impl<H: PartitionProcessorPlacementHints> PartitionProcessorPlacementHints
+    for ConstrainedPlacementHints<'_, H>
+{
+    fn preferred_nodes(&self, partition_id: &PartitionId) -> impl Iterator<Item = &PlainNodeId> {
+        match self.placement_constraints(partition_id) {
+            Some(constraints) if !constraints.pinned_nodes.is_empty() => {
+                Either::Left(constraints.pinned_nodes.iter())
+            }
+            _ => Either::Right(self.placement_hints.preferred_nodes(partition_id)),
+        }
+    }
+
+    fn preferred_leader(&self, partition_id: &PartitionId) -> Option<PlainNodeId> {
+        self.leader_override(partition_id).or_else(|| {
+            self.placement_hints
+                .preferred_leader(partition_id)
+                .filter(|node_id| self.can_lead(partition_id, *node_id))
+        })
+    }
+}
+
 /// Placement hints for the [`logs_controller::LogsController`] based on the current
 /// [`SchedulingPlan`].
 pub struct PartitionTableNodeSetSelectorHints {
@@ -571,8 +726,8 @@ mod tests {
     };
     use restate_types::partition_table::{
         PartitionPlacement, PartitionReplication, PartitionTable, PartitionTableBuilder,
+        PlacementConstraints,
     };
-    use restate_types::replication::NodeSet;
     use restate_types::replication::ReplicationProperty;
     use restate_types::time::MillisSinceEpoch;
     use restate_types::{GenerationalNodeId, PlainNodeId, Version};
@@ -580,7 +735,8 @@ mod tests {
     use crate::cluster_controller::logs_controller::tests::MockNodes;
     use crate::cluster_controller::observed_cluster_state::ObservedClusterState;
     use crate::cluster_controller::scheduler::{
-        PartitionProcessorPlacementHints, Scheduler, TargetPartitionPlacementState,
+        ConstrainedPlacementHints, PartitionProcessorPlacementHints, Scheduler,
+        TargetPartitionPlacementState,
     };
 
     struct NoPlacementHints;
@@ -1074,4 +1230,118 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn placement_constraints_take_precedence_over_hints() -> googletest::Result<()> {
+        struct PreferNodeZero;
+
+        impl PartitionProcessorPlacementHints for PreferNodeZero {
+            fn preferred_nodes(
+                &self,
+                _partition_id: &PartitionId,
+            ) -> impl Iterator<Item = &PlainNodeId> {
+                iter::empty()
+            }
+
+            fn preferred_leader(&self, _partition_id: &PartitionId) -> Option<PlainNodeId> {
+                Some(PlainNodeId::from(0))
+            }
+        }
+
+        let mut builder =
+            PartitionTable::with_equally_sized_partitions(Version::MIN, 3).into_builder();
+        builder.set_placement_constraints(
+            PartitionId::from(1),
+            PlacementConstraints {
+                preferred_leader: Some(PlainNodeId::from(2)),
+                pinned_nodes: NodeSet::default(),
+            },
+        )?;
+        builder.set_placement_constraints(
+            PartitionId::from(2),
+            PlacementConstraints {
+                preferred_leader: None,
+                pinned_nodes: [1, 2].into(),
+            },
+        )?;
+        let partition_table = builder.build();
+
+        let alive_workers = HashSet::from_iter([0, 1, 2].map(PlainNodeId::from));
+        let placement_hints = ConstrainedPlacementHints {
+            partition_table: &partition_table,
+            alive_workers: &alive_workers,
+            placement_hints: PreferNodeZero,
+        };
+
+        assert_eq!(
+            placement_hints.preferred_leader(&PartitionId::from(0)),
+            Some(PlainNodeId::from(0))
+        );
+        assert_eq!(
+            placement_hints.preferred_leader(&PartitionId::from(1)),
+            Some(PlainNodeId::from(2))
+        );
+        // the hinted leader is not among the pinned nodes
+        assert_eq!(
+            placement_hints.preferred_leader(&PartitionId::from(2)),
+            None
+        );
+        assert_eq!(
+            placement_hints
+                .preferred_nodes(&PartitionId::from(2))
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![PlainNodeId::from(1), PlainNodeId::from(2)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn drained_nodes_lose_leadership() {
+        struct PreferNodeOne;
+
+        impl PartitionProcessorPlacementHints for PreferNodeOne {
+            fn preferred_nodes(
+                &self,
+                _partition_id: &PartitionId,
+            ) -> impl Iterator<Item = &PlainNodeId> {
+                iter::empty()
+            }
+
+            fn preferred_leader(&self, _partition_id: &PartitionId) -> Option<PlainNodeId> {
+                Some(PlainNodeId::from(1))
+            }
+        }
+
+        let mut builder =
+            PartitionTable::with_equally_sized_partitions(Version::MIN, 2).into_builder();
+        builder.for_each(|partition_id, placement| {
+            *placement = if *partition_id == PartitionId::from(0) {
+                PartitionPlacement::from_iter([PlainNodeId::from(1), PlainNodeId::from(2)])
+            } else {
+                PartitionPlacement::from_iter([PlainNodeId::from(2)])
+            };
+        });
+        builder.set_drained(PlainNodeId::from(1), true);
+        let partition_table = builder.build();
+
+        let alive_workers = HashSet::from_iter([1, 2, 3].map(PlainNodeId::from));
+        let placement_hints = ConstrainedPlacementHints {
+            partition_table: &partition_table,
+            alive_workers: &alive_workers,
+            placement_hints: PreferNodeOne,
+        };
+
+        // the follower takes over the leadership of the drained node
+        assert_eq!(
+            placement_hints.preferred_leader(&PartitionId::from(0)),
+            Some(PlainNodeId::from(2))
+        );
+        // the drained node is not hinted as leader
+        assert_eq!(
+            placement_hints.preferred_leader(&PartitionId::from(1)),
+            None
+        );
+    }
 }
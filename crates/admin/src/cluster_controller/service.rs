@@ -13,7 +13,7 @@ mod state;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{Context, anyhow};
+use anyhow::{Context, anyhow, bail};
 use codederror::CodedError;
 use futures::never::Never;
 use tokio::sync::{mpsc, oneshot};
@@ -33,27 +33,30 @@ use restate_core::{
 };
 use restate_storage_query_datafusion::BuildError;
 use restate_storage_query_datafusion::context::{ClusterTables, QueryContext};
-use restate_types::cluster::cluster_state::ClusterState;
+use restate_types::cluster::cluster_state::{ClusterState, ReplayStatus};
 use restate_types::config::{AdminOptions, Configuration};
 use restate_types::health::HealthStatus;
-use restate_types::identifiers::PartitionId;
+use restate_types::identifiers::{PartitionId, PartitionKey, SnapshotId};
 use restate_types::live::Live;
 use restate_types::logs::metadata::{
     LogletParams, Logs, LogsConfiguration, ProviderConfiguration, ProviderKind,
     ReplicatedLogletConfig, SegmentIndex,
 };
-use restate_types::logs::{LogId, LogletId, Lsn};
-use restate_types::net::partition_processor_manager::{CreateSnapshotRequest, Snapshot};
+use restate_types::logs::{LogId, LogletId, Lsn, SequenceNumber};
+use restate_types::net::partition_processor_manager::{
+    CreateSnapshotRequest, DeleteSnapshotRequest, ListSnapshotsRequest, Snapshot, SnapshotInfo,
+};
 use restate_types::partition_table::{
-    self, PartitionReplication, PartitionTable, PartitionTableBuilder,
+    self, PartitionReplication, PartitionTable, PartitionTableBuilder, PlacementConstraints,
 };
 use restate_types::protobuf::common::AdminStatus;
 use restate_types::replicated_loglet::ReplicatedLogletParams;
 use restate_types::replication::{NodeSet, ReplicationProperty};
-use restate_types::{GenerationalNodeId, NodeId, Version};
+use restate_types::retries::RetryPolicy;
+use restate_types::{GenerationalNodeId, NodeId, PlainNodeId, Version};
 
 use self::state::ClusterControllerState;
-use super::cluster_state_refresher::ClusterStateRefresher;
+use super::cluster_state_refresher::{ClusterStateRefresher, ClusterStateWatcher};
 use super::grpc_svc_handler::ClusterCtrlSvcHandler;
 use crate::cluster_controller::logs_controller::{self, NodeSetSelectorHints};
 use crate::cluster_controller::observed_cluster_state::ObservedClusterState;
@@ -187,6 +190,37 @@ enum ClusterControllerCommand {
         extension: Option<ChainExtension>,
         response_tx: oneshot::Sender<anyhow::Result<SealedSegment>>,
     },
+    Repartition {
+        repartitioning: Repartitioning,
+        response_tx: oneshot::Sender<anyhow::Result<Vec<PartitionId>>>,
+    },
+    MoveLeader {
+        partition_id: PartitionId,
+        node_id: PlainNodeId,
+        response_tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    PinPartition {
+        partition_id: PartitionId,
+        nodes: NodeSet,
+        response_tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    DrainNode {
+        node_id: PlainNodeId,
+        response_tx: oneshot::Sender<anyhow::Result<Vec<PartitionId>>>,
+    },
+    UndrainNode {
+        node_id: PlainNodeId,
+        response_tx: oneshot::Sender<anyhow::Result<()>>,
+    },
+    ListSnapshots {
+        partition_id: PartitionId,
+        response_tx: oneshot::Sender<anyhow::Result<Vec<SnapshotInfo>>>,
+    },
+    DeleteSnapshot {
+        partition_id: PartitionId,
+        snapshot_id: SnapshotId,
+        response_tx: oneshot::Sender<anyhow::Result<()>>,
+    },
 }
 
 pub struct ClusterControllerHandle {
@@ -299,6 +333,166 @@ impl ClusterControllerHandle {
 
         response_rx.await.map_err(|_| ShutdownError)
     }
+
+    /// Splits the partition in two at `split_key`, or in half if no split key is given.
+    /// Returns the ids of the new partitions.
+    pub async fn split_partition(
+        &self,
+        partition_id: PartitionId,
+        split_key: Option<PartitionKey>,
+    ) -> Result<anyhow::Result<Vec<PartitionId>>, ShutdownError> {
+        self.repartition(Repartitioning::Split {
+            partition_id,
+            split_key,
+        })
+        .await
+    }
+
+    /// Merges two adjacent partitions. Returns the id of the new partition.
+    pub async fn merge_partitions(
+        &self,
+        left: PartitionId,
+        right: PartitionId,
+    ) -> Result<anyhow::Result<Vec<PartitionId>>, ShutdownError> {
+        self.repartition(Repartitioning::Merge { left, right })
+            .await
+    }
+
+    async fn repartition(
+        &self,
+        repartitioning: Repartitioning,
+    ) -> Result<anyhow::Result<Vec<PartitionId>>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::Repartition {
+                repartitioning,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
+
+    /// Makes the given node the preferred leader of the partition.
+    pub async fn move_leader(
+        &self,
+        partition_id: PartitionId,
+        node_id: PlainNodeId,
+    ) -> Result<anyhow::Result<()>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::MoveLeader {
+                partition_id,
+                node_id,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
+
+    /// Restricts the partition processors of the partition to the given nodes. An empty node set
+    /// removes all placement constraints of the partition.
+    pub async fn pin_partition(
+        &self,
+        partition_id: PartitionId,
+        nodes: NodeSet,
+    ) -> Result<anyhow::Result<()>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::PinPartition {
+                partition_id,
+                nodes,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
+
+    /// Marks the node as drained, which moves the leadership of all partitions led by the node to
+    /// other nodes until the node is undrained. Returns the ids of the partitions whose leadership
+    /// is being moved.
+    pub async fn drain_node(
+        &self,
+        node_id: PlainNodeId,
+    ) -> Result<anyhow::Result<Vec<PartitionId>>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::DrainNode {
+                node_id,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
+
+    /// Lets the drained node lead partitions again.
+    pub async fn undrain_node(
+        &self,
+        node_id: PlainNodeId,
+    ) -> Result<anyhow::Result<()>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::UndrainNode {
+                node_id,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
+
+    /// Lists the snapshots of the given partition stored in the snapshot repository, ordered from
+    /// the newest to the oldest.
+    pub async fn list_partition_snapshots(
+        &self,
+        partition_id: PartitionId,
+    ) -> Result<anyhow::Result<Vec<SnapshotInfo>>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::ListSnapshots {
+                partition_id,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
+
+    /// Deletes a snapshot of the given partition from the snapshot repository. The latest
+    /// snapshot of a partition cannot be deleted.
+    pub async fn delete_partition_snapshot(
+        &self,
+        partition_id: PartitionId,
+        snapshot_id: SnapshotId,
+    ) -> Result<anyhow::Result<()>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::DeleteSnapshot {
+                partition_id,
+                snapshot_id,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
 }
 
 impl<T: TransportConnect> Service<T> {
@@ -438,6 +632,88 @@ impl<T: TransportConnect> Service<T> {
         };
     }
 
+    /// Lists the snapshots of the given partition by issuing an RPC to a node hosting the
+    /// partition.
+    fn list_partition_snapshots(
+        &self,
+        partition_id: PartitionId,
+        response_tx: oneshot::Sender<anyhow::Result<Vec<SnapshotInfo>>>,
+    ) {
+        let node_id = match self.snapshot_repository_node(partition_id) {
+            Ok(node_id) => node_id,
+            Err(err) => {
+                let _ = response_tx.send(Err(err));
+                return;
+            }
+        };
+
+        let node_rpc_client = self.processor_manager_client.clone();
+        let _ = TaskCenter::spawn_child(
+            TaskKind::Disposable,
+            "list-snapshots-response",
+            async move {
+                let _ =
+                    response_tx.send(node_rpc_client.list_snapshots(node_id, partition_id).await);
+                Ok(())
+            },
+        );
+    }
+
+    /// Deletes a snapshot of the given partition by issuing an RPC to a node hosting the
+    /// partition.
+    fn delete_partition_snapshot(
+        &self,
+        partition_id: PartitionId,
+        snapshot_id: SnapshotId,
+        response_tx: oneshot::Sender<anyhow::Result<()>>,
+    ) {
+        let node_id = match self.snapshot_repository_node(partition_id) {
+            Ok(node_id) => node_id,
+            Err(err) => {
+                let _ = response_tx.send(Err(err));
+                return;
+            }
+        };
+
+        let node_rpc_client = self.processor_manager_client.clone();
+        let _ = TaskCenter::spawn_child(
+            TaskKind::Disposable,
+            "delete-snapshot-response",
+            async move {
+                let _ = response_tx.send(
+                    node_rpc_client
+                        .delete_snapshot(node_id, partition_id, snapshot_id)
+                        .await,
+                );
+                Ok(())
+            },
+        );
+    }
+
+    /// Picks an alive node running a processor for the given partition to access the snapshot
+    /// repository through. The effective leader is preferred.
+    fn snapshot_repository_node(
+        &self,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<GenerationalNodeId> {
+        let cluster_state = self.cluster_state_refresher.get_cluster_state();
+
+        cluster_state
+            .alive_nodes()
+            .filter_map(|node| {
+                node.partitions
+                    .get(&partition_id)
+                    .map(|status| (node.generational_node_id, status.is_effective_leader()))
+            })
+            .max_by_key(|(_, is_leader)| *is_leader)
+            .map(|(node_id, _)| node_id)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Can not find a node hosting partition {partition_id} to access its snapshots"
+                )
+            })
+    }
+
     async fn update_cluster_configuration(
         &self,
         partition_replication: Option<ReplicationProperty>,
@@ -551,6 +827,44 @@ impl<T: TransportConnect> Service<T> {
         });
     }
 
+    fn repartition(
+        &self,
+        repartitioning: Repartitioning,
+        response_tx: oneshot::Sender<anyhow::Result<Vec<PartitionId>>>,
+    ) {
+        let task = RepartitionTask {
+            repartitioning,
+            bifrost: self.bifrost.clone(),
+            metadata_writer: self.metadata_writer.clone(),
+            cluster_state_watcher: self.cluster_state_refresher.cluster_state_watcher(),
+            processor_manager_client: self.processor_manager_client.clone(),
+        };
+
+        _ = TaskCenter::spawn(TaskKind::Disposable, "repartition", async move {
+            let result = task.run().await;
+            _ = response_tx.send(result);
+            Ok(())
+        });
+    }
+
+    fn update_placement<R: Send + 'static, F>(
+        &self,
+        name: &'static str,
+        response_tx: oneshot::Sender<anyhow::Result<R>>,
+        update: impl FnOnce(PlacementUpdater) -> F,
+    ) where
+        F: Future<Output = anyhow::Result<R>> + Send + 'static,
+    {
+        let update = update(PlacementUpdater {
+            metadata_writer: self.metadata_writer.clone(),
+        });
+
+        _ = TaskCenter::spawn(TaskKind::Disposable, name, async move {
+            _ = response_tx.send(update.await);
+            Ok(())
+        });
+    }
+
     async fn on_cluster_cmd(&self, command: ClusterControllerCommand) {
         match command {
             ClusterControllerCommand::GetClusterState(tx) => {
@@ -608,6 +922,66 @@ impl<T: TransportConnect> Service<T> {
                 extension,
                 response_tx,
             } => self.seal_and_extend_chain(log_id, min_version, extension, response_tx),
+            ClusterControllerCommand::Repartition {
+                repartitioning,
+                response_tx,
+            } => {
+                info!(?repartitioning, "Repartition command received");
+                self.repartition(repartitioning, response_tx)
+            }
+            ClusterControllerCommand::MoveLeader {
+                partition_id,
+                node_id,
+                response_tx,
+            } => {
+                info!(%partition_id, %node_id, "Move leader command received");
+                self.update_placement("move-leader", response_tx, |updater| {
+                    updater.move_leader(partition_id, node_id)
+                })
+            }
+            ClusterControllerCommand::PinPartition {
+                partition_id,
+                nodes,
+                response_tx,
+            } => {
+                info!(%partition_id, %nodes, "Pin partition command received");
+                self.update_placement("pin-partition", response_tx, |updater| {
+                    updater.pin_partition(partition_id, nodes)
+                })
+            }
+            ClusterControllerCommand::DrainNode {
+                node_id,
+                response_tx,
+            } => {
+                info!(%node_id, "Drain node command received");
+                self.update_placement("drain-node", response_tx, |updater| {
+                    updater.drain_node(node_id)
+                })
+            }
+            ClusterControllerCommand::UndrainNode {
+                node_id,
+                response_tx,
+            } => {
+                info!(%node_id, "Undrain node command received");
+                self.update_placement("undrain-node", response_tx, |updater| {
+                    updater.undrain_node(node_id)
+                })
+            }
+            ClusterControllerCommand::ListSnapshots {
+                partition_id,
+                response_tx,
+            } => {
+                debug!(%partition_id, "List snapshots command received");
+                self.list_partition_snapshots(partition_id, response_tx);
+            }
+            ClusterControllerCommand::DeleteSnapshot {
+                partition_id,
+                snapshot_id,
+                response_tx,
+            } => {
+                info!(%partition_id, %snapshot_id, "Delete snapshot command received");
+                self.delete_partition_snapshot(partition_id, snapshot_id, response_tx);
+            }
         }
     }
 }
@@ -622,10 +996,168 @@ enum ClusterConfigurationUpdateError {
     BuildError(#[from] partition_table::BuilderError),
     #[error("missing partition table; cluster seems to be not provisioned")]
     MissingPartitionTable,
-    #[error("changing the number of partitions is not yet supported by Restate")]
+    #[error(
+        "changing the number of partitions is not supported, split or merge partitions instead"
+    )]
     Repartitioning,
 }
 
+/// Updates the placement constraints and the drained nodes of the partition table. The updates
+/// are retried on concurrent modifications of the partition table, so they run outside of the
+/// command loop of the cluster controller.
+struct PlacementUpdater {
+    metadata_writer: MetadataWriter,
+}
+
+impl PlacementUpdater {
+    async fn move_leader(
+        self,
+        partition_id: PartitionId,
+        node_id: PlainNodeId,
+    ) -> anyhow::Result<()> {
+        if !Metadata::with_current(|m| m.nodes_config_ref()).has_worker_role(&node_id) {
+            return Err(PlacementUpdateError::NotAWorker(node_id).into());
+        }
+
+        self.update_partition_table(|partition_table, builder| {
+            let mut constraints = placement_constraints(partition_table, partition_id)?;
+            if !constraints.allows(node_id) {
+                return Err(PlacementUpdateError::NotPinned {
+                    partition_id,
+                    node_id,
+                });
+            }
+
+            constraints.preferred_leader = Some(node_id);
+            builder.set_placement_constraints(partition_id, constraints)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn pin_partition(self, partition_id: PartitionId, nodes: NodeSet) -> anyhow::Result<()> {
+        let not_a_worker = {
+            let nodes_config = Metadata::with_current(|m| m.nodes_config_ref());
+            nodes
+                .iter()
+                .find(|node_id| !nodes_config.has_worker_role(node_id))
+                .copied()
+        };
+        if let Some(node_id) = not_a_worker {
+            return Err(PlacementUpdateError::NotAWorker(node_id).into());
+        }
+
+        self.update_partition_table(|partition_table, builder| {
+            let mut constraints = placement_constraints(partition_table, partition_id)?;
+            if nodes.is_empty() {
+                constraints = PlacementConstraints::default();
+            } else {
+                constraints.pinned_nodes = nodes.clone();
+                if constraints
+                    .preferred_leader
+                    .is_some_and(|node_id| !constraints.allows(node_id))
+                {
+                    constraints.preferred_leader = None;
+                }
+            }
+
+            builder.set_placement_constraints(partition_id, constraints)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Marks the node as drained, so that the scheduler hands the leadership of its partitions
+    /// over to other nodes. Returns the ids of the partitions currently led by the node.
+    async fn drain_node(self, node_id: PlainNodeId) -> anyhow::Result<Vec<PartitionId>> {
+        if !Metadata::with_current(|m| m.nodes_config_ref()).has_worker_role(&node_id) {
+            return Err(PlacementUpdateError::NotAWorker(node_id).into());
+        }
+
+        let mut led_partitions = Vec::new();
+        self.update_partition_table(|partition_table, builder| {
+            led_partitions = partition_table
+                .partitions()
+                .filter(|(_, partition)| partition.placement.leader() == Some(node_id))
+                .map(|(partition_id, _)| *partition_id)
+                .collect();
+
+            builder.set_drained(node_id, true);
+            Ok(())
+        })
+        .await?;
+
+        Ok(led_partitions)
+    }
+
+    /// Lets the node lead partitions again. The preferred leaders of the partitions are kept
+    /// while the node is drained, so the node gets back the leadership of those.
+    async fn undrain_node(self, node_id: PlainNodeId) -> anyhow::Result<()> {
+        self.update_partition_table(|_, builder| {
+            builder.set_drained(node_id, false);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn update_partition_table(
+        &self,
+        mut update: impl FnMut(
+            &PartitionTable,
+            &mut PartitionTableBuilder,
+        ) -> Result<(), PlacementUpdateError>,
+    ) -> anyhow::Result<()> {
+        let result = self
+            .metadata_writer
+            .global_metadata()
+            .read_modify_write(|current: Option<Arc<PartitionTable>>| {
+                let partition_table = current.ok_or(PlacementUpdateError::MissingPartitionTable)?;
+
+                let mut builder = partition_table.as_ref().clone().into_builder();
+                update(&partition_table, &mut builder)?;
+
+                builder
+                    .build_if_modified()
+                    .ok_or(PlacementUpdateError::Unchanged)
+            })
+            .await;
+
+        match result {
+            Ok(_) | Err(ReadModifyWriteError::FailedOperation(PlacementUpdateError::Unchanged)) => {
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum PlacementUpdateError {
+    #[error("unchanged")]
+    Unchanged,
+    #[error(transparent)]
+    BuildError(#[from] partition_table::BuilderError),
+    #[error("missing partition table; cluster seems to be not provisioned")]
+    MissingPartitionTable,
+    #[error("node {0} is not a worker node")]
+    NotAWorker(PlainNodeId),
+    #[error("partition {partition_id} is not pinned to node {node_id}")]
+    NotPinned {
+        partition_id: PartitionId,
+        node_id: PlainNodeId,
+    },
+}
+
+fn placement_constraints(
+    partition_table: &PartitionTable,
+    partition_id: PartitionId,
+) -> Result<PlacementConstraints, PlacementUpdateError> {
+    partition_table
+        .get_partition(&partition_id)
+        .map(|partition| partition.placement_constraints.clone())
+        .ok_or(partition_table::BuilderError::UnknownPartition(partition_id).into())
+}
+
 #[derive(Clone)]
 struct PartitionProcessorManagerClient<N> {
     network_sender: N,
@@ -660,6 +1192,46 @@ where
             .result
             .map_err(|e| anyhow!("Failed to create snapshot: {:?}", e))
     }
+
+    pub async fn list_snapshots(
+        &self,
+        node_id: GenerationalNodeId,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<Vec<SnapshotInfo>> {
+        self.network_sender
+            .call_rpc(
+                node_id,
+                Swimlane::default(),
+                ListSnapshotsRequest { partition_id },
+                Some(partition_id.into()),
+                None,
+            )
+            .await?
+            .result
+            .map_err(|e| anyhow!("Failed to list snapshots: {:?}", e))
+    }
+
+    pub async fn delete_snapshot(
+        &self,
+        node_id: GenerationalNodeId,
+        partition_id: PartitionId,
+        snapshot_id: SnapshotId,
+    ) -> anyhow::Result<()> {
+        self.network_sender
+            .call_rpc(
+                node_id,
+                Swimlane::default(),
+                DeleteSnapshotRequest {
+                    partition_id,
+                    snapshot_id,
+                },
+                Some(partition_id.into()),
+                None,
+            )
+            .await?
+            .result
+            .map_err(|e| anyhow!("Failed to delete snapshot: {:?}", e))
+    }
 }
 
 struct SealAndExtendTask {
@@ -787,6 +1359,224 @@ impl SealAndExtendTask {
         Ok((provider, params))
     }
 }
+
+#[derive(Debug, Clone, Copy)]
+enum Repartitioning {
+    Split {
+        partition_id: PartitionId,
+        split_key: Option<PartitionKey>,
+    },
+    Merge {
+        left: PartitionId,
+        right: PartitionId,
+    },
+}
+
+impl Repartitioning {
+    fn parents(&self) -> Vec<PartitionId> {
+        match self {
+            Repartitioning::Split { partition_id, .. } => vec![*partition_id],
+            Repartitioning::Merge { left, right } => vec![*left, *right],
+        }
+    }
+
+    fn apply(
+        &self,
+        builder: &mut PartitionTableBuilder,
+    ) -> Result<Vec<PartitionId>, partition_table::BuilderError> {
+        match *self {
+            Repartitioning::Split {
+                partition_id,
+                split_key,
+            } => {
+                let (left, right) = builder.split_partition(partition_id, split_key)?;
+                Ok(vec![left, right])
+            }
+            Repartitioning::Merge { left, right } => {
+                Ok(vec![builder.merge_partitions(left, right)?])
+            }
+        }
+    }
+}
+
+/// Replaces partitions by splitting or merging them.
+///
+/// The logs of the parent partitions are permanently sealed first, so that their state can no
+/// longer change. Once a snapshot covering the whole log of every parent has been archived, the
+/// new partitions are published in the partition table. Their processors bootstrap their
+/// partition store from the snapshots of the parents, and start with a new log.
+///
+/// Requests which are in flight while the logs are sealed fail, and have to be retried by the
+/// clients. If the final snapshots cannot be created, the logs of the parents are reopened and
+/// the parents keep serving their key range. If the partition table cannot be updated, running
+/// the task again resumes the operation, as sealing an already sealed log is a no-op.
+struct RepartitionTask<N> {
+    repartitioning: Repartitioning,
+    bifrost: Bifrost,
+    metadata_writer: MetadataWriter,
+    cluster_state_watcher: ClusterStateWatcher,
+    processor_manager_client: PartitionProcessorManagerClient<N>,
+}
+
+impl<N> RepartitionTask<N>
+where
+    N: NetworkSender + 'static,
+{
+    async fn run(self) -> anyhow::Result<Vec<PartitionId>> {
+        // validate the operation upfront to not seal any log in vain
+        let partition_table = Metadata::with_current(|m| m.partition_table_snapshot());
+        self.repartitioning
+            .apply(&mut partition_table.as_ref().clone().into_builder())?;
+        self.check_parents_can_be_snapshotted()?;
+
+        let mut sealed_logs = Vec::new();
+        if let Err(err) = self
+            .seal_and_snapshot_parents(&partition_table, &mut sealed_logs)
+            .await
+        {
+            // nothing has been published yet, the parents can keep serving their key range
+            for (partition_id, log_id) in sealed_logs {
+                match self.bifrost.admin().reopen_chain(log_id).await {
+                    Ok(()) => info!(%partition_id, %log_id, "Reopened the log of the partition"),
+                    Err(reopen_err) => warn!(
+                        %partition_id,
+                        %log_id,
+                        %reopen_err,
+                        "Failed to reopen the log of the partition, run the repartitioning again \
+                        to complete it"
+                    ),
+                }
+            }
+            return Err(err.context("repartitioning aborted"));
+        }
+
+        let mut new_partitions = Vec::new();
+        self.metadata_writer
+            .global_metadata()
+            .read_modify_write(|current: Option<Arc<PartitionTable>>| {
+                let partition_table =
+                    current.ok_or(ClusterConfigurationUpdateError::MissingPartitionTable)?;
+
+                let mut builder = partition_table.as_ref().clone().into_builder();
+                new_partitions = self.repartitioning.apply(&mut builder)?;
+                Ok::<_, ClusterConfigurationUpdateError>(builder.build())
+            })
+            .await?;
+
+        info!(parents = ?self.repartitioning.parents(), ?new_partitions, "Repartitioning completed");
+        Ok(new_partitions)
+    }
+
+    /// Fails if the final snapshots of the parents could not be created, so that no log is sealed
+    /// in vain: snapshots require a repository, and a processor which has caught up with the log.
+    fn check_parents_can_be_snapshotted(&self) -> anyhow::Result<()> {
+        if Configuration::pinned()
+            .worker
+            .snapshots
+            .destination
+            .is_none()
+        {
+            bail!(
+                "splitting or merging partitions requires a snapshot repository, configure \
+                'worker.snapshots.destination'"
+            );
+        }
+
+        let cluster_state = self.cluster_state_watcher.current();
+        for partition_id in self.repartitioning.parents() {
+            let has_active_processor = cluster_state.alive_nodes().any(|node| {
+                node.partitions
+                    .get(&partition_id)
+                    .is_some_and(|status| status.replay_status == ReplayStatus::Active)
+            });
+            if !has_active_processor {
+                bail!("no alive node runs a caught up processor of partition {partition_id}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Seals the logs of the parents and archives their final snapshots. The sealed logs are
+    /// added to `sealed_logs`, to reopen them on failure.
+    async fn seal_and_snapshot_parents(
+        &self,
+        partition_table: &PartitionTable,
+        sealed_logs: &mut Vec<(PartitionId, LogId)>,
+    ) -> anyhow::Result<()> {
+        for partition_id in self.repartitioning.parents() {
+            let log_id = partition_table
+                .get_partition(&partition_id)
+                .map(|partition| partition.log_id())
+                .expect("partition exists");
+            let tail_lsn = self.bifrost.admin().seal_chain(log_id).await?;
+            sealed_logs.push((partition_id, log_id));
+            info!(%partition_id, %log_id, %tail_lsn, "Sealed the log of the partition");
+
+            let snapshot = self.create_snapshot(partition_id, tail_lsn.prev()).await?;
+            info!(
+                %partition_id,
+                snapshot_id = %snapshot.snapshot_id,
+                min_applied_lsn = %snapshot.min_applied_lsn,
+                "Archived the final snapshot of the partition"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Creates a snapshot of the partition on any node which has applied the log up to
+    /// `min_target_lsn`, retrying until the processors have caught up with the sealed log.
+    async fn create_snapshot(
+        &self,
+        partition_id: PartitionId,
+        min_target_lsn: Lsn,
+    ) -> anyhow::Result<Snapshot> {
+        let mut retry_iter = RetryPolicy::exponential(
+            Duration::from_millis(100),
+            2.0,
+            Some(20),
+            Some(Duration::from_secs(5)),
+        )
+        .into_iter();
+
+        loop {
+            let cluster_state = self.cluster_state_watcher.current();
+            let node_id = cluster_state
+                .alive_nodes()
+                .find(|node| {
+                    node.partitions
+                        .get(&partition_id)
+                        .and_then(|status| status.last_applied_log_lsn)
+                        .is_some_and(|applied_lsn| applied_lsn >= min_target_lsn)
+                })
+                .map(|node| node.generational_node_id);
+
+            let result = match node_id {
+                Some(node_id) => {
+                    self.processor_manager_client
+                        .create_snapshot(node_id, partition_id, Some(min_target_lsn))
+                        .await
+                }
+                None => Err(anyhow!(
+                    "no processor of partition {partition_id} has applied its log up to {min_target_lsn}"
+                )),
+            };
+
+            match result {
+                Ok(snapshot) => return Ok(snapshot),
+                Err(err) => {
+                    let Some(delay) = retry_iter.next() else {
+                        return Err(err);
+                    };
+                    debug!(%partition_id, %err, "Failed to create snapshot, retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Service;
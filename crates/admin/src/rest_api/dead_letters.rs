@@ -0,0 +1,97 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::error::*;
+use super::invocations::append_invocation_command;
+
+use crate::state::AdminServiceState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use okapi_operation::*;
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::{DiscardDeadLetterRequest, RedriveDeadLetterRequest};
+use restate_wal_protocol::Command;
+
+/// Redrive a dead letter
+#[openapi(
+    summary = "Redrive a dead letter",
+    description = "Submit again the request of the given dead-lettered invocation as a new invocation, \
+    and remove it from the dead letter queue. Dead letters can be listed querying the sys_dead_letter table.",
+    operation_id = "redrive_dead_letter",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Identifier of the dead-lettered invocation.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn redrive_dead_letter<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::RedriveDeadLetter(RedriveDeadLetterRequest { invocation_id }),
+        "dead letter redrive",
+    )
+    .await
+}
+
+/// Discard a dead letter
+#[openapi(
+    summary = "Discard a dead letter",
+    description = "Remove the given dead-lettered invocation from the dead letter queue, without submitting it again.",
+    operation_id = "discard_dead_letter",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Identifier of the dead-lettered invocation.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn discard_dead_letter<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::DiscardDeadLetter(DiscardDeadLetterRequest { invocation_id }),
+        "dead letter discard",
+    )
+    .await
+}
@@ -18,7 +18,7 @@ use okapi_operation::okapi::map;
 use okapi_operation::okapi::openapi3::Responses;
 use okapi_operation::{Components, ToMediaTypes, ToResponses, okapi};
 use restate_core::ShutdownError;
-use restate_types::identifiers::{DeploymentId, SubscriptionId};
+use restate_types::identifiers::{DeploymentId, InvocationId, ScheduleId, SubscriptionId};
 use restate_types::invocation::ServiceType;
 use schemars::JsonSchema;
 use serde::Serialize;
@@ -40,6 +40,12 @@ pub enum MetaApiError {
     },
     #[error("The requested subscription '{0}' does not exist")]
     SubscriptionNotFound(SubscriptionId),
+    #[error("The requested schedule '{0}' does not exist")]
+    ScheduleNotFound(ScheduleId),
+    #[error("The requested invocation '{0}' does not exist")]
+    InvocationNotFound(InvocationId),
+    #[error("Cannot {0} the invocation '{1}': {2}")]
+    InvalidInvocationStatus(&'static str, InvocationId, &'static str),
     #[error("Cannot {0} for service type {1}")]
     UnsupportedOperation(&'static str, ServiceType),
     #[error(transparent)]
@@ -68,7 +74,10 @@ impl IntoResponse for MetaApiError {
             MetaApiError::ServiceNotFound(_)
             | MetaApiError::HandlerNotFound { .. }
             | MetaApiError::DeploymentNotFound(_)
-            | MetaApiError::SubscriptionNotFound(_) => StatusCode::NOT_FOUND,
+            | MetaApiError::SubscriptionNotFound(_)
+            | MetaApiError::ScheduleNotFound(_)
+            | MetaApiError::InvocationNotFound(_) => StatusCode::NOT_FOUND,
+            MetaApiError::InvalidInvocationStatus(_, _, _) => StatusCode::CONFLICT,
             MetaApiError::InvalidField(_, _) | MetaApiError::UnsupportedOperation(_, _) => {
                 StatusCode::BAD_REQUEST
             }
@@ -13,11 +13,19 @@ use std::sync::Arc;
 
 use crate::rest_api::create_envelope_header;
 use crate::state::AdminServiceState;
+use axum::Json;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use datafusion::arrow::array::{Array, LargeStringArray, UInt32Array};
+use futures::TryStreamExt;
 use okapi_operation::*;
-use restate_types::identifiers::{InvocationId, WithPartitionKey};
-use restate_types::invocation::{InvocationTermination, PurgeInvocationRequest};
+use restate_admin_rest_model::invocations::RestartAsNewInvocationResponse;
+use restate_storage_query_datafusion::context::QueryContext;
+use restate_types::identifiers::{EntryIndex, InvocationId, InvocationUuid, WithPartitionKey};
+use restate_types::invocation::{
+    InvocationTermination, PauseInvocationRequest, PurgeInvocationRequest,
+    RestartAsNewInvocationRequest, ResumeInvocationRequest,
+};
 use restate_wal_protocol::{Command, Envelope};
 use serde::Deserialize;
 use tracing::warn;
@@ -109,3 +117,251 @@ pub async fn delete_invocation<V>(
         Ok(StatusCode::ACCEPTED)
     }
 }
+
+/// Pause an invocation
+#[openapi(
+    summary = "Pause an invocation",
+    description = "Pause the given invocation. A paused invocation is not executed by the invoker, \
+    and retains its journal, state locks and completions until it's resumed, cancelled or killed. \
+    This is useful to stop an invocation from retrying while a downstream dependency is unavailable.",
+    operation_id = "pause_invocation",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Invocation identifier.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn pause_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::PauseInvocation(PauseInvocationRequest { invocation_id }),
+        "pause",
+    )
+    .await
+}
+
+/// Resume a paused invocation
+#[openapi(
+    summary = "Resume an invocation",
+    description = "Resume the given paused invocation. The invocation will be dispatched again to the invoker, \
+    replaying its journal on the latest pinned deployment.",
+    operation_id = "resume_invocation",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Invocation identifier.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn resume_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::ResumeInvocation(ResumeInvocationRequest { invocation_id }),
+        "resume",
+    )
+    .await
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RestartAsNewInvocationParams {
+    pub from_entry: EntryIndex,
+}
+
+/// Restart a completed invocation as a new invocation
+#[openapi(
+    summary = "Restart as new invocation",
+    description = "Restart the given completed invocation as a new invocation, on the latest deployment. \
+    The journal entries of the completed invocation up to `from_entry` (included) are copied to the new invocation, \
+    so they won't be executed again. This requires the journal of the completed invocation to be retained.",
+    operation_id = "restart_as_new_invocation",
+    tags = "invocation",
+    parameters(
+        path(
+            name = "invocation_id",
+            description = "Invocation identifier.",
+            schema = "std::string::String"
+        ),
+        query(
+            name = "from_entry",
+            description = "Index of the last journal entry to copy to the new invocation. \
+            Use 0 to copy only the input entry.",
+            required = true,
+            style = "simple",
+            allow_empty_value = false,
+            schema = "EntryIndex",
+        )
+    ),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "Json<RestartAsNewInvocationResponse>",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn restart_as_new_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+    Query(RestartAsNewInvocationParams { from_entry }): Query<RestartAsNewInvocationParams>,
+) -> Result<(StatusCode, Json<RestartAsNewInvocationResponse>), MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    // The new invocation must live in the same partition, as the journal is copied by the partition processor.
+    let new_invocation_id = InvocationId::from_parts(
+        invocation_id.partition_key(),
+        InvocationUuid::generate_random(),
+    );
+
+    if let Some(query_context) = &state.query_context {
+        validate_restart_as_new(query_context, invocation_id, from_entry).await?;
+    }
+
+    let status_code = append_invocation_command(
+        &state,
+        invocation_id,
+        Command::RestartAsNewInvocation(RestartAsNewInvocationRequest {
+            invocation_id,
+            new_invocation_id,
+            copy_prefix_up_to_index_included: from_entry,
+        }),
+        "restart as new",
+    )
+    .await?;
+
+    Ok((
+        status_code,
+        Json(RestartAsNewInvocationResponse { new_invocation_id }),
+    ))
+}
+
+/// Checks that the invocation is completed, and that its retained journal contains `from_entry`.
+///
+/// The partition processor checks again the journal prefix when applying the command, this
+/// check only rejects the requests which are known to be invalid upfront.
+async fn validate_restart_as_new(
+    query_context: &QueryContext,
+    invocation_id: InvocationId,
+    from_entry: EntryIndex,
+) -> Result<(), MetaApiError> {
+    let record_batches: Vec<_> = query_context
+        .execute(&format!(
+            "SELECT status, journal_size FROM sys_invocation_status WHERE id = '{invocation_id}'"
+        ))
+        .await
+        .map_err(|err| MetaApiError::Internal(err.to_string()))?
+        .try_collect()
+        .await
+        .map_err(|err| MetaApiError::Internal(err.to_string()))?;
+
+    let Some(record_batch) = record_batches
+        .into_iter()
+        .find(|record_batch| record_batch.num_rows() > 0)
+    else {
+        return Err(MetaApiError::InvocationNotFound(invocation_id));
+    };
+    let (Some(status), Some(journal_size)) = (
+        record_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<LargeStringArray>(),
+        record_batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<UInt32Array>(),
+    ) else {
+        return Err(MetaApiError::Internal(
+            "unexpected schema of sys_invocation_status".to_owned(),
+        ));
+    };
+
+    if status.value(0) != "completed" {
+        return Err(MetaApiError::InvalidInvocationStatus(
+            "restart as new",
+            invocation_id,
+            "the invocation is not completed",
+        ));
+    }
+    let journal_size = if journal_size.is_null(0) {
+        0
+    } else {
+        journal_size.value(0)
+    };
+    if journal_size == 0 {
+        return Err(MetaApiError::InvalidInvocationStatus(
+            "restart as new",
+            invocation_id,
+            "the journal of the invocation was not retained",
+        ));
+    }
+    if from_entry >= journal_size {
+        return Err(MetaApiError::InvalidField(
+            "from_entry",
+            format!("the retained journal has {journal_size} entries"),
+        ));
+    }
+
+    Ok(())
+}
+
+pub(super) async fn append_invocation_command<V>(
+    state: &AdminServiceState<V>,
+    invocation_id: InvocationId,
+    cmd: Command,
+    operation: &'static str,
+) -> Result<StatusCode, MetaApiError> {
+    let partition_key = invocation_id.partition_key();
+
+    let result = restate_bifrost::append_to_bifrost(
+        &state.bifrost,
+        Arc::new(Envelope::new(create_envelope_header(partition_key), cmd)),
+    )
+    .await;
+
+    if let Err(err) = result {
+        warn!("Could not append invocation {operation} command to Bifrost: {err}");
+        Err(MetaApiError::Internal(format!(
+            "Failed sending invocation {operation} to the cluster."
+        )))
+    } else {
+        Ok(StatusCode::ACCEPTED)
+    }
+}
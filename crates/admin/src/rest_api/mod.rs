@@ -11,11 +11,13 @@
 //! This module implements the Meta API endpoint.
 
 mod cluster_health;
+mod dead_letters;
 mod deployments;
 mod error;
 mod handlers;
 mod health;
 mod invocations;
+mod schedules;
 mod services;
 mod subscriptions;
 mod version;
@@ -84,6 +86,42 @@ where
             "/invocations/:invocation_id",
             delete(openapi_handler!(invocations::delete_invocation)),
         )
+        .route(
+            "/invocations/:invocation_id/pause",
+            patch(openapi_handler!(invocations::pause_invocation)),
+        )
+        .route(
+            "/invocations/:invocation_id/resume",
+            patch(openapi_handler!(invocations::resume_invocation)),
+        )
+        .route(
+            "/invocations/:invocation_id/restart-as-new",
+            patch(openapi_handler!(invocations::restart_as_new_invocation)),
+        )
+        .route(
+            "/dead-letters/:invocation_id/redrive",
+            patch(openapi_handler!(dead_letters::redrive_dead_letter)),
+        )
+        .route(
+            "/dead-letters/:invocation_id",
+            delete(openapi_handler!(dead_letters::discard_dead_letter)),
+        )
+        .route(
+            "/schedules",
+            post(openapi_handler!(schedules::create_schedule)),
+        )
+        .route(
+            "/schedules",
+            get(openapi_handler!(schedules::list_schedules)),
+        )
+        .route(
+            "/schedules/:schedule",
+            get(openapi_handler!(schedules::get_schedule)),
+        )
+        .route(
+            "/schedules/:schedule",
+            delete(openapi_handler!(schedules::delete_schedule)),
+        )
         .route(
             "/subscriptions",
             post(openapi_handler!(subscriptions::create_subscription)),
@@ -137,6 +175,11 @@ where
             }),
             ..Default::default()
         })
+        .tag(Tag {
+            name: "schedule".to_string(),
+            description: Some("Cron schedules management".to_string()),
+            ..Default::default()
+        })
         .tag(Tag {
             name: "service".to_string(),
             description: Some("Service management".to_string()),
@@ -170,7 +213,7 @@ where
         .with_state(state)
 }
 
-fn create_envelope_header(partition_key: PartitionKey) -> Header {
+pub(crate) fn create_envelope_header(partition_key: PartitionKey) -> Header {
     Header {
         source: Source::ControlPlane {},
         dest: Destination::Processor {
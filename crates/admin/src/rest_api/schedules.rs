@@ -0,0 +1,272 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::error::*;
+use std::sync::Arc;
+
+use crate::rest_api::create_envelope_header;
+use crate::state::AdminServiceState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Json, http};
+use bytes::Bytes;
+use okapi_operation::*;
+use restate_admin_rest_model::schedules::*;
+use restate_errors::warn_it;
+use restate_types::identifiers::{ScheduleId, WithPartitionKey};
+use restate_types::invocation::{InvocationTarget, ServiceType, VirtualObjectHandlerType};
+use restate_types::schedule::{CronExpression, Schedule};
+use restate_types::schema::service::HandlerMetadataType;
+use restate_wal_protocol::{Command, Envelope};
+use tracing::{error, warn};
+
+/// Create schedule.
+#[openapi(
+    summary = "Create schedule",
+    description = "Create a schedule invoking the given handler every time the cron expression fires.",
+    operation_id = "create_schedule",
+    tags = "schedule",
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "201",
+            description = "Created",
+            content = "Json<ScheduleResponse>",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn create_schedule<V>(
+    State(state): State<AdminServiceState<V>>,
+    #[request_body(required = true)] Json(payload): Json<CreateScheduleRequest>,
+) -> Result<impl axum::response::IntoResponse, MetaApiError> {
+    let cron = payload
+        .cron
+        .parse::<CronExpression>()
+        .map_err(|e| MetaApiError::InvalidField("cron", e.to_string()))?;
+
+    let service = state
+        .schema_registry
+        .get_service(&payload.service)
+        .ok_or_else(|| MetaApiError::ServiceNotFound(payload.service.clone()))?;
+    let handler = service
+        .handlers
+        .iter()
+        .find(|handler| handler.name == payload.handler)
+        .ok_or_else(|| MetaApiError::HandlerNotFound {
+            service_name: payload.service.clone(),
+            handler_name: payload.handler.clone(),
+        })?;
+
+    let invocation_target = match (service.ty, payload.key) {
+        (ServiceType::Service, None) => InvocationTarget::service(payload.service, payload.handler),
+        (ServiceType::Service, Some(_)) => {
+            return Err(MetaApiError::InvalidField(
+                "key",
+                "a key can be provided only when invoking a virtual object".to_owned(),
+            ));
+        }
+        (ServiceType::VirtualObject, Some(key)) => InvocationTarget::virtual_object(
+            payload.service,
+            key,
+            payload.handler,
+            if handler.ty == Some(HandlerMetadataType::Shared) {
+                VirtualObjectHandlerType::Shared
+            } else {
+                VirtualObjectHandlerType::Exclusive
+            },
+        ),
+        (ServiceType::VirtualObject, None) => {
+            return Err(MetaApiError::InvalidField(
+                "key",
+                "a key is required when invoking a virtual object".to_owned(),
+            ));
+        }
+        (ServiceType::Workflow, _) => {
+            // A workflow runs once per key, hence it cannot be scheduled repeatedly
+            return Err(MetaApiError::UnsupportedOperation(
+                "create a schedule",
+                ServiceType::Workflow,
+            ));
+        }
+    };
+
+    let argument = match payload.payload {
+        Some(value) => Bytes::from(
+            serde_json::to_vec(&value)
+                .map_err(|e| MetaApiError::InvalidField("payload", e.to_string()))?,
+        ),
+        None => Bytes::new(),
+    };
+
+    let schedule = Schedule {
+        id: ScheduleId::new(),
+        cron,
+        invocation_target,
+        argument,
+        catch_up: payload.catch_up.unwrap_or_default(),
+    };
+
+    state
+        .schema_registry
+        .create_schedule(schedule.clone())
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    // The partition materializes the schedule, if that's not possible remove it again so that
+    // the schedule is either listed and running, or neither of the two.
+    if let Err(err) = append_schedule_command(
+        &state,
+        schedule.id,
+        Command::UpsertSchedule(schedule.clone()),
+        "create",
+    )
+    .await
+    {
+        if let Err(rollback_err) = state.schema_registry.delete_schedule(schedule.id).await {
+            error!(
+                restate.schedule.id = %schedule.id,
+                "Could not remove the schedule which failed to be created, delete it manually: {rollback_err}"
+            );
+        }
+        return Err(err);
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        [(http::header::LOCATION, format!("schedules/{}", schedule.id))],
+        Json(ScheduleResponse::from(schedule)),
+    ))
+}
+
+/// Get schedule.
+#[openapi(
+    summary = "Get schedule",
+    description = "Get schedule. The next and last runs can be queried in the sys_schedule table.",
+    operation_id = "get_schedule",
+    tags = "schedule",
+    parameters(path(
+        name = "schedule",
+        description = "Schedule identifier",
+        schema = "std::string::String"
+    ))
+)]
+pub async fn get_schedule<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(schedule_id): Path<ScheduleId>,
+) -> Result<Json<ScheduleResponse>, MetaApiError> {
+    let schedules = state.schema_registry.list_schedules().await?;
+    let schedule = schedules
+        .get(&schedule_id)
+        .cloned()
+        .ok_or_else(|| MetaApiError::ScheduleNotFound(schedule_id))?;
+
+    Ok(ScheduleResponse::from(schedule).into())
+}
+
+/// List schedules.
+#[openapi(
+    summary = "List schedules",
+    description = "List all schedules.",
+    operation_id = "list_schedules",
+    tags = "schedule"
+)]
+pub async fn list_schedules<V>(
+    State(state): State<AdminServiceState<V>>,
+) -> Result<Json<ListSchedulesResponse>, MetaApiError> {
+    let schedules = state.schema_registry.list_schedules().await?;
+
+    Ok(ListSchedulesResponse {
+        schedules: schedules
+            .iter()
+            .cloned()
+            .map(ScheduleResponse::from)
+            .collect(),
+    }
+    .into())
+}
+
+/// Delete schedule.
+#[openapi(
+    summary = "Delete schedule",
+    description = "Delete schedule. The runs already started are not affected.",
+    operation_id = "delete_schedule",
+    tags = "schedule",
+    parameters(path(
+        name = "schedule",
+        description = "Schedule identifier",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn delete_schedule<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(schedule_id): Path<ScheduleId>,
+) -> Result<StatusCode, MetaApiError> {
+    if state
+        .schema_registry
+        .list_schedules()
+        .await?
+        .get(&schedule_id)
+        .is_none()
+    {
+        return Err(MetaApiError::ScheduleNotFound(schedule_id));
+    }
+
+    // Stop the schedule first, so that a failure leaves it listed and the delete can be retried.
+    // Deleting a schedule which is not running anymore has no effect on the partition.
+    let status = append_schedule_command(
+        &state,
+        schedule_id,
+        Command::DeleteSchedule(schedule_id),
+        "delete",
+    )
+    .await?;
+
+    state
+        .schema_registry
+        .delete_schedule(schedule_id)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(status)
+}
+
+async fn append_schedule_command<V>(
+    state: &AdminServiceState<V>,
+    schedule_id: ScheduleId,
+    cmd: Command,
+    operation: &'static str,
+) -> Result<StatusCode, MetaApiError> {
+    let partition_key = schedule_id.partition_key();
+
+    let result = restate_bifrost::append_to_bifrost(
+        &state.bifrost,
+        Arc::new(Envelope::new(create_envelope_header(partition_key), cmd)),
+    )
+    .await;
+
+    if let Err(err) = result {
+        warn!("Could not append schedule {operation} command to Bifrost: {err}");
+        Err(MetaApiError::Internal(format!(
+            "Failed sending schedule {operation} to the cluster."
+        )))
+    } else {
+        Ok(StatusCode::ACCEPTED)
+    }
+}
@@ -131,8 +131,14 @@ pub async fn modify_service<V>(
         public,
         idempotency_retention,
         workflow_completion_retention,
+        journal_retention,
         inactivity_timeout,
         abort_timeout,
+        retry_policy,
+        dead_letter_queue,
+        cors,
+        validate_input,
+        state_ttl,
     }): Json<ModifyServiceRequest>,
 ) -> Result<Json<ServiceMetadata>, MetaApiError> {
     let mut modify_request = vec![];
@@ -149,12 +155,30 @@ pub async fn modify_service<V>(
             new_workflow_completion_retention,
         ));
     }
+    if let Some(new_journal_retention) = journal_retention {
+        modify_request.push(ModifyServiceChange::JournalRetention(new_journal_retention));
+    }
     if let Some(inactivity_timeout) = inactivity_timeout {
         modify_request.push(ModifyServiceChange::InactivityTimeout(inactivity_timeout));
     }
     if let Some(abort_timeout) = abort_timeout {
         modify_request.push(ModifyServiceChange::AbortTimeout(abort_timeout));
     }
+    if let Some(retry_policy) = retry_policy {
+        modify_request.push(ModifyServiceChange::RetryPolicy(retry_policy));
+    }
+    if let Some(dead_letter_queue) = dead_letter_queue {
+        modify_request.push(ModifyServiceChange::DeadLetterQueue(dead_letter_queue));
+    }
+    if let Some(cors) = cors {
+        modify_request.push(ModifyServiceChange::Cors(cors));
+    }
+    if let Some(validate_input) = validate_input {
+        modify_request.push(ModifyServiceChange::ValidateInput(validate_input));
+    }
+    if let Some(state_ttl) = state_ttl {
+        modify_request.push(ModifyServiceChange::StateTtl(state_ttl));
+    }
 
     if modify_request.is_empty() {
         // No need to do anything
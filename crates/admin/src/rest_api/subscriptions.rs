@@ -47,7 +47,12 @@ pub async fn create_subscription<V: SubscriptionValidator>(
 ) -> Result<impl axum::response::IntoResponse, MetaApiError> {
     let subscription = state
         .schema_registry
-        .create_subscription(payload.source, payload.sink, payload.options)
+        .create_subscription(
+            payload.source,
+            payload.sink,
+            payload.options,
+            payload.dead_letter_queue.unwrap_or_default(),
+        )
         .await
         .inspect_err(|e| warn_it!(e))?;
 
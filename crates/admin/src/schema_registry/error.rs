@@ -106,25 +106,60 @@ pub enum ServiceError {
         #[source]
         error: Box<dyn std::error::Error + Send + Sync + 'static>,
     },
+    #[error("the protobuf descriptor of the service '{0}' is invalid: {1}")]
+    #[code(unknown)]
+    BadProtobufDescriptor(ServiceName, String),
+    #[error(
+        "the handler '{service}/{handler}' has the invalid metadata {}='{value}', expected either 'true' or 'false'",
+        restate_types::schema::service::VALIDATE_INPUT_METADATA_KEY
+    )]
+    #[code(unknown)]
+    BadValidateInputMetadata {
+        service: String,
+        handler: String,
+        value: String,
+    },
     #[error("modifying retention time for service type {0} is unsupported")]
     #[code(unknown)]
     CannotModifyRetentionTime(ServiceType),
+    #[error("setting the state TTL for service type {0} is unsupported, as it has no state")]
+    #[code(unknown)]
+    CannotSetStateTtl(ServiceType),
+    #[error(
+        "the CORS policy of the service '{0}' allows credentials, which requires an explicit list of allowed origins without '*'"
+    )]
+    #[code(unknown)]
+    CorsCredentialsWithoutOrigins(String),
 }
 
 #[derive(Debug, thiserror::Error, codederror::CodedError)]
 #[code(restate_errors::META0009)]
 pub enum SubscriptionError {
     #[error(
-        "invalid source URI '{0}': must have a scheme segment, with supported schemes: [kafka]."
+        "invalid source URI '{0}': must have a scheme segment, with supported schemes: [kafka, file, webhook, service]."
     )]
     InvalidSourceScheme(Uri),
     #[error(
         "invalid source URI '{0}': source URI of Kafka type must have a authority segment containing the cluster name."
     )]
     InvalidKafkaSourceAuthority(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of file type must have the localhost authority and an absolute path segment, e.g. file://localhost/var/events."
+    )]
+    InvalidFileSourcePath(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of webhook type must have a authority segment containing the webhook name."
+    )]
+    InvalidWebhookSourceAuthority(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of service type must have a authority segment containing the service name."
+    )]
+    InvalidServiceSourceAuthority(Uri),
+    #[error("invalid source URI '{0}': cannot find service/handler specified in the source URI.")]
+    SourceServiceNotFound(Uri),
 
     #[error(
-        "invalid sink URI '{0}': must have a scheme segment, with supported schemes: [service]."
+        "invalid sink URI '{0}': must have a scheme segment, with supported schemes: [service, kafka]."
     )]
     InvalidSinkScheme(Uri),
     #[error(
@@ -133,6 +168,10 @@ pub enum SubscriptionError {
     InvalidServiceSinkAuthority(Uri),
     #[error("invalid sink URI '{0}': cannot find service/handler specified in the sink URI.")]
     SinkServiceNotFound(Uri),
+    #[error(
+        "invalid sink URI '{0}': sink URI of Kafka type must have a authority segment containing the cluster name."
+    )]
+    InvalidKafkaSinkAuthority(Uri),
 
     #[error(transparent)]
     #[code(unknown)]
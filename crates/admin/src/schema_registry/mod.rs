@@ -22,12 +22,17 @@ use tracing::subscriber::NoSubscriber;
 
 use restate_core::{Metadata, MetadataWriter};
 use restate_service_protocol::discovery::{DiscoverEndpoint, DiscoveredEndpoint, ServiceDiscovery};
-use restate_types::identifiers::{DeploymentId, ServiceRevision, SubscriptionId};
+use restate_types::identifiers::{DeploymentId, ScheduleId, ServiceRevision, SubscriptionId};
+use restate_types::metadata_store::keys::SCHEDULES_KEY;
+use restate_types::retries::RetryPolicy;
+use restate_types::schedule::{Schedule, Schedules};
 use restate_types::schema::Schema;
 use restate_types::schema::deployment::{
     DeliveryOptions, Deployment, DeploymentMetadata, DeploymentResolver,
 };
-use restate_types::schema::service::{HandlerMetadata, ServiceMetadata, ServiceMetadataResolver};
+use restate_types::schema::service::{
+    HandlerMetadata, ServiceCorsPolicy, ServiceMetadata, ServiceMetadataResolver,
+};
 use restate_types::schema::subscriptions::{
     ListSubscriptionFilter, Subscription, SubscriptionResolver, SubscriptionValidator,
 };
@@ -67,8 +72,14 @@ pub enum ModifyServiceChange {
     Public(bool),
     IdempotencyRetention(Duration),
     WorkflowCompletionRetention(Duration),
+    JournalRetention(Duration),
     InactivityTimeout(Duration),
     AbortTimeout(Duration),
+    RetryPolicy(RetryPolicy),
+    DeadLetterQueue(bool),
+    Cors(ServiceCorsPolicy),
+    ValidateInput(bool),
+    StateTtl(Duration),
 }
 
 /// Responsible for updating the registered schema information. This includes the discovery of
@@ -388,6 +399,52 @@ impl<V> SchemaRegistry<V> {
     pub fn list_subscriptions(&self, filters: &[ListSubscriptionFilter]) -> Vec<Subscription> {
         Metadata::with_current(|m| m.schema()).list_subscriptions(filters)
     }
+
+    pub async fn create_schedule(&self, schedule: Schedule) -> Result<(), SchemaRegistryError> {
+        self.metadata_writer
+            .raw_metadata_store_client()
+            .read_modify_write(SCHEDULES_KEY.clone(), |schedules: Option<Schedules>| {
+                let mut schedules = schedules.unwrap_or_default();
+                schedules.insert(schedule.clone());
+                Ok::<_, SchemaError>(schedules)
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_schedule(
+        &self,
+        schedule_id: ScheduleId,
+    ) -> Result<Schedule, SchemaRegistryError> {
+        let mut deleted = None;
+
+        self.metadata_writer
+            .raw_metadata_store_client()
+            .read_modify_write(SCHEDULES_KEY.clone(), |schedules: Option<Schedules>| {
+                let mut schedules = schedules.unwrap_or_default();
+                deleted = schedules.remove(&schedule_id);
+                if deleted.is_some() {
+                    Ok(schedules)
+                } else {
+                    Err(SchemaError::NotFound(format!(
+                        "schedule with id '{schedule_id}'"
+                    )))
+                }
+            })
+            .await?;
+
+        Ok(deleted.expect("schedule was just deleted"))
+    }
+
+    pub async fn list_schedules(&self) -> Result<Schedules, SchemaRegistryError> {
+        self.metadata_writer
+            .raw_metadata_store_client()
+            .get::<Schedules>(SCHEDULES_KEY.clone())
+            .await
+            .map(Option::unwrap_or_default)
+            .map_err(|err| SchemaRegistryError::Internal(err.to_string()))
+    }
 }
 
 impl<V> SchemaRegistry<V>
@@ -399,6 +456,7 @@ where
         source: Uri,
         sink: Uri,
         options: Option<HashMap<String, String>>,
+        dead_letter_queue: bool,
     ) -> Result<Subscription, SchemaRegistryError> {
         let mut subscription_id = None;
 
@@ -416,6 +474,7 @@ where
                     source.clone(),
                     sink.clone(),
                     options.clone(),
+                    dead_letter_queue,
                     &self.subscription_validator,
                 )?);
 
@@ -12,7 +12,9 @@ use crate::schema_registry::error::{
     DeploymentError, SchemaError, ServiceError, SubscriptionError,
 };
 use crate::schema_registry::{ModifyServiceChange, ServiceName};
+use base64::Engine;
 use http::{HeaderValue, Uri};
+use prost::Message;
 use restate_types::endpoint_manifest;
 use restate_types::identifiers::{DeploymentId, SubscriptionId};
 use restate_types::invocation::{
@@ -23,16 +25,23 @@ use restate_types::schema::deployment::DeploymentMetadata;
 use restate_types::schema::deployment::DeploymentSchemas;
 use restate_types::schema::invocation_target::{
     DEFAULT_IDEMPOTENCY_RETENTION, DEFAULT_WORKFLOW_COMPLETION_RETENTION, InputRules,
-    InputValidationRule, InvocationTargetMetadata, OutputContentTypeRule, OutputRules,
+    InputValidationRule, InvocationRetryPolicy, InvocationTargetMetadata, OutputContentTypeRule,
+    OutputRules,
+};
+use restate_types::schema::service::{
+    HandlerSchemas, PROTOBUF_DESCRIPTOR_SET_METADATA_KEY, ProtobufHandlerSchemas, ServiceLocation,
+    ServiceSchemas, VALIDATE_INPUT_METADATA_KEY,
 };
-use restate_types::schema::service::{HandlerSchemas, ServiceLocation, ServiceSchemas};
 use restate_types::schema::subscriptions::{
-    EventInvocationTargetTemplate, Sink, Source, Subscription, SubscriptionValidator,
+    EventInvocationTargetTemplate, KAFKA_SINK_KEY_TEMPLATE_OPTION, Sink, Source, Subscription,
+    SubscriptionValidator,
 };
 use serde_json::Value;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::error::Error;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// Responsible for updating the provided [`Schema`] with new
@@ -125,10 +134,16 @@ impl SchemaUpdater {
         for (service_name, service) in proposed_services {
             let service_type = ServiceType::from(service.ty);
 
+            let service_retry_policy = service.retry_policy.as_ref().map(retry_policy_from_schema);
+
             // For the time being when updating we overwrite existing data
             let service_schema = if let Some(existing_service) =
                 self.schema_information.services.get(service_name.as_ref())
             {
+                // Retry policy overrides set through the admin API are kept,
+                // unless the new manifest declares one.
+                let service_retry_policy =
+                    service_retry_policy.or_else(|| existing_service.retry_policy.clone());
                 let handlers = DiscoveredHandlerMetadata::compute_handlers(
                     service
                         .handlers
@@ -142,6 +157,9 @@ impl SchemaUpdater {
                         })
                         .collect::<Result<Vec<_>, _>>()?,
                     existing_service.location.public,
+                    service_retry_policy.as_ref(),
+                    existing_service.dead_letter_queue,
+                    existing_service.journal_retention,
                 );
 
                 let removed_handlers: Vec<String> = existing_service
@@ -197,6 +215,7 @@ impl SchemaUpdater {
                 service_schemas.service_openapi_cache = Default::default();
                 service_schemas.documentation = service.documentation;
                 service_schemas.metadata = service.metadata;
+                service_schemas.retry_policy = service_retry_policy;
 
                 service_schemas
             } else {
@@ -215,6 +234,9 @@ impl SchemaUpdater {
                             })
                             .collect::<Result<Vec<_>, _>>()?,
                         true,
+                        service_retry_policy.as_ref(),
+                        false,
+                        None,
                     ),
                     ty: service_type,
                     location: ServiceLocation {
@@ -227,13 +249,22 @@ impl SchemaUpdater {
                     } else {
                         None
                     },
+                    journal_retention: None,
                     inactivity_timeout: None,
                     abort_timeout: None,
+                    retry_policy: service_retry_policy,
+                    dead_letter_queue: false,
+                    cors: None,
+                    validate_input: false,
+                    state_ttl: None,
                     service_openapi_cache: Default::default(),
                     documentation: service.documentation,
                     metadata: service.metadata,
+                    protobuf_file_descriptor_set: None,
                 }
             };
+            let mut service_schema = compute_protobuf_schemas(&service_name, service_schema)?;
+            compute_input_validation(service_name.as_ref(), &mut service_schema)?;
 
             services_to_add.insert(service_name, service_schema);
         }
@@ -323,9 +354,14 @@ impl SchemaUpdater {
         // Compute service schemas
         for (service_name, service) in proposed_services {
             let service_type = ServiceType::from(service.ty);
+            let service_retry_policy = service.retry_policy.as_ref().map(retry_policy_from_schema);
             let service_schema = if let Some(existing_service) =
                 self.schema_information.services.get(service_name.as_ref())
             {
+                // Retry policy overrides set through the admin API are kept,
+                // unless the new manifest declares one.
+                let service_retry_policy =
+                    service_retry_policy.or_else(|| existing_service.retry_policy.clone());
                 let handlers = DiscoveredHandlerMetadata::compute_handlers(
                     service
                         .handlers
@@ -339,6 +375,9 @@ impl SchemaUpdater {
                         })
                         .collect::<Result<Vec<_>, _>>()?,
                     existing_service.location.public,
+                    service_retry_policy.as_ref(),
+                    existing_service.dead_letter_queue,
+                    existing_service.journal_retention,
                 );
 
                 let removed_handlers: Vec<String> = existing_service
@@ -369,6 +408,7 @@ impl SchemaUpdater {
                 service_schemas.service_openapi_cache = Default::default();
                 service_schemas.documentation = service.documentation;
                 service_schemas.metadata = service.metadata;
+                service_schemas.retry_policy = service_retry_policy;
 
                 service_schemas
             } else {
@@ -387,6 +427,9 @@ impl SchemaUpdater {
                             })
                             .collect::<Result<Vec<_>, _>>()?,
                         true,
+                        service_retry_policy.as_ref(),
+                        false,
+                        None,
                     ),
                     ty: service_type,
                     location: ServiceLocation {
@@ -399,13 +442,22 @@ impl SchemaUpdater {
                     } else {
                         None
                     },
+                    journal_retention: None,
                     inactivity_timeout: None,
                     abort_timeout: None,
+                    retry_policy: service_retry_policy,
+                    dead_letter_queue: false,
+                    cors: None,
+                    validate_input: false,
+                    state_ttl: None,
                     service_openapi_cache: Default::default(),
                     documentation: service.documentation,
                     metadata: service.metadata,
+                    protobuf_file_descriptor_set: None,
                 }
             };
+            let mut service_schema = compute_protobuf_schemas(&service_name, service_schema)?;
+            compute_input_validation(service_name.as_ref(), &mut service_schema)?;
 
             services_to_add.insert(service_name, service_schema);
         }
@@ -485,6 +537,7 @@ impl SchemaUpdater {
         source: Uri,
         sink: Uri,
         metadata: Option<HashMap<String, String>>,
+        dead_letter_queue: bool,
         validator: &V,
     ) -> Result<SubscriptionId, SchemaError> {
         // generate id if not provided
@@ -516,6 +569,59 @@ impl SchemaUpdater {
                     topic: topic_name.to_string(),
                 }
             }
+            Some("file") => {
+                // Files are tailed by the local worker, hence only localhost is accepted as authority.
+                let path = source.path();
+                if source.host() != Some("localhost") || path.is_empty() || path == "/" {
+                    return Err(SchemaError::Subscription(
+                        SubscriptionError::InvalidFileSourcePath(source),
+                    ));
+                }
+                Source::File {
+                    path: path.to_string(),
+                }
+            }
+            Some("webhook") => {
+                let webhook_name = source
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidWebhookSourceAuthority(
+                            source.clone(),
+                        ))
+                    })?
+                    .as_str();
+                Source::Webhook {
+                    name: webhook_name.to_string(),
+                }
+            }
+            Some("service") => {
+                let service_name = source
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidServiceSourceAuthority(
+                            source.clone(),
+                        ))
+                    })?
+                    .as_str();
+                let handler_name = &source.path()[1..];
+
+                if !self
+                    .schema_information
+                    .services
+                    .get(service_name)
+                    .is_some_and(|service_schemas| {
+                        service_schemas.handlers.contains_key(handler_name)
+                    })
+                {
+                    return Err(SchemaError::Subscription(
+                        SubscriptionError::SourceServiceNotFound(source),
+                    ));
+                }
+                Source::Service {
+                    name: service_name.to_owned(),
+                    handler: handler_name.to_owned(),
+                }
+            }
             _ => {
                 return Err(SchemaError::Subscription(
                     SubscriptionError::InvalidSourceScheme(source),
@@ -523,6 +629,8 @@ impl SchemaUpdater {
             }
         };
 
+        let mut metadata = metadata.unwrap_or_default();
+
         // Parse sink
         let sink = match sink.scheme_str() {
             Some("service") => {
@@ -576,6 +684,22 @@ impl SchemaUpdater {
                     },
                 }
             }
+            Some("kafka") => {
+                let cluster_name = sink
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidKafkaSinkAuthority(
+                            sink.clone(),
+                        ))
+                    })?
+                    .as_str();
+                let topic_name = &sink.path()[1..];
+                Sink::Kafka {
+                    cluster: cluster_name.to_owned(),
+                    topic: topic_name.to_owned(),
+                    key_template: metadata.remove(KAFKA_SINK_KEY_TEMPLATE_OPTION),
+                }
+            }
             _ => {
                 return Err(SchemaError::Subscription(
                     SubscriptionError::InvalidSinkScheme(sink),
@@ -588,7 +712,8 @@ impl SchemaUpdater {
                 id,
                 source,
                 sink,
-                metadata.unwrap_or_default(),
+                metadata,
+                dead_letter_queue,
             ))
             .map_err(|e| SchemaError::Subscription(SubscriptionError::Validation(e.into())))?;
 
@@ -651,12 +776,58 @@ impl SchemaUpdater {
                                 Some(new_workflow_completion_retention);
                         }
                     }
+                    ModifyServiceChange::JournalRetention(new_journal_retention) => {
+                        schemas.journal_retention = Some(new_journal_retention);
+                        for h in schemas.handlers.values_mut() {
+                            h.target_meta.journal_retention = Some(new_journal_retention);
+                        }
+                    }
                     ModifyServiceChange::InactivityTimeout(inactivity_timeout) => {
                         schemas.inactivity_timeout = Some(inactivity_timeout);
                     }
                     ModifyServiceChange::AbortTimeout(abort_timeout) => {
                         schemas.abort_timeout = Some(abort_timeout);
                     }
+                    ModifyServiceChange::RetryPolicy(new_retry_policy) => {
+                        let new_retry_policy = InvocationRetryPolicy::Policy(new_retry_policy);
+                        for h in schemas.handlers.values_mut() {
+                            // Handler overrides take precedence over the service one
+                            h.target_meta.retry_policy = Some(
+                                h.retry_policy
+                                    .clone()
+                                    .unwrap_or_else(|| new_retry_policy.clone()),
+                            );
+                        }
+                        schemas.retry_policy = Some(new_retry_policy);
+                    }
+                    ModifyServiceChange::DeadLetterQueue(dead_letter_queue) => {
+                        schemas.dead_letter_queue = dead_letter_queue;
+                        for h in schemas.handlers.values_mut() {
+                            h.target_meta.dead_letter_queue = dead_letter_queue;
+                        }
+                    }
+                    ModifyServiceChange::Cors(cors) => {
+                        if cors.allows_credentials_from_unlisted_origins() {
+                            return Err(SchemaError::Service(
+                                ServiceError::CorsCredentialsWithoutOrigins(name.clone()),
+                            ));
+                        }
+                        // An empty policy removes the overrides
+                        schemas.cors = Some(cors).filter(|cors| cors != &Default::default());
+                    }
+                    ModifyServiceChange::ValidateInput(validate_input) => {
+                        schemas.validate_input = validate_input;
+                        compute_input_validation(&name, schemas)?;
+                    }
+                    ModifyServiceChange::StateTtl(state_ttl) => {
+                        if !schemas.ty.has_state() {
+                            return Err(SchemaError::Service(ServiceError::CannotSetStateTtl(
+                                schemas.ty,
+                            )));
+                        }
+                        // A zero TTL removes it
+                        schemas.state_ttl = Some(state_ttl).filter(|ttl| !ttl.is_zero());
+                    }
                 }
             }
         }
@@ -667,7 +838,7 @@ impl SchemaUpdater {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 struct DiscoveredHandlerMetadata {
     name: String,
     ty: InvocationTargetType,
@@ -675,6 +846,7 @@ struct DiscoveredHandlerMetadata {
     metadata: HashMap<String, String>,
     input: InputRules,
     output: OutputRules,
+    retry_policy: Option<InvocationRetryPolicy>,
 }
 
 impl DiscoveredHandlerMetadata {
@@ -735,6 +907,7 @@ impl DiscoveredHandlerMetadata {
                 })
                 .transpose()?
                 .unwrap_or_default(),
+            retry_policy: handler.retry_policy.as_ref().map(retry_policy_from_schema),
         })
     }
 
@@ -829,6 +1002,9 @@ impl DiscoveredHandlerMetadata {
     fn compute_handlers(
         handlers: Vec<DiscoveredHandlerMetadata>,
         public: bool,
+        service_retry_policy: Option<&InvocationRetryPolicy>,
+        dead_letter_queue: bool,
+        journal_retention: Option<Duration>,
     ) -> HashMap<String, HandlerSchemas> {
         handlers
             .into_iter()
@@ -846,12 +1022,22 @@ impl DiscoveredHandlerMetadata {
                             } else {
                                 None
                             },
+                            journal_retention,
+                            retry_policy: handler
+                                .retry_policy
+                                .clone()
+                                .or_else(|| service_retry_policy.cloned()),
+                            dead_letter_queue,
+                            // computed by compute_input_validation
+                            validate_input: false,
                             target_ty: handler.ty,
                             input_rules: handler.input,
                             output_rules: handler.output,
                         },
                         documentation: handler.documentation,
                         metadata: handler.metadata,
+                        retry_policy: handler.retry_policy,
+                        protobuf: None,
                     },
                 )
             })
@@ -859,6 +1045,121 @@ impl DiscoveredHandlerMetadata {
     }
 }
 
+/// Converts the retry policy declared in the endpoint manifest to an [`InvocationRetryPolicy`].
+/// The unset fields are left unset, so that the invoker fills them with its own retry policy.
+fn retry_policy_from_schema(
+    retry_policy: &endpoint_manifest::RetryPolicy,
+) -> InvocationRetryPolicy {
+    InvocationRetryPolicy::Exponential {
+        initial_interval: retry_policy
+            .initial_interval
+            .map(|initial_interval| Duration::from_millis(initial_interval as u64).into()),
+        factor: retry_policy
+            .exponentiation_factor
+            .map(|exponentiation_factor| exponentiation_factor as f32),
+        max_attempts: retry_policy
+            .max_attempts
+            .and_then(|max_attempts| NonZeroUsize::new(max_attempts as usize)),
+        max_interval: retry_policy
+            .max_interval
+            .map(|max_interval| Duration::from_millis(max_interval as u64).into()),
+    }
+}
+
+/// Applies the input validation setting of the service to its handlers, unless they override it
+/// with the [`VALIDATE_INPUT_METADATA_KEY`] metadata.
+fn compute_input_validation(
+    service_name: &str,
+    service_schemas: &mut ServiceSchemas,
+) -> Result<(), ServiceError> {
+    let validate_input = service_schemas.validate_input;
+    for (handler_name, handler) in service_schemas.handlers.iter_mut() {
+        handler.target_meta.validate_input = match handler.metadata.get(VALIDATE_INPUT_METADATA_KEY)
+        {
+            None => validate_input,
+            Some(value) => value
+                .parse()
+                .map_err(|_| ServiceError::BadValidateInputMetadata {
+                    service: service_name.to_owned(),
+                    handler: handler_name.clone(),
+                    value: value.clone(),
+                })?,
+        };
+    }
+    Ok(())
+}
+
+/// Resolves the protobuf schemas of the handlers from the protobuf descriptor declared in the
+/// service metadata, matching the methods of the gRPC service with the same name by handler name.
+fn compute_protobuf_schemas(
+    service_name: &ServiceName,
+    mut service_schemas: ServiceSchemas,
+) -> Result<ServiceSchemas, ServiceError> {
+    service_schemas.protobuf_file_descriptor_set = None;
+    for handler in service_schemas.handlers.values_mut() {
+        handler.protobuf = None;
+    }
+    let Some(encoded) = service_schemas
+        .metadata
+        .get(PROTOBUF_DESCRIPTOR_SET_METADATA_KEY)
+    else {
+        return Ok(service_schemas);
+    };
+
+    let bad_descriptor =
+        |reason: String| ServiceError::BadProtobufDescriptor(service_name.clone(), reason);
+    let file_descriptor_set = base64::prelude::BASE64_STANDARD
+        .decode(encoded.trim())
+        .map_err(|err| bad_descriptor(err.to_string()))?;
+    let descriptor = prost_types::FileDescriptorSet::decode(file_descriptor_set.as_slice())
+        .map_err(|err| bad_descriptor(err.to_string()))?;
+
+    let service_descriptor = descriptor
+        .file
+        .iter()
+        .flat_map(|file| {
+            file.service
+                .iter()
+                .map(move |service| (file.package(), service))
+        })
+        .find(|(package, service)| {
+            let full_name = if package.is_empty() {
+                service.name().to_owned()
+            } else {
+                format!("{package}.{}", service.name())
+            };
+            full_name == service_name.as_ref()
+        })
+        .map(|(_, service)| service)
+        .ok_or_else(|| {
+            bad_descriptor("the descriptor set doesn't declare a service with this name".to_owned())
+        })?;
+
+    for (handler_name, handler) in service_schemas.handlers.iter_mut() {
+        let Some(method) = service_descriptor
+            .method
+            .iter()
+            .find(|method| method.name().eq_ignore_ascii_case(handler_name))
+        else {
+            continue;
+        };
+        if method.client_streaming() || method.server_streaming() {
+            return Err(bad_descriptor(format!(
+                "the method '{}' is streaming, only unary methods are supported",
+                method.name()
+            )));
+        }
+        handler.protobuf = Some(ProtobufHandlerSchemas {
+            method: method.name().to_owned(),
+            input_type: method.input_type().trim_start_matches('.').to_owned(),
+            output_type: method.output_type().trim_start_matches('.').to_owned(),
+        });
+    }
+
+    service_schemas.protobuf_file_descriptor_set = Some(file_descriptor_set.into());
+    Ok(service_schemas)
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error(
     "the schema contains an external reference {0}. This is not supported, all schemas uploaded to Restate should be normalized first, bundling the external references."
@@ -878,11 +1179,15 @@ mod tests {
     use super::*;
 
     use http::HeaderName;
-    use restate_test_util::{assert, assert_eq};
+    use restate_test_util::{assert, assert_eq, let_assert};
     use restate_types::schema::deployment::{Deployment, DeploymentResolver};
-    use restate_types::schema::service::ServiceMetadataResolver;
+    use restate_types::schema::service::{ServiceCorsPolicy, ServiceMetadataResolver};
 
     use restate_types::Versioned;
+    use restate_types::config::{IngressOptionsBuilder, KafkaClusterOptions};
+    use restate_types::invocation::KafkaSink;
+    use restate_types::retries::RetryPolicy;
+    use restate_types::schema::subscriptions::SubscriptionResolver;
     use test_log::test;
 
     const GREETER_SERVICE_NAME: &str = "greeter.Greeter";
@@ -900,8 +1205,10 @@ mod tests {
                 input: None,
                 output: None,
                 metadata: Default::default(),
+                retry_policy: None,
             }],
             metadata: Default::default(),
+            retry_policy: None,
         }
     }
 
@@ -917,8 +1224,10 @@ mod tests {
                 input: None,
                 output: None,
                 metadata: Default::default(),
+                retry_policy: None,
             }],
             metadata: Default::default(),
+            retry_policy: None,
         }
     }
 
@@ -934,8 +1243,10 @@ mod tests {
                 input: None,
                 output: None,
                 metadata: Default::default(),
+                retry_policy: None,
             }],
             metadata: Default::default(),
+            retry_policy: None,
         }
     }
 
@@ -999,6 +1310,147 @@ mod tests {
         schemas.assert_service_revision(ANOTHER_GREETER_SERVICE_NAME, 1);
     }
 
+    #[test]
+    fn retry_policy_overrides() {
+        let mut updater = SchemaUpdater::default();
+
+        let service_retry_policy = endpoint_manifest::RetryPolicy {
+            initial_interval: Some(100),
+            exponentiation_factor: Some(2.0),
+            max_interval: Some(1_000),
+            max_attempts: Some(5),
+        };
+        let handler_retry_policy = endpoint_manifest::RetryPolicy {
+            initial_interval: Some(10),
+            exponentiation_factor: None,
+            max_interval: None,
+            max_attempts: Some(1),
+        };
+
+        let mut greeter_virtual_object = greeter_virtual_object();
+        greeter_virtual_object.retry_policy = Some(service_retry_policy.clone());
+        greeter_virtual_object
+            .handlers
+            .push(endpoint_manifest::Handler {
+                name: "getGreetCount".parse().unwrap(),
+                ty: Some(endpoint_manifest::HandlerType::Shared),
+                input: None,
+                output: None,
+                metadata: Default::default(),
+                documentation: None,
+                retry_policy: Some(handler_retry_policy.clone()),
+            });
+
+        let deployment = Deployment::mock();
+        updater
+            .add_deployment(
+                deployment.metadata.clone(),
+                vec![another_greeter_service(), greeter_virtual_object],
+                false,
+            )
+            .unwrap();
+        let schemas = updater.into_inner();
+
+        assert_eq!(
+            schemas
+                .assert_service_handler(ANOTHER_GREETER_SERVICE_NAME, "another_greeter")
+                .retry_policy,
+            None
+        );
+        assert_eq!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greet")
+                .retry_policy,
+            Some(retry_policy_from_schema(&service_retry_policy))
+        );
+        assert_eq!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "getGreetCount")
+                .retry_policy,
+            Some(retry_policy_from_schema(&handler_retry_policy))
+        );
+        // Unset fields are left to the invoker retry policy
+        assert_eq!(
+            retry_policy_from_schema(&handler_retry_policy),
+            InvocationRetryPolicy::Exponential {
+                initial_interval: Some(Duration::from_millis(10).into()),
+                factor: None,
+                max_attempts: NonZeroUsize::new(1),
+                max_interval: None,
+            }
+        );
+
+        // Modifying the service retry policy doesn't affect the handler override
+        let new_retry_policy = RetryPolicy::fixed_delay(Duration::from_secs(1), Some(3));
+        let mut updater = SchemaUpdater::new(schemas);
+        updater
+            .modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::RetryPolicy(new_retry_policy.clone())],
+            )
+            .unwrap();
+        let schemas = updater.into_inner();
+
+        assert_eq!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greet")
+                .retry_policy,
+            Some(InvocationRetryPolicy::Policy(new_retry_policy.clone()))
+        );
+        assert_eq!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "getGreetCount")
+                .retry_policy,
+            Some(retry_policy_from_schema(&handler_retry_policy))
+        );
+        assert_eq!(
+            schemas.assert_service(GREETER_SERVICE_NAME).retry_policy,
+            Some(InvocationRetryPolicy::Policy(new_retry_policy))
+        );
+    }
+
+    #[test]
+    fn cors_credentials_require_listed_origins() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![greeter_service()], false)
+            .unwrap();
+
+        for allowed_origins in [None, Some(vec!["*".to_owned()])] {
+            let_assert!(
+                Err(SchemaError::Service(
+                    ServiceError::CorsCredentialsWithoutOrigins(_)
+                )) = updater.modify_service(
+                    GREETER_SERVICE_NAME.to_owned(),
+                    vec![ModifyServiceChange::Cors(ServiceCorsPolicy {
+                        allowed_origins,
+                        allow_credentials: Some(true),
+                        ..ServiceCorsPolicy::default()
+                    })],
+                )
+            );
+        }
+
+        let cors = ServiceCorsPolicy {
+            allowed_origins: Some(vec!["https://example.com".to_owned()]),
+            allow_credentials: Some(true),
+            ..ServiceCorsPolicy::default()
+        };
+        updater
+            .modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::Cors(cors.clone())],
+            )
+            .unwrap();
+        assert_eq!(
+            updater
+                .into_inner()
+                .assert_service(GREETER_SERVICE_NAME)
+                .cors,
+            Some(cors)
+        );
+    }
+
     /// This test case ensures that https://github.com/restatedev/restate/issues/1205 works
     #[test]
     fn force_deploy_private_service() -> Result<(), SchemaError> {
@@ -1198,6 +1650,7 @@ mod tests {
                         input: None,
                         output: None,
                         metadata: Default::default(),
+                        retry_policy: None,
                     },
                     endpoint_manifest::Handler {
                         documentation: None,
@@ -1206,9 +1659,11 @@ mod tests {
                         input: None,
                         output: None,
                         metadata: Default::default(),
+                        retry_policy: None,
                     },
                 ],
                 metadata: Default::default(),
+                retry_policy: None,
             }
         }
 
@@ -1224,8 +1679,10 @@ mod tests {
                     input: None,
                     output: None,
                     metadata: Default::default(),
+                    retry_policy: None,
                 }],
                 metadata: Default::default(),
+                retry_policy: None,
             }
         }
 
@@ -1517,6 +1974,7 @@ mod tests {
                 input: None,
                 output: None,
                 metadata: Default::default(),
+                retry_policy: None,
             });
 
         updater
@@ -1584,6 +2042,7 @@ mod tests {
                 input: None,
                 output: None,
                 metadata: Default::default(),
+                retry_policy: None,
             });
 
         updater
@@ -1767,4 +2226,307 @@ mod tests {
 
         Ok(())
     }
+
+    fn greeter_descriptor_set() -> String {
+        let descriptor = prost_types::FileDescriptorSet {
+            file: vec![prost_types::FileDescriptorProto {
+                name: Some("greeter.proto".to_owned()),
+                package: Some("greeter".to_owned()),
+                message_type: vec![
+                    prost_types::DescriptorProto {
+                        name: Some("GreetRequest".to_owned()),
+                        ..Default::default()
+                    },
+                    prost_types::DescriptorProto {
+                        name: Some("GreetResponse".to_owned()),
+                        ..Default::default()
+                    },
+                ],
+                service: vec![prost_types::ServiceDescriptorProto {
+                    name: Some("Greeter".to_owned()),
+                    method: vec![prost_types::MethodDescriptorProto {
+                        name: Some("Greet".to_owned()),
+                        input_type: Some(".greeter.GreetRequest".to_owned()),
+                        output_type: Some(".greeter.GreetResponse".to_owned()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        base64::prelude::BASE64_STANDARD.encode(descriptor.encode_to_vec())
+    }
+
+    #[test]
+    fn register_protobuf_service() {
+        let mut updater = SchemaUpdater::default();
+
+        let mut service = greeter_service();
+        service.metadata.insert(
+            PROTOBUF_DESCRIPTOR_SET_METADATA_KEY.to_owned(),
+            greeter_descriptor_set(),
+        );
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![service], false)
+            .unwrap();
+
+        let schemas = updater.into_inner();
+        let protobuf = schemas
+            .resolve_latest_service_protobuf(GREETER_SERVICE_NAME)
+            .unwrap();
+        let (handler, handler_schemas) = protobuf.resolve_method("Greet").unwrap();
+        assert_eq!(handler, "greet");
+        assert_eq!(handler_schemas.input_type, "greeter.GreetRequest");
+        assert_eq!(handler_schemas.output_type, "greeter.GreetResponse");
+    }
+
+    #[test]
+    fn register_protobuf_service_with_bad_descriptor() {
+        let mut updater = SchemaUpdater::default();
+
+        let mut service = another_greeter_service();
+        service.metadata.insert(
+            PROTOBUF_DESCRIPTOR_SET_METADATA_KEY.to_owned(),
+            greeter_descriptor_set(),
+        );
+        let_assert!(
+            Err(SchemaError::Service(ServiceError::BadProtobufDescriptor(
+                _,
+                _
+            ))) = updater.add_deployment(Deployment::mock().metadata, vec![service], false)
+        );
+    }
+
+    #[test]
+    fn validate_input() {
+        let mut updater = SchemaUpdater::default();
+
+        let mut service = greeter_service();
+        let mut other_handler = service.handlers[0].clone();
+        other_handler.name = "greetUnchecked".parse().unwrap();
+        other_handler
+            .metadata
+            .insert(VALIDATE_INPUT_METADATA_KEY.to_owned(), "false".to_owned());
+        service.handlers.push(other_handler);
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![service.clone()], false)
+            .unwrap();
+        updater
+            .modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::ValidateInput(true)],
+            )
+            .unwrap();
+
+        let schemas = updater.into_inner();
+        assert!(schemas.assert_service(GREETER_SERVICE_NAME).validate_input);
+        assert!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greet")
+                .validate_input
+        );
+        assert!(
+            !schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greetUnchecked")
+                .validate_input
+        );
+
+        // the service setting is kept when registering a new revision
+        let mut updater = SchemaUpdater::new(schemas);
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![service.clone()], true)
+            .unwrap();
+        let schemas = updater.into_inner();
+        assert!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greet")
+                .validate_input
+        );
+
+        service.handlers[1]
+            .metadata
+            .insert(VALIDATE_INPUT_METADATA_KEY.to_owned(), "yes".to_owned());
+        let mut updater = SchemaUpdater::new(schemas);
+        let_assert!(
+            Err(SchemaError::Service(
+                ServiceError::BadValidateInputMetadata { .. }
+            )) = updater.add_deployment(Deployment::mock().metadata, vec![service], true)
+        );
+    }
+
+    #[test]
+    fn state_ttl() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(
+                Deployment::mock().metadata,
+                vec![greeter_virtual_object()],
+                false,
+            )
+            .unwrap();
+        updater
+            .modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::StateTtl(Duration::from_secs(60))],
+            )
+            .unwrap();
+        let schemas = updater.into_inner();
+        assert_eq!(
+            schemas.assert_service(GREETER_SERVICE_NAME).state_ttl,
+            Some(Duration::from_secs(60).into())
+        );
+
+        // A zero TTL removes it
+        let mut updater = SchemaUpdater::new(schemas);
+        updater
+            .modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::StateTtl(Duration::ZERO)],
+            )
+            .unwrap();
+        let schemas = updater.into_inner();
+        assert_eq!(schemas.assert_service(GREETER_SERVICE_NAME).state_ttl, None);
+
+        // Services have no state
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![greeter_service()], false)
+            .unwrap();
+        let_assert!(
+            Err(SchemaError::Service(ServiceError::CannotSetStateTtl(
+                ServiceType::Service
+            ))) = updater.modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::StateTtl(Duration::from_secs(60))],
+            )
+        );
+    }
+
+    #[test]
+    fn journal_retention_is_kept_when_registering_a_new_revision() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![greeter_service()], false)
+            .unwrap();
+        updater
+            .modify_service(
+                GREETER_SERVICE_NAME.to_owned(),
+                vec![ModifyServiceChange::JournalRetention(Duration::from_secs(
+                    60,
+                ))],
+            )
+            .unwrap();
+        let schemas = updater.into_inner();
+        assert_eq!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greet")
+                .journal_retention,
+            Some(Duration::from_secs(60))
+        );
+
+        let mut updater = SchemaUpdater::new(schemas);
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![greeter_service()], true)
+            .unwrap();
+        let schemas = updater.into_inner();
+        assert_eq!(
+            schemas
+                .assert_service_handler(GREETER_SERVICE_NAME, "greet")
+                .journal_retention,
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn add_kafka_sink_subscription() {
+        let ingress_options = IngressOptionsBuilder::default()
+            .kafka_clusters(vec![KafkaClusterOptions {
+                name: "my-cluster".to_owned(),
+                brokers: vec![],
+                additional_options: HashMap::default(),
+            }])
+            .build()
+            .unwrap();
+
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(Deployment::mock().metadata, vec![greeter_service()], false)
+            .unwrap();
+
+        let id = updater
+            .add_subscription(
+                None,
+                Uri::from_static("service://greeter.Greeter/greet"),
+                Uri::from_static("kafka://my-cluster/greetings"),
+                Some(HashMap::from([(
+                    KAFKA_SINK_KEY_TEMPLATE_OPTION.to_owned(),
+                    "{invocation_id}".to_owned(),
+                )])),
+                false,
+                &ingress_options,
+            )
+            .unwrap();
+        let schemas = updater.into_inner();
+
+        let subscription = schemas.get_subscription(id).unwrap();
+        assert_eq!(
+            subscription.sink(),
+            &Sink::Kafka {
+                cluster: "my-cluster".to_owned(),
+                topic: "greetings".to_owned(),
+                key_template: Some("{invocation_id}".to_owned()),
+            }
+        );
+        assert!(subscription.metadata().is_empty());
+        assert_eq!(
+            schemas.resolve_kafka_sinks(GREETER_SERVICE_NAME, "greet"),
+            vec![KafkaSink {
+                cluster: "my-cluster".to_owned(),
+                topic: "greetings".to_owned(),
+                key_template: Some("{invocation_id}".to_owned()),
+            }]
+        );
+
+        // Unknown handler
+        let mut updater = SchemaUpdater::new(schemas);
+        let_assert!(
+            Err(SchemaError::Subscription(
+                SubscriptionError::SourceServiceNotFound(_)
+            )) = updater.add_subscription(
+                None,
+                Uri::from_static("service://greeter.Greeter/unknown"),
+                Uri::from_static("kafka://my-cluster/greetings"),
+                None,
+                false,
+                &ingress_options,
+            )
+        );
+
+        // Unknown cluster
+        let_assert!(
+            Err(SchemaError::Subscription(SubscriptionError::Validation(_))) = updater
+                .add_subscription(
+                    None,
+                    Uri::from_static("service://greeter.Greeter/greet"),
+                    Uri::from_static("kafka://other-cluster/greetings"),
+                    None,
+                    false,
+                    &ingress_options,
+                )
+        );
+
+        // Kafka sinks only accept the output of services
+        let_assert!(
+            Err(SchemaError::Subscription(SubscriptionError::Validation(_))) = updater
+                .add_subscription(
+                    None,
+                    Uri::from_static("webhook://github"),
+                    Uri::from_static("kafka://my-cluster/greetings"),
+                    None,
+                    false,
+                    &ingress_options,
+                )
+        );
+    }
 }
@@ -8,6 +8,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::Arc;
+
+use axum::Json;
 use axum::error_handling::HandleErrorLayer;
 use http::StatusCode;
 use restate_admin_rest_model::version::AdminApiVersion;
@@ -18,11 +21,13 @@ use tower::ServiceBuilder;
 
 use restate_core::MetadataWriter;
 use restate_core::network::net_util;
+use restate_service_client::JsonWebKeySet;
 use restate_service_protocol::discovery::ServiceDiscovery;
 use restate_types::net::BindAddress;
 use restate_types::schema::subscriptions::SubscriptionValidator;
 use tracing::info;
 
+use crate::auth::AdminAuth;
 use crate::schema_registry::SchemaRegistry;
 use crate::{rest_api, state};
 
@@ -37,6 +42,7 @@ pub struct AdminService<V> {
     query_context: Option<restate_storage_query_datafusion::context::QueryContext>,
     #[cfg(feature = "metadata-api")]
     metadata_writer: MetadataWriter,
+    request_identity_keys: JsonWebKeySet,
 }
 
 impl<V> AdminService<V>
@@ -60,6 +66,15 @@ where
             ),
             #[cfg(feature = "storage-query")]
             query_context: None,
+            request_identity_keys: JsonWebKeySet::default(),
+        }
+    }
+
+    /// Public keys of the request identity keyring, published on the `/keys` endpoint.
+    pub fn with_request_identity_keys(self, request_identity_keys: JsonWebKeySet) -> Self {
+        Self {
+            request_identity_keys,
+            ..self
         }
     }
 
@@ -80,13 +95,22 @@ where
     ) -> anyhow::Result<()> {
         let opts = updateable_config.live_load();
 
-        let rest_state = state::AdminServiceState::new(self.schema_registry, self.bifrost);
+        #[cfg(feature = "storage-query")]
+        let query_context = self.query_context;
+        #[cfg(not(feature = "storage-query"))]
+        let query_context = None;
+
+        let rest_state = state::AdminServiceState::new(
+            self.schema_registry,
+            self.bifrost.clone(),
+            query_context.clone(),
+        );
 
         let router = axum::Router::new();
 
         #[cfg(feature = "storage-query")]
-        let router = if let Some(query_context) = self.query_context {
-            router.merge(crate::storage_query::router(query_context))
+        let router = if let Some(query_context) = query_context {
+            router.merge(crate::storage_query::router(query_context, self.bifrost))
         } else {
             router
         };
@@ -96,7 +120,24 @@ where
             self.metadata_writer.raw_metadata_store_client(),
         ));
 
-        // Merge Web UI router
+        // Merge meta API router
+        let router = router.merge(rest_api::create_router(rest_state));
+
+        // Publish the request identity public keys, for the service endpoints to verify requests
+        let request_identity_keys = self.request_identity_keys;
+        let router = router.route(
+            "/keys",
+            axum::routing::get(move || std::future::ready(Json(request_identity_keys.clone()))),
+        );
+
+        // Authenticate the requests, before nesting the versioned APIs
+        let router = if let Some(auth_options) = &opts.auth {
+            Arc::new(AdminAuth::new(auth_options.clone())).layer(router)
+        } else {
+            router
+        };
+
+        // Merge Web UI router. The assets are public, the UI authenticates its own API requests.
         #[cfg(feature = "serve-web-ui")]
         let router = if !opts.disable_web_ui {
             router.merge(crate::web_ui::web_ui_router())
@@ -104,9 +145,6 @@ where
             router
         };
 
-        // Merge meta API router
-        let router = router.merge(rest_api::create_router(rest_state));
-
         let router = axum::Router::new()
             .merge(with_unknown_api_version_middleware(router.clone()))
             .nest(
@@ -137,6 +175,7 @@ where
 
         net_util::run_hyper_server(
             &BindAddress::Socket(opts.bind_address),
+            opts.tls.as_ref(),
             service,
             "admin-api-server",
             || (),
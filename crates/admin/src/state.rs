@@ -10,18 +10,26 @@
 
 use crate::schema_registry::SchemaRegistry;
 use restate_bifrost::Bifrost;
+use restate_storage_query_datafusion::context::QueryContext;
 
 #[derive(Clone, derive_builder::Builder)]
 pub struct AdminServiceState<V> {
     pub schema_registry: SchemaRegistry<V>,
     pub bifrost: Bifrost,
+    /// Used to validate the invocation operations before appending them, if available.
+    pub query_context: Option<QueryContext>,
 }
 
 impl<V> AdminServiceState<V> {
-    pub fn new(schema_registry: SchemaRegistry<V>, bifrost: Bifrost) -> Self {
+    pub fn new(
+        schema_registry: SchemaRegistry<V>,
+        bifrost: Bifrost,
+        query_context: Option<QueryContext>,
+    ) -> Self {
         Self {
             schema_registry,
             bifrost,
+            query_context,
         }
     }
 }
@@ -16,6 +16,7 @@ use okapi_operation::anyhow::Error;
 use okapi_operation::okapi::map;
 use okapi_operation::okapi::openapi3::Responses;
 use okapi_operation::{Components, ToMediaTypes, ToResponses, okapi};
+use restate_types::partition_table::PartitionTableError;
 use schemars::JsonSchema;
 use serde::Serialize;
 
@@ -25,6 +26,10 @@ use serde::Serialize;
 pub enum StorageQueryError {
     #[error("datafusion failed: {0}")]
     DataFusion(#[from] DataFusionError),
+    #[error("partition table lookup failed: {0}")]
+    PartitionTable(#[from] PartitionTableError),
+    #[error("invalid where clause: {0}")]
+    InvalidWhereClause(String),
 }
 
 /// # Error description response
@@ -37,7 +42,10 @@ struct ErrorDescriptionResponse {
 
 impl IntoResponse for StorageQueryError {
     fn into_response(self) -> Response {
-        let status_code = StatusCode::INTERNAL_SERVER_ERROR;
+        let status_code = match &self {
+            StorageQueryError::InvalidWhereClause(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
 
         (
             status_code,
@@ -0,0 +1,455 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use super::QueryServiceState;
+use super::error::StorageQueryError;
+use crate::rest_api::create_envelope_header;
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::{Json, http};
+use bytes::Bytes;
+use datafusion::arrow::array::{Array, LargeStringArray};
+use datafusion::arrow::datatypes::Schema;
+use datafusion::sql::sqlparser::ast::{Expr, Ident, Query, Visit, Visitor};
+use datafusion::sql::sqlparser::dialect::PostgreSqlDialect;
+use datafusion::sql::sqlparser::parser::Parser;
+use datafusion::sql::sqlparser::tokenizer::Token;
+use futures::{StreamExt, TryStreamExt, stream};
+use http_body::Frame;
+use http_body_util::StreamBody;
+use okapi_operation::*;
+use restate_admin_rest_model::invocations::{
+    BulkInvocationOperation, BulkInvocationsEvent, BulkInvocationsFilter, BulkInvocationsRequest,
+};
+use restate_bifrost::ErrorRecoveryStrategy;
+use restate_core::Metadata;
+use restate_types::identifiers::{InvocationId, PartitionId, WithPartitionKey};
+use restate_types::invocation::{InvocationTermination, PurgeInvocationRequest};
+use restate_types::logs::LogId;
+use restate_types::partition_table::FindPartition;
+use restate_wal_protocol::{Command, Envelope};
+use tracing::warn;
+
+/// Maximum number of commands appended to a partition log in a single batch.
+const BULK_OPERATION_BATCH_SIZE: usize = 500;
+
+/// Apply an operation to all the invocations matching a filter.
+///
+/// The response is a stream of newline delimited [`BulkInvocationsEvent`], reporting the progress
+/// of the operation as the commands are submitted, in batches, to the partitions.
+#[openapi(
+    summary = "Bulk invocation operation",
+    description = "Cancel, kill or purge all the invocations matching the filter. The response is a stream of newline delimited JSON progress events.",
+    operation_id = "bulk_invocations",
+    tags = "invocation",
+    responses(ignore_return_type = true, from_type = "StorageQueryError")
+)]
+pub async fn bulk_invocations(
+    State(state): State<Arc<QueryServiceState>>,
+    #[request_body(required = true)] Json(payload): Json<BulkInvocationsRequest>,
+) -> Result<impl IntoResponse, StorageQueryError> {
+    let where_clause = match &payload.filter.where_clause {
+        Some(where_clause) => {
+            let sys_invocation = state
+                .query_context
+                .as_ref()
+                .table_provider("sys_invocation")
+                .await?;
+            Some(parse_where_clause(where_clause, &sys_invocation.schema())?)
+        }
+        None => None,
+    };
+    let query = bulk_invocations_query(payload.operation, &payload.filter, where_clause.as_ref());
+
+    let record_batches: Vec<_> = state
+        .query_context
+        .execute(&query)
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut invocation_ids = Vec::new();
+    for record_batch in record_batches {
+        let Some(ids) = record_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+        else {
+            continue;
+        };
+        for id in ids.iter().flatten() {
+            match id.parse::<InvocationId>() {
+                Ok(invocation_id) => invocation_ids.push(invocation_id),
+                Err(err) => warn!("Skipping invalid invocation id '{id}': {err}"),
+            }
+        }
+    }
+
+    let matched = stream::once(async move {
+        BulkInvocationsEvent::Matched {
+            count: invocation_ids.len(),
+        }
+    });
+    let events = if payload.dry_run {
+        matched.left_stream()
+    } else {
+        let batches = partition_batches(payload.operation, invocation_ids)?;
+        let bifrost = state.bifrost.clone();
+        matched
+            .chain(stream::iter(batches).then(move |(partition_id, batch)| {
+                let bifrost = bifrost.clone();
+                async move {
+                    let count = batch.len();
+                    match bifrost
+                        .append_batch(
+                            LogId::from(*partition_id),
+                            ErrorRecoveryStrategy::default(),
+                            batch,
+                        )
+                        .await
+                    {
+                        Ok(_) => BulkInvocationsEvent::Submitted {
+                            partition_id: *partition_id,
+                            count,
+                        },
+                        Err(err) => {
+                            warn!(
+                                "Could not append bulk invocation commands to partition {partition_id}: {err}"
+                            );
+                            BulkInvocationsEvent::Failed {
+                                partition_id: *partition_id,
+                                count,
+                                reason: err.to_string(),
+                            }
+                        }
+                    }
+                }
+            }))
+            .right_stream()
+    };
+
+    let body = events.map(|event| {
+        let mut line = serde_json::to_vec(&event).expect("event must be serializable");
+        line.push(b'\n');
+        Ok::<_, Infallible>(Frame::data(Bytes::from(line)))
+    });
+
+    Ok(Response::builder()
+        .header(http::header::CONTENT_TYPE, "application/x-ndjson")
+        .body(StreamBody::new(body))
+        .expect("content-type header is correct"))
+}
+
+/// Builds the query selecting the ids of the invocations the operation applies to. The filter
+/// values are always rendered as escaped literals, and the where clause is rendered from its
+/// validated expression, never from the raw request.
+fn bulk_invocations_query(
+    operation: BulkInvocationOperation,
+    filter: &BulkInvocationsFilter,
+    where_clause: Option<&Expr>,
+) -> String {
+    let mut predicates = vec![match operation {
+        BulkInvocationOperation::Cancel | BulkInvocationOperation::Kill => {
+            "status != 'completed'".to_owned()
+        }
+        BulkInvocationOperation::Purge => "status = 'completed'".to_owned(),
+    }];
+
+    if let Some(service) = &filter.service {
+        predicates.push(format!("target_service_name = {}", sql_literal(service)));
+    }
+    if let Some(handler) = &filter.handler {
+        predicates.push(format!("target_handler_name = {}", sql_literal(handler)));
+    }
+    if !filter.status.is_empty() {
+        let statuses: Vec<_> = filter
+            .status
+            .iter()
+            .map(|status| sql_literal(status.into()))
+            .collect();
+        predicates.push(format!("status IN ({})", statuses.join(", ")));
+    }
+    for (column, operator, timestamp) in [
+        ("created_at", ">=", &filter.created_after),
+        ("created_at", "<", &filter.created_before),
+        ("modified_at", ">=", &filter.modified_after),
+        ("modified_at", "<", &filter.modified_before),
+    ] {
+        if let Some(timestamp) = timestamp {
+            predicates.push(format!(
+                "{column} {operator} {}",
+                sql_literal(&timestamp.to_string())
+            ));
+        }
+    }
+
+    if let Some(where_clause) = where_clause {
+        predicates.push(format!("({where_clause})"));
+    }
+
+    format!(
+        "SELECT id FROM sys_invocation WHERE {}",
+        predicates.join(" AND ")
+    )
+}
+
+/// Parses the where clause of a bulk operation as a single SQL expression, which can only
+/// reference the columns of the `sys_invocation` table, and can't contain subqueries.
+fn parse_where_clause(
+    where_clause: &str,
+    sys_invocation: &Schema,
+) -> Result<Expr, StorageQueryError> {
+    let expr = Parser::new(&PostgreSqlDialect {})
+        .try_with_sql(where_clause)
+        .and_then(|mut parser| {
+            let expr = parser.parse_expr()?;
+            parser.expect_token(&Token::EOF)?;
+            Ok(expr)
+        })
+        .map_err(|err| StorageQueryError::InvalidWhereClause(err.to_string()))?;
+
+    if let ControlFlow::Break(reason) = expr.visit(&mut WhereClauseValidator { sys_invocation }) {
+        return Err(StorageQueryError::InvalidWhereClause(reason));
+    }
+
+    Ok(expr)
+}
+
+struct WhereClauseValidator<'a> {
+    sys_invocation: &'a Schema,
+}
+
+impl Visitor for WhereClauseValidator<'_> {
+    type Break = String;
+
+    fn pre_visit_query(&mut self, _query: &Query) -> ControlFlow<Self::Break> {
+        ControlFlow::Break("subqueries are not allowed".to_owned())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        let column = match expr {
+            Expr::Identifier(column) => column,
+            Expr::CompoundIdentifier(idents) => match idents.as_slice() {
+                [table, column] if normalize(table) == "sys_invocation" => column,
+                _ => {
+                    return ControlFlow::Break(format!(
+                        "'{}' is not a column of sys_invocation",
+                        Expr::CompoundIdentifier(idents.clone())
+                    ));
+                }
+            },
+            _ => return ControlFlow::Continue(()),
+        };
+
+        if self
+            .sys_invocation
+            .field_with_name(&normalize(column))
+            .is_err()
+        {
+            return ControlFlow::Break(format!("'{column}' is not a column of sys_invocation"));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Unquoted identifiers are case-insensitive, as in DataFusion.
+fn normalize(ident: &Ident) -> String {
+    if ident.quote_style.is_some() {
+        ident.value.clone()
+    } else {
+        ident.value.to_lowercase()
+    }
+}
+
+/// Renders a SQL string literal, escaping the quotes.
+fn sql_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Groups the commands by partition, and splits them in batches of at most [`BULK_OPERATION_BATCH_SIZE`].
+fn partition_batches(
+    operation: BulkInvocationOperation,
+    invocation_ids: Vec<InvocationId>,
+) -> Result<Vec<(PartitionId, Vec<Arc<Envelope>>)>, StorageQueryError> {
+    let partition_table = Metadata::with_current(|m| m.partition_table_snapshot());
+
+    let mut commands_by_partition: BTreeMap<PartitionId, Vec<Arc<Envelope>>> = BTreeMap::new();
+    for invocation_id in invocation_ids {
+        let partition_key = invocation_id.partition_key();
+        let partition_id = partition_table.find_partition_id(partition_key)?;
+        let cmd = match operation {
+            BulkInvocationOperation::Cancel => {
+                Command::TerminateInvocation(InvocationTermination::cancel(invocation_id))
+            }
+            BulkInvocationOperation::Kill => {
+                Command::TerminateInvocation(InvocationTermination::kill(invocation_id))
+            }
+            BulkInvocationOperation::Purge => {
+                Command::PurgeInvocation(PurgeInvocationRequest { invocation_id })
+            }
+        };
+        commands_by_partition
+            .entry(partition_id)
+            .or_default()
+            .push(Arc::new(Envelope::new(
+                create_envelope_header(partition_key),
+                cmd,
+            )));
+    }
+
+    Ok(commands_by_partition
+        .into_iter()
+        .flat_map(|(partition_id, mut commands)| {
+            let mut batches =
+                Vec::with_capacity(commands.len().div_ceil(BULK_OPERATION_BATCH_SIZE));
+            while !commands.is_empty() {
+                let rest = commands.split_off(commands.len().min(BULK_OPERATION_BATCH_SIZE));
+                batches.push((partition_id, commands));
+                commands = rest;
+            }
+            batches
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    use restate_admin_rest_model::invocations::InvocationStatusFilter;
+
+    #[test]
+    fn status_guard_depends_on_operation() {
+        let filter = BulkInvocationsFilter::default();
+
+        assert_eq!(
+            "SELECT id FROM sys_invocation WHERE status != 'completed'",
+            bulk_invocations_query(BulkInvocationOperation::Kill, &filter, None)
+        );
+        assert_eq!(
+            "SELECT id FROM sys_invocation WHERE status = 'completed'",
+            bulk_invocations_query(BulkInvocationOperation::Purge, &filter, None)
+        );
+    }
+
+    #[test]
+    fn filter_conditions_are_combined() {
+        let created_after = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let filter = BulkInvocationsFilter {
+            service: Some("Greeter".to_owned()),
+            handler: Some("greet".to_owned()),
+            status: vec![
+                InvocationStatusFilter::BackingOff,
+                InvocationStatusFilter::Suspended,
+            ],
+            created_after: Some(created_after.into()),
+            ..BulkInvocationsFilter::default()
+        };
+
+        assert_eq!(
+            "SELECT id FROM sys_invocation WHERE status != 'completed' \
+            AND target_service_name = 'Greeter' \
+            AND target_handler_name = 'greet' \
+            AND status IN ('backing-off', 'suspended') \
+            AND created_at >= '2023-11-14T22:13:20Z'",
+            bulk_invocations_query(BulkInvocationOperation::Cancel, &filter, None)
+        );
+    }
+
+    #[test]
+    fn filter_values_cannot_escape_literals() {
+        let filter = BulkInvocationsFilter {
+            service: Some("Greeter') OR (1=1".to_owned()),
+            handler: Some("x' OR 'a'='a".to_owned()),
+            ..BulkInvocationsFilter::default()
+        };
+
+        assert_eq!(
+            "SELECT id FROM sys_invocation WHERE status = 'completed' \
+            AND target_service_name = 'Greeter'') OR (1=1' \
+            AND target_handler_name = 'x'' OR ''a''=''a'",
+            bulk_invocations_query(BulkInvocationOperation::Purge, &filter, None)
+        );
+    }
+
+    fn sys_invocation() -> Schema {
+        use datafusion::arrow::datatypes::{DataType, Field};
+
+        Schema::new(vec![
+            Field::new("id", DataType::LargeUtf8, false),
+            Field::new("status", DataType::LargeUtf8, false),
+            Field::new("target_service_name", DataType::LargeUtf8, false),
+            Field::new("retry_count", DataType::UInt64, true),
+        ])
+    }
+
+    #[test]
+    fn where_clause_is_combined_with_the_status_guard() {
+        let where_clause = parse_where_clause(
+            "target_service_name = 'Greeter' OR RETRY_COUNT > 10",
+            &sys_invocation(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "SELECT id FROM sys_invocation WHERE status != 'completed' \
+            AND (target_service_name = 'Greeter' OR RETRY_COUNT > 10)",
+            bulk_invocations_query(
+                BulkInvocationOperation::Cancel,
+                &BulkInvocationsFilter::default(),
+                Some(&where_clause)
+            )
+        );
+    }
+
+    #[test]
+    fn where_clause_is_validated() {
+        for where_clause in [
+            "1=1) OR (1=1",
+            "retry_count > 10; DROP TABLE sys_invocation",
+            "unknown_column LIKE '%timeout%'",
+            "sys_journal.id = 'inv_1'",
+            "id IN (SELECT invocation_id FROM sys_journal)",
+            "EXISTS (SELECT 1)",
+        ] {
+            assert!(
+                matches!(
+                    parse_where_clause(where_clause, &sys_invocation()),
+                    Err(StorageQueryError::InvalidWhereClause(_))
+                ),
+                "where clause '{where_clause}' should be rejected"
+            );
+        }
+
+        assert!(parse_where_clause("sys_invocation.status = 'paused'", &sys_invocation()).is_ok());
+    }
+
+    #[test]
+    fn bulk_invocations_request_rejects_sql_filters() {
+        let request = serde_json::from_str::<BulkInvocationsRequest>(
+            r#"{"filter": "1=1) OR (1=1", "operation": "purge"}"#,
+        );
+        assert!(request.is_err());
+
+        let request = serde_json::from_str::<BulkInvocationsRequest>(
+            r#"{"filter": {"service": "Greeter", "status": ["running"]}, "operation": "kill"}"#,
+        )
+        .unwrap();
+        assert_eq!(Some("Greeter"), request.filter.service.as_deref());
+        assert_eq!(vec![InvocationStatusFilter::Running], request.filter.status);
+    }
+}
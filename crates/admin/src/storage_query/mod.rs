@@ -10,23 +10,30 @@
 
 mod convert;
 mod error;
+mod invocations;
 mod query;
 
 use axum::{Router, routing::post};
 use std::sync::Arc;
 
+use restate_bifrost::Bifrost;
 use restate_storage_query_datafusion::context::QueryContext;
 
 #[derive(Clone)]
 pub struct QueryServiceState {
     pub query_context: QueryContext,
+    pub bifrost: Bifrost,
 }
 
-pub fn router(query_context: QueryContext) -> Router {
-    let query_state = Arc::new(QueryServiceState { query_context });
+pub fn router(query_context: QueryContext, bifrost: Bifrost) -> Router {
+    let query_state = Arc::new(QueryServiceState {
+        query_context,
+        bifrost,
+    });
 
     // Setup the router
     axum::Router::new()
         .route("/query", post(query::query))
+        .route("/invocations/bulk", post(invocations::bulk_invocations))
         .with_state(query_state)
 }
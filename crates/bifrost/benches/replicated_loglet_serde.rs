@@ -109,10 +109,12 @@ pub fn generate_envelope() -> Arc<Envelope> {
         )],
         execution_time: Some(MillisSinceEpoch::after(Duration::from_secs(10))),
         completion_retention_duration: Some(Duration::from_secs(10)),
+        journal_retention_duration: None,
         idempotency_key: Some(idempotency_key),
         response_sink: Some(
             restate_types::invocation::ServiceInvocationResponseSink::Ingress { request_id },
         ),
+        dead_letter_queue: false,
         submit_notification_sink: Some(
             restate_types::invocation::SubmitNotificationSink::Ingress { request_id },
         ),
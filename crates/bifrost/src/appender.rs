@@ -23,6 +23,7 @@ use restate_types::logs::{LogId, Lsn, Record};
 use restate_types::storage::StorageEncode;
 
 use crate::bifrost::{BifrostInner, ErrorRecoveryStrategy};
+use crate::error::AdminError;
 use crate::loglet::AppendError;
 use crate::loglet_wrapper::LogletWrapper;
 use crate::{BifrostAdmin, Error, InputRecord, Result};
@@ -192,6 +193,14 @@ impl Appender {
             let loglet = bifrost_inner
                 .writeable_loglet_from_metadata(log_metadata, log_id)
                 .await?;
+            // A permanently sealed log will never get a new segment
+            if log_metadata
+                .chain(&log_id)
+                .is_some_and(|chain| chain.sealed_tail_lsn().is_some())
+            {
+                return Err(AdminError::ChainPermanentlySealed(log_id).into());
+            }
+
             let tone_escalated = start.elapsed() > auto_recovery_threshold;
             // Do we think that the last tail loglet is different and unsealed?
             if loglet.tail_lsn.is_none() && loglet.segment_index() > sealed_segment {
@@ -14,11 +14,11 @@ use tracing::{debug, instrument, warn};
 
 use restate_core::metadata_store::retry_on_retryable_error;
 use restate_core::{Metadata, MetadataKind};
-use restate_types::Version;
 use restate_types::config::Configuration;
 use restate_types::logs::metadata::{Chain, LogletParams, Logs, ProviderKind, SegmentIndex};
 use restate_types::logs::{LogId, Lsn, TailState};
 use restate_types::metadata_store::keys::BIFROST_CONFIG_KEY;
+use restate_types::{Version, Versioned};
 
 use crate::bifrost::BifrostInner;
 use crate::error::AdminError;
@@ -155,6 +155,103 @@ impl<'a> BifrostAdmin<'a> {
         Ok(sealed_segment)
     }
 
+    /// Permanently seals the log. The tail loglet is sealed and no segments can be appended to
+    /// the chain afterwards, so the log never accepts new records. Returns the tail LSN of
+    /// the sealed log.
+    ///
+    /// Sealing a log that is already permanently sealed returns its tail LSN.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn seal_chain(&self, log_id: LogId) -> Result<Lsn> {
+        self.inner.fail_if_shutting_down()?;
+        loop {
+            let logs = Metadata::with_current(|m| m.logs_snapshot());
+            let chain = logs.chain(&log_id).ok_or(Error::UnknownLogId(log_id))?;
+            if let Some(tail_lsn) = chain.sealed_tail_lsn() {
+                return Ok(tail_lsn);
+            }
+            let segment_index = chain.tail_index();
+
+            let sealed_segment = loop {
+                let sealed_segment = self.seal(log_id, segment_index).await?;
+                if sealed_segment.tail.is_sealed() {
+                    break sealed_segment;
+                }
+                debug!(%log_id, %segment_index, "Segment is not sealed yet");
+                tokio::time::sleep(Configuration::pinned().bifrost.seal_retry_interval.into())
+                    .await;
+            };
+            let tail_lsn = sealed_segment.tail.offset();
+
+            let result = self
+                .inner
+                .metadata_writer
+                .global_metadata()
+                .read_modify_write(|logs: Option<Arc<Logs>>| {
+                    let logs = logs.ok_or(Error::UnknownLogId(log_id))?;
+
+                    let mut builder = logs.as_ref().clone().into_builder();
+                    let mut chain_builder =
+                        builder.chain(log_id).ok_or(Error::UnknownLogId(log_id))?;
+
+                    if chain_builder.tail().index() != segment_index {
+                        return Err(Error::from(AdminError::SegmentMismatch {
+                            expected: segment_index,
+                            found: chain_builder.tail().index(),
+                        }));
+                    }
+
+                    chain_builder.seal(tail_lsn).map_err(AdminError::from)?;
+                    Ok(builder.build())
+                })
+                .await
+                .map_err(|e| e.transpose());
+
+            match result {
+                Ok(_) => return Ok(tail_lsn),
+                Err(Error::AdminError(AdminError::SegmentMismatch { .. })) => {
+                    // the sealed segment got replaced by a new one in the meantime, seal again
+                    debug!(%log_id, %segment_index, "Chain was extended concurrently");
+                    let _ = Metadata::current()
+                        .wait_for_version(MetadataKind::Logs, logs.version().next())
+                        .await?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Reopens a log permanently sealed by [`Self::seal_chain`]. Its tail segment stays sealed
+    /// until a new segment is appended to the chain.
+    ///
+    /// Reopening a log that is not permanently sealed does nothing.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn reopen_chain(&self, log_id: LogId) -> Result<()> {
+        self.inner.fail_if_shutting_down()?;
+        let logs = Metadata::with_current(|m| m.logs_snapshot());
+        let chain = logs.chain(&log_id).ok_or(Error::UnknownLogId(log_id))?;
+        if chain.sealed_tail_lsn().is_none() {
+            return Ok(());
+        }
+
+        self.inner
+            .metadata_writer
+            .global_metadata()
+            .read_modify_write(|logs: Option<Arc<Logs>>| {
+                let logs = logs.ok_or(Error::UnknownLogId(log_id))?;
+
+                let mut builder = logs.as_ref().clone().into_builder();
+                builder
+                    .chain(log_id)
+                    .ok_or(Error::UnknownLogId(log_id))?
+                    .reopen();
+                Ok(builder.build())
+            })
+            .await
+            .map_err(|e| e.transpose())?;
+
+        Ok(())
+    }
+
     pub async fn writeable_loglet(&self, log_id: LogId) -> Result<LogletWrapper> {
         self.inner.writeable_loglet(log_id).await
     }
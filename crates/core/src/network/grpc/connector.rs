@@ -26,6 +26,7 @@ use super::MAX_MESSAGE_SIZE;
 use crate::network::grpc::DEFAULT_GRPC_COMPRESSION;
 use crate::network::protobuf::core_node_svc::core_node_svc_client::CoreNodeSvcClient;
 use crate::network::protobuf::network::Message;
+use crate::network::tls::{TlsError, configure_client_tls};
 use crate::network::transport_connector::find_node;
 use crate::network::{ConnectError, Destination, Swimlane, TransportConnect};
 use crate::{Metadata, TaskCenter, TaskKind};
@@ -52,7 +53,8 @@ impl TransportConnect for GrpcConnector {
         };
 
         debug!("Connecting to {} at {}", destination, address);
-        let channel = create_channel(address, swimlane, &Configuration::pinned().networking);
+        let channel = create_channel(address, swimlane, &Configuration::pinned().networking)
+            .map_err(|err| ConnectError::Transport(err.to_string()))?;
 
         // Establish the connection
         let mut client = CoreNodeSvcClient::new(channel)
@@ -71,7 +73,7 @@ fn create_channel(
     address: AdvertisedAddress,
     _swimlane: Swimlane,
     options: &NetworkingOptions,
-) -> Channel {
+) -> Result<Channel, TlsError> {
     let endpoint = match &address {
         AdvertisedAddress::Uds(_) => {
             // dummy endpoint required to specify an uds connector, it is not used anywhere
@@ -95,8 +97,9 @@ fn create_channel(
         .keep_alive_while_idle(true)
         // this true by default, but this is to guard against any change in defaults
         .tcp_nodelay(true);
+    let endpoint = configure_client_tls(endpoint, &address, options.tls.as_ref())?;
 
-    match address {
+    Ok(match address {
         AdvertisedAddress::Uds(uds_path) => {
             endpoint.connect_with_connector_lazy(tower::service_fn(move |_: Uri| {
                 let uds_path = uds_path.clone();
@@ -105,8 +108,8 @@ fn create_channel(
                 }
             }))
         }
-        AdvertisedAddress::Http(_) => endpoint.connect_lazy()
-    }
+        AdvertisedAddress::Http(_) => endpoint.connect_lazy(),
+    })
 }
 
 #[derive(Clone, Default)]
@@ -21,14 +21,16 @@ use hyper_util::rt::TokioIo;
 use hyper_util::server::graceful::GracefulShutdown;
 use tokio::io;
 use tokio::net::{TcpListener, UnixListener, UnixStream};
+use tokio::sync::mpsc;
 use tokio_util::net::Listener;
 use tonic::transport::{Channel, Endpoint};
 use tracing::{Instrument, Span, debug, error_span, info, instrument, trace};
 
-use restate_types::config::{Configuration, MetadataClientOptions, NetworkingOptions};
+use restate_types::config::{Configuration, MetadataClientOptions, NetworkingOptions, TlsOptions};
 use restate_types::errors::GenericError;
 use restate_types::net::{AdvertisedAddress, BindAddress};
 
+use super::tls::{ReloadingTlsAcceptor, TlsError, configure_client_tls};
 use crate::{ShutdownError, TaskCenter, TaskKind, cancellation_watcher};
 
 pub fn create_tonic_channel<T: CommonClientConnectionOptions + Send + Sync + ?Sized>(
@@ -43,7 +45,23 @@ pub fn create_tonic_channel<T: CommonClientConnectionOptions + Send + Sync + ?Si
         AdvertisedAddress::Http(uri) => Channel::builder(uri.clone()),
     };
 
-    let endpoint = apply_options(endpoint, options);
+    let endpoint = match configure_client_tls(
+        apply_options(endpoint, options),
+        &address,
+        Configuration::pinned().networking.tls.as_ref(),
+    ) {
+        Ok(endpoint) => endpoint,
+        Err(err) => {
+            // Fail every connection attempt rather than connecting without the configured TLS
+            let err = err.to_string();
+            return Endpoint::try_from("http://127.0.0.1")
+                .expect("/ should be a valid Uri")
+                .connect_with_connector_lazy(tower::service_fn(move |_: Uri| {
+                    let err = err.clone();
+                    async move { Err::<TokioIo<UnixStream>, _>(io::Error::other(err)) }
+                }));
+        }
+    };
 
     match address {
         AdvertisedAddress::Uds(uds_path) => {
@@ -75,6 +93,12 @@ fn apply_options<T: CommonClientConnectionOptions + Send + Sync + ?Sized>(
     .tcp_nodelay(true)
 }
 
+/// Subject distinguished name of the verified client certificate in the RFC 4514 string form, that
+/// is starting from the most specific attribute, for example `CN=node-1,O=Example,C=DE`. Servers
+/// accepting client certificates add it to the extensions of the requests of the connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerCertificateSubject(pub String);
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("failed binding to address '{address}': {source}")]
@@ -93,6 +117,8 @@ pub enum Error {
     HandlingConnection(#[from] GenericError),
     #[error("failed listening on incoming connections: {0}")]
     Listening(#[from] io::Error),
+    #[error("failed configuring TLS: {0}")]
+    Tls(#[from] TlsError),
     #[error(transparent)]
     Shutdown(#[from] ShutdownError),
 }
@@ -105,6 +131,7 @@ pub enum Error {
 )]
 pub async fn run_hyper_server<S, B>(
     bind_address: &BindAddress,
+    tls: Option<&TlsOptions>,
     service: S,
     server_name: &'static str,
     on_bind: impl Fn(),
@@ -121,6 +148,10 @@ where
     B::Data: Send,
     B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
 {
+    let tls_acceptor = tls
+        .map(|tls| ReloadingTlsAcceptor::new(tls.clone()).map(Arc::new))
+        .transpose()?;
+
     match bind_address {
         BindAddress::Uds(uds_path) => {
             if uds_path.exists() {
@@ -136,7 +167,7 @@ where
             info!("Server listening");
             on_bind();
 
-            run_listener_loop(unix_listener, service, server_name).await?;
+            run_listener_loop(unix_listener, tls_acceptor, service, server_name).await?;
         }
         BindAddress::Socket(socket_addr) => {
             let tcp_listener =
@@ -157,7 +188,7 @@ where
             info!("Server listening");
             on_bind();
 
-            run_listener_loop(tcp_listener, service, server_name).await?;
+            run_listener_loop(tcp_listener, tls_acceptor, service, server_name).await?;
         }
     }
     on_stop();
@@ -169,6 +200,7 @@ where
 
 async fn run_listener_loop<L, S, B>(
     mut listener: L,
+    tls_acceptor: Option<Arc<ReloadingTlsAcceptor>>,
     service: S,
     server_name: &'static str,
 ) -> Result<(), Error>
@@ -190,8 +222,12 @@ where
     let mut shutdown = std::pin::pin!(cancellation_watcher());
     let graceful_shutdown = GracefulShutdown::new();
     let task_name: Arc<str> = Arc::from(format!("{server_name}-socket"));
+    // TLS handshakes run in their own tasks, to not block accepting new connections. The
+    // established connections are sent back to be watched by the graceful shutdown.
+    let (tls_connections_tx, mut tls_connections_rx) = mpsc::unbounded_channel();
 
     loop {
+        let network_options = &configuration.live_load().networking;
         tokio::select! {
             biased;
             _ = &mut shutdown => {
@@ -199,41 +235,43 @@ where
                 drop(listener);
                 break;
             }
+            Some((stream, peer_addr, subject)) = tls_connections_rx.recv() => {
+                serve_connection(
+                    TokioIo::new(stream),
+                    peer_addr,
+                    WithPeerCertificate { inner: service.clone(), subject },
+                    network_options,
+                    &graceful_shutdown,
+                    task_name.clone(),
+                )?;
+            }
             incoming_connection = listener.accept() => {
                 let (stream, peer_addr) = incoming_connection?;
-                let io = TokioIo::new(stream);
-
-                let network_options = &configuration.live_load().networking;
-                let mut builder = hyper_util::server::conn::auto::Builder::new(TaskCenterExecutor);
-                builder
-                    .http2()
-                    .timer(hyper_util::rt::TokioTimer::default())
-                    .adaptive_window(network_options.http2_adaptive_window)
-                    .initial_connection_window_size(network_options.connection_window_size())
-                    .initial_stream_window_size(network_options.stream_window_size())
-                    .keep_alive_interval(Some(network_options.http2_keep_alive_interval.into()))
-                    .keep_alive_timeout(network_options.http2_keep_alive_timeout.into());
-
-
-                let socket_span = error_span!("SocketHandler", ?peer_addr);
-                let connection = graceful_shutdown.watch(builder
-                    .serve_connection(io, service.clone()).into_owned());
 
+                let Some(tls_acceptor) = &tls_acceptor else {
+                    serve_connection(
+                        TokioIo::new(stream),
+                        peer_addr,
+                        service.clone(),
+                        network_options,
+                        &graceful_shutdown,
+                        task_name.clone(),
+                    )?;
+                    continue;
+                };
+
+                let tls_acceptor = Arc::clone(tls_acceptor);
+                let tls_connections_tx = tls_connections_tx.clone();
                 TaskCenter::spawn(TaskKind::SocketHandler, task_name.clone(), async move {
-                    trace!("New connection accepted");
-                    if let Err(e) = connection.await {
-                        if let Some(hyper_error) = e.downcast_ref::<hyper::Error>() {
-                            if hyper_error.is_incomplete_message() {
-                                debug!("Connection closed before request completed");
-                            }
-                        } else {
-                            debug!("Connection terminated due to error: {e}");
+                    match tls_acceptor.accept(stream).await {
+                        Ok((stream, subject)) => {
+                            // the receiver is only gone on shutdown
+                            let _ = tls_connections_tx.send((stream, peer_addr, subject));
                         }
-                    } else {
-                        trace!("Connection completed cleanly");
+                        Err(err) => debug!(?peer_addr, "TLS handshake failed: {err}"),
                     }
                     Ok(())
-                }.instrument(socket_span))?;
+                })?;
             }
         }
     }
@@ -252,6 +290,89 @@ where
     Ok(())
 }
 
+fn serve_connection<I, A, S, B>(
+    io: TokioIo<I>,
+    peer_addr: A,
+    service: S,
+    network_options: &NetworkingOptions,
+    graceful_shutdown: &GracefulShutdown,
+    task_name: Arc<str>,
+) -> Result<(), ShutdownError>
+where
+    I: io::AsyncRead + io::AsyncWrite + Send + Unpin + 'static,
+    A: Debug,
+    S: hyper::service::Service<http::Request<Incoming>, Response = hyper::Response<B>>
+        + Send
+        + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    S::Future: Send,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let mut builder = hyper_util::server::conn::auto::Builder::new(TaskCenterExecutor);
+    builder
+        .http2()
+        .timer(hyper_util::rt::TokioTimer::default())
+        .adaptive_window(network_options.http2_adaptive_window)
+        .initial_connection_window_size(network_options.connection_window_size())
+        .initial_stream_window_size(network_options.stream_window_size())
+        .keep_alive_interval(Some(network_options.http2_keep_alive_interval.into()))
+        .keep_alive_timeout(network_options.http2_keep_alive_timeout.into());
+
+    let socket_span = error_span!("SocketHandler", ?peer_addr);
+    let connection = graceful_shutdown.watch(builder.serve_connection(io, service).into_owned());
+
+    TaskCenter::spawn(
+        TaskKind::SocketHandler,
+        task_name,
+        async move {
+            trace!("New connection accepted");
+            if let Err(e) = connection.await {
+                if let Some(hyper_error) = e.downcast_ref::<hyper::Error>() {
+                    if hyper_error.is_incomplete_message() {
+                        debug!("Connection closed before request completed");
+                    }
+                } else {
+                    debug!("Connection terminated due to error: {e}");
+                }
+            } else {
+                trace!("Connection completed cleanly");
+            }
+            Ok(())
+        }
+        .instrument(socket_span),
+    )?;
+
+    Ok(())
+}
+
+/// Adds the subject of the client certificate of the connection to the request extensions.
+#[derive(Clone)]
+struct WithPeerCertificate<S> {
+    inner: S,
+    subject: Option<PeerCertificateSubject>,
+}
+
+impl<S, B> hyper::service::Service<http::Request<B>> for WithPeerCertificate<S>
+where
+    S: hyper::service::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, mut req: http::Request<B>) -> Self::Future {
+        if let Some(subject) = &self.subject {
+            req.extensions_mut().insert(subject.clone());
+        } else {
+            // never trust an extension that the connection didn't set
+            req.extensions_mut().remove::<PeerCertificateSubject>();
+        }
+        self.inner.call(req)
+    }
+}
+
 #[derive(Clone, Default)]
 struct TaskCenterExecutor;
 
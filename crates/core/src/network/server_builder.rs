@@ -19,6 +19,7 @@ use tower::ServiceExt;
 use tower_http::trace::{DefaultOnFailure, TraceLayer};
 use tracing::{Level, debug};
 
+use restate_types::config::Configuration;
 use restate_types::health::HealthStatus;
 use restate_types::net::BindAddress;
 use restate_types::protobuf::common::NodeRpcStatus;
@@ -105,6 +106,7 @@ impl NetworkServerBuilder {
 
         run_hyper_server(
             bind_address,
+            Configuration::pinned().networking.tls.as_ref(),
             service,
             "node-rpc-server",
             || node_rpc_health.update(NodeRpcStatus::Ready),
@@ -0,0 +1,560 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! TLS termination of the servers and TLS configuration of the node-to-node clients.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use http::uri::Scheme;
+use parking_lot::Mutex;
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use tokio::io::{self, AsyncRead, AsyncWrite};
+use tokio::time::Instant;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use tracing::{info, warn};
+use x509_parser::der_parser::asn1_rs::{Any, ToDer};
+use x509_parser::objects::{oid_registry, oid2abbrev};
+use x509_parser::x509::X509Name;
+
+use restate_types::config::TlsOptions;
+use restate_types::net::AdvertisedAddress;
+
+use super::net_util::PeerCertificateSubject;
+
+/// How often the certificate files are checked for changes.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// Maximum duration of the TLS handshake of the incoming connections.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("failed reading '{}': {source}", path.display())]
+    Pem {
+        path: PathBuf,
+        #[source]
+        source: rustls::pki_types::pem::Error,
+    },
+    #[error("failed reading '{}': {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("no certificate found in '{}'", .0.display())]
+    NoCertificate(PathBuf),
+    #[error("invalid TLS configuration: {0}")]
+    Rustls(#[from] rustls::Error),
+    #[error("invalid client certificate verification: {0}")]
+    ClientVerifier(#[from] rustls::server::VerifierBuilderError),
+    #[error("invalid client TLS configuration: {0}")]
+    Client(#[from] tonic::transport::Error),
+}
+
+/// TLS acceptor of a server, reloading the certificate files when they change.
+pub struct ReloadingTlsAcceptor {
+    options: TlsOptions,
+    state: Mutex<AcceptorState>,
+}
+
+struct AcceptorState {
+    acceptor: TlsAcceptor,
+    modified: Vec<Option<SystemTime>>,
+    next_check: Instant,
+}
+
+impl ReloadingTlsAcceptor {
+    /// Loads the certificate files, failing if they are invalid.
+    pub fn new(options: TlsOptions) -> Result<Self, TlsError> {
+        let modified = modified_times(&options);
+        let acceptor = TlsAcceptor::from(Arc::new(server_config(&options)?));
+
+        Ok(Self {
+            state: Mutex::new(AcceptorState {
+                acceptor,
+                modified,
+                next_check: Instant::now() + RELOAD_CHECK_INTERVAL,
+            }),
+            options,
+        })
+    }
+
+    /// Performs the TLS handshake of an incoming connection, returning the TLS stream and the
+    /// subject of the verified client certificate, if the client presented one.
+    pub async fn accept<IO>(
+        &self,
+        stream: IO,
+    ) -> io::Result<(TlsStream<IO>, Option<PeerCertificateSubject>)>
+    where
+        IO: AsyncRead + AsyncWrite + Unpin,
+    {
+        let acceptor = self.acceptor();
+        let stream = tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))??;
+
+        let subject = stream
+            .get_ref()
+            .1
+            .peer_certificates()
+            .and_then(|certificates| certificates.first())
+            .and_then(|certificate| {
+                x509_parser::parse_x509_certificate(certificate.as_ref())
+                    .ok()
+                    .map(|(_, certificate)| {
+                        PeerCertificateSubject(rfc4514_name(certificate.subject()))
+                    })
+            });
+
+        Ok((stream, subject))
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        if now >= state.next_check {
+            state.next_check = now + RELOAD_CHECK_INTERVAL;
+
+            let modified = modified_times(&self.options);
+            if modified != state.modified {
+                match server_config(&self.options) {
+                    Ok(config) => {
+                        info!(
+                            "Reloaded the TLS certificate '{}'",
+                            self.options.cert_file.display()
+                        );
+                        state.acceptor = TlsAcceptor::from(Arc::new(config));
+                        state.modified = modified;
+                    }
+                    Err(err) => {
+                        // Keep the previous modification times to retry on the next check, as
+                        // the files might have been only partially updated
+                        warn!(
+                            "Failed reloading the TLS certificate, keeping the current one: {err}"
+                        );
+                    }
+                }
+            }
+        }
+
+        state.acceptor.clone()
+    }
+}
+
+/// Configures TLS on the endpoint if the address uses the `https` scheme. The server certificate
+/// is verified with the CA of the given options, or with the system roots, and the configured
+/// certificate is presented to servers requesting client authentication.
+///
+/// The files are read every time an endpoint is configured, so new channels always use the
+/// current certificates. Fails if the files cannot be read, rather than connecting without the
+/// configured CA or client certificate.
+pub fn configure_client_tls(
+    endpoint: Endpoint,
+    address: &AdvertisedAddress,
+    options: Option<&TlsOptions>,
+) -> Result<Endpoint, TlsError> {
+    let AdvertisedAddress::Http(uri) = address else {
+        return Ok(endpoint);
+    };
+    if uri.scheme() != Some(&Scheme::HTTPS) {
+        return Ok(endpoint);
+    }
+
+    let mut tls_config = ClientTlsConfig::new();
+    match options {
+        Some(options) => {
+            if let Some(ca_file) = &options.ca_file {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(read_file(ca_file)?));
+            } else {
+                tls_config = tls_config.with_native_roots();
+            }
+            let cert = read_file(&options.cert_file)?;
+            let key = read_file(&options.key_file)?;
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+        None => tls_config = tls_config.with_native_roots(),
+    }
+
+    Ok(endpoint.tls_config(tls_config)?)
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, TlsError> {
+    std::fs::read(path).map_err(|source| TlsError::Read {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Formats the distinguished name according to RFC 4514, that is starting from the most specific
+/// attribute, for example `CN=node-1,O=Example,C=DE`.
+fn rfc4514_name(name: &X509Name<'_>) -> String {
+    let rdns: Vec<_> = name.iter().collect();
+    rdns.into_iter()
+        .rev()
+        .map(|rdn| {
+            rdn.iter()
+                .map(|attribute| {
+                    let attribute_type = oid2abbrev(attribute.attr_type(), oid_registry())
+                        .map(ToOwned::to_owned)
+                        .unwrap_or_else(|_| attribute.attr_type().to_id_string());
+                    let value = match attribute.as_str() {
+                        Ok(value) => escape_rfc4514_value(value),
+                        Err(_) => hex_rfc4514_value(attribute.attr_value()),
+                    };
+                    format!("{attribute_type}={value}")
+                })
+                .collect::<Vec<_>>()
+                .join("+")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Values which are not strings are encoded as `#` followed by the hex of their DER encoding.
+fn hex_rfc4514_value(value: &Any<'_>) -> String {
+    let der = value
+        .to_der_vec()
+        .unwrap_or_else(|_| value.as_bytes().to_vec());
+    der.iter().fold(String::from("#"), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+fn escape_rfc4514_value(value: &str) -> String {
+    let last = value.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(value.len());
+    for (idx, c) in value.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' if idx == 0 => escaped.push_str("\\#"),
+            ' ' if idx == 0 || idx == last => escaped.push_str("\\ "),
+            '\0' => escaped.push_str("\\00"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn server_config(options: &TlsOptions) -> Result<ServerConfig, TlsError> {
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_protocol_versions(rustls::DEFAULT_VERSIONS)?;
+
+    let builder = match &options.ca_file {
+        Some(ca_file) => {
+            let mut roots = RootCertStore::empty();
+            for certificate in load_certificates(ca_file)? {
+                roots.add(certificate)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if options.client_certificate_optional {
+                verifier.allow_unauthenticated()
+            } else {
+                verifier
+            };
+            builder.with_client_cert_verifier(verifier.build()?)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let key = PrivateKeyDer::from_pem_file(&options.key_file).map_err(|source| TlsError::Pem {
+        path: options.key_file.clone(),
+        source,
+    })?;
+    let mut config = builder.with_single_cert(load_certificates(&options.cert_file)?, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let certificates = CertificateDer::pem_file_iter(path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(|source| TlsError::Pem {
+            path: path.to_owned(),
+            source,
+        })?;
+
+    if certificates.is_empty() {
+        return Err(TlsError::NoCertificate(path.to_owned()));
+    }
+    Ok(certificates)
+}
+
+fn modified_times(options: &TlsOptions) -> Vec<Option<SystemTime>> {
+    [
+        Some(&options.cert_file),
+        Some(&options.key_file),
+        options.ca_file.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+    use rustls::pki_types::ServerName;
+    use tokio_rustls::TlsConnector;
+
+    struct Authority {
+        certificate: rcgen::Certificate,
+        key: KeyPair,
+    }
+
+    impl Authority {
+        fn new(name: &str) -> Self {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            params.distinguished_name.push(DnType::CommonName, name);
+            let certificate = params.self_signed(&key).unwrap();
+            Self { certificate, key }
+        }
+
+        /// Writes a certificate signed by this authority, returning the paths of the certificate
+        /// and of its key.
+        fn issue(&self, dir: &Path, name: &str, params: CertificateParams) -> (PathBuf, PathBuf) {
+            let key = KeyPair::generate().unwrap();
+            let certificate = params
+                .signed_by(&key, &self.certificate, &self.key)
+                .unwrap();
+
+            let cert_file = dir.join(format!("{name}.pem"));
+            let key_file = dir.join(format!("{name}.key"));
+            std::fs::write(&cert_file, certificate.pem()).unwrap();
+            std::fs::write(&key_file, key.serialize_pem()).unwrap();
+            (cert_file, key_file)
+        }
+
+        fn write(&self, dir: &Path, name: &str) -> PathBuf {
+            let path = dir.join(format!("{name}.pem"));
+            std::fs::write(&path, self.certificate.pem()).unwrap();
+            path
+        }
+    }
+
+    fn connector(ca: &Authority, client: Option<(&Path, &Path)>) -> TlsConnector {
+        let mut roots = RootCertStore::empty();
+        roots.add(ca.certificate.der().clone()).unwrap();
+        let builder = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::aws_lc_rs::default_provider(),
+        ))
+        .with_protocol_versions(rustls::DEFAULT_VERSIONS)
+        .unwrap()
+        .with_root_certificates(roots);
+
+        let config = match client {
+            Some((cert_file, key_file)) => builder
+                .with_client_auth_cert(
+                    load_certificates(cert_file).unwrap(),
+                    PrivateKeyDer::from_pem_file(key_file).unwrap(),
+                )
+                .unwrap(),
+            None => builder.with_no_client_auth(),
+        };
+        TlsConnector::from(Arc::new(config))
+    }
+
+    /// Runs a handshake, returning the client certificate subject seen by the server.
+    async fn handshake(
+        acceptor: &ReloadingTlsAcceptor,
+        connector: &TlsConnector,
+    ) -> io::Result<Option<PeerCertificateSubject>> {
+        let (client, server) = io::duplex(16 * 1024);
+        let server_name = ServerName::try_from("localhost").unwrap();
+
+        let (client, server) = tokio::join!(
+            connector.connect(server_name, client),
+            acceptor.accept(server)
+        );
+        client?;
+        server.map(|(_, subject)| subject)
+    }
+
+    fn server_params() -> CertificateParams {
+        CertificateParams::new(vec!["localhost".to_owned()]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn mutual_tls() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = Authority::new("test-ca");
+        let (cert_file, key_file) = ca.issue(dir.path(), "server", server_params());
+
+        let mut client_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        client_params
+            .distinguished_name
+            .push(DnType::OrganizationName, "Example");
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "client");
+        let (client_cert, client_key) = ca.issue(dir.path(), "client", client_params);
+
+        let acceptor = ReloadingTlsAcceptor::new(TlsOptions {
+            cert_file,
+            key_file,
+            ca_file: Some(ca.write(dir.path(), "ca")),
+            client_certificate_optional: false,
+        })
+        .unwrap();
+
+        let subject = handshake(
+            &acceptor,
+            &connector(&ca, Some((&client_cert, &client_key))),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            subject,
+            Some(PeerCertificateSubject("CN=client,O=Example".to_owned()))
+        );
+
+        // Clients without certificate are rejected
+        assert!(handshake(&acceptor, &connector(&ca, None)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn optional_client_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = Authority::new("test-ca");
+        let (cert_file, key_file) = ca.issue(dir.path(), "server", server_params());
+        let mut client_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "client");
+        let (client_cert, client_key) = ca.issue(dir.path(), "client", client_params);
+
+        let acceptor = ReloadingTlsAcceptor::new(TlsOptions {
+            cert_file,
+            key_file,
+            ca_file: Some(ca.write(dir.path(), "ca")),
+            client_certificate_optional: true,
+        })
+        .unwrap();
+
+        assert_eq!(
+            handshake(
+                &acceptor,
+                &connector(&ca, Some((&client_cert, &client_key)))
+            )
+            .await
+            .unwrap(),
+            Some(PeerCertificateSubject("CN=client".to_owned()))
+        );
+        // Clients without certificate are accepted, e.g. to authenticate with a bearer token
+        assert_eq!(
+            handshake(&acceptor, &connector(&ca, None)).await.unwrap(),
+            None
+        );
+
+        // Certificates signed by other CAs are still rejected
+        let other_ca = Authority::new("other-ca");
+        let mut other_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        other_params
+            .distinguished_name
+            .push(DnType::CommonName, "intruder");
+        let (other_cert, other_key) = other_ca.issue(dir.path(), "intruder", other_params);
+        assert!(
+            handshake(&acceptor, &connector(&ca, Some((&other_cert, &other_key))))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reload_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_ca = Authority::new("old-ca");
+        let (cert_file, key_file) = old_ca.issue(dir.path(), "server", server_params());
+
+        let acceptor = ReloadingTlsAcceptor::new(TlsOptions {
+            cert_file,
+            key_file,
+            ca_file: None,
+            client_certificate_optional: false,
+        })
+        .unwrap();
+        assert_eq!(
+            handshake(&acceptor, &connector(&old_ca, None))
+                .await
+                .unwrap(),
+            None
+        );
+
+        // Rotate the certificate, it's picked up at the next check
+        let new_ca = Authority::new("new-ca");
+        new_ca.issue(dir.path(), "server", server_params());
+        assert!(
+            handshake(&acceptor, &connector(&old_ca, None))
+                .await
+                .is_ok()
+        );
+
+        tokio::time::advance(RELOAD_CHECK_INTERVAL).await;
+        assert!(
+            handshake(&acceptor, &connector(&new_ca, None))
+                .await
+                .is_ok()
+        );
+        assert!(
+            handshake(&acceptor, &connector(&old_ca, None))
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn escape_subject_values() {
+        assert_eq!(escape_rfc4514_value("node-1"), "node-1");
+        assert_eq!(escape_rfc4514_value("Example, Inc."), "Example\\, Inc.");
+        assert_eq!(escape_rfc4514_value("#1 a+b "), "\\#1 a\\+b\\ ");
+        assert_eq!(escape_rfc4514_value(" x"), "\\ x");
+    }
+
+    #[test]
+    fn client_tls_fails_on_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let address: AdvertisedAddress = "https://localhost:5122".parse().unwrap();
+        let options = TlsOptions {
+            cert_file: dir.path().join("missing.pem"),
+            key_file: dir.path().join("missing.key"),
+            ca_file: None,
+            client_certificate_optional: false,
+        };
+
+        let result = configure_client_tls(
+            Endpoint::from_static("https://localhost:5122"),
+            &address,
+            Some(&options),
+        );
+        assert!(matches!(result, Err(TlsError::Read { .. })));
+    }
+}
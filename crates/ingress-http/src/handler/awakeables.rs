@@ -13,19 +13,24 @@ use super::HandlerError;
 use super::path_parsing::AwakeableRequestType;
 
 use crate::RequestDispatcher;
+use crate::layers::auth::AuthenticatedPrincipal;
 use bytes::Bytes;
 use http::{Method, Request, Response, StatusCode};
 use http_body_util::BodyExt;
 use http_body_util::Full;
 use restate_types::errors::{InvocationError, codes};
 use restate_types::identifiers::{AwakeableIdentifier, ExternalSignalIdentifier, WithInvocationId};
-use restate_types::invocation::{InvocationResponse, JournalCompletionTarget, ResponseResult};
+use restate_types::invocation::{
+    InvocationQuery, InvocationResponse, JournalCompletionTarget, ResponseResult,
+};
 use restate_types::journal_v2::{Signal, SignalResult};
+use restate_types::schema::invocation_target::InvocationTargetResolver;
 use std::str::FromStr;
 use tracing::{info, trace, warn};
 
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
 where
+    Schemas: InvocationTargetResolver + Clone + Send + Sync + 'static,
     Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
 {
     pub(crate) async fn handle_awakeable<B: http_body::Body>(
@@ -41,6 +46,26 @@ where
             return Err(HandlerError::MethodNotAllowed);
         }
 
+        let awakeable_id = match &awakeable_request_type {
+            AwakeableRequestType::Resolve { awakeable_id }
+            | AwakeableRequestType::Reject { awakeable_id } => awakeable_id,
+        };
+        let invocation_id = if let Ok(signal_id) = ExternalSignalIdentifier::from_str(awakeable_id)
+        {
+            signal_id.invocation_id()
+        } else {
+            AwakeableIdentifier::from_str(awakeable_id)
+                .map_err(|e| HandlerError::BadAwakeableId(awakeable_id.clone(), e))?
+                .into_inner()
+                .0
+        };
+        // Only principals allowed to invoke the handler owning the awakeable can complete it
+        self.check_invocation_principal(
+            req.extensions().get::<AuthenticatedPrincipal>().cloned(),
+            &InvocationQuery::Invocation(invocation_id),
+        )
+        .await?;
+
         // Collect body
         let collected_request_bytes = req
             .into_body()
@@ -0,0 +1,363 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::convert::Infallible;
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use bytestring::ByteString;
+use futures::channel::mpsc;
+use futures::{StreamExt, stream};
+use http::{HeaderValue, Method, Request, Response, StatusCode, header};
+use http_body::Frame;
+use http_body_util::{BodyExt, StreamBody};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use tracing::{debug, trace, warn};
+
+use super::error::ErrorResponse;
+use super::service_handler::{SendResponse, parse_headers};
+use super::tracing::prepare_tracing_span;
+use super::{Handler, HandlerError, ResponseBody, check_principal, collect_limited_body};
+use crate::RequestDispatcher;
+use restate_types::identifiers::{InvocationId, WithInvocationId};
+use restate_types::invocation::{
+    Header, InvocationRequest, InvocationRequestHeader, InvocationTarget, InvocationTargetType,
+    SpanRelation, WorkflowHandlerType,
+};
+use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::service::ServiceMetadataResolver;
+
+const APPLICATION_NDJSON: HeaderValue = HeaderValue::from_static("application/x-ndjson");
+/// Maximum size of the body of a batch request.
+const MAX_BATCH_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Single record of a batch, the body can be either a JSON array of records, or a record per line.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchRecord {
+    /// Target in the form `:service-name/:handler`.
+    target: String,
+    /// Key of the target, required by virtual objects and workflows.
+    #[serde(default)]
+    key: Option<String>,
+    #[serde(default)]
+    idempotency_key: Option<String>,
+    #[serde_as(as = "Option<restate_serde_util::DurationString>")]
+    #[serde(default)]
+    delay: Option<Duration>,
+    /// JSON input of the handler. When omitted, the handler is invoked without input.
+    #[serde(default)]
+    body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchItemResponse {
+    index: usize,
+    #[serde(flatten)]
+    result: BatchItemResult,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum BatchItemResult {
+    Sent(SendResponse),
+    Failed { error: ErrorResponse },
+}
+
+impl BatchItemResponse {
+    fn new(index: usize, result: Result<SendResponse, HandlerError>) -> Self {
+        Self {
+            index,
+            result: match result {
+                Ok(send_response) => BatchItemResult::Sent(send_response),
+                Err(e) => BatchItemResult::Failed { error: e.into() },
+            },
+        }
+    }
+
+    fn to_line(&self) -> Bytes {
+        let mut line =
+            serde_json::to_vec(self).expect("Serializing BatchItemResponse should not fail");
+        line.push(b'\n');
+        line.into()
+    }
+}
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
+{
+    /// Sends a batch of invocations. The response is a stream of JSON lines, one for each record
+    /// of the batch in completion order, each carrying the index of the record within the batch.
+    pub(crate) async fn handle_batch<B: http_body::Body>(
+        self,
+        req: Request<B>,
+    ) -> Result<Response<ResponseBody>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Check HTTP Method
+        if req.method() != Method::POST {
+            return Err(HandlerError::MethodNotAllowed);
+        }
+
+        let (parts, body) = req.into_parts();
+
+        // Collect body
+        let body = collect_limited_body(body, MAX_BATCH_BODY_SIZE).await?;
+        trace!(rpc.request = ?body);
+
+        let records = if parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("application/json"))
+        {
+            parse_json_array(&body)?
+        } else {
+            parse_ndjson(&body)
+        };
+        debug!("Processing ingress batch of {} records", records.len());
+
+        let headers = parse_headers(&parts)?;
+        let req = Request::from_parts(parts, ());
+
+        let mut responses = Vec::new();
+        let mut invocation_requests = Vec::with_capacity(records.len());
+        let mut indexes = Vec::with_capacity(records.len());
+        for (index, record) in records.into_iter().enumerate() {
+            match record.and_then(|record| self.prepare_batch_item(record, &headers, &req)) {
+                Ok(invocation_request) => {
+                    indexes.push(index);
+                    invocation_requests.push(invocation_request);
+                }
+                Err(e) => responses.push(BatchItemResponse::new(index, Err(e)).to_line()),
+            }
+        }
+
+        // Drive the batch within the response body, so the lines are sent as soon as each
+        // invocation is submitted, and the batch is dropped together with the connection.
+        let (results_tx, results_rx) = mpsc::unbounded();
+        let dispatcher = self.dispatcher;
+        let driver = async move {
+            let invocation_ids: Vec<_> = invocation_requests
+                .iter()
+                .map(|invocation_request| invocation_request.invocation_id())
+                .collect();
+            let mut results = std::pin::pin!(dispatcher.send_batch(invocation_requests));
+            while let Some((batch_index, result)) = results.next().await {
+                let result = result
+                    .map(|submit_notification| {
+                        SendResponse::new(invocation_ids[batch_index], submit_notification)
+                    })
+                    .map_err(|e| {
+                        warn!("Failed to send batch item: {e}");
+                        HandlerError::DispatcherError(e)
+                    });
+                let line = BatchItemResponse::new(indexes[batch_index], result).to_line();
+                if results_tx.unbounded_send(line).is_err() {
+                    // Client went away
+                    return;
+                }
+            }
+        };
+
+        let lines = stream::iter(responses)
+            .chain(stream::select(
+                results_rx,
+                stream::once(driver).filter_map(|()| std::future::ready(None)),
+            ))
+            .map(|line| Ok::<_, Infallible>(Frame::data(line)));
+
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, APPLICATION_NDJSON)
+            .body(StreamBody::new(lines).boxed_unsync())
+            .unwrap())
+    }
+
+    fn prepare_batch_item(
+        &self,
+        BatchRecord {
+            target,
+            key,
+            idempotency_key,
+            delay,
+            body,
+        }: BatchRecord,
+        headers: &[Header],
+        req: &Request<()>,
+    ) -> Result<InvocationRequest, HandlerError> {
+        let (service_name, handler_name) = target
+            .split_once('/')
+            .filter(|(service, handler)| {
+                !service.is_empty() && !handler.is_empty() && !handler.contains('/')
+            })
+            .ok_or_else(|| HandlerError::BadBatchTarget(target.clone()))?;
+
+        let invocation_target_meta = if let Some(invocation_target) = self
+            .schemas
+            .pinned()
+            .resolve_latest_invocation_target(service_name, handler_name)
+        {
+            if !invocation_target.public {
+                return Err(HandlerError::PrivateService);
+            }
+            check_principal(req, service_name, Some(handler_name))?;
+            invocation_target
+        } else {
+            return Err(HandlerError::ServiceHandlerNotFound(
+                service_name.to_owned(),
+                handler_name.to_owned(),
+            ));
+        };
+
+        let idempotency_key = idempotency_key.map(ByteString::from);
+        if idempotency_key.is_some()
+            && invocation_target_meta.target_ty
+                == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow)
+        {
+            return Err(HandlerError::UnsupportedIdempotencyKey);
+        }
+
+        // Craft Invocation Target and Id
+        let invocation_target = match (invocation_target_meta.target_ty, key) {
+            (InvocationTargetType::Service, None) => {
+                InvocationTarget::service(service_name, handler_name)
+            }
+            (InvocationTargetType::VirtualObject(handler_ty), Some(key)) => {
+                InvocationTarget::virtual_object(service_name, key, handler_name, handler_ty)
+            }
+            (InvocationTargetType::Workflow(handler_ty), Some(key)) => {
+                InvocationTarget::workflow(service_name, key, handler_name, handler_ty)
+            }
+            _ => return Err(HandlerError::BadBatchTarget(target.clone())),
+        };
+        let invocation_id = InvocationId::generate(&invocation_target, idempotency_key.as_deref());
+
+        // Validate the body
+        let (content_type, body) = match body {
+            Some(body) => (
+                Some("application/json"),
+                Bytes::from(
+                    serde_json::to_vec(&body).expect("Serializing a JSON value should not fail"),
+                ),
+            ),
+            None => (None, Bytes::new()),
+        };
+        invocation_target_meta
+            .input_rules
+            .validate(content_type, &body)?;
+        if invocation_target_meta.validate_input {
+            self.input_validator.validate(
+                &*self.schemas.pinned(),
+                service_name,
+                handler_name,
+                &invocation_target_meta.input_rules,
+                content_type,
+                &body,
+            )?;
+        }
+
+        // Each record takes a token from the bucket of its own service
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.check_target(
+                req,
+                Some(service_name),
+                invocation_target.key().map(|key| &**key),
+            )?;
+        }
+
+        let ingress_span_context = prepare_tracing_span(&invocation_id, &invocation_target, req);
+
+        // Prepare service invocation
+        let mut invocation_request_header =
+            InvocationRequestHeader::initialize(invocation_id, invocation_target);
+        invocation_request_header.with_related_span(SpanRelation::Parent(ingress_span_context));
+        invocation_request_header.completion_retention_duration =
+            invocation_target_meta.compute_retention(idempotency_key.is_some());
+        invocation_request_header.journal_retention_duration =
+            invocation_target_meta.journal_retention;
+        invocation_request_header.dead_letter_queue = invocation_target_meta.dead_letter_queue;
+        invocation_request_header.idempotency_key = idempotency_key;
+        invocation_request_header.execution_time =
+            delay.map(|d| SystemTime::now() + d).map(Into::into);
+        invocation_request_header.headers = headers.to_vec();
+
+        Ok(InvocationRequest::new(invocation_request_header, body))
+    }
+}
+
+/// Parses a JSON array of records. A body which is not a JSON array fails the whole batch, while
+/// malformed records fail only themselves.
+fn parse_json_array(body: &[u8]) -> Result<Vec<Result<BatchRecord, HandlerError>>, HandlerError> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_slice(body).map_err(|e| HandlerError::BadBatchRecord(e.to_string()))?;
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            BatchRecord::deserialize(value).map_err(|e| HandlerError::BadBatchRecord(e.to_string()))
+        })
+        .collect())
+}
+
+/// Parses a record per line, skipping blank lines.
+fn parse_ndjson(body: &[u8]) -> Vec<Result<BatchRecord, HandlerError>> {
+    body.split(|b| *b == b'\n')
+        .filter(|line| !line.trim_ascii().is_empty())
+        .map(|line| {
+            serde_json::from_slice(line).map_err(|e| HandlerError::BadBatchRecord(e.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ndjson_records() {
+        let records = parse_ndjson(
+            b"{\"target\": \"Greeter/greet\", \"body\": \"Francesco\"}\n\n  \n{\"target\": \"Counter/add\", \"key\": \"my-key\", \"delay\": \"10s\", \"idempotency_key\": \"abc\"}\nnot-json\n",
+        );
+        assert_eq!(records.len(), 3);
+
+        let first = records[0].as_ref().unwrap();
+        assert_eq!(first.target, "Greeter/greet");
+        assert_eq!(first.body, Some(serde_json::json!("Francesco")));
+
+        let second = records[1].as_ref().unwrap();
+        assert_eq!(second.key.as_deref(), Some("my-key"));
+        assert_eq!(second.idempotency_key.as_deref(), Some("abc"));
+        assert_eq!(second.delay, Some(Duration::from_secs(10)));
+        assert_eq!(second.body, None);
+
+        assert!(matches!(records[2], Err(HandlerError::BadBatchRecord(_))));
+    }
+
+    #[test]
+    fn json_array_records() {
+        let records =
+            parse_json_array(br#"[{"target": "Greeter/greet"}, {"target": 1}, {"unknown": 1}]"#)
+                .unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records[0].is_ok());
+        assert!(matches!(records[1], Err(HandlerError::BadBatchRecord(_))));
+        assert!(matches!(records[2], Err(HandlerError::BadBatchRecord(_))));
+
+        assert!(matches!(
+            parse_json_array(br#"{"target": "Greeter/greet"}"#),
+            Err(HandlerError::BadBatchRecord(_))
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use std::convert::Infallible;
+use std::task::{Context, Poll};
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use restate_types::config::IngressCorsOptions;
+use restate_types::schema::service::{ServiceCorsPolicy, ServiceMetadataResolver};
+use tower::{Layer, Service};
+
+use super::Handler;
+
+const WILDCARD: &str = "*";
+
+/// CORS policy of a request, merging the ingress options with the overrides of the invoked service.
+#[derive(Debug)]
+pub(crate) struct CorsPolicy {
+    origin: HeaderValue,
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    exposed_headers: Vec<String>,
+    allow_credentials: bool,
+    max_age: Option<u64>,
+}
+
+impl CorsPolicy {
+    /// Returns the policy to apply to the request, or `None` if it's not a cross-origin request.
+    pub(crate) fn for_request<B>(
+        req: &Request<B>,
+        options: &IngressCorsOptions,
+        overrides: Option<ServiceCorsPolicy>,
+    ) -> Option<Self> {
+        let origin = req.headers().get(ORIGIN)?.clone();
+        let ServiceCorsPolicy {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers,
+            allow_credentials,
+        } = overrides.unwrap_or_default();
+
+        Some(Self {
+            origin,
+            allowed_origins: allowed_origins.unwrap_or_else(|| options.allowed_origins.clone()),
+            allowed_methods: allowed_methods.unwrap_or_else(|| options.allowed_methods.clone()),
+            allowed_headers: allowed_headers.unwrap_or_else(|| options.allowed_headers.clone()),
+            exposed_headers: options.exposed_headers.clone(),
+            allow_credentials: allow_credentials.unwrap_or(options.allow_credentials),
+            max_age: options.max_age.map(|max_age| max_age.as_secs()),
+        })
+    }
+
+    fn allows_origin(&self) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == WILDCARD || self.origin == allowed.as_str())
+    }
+
+    fn lists_origin(&self) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| self.origin == allowed.as_str())
+    }
+
+    fn allows_method(&self, method: &HeaderValue) -> bool {
+        self.allowed_methods
+            .iter()
+            .any(|allowed| method.as_bytes().eq_ignore_ascii_case(allowed.as_bytes()))
+    }
+
+    fn allows_any_header(&self) -> bool {
+        self.allowed_headers
+            .iter()
+            .any(|allowed| allowed == WILDCARD)
+    }
+
+    fn allows_headers(&self, requested: Option<&HeaderValue>) -> bool {
+        let Some(requested) = requested else {
+            return true;
+        };
+        if self.allows_any_header() {
+            return true;
+        }
+
+        requested.to_str().is_ok_and(|requested| {
+            requested
+                .split(',')
+                .map(str::trim)
+                .filter(|header| !header.is_empty())
+                .all(|header| {
+                    self.allowed_headers
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(header))
+                })
+        })
+    }
+
+    /// Responds to a preflight request. Requests not allowed by the policy get a 403 response
+    /// without CORS headers.
+    pub(crate) fn preflight<B: Default>(&self, request_headers: &HeaderMap) -> Response<B> {
+        let requested_headers = request_headers.get(ACCESS_CONTROL_REQUEST_HEADERS);
+        if !self.allows_origin()
+            || !request_headers
+                .get(ACCESS_CONTROL_REQUEST_METHOD)
+                .is_some_and(|method| self.allows_method(method))
+            || !self.allows_headers(requested_headers)
+        {
+            return Response::builder()
+                .status(StatusCode::FORBIDDEN)
+                .body(Default::default())
+                .unwrap();
+        }
+
+        let mut response = Response::builder().status(StatusCode::OK).header(
+            ACCESS_CONTROL_ALLOW_METHODS,
+            self.allowed_methods.join(", "),
+        );
+        if self.allows_any_header() {
+            if let Some(requested_headers) = requested_headers {
+                response = response.header(ACCESS_CONTROL_ALLOW_HEADERS, requested_headers);
+            }
+        } else if !self.allowed_headers.is_empty() {
+            response = response.header(
+                ACCESS_CONTROL_ALLOW_HEADERS,
+                self.allowed_headers.join(", "),
+            );
+        }
+        if let Some(max_age) = self.max_age {
+            response = response.header(ACCESS_CONTROL_MAX_AGE, max_age);
+        }
+
+        let mut response = response.body(Default::default()).unwrap();
+        self.apply(response.headers_mut());
+        response
+            .headers_mut()
+            .append(VARY, HeaderValue::from_name(ACCESS_CONTROL_REQUEST_METHOD));
+        response
+            .headers_mut()
+            .append(VARY, HeaderValue::from_name(ACCESS_CONTROL_REQUEST_HEADERS));
+        response
+    }
+
+    /// Adds the CORS headers to the response, if the origin is allowed.
+    pub(crate) fn apply(&self, response_headers: &mut HeaderMap) {
+        response_headers.append(VARY, HeaderValue::from_name(ORIGIN));
+        if !self.allows_origin() {
+            return;
+        }
+
+        // Echoing the origin is valid both with and without credentials
+        response_headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, self.origin.clone());
+        // Credentials are never allowed for origins only matched by `*`, which the validation of
+        // the ingress options and of the service overrides rejects, but the two can still be
+        // combined, e.g. a service allowing `*` while the ingress options allow credentials.
+        if self.allow_credentials && self.lists_origin() {
+            response_headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+        if !self.exposed_headers.is_empty() {
+            if let Ok(exposed_headers) = HeaderValue::from_str(&self.exposed_headers.join(", ")) {
+                response_headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, exposed_headers);
+            }
+        }
+    }
+}
+
+pub(crate) fn is_preflight<B>(req: &Request<B>) -> bool {
+    req.method() == Method::OPTIONS && req.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+}
+
+/// Enforces the CORS policy resolved by the [`Handler`]. This layer must wrap the authentication,
+/// so that the responses it rejects, such as 401s, carry the CORS headers too and can be read by
+/// the browser scripts.
+#[derive(Clone)]
+pub(crate) struct CorsLayer<Schemas, Dispatcher> {
+    handler: Handler<Schemas, Dispatcher>,
+}
+
+impl<Schemas, Dispatcher> CorsLayer<Schemas, Dispatcher> {
+    pub(crate) fn new(handler: Handler<Schemas, Dispatcher>) -> Self {
+        Self { handler }
+    }
+}
+
+impl<S, Schemas: Clone, Dispatcher: Clone> Layer<S> for CorsLayer<Schemas, Dispatcher> {
+    type Service = Cors<S, Schemas, Dispatcher>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Cors {
+            inner,
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Cors<S, Schemas, Dispatcher> {
+    inner: S,
+    handler: Handler<Schemas, Dispatcher>,
+}
+
+impl<S, Schemas, Dispatcher, ReqBody, ResBody> Service<Request<ReqBody>>
+    for Cors<S, Schemas, Dispatcher>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>, Error = Infallible>,
+    S::Future: Send + 'static,
+    Schemas: ServiceMetadataResolver + Clone + Send + Sync + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = Response<ResBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let Some(cors_policy) = self.handler.cors_policy(&req) else {
+            return self.inner.call(req).boxed();
+        };
+        if is_preflight(&req) {
+            return std::future::ready(Ok(cors_policy.preflight(req.headers()))).boxed();
+        }
+
+        self.inner
+            .call(req)
+            .map(move |response| {
+                response.map(|mut response| {
+                    cors_policy.apply(response.headers_mut());
+                    response
+                })
+            })
+            .boxed()
+    }
+}
@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use super::APPLICATION_JSON;
+use super::schema_validation::SchemaViolation;
 
 use crate::RequestDispatcherError;
 use bytes::Bytes;
@@ -17,6 +18,10 @@ use restate_types::errors::{IdDecodeError, InvocationError};
 use restate_types::schema::invocation_target::InputValidationError;
 use serde::Serialize;
 use std::string;
+use std::time::Duration;
+
+const INPUT_SCHEMA_VALIDATION_MESSAGE: &str =
+    "the request body doesn't match the input JSON schema of the handler";
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum HandlerError {
@@ -39,13 +44,45 @@ pub(crate) enum HandlerError {
     )]
     BadAwakeablesPath,
     #[error(
-        "bad path, expected either /restate/invocation/:invocation_id/output or /restate/invocation/:invocation_id/attach or /restate/invocation/:invocation_target/:idempotency_key/output or /restate/invocation/:invocation_target/:idempotency_key/attach"
+        "bad path, expected either /restate/invocation/:invocation_id/output or /restate/invocation/:invocation_id/attach or /restate/invocation/:invocation_id/events or /restate/invocation/:invocation_target/:idempotency_key/output or /restate/invocation/:invocation_target/:idempotency_key/attach or /restate/invocation/:invocation_target/:idempotency_key/events"
     )]
     BadInvocationPath,
     #[error(
         "bad path, expected either /restate/workflow/:workflow_name/:workflow_key/output or /restate/workflow/:workflow_name/:workflow_key/attach"
     )]
     BadWorkflowPath,
+    #[error(
+        "bad path, expected either /restate/idempotency/:service-name/:handler/:idempotency_key or /restate/idempotency/:object-name/:object-key/:handler/:idempotency_key"
+    )]
+    BadIdempotencyPath,
+    #[error("bad path, expected /:package.:service/:method")]
+    BadGrpcPath,
+    #[error(
+        "the gRPC method '{0}/{1}' was not found, make sure the service declares its protobuf schemas."
+    )]
+    GrpcMethodNotFound(String, String),
+    #[error(
+        "the service '{0}' is keyed, the key must be provided with the 'x-restate-key' metadata"
+    )]
+    MissingGrpcKey(String),
+    #[error("bad gRPC message: {0}")]
+    BadGrpcMessage(&'static str),
+    #[error("bad path, expected /restate/webhook/:webhook_name")]
+    BadWebhookPath,
+    #[error("webhook '{0}' not found, make sure it's defined in the ingress options.")]
+    WebhookNotFound(String),
+    #[error("no subscription is consuming from the webhook '{0}'")]
+    NoWebhookSubscriptions(String),
+    #[error("bad webhook signature: {0}")]
+    BadWebhookSignature(&'static str),
+    #[error(
+        "bad batch record target '{0}', expected :service-name/:handler, with the key set only for virtual objects and workflows"
+    )]
+    BadBatchTarget(String),
+    #[error(
+        "bad batch record, expected a JSON object with the fields target, key, idempotency_key, delay and body: {0}"
+    )]
+    BadBatchRecord(String),
     #[error("not implemented")]
     NotImplemented,
     #[error("bad header {0}: {1:?}")]
@@ -56,12 +93,20 @@ pub(crate) enum HandlerError {
     UrlDecodingError(string::FromUtf8Error),
     #[error("the invoked service is not public")]
     PrivateService,
+    #[error("the principal '{0}' is not allowed to invoke '{1}'")]
+    Forbidden(String, String),
+    #[error("rate limit exceeded, retry after {0:?}")]
+    RateLimited(Duration),
     #[error("cannot read body: {0:?}")]
     Body(anyhow::Error),
+    #[error("the request body exceeds the limit of {0} bytes")]
+    BodyTooLarge(usize),
     #[error("unavailable")]
     Unavailable,
     #[error("the invocation exists but has not completed yet")]
     NotReady,
+    #[error("the invocation already completed")]
+    InvocationAlreadyCompleted,
     #[error("method not allowed")]
     MethodNotAllowed,
     #[error(
@@ -72,6 +117,8 @@ pub(crate) enum HandlerError {
     Invocation(InvocationError),
     #[error("input validation error: {0}")]
     InputValidation(#[from] InputValidationError),
+    #[error("{INPUT_SCHEMA_VALIDATION_MESSAGE}")]
+    InputSchemaValidation(Vec<SchemaViolation>),
     #[error(
         "cannot use the delay query parameter with calls. The delay is supported only with sends"
     )]
@@ -98,6 +145,10 @@ pub enum ErrorResponse {
         // InvocationError has its own json representation, we simply use that
         InvocationError,
     ),
+    InputSchemaValidation {
+        message: &'static str,
+        violations: Vec<SchemaViolation>,
+    },
     Other {
         // This will simply write the error using the Display trait
         #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
@@ -105,16 +156,30 @@ pub enum ErrorResponse {
     },
 }
 
+impl From<HandlerError> for ErrorResponse {
+    fn from(error: HandlerError) -> Self {
+        match error {
+            HandlerError::Invocation(e) => ErrorResponse::Invocation(e),
+            HandlerError::InputSchemaValidation(violations) => {
+                ErrorResponse::InputSchemaValidation {
+                    message: INPUT_SCHEMA_VALIDATION_MESSAGE,
+                    violations,
+                }
+            }
+            e => ErrorResponse::Other { message: e },
+        }
+    }
+}
+
 impl HandlerError {
-    pub(crate) fn fill_builder<B: http_body::Body + Default + From<Bytes>>(
-        self,
-        res_builder: http::response::Builder,
-    ) -> Response<B> {
-        let status_code = match &self {
+    pub(crate) fn status_code(&self) -> StatusCode {
+        match self {
             HandlerError::NotFound
             | HandlerError::ServiceNotFound(_)
             | HandlerError::ServiceHandlerNotFound(_, _)
-            | HandlerError::InvocationNotFound => StatusCode::NOT_FOUND,
+            | HandlerError::InvocationNotFound
+            | HandlerError::WebhookNotFound(_)
+            | HandlerError::NoWebhookSubscriptions(_) => StatusCode::NOT_FOUND,
             HandlerError::BadServicePath
             | HandlerError::PrivateService
             | HandlerError::UrlDecodingError(_)
@@ -126,7 +191,15 @@ impl HandlerError {
             | HandlerError::BadInvocationPath
             | HandlerError::BadInvocationId(_, _)
             | HandlerError::BadWorkflowPath
+            | HandlerError::BadIdempotencyPath
+            | HandlerError::BadWebhookPath
+            | HandlerError::BadGrpcPath
+            | HandlerError::MissingGrpcKey(_)
+            | HandlerError::BadGrpcMessage(_)
+            | HandlerError::BadBatchTarget(_)
+            | HandlerError::BadBatchRecord(_)
             | HandlerError::InputValidation(_)
+            | HandlerError::InputSchemaValidation(_)
             | HandlerError::UnsupportedIdempotencyKey
             | HandlerError::UnsupportedGetOutput => StatusCode::BAD_REQUEST,
             HandlerError::DispatcherError(_) => {
@@ -134,20 +207,43 @@ impl HandlerError {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
             HandlerError::Body(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            HandlerError::BodyTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            HandlerError::BadWebhookSignature(_) => StatusCode::UNAUTHORIZED,
+            HandlerError::Forbidden(_, _) => StatusCode::FORBIDDEN,
+            HandlerError::InvocationAlreadyCompleted => StatusCode::CONFLICT,
+            HandlerError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
             HandlerError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
             HandlerError::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
-            HandlerError::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+            HandlerError::NotImplemented | HandlerError::GrpcMethodNotFound(_, _) => {
+                StatusCode::NOT_IMPLEMENTED
+            }
             HandlerError::Invocation(e) => {
                 StatusCode::from_u16(e.code().into()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
             HandlerError::NotReady => StatusCode::from_u16(470).unwrap(),
-        };
+        }
+    }
 
-        let error_response = match self {
-            HandlerError::Invocation(e) => ErrorResponse::Invocation(e),
-            e => ErrorResponse::Other { message: e },
+    pub(crate) fn fill_builder<B: http_body::Body + Default + From<Bytes>>(
+        self,
+        res_builder: http::response::Builder,
+    ) -> Response<B> {
+        let status_code = self.status_code();
+
+        let res_builder = match &self {
+            HandlerError::RateLimited(retry_after) => {
+                // Retry-After is expressed in whole seconds
+                let retry_after_secs =
+                    retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                res_builder.header(header::RETRY_AFTER, retry_after_secs.max(1))
+            }
+            // Keep the original error around, as the gRPC ingress maps its code to a gRPC status
+            HandlerError::Invocation(error) => res_builder.extension(error.clone()),
+            _ => res_builder,
         };
 
+        let error_response = ErrorResponse::from(self);
+
         res_builder
             .status(status_code)
             .header(http::header::CONTENT_TYPE, APPLICATION_JSON)
@@ -0,0 +1,760 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Unary gRPC and Connect protocols, for the services declaring protobuf schemas. The requests are
+//! translated to regular service calls, with the protobuf message as `application/proto` body.
+
+use std::collections::{HashSet, VecDeque};
+use std::convert::Infallible;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, header};
+use http_body::Frame;
+use http_body_util::{BodyExt, Full, StreamBody};
+use prost::Message;
+use prost_types::{DescriptorProto, FileDescriptorProto, FileDescriptorSet};
+use serde::Serialize;
+use tonic::{Code, Status};
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::{
+    ErrorResponse, FileDescriptorResponse, ListServiceResponse, ServerReflectionRequest,
+    ServerReflectionResponse, ServiceResponse,
+};
+use tracing::debug;
+
+use super::path_parsing::{InvokeType, ServiceRequestType, TargetType};
+use super::{APPLICATION_JSON, Handler, HandlerError, ResponseBody, collect_limited_body};
+use crate::RequestDispatcher;
+use restate_types::errors::InvocationError;
+use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::service::ServiceMetadataResolver;
+
+const GRPC_REFLECTION_SERVICE: &str = "grpc.reflection.v1.ServerReflection";
+const GRPC_REFLECTION_METHOD: &str = "ServerReflectionInfo";
+
+const X_RESTATE_KEY: HeaderName = HeaderName::from_static("x-restate-key");
+const CONNECT_PROTOCOL_VERSION: HeaderName = HeaderName::from_static("connect-protocol-version");
+const GRPC_STATUS: HeaderName = HeaderName::from_static("grpc-status");
+const GRPC_MESSAGE: HeaderName = HeaderName::from_static("grpc-message");
+const APPLICATION_GRPC: HeaderValue = HeaderValue::from_static("application/grpc");
+const APPLICATION_PROTO: HeaderValue = HeaderValue::from_static("application/proto");
+
+/// Headers of the gRPC and Connect transports, which are not propagated to the invocation.
+const TRANSPORT_HEADERS: [&str; 9] = [
+    "te",
+    "content-length",
+    "grpc-timeout",
+    "grpc-encoding",
+    "grpc-accept-encoding",
+    "connect-protocol-version",
+    "connect-timeout-ms",
+    "connect-content-encoding",
+    "connect-accept-encoding",
+];
+
+/// Length of the prefix of the gRPC messages: the compression flag and the message length.
+const GRPC_FRAME_PREFIX_LEN: usize = 5;
+/// Maximum size of the request messages, like the default of the gRPC servers.
+const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GrpcProtocol {
+    Grpc,
+    Connect,
+}
+
+impl GrpcProtocol {
+    /// Detects the protocol from the content type, falling back to the Restate HTTP protocol.
+    pub(crate) fn detect<B>(req: &Request<B>) -> Option<Self> {
+        let content_type = req.headers().get(header::CONTENT_TYPE)?.to_str().ok()?;
+        let content_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        match content_type {
+            "application/grpc" | "application/grpc+proto" => Some(GrpcProtocol::Grpc),
+            "application/proto"
+                if req.method() == Method::POST
+                    && req.headers().contains_key(CONNECT_PROTOCOL_VERSION) =>
+            {
+                Some(GrpcProtocol::Connect)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn error_response(self, error: HandlerError) -> Response<ResponseBody> {
+        let code = grpc_code(&error);
+        let message = match &error {
+            HandlerError::Invocation(error) => error.message().to_owned(),
+            error => error.to_string(),
+        };
+        debug!(?code, "Complete gRPC request with a failure: {message}");
+
+        match self {
+            // Trailers-only response, the status is carried by the headers
+            GrpcProtocol::Grpc => {
+                let mut response = Response::new(Full::default().boxed_unsync());
+                *response.headers_mut() = status_headers(code, &message);
+                response
+                    .headers_mut()
+                    .insert(header::CONTENT_TYPE, APPLICATION_GRPC);
+                response
+            }
+            GrpcProtocol::Connect => {
+                let (connect_code, status_code) = connect_code(code);
+                Response::builder()
+                    .status(status_code)
+                    .header(header::CONTENT_TYPE, APPLICATION_JSON)
+                    .body(
+                        Full::new(Bytes::from(
+                            serde_json::to_vec(&ConnectError {
+                                code: connect_code,
+                                message,
+                            })
+                            .expect("Serializing ConnectError should not fail"),
+                        ))
+                        .boxed_unsync(),
+                    )
+                    .unwrap()
+            }
+        }
+    }
+}
+
+pub(crate) struct GrpcRequestType {
+    pub(crate) protocol: GrpcProtocol,
+    pub(crate) service: String,
+    pub(crate) method: String,
+    pub(crate) key: Option<String>,
+}
+
+impl GrpcRequestType {
+    pub(crate) fn from_request<B>(
+        protocol: GrpcProtocol,
+        req: &Request<B>,
+    ) -> Result<Self, HandlerError> {
+        let mut path_parts = req.uri().path().split('/').skip(1);
+        let service = path_parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .ok_or(HandlerError::BadGrpcPath)?
+            .to_owned();
+        let method = path_parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .ok_or(HandlerError::BadGrpcPath)?
+            .to_owned();
+        if path_parts.next().is_some() {
+            return Err(HandlerError::BadGrpcPath);
+        }
+
+        let key = req
+            .headers()
+            .get(X_RESTATE_KEY)
+            .map(|key| {
+                key.to_str()
+                    .map(ToOwned::to_owned)
+                    .map_err(|e| HandlerError::BadHeader(X_RESTATE_KEY, e))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            protocol,
+            service,
+            method,
+            key,
+        })
+    }
+}
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
+{
+    pub(crate) async fn handle_grpc<B>(
+        self,
+        req: Request<B>,
+        grpc_request: GrpcRequestType,
+    ) -> Result<Response<ResponseBody>, HandlerError>
+    where
+        B: http_body::Body + Send + 'static,
+        <B as http_body::Body>::Data: Send + 'static,
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let GrpcRequestType {
+            protocol,
+            service,
+            method,
+            key,
+        } = grpc_request;
+
+        if service == GRPC_REFLECTION_SERVICE {
+            let reflection = self.grpc.as_ref().is_some_and(|grpc| grpc.reflection);
+            // Reflection is a bidirectional stream, which Connect doesn't support for HTTP/1
+            if !reflection || method != GRPC_REFLECTION_METHOD || protocol != GrpcProtocol::Grpc {
+                return Err(HandlerError::GrpcMethodNotFound(service, method));
+            }
+            return Ok(self.handle_grpc_reflection(req));
+        }
+
+        let handler = {
+            let schemas = self.schemas.pinned();
+            schemas
+                .resolve_latest_service_protobuf(&service)
+                .and_then(|protobuf| {
+                    protobuf
+                        .resolve_method(&method)
+                        .map(|(handler, _)| handler.to_owned())
+                })
+                .ok_or_else(|| HandlerError::GrpcMethodNotFound(service.clone(), method.clone()))?
+        };
+        let service_type = self
+            .schemas
+            .pinned()
+            .resolve_latest_service_type(&service)
+            .ok_or_else(|| HandlerError::ServiceNotFound(service.clone()))?;
+        let target = if service_type.is_keyed() {
+            TargetType::Keyed {
+                key: key.ok_or_else(|| HandlerError::MissingGrpcKey(service.clone()))?,
+            }
+        } else {
+            TargetType::Unkeyed
+        };
+
+        let (mut parts, body) = req.into_parts();
+        let body = collect_limited_body(body, GRPC_FRAME_PREFIX_LEN + MAX_MESSAGE_SIZE).await?;
+        let message = match protocol {
+            GrpcProtocol::Grpc => decode_unary_message(body)?,
+            GrpcProtocol::Connect => body,
+        };
+
+        for name in TRANSPORT_HEADERS {
+            parts.headers.remove(name);
+        }
+        parts
+            .headers
+            .insert(header::CONTENT_TYPE, APPLICATION_PROTO);
+
+        let mut response = self
+            .handle_service_request(
+                Request::from_parts(parts, Full::new(message)),
+                ServiceRequestType {
+                    name: service,
+                    handler,
+                    target,
+                    invoke_ty: InvokeType::Call,
+                },
+            )
+            .await?;
+        if let Some(error) = response.extensions_mut().remove::<InvocationError>() {
+            return Err(HandlerError::Invocation(error));
+        }
+
+        let (mut parts, body) = response.into_parts();
+        let Ok(payload) = body.collect().await.map(|body| body.to_bytes());
+        Ok(match protocol {
+            GrpcProtocol::Grpc => {
+                parts.headers.insert(header::CONTENT_TYPE, APPLICATION_GRPC);
+                Response::from_parts(parts, grpc_body(futures::stream::iter([Ok(payload)])))
+            }
+            GrpcProtocol::Connect => {
+                parts
+                    .headers
+                    .insert(header::CONTENT_TYPE, APPLICATION_PROTO);
+                Response::from_parts(parts, Full::new(payload).boxed_unsync())
+            }
+        })
+    }
+
+    /// Serves the `grpc.reflection.v1.ServerReflection` service from the registered protobuf
+    /// schemas, answering every request message as soon as it's received.
+    fn handle_grpc_reflection<B>(&self, req: Request<B>) -> Response<ResponseBody>
+    where
+        B: http_body::Body + Send + 'static,
+        <B as http_body::Body>::Data: Send + 'static,
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let schemas = self.schemas.clone();
+        let responses = decode_messages(req.into_body()).map(move |message| {
+            let request = ServerReflectionRequest::decode(message?)
+                .map_err(|e| Status::invalid_argument(format!("bad reflection request: {e}")))?;
+            Ok(Bytes::from(
+                reflection_response(&*schemas.pinned(), request).encode_to_vec(),
+            ))
+        });
+
+        Response::builder()
+            .header(header::CONTENT_TYPE, APPLICATION_GRPC)
+            .body(grpc_body(responses))
+            .unwrap()
+    }
+}
+
+#[derive(Serialize)]
+struct ConnectError {
+    code: &'static str,
+    message: String,
+}
+
+/// Maps the error to a gRPC status code. Invocation errors with a code between 1 and 16 are
+/// considered gRPC codes already, the other ones are mapped like HTTP status codes.
+fn grpc_code(error: &HandlerError) -> Code {
+    let code = match error {
+        HandlerError::Invocation(error) => u16::from(error.code()),
+        error => error.status_code().as_u16(),
+    };
+    match code {
+        1..=16 => Code::from_i32(code.into()),
+        400 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 | 410 => Code::NotFound,
+        409 => Code::Aborted,
+        412 => Code::FailedPrecondition,
+        413 | 429 => Code::ResourceExhausted,
+        // 470 is the Restate code for invocations that are not ready yet
+        470 | 503 => Code::Unavailable,
+        499 => Code::Cancelled,
+        501 => Code::Unimplemented,
+        504 => Code::DeadlineExceeded,
+        500 => Code::Internal,
+        _ => Code::Unknown,
+    }
+}
+
+/// Connect name and HTTP status of the gRPC code, see
+/// <https://connectrpc.com/docs/protocol#error-codes>.
+fn connect_code(code: Code) -> (&'static str, StatusCode) {
+    match code {
+        Code::Ok => ("ok", StatusCode::OK),
+        Code::Cancelled => ("canceled", StatusCode::from_u16(499).unwrap()),
+        Code::Unknown => ("unknown", StatusCode::INTERNAL_SERVER_ERROR),
+        Code::InvalidArgument => ("invalid_argument", StatusCode::BAD_REQUEST),
+        Code::DeadlineExceeded => ("deadline_exceeded", StatusCode::GATEWAY_TIMEOUT),
+        Code::NotFound => ("not_found", StatusCode::NOT_FOUND),
+        Code::AlreadyExists => ("already_exists", StatusCode::CONFLICT),
+        Code::PermissionDenied => ("permission_denied", StatusCode::FORBIDDEN),
+        Code::ResourceExhausted => ("resource_exhausted", StatusCode::TOO_MANY_REQUESTS),
+        Code::FailedPrecondition => ("failed_precondition", StatusCode::BAD_REQUEST),
+        Code::Aborted => ("aborted", StatusCode::CONFLICT),
+        Code::OutOfRange => ("out_of_range", StatusCode::BAD_REQUEST),
+        Code::Unimplemented => ("unimplemented", StatusCode::NOT_IMPLEMENTED),
+        Code::Internal => ("internal", StatusCode::INTERNAL_SERVER_ERROR),
+        Code::Unavailable => ("unavailable", StatusCode::SERVICE_UNAVAILABLE),
+        Code::DataLoss => ("data_loss", StatusCode::INTERNAL_SERVER_ERROR),
+        Code::Unauthenticated => ("unauthenticated", StatusCode::UNAUTHORIZED),
+    }
+}
+
+fn status_headers(code: Code, message: &str) -> HeaderMap {
+    let mut headers = HeaderMap::with_capacity(2);
+    headers.insert(GRPC_STATUS, HeaderValue::from(code as i32));
+    if !message.is_empty() {
+        // The message is percent encoded, hence always a valid header value
+        headers.insert(
+            GRPC_MESSAGE,
+            HeaderValue::from_str(&urlencoding::encode(message))
+                .expect("percent encoded message is a valid header value"),
+        );
+    }
+    headers
+}
+
+/// Body of a gRPC response: the length prefixed messages, followed by the status trailers.
+fn grpc_body<S>(messages: S) -> ResponseBody
+where
+    S: Stream<Item = Result<Bytes, Status>> + Send + 'static,
+{
+    let frames = futures::stream::unfold(Some(Box::pin(messages)), |messages| async move {
+        let mut messages = messages?;
+        let frame = match messages.next().await {
+            Some(Ok(message)) => {
+                let frame = Frame::data(encode_message(message));
+                return Some((Ok::<_, Infallible>(frame), Some(messages)));
+            }
+            Some(Err(status)) => Frame::trailers(status_headers(status.code(), status.message())),
+            None => Frame::trailers(status_headers(Code::Ok, "")),
+        };
+        Some((Ok(frame), None))
+    });
+    StreamBody::new(frames).boxed_unsync()
+}
+
+fn encode_message(message: Bytes) -> Bytes {
+    let mut frame = BytesMut::with_capacity(GRPC_FRAME_PREFIX_LEN + message.len());
+    // uncompressed
+    frame.put_u8(0);
+    frame.put_u32(message.len() as u32);
+    frame.put(message);
+    frame.freeze()
+}
+
+/// Decodes the next length prefixed message from the buffer, if it was fully received.
+fn decode_message(buf: &mut BytesMut) -> Result<Option<Bytes>, &'static str> {
+    if buf.len() < GRPC_FRAME_PREFIX_LEN {
+        return Ok(None);
+    }
+    match buf[0] {
+        0 => {}
+        1 => return Err("compressed messages are not supported"),
+        _ => return Err("invalid compression flag"),
+    }
+    let len = u32::from_be_bytes(buf[1..GRPC_FRAME_PREFIX_LEN].try_into().unwrap()) as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err("message exceeds the maximum size");
+    }
+    if buf.len() < GRPC_FRAME_PREFIX_LEN + len {
+        return Ok(None);
+    }
+    buf.advance(GRPC_FRAME_PREFIX_LEN);
+    Ok(Some(buf.split_to(len).freeze()))
+}
+
+fn decode_unary_message(body: Bytes) -> Result<Bytes, HandlerError> {
+    let mut buf = BytesMut::from(body);
+    let message = decode_message(&mut buf)
+        .map_err(HandlerError::BadGrpcMessage)?
+        .ok_or(HandlerError::BadGrpcMessage("truncated message"))?;
+    if !buf.is_empty() {
+        return Err(HandlerError::BadGrpcMessage(
+            "expected a single message for a unary method",
+        ));
+    }
+    Ok(message)
+}
+
+/// Decodes the length prefixed messages of a streaming request body, as they're received.
+fn decode_messages<B>(body: B) -> impl Stream<Item = Result<Bytes, Status>> + Send + 'static
+where
+    B: http_body::Body + Send + 'static,
+    <B as http_body::Body>::Data: Send + 'static,
+    <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+{
+    let chunks = Box::pin(body).into_data_stream();
+    futures::stream::unfold(Some((chunks, BytesMut::new())), |state| async move {
+        let (mut chunks, mut buf) = state?;
+        loop {
+            match decode_message(&mut buf) {
+                Ok(Some(message)) => return Some((Ok(message), Some((chunks, buf)))),
+                Ok(None) => {}
+                Err(err) => return Some((Err(Status::invalid_argument(err)), None)),
+            }
+            match chunks.next().await {
+                Some(Ok(chunk)) => buf.put(chunk),
+                Some(Err(err)) => {
+                    return Some((
+                        Err(Status::internal(format!("cannot read body: {err}"))),
+                        None,
+                    ));
+                }
+                None if buf.is_empty() => return None,
+                None => {
+                    return Some((Err(Status::invalid_argument("truncated message")), None));
+                }
+            }
+        }
+    })
+}
+
+fn reflection_response<Schemas: ServiceMetadataResolver>(
+    schemas: &Schemas,
+    request: ServerReflectionRequest,
+) -> ServerReflectionResponse {
+    let message_response = match &request.message_request {
+        Some(MessageRequest::ListServices(_)) => {
+            let mut service: Vec<_> = schemas
+                .list_services()
+                .into_iter()
+                .filter(|service| {
+                    service.public
+                        && schemas
+                            .resolve_latest_service_protobuf(&service.name)
+                            .is_some()
+                })
+                .map(|service| ServiceResponse { name: service.name })
+                .collect();
+            service.push(ServiceResponse {
+                name: GRPC_REFLECTION_SERVICE.to_owned(),
+            });
+            MessageResponse::ListServicesResponse(ListServiceResponse { service })
+        }
+        Some(MessageRequest::FileContainingSymbol(symbol)) => {
+            file_descriptor_response(schemas, |file| declares_symbol(file, symbol))
+        }
+        Some(MessageRequest::FileByFilename(filename)) => {
+            file_descriptor_response(schemas, |file| file.name() == filename)
+        }
+        _ => MessageResponse::ErrorResponse(ErrorResponse {
+            error_code: Code::Unimplemented as i32,
+            error_message:
+                "only list_services, file_by_filename and file_containing_symbol are supported"
+                    .to_owned(),
+        }),
+    };
+
+    ServerReflectionResponse {
+        valid_host: request.host.clone(),
+        original_request: Some(request),
+        message_response: Some(message_response),
+    }
+}
+
+/// Looks up the first file matching the predicate in the descriptor sets of the services, and
+/// returns it followed by its transitive dependencies.
+fn file_descriptor_response<Schemas: ServiceMetadataResolver>(
+    schemas: &Schemas,
+    predicate: impl Fn(&FileDescriptorProto) -> bool,
+) -> MessageResponse {
+    // Private services can't be invoked through the ingress, so their schemas are not exposed
+    for service in schemas.list_services().into_iter().filter(|s| s.public) {
+        let Some(protobuf) = schemas.resolve_latest_service_protobuf(&service.name) else {
+            continue;
+        };
+        let Ok(descriptor_set) = FileDescriptorSet::decode(protobuf.file_descriptor_set) else {
+            continue;
+        };
+        let Some(file) = descriptor_set.file.iter().find(|file| predicate(file)) else {
+            continue;
+        };
+
+        let mut file_descriptor_proto = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([file]);
+        while let Some(file) = queue.pop_front() {
+            if !visited.insert(file.name()) {
+                continue;
+            }
+            file_descriptor_proto.push(file.encode_to_vec());
+            queue.extend(
+                file.dependency
+                    .iter()
+                    .filter_map(|dep| descriptor_set.file.iter().find(|f| f.name() == dep)),
+            );
+        }
+        return MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+            file_descriptor_proto,
+        });
+    }
+
+    MessageResponse::ErrorResponse(ErrorResponse {
+        error_code: Code::NotFound as i32,
+        error_message: "file not found".to_owned(),
+    })
+}
+
+/// Whether the file declares the fully qualified symbol, being a service, a method, a message or
+/// an enum.
+fn declares_symbol(file: &FileDescriptorProto, symbol: &str) -> bool {
+    let name = if file.package().is_empty() {
+        symbol
+    } else {
+        match symbol
+            .strip_prefix(file.package())
+            .and_then(|name| name.strip_prefix('.'))
+        {
+            Some(name) => name,
+            None => return false,
+        }
+    };
+
+    file.service.iter().any(|service| {
+        name == service.name()
+            || name
+                .strip_prefix(service.name())
+                .and_then(|method| method.strip_prefix('.'))
+                .is_some_and(|method| service.method.iter().any(|m| m.name() == method))
+    }) || file.enum_type.iter().any(|e| e.name() == name)
+        || file
+            .message_type
+            .iter()
+            .any(|message| message_declares_symbol(message, name))
+}
+
+fn message_declares_symbol(message: &DescriptorProto, name: &str) -> bool {
+    if name == message.name() {
+        return true;
+    }
+    let Some(nested) = name
+        .strip_prefix(message.name())
+        .and_then(|nested| nested.strip_prefix('.'))
+    else {
+        return false;
+    };
+    message.enum_type.iter().any(|e| e.name() == nested)
+        || message
+            .nested_type
+            .iter()
+            .any(|message| message_declares_symbol(message, nested))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost_types::{EnumDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto};
+    use restate_types::errors::codes;
+
+    fn request(content_type: &str, connect: bool) -> Request<()> {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost/greeter.Greeter/Greet")
+            .header(header::CONTENT_TYPE, content_type);
+        if connect {
+            builder = builder.header(CONNECT_PROTOCOL_VERSION, "1");
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn detect_protocol() {
+        assert_eq!(
+            GrpcProtocol::detect(&request("application/grpc", false)),
+            Some(GrpcProtocol::Grpc)
+        );
+        assert_eq!(
+            GrpcProtocol::detect(&request("application/grpc+proto", false)),
+            Some(GrpcProtocol::Grpc)
+        );
+        assert_eq!(
+            GrpcProtocol::detect(&request("application/proto", true)),
+            Some(GrpcProtocol::Connect)
+        );
+        assert_eq!(
+            GrpcProtocol::detect(&request("application/proto", false)),
+            None
+        );
+        assert_eq!(
+            GrpcProtocol::detect(&request("application/grpc+json", false)),
+            None
+        );
+        assert_eq!(
+            GrpcProtocol::detect(&request("application/json", true)),
+            None
+        );
+    }
+
+    #[test]
+    fn message_framing() {
+        let message = Bytes::from_static(b"hello");
+        let encoded = encode_message(message.clone());
+        assert_eq!(&encoded[..GRPC_FRAME_PREFIX_LEN], &[0, 0, 0, 0, 5]);
+        assert_eq!(decode_unary_message(encoded.clone()).unwrap(), message);
+
+        // partially received messages are decoded once complete
+        let mut buf = BytesMut::from(&encoded[..3]);
+        assert_eq!(decode_message(&mut buf), Ok(None));
+        buf.extend_from_slice(&encoded[3..]);
+        buf.extend_from_slice(&encoded);
+        assert_eq!(decode_message(&mut buf), Ok(Some(message.clone())));
+        assert_eq!(decode_message(&mut buf), Ok(Some(message)));
+        assert!(buf.is_empty());
+
+        let mut compressed = BytesMut::from(&encoded[..]);
+        compressed[0] = 1;
+        assert!(matches!(
+            decode_unary_message(compressed.freeze()),
+            Err(HandlerError::BadGrpcMessage(_))
+        ));
+        assert!(matches!(
+            decode_unary_message(encoded.slice(..6)),
+            Err(HandlerError::BadGrpcMessage(_))
+        ));
+
+        let mut oversized = BytesMut::from(&encoded[..]);
+        oversized[1..GRPC_FRAME_PREFIX_LEN]
+            .copy_from_slice(&(MAX_MESSAGE_SIZE as u32 + 1).to_be_bytes());
+        assert!(decode_message(&mut oversized).is_err());
+    }
+
+    #[test]
+    fn status_mapping() {
+        let invocation_error = |code| HandlerError::Invocation(InvocationError::new(code, "boom"));
+
+        assert_eq!(
+            grpc_code(&invocation_error(codes::BAD_REQUEST)),
+            Code::InvalidArgument
+        );
+        assert_eq!(
+            grpc_code(&invocation_error(codes::NOT_FOUND)),
+            Code::NotFound
+        );
+        assert_eq!(
+            grpc_code(&invocation_error(codes::INTERNAL)),
+            Code::Internal
+        );
+        assert_eq!(grpc_code(&invocation_error(codes::ABORTED)), Code::Aborted);
+        assert_eq!(
+            grpc_code(&invocation_error(codes::NOT_READY)),
+            Code::Unavailable
+        );
+        assert_eq!(grpc_code(&invocation_error(7u16)), Code::PermissionDenied);
+        assert_eq!(grpc_code(&invocation_error(599u16)), Code::Unknown);
+        assert_eq!(
+            grpc_code(&HandlerError::GrpcMethodNotFound(
+                "greeter.Greeter".to_owned(),
+                "Greet".to_owned()
+            )),
+            Code::Unimplemented
+        );
+        assert_eq!(
+            grpc_code(&HandlerError::RateLimited(std::time::Duration::from_secs(
+                1
+            ))),
+            Code::ResourceExhausted
+        );
+        assert_eq!(
+            grpc_code(&HandlerError::BodyTooLarge(MAX_MESSAGE_SIZE)),
+            Code::ResourceExhausted
+        );
+
+        let response = GrpcProtocol::Grpc.error_response(invocation_error(codes::NOT_FOUND));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[GRPC_STATUS], "5");
+        assert_eq!(response.headers()[GRPC_MESSAGE], "boom");
+
+        let response = GrpcProtocol::Connect.error_response(invocation_error(codes::NOT_FOUND));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn reflection_symbols() {
+        let file = FileDescriptorProto {
+            name: Some("greeter.proto".to_owned()),
+            package: Some("greeter".to_owned()),
+            message_type: vec![DescriptorProto {
+                name: Some("GreetRequest".to_owned()),
+                enum_type: vec![EnumDescriptorProto {
+                    name: Some("Language".to_owned()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Greeter".to_owned()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("Greet".to_owned()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(declares_symbol(&file, "greeter.Greeter"));
+        assert!(declares_symbol(&file, "greeter.Greeter.Greet"));
+        assert!(declares_symbol(&file, "greeter.GreetRequest"));
+        assert!(declares_symbol(&file, "greeter.GreetRequest.Language"));
+        assert!(!declares_symbol(&file, "greeter.Greeter.Unknown"));
+        assert!(!declares_symbol(&file, "other.Greeter"));
+        assert!(!declares_symbol(&file, "greeterx.Greeter"));
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bytes::Bytes;
+use http::{Method, Request, Response, StatusCode, header};
+use http_body_util::Full;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use restate_types::identifiers::{IdempotencyId, InvocationId};
+use restate_types::invocation::InvocationQuery;
+use restate_types::net::partition_processor::{InvocationDescription, InvocationProgress};
+
+use super::invocation::InvocationEvent;
+use super::path_parsing::{IdempotencyRequestType, TargetType};
+use super::responses::X_RESTATE_ID;
+use super::{APPLICATION_JSON, Handler, HandlerError, check_principal};
+use crate::RequestDispatcher;
+use crate::partition_processor_rpc_client::DescribeInvocationResponse;
+
+// Response of GET /restate/idempotency/:service/:handler/:key
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IdempotentInvocationResponse {
+    invocation_id: InvocationId,
+    #[serde(flatten)]
+    progress: InvocationEvent,
+    /// Output of the invocation when completed successfully, if it's valid JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<Value>,
+    /// Output of the invocation when completed successfully, if it's not valid JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_base64: Option<String>,
+}
+
+impl From<InvocationDescription> for IdempotentInvocationResponse {
+    fn from(description: InvocationDescription) -> Self {
+        let (output, output_base64) = match description.output {
+            Some(output) => match serde_json::from_slice(&output) {
+                Ok(value) => (Some(value), None),
+                Err(_) => (None, Some(BASE64_STANDARD.encode(output))),
+            },
+            None => (None, None),
+        };
+        Self {
+            invocation_id: description.invocation_id,
+            progress: description.progress.into(),
+            output,
+            output_base64,
+        }
+    }
+}
+
+// Response of DELETE /restate/idempotency/:service/:handler/:key
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelledInvocationResponse {
+    invocation_id: InvocationId,
+}
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Schemas: Clone + Send + Sync + 'static,
+    Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
+{
+    /// Looks up or cancels the invocation submitted with an idempotency key, resolving it through
+    /// the idempotency table of the partition.
+    pub(crate) async fn handle_idempotency<B: http_body::Body>(
+        self,
+        req: Request<B>,
+        IdempotencyRequestType {
+            name,
+            target,
+            handler,
+            idempotency_key,
+        }: IdempotencyRequestType,
+    ) -> Result<Response<Full<Bytes>>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        check_principal(&req, &name, Some(&handler))?;
+
+        let idempotency_id = IdempotencyId::new(
+            name.into(),
+            match target {
+                TargetType::Unkeyed => None,
+                TargetType::Keyed { key } => Some(key.into()),
+            },
+            handler.into(),
+            idempotency_key.into(),
+        );
+
+        match *req.method() {
+            Method::GET => self.handle_idempotency_get(idempotency_id).await,
+            Method::DELETE => self.handle_idempotency_cancel(idempotency_id).await,
+            _ => Err(HandlerError::MethodNotAllowed),
+        }
+    }
+
+    async fn handle_idempotency_get(
+        self,
+        idempotency_id: IdempotencyId,
+    ) -> Result<Response<Full<Bytes>>, HandlerError> {
+        let description = self.describe_idempotent_invocation(&idempotency_id).await?;
+
+        Ok(Self::reply_with_json(
+            StatusCode::OK,
+            description.invocation_id,
+            &IdempotentInvocationResponse::from(description),
+        ))
+    }
+
+    async fn handle_idempotency_cancel(
+        self,
+        idempotency_id: IdempotencyId,
+    ) -> Result<Response<Full<Bytes>>, HandlerError> {
+        let description = self.describe_idempotent_invocation(&idempotency_id).await?;
+        if matches!(description.progress, InvocationProgress::Completed { .. }) {
+            return Err(HandlerError::InvocationAlreadyCompleted);
+        }
+
+        let invocation_id = description.invocation_id;
+        info!(
+            restate.invocation.id = %invocation_id,
+            "Cancelling invocation with idempotency key"
+        );
+        self.dispatcher.cancel_invocation(invocation_id).await?;
+
+        Ok(Self::reply_with_json(
+            StatusCode::ACCEPTED,
+            invocation_id,
+            &CancelledInvocationResponse { invocation_id },
+        ))
+    }
+
+    async fn describe_idempotent_invocation(
+        &self,
+        idempotency_id: &IdempotencyId,
+    ) -> Result<InvocationDescription, HandlerError> {
+        match self
+            .dispatcher
+            .describe_invocation(InvocationQuery::IdempotencyId(idempotency_id.clone()))
+            .await
+        {
+            Ok(DescribeInvocationResponse::Description(description)) => Ok(description),
+            Ok(DescribeInvocationResponse::NotFound) => Err(HandlerError::InvocationNotFound),
+            Err(e) => {
+                warn!(
+                    restate.invocation.query = ?idempotency_id,
+                    "Failed to describe invocation: {}",
+                    e,
+                );
+                Err(HandlerError::Unavailable)
+            }
+        }
+    }
+
+    fn reply_with_json(
+        status: StatusCode,
+        invocation_id: InvocationId,
+        body: &impl Serialize,
+    ) -> Response<Full<Bytes>> {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, APPLICATION_JSON)
+            .header(X_RESTATE_ID, invocation_id.to_string())
+            .body(Full::new(Bytes::from(
+                serde_json::to_vec(body).expect("Serializing the response should not fail"),
+            )))
+            .unwrap()
+    }
+}
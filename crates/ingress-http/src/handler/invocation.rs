@@ -8,21 +8,120 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::convert::Infallible;
+use std::time::{Duration, SystemTime};
+
 use bytes::Bytes;
-use http::{Method, Request, Response};
-use http_body_util::Full;
+use futures::{StreamExt, stream};
+use http::{HeaderValue, Method, Request, Response, StatusCode, header};
+use http_body::Frame;
+use http_body_util::{BodyExt, Full, StreamBody};
+use serde::Serialize;
 use tracing::warn;
 
-use super::Handler;
 use super::HandlerError;
 use super::path_parsing::{InvocationRequestType, InvocationTargetType, TargetType};
+use super::progress_poller::PolledProgress;
+use super::{Handler, ResponseBody, check_principal_allowed, full_response};
 use crate::RequestDispatcher;
+use crate::layers::auth::AuthenticatedPrincipal;
 use crate::partition_processor_rpc_client::{
-    AttachInvocationResponse, GetInvocationOutputResponse,
+    AttachInvocationResponse, DescribeInvocationResponse, GetInvocationOutputResponse,
 };
+use restate_types::errors::InvocationError;
 use restate_types::identifiers::IdempotencyId;
 use restate_types::invocation::InvocationQuery;
+use restate_types::net::partition_processor::InvocationProgress;
 use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::time::MillisSinceEpoch;
+
+const TEXT_EVENT_STREAM: HeaderValue = HeaderValue::from_static("text/event-stream");
+/// How long the events stream can be idle before a keepalive comment is sent.
+const EVENTS_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const KEEPALIVE_FRAME: &[u8] = b": keepalive\n\n";
+
+// Data of the events sent by /restate/invocation/:id/events, the event name is the status.
+// The events are sampled from the invocation status, so some intermediate ones can be missing.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct InvocationEvent {
+    status: &'static str,
+    #[serde(
+        with = "serde_with::As::<Option<serde_with::DisplayFromStr>>",
+        skip_serializing_if = "Option::is_none"
+    )]
+    execution_time: Option<humantime::Timestamp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attempt: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_count: Option<u32>,
+    #[serde(
+        with = "serde_with::As::<Option<serde_with::DisplayFromStr>>",
+        skip_serializing_if = "Option::is_none"
+    )]
+    next_retry_at: Option<humantime::Timestamp>,
+    /// Failure of the last attempt when retrying, or failure of the invocation when completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<InvocationError>,
+}
+
+impl InvocationEvent {
+    fn new(status: &'static str) -> Self {
+        Self {
+            status,
+            execution_time: None,
+            attempt: None,
+            retry_count: None,
+            next_retry_at: None,
+            error: None,
+        }
+    }
+
+    fn to_sse_frame(&self) -> Bytes {
+        format!(
+            "event: {}\ndata: {}\n\n",
+            self.status,
+            serde_json::to_string(self).expect("Serializing InvocationEvent should not fail")
+        )
+        .into()
+    }
+}
+
+impl From<InvocationProgress> for InvocationEvent {
+    fn from(progress: InvocationProgress) -> Self {
+        fn to_timestamp(millis: MillisSinceEpoch) -> humantime::Timestamp {
+            SystemTime::from(millis).into()
+        }
+
+        match progress {
+            InvocationProgress::Scheduled { execution_time } => InvocationEvent {
+                execution_time: execution_time.map(to_timestamp),
+                ..InvocationEvent::new("scheduled")
+            },
+            InvocationProgress::Inboxed => InvocationEvent::new("inboxed"),
+            InvocationProgress::Invoked { attempt } => InvocationEvent {
+                attempt: Some(attempt),
+                ..InvocationEvent::new("invoked")
+            },
+            InvocationProgress::Suspended => InvocationEvent::new("suspended"),
+            InvocationProgress::Paused => InvocationEvent::new("paused"),
+            InvocationProgress::Retrying {
+                retry_count,
+                last_failure,
+                next_retry_at,
+            } => InvocationEvent {
+                retry_count: Some(retry_count),
+                next_retry_at: next_retry_at.map(to_timestamp),
+                error: last_failure,
+                ..InvocationEvent::new("retrying")
+            },
+            InvocationProgress::Completed { failure } => InvocationEvent {
+                error: failure,
+                ..InvocationEvent::new("completed")
+            },
+        }
+    }
+}
 
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
 where
@@ -33,24 +132,96 @@ where
         self,
         req: Request<B>,
         invocation_request_type: InvocationRequestType,
-    ) -> Result<Response<Full<Bytes>>, HandlerError>
+    ) -> Result<Response<ResponseBody>, HandlerError>
     where
         <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
     {
         match invocation_request_type {
             InvocationRequestType::Attach(invocation_target_type) => {
-                self.handle_invocation_attach(
-                    req,
-                    Self::convert_to_invocation_query(invocation_target_type)?,
-                )
-                .await
+                let invocation_query = self
+                    .authorized_invocation_query(&req, invocation_target_type)
+                    .await?;
+                self.handle_invocation_attach(req, invocation_query)
+                    .await
+                    .map(full_response)
             }
             InvocationRequestType::GetOutput(invocation_target_type) => {
-                self.handle_invocation_get_output(
-                    req,
-                    Self::convert_to_invocation_query(invocation_target_type)?,
-                )
-                .await
+                let invocation_query = self
+                    .authorized_invocation_query(&req, invocation_target_type)
+                    .await?;
+                self.handle_invocation_get_output(req, invocation_query)
+                    .await
+                    .map(full_response)
+            }
+            InvocationRequestType::Events(invocation_target_type) => {
+                let invocation_query = self
+                    .authorized_invocation_query(&req, invocation_target_type)
+                    .await?;
+                self.handle_invocation_events(req, invocation_query).await
+            }
+        }
+    }
+
+    async fn authorized_invocation_query<B>(
+        &self,
+        req: &Request<B>,
+        invocation_target_type: InvocationTargetType,
+    ) -> Result<InvocationQuery, HandlerError> {
+        let invocation_query = Self::convert_to_invocation_query(invocation_target_type)?;
+        self.check_invocation_principal(
+            req.extensions().get::<AuthenticatedPrincipal>().cloned(),
+            &invocation_query,
+        )
+        .await?;
+        Ok(invocation_query)
+    }
+
+    /// Checks the allow rules of the authenticated principal, if any, against the target of the
+    /// invocation selected by the query. When the query doesn't name the target service, the
+    /// target is resolved through the partition processor.
+    pub(crate) async fn check_invocation_principal(
+        &self,
+        principal: Option<AuthenticatedPrincipal>,
+        invocation_query: &InvocationQuery,
+    ) -> Result<(), HandlerError> {
+        let Some(principal) = principal else {
+            return Ok(());
+        };
+
+        match invocation_query {
+            InvocationQuery::IdempotencyId(idempotency_id) => check_principal_allowed(
+                Some(&principal),
+                &idempotency_id.service_name,
+                Some(&*idempotency_id.service_handler),
+            ),
+            InvocationQuery::Workflow(service_id) => {
+                check_principal_allowed(Some(&principal), &service_id.service_name, None)
+            }
+            InvocationQuery::Invocation(_) => {
+                match self
+                    .dispatcher
+                    .describe_invocation(invocation_query.clone())
+                    .await
+                {
+                    Ok(DescribeInvocationResponse::Description(description)) => {
+                        check_principal_allowed(
+                            Some(&principal),
+                            description.invocation_target.service_name(),
+                            Some(&**description.invocation_target.handler_name()),
+                        )
+                    }
+                    Ok(DescribeInvocationResponse::NotFound) => {
+                        Err(HandlerError::InvocationNotFound)
+                    }
+                    Err(e) => {
+                        warn!(
+                            restate.invocation.query = ?invocation_query,
+                            "Failed to resolve the invocation target: {}",
+                            e,
+                        );
+                        Err(HandlerError::Unavailable)
+                    }
+                }
             }
         }
     }
@@ -165,4 +336,90 @@ where
                 .ok_or(HandlerError::NotFound)
         })
     }
+
+    /// Streams the status of the invocation as server-sent events, until it completes.
+    ///
+    /// The status is polled from the partition processor every 500ms and an event is sent when
+    /// it changed, so states shorter than that can be skipped. Clients must not rely on seeing
+    /// every transition, e.g. gaps in the `attempt` number of the `invoked` events mean that
+    /// some attempts were not observed.
+    pub(crate) async fn handle_invocation_events<B: http_body::Body>(
+        self,
+        req: Request<B>,
+        invocation_query: InvocationQuery,
+    ) -> Result<Response<ResponseBody>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Check HTTP Method
+        if req.method() != Method::GET {
+            return Err(HandlerError::MethodNotAllowed);
+        }
+
+        let mut progress_rx = self
+            .progress_pollers
+            .subscribe(self.dispatcher.clone(), invocation_query)
+            .map_err(|_| HandlerError::Unavailable)?;
+        let progress = match &*progress_rx
+            .wait_for(|progress| *progress != PolledProgress::Pending)
+            .await
+            .map_err(|_| HandlerError::Unavailable)?
+        {
+            PolledProgress::Progress(progress) => progress.clone(),
+            PolledProgress::NotFound => return Err(HandlerError::InvocationNotFound),
+            PolledProgress::Pending | PolledProgress::Unavailable => {
+                return Err(HandlerError::Unavailable);
+            }
+        };
+
+        let events = stream::unfold(
+            (progress_rx, Some(progress), None),
+            |(mut progress_rx, mut next_progress, last_progress)| async move {
+                loop {
+                    let progress = match next_progress.take() {
+                        Some(progress) => progress,
+                        None => {
+                            if matches!(last_progress, Some(InvocationProgress::Completed { .. })) {
+                                return None;
+                            }
+                            match tokio::time::timeout(
+                                EVENTS_KEEPALIVE_INTERVAL,
+                                progress_rx.changed(),
+                            )
+                            .await
+                            {
+                                Ok(Ok(())) => {}
+                                // The poller stopped, as the invocation is not found anymore
+                                Ok(Err(_)) => return None,
+                                Err(_elapsed) => {
+                                    // Keep the connection open through proxies and load balancers
+                                    return Some((
+                                        Bytes::from_static(KEEPALIVE_FRAME),
+                                        (progress_rx, None, last_progress),
+                                    ));
+                                }
+                            }
+                            match &*progress_rx.borrow_and_update() {
+                                PolledProgress::Progress(progress) => progress.clone(),
+                                _ => return None,
+                            }
+                        }
+                    };
+
+                    if last_progress.as_ref() != Some(&progress) {
+                        let frame = InvocationEvent::from(progress.clone()).to_sse_frame();
+                        return Some((frame, (progress_rx, None, Some(progress))));
+                    }
+                }
+            },
+        )
+        .map(|frame| Ok::<_, Infallible>(Frame::data(frame)));
+
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, TEXT_EVENT_STREAM)
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(StreamBody::new(events).boxed_unsync())
+            .unwrap())
+    }
 }
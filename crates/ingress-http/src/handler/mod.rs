@@ -9,39 +9,113 @@
 // by the Apache License, Version 2.0.
 
 mod awakeables;
+mod batch;
+mod cors;
 mod error;
+mod grpc;
 mod health;
+mod idempotency;
 mod invocation;
 mod path_parsing;
+mod progress_poller;
+mod rate_limit;
 mod responses;
+mod schema_validation;
 mod service_handler;
 #[cfg(test)]
 mod tests;
 mod tracing;
+mod webhook;
 mod workflow;
 
 use std::convert::Infallible;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
+pub(crate) use cors::CorsLayer;
+use cors::CorsPolicy;
 use error::HandlerError;
 use futures::FutureExt;
 use futures::future::BoxFuture;
-use http_body_util::Full;
+use grpc::{GrpcProtocol, GrpcRequestType};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full};
 use hyper::http::HeaderValue;
 use hyper::{Request, Response};
 use path_parsing::RequestType;
+use progress_poller::ProgressPollers;
+use rate_limit::RateLimiter;
+use restate_types::config::{IngressCorsOptions, IngressGrpcOptions, IngressRateLimitOptions};
 use restate_types::live::Live;
 use restate_types::schema::invocation_target::InvocationTargetResolver;
 use restate_types::schema::service::ServiceMetadataResolver;
+use schema_validation::InputSchemaValidator;
 
 use super::*;
+use crate::layers::auth::AuthenticatedPrincipal;
 
 const APPLICATION_JSON: HeaderValue = HeaderValue::from_static("application/json");
 
+/// Body of the ingress responses, which can be either buffered or streamed.
+pub(crate) type ResponseBody = UnsyncBoxBody<Bytes, Infallible>;
+
+fn full_response(response: Response<Full<Bytes>>) -> Response<ResponseBody> {
+    response.map(BodyExt::boxed_unsync)
+}
+
+/// Collects the request body, failing with [`HandlerError::BodyTooLarge`] if it exceeds `limit`
+/// bytes.
+async fn collect_limited_body<B>(body: B, limit: usize) -> Result<Bytes, HandlerError>
+where
+    B: http_body::Body,
+    <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+{
+    match http_body_util::Limited::new(body, limit).collect().await {
+        Ok(collected) => Ok(collected.to_bytes()),
+        Err(err) if err.is::<http_body_util::LengthLimitError>() => {
+            Err(HandlerError::BodyTooLarge(limit))
+        }
+        Err(err) => Err(HandlerError::Body(anyhow::anyhow!(err))),
+    }
+}
+
+/// Checks the allow rules of the principal authenticated by the auth layer, if any. When
+/// `handler` is `None`, the principal must be allowed to invoke every handler of the service.
+fn check_principal<B>(
+    req: &Request<B>,
+    service: &str,
+    handler: Option<&str>,
+) -> Result<(), HandlerError> {
+    check_principal_allowed(
+        req.extensions().get::<AuthenticatedPrincipal>(),
+        service,
+        handler,
+    )
+}
+
+fn check_principal_allowed(
+    principal: Option<&AuthenticatedPrincipal>,
+    service: &str,
+    handler: Option<&str>,
+) -> Result<(), HandlerError> {
+    match principal {
+        Some(principal) if !principal.is_allowed(service, handler) => Err(HandlerError::Forbidden(
+            principal.principal().to_owned(),
+            handler.map_or_else(|| service.to_owned(), |h| format!("{service}/{h}")),
+        )),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct Handler<Schemas, Dispatcher> {
     schemas: Live<Schemas>,
     dispatcher: Dispatcher,
+    cors: Option<Arc<IngressCorsOptions>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    grpc: Option<Arc<IngressGrpcOptions>>,
+    input_validator: Arc<InputSchemaValidator>,
+    progress_pollers: Arc<ProgressPollers>,
 }
 
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher> {
@@ -49,8 +123,32 @@ impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher> {
         Self {
             schemas,
             dispatcher,
+            cors: None,
+            rate_limiter: None,
+            grpc: None,
+            input_validator: Arc::default(),
+            progress_pollers: Arc::default(),
         }
     }
+
+    /// Applies the given CORS policy, which can be further overridden per service. The policy is
+    /// enforced by the [`CorsLayer`], wrapping the authentication as well.
+    pub(crate) fn with_cors(mut self, cors: Option<IngressCorsOptions>) -> Self {
+        self.cors = cors.map(Arc::new);
+        self
+    }
+
+    /// Applies the given rate limits.
+    pub(crate) fn with_rate_limit(mut self, rate_limit: Option<IngressRateLimitOptions>) -> Self {
+        self.rate_limiter = rate_limit.map(|options| Arc::new(RateLimiter::new(options)));
+        self
+    }
+
+    /// Serves the gRPC and Connect protocols, next to the Restate HTTP protocol.
+    pub(crate) fn with_grpc(mut self, grpc: Option<IngressGrpcOptions>) -> Self {
+        self.grpc = grpc.map(Arc::new);
+        self
+    }
 }
 
 impl<Schemas, Dispatcher, Body> tower::Service<Request<Body>> for Handler<Schemas, Dispatcher>
@@ -61,7 +159,7 @@ where
     <Body as http_body::Body>::Data: Send + 'static,
     <Body as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
 {
-    type Response = Response<Full<Bytes>>;
+    type Response = Response<ResponseBody>;
     type Error = Infallible;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -70,31 +168,97 @@ where
     }
 
     fn call(&mut self, req: Request<Body>) -> Self::Future {
-        let res = self.parse_path(req.uri());
+        let grpc_protocol = self.grpc_protocol(&req);
+        let res = self.request_type(grpc_protocol, &req);
 
         let mut this = self.clone();
         async move {
-            match res? {
-                RequestType::Health => this.handle_health(req),
+            let request_type = res?;
+            if let Some(rate_limiter) = &this.rate_limiter {
+                rate_limiter.check(&req, &request_type)?;
+            }
+
+            match request_type {
+                RequestType::Health => this.handle_health(req).map(full_response),
                 RequestType::OpenAPI => {
                     // TODO
                     Err(HandlerError::NotImplemented)
                 }
-                RequestType::Awakeable(awakeable_request) => {
-                    this.handle_awakeable(req, awakeable_request).await
-                }
-                RequestType::Service(service_request) => {
-                    this.handle_service_request(req, service_request).await
-                }
+                RequestType::Awakeable(awakeable_request) => this
+                    .handle_awakeable(req, awakeable_request)
+                    .await
+                    .map(full_response),
+                RequestType::Service(service_request) => this
+                    .handle_service_request(req, service_request)
+                    .await
+                    .map(full_response),
                 RequestType::Invocation(invocation_request) => {
                     this.handle_invocation(req, invocation_request).await
                 }
-                RequestType::Workflow(workflow_request) => {
-                    this.handle_workflow(req, workflow_request).await
-                }
+                RequestType::Idempotency(idempotency_request) => this
+                    .handle_idempotency(req, idempotency_request)
+                    .await
+                    .map(full_response),
+                RequestType::Workflow(workflow_request) => this
+                    .handle_workflow(req, workflow_request)
+                    .await
+                    .map(full_response),
+                RequestType::Webhook(webhook_request) => this
+                    .handle_webhook(req, webhook_request)
+                    .await
+                    .map(full_response),
+                RequestType::Batch => this.handle_batch(req).await,
+                RequestType::Grpc(grpc_request) => this.handle_grpc(req, grpc_request).await,
             }
         }
-        .map(|r| Ok::<_, Infallible>(r.unwrap_or_else(|e| e.into_response())))
+        .map(move |r| {
+            Ok::<_, Infallible>(r.unwrap_or_else(|e| match grpc_protocol {
+                Some(protocol) => protocol.error_response(e),
+                None => full_response(e.into_response()),
+            }))
+        })
         .boxed()
     }
 }
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Schemas: ServiceMetadataResolver + Clone + Send + Sync + 'static,
+{
+    fn grpc_protocol<B>(&self, req: &Request<B>) -> Option<GrpcProtocol> {
+        self.grpc.as_ref().and_then(|_| GrpcProtocol::detect(req))
+    }
+
+    fn request_type<B>(
+        &mut self,
+        grpc_protocol: Option<GrpcProtocol>,
+        req: &Request<B>,
+    ) -> Result<RequestType, HandlerError> {
+        match grpc_protocol {
+            Some(protocol) => GrpcRequestType::from_request(protocol, req).map(RequestType::Grpc),
+            None => self.parse_path(req.uri()),
+        }
+    }
+
+    /// CORS policy of the request, merging the configured policy with the overrides of the
+    /// invoked service. Returns `None` if no policy is configured or it's not a cross-origin
+    /// request.
+    pub(crate) fn cors_policy<B>(&mut self, req: &Request<B>) -> Option<CorsPolicy> {
+        let options = Arc::clone(self.cors.as_ref()?);
+        if !req.headers().contains_key(http::header::ORIGIN) {
+            return None;
+        }
+
+        let overrides = self
+            .request_type(self.grpc_protocol(req), req)
+            .ok()
+            .as_ref()
+            .and_then(RequestType::service_name)
+            .and_then(|service_name| {
+                self.schemas
+                    .pinned()
+                    .resolve_latest_service_cors(service_name)
+            });
+        CorsPolicy::for_request(req, &options, overrides)
+    }
+}
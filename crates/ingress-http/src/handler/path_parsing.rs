@@ -12,6 +12,7 @@ use http::Uri;
 
 use super::Handler;
 use super::HandlerError;
+use super::grpc::GrpcRequestType;
 use restate_types::schema::service::ServiceMetadataResolver;
 
 pub(crate) enum WorkflowRequestType {
@@ -55,6 +56,7 @@ pub(crate) enum InvocationTargetType {
 pub(crate) enum InvocationRequestType {
     Attach(InvocationTargetType),
     GetOutput(InvocationTargetType),
+    Events(InvocationTargetType),
 }
 
 impl InvocationRequestType {
@@ -112,15 +114,75 @@ impl InvocationRequestType {
             )
         };
 
-        // Output or attach
+        // Output, attach or events
         match last_chunk {
             "output" => Ok(InvocationRequestType::GetOutput(invocation_target)),
             "attach" => Ok(InvocationRequestType::Attach(invocation_target)),
+            "events" => Ok(InvocationRequestType::Events(invocation_target)),
             _ => Err(HandlerError::NotFound),
         }
     }
 }
 
+pub(crate) struct IdempotencyRequestType {
+    pub(crate) name: String,
+    pub(crate) target: TargetType,
+    pub(crate) handler: String,
+    pub(crate) idempotency_key: String,
+}
+
+impl IdempotencyRequestType {
+    fn from_path_chunks<'a, Schemas>(
+        mut path_parts: impl Iterator<Item = &'a str>,
+        schemas: &Schemas,
+    ) -> Result<Self, HandlerError>
+    where
+        Schemas: ServiceMetadataResolver + Clone + Send + Sync + 'static,
+    {
+        let name = path_parts
+            .next()
+            .ok_or(HandlerError::BadIdempotencyPath)?
+            .to_owned();
+
+        // We need to query the service type before continuing to parse
+        let service_type = schemas
+            .resolve_latest_service_type(&name)
+            .ok_or_else(|| HandlerError::ServiceNotFound(name.clone()))?;
+
+        let target = if service_type.is_keyed() {
+            TargetType::Keyed {
+                key: urlencoding::decode(
+                    path_parts.next().ok_or(HandlerError::BadIdempotencyPath)?,
+                )
+                .map_err(HandlerError::UrlDecodingError)?
+                .into_owned(),
+            }
+        } else {
+            TargetType::Unkeyed
+        };
+
+        let handler = path_parts
+            .next()
+            .ok_or(HandlerError::BadIdempotencyPath)?
+            .to_owned();
+        let idempotency_key =
+            urlencoding::decode(path_parts.next().ok_or(HandlerError::BadIdempotencyPath)?)
+                .map_err(HandlerError::UrlDecodingError)?
+                .into_owned();
+
+        if path_parts.next().is_some() {
+            return Err(HandlerError::BadIdempotencyPath);
+        }
+
+        Ok(Self {
+            name,
+            target,
+            handler,
+            idempotency_key,
+        })
+    }
+}
+
 pub(crate) enum AwakeableRequestType {
     Resolve { awakeable_id: String },
     Reject { awakeable_id: String },
@@ -145,6 +207,32 @@ impl AwakeableRequestType {
     }
 }
 
+pub(crate) struct WebhookRequestType {
+    pub(crate) name: String,
+    pub(crate) key: Option<String>,
+}
+
+impl WebhookRequestType {
+    fn from_path_chunks<'a>(
+        mut path_parts: impl Iterator<Item = &'a str>,
+        query: Option<&str>,
+    ) -> Result<Self, HandlerError> {
+        let name = path_parts
+            .next()
+            .ok_or(HandlerError::BadWebhookPath)?
+            .to_owned();
+        if path_parts.next().is_some() {
+            return Err(HandlerError::BadWebhookPath);
+        }
+
+        let key = url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+            .find(|(k, _)| k == "key")
+            .map(|(_, v)| v.into_owned());
+
+        Ok(Self { name, key })
+    }
+}
+
 pub(crate) enum TargetType {
     Unkeyed,
     Keyed { key: String },
@@ -217,8 +305,46 @@ pub(crate) enum RequestType {
     OpenAPI,
     Awakeable(AwakeableRequestType),
     Invocation(InvocationRequestType),
+    Idempotency(IdempotencyRequestType),
     Service(ServiceRequestType),
     Workflow(WorkflowRequestType),
+    Webhook(WebhookRequestType),
+    Batch,
+    Grpc(GrpcRequestType),
+}
+
+impl RequestType {
+    /// Name of the service targeted by the request, if any.
+    pub(crate) fn service_name(&self) -> Option<&str> {
+        match self {
+            RequestType::Service(ServiceRequestType { name, .. })
+            | RequestType::Idempotency(IdempotencyRequestType { name, .. })
+            | RequestType::Grpc(GrpcRequestType { service: name, .. })
+            | RequestType::Workflow(
+                WorkflowRequestType::Attach(name, _) | WorkflowRequestType::GetOutput(name, _),
+            ) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Key of the virtual object or workflow targeted by the request, if any.
+    pub(crate) fn object_key(&self) -> Option<&str> {
+        match self {
+            RequestType::Service(ServiceRequestType {
+                target: TargetType::Keyed { key },
+                ..
+            })
+            | RequestType::Idempotency(IdempotencyRequestType {
+                target: TargetType::Keyed { key },
+                ..
+            })
+            | RequestType::Grpc(GrpcRequestType { key: Some(key), .. })
+            | RequestType::Workflow(
+                WorkflowRequestType::Attach(_, key) | WorkflowRequestType::GetOutput(_, key),
+            ) => Some(key),
+            _ => None,
+        }
+    }
 }
 
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
@@ -241,9 +367,20 @@ where
                 "invocation" => Ok(RequestType::Invocation(
                     InvocationRequestType::from_path_chunks(path_parts, schema)?,
                 )),
+                "idempotency" => Ok(RequestType::Idempotency(
+                    IdempotencyRequestType::from_path_chunks(path_parts, schema)?,
+                )),
                 "workflow" => Ok(RequestType::Workflow(
                     WorkflowRequestType::from_path_chunks(path_parts)?,
                 )),
+                "webhook" => Ok(RequestType::Webhook(WebhookRequestType::from_path_chunks(
+                    path_parts,
+                    uri.query(),
+                )?)),
+                "batch" => match path_parts.next() {
+                    None => Ok(RequestType::Batch),
+                    Some(_) => Err(HandlerError::NotFound),
+                },
                 _ => Err(HandlerError::NotFound),
             },
             "openapi" => Ok(RequestType::OpenAPI),
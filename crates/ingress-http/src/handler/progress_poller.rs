@@ -0,0 +1,154 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+use restate_core::{ShutdownError, TaskCenter, TaskKind};
+use restate_types::invocation::InvocationQuery;
+use restate_types::net::partition_processor::InvocationProgress;
+
+use crate::RequestDispatcher;
+use crate::partition_processor_rpc_client::GetInvocationProgressResponse;
+
+/// How often the progress of an invocation is polled while its events are streamed.
+///
+/// The events stream is a sampling of the invocation status, not a log of its transitions: a
+/// state held for less than this interval, e.g. an invocation suspended and woken up again
+/// between two polls, or a retry failing fast, can be skipped. The final `completed` event is
+/// always streamed, unless the invocation is purged before it is polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Last polled progress of an invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PolledProgress {
+    /// The first poll didn't complete yet.
+    Pending,
+    Progress(InvocationProgress),
+    NotFound,
+    /// The first poll failed.
+    Unavailable,
+}
+
+type ProgressSender = Arc<watch::Sender<PolledProgress>>;
+
+/// Polls the progress of the invocations whose events are streamed, once per invocation no matter
+/// how many clients are subscribed, every [`POLL_INTERVAL`]. A poller stops once its invocation
+/// completes or is not found anymore, or once no client is subscribed.
+#[derive(Default)]
+pub(crate) struct ProgressPollers {
+    pollers: Mutex<HashMap<InvocationQuery, ProgressSender>>,
+}
+
+impl ProgressPollers {
+    /// Subscribes to the progress of the invocation, starting a poller if none is running.
+    pub(crate) fn subscribe<Dispatcher>(
+        self: &Arc<Self>,
+        dispatcher: Dispatcher,
+        invocation_query: InvocationQuery,
+    ) -> Result<watch::Receiver<PolledProgress>, ShutdownError>
+    where
+        Dispatcher: RequestDispatcher + Send + Sync + 'static,
+    {
+        let mut pollers = self.pollers.lock().unwrap();
+        if let Some(progress_tx) = pollers.get(&invocation_query) {
+            return Ok(progress_tx.subscribe());
+        }
+
+        let progress_tx = Arc::new(watch::Sender::new(PolledProgress::Pending));
+        let progress_rx = progress_tx.subscribe();
+        TaskCenter::spawn(
+            TaskKind::Ingress,
+            "invocation-progress-poller",
+            Arc::clone(self).poll(
+                dispatcher,
+                invocation_query.clone(),
+                Arc::clone(&progress_tx),
+            ),
+        )?;
+        pollers.insert(invocation_query, progress_tx);
+
+        Ok(progress_rx)
+    }
+
+    async fn poll<Dispatcher>(
+        self: Arc<Self>,
+        dispatcher: Dispatcher,
+        invocation_query: InvocationQuery,
+        progress_tx: ProgressSender,
+    ) -> anyhow::Result<()>
+    where
+        Dispatcher: RequestDispatcher,
+    {
+        let mut first_poll = true;
+        loop {
+            if !first_poll {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+
+            let (progress, done) = match dispatcher
+                .get_invocation_progress(invocation_query.clone())
+                .await
+            {
+                Ok(GetInvocationProgressResponse::Progress(progress)) => {
+                    let completed = matches!(progress, InvocationProgress::Completed { .. });
+                    (Some(PolledProgress::Progress(progress)), completed)
+                }
+                Ok(GetInvocationProgressResponse::NotFound) => {
+                    // The invocation was purged or cancelled before being started
+                    debug!(
+                        restate.invocation.query = ?invocation_query,
+                        "Invocation not found, stopping polling its progress"
+                    );
+                    (Some(PolledProgress::NotFound), true)
+                }
+                Err(e) => {
+                    warn!(
+                        restate.invocation.query = ?invocation_query,
+                        "Failed to read progress: {}",
+                        e,
+                    );
+                    if first_poll {
+                        (Some(PolledProgress::Unavailable), true)
+                    } else {
+                        // Retry on the next poll, the clients keep the last progress
+                        (None, false)
+                    }
+                }
+            };
+            first_poll = false;
+
+            if let Some(progress) = progress {
+                progress_tx.send_if_modified(|current| {
+                    let modified = *current != progress;
+                    *current = progress;
+                    modified
+                });
+            }
+
+            // Subscriptions happen under the lock, so no client can subscribe to a poller
+            // which is about to stop
+            let mut pollers = self.pollers.lock().unwrap();
+            if done || progress_tx.receiver_count() == 0 {
+                if pollers
+                    .get(&invocation_query)
+                    .is_some_and(|current| Arc::ptr_eq(current, &progress_tx))
+                {
+                    pollers.remove(&invocation_query);
+                }
+                return Ok(());
+            }
+        }
+    }
+}
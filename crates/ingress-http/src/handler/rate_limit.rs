@@ -0,0 +1,231 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use http::Request;
+use metrics::counter;
+use tokio::time::Instant;
+use tracing::debug;
+
+use restate_types::config::{IngressRateLimitOptions, RateLimitKey};
+
+use super::HandlerError;
+use super::path_parsing::RequestType;
+use crate::layers::auth::AuthenticatedPrincipal;
+use crate::metric_definitions::{INGRESS_REQUESTS, REQUEST_RATE_LIMITED};
+
+/// Number of shards of the buckets, so that concurrent requests rarely contend on the same lock.
+const SHARDS: usize = 32;
+/// Number of buckets of a shard above which its full buckets are evicted, as they behave like
+/// new ones.
+const SHARD_CLEANUP_THRESHOLD: usize = 10_000 / SHARDS;
+
+type BucketKey = (Option<String>, String);
+
+/// Token bucket rate limiter of the ingress requests.
+pub(crate) struct RateLimiter {
+    options: IngressRateLimitOptions,
+    // Buckets by service, for the services with their own limit, and key
+    shards: [Mutex<HashMap<BucketKey, TokenBucket>>; SHARDS],
+    hasher: RandomState,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(options: IngressRateLimitOptions) -> Self {
+        Self {
+            options,
+            shards: Default::default(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Takes a token for the request, failing with [`HandlerError::RateLimited`] if the bucket
+    /// of the request is empty. Batch requests are checked per record, with [`Self::check_target`].
+    pub(crate) fn check<B>(
+        &self,
+        req: &Request<B>,
+        request_type: &RequestType,
+    ) -> Result<(), HandlerError> {
+        if matches!(
+            request_type,
+            RequestType::Health | RequestType::Webhook(_) | RequestType::Batch
+        ) {
+            return Ok(());
+        }
+
+        self.check_target(req, request_type.service_name(), request_type.object_key())
+    }
+
+    /// Takes a token for a request to the given service and object key.
+    pub(crate) fn check_target<B>(
+        &self,
+        req: &Request<B>,
+        service: Option<&str>,
+        object_key: Option<&str>,
+    ) -> Result<(), HandlerError> {
+        let key = match &self.options.key {
+            RateLimitKey::Principal => req
+                .extensions()
+                .get::<AuthenticatedPrincipal>()
+                .map(AuthenticatedPrincipal::principal),
+            RateLimitKey::Header { name } => req
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok()),
+            RateLimitKey::ObjectKey => object_key,
+        }
+        .unwrap_or_default();
+
+        self.acquire(service, key, Instant::now())
+            .map_err(|retry_after| {
+                debug!("Rate limiting ingress request with key '{key}'");
+                counter!(
+                    INGRESS_REQUESTS,
+                    "status" => REQUEST_RATE_LIMITED,
+                    "rpc.service" => service.unwrap_or_default().to_owned(),
+                )
+                .increment(1);
+                HandlerError::RateLimited(retry_after)
+            })
+    }
+
+    fn acquire(&self, service: Option<&str>, key: &str, now: Instant) -> Result<(), Duration> {
+        let (scope, rate, burst) = match service.and_then(|service| {
+            self.options
+                .services
+                .iter()
+                .find(|limit| limit.service == service)
+        }) {
+            Some(limit) => (
+                Some(limit.service.as_str()),
+                limit.requests_per_second,
+                limit.burst.unwrap_or(limit.requests_per_second),
+            ),
+            None => match self.options.requests_per_second {
+                Some(rate) => (None, rate, self.options.burst.unwrap_or(rate)),
+                None => return Ok(()),
+            },
+        };
+
+        let bucket_key = (scope.map(ToOwned::to_owned), key.to_owned());
+        let shard = self.hasher.hash_one(&bucket_key) as usize % SHARDS;
+        let mut buckets = self.shards[shard].lock().unwrap();
+        if buckets.len() >= SHARD_CLEANUP_THRESHOLD {
+            buckets.retain(|_, bucket| !bucket.is_full(now));
+        }
+
+        buckets
+            .entry(bucket_key)
+            .or_insert_with(|| TokenBucket::new(rate.get(), burst.get(), now))
+            .try_acquire(now)
+    }
+}
+
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, burst: u32, now: Instant) -> Self {
+        Self {
+            rate: f64::from(rate),
+            burst: f64::from(burst),
+            tokens: f64::from(burst),
+            last_refill: now,
+        }
+    }
+
+    fn tokens_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.burst)
+    }
+
+    fn is_full(&self, now: Instant) -> bool {
+        self.tokens_at(now) >= self.burst
+    }
+
+    /// Takes a token, or returns how long to wait for the next token to be available.
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        self.tokens = self.tokens_at(now);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroU32;
+
+    use restate_types::config::ServiceRateLimitOptions;
+
+    #[test]
+    fn token_bucket() {
+        let limiter = RateLimiter::new(IngressRateLimitOptions {
+            requests_per_second: NonZeroU32::new(2),
+            burst: NonZeroU32::new(3),
+            ..IngressRateLimitOptions::default()
+        });
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.acquire(None, "alice", now).is_ok());
+        }
+        assert_eq!(
+            limiter.acquire(None, "alice", now),
+            Err(Duration::from_millis(500))
+        );
+        // Other keys have their own bucket
+        assert!(limiter.acquire(None, "bob", now).is_ok());
+
+        // Refilled at the configured rate
+        let now = now + Duration::from_millis(500);
+        assert!(limiter.acquire(None, "alice", now).is_ok());
+        assert!(limiter.acquire(None, "alice", now).is_err());
+    }
+
+    #[test]
+    fn service_limits() {
+        let limiter = RateLimiter::new(IngressRateLimitOptions {
+            services: vec![ServiceRateLimitOptions {
+                service: "Greeter".to_owned(),
+                requests_per_second: NonZeroU32::new(1).unwrap(),
+                burst: None,
+            }],
+            ..IngressRateLimitOptions::default()
+        });
+        let now = Instant::now();
+
+        assert!(limiter.acquire(Some("Greeter"), "alice", now).is_ok());
+        assert_eq!(
+            limiter.acquire(Some("Greeter"), "alice", now),
+            Err(Duration::from_secs(1))
+        );
+
+        // No global limit configured
+        for _ in 0..10 {
+            assert!(limiter.acquire(Some("Counter"), "alice", now).is_ok());
+        }
+    }
+}
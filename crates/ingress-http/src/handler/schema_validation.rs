@@ -0,0 +1,356 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use bytes::Bytes;
+use metrics::counter;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use restate_types::identifiers::ServiceRevision;
+use restate_types::schema::invocation_target::InputRules;
+use restate_types::schema::service::ServiceMetadataResolver;
+
+use super::HandlerError;
+use crate::metric_definitions::INGRESS_INPUT_VALIDATION_FAILURES;
+
+/// Maximum number of violations reported for an invalid request body.
+const MAX_VIOLATIONS: usize = 10;
+
+/// A violation of the input JSON schema by the request body.
+// IMPORTANT! If you touch this, please update crates/types/src/schema/openapi.rs too
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(serde::Deserialize))]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SchemaViolation {
+    /// JSON pointer to the invalid value of the request body.
+    pub(crate) instance_path: String,
+    /// JSON pointer to the violated keyword of the schema.
+    pub(crate) schema_path: String,
+    pub(crate) message: String,
+}
+
+/// Validates the JSON request bodies against the input JSON schemas of the handlers. The schemas
+/// are compiled on first use, and cached per service revision. The validators of the removed
+/// services and of the old revisions are evicted whenever a new schema is compiled.
+#[derive(Default)]
+pub(crate) struct InputSchemaValidator {
+    // Validators by service, and handler
+    validators: ArcSwap<HashMap<String, ServiceValidators>>,
+}
+
+#[derive(Clone)]
+struct ServiceValidators {
+    revision: ServiceRevision,
+    handlers: HashMap<String, Arc<jsonschema::Validator>>,
+}
+
+impl InputSchemaValidator {
+    pub(crate) fn validate<Schemas: ServiceMetadataResolver>(
+        &self,
+        schemas: &Schemas,
+        service_name: &str,
+        handler_name: &str,
+        input_rules: &InputRules,
+        content_type: Option<&str>,
+        body: &Bytes,
+    ) -> Result<(), HandlerError> {
+        let Some(schema) = input_rules.json_schema_ref() else {
+            return Ok(());
+        };
+        // Whether the body can be empty is already checked by the input rules, and only JSON
+        // bodies can be validated, e.g. the gRPC ingress sends protobuf messages
+        if body.is_empty() || !content_type.is_some_and(is_json) {
+            return Ok(());
+        }
+
+        let violations = match serde_json::from_slice::<Value>(body) {
+            Ok(instance) => {
+                let Some(validator) = self.validator(schemas, service_name, handler_name, schema)
+                else {
+                    return Ok(());
+                };
+                validator
+                    .iter_errors(&instance)
+                    .take(MAX_VIOLATIONS)
+                    .map(|error| SchemaViolation {
+                        instance_path: error.instance_path.to_string(),
+                        schema_path: error.schema_path.to_string(),
+                        message: error.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            }
+            Err(err) => vec![SchemaViolation {
+                instance_path: String::new(),
+                schema_path: String::new(),
+                message: format!("the body is not valid JSON: {err}"),
+            }],
+        };
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        debug!(
+            rpc.service = service_name,
+            rpc.method = handler_name,
+            "Rejecting request body not matching the input JSON schema: {violations:?}"
+        );
+        counter!(
+            INGRESS_INPUT_VALIDATION_FAILURES,
+            "rpc.service" => service_name.to_owned(),
+            "rpc.method" => handler_name.to_owned(),
+        )
+        .increment(1);
+        Err(HandlerError::InputSchemaValidation(violations))
+    }
+
+    fn validator<Schemas: ServiceMetadataResolver>(
+        &self,
+        schemas: &Schemas,
+        service_name: &str,
+        handler_name: &str,
+        schema: &Value,
+    ) -> Option<Arc<jsonschema::Validator>> {
+        let revision = schemas.resolve_latest_service_revision(service_name);
+        if let Some(validator) = self
+            .validators
+            .load()
+            .get(service_name)
+            .filter(|service| Some(service.revision) == revision)
+            .and_then(|service| service.handlers.get(handler_name))
+        {
+            return Some(Arc::clone(validator));
+        }
+
+        let validator = match jsonschema::validator_for(schema) {
+            Ok(validator) => Arc::new(validator),
+            Err(err) => {
+                // The schemas are checked when registering the deployments, this should not happen
+                warn!(
+                    rpc.service = service_name,
+                    rpc.method = handler_name,
+                    "Skipping the validation of the request, cannot compile the input JSON schema: {err}"
+                );
+                return None;
+            }
+        };
+
+        // The service has been removed in the meantime, there's nothing to cache
+        let Some(revision) = revision else {
+            return Some(validator);
+        };
+        self.validators.rcu(|validators| {
+            let mut validators: HashMap<_, _> = validators
+                .iter()
+                .filter(|(service_name, service)| {
+                    schemas.resolve_latest_service_revision(service_name) == Some(service.revision)
+                })
+                .map(|(service_name, service)| (service_name.clone(), service.clone()))
+                .collect();
+            let service = validators
+                .entry(service_name.to_owned())
+                .or_insert_with(|| ServiceValidators {
+                    revision,
+                    handlers: HashMap::new(),
+                });
+            if service.revision == revision {
+                service
+                    .handlers
+                    .insert(handler_name.to_owned(), Arc::clone(&validator));
+            }
+            validators
+        });
+
+        Some(validator)
+    }
+}
+
+/// Returns true for the `application/json` content type, and the ones with the `+json` suffix.
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use restate_types::invocation::ServiceType;
+    use restate_types::schema::invocation_target::{InputContentType, InputValidationRule};
+    use restate_types::schema::service::ServiceMetadata;
+    use serde_json::json;
+
+    const JSON: Option<&str> = Some("application/json");
+
+    /// Resolves only the revisions of the services.
+    #[derive(Default)]
+    struct Revisions(HashMap<&'static str, ServiceRevision>);
+
+    impl ServiceMetadataResolver for Revisions {
+        fn resolve_latest_service(&self, _: impl AsRef<str>) -> Option<ServiceMetadata> {
+            unimplemented!()
+        }
+
+        fn resolve_latest_service_openapi(&self, _: impl AsRef<str>) -> Option<Value> {
+            unimplemented!()
+        }
+
+        fn resolve_latest_service_type(&self, _: impl AsRef<str>) -> Option<ServiceType> {
+            unimplemented!()
+        }
+
+        fn resolve_latest_service_revision(
+            &self,
+            service_name: impl AsRef<str>,
+        ) -> Option<ServiceRevision> {
+            self.0.get(service_name.as_ref()).copied()
+        }
+
+        fn list_services(&self) -> Vec<ServiceMetadata> {
+            unimplemented!()
+        }
+    }
+
+    fn input_rules(schema: Value) -> InputRules {
+        InputRules {
+            input_validation_rules: vec![InputValidationRule::JsonValue {
+                content_type: InputContentType::MimeTypeAndSubtype(
+                    "application".into(),
+                    "json".into(),
+                ),
+                schema: Some(schema),
+            }],
+        }
+    }
+
+    #[test]
+    fn validate_body() {
+        let validator = InputSchemaValidator::default();
+        let mut schemas = Revisions::default();
+        schemas.0.insert("Greeter", 1);
+        let rules = input_rules(json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}},
+            "required": ["name"]
+        }));
+
+        validator
+            .validate(
+                &schemas,
+                "Greeter",
+                "greet",
+                &rules,
+                JSON,
+                &Bytes::from_static(b"{\"name\": \"Till\"}"),
+            )
+            .unwrap();
+
+        let Err(HandlerError::InputSchemaValidation(violations)) = validator.validate(
+            &schemas,
+            "Greeter",
+            "greet",
+            &rules,
+            JSON,
+            &Bytes::from_static(b"{\"name\": 1}"),
+        ) else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].instance_path, "/name");
+
+        assert!(matches!(
+            validator.validate(
+                &schemas,
+                "Greeter",
+                "greet",
+                &rules,
+                Some("application/vnd.api+json; charset=utf-8"),
+                &Bytes::from_static(b"{")
+            ),
+            Err(HandlerError::InputSchemaValidation(_))
+        ));
+
+        // Non JSON bodies are not validated
+        validator
+            .validate(
+                &schemas,
+                "Greeter",
+                "greet",
+                &rules,
+                Some("application/proto"),
+                &Bytes::from_static(b"\x0a\x04Till"),
+            )
+            .unwrap();
+
+        // A new revision changing the schema is picked up
+        schemas.0.insert("Greeter", 2);
+        let rules = input_rules(json!({"type": "integer"}));
+        validator
+            .validate(
+                &schemas,
+                "Greeter",
+                "greet",
+                &rules,
+                JSON,
+                &Bytes::from_static(b"1"),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn evict_removed_services() {
+        let validator = InputSchemaValidator::default();
+        let mut schemas = Revisions::default();
+        schemas.0.insert("Greeter", 1);
+        schemas.0.insert("Counter", 1);
+        let rules = input_rules(json!({"type": "integer"}));
+
+        for service in ["Greeter", "Counter"] {
+            validator
+                .validate(
+                    &schemas,
+                    service,
+                    "handle",
+                    &rules,
+                    JSON,
+                    &Bytes::from_static(b"1"),
+                )
+                .unwrap();
+        }
+        assert_eq!(validator.validators.load().len(), 2);
+
+        // Compiling the schema of a new revision evicts the removed services
+        schemas.0.remove("Counter");
+        schemas.0.insert("Greeter", 2);
+        validator
+            .validate(
+                &schemas,
+                "Greeter",
+                "handle",
+                &rules,
+                JSON,
+                &Bytes::from_static(b"1"),
+            )
+            .unwrap();
+
+        let validators = validator.validators.load();
+        assert_eq!(validators.len(), 1);
+        assert_eq!(validators["Greeter"].revision, 2);
+    }
+}
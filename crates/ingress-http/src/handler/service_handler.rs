@@ -23,7 +23,7 @@ use tracing::{Instrument, debug, trace, trace_span};
 use super::HandlerError;
 use super::path_parsing::{InvokeType, ServiceRequestType, TargetType};
 use super::tracing::prepare_tracing_span;
-use super::{APPLICATION_JSON, Handler};
+use super::{APPLICATION_JSON, Handler, check_principal};
 use crate::RequestDispatcher;
 use crate::handler::responses::{IDEMPOTENCY_EXPIRES, X_RESTATE_ID};
 use crate::metric_definitions::{INGRESS_REQUEST_DURATION, INGRESS_REQUESTS, REQUEST_COMPLETED};
@@ -32,9 +32,11 @@ use restate_types::invocation::{
     Header, InvocationRequest, InvocationRequestHeader, InvocationTarget, InvocationTargetType,
     SpanRelation, WorkflowHandlerType,
 };
+use restate_types::net::partition_processor::SubmittedInvocationNotification;
 use restate_types::schema::invocation_target::{
     InvocationTargetMetadata, InvocationTargetResolver,
 };
+use restate_types::schema::service::ServiceMetadataResolver;
 use restate_types::time::MillisSinceEpoch;
 
 pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
@@ -63,9 +65,37 @@ pub(crate) struct SendResponse {
     status: SendStatus,
 }
 
+impl SendResponse {
+    pub(crate) fn new(
+        invocation_id: InvocationId,
+        submit_notification: SubmittedInvocationNotification,
+    ) -> Self {
+        Self {
+            invocation_id,
+            execution_time: submit_notification
+                .execution_time
+                .and_then(|m| {
+                    if m == MillisSinceEpoch::UNIX_EPOCH {
+                        // Ignore
+                        None
+                    } else {
+                        Some(m)
+                    }
+                })
+                .map(SystemTime::from)
+                .map(Into::into),
+            status: if submit_notification.is_new_invocation {
+                SendStatus::Accepted
+            } else {
+                SendStatus::PreviouslyAccepted
+            },
+        }
+    }
+}
+
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
 where
-    Schemas: InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
     Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
 {
     pub(crate) async fn handle_service_request<B: http_body::Body>(
@@ -93,6 +123,7 @@ where
             if !invocation_target.public {
                 return Err(HandlerError::PrivateService);
             }
+            check_principal(&req, &service_name, Some(&handler_name))?;
             invocation_target
         } else {
             return Err(HandlerError::ServiceHandlerNotFound(
@@ -164,23 +195,33 @@ where
             trace!(rpc.request = ?body);
 
             // Validate content-type and body
-            invocation_target_meta.input_rules.validate(
-                parts
-                    .headers
-                    .get(header::CONTENT_TYPE)
-                    .map(|h| {
-                        h.to_str()
-                            .map_err(|e| HandlerError::BadHeader(header::CONTENT_TYPE, e))
-                    })
-                    .transpose()?,
-                &body,
-            )?;
+            let content_type = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .map(|h| {
+                    h.to_str()
+                        .map_err(|e| HandlerError::BadHeader(header::CONTENT_TYPE, e))
+                })
+                .transpose()?;
+            invocation_target_meta
+                .input_rules
+                .validate(content_type, &body)?;
+            if invocation_target_meta.validate_input {
+                self.input_validator.validate(
+                    &*self.schemas.pinned(),
+                    invocation_target.service_name(),
+                    invocation_target.handler_name(),
+                    &invocation_target_meta.input_rules,
+                    content_type,
+                    &body,
+                )?;
+            }
 
             // Parse delay query parameter
             let delay = parse_delay(parts.uri.query())?;
 
             // Get headers
-            let headers = parse_headers(parts)?;
+            let headers = parse_headers(&parts)?;
 
             // Prepare service invocation
             let mut invocation_request_header =
@@ -188,6 +229,9 @@ where
             invocation_request_header.with_related_span(SpanRelation::Parent(ingress_span_context));
             invocation_request_header.completion_retention_duration =
                 invocation_target_meta.compute_retention(idempotency_key.is_some());
+            invocation_request_header.journal_retention_duration =
+                invocation_target_meta.journal_retention;
+            invocation_request_header.dead_letter_queue = invocation_target_meta.dead_letter_queue;
             if let Some(key) = idempotency_key {
                 invocation_request_header.idempotency_key = Some(key);
             }
@@ -267,45 +311,22 @@ where
             .header(header::CONTENT_TYPE, APPLICATION_JSON)
             .header(X_RESTATE_ID, invocation_id.to_string())
             .body(Full::new(
-                serde_json::to_vec(&SendResponse {
-                    invocation_id,
-                    execution_time: response
-                        .execution_time
-                        .and_then(|m| {
-                            if m == MillisSinceEpoch::UNIX_EPOCH {
-                                // Ignore
-                                None
-                            } else {
-                                Some(m)
-                            }
-                        })
-                        .map(SystemTime::from)
-                        .map(Into::into),
-                    status: if response.is_new_invocation {
-                        SendStatus::Accepted
-                    } else {
-                        SendStatus::PreviouslyAccepted
-                    },
-                })
-                .unwrap()
-                .into(),
+                serde_json::to_vec(&SendResponse::new(invocation_id, response))
+                    .unwrap()
+                    .into(),
             ))
             .unwrap())
     }
 }
 
-fn parse_headers(parts: http::request::Parts) -> Result<Vec<Header>, HandlerError> {
+pub(super) fn parse_headers(parts: &http::request::Parts) -> Result<Vec<Header>, HandlerError> {
     let mut headers = Vec::with_capacity(1 + parts.headers.keys_len());
 
     if let Some(path_and_query) = parts.uri.path_and_query() {
         headers.push(Header::new(X_RESTATE_INGRESS_PATH, path_and_query.as_str()));
     }
 
-    for (k, v) in parts.headers {
-        let Some(k) = k else {
-            continue;
-        };
-
+    for (k, v) in &parts.headers {
         if k == header::CONNECTION
             || k == header::HOST
             || k == IDEMPOTENCY_KEY
@@ -348,7 +369,7 @@ fn parse_delay(query: Option<&str>) -> Result<Option<Duration>, HandlerError> {
     Ok(None)
 }
 
-fn parse_idempotency(headers: &HeaderMap) -> Result<Option<ByteString>, HandlerError> {
+pub(super) fn parse_idempotency(headers: &HeaderMap) -> Result<Option<ByteString>, HandlerError> {
     let idempotency_key = if let Some(idempotency_key) = headers.get(IDEMPOTENCY_KEY) {
         ByteString::from(
             idempotency_key
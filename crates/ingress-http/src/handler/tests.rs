@@ -8,44 +8,60 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::future::ready;
+use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
 use bytestring::ByteString;
-use futures::FutureExt;
+use futures::{FutureExt, StreamExt};
 use http::StatusCode;
-use http::{HeaderValue, Method, Request, Response};
+use http::{HeaderValue, Method, Request, Response, header};
 use http_body_util::{BodyExt, Empty, Full};
-use tower::ServiceExt;
+use tower::{Layer, ServiceExt};
 use tracing_test::traced_test;
 
 use restate_core::TestCoreEnv;
 use restate_test_util::{assert, assert_eq};
-use restate_types::identifiers::{IdempotencyId, InvocationId, ServiceId, WithInvocationId};
+use restate_types::config::{
+    ApiKeyOptions, IngressAllowRule, IngressAuthOptions, IngressCorsOptions, IngressGrpcOptions,
+    IngressRateLimitOptions, RateLimitKey, ServiceRateLimitOptions,
+};
+use restate_types::identifiers::{
+    ExternalSignalIdentifier, IdempotencyId, InvocationId, ServiceId, WithInvocationId,
+};
 use restate_types::invocation::{
     InvocationQuery, InvocationTarget, InvocationTargetType, VirtualObjectHandlerType,
     WorkflowHandlerType,
 };
 use restate_types::live::Live;
 use restate_types::net::partition_processor::{
-    IngressResponseResult, InvocationOutput, SubmittedInvocationNotification,
+    IngressResponseResult, InvocationDescription, InvocationOutput, InvocationProgress,
+    SubmittedInvocationNotification,
 };
 use restate_types::schema::invocation_target::{
     InputContentType, InputRules, InputValidationRule, InvocationTargetMetadata,
     OutputContentTypeRule, OutputRules,
 };
+use restate_types::schema::service::{
+    ProtobufHandlerSchemas, ServiceCorsPolicy, ServiceMetadataResolver, ServiceProtobufSchemas,
+};
 
 use super::ConnectInfo;
-use super::Handler;
+use super::ResponseBody;
 use super::health::HealthResponse;
 use super::mocks::*;
 use super::service_handler::*;
+use super::{CorsLayer, Handler};
 use crate::MockRequestDispatcher;
 use crate::handler::responses::X_RESTATE_ID;
+use crate::layers::auth::{AuthLayer, AuthenticatedPrincipal};
 use crate::partition_processor_rpc_client::{
-    AttachInvocationResponse, GetInvocationOutputResponse,
+    AttachInvocationResponse, DescribeInvocationResponse, GetInvocationOutputResponse,
+    GetInvocationProgressResponse,
 };
 
 #[restate_core::test]
@@ -771,6 +787,361 @@ async fn get_output_with_invocation_id() {
     assert_eq!(response_value.greeting, "Igal");
 }
 
+#[restate_core::test(start_paused = true)]
+#[traced_test]
+async fn invocation_events() {
+    let invocation_id = InvocationId::mock_random();
+
+    let req = hyper::Request::builder()
+        .uri(format!(
+            "http://localhost/restate/invocation/{invocation_id}/events"
+        ))
+        .method(Method::GET)
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+
+    let mut progresses = vec![
+        InvocationProgress::Completed { failure: None },
+        InvocationProgress::Invoked { attempt: 1 },
+        InvocationProgress::Invoked { attempt: 1 },
+        InvocationProgress::Inboxed,
+    ];
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_get_invocation_progress()
+        .times(4)
+        .returning(move |actual_invocation_query| {
+            assert_eq!(
+                InvocationQuery::Invocation(invocation_id),
+                actual_invocation_query
+            );
+            ready(Ok(GetInvocationProgressResponse::Progress(
+                progresses.pop().unwrap(),
+            )))
+            .boxed()
+        });
+
+    let response = handle(req, mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+    let (_, response_body) = response.into_parts();
+    let response_bytes = response_body.collect().await.unwrap().to_bytes();
+    assert_eq!(
+        std::str::from_utf8(&response_bytes).unwrap(),
+        "event: inboxed\ndata: {\"status\":\"inboxed\"}\n\n\
+         event: invoked\ndata: {\"status\":\"invoked\",\"attempt\":1}\n\n\
+         event: completed\ndata: {\"status\":\"completed\"}\n\n"
+    );
+}
+
+#[restate_core::test(start_paused = true)]
+#[traced_test]
+async fn invocation_events_share_one_poll() {
+    let invocation_id = InvocationId::mock_random();
+    let events_request = || {
+        hyper::Request::builder()
+            .uri(format!(
+                "http://localhost/restate/invocation/{invocation_id}/events"
+            ))
+            .method(Method::GET)
+            .body(Empty::<Bytes>::new())
+            .unwrap()
+    };
+
+    let mut progresses = vec![
+        InvocationProgress::Completed { failure: None },
+        InvocationProgress::Invoked { attempt: 1 },
+        InvocationProgress::Inboxed,
+    ];
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_get_invocation_progress()
+        .times(3)
+        .returning(move |_| {
+            ready(Ok(GetInvocationProgressResponse::Progress(
+                progresses.pop().unwrap(),
+            )))
+            .boxed()
+        });
+
+    let handler = Handler::new(Live::from_value(mock_schemas()), Arc::new(mock_dispatcher));
+    let first = call_handler(handler.clone(), events_request()).await;
+    let second = call_handler(handler, events_request()).await;
+
+    let (first, second) = futures::join!(first.into_body().collect(), second.into_body().collect());
+    let expected = "event: inboxed\ndata: {\"status\":\"inboxed\"}\n\n\
+         event: invoked\ndata: {\"status\":\"invoked\",\"attempt\":1}\n\n\
+         event: completed\ndata: {\"status\":\"completed\"}\n\n";
+    assert_eq!(first.unwrap().to_bytes(), expected);
+    assert_eq!(second.unwrap().to_bytes(), expected);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn attach_requires_principal_allowed_on_invocation_target() {
+    let invocation_id = InvocationId::mock_random();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_describe_invocation()
+        .times(2)
+        .returning(move |actual_invocation_query| {
+            assert_eq!(
+                InvocationQuery::Invocation(invocation_id),
+                actual_invocation_query
+            );
+            ready(Ok(DescribeInvocationResponse::Description(
+                InvocationDescription {
+                    invocation_id,
+                    invocation_target: InvocationTarget::service("greeter.Greeter", "greet"),
+                    progress: InvocationProgress::Suspended,
+                    output: None,
+                },
+            )))
+            .boxed()
+        });
+    mock_dispatcher
+        .expect_get_invocation_output()
+        .times(1)
+        .returning(|_| ready(Ok(GetInvocationOutputResponse::NotReady)).boxed());
+    let mock_dispatcher = Arc::new(mock_dispatcher);
+
+    let _env = TestCoreEnv::create_with_single_node(1, 1).await;
+    for (principal, expected_status) in [
+        ("mallory", StatusCode::FORBIDDEN),
+        ("alice", StatusCode::from_u16(470).unwrap()),
+    ] {
+        let req = with_principal(
+            hyper::Request::builder()
+                .uri(format!(
+                    "http://localhost/restate/invocation/{invocation_id}/output"
+                ))
+                .method(Method::GET)
+                .body(Empty::<Bytes>::new())
+                .unwrap(),
+            principal,
+        );
+
+        let response = call_handler(
+            Handler::new(
+                Live::from_value(mock_schemas()),
+                Arc::clone(&mock_dispatcher),
+            ),
+            req,
+        )
+        .await;
+        assert_eq!(response.status(), expected_status);
+    }
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn invocation_events_by_idempotency_key_require_allowed_principal() {
+    let req = with_principal(
+        hyper::Request::builder()
+            .uri("http://localhost/restate/invocation/greeter.GreeterObject/mygreet/greet/myid/events")
+            .method(Method::GET)
+            .body(Empty::<Bytes>::new())
+            .unwrap(),
+        "alice",
+    );
+
+    // The target service is part of the path, no partition processor lookup is needed
+    let response = handle(req, MockRequestDispatcher::default()).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn resolve_awakeable_requires_allowed_principal() {
+    let invocation_id = InvocationId::mock_random();
+    let awakeable_id = ExternalSignalIdentifier::new(invocation_id, 17);
+
+    let req = with_principal(
+        hyper::Request::builder()
+            .uri(format!(
+                "http://localhost/restate/awakeables/{awakeable_id}/resolve"
+            ))
+            .method(Method::POST)
+            .body(Full::new(Bytes::from_static(b"true")))
+            .unwrap(),
+        "alice",
+    );
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_describe_invocation()
+        .return_once(move |_| {
+            ready(Ok(DescribeInvocationResponse::Description(
+                InvocationDescription {
+                    invocation_id,
+                    invocation_target: InvocationTarget::service("payments.Payments", "charge"),
+                    progress: InvocationProgress::Suspended,
+                    output: None,
+                },
+            )))
+            .boxed()
+        });
+    mock_dispatcher.expect_send_signal().never();
+
+    let response = handle(req, mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn get_idempotent_invocation_of_keyed_service() {
+    let mock_schemas = MockSchemas::default().with_service_and_target(
+        "greeter.Greeter",
+        "greet",
+        InvocationTargetMetadata::mock(InvocationTargetType::VirtualObject(
+            VirtualObjectHandlerType::Exclusive,
+        )),
+    );
+    let invocation_id = InvocationId::mock_random();
+
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/idempotency/greeter.Greeter/mygreet/greet/myid")
+        .method(Method::GET)
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_describe_invocation()
+        .return_once(move |actual_invocation_query| {
+            assert_eq!(
+                InvocationQuery::IdempotencyId(IdempotencyId::new(
+                    "greeter.Greeter".into(),
+                    Some("mygreet".into()),
+                    "greet".into(),
+                    "myid".into()
+                )),
+                actual_invocation_query
+            );
+
+            ready(Ok(DescribeInvocationResponse::Description(
+                InvocationDescription {
+                    invocation_id,
+                    invocation_target: InvocationTarget::virtual_object(
+                        "greeter.Greeter",
+                        "mygreet",
+                        "greet",
+                        VirtualObjectHandlerType::Exclusive,
+                    ),
+                    progress: InvocationProgress::Completed { failure: None },
+                    output: Some(Bytes::from_static(b"{\"greeting\":\"Igal\"}")),
+                },
+            )))
+            .boxed()
+        });
+
+    let response = handle_with_schemas_and_dispatcher(req, mock_schemas, mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(X_RESTATE_ID).unwrap(),
+        invocation_id.to_string().as_str()
+    );
+    let (_, response_body) = response.into_parts();
+    let response_bytes = response_body.collect().await.unwrap().to_bytes();
+    let response_value: serde_json::Value = serde_json::from_slice(&response_bytes).unwrap();
+    assert_eq!(
+        response_value,
+        serde_json::json!({
+            "invocationId": invocation_id.to_string(),
+            "status": "completed",
+            "output": {"greeting": "Igal"}
+        })
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cancel_idempotent_invocation() {
+    let invocation_id = InvocationId::mock_random();
+
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/idempotency/greeter.Greeter/greet/myid")
+        .method(Method::DELETE)
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_describe_invocation()
+        .return_once(move |actual_invocation_query| {
+            assert_eq!(
+                InvocationQuery::IdempotencyId(IdempotencyId::new(
+                    "greeter.Greeter".into(),
+                    None,
+                    "greet".into(),
+                    "myid".into()
+                )),
+                actual_invocation_query
+            );
+
+            ready(Ok(DescribeInvocationResponse::Description(
+                InvocationDescription {
+                    invocation_id,
+                    invocation_target: InvocationTarget::service("greeter.Greeter", "greet"),
+                    progress: InvocationProgress::Suspended,
+                    output: None,
+                },
+            )))
+            .boxed()
+        });
+    mock_dispatcher
+        .expect_cancel_invocation()
+        .return_once(move |actual_invocation_id| {
+            assert_eq!(invocation_id, actual_invocation_id);
+            ready(Ok(())).boxed()
+        });
+
+    let response = handle(req, mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let (_, response_body) = response.into_parts();
+    let response_bytes = response_body.collect().await.unwrap().to_bytes();
+    let response_value: serde_json::Value = serde_json::from_slice(&response_bytes).unwrap();
+    assert_eq!(response_value["invocationId"], invocation_id.to_string());
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cancel_completed_idempotent_invocation() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/idempotency/greeter.Greeter/greet/myid")
+        .method(Method::DELETE)
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_describe_invocation()
+        .return_once(move |_| {
+            ready(Ok(DescribeInvocationResponse::Description(
+                InvocationDescription {
+                    invocation_id: InvocationId::mock_random(),
+                    invocation_target: InvocationTarget::service("greeter.Greeter", "greet"),
+                    progress: InvocationProgress::Completed { failure: None },
+                    output: None,
+                },
+            )))
+            .boxed()
+        });
+
+    let response = handle(req, mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
 #[restate_core::test]
 #[traced_test]
 async fn get_output_with_workflow_key() {
@@ -1089,6 +1460,582 @@ async fn health() {
     let _: HealthResponse = serde_json::from_slice(&response_bytes).unwrap();
 }
 
+#[restate_core::test]
+#[traced_test]
+async fn send_batch() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/batch")
+        .method(Method::POST)
+        .header("content-type", "application/x-ndjson")
+        .body(Full::new(Bytes::from_static(
+            br#"{"target": "greeter.Greeter/greet", "body": {"person": "Francesco"}}
+{"target": "greeter.GreeterObject/greet", "idempotency_key": "123"}
+{"target": "greeter.GreeterObject/greet", "key": "my-key", "delay": "10s"}
+"#,
+        )))
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_send_batch()
+        .return_once(|invocation_requests| {
+            assert_eq!(invocation_requests.len(), 2);
+
+            let greeter_request = &invocation_requests[0];
+            assert_eq!(
+                greeter_request.header.target.service_name(),
+                "greeter.Greeter"
+            );
+            let greeting_req: GreetingRequest =
+                serde_json::from_slice(&greeter_request.body).unwrap();
+            assert_eq!(&greeting_req.person, "Francesco");
+
+            let object_request = &invocation_requests[1];
+            assert_eq!(object_request.header.target.key().unwrap(), &"my-key");
+            assert!(object_request.header.execution_time.is_some());
+
+            futures::stream::iter([
+                (
+                    1,
+                    Err(crate::RequestDispatcherError::Internal(anyhow::anyhow!(
+                        "unavailable"
+                    ))),
+                ),
+                (
+                    0,
+                    Ok(SubmittedInvocationNotification {
+                        request_id: Default::default(),
+                        execution_time: None,
+                        is_new_invocation: true,
+                    }),
+                ),
+            ])
+            .boxed()
+        });
+
+    let response = handle(req, mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let (_, response_body) = response.into_parts();
+    let response_bytes = response_body.collect().await.unwrap().to_bytes();
+    let lines: Vec<serde_json::Value> = response_bytes
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+
+    // Records failing validation are reported first
+    assert_eq!(lines[0]["index"], 1);
+    assert!(lines[0]["error"]["message"].is_string());
+    assert_eq!(lines[1]["index"], 2);
+    assert!(lines[1]["error"]["message"].is_string());
+    assert_eq!(lines[2]["index"], 0);
+    assert_eq!(lines[2]["status"], "Accepted");
+    assert!(lines[2]["invocationId"].is_string());
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cors_preflight() {
+    let cors = IngressCorsOptions {
+        allowed_origins: vec!["https://example.com".to_owned()],
+        allowed_headers: vec!["content-type".to_owned()],
+        allow_credentials: true,
+        max_age: Some(Duration::from_secs(600).into()),
+        ..IngressCorsOptions::default()
+    };
+
+    let response = handle_with_cors(
+        preflight_request("https://example.com", "POST"),
+        mock_schemas(),
+        cors.clone(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(
+        headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+        "https://example.com"
+    );
+    assert_eq!(
+        headers.get(header::ACCESS_CONTROL_ALLOW_METHODS).unwrap(),
+        "GET, POST, DELETE"
+    );
+    assert_eq!(
+        headers.get(header::ACCESS_CONTROL_ALLOW_HEADERS).unwrap(),
+        "content-type"
+    );
+    assert_eq!(
+        headers
+            .get(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+            .unwrap(),
+        "true"
+    );
+    assert_eq!(headers.get(header::ACCESS_CONTROL_MAX_AGE).unwrap(), "600");
+
+    // Origin not allowed
+    let response = handle_with_cors(
+        preflight_request("https://evil.com", "POST"),
+        mock_schemas(),
+        cors.clone(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(
+        !response
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+    );
+
+    // Method not allowed
+    let response = handle_with_cors(
+        preflight_request("https://example.com", "PUT"),
+        mock_schemas(),
+        cors,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cors_preflight_service_override() {
+    let mut schemas = mock_schemas();
+    let mut service = schemas.0.resolve_latest_service("greeter.Greeter").unwrap();
+    service.cors = Some(ServiceCorsPolicy {
+        allowed_origins: Some(vec!["https://other.com".to_owned()]),
+        ..ServiceCorsPolicy::default()
+    });
+    schemas.0.add(service);
+    let cors = IngressCorsOptions {
+        allowed_origins: vec!["https://example.com".to_owned()],
+        ..IngressCorsOptions::default()
+    };
+
+    let response = handle_with_cors(
+        preflight_request("https://example.com", "POST"),
+        schemas.clone(),
+        cors.clone(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = handle_with_cors(
+        preflight_request("https://other.com", "POST"),
+        schemas.clone(),
+        cors.clone(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://other.com"
+    );
+
+    // Services without overrides use the ingress policy
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.GreeterObject/my-key/greet")
+        .method(Method::OPTIONS)
+        .header(header::ORIGIN, "https://example.com")
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+    let response = handle_with_cors(req, schemas, cors).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cors_simple_request() {
+    let cors = IngressCorsOptions {
+        allowed_origins: vec!["https://example.com".to_owned()],
+        exposed_headers: vec![X_RESTATE_ID.to_string()],
+        ..IngressCorsOptions::default()
+    };
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/greet")
+        .method(Method::POST)
+        .header(header::ORIGIN, "https://example.com")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+
+    let response = handle_with_schemas_dispatcher_and_cors(
+        req,
+        mock_schemas(),
+        expect_invocation_and_reply_with_empty(),
+        Some(cors),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://example.com"
+    );
+    assert_eq!(
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_EXPOSE_HEADERS)
+            .unwrap(),
+        X_RESTATE_ID.as_str()
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cors_headers_on_unauthorized_response() {
+    let _env = TestCoreEnv::create_with_single_node(1, 1).await;
+    let cors = IngressCorsOptions {
+        allowed_origins: vec!["https://example.com".to_owned()],
+        ..IngressCorsOptions::default()
+    };
+    let auth = IngressAuthOptions {
+        api_keys: vec![ApiKeyOptions {
+            principal: "alice".to_owned(),
+            key: "alice-key".to_owned(),
+        }],
+        ..IngressAuthOptions::default()
+    };
+    let handler = Handler::new(
+        Live::from_value(mock_schemas()),
+        Arc::new(MockRequestDispatcher::default()),
+    )
+    .with_cors(Some(cors));
+    let service = tower::ServiceBuilder::new()
+        .layer(CorsLayer::new(handler.clone()))
+        .layer(AuthLayer::new(Some(&auth)).unwrap())
+        .service(handler);
+
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/greet")
+        .method(Method::POST)
+        .header(header::ORIGIN, "https://example.com")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+    let response = call_handler(service, req).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://example.com"
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn cors_credentials_are_not_allowed_for_any_origin() {
+    let mut schemas = mock_schemas();
+    let mut service = schemas.0.resolve_latest_service("greeter.Greeter").unwrap();
+    service.cors = Some(ServiceCorsPolicy {
+        allowed_origins: Some(vec!["*".to_owned()]),
+        ..ServiceCorsPolicy::default()
+    });
+    schemas.0.add(service);
+    let cors = IngressCorsOptions {
+        allowed_origins: vec!["https://example.com".to_owned()],
+        allow_credentials: true,
+        ..IngressCorsOptions::default()
+    };
+
+    let response =
+        handle_with_cors(preflight_request("https://evil.com", "POST"), schemas, cors).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://evil.com"
+    );
+    assert!(
+        !response
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn call_service_with_invalid_input() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/greet")
+        .method(Method::POST)
+        .header("content-type", "application/json")
+        .body(Full::new(Bytes::from_static(b"{\"person\": 1}")))
+        .unwrap();
+
+    // The dispatcher has no expectations, the request must be rejected before
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        MockSchemas::default().with_service_and_target(
+            "greeter.Greeter",
+            "greet",
+            InvocationTargetMetadata {
+                validate_input: true,
+                input_rules: InputRules {
+                    input_validation_rules: vec![InputValidationRule::JsonValue {
+                        content_type: InputContentType::MimeTypeAndSubtype(
+                            "application".into(),
+                            "json".into(),
+                        ),
+                        schema: Some(serde_json::json!({
+                            "type": "object",
+                            "properties": {"person": {"type": "string"}}
+                        })),
+                    }],
+                },
+                ..InvocationTargetMetadata::mock(InvocationTargetType::Service)
+            },
+        ),
+        MockRequestDispatcher::default(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value =
+        serde_json::from_slice(&response.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert_eq!(body["violations"][0]["instancePath"], "/person");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn send_batch_with_invalid_input() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/batch")
+        .method(Method::POST)
+        .header("content-type", "application/x-ndjson")
+        .body(Full::new(Bytes::from_static(
+            br#"{"target": "greeter.Greeter/greet", "body": {"person": 1}}
+"#,
+        )))
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_send_batch()
+        .return_once(|invocation_requests| {
+            assert!(invocation_requests.is_empty());
+            futures::stream::empty().boxed()
+        });
+
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        MockSchemas::default().with_service_and_target(
+            "greeter.Greeter",
+            "greet",
+            InvocationTargetMetadata {
+                validate_input: true,
+                input_rules: InputRules {
+                    input_validation_rules: vec![InputValidationRule::JsonValue {
+                        content_type: InputContentType::MimeTypeAndSubtype(
+                            "application".into(),
+                            "json".into(),
+                        ),
+                        schema: Some(serde_json::json!({
+                            "type": "object",
+                            "properties": {"person": {"type": "string"}}
+                        })),
+                    }],
+                },
+                ..InvocationTargetMetadata::mock(InvocationTargetType::Service)
+            },
+        ),
+        mock_dispatcher,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let line: serde_json::Value =
+        serde_json::from_slice(&response.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert_eq!(line["index"], 0);
+    assert_eq!(line["error"]["violations"][0]["instancePath"], "/person");
+}
+
+#[restate_core::test(start_paused = true)]
+#[traced_test]
+async fn rate_limit() {
+    let _env = TestCoreEnv::create_with_single_node(1, 1).await;
+
+    let handler = Handler::new(
+        Live::from_value(mock_schemas()),
+        Arc::new(expect_invocation_and_reply_with_empty()),
+    )
+    .with_rate_limit(Some(IngressRateLimitOptions {
+        key: RateLimitKey::Header {
+            name: "x-tenant".to_owned(),
+        },
+        requests_per_second: NonZeroU32::new(1),
+        ..IngressRateLimitOptions::default()
+    }));
+    let request = |tenant: &str| {
+        hyper::Request::builder()
+            .uri("http://localhost/greeter.Greeter/greet")
+            .method(Method::POST)
+            .header("x-tenant", tenant)
+            .body(Empty::<Bytes>::default())
+            .unwrap()
+    };
+
+    let response = call_handler(handler.clone(), request("tenant-1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The bucket of tenant-1 is empty, the request is rejected before reaching the dispatcher
+    let response = call_handler(handler.clone(), request("tenant-1")).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "1");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn rate_limit_batch_records() {
+    let _env = TestCoreEnv::create_with_single_node(1, 1).await;
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_send_batch()
+        .return_once(|invocation_requests| {
+            // Only the first record of the rate limited service is sent
+            assert_eq!(invocation_requests.len(), 2);
+            assert_eq!(
+                invocation_requests[1].header.target.service_name(),
+                "greeter.GreeterObject"
+            );
+            futures::stream::empty().boxed()
+        });
+
+    let handler = Handler::new(Live::from_value(mock_schemas()), Arc::new(mock_dispatcher))
+        .with_rate_limit(Some(IngressRateLimitOptions {
+            services: vec![ServiceRateLimitOptions {
+                service: "greeter.Greeter".to_owned(),
+                requests_per_second: NonZeroU32::new(1).unwrap(),
+                burst: None,
+            }],
+            ..IngressRateLimitOptions::default()
+        }));
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/batch")
+        .method(Method::POST)
+        .header("content-type", "application/x-ndjson")
+        .body(Full::new(Bytes::from_static(
+            br#"{"target": "greeter.Greeter/greet"}
+{"target": "greeter.Greeter/greet"}
+{"target": "greeter.GreeterObject/greet", "key": "my-key"}
+"#,
+        )))
+        .unwrap();
+
+    let response = call_handler(handler, req).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let line: serde_json::Value =
+        serde_json::from_slice(&response.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert_eq!(line["index"], 1);
+    assert!(
+        line["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("rate limit exceeded")
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_call() {
+    let _env = TestCoreEnv::create_with_single_node(1, 1).await;
+
+    let schemas = mock_schemas().with_protobuf(
+        "greeter.Greeter",
+        ServiceProtobufSchemas {
+            file_descriptor_set: Bytes::new(),
+            handlers: HashMap::from([(
+                "greet".to_owned(),
+                ProtobufHandlerSchemas {
+                    method: "Greet".to_owned(),
+                    input_type: ".greeter.GreetRequest".to_owned(),
+                    output_type: ".greeter.GreetResponse".to_owned(),
+                },
+            )]),
+        },
+    );
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_call()
+        .return_once(|invocation_request| {
+            assert_eq!(invocation_request.header.target.handler_name(), "greet");
+            assert_eq!(&invocation_request.body[..], b"hello");
+            assert!(
+                invocation_request
+                    .header
+                    .headers
+                    .iter()
+                    .all(|h| h.name != "te" && h.name != "grpc-timeout")
+            );
+
+            ready(Ok(InvocationOutput {
+                request_id: Default::default(),
+                invocation_id: Some(invocation_request.invocation_id()),
+                completion_expiry_time: None,
+                response: IngressResponseResult::Success(
+                    invocation_request.header.target,
+                    Bytes::from_static(b"world"),
+                ),
+            }))
+            .boxed()
+        });
+    let handler = Handler::new(Live::from_value(schemas), Arc::new(mock_dispatcher))
+        .with_grpc(Some(IngressGrpcOptions::default()));
+
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/Greet")
+        .method(Method::POST)
+        .header(header::CONTENT_TYPE, "application/grpc")
+        .header("te", "trailers")
+        .header("grpc-timeout", "1S")
+        .body(Full::new(Bytes::from_static(b"\0\0\0\0\x05hello")))
+        .unwrap();
+    let response = call_handler(handler.clone(), req).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/grpc"
+    );
+    let body = response.into_body().collect().await.unwrap();
+    assert_eq!(body.trailers().unwrap().get("grpc-status").unwrap(), "0");
+    assert_eq!(&body.to_bytes()[..], b"\0\0\0\0\x05world");
+
+    // Methods without protobuf schemas are unimplemented
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.GreeterObject/Greet")
+        .method(Method::POST)
+        .header(header::CONTENT_TYPE, "application/grpc")
+        .body(Full::new(Bytes::from_static(b"\0\0\0\0\x05hello")))
+        .unwrap();
+    let response = call_handler(handler, req).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get("grpc-status").unwrap(), "12");
+}
+
+fn preflight_request(origin: &str, method: &str) -> Request<Empty<Bytes>> {
+    hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/greet")
+        .method(Method::OPTIONS)
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+        .body(Empty::<Bytes>::default())
+        .unwrap()
+}
+
 fn expect_invocation_and_reply_with_empty() -> MockRequestDispatcher {
     let mut mock_dispatcher = MockRequestDispatcher::new();
     mock_dispatcher
@@ -1130,29 +2077,88 @@ fn expect_invocation_and_reply_with_non_empty() -> MockRequestDispatcher {
 }
 
 pub async fn handle_with_schemas_and_dispatcher<B: http_body::Body + Send + 'static>(
-    mut req: Request<B>,
+    req: Request<B>,
+    schemas: MockSchemas,
+    dispatcher: MockRequestDispatcher,
+) -> Response<ResponseBody>
+where
+    <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    <B as http_body::Body>::Data: Send + Sync + 'static,
+{
+    handle_with_schemas_dispatcher_and_cors(req, schemas, dispatcher, None).await
+}
+
+pub async fn handle_with_cors<B: http_body::Body + Send + 'static>(
+    req: Request<B>,
+    schemas: MockSchemas,
+    cors: IngressCorsOptions,
+) -> Response<ResponseBody>
+where
+    <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    <B as http_body::Body>::Data: Send + Sync + 'static,
+{
+    handle_with_schemas_dispatcher_and_cors(
+        req,
+        schemas,
+        MockRequestDispatcher::default(),
+        Some(cors),
+    )
+    .await
+}
+
+pub async fn handle_with_schemas_dispatcher_and_cors<B: http_body::Body + Send + 'static>(
+    req: Request<B>,
     schemas: MockSchemas,
     dispatcher: MockRequestDispatcher,
-) -> Response<Full<Bytes>>
+    cors: Option<IngressCorsOptions>,
+) -> Response<ResponseBody>
 where
     <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
     <B as http_body::Body>::Data: Send + Sync + 'static,
 {
     let _env = TestCoreEnv::create_with_single_node(1, 1).await;
 
+    let handler = Handler::new(Live::from_value(schemas), Arc::new(dispatcher));
+    if cors.is_some() {
+        let handler = handler.with_cors(cors);
+        call_handler(CorsLayer::new(handler.clone()).layer(handler), req).await
+    } else {
+        call_handler(handler, req).await
+    }
+}
+
+async fn call_handler<S, B>(handler: S, mut req: Request<B>) -> Response<ResponseBody>
+where
+    S: tower::Service<Request<B>, Response = Response<ResponseBody>, Error = Infallible>,
+{
     req.extensions_mut()
         .insert(ConnectInfo::new("0.0.0.0:0".parse().unwrap()));
     req.extensions_mut().insert(opentelemetry::Context::new());
 
-    let handler_fut = Handler::new(Live::from_value(schemas), Arc::new(dispatcher)).oneshot(req);
+    handler.oneshot(req).await.unwrap()
+}
 
-    handler_fut.await.unwrap()
+/// Marks the request as authenticated for the given principal, who is only allowed to invoke
+/// `greeter.Greeter`.
+fn with_principal<B>(mut req: Request<B>, principal: &str) -> Request<B> {
+    req.extensions_mut().insert(AuthenticatedPrincipal::new(
+        principal.to_owned(),
+        Arc::new(IngressAuthOptions {
+            allow: vec![IngressAllowRule {
+                service: "greeter.Greeter".to_owned(),
+                handler: None,
+                principals: vec!["alice".to_owned()],
+            }],
+            ..IngressAuthOptions::default()
+        }),
+    ));
+    req
 }
 
 pub async fn handle<B: http_body::Body + Send + 'static>(
     req: Request<B>,
     mock_request_dispatcher: MockRequestDispatcher,
-) -> Response<Full<Bytes>>
+) -> Response<ResponseBody>
 where
     <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
     <B as http_body::Body>::Data: Send + Sync + 'static,
@@ -0,0 +1,265 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::{Duration, SystemTime};
+
+use super::Handler;
+use super::HandlerError;
+use super::path_parsing::WebhookRequestType;
+
+use crate::{RequestDispatcher, WebhookDelivery};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bytes::Bytes;
+use hmac::{Hmac, Mac};
+use http::{HeaderMap, Method, Request, Response, StatusCode};
+use http_body_util::BodyExt;
+use http_body_util::Full;
+use restate_types::config::{Configuration, WebhookOptions};
+use restate_types::invocation::Header;
+use sha2::Sha256;
+use tracing::{info, trace, warn};
+
+const WEBHOOK_ID_HEADER: &str = "webhook-id";
+const WEBHOOK_TIMESTAMP_HEADER: &str = "webhook-timestamp";
+const WEBHOOK_SIGNATURE_HEADER: &str = "webhook-signature";
+const SECRET_PREFIX: &str = "whsec_";
+const SIGNATURE_VERSION: &str = "v1";
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
+{
+    pub(crate) async fn handle_webhook<B: http_body::Body>(
+        self,
+        req: Request<B>,
+        WebhookRequestType { name, key }: WebhookRequestType,
+    ) -> Result<Response<Full<Bytes>>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Check HTTP Method
+        if req.method() != Method::POST {
+            return Err(HandlerError::MethodNotAllowed);
+        }
+
+        let webhook_options = Configuration::pinned()
+            .ingress
+            .get_webhook(&name)
+            .cloned()
+            .ok_or_else(|| HandlerError::WebhookNotFound(name.clone()))?;
+
+        let (parts, body) = req.into_parts();
+
+        // Collect body
+        let payload = body
+            .collect()
+            .await
+            .map_err(|e| HandlerError::Body(e.into()))?
+            .to_bytes();
+        trace!(rpc.request = ?payload);
+
+        let (id, timestamp) = verify_signature(
+            &webhook_options,
+            &parts.headers,
+            &payload,
+            SystemTime::now(),
+        )?;
+
+        info!(
+            restate.webhook.name = %name,
+            restate.webhook.delivery.id = %id,
+            "Processing webhook delivery"
+        );
+
+        let delivery = WebhookDelivery {
+            headers: vec![
+                Header::new("webhook.name", &*name),
+                Header::new("webhook.id", &*id),
+                Header::new("webhook.timestamp", timestamp.to_string()),
+            ],
+            webhook: name.clone(),
+            id,
+            key,
+            payload,
+        };
+
+        match self.dispatcher.dispatch_webhook_delivery(delivery).await {
+            Ok(0) => Err(HandlerError::NoWebhookSubscriptions(name)),
+            Ok(_) => Ok(hyper::Response::builder()
+                .status(StatusCode::ACCEPTED)
+                .body(Full::default())
+                .unwrap()),
+            Err(e) => {
+                warn!("Failed to dispatch webhook delivery: {}", e);
+                Err(HandlerError::Unavailable)
+            }
+        }
+    }
+}
+
+/// Verifies the delivery signature as described by the [Standard Webhooks](https://www.standardwebhooks.com/)
+/// specification. Returns the delivery id and timestamp.
+fn verify_signature(
+    options: &WebhookOptions,
+    headers: &HeaderMap,
+    payload: &[u8],
+    now: SystemTime,
+) -> Result<(String, u64), HandlerError> {
+    let header = |name: &'static str| {
+        headers
+            .get(name)
+            .ok_or(HandlerError::BadWebhookSignature("missing webhook headers"))?
+            .to_str()
+            .map_err(|e| HandlerError::BadHeader(http::HeaderName::from_static(name), e))
+    };
+    let id = header(WEBHOOK_ID_HEADER)?;
+    let timestamp = header(WEBHOOK_TIMESTAMP_HEADER)?;
+    let signatures = header(WEBHOOK_SIGNATURE_HEADER)?;
+
+    let timestamp_secs: u64 = timestamp
+        .parse()
+        .map_err(|_| HandlerError::BadWebhookSignature("bad timestamp"))?;
+    let timestamp_time = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp_secs);
+    let skew = now
+        .duration_since(timestamp_time)
+        .or_else(|_| timestamp_time.duration_since(now))
+        .unwrap_or_default();
+    if skew > *options.timestamp_tolerance {
+        return Err(HandlerError::BadWebhookSignature(
+            "timestamp outside of the tolerance window",
+        ));
+    }
+
+    let secret = BASE64_STANDARD
+        .decode(
+            options
+                .signing_secret
+                .strip_prefix(SECRET_PREFIX)
+                .unwrap_or(&options.signing_secret),
+        )
+        .map_err(|_| HandlerError::BadWebhookSignature("the signing secret is not valid base64"))?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(&secret).expect("HMAC accepts keys of any length");
+    mac.update(id.as_bytes());
+    mac.update(b".");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+
+    // The header might contain several space separated signatures, e.g. during secret rotation
+    let verified = signatures.split(' ').any(|versioned_signature| {
+        versioned_signature
+            .split_once(',')
+            .filter(|(version, _)| *version == SIGNATURE_VERSION)
+            .and_then(|(_, signature)| BASE64_STANDARD.decode(signature).ok())
+            .is_some_and(|signature| mac.clone().verify_slice(&signature).is_ok())
+    });
+    if !verified {
+        return Err(HandlerError::BadWebhookSignature("signature mismatch"));
+    }
+
+    Ok((id.to_owned(), timestamp_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http::HeaderValue;
+
+    const SECRET: &str = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+
+    fn options() -> WebhookOptions {
+        WebhookOptions {
+            name: "my-webhook".to_owned(),
+            signing_secret: SECRET.to_owned(),
+            timestamp_tolerance: Duration::from_secs(300).into(),
+        }
+    }
+
+    fn sign(id: &str, timestamp: u64, payload: &[u8]) -> String {
+        let secret = BASE64_STANDARD
+            .decode(SECRET.strip_prefix(SECRET_PREFIX).unwrap())
+            .unwrap();
+        let mut mac = Hmac::<Sha256>::new_from_slice(&secret).unwrap();
+        mac.update(format!("{id}.{timestamp}.").as_bytes());
+        mac.update(payload);
+        format!("v1,{}", BASE64_STANDARD.encode(mac.finalize().into_bytes()))
+    }
+
+    fn headers(id: &str, timestamp: u64, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(WEBHOOK_ID_HEADER, HeaderValue::from_str(id).unwrap());
+        headers.insert(
+            WEBHOOK_TIMESTAMP_HEADER,
+            HeaderValue::from_str(&timestamp.to_string()).unwrap(),
+        );
+        headers.insert(
+            WEBHOOK_SIGNATURE_HEADER,
+            HeaderValue::from_str(signature).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn valid_signature() {
+        let timestamp = 1_700_000_000;
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp + 10);
+        let payload = br#"{"hello":"world"}"#;
+        let signature = format!(
+            "v1,bm90LWEtc2lnbmF0dXJl {}",
+            sign("msg_1", timestamp, payload)
+        );
+
+        let (id, ts) = verify_signature(
+            &options(),
+            &headers("msg_1", timestamp, &signature),
+            payload,
+            now,
+        )
+        .unwrap();
+        assert_eq!(id, "msg_1");
+        assert_eq!(ts, timestamp);
+    }
+
+    #[test]
+    fn tampered_payload() {
+        let timestamp = 1_700_000_000;
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp);
+        let signature = sign("msg_1", timestamp, b"original");
+
+        assert!(matches!(
+            verify_signature(
+                &options(),
+                &headers("msg_1", timestamp, &signature),
+                b"tampered",
+                now
+            ),
+            Err(HandlerError::BadWebhookSignature(_))
+        ));
+    }
+
+    #[test]
+    fn expired_timestamp() {
+        let timestamp = 1_700_000_000;
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp + 301);
+        let signature = sign("msg_1", timestamp, b"payload");
+
+        assert!(matches!(
+            verify_signature(
+                &options(),
+                &headers("msg_1", timestamp, &signature),
+                b"payload",
+                now
+            ),
+            Err(HandlerError::BadWebhookSignature(_))
+        ));
+    }
+}
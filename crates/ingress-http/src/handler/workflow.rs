@@ -19,6 +19,7 @@ use restate_types::schema::invocation_target::InvocationTargetResolver;
 
 use super::Handler;
 use super::HandlerError;
+use super::check_principal;
 use super::path_parsing::WorkflowRequestType;
 use crate::RequestDispatcher;
 use crate::partition_processor_rpc_client::{
@@ -40,10 +41,12 @@ where
     {
         match workflow_request_type {
             WorkflowRequestType::Attach(name, key) => {
+                check_principal(&req, &name, None)?;
                 self.handle_workflow_attach(req, ServiceId::new(name, key))
                     .await
             }
             WorkflowRequestType::GetOutput(name, key) => {
+                check_principal(&req, &name, None)?;
                 self.handle_workflow_get_output(req, ServiceId::new(name, key))
                     .await
             }
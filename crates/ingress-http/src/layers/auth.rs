@@ -0,0 +1,585 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+use arc_swap::ArcSwap;
+use futures::future::{Either, Ready, ready};
+use http::header::{
+    ACCESS_CONTROL_REQUEST_METHOD, AUTHORIZATION, InvalidHeaderName, WWW_AUTHENTICATE,
+};
+use http::{HeaderName, HeaderValue, Method, Request, Response, StatusCode};
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use restate_types::config::IngressAuthOptions;
+use tokio::time::Instant;
+use tower::{Layer, Service};
+use tracing::{debug, info, warn};
+
+const HEALTH_PATH: &str = "/restate/health";
+const WEBHOOK_PATH_PREFIX: &str = "/restate/webhook/";
+/// How often the JWKS file is checked for changes.
+const JWKS_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Minimum delay between two checks of the JWKS file triggered by tokens signed by unknown keys.
+const JWKS_UNKNOWN_KID_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("cannot read the JWKS file '{}': {source}", path.display())]
+    ReadJwks {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("cannot parse the JWKS file '{}': {source}", path.display())]
+    ParseJwks {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("bad principal header name: {0}")]
+    PrincipalHeader(#[from] InvalidHeaderName),
+    #[error("unknown JWT algorithm '{0}'")]
+    JwtAlgorithm(String),
+}
+
+/// Principal authenticated by the [`Auth`] service, available in the request extensions.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedPrincipal {
+    principal: String,
+    options: Arc<IngressAuthOptions>,
+}
+
+impl AuthenticatedPrincipal {
+    pub(crate) fn new(principal: String, options: Arc<IngressAuthOptions>) -> Self {
+        Self { principal, options }
+    }
+
+    pub fn principal(&self) -> &str {
+        &self.principal
+    }
+
+    /// Returns true if the allow rules grant access to the given service handler.
+    pub fn is_allowed(&self, service: &str, handler: Option<&str>) -> bool {
+        self.options.is_allowed(&self.principal, service, handler)
+    }
+}
+
+/// Authenticates the ingress requests using either the configured API keys or JWTs. If no auth
+/// options are configured, requests are passed through untouched.
+#[derive(Clone)]
+pub struct AuthLayer {
+    authenticator: Option<Arc<Authenticator>>,
+}
+
+impl AuthLayer {
+    pub fn new(options: Option<&IngressAuthOptions>) -> Result<Self, AuthError> {
+        let authenticator = options
+            .map(|options| {
+                let jwks = options
+                    .jwks_file
+                    .as_deref()
+                    .map(ReloadingJwks::new)
+                    .transpose()?;
+                Authenticator::new(options.clone(), jwks).map(Arc::new)
+            })
+            .transpose()?;
+
+        Ok(Self { authenticator })
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            authenticator: self.authenticator.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Auth<S> {
+    inner: S,
+    authenticator: Option<Arc<Authenticator>>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Auth<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: Default,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response<ResBody>, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let Some(authenticator) = &self.authenticator else {
+            return Either::Right(self.inner.call(req));
+        };
+
+        // Never trust the principal header sent by the client
+        req.headers_mut().remove(&authenticator.principal_header);
+
+        let path = req.uri().path();
+        if path == HEALTH_PATH || path.starts_with(WEBHOOK_PATH_PREFIX) {
+            // Health checks are unauthenticated, while webhooks authenticate with their signature
+            return Either::Right(self.inner.call(req));
+        }
+        if req.method() == Method::OPTIONS
+            && req.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+        {
+            // Browsers never send credentials with CORS preflight requests
+            return Either::Right(self.inner.call(req));
+        }
+
+        match authenticator.authenticate(&req) {
+            Ok(principal) => {
+                if let Ok(value) = HeaderValue::from_str(&principal) {
+                    req.headers_mut()
+                        .insert(authenticator.principal_header.clone(), value);
+                }
+                req.extensions_mut().insert(AuthenticatedPrincipal::new(
+                    principal,
+                    Arc::clone(&authenticator.options),
+                ));
+                Either::Right(self.inner.call(req))
+            }
+            Err(reason) => {
+                debug!("Rejecting unauthenticated ingress request: {reason}");
+                Either::Left(ready(Ok(unauthorized(reason))))
+            }
+        }
+    }
+}
+
+struct Authenticator {
+    options: Arc<IngressAuthOptions>,
+    principal_header: HeaderName,
+    jwt_algorithm: Option<Algorithm>,
+    jwks: Option<ReloadingJwks>,
+}
+
+impl Authenticator {
+    fn new(options: IngressAuthOptions, jwks: Option<ReloadingJwks>) -> Result<Self, AuthError> {
+        let jwt_algorithm = options
+            .jwt_algorithm
+            .as_deref()
+            .map(|alg| {
+                Algorithm::from_str(alg).map_err(|_| AuthError::JwtAlgorithm(alg.to_owned()))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            principal_header: HeaderName::try_from(options.principal_header.as_str())?,
+            options: Arc::new(options),
+            jwt_algorithm,
+            jwks,
+        })
+    }
+
+    fn authenticate<B>(&self, req: &Request<B>) -> Result<String, &'static str> {
+        let token = req
+            .headers()
+            .get(AUTHORIZATION)
+            .ok_or("missing bearer token")?
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or("malformed authorization header")?;
+
+        if let Some(api_key) = self
+            .options
+            .api_keys
+            .iter()
+            .find(|api_key| constant_time_eq(api_key.key.as_bytes(), token.as_bytes()))
+        {
+            return Ok(api_key.principal.clone());
+        }
+
+        self.verify_jwt(token)
+    }
+
+    fn verify_jwt(&self, token: &str) -> Result<String, &'static str> {
+        let jwks = self.jwks.as_ref().ok_or("unknown token")?;
+        let header = jsonwebtoken::decode_header(token).map_err(|_| "unknown token")?;
+
+        let mut jwk_set = jwks.current(false);
+        if find_jwk(&jwk_set, header.kid.as_deref()).is_none() {
+            // The keys might have been rotated in the meantime
+            jwk_set = jwks.current(true);
+        }
+        let jwk = find_jwk(&jwk_set, header.kid.as_deref()).ok_or("unknown signing key")?;
+        let key = DecodingKey::from_jwk(jwk).map_err(|_| "unknown signing key")?;
+
+        // Never trust the algorithm of the unverified token header, it must match the one of the key
+        let algorithm = jwk
+            .common
+            .key_algorithm
+            .as_ref()
+            .and_then(|alg| serde_json::from_value(serde_json::to_value(alg).ok()?).ok())
+            .or(self.jwt_algorithm)
+            .ok_or("unknown signing algorithm")?;
+        if header.alg != algorithm {
+            return Err("unexpected signing algorithm");
+        }
+
+        let mut validation = Validation::new(algorithm);
+        if let Some(issuer) = &self.options.jwt_issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if let Some(audience) = &self.options.jwt_audience {
+            validation.set_audience(&[audience]);
+        } else {
+            validation.validate_aud = false;
+        }
+
+        let claims = jsonwebtoken::decode::<serde_json::Map<String, serde_json::Value>>(
+            token,
+            &key,
+            &validation,
+        )
+        .map_err(|_| "invalid token")?
+        .claims;
+
+        claims
+            .get(&self.options.jwt_principal_claim)
+            .and_then(serde_json::Value::as_str)
+            .map(ToOwned::to_owned)
+            .ok_or("missing principal claim")
+    }
+}
+
+fn find_jwk<'a>(jwk_set: &'a JwkSet, kid: Option<&str>) -> Option<&'a Jwk> {
+    match (kid, jwk_set.keys.as_slice()) {
+        (Some(kid), _) => jwk_set.find(kid),
+        (None, [jwk]) => Some(jwk),
+        (None, _) => None,
+    }
+}
+
+/// JWKS loaded from a file, reloaded when the file changes.
+struct ReloadingJwks {
+    path: PathBuf,
+    jwk_set: ArcSwap<JwkSet>,
+    state: Mutex<JwksState>,
+}
+
+struct JwksState {
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl ReloadingJwks {
+    fn new(path: &Path) -> Result<Self, AuthError> {
+        let modified = modified_time(path);
+        Ok(Self {
+            jwk_set: ArcSwap::from_pointee(load_jwks(path)?),
+            path: path.to_owned(),
+            state: Mutex::new(JwksState {
+                modified,
+                last_check: Instant::now(),
+            }),
+        })
+    }
+
+    /// Returns the current key set, after checking whether the file changed if the check is due.
+    /// With `unknown_kid`, the check is due sooner, as the keys might have been rotated.
+    fn current(&self, unknown_kid: bool) -> Arc<JwkSet> {
+        let check_interval = if unknown_kid {
+            JWKS_UNKNOWN_KID_CHECK_INTERVAL
+        } else {
+            JWKS_RELOAD_CHECK_INTERVAL
+        };
+
+        // Concurrent requests keep using the current keys while one of them checks the file
+        if let Ok(mut state) = self.state.try_lock() {
+            let now = Instant::now();
+            if now.duration_since(state.last_check) >= check_interval {
+                state.last_check = now;
+
+                let modified = modified_time(&self.path);
+                if modified != state.modified {
+                    match load_jwks(&self.path) {
+                        Ok(jwk_set) => {
+                            info!("Reloaded the JWKS file '{}'", self.path.display());
+                            self.jwk_set.store(Arc::new(jwk_set));
+                            state.modified = modified;
+                        }
+                        Err(err) => {
+                            // Keep the previous modification time to retry on the next check, as
+                            // the file might have been only partially written
+                            warn!(
+                                "Failed reloading the JWKS file, keeping the current keys: {err}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        self.jwk_set.load_full()
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn load_jwks(path: &Path) -> Result<JwkSet, AuthError> {
+    let contents = std::fs::read(path).map_err(|source| AuthError::ReadJwks {
+        path: path.to_owned(),
+        source,
+    })?;
+    serde_json::from_slice(&contents).map_err(|source| AuthError::ParseJwks {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn unauthorized<B: Default>(reason: &str) -> Response<B> {
+    Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .header(
+            WWW_AUTHENTICATE,
+            format!("Bearer error=\"invalid_token\", error_description=\"{reason}\""),
+        )
+        .body(Default::default())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+
+    use base64::Engine;
+    use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+    use jsonwebtoken::{EncodingKey, Header};
+    use restate_types::config::ApiKeyOptions;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    const SECRET: &[u8] = b"ingress-auth-test-secret";
+
+    fn jwk_set(kid: &str, secret: &[u8]) -> serde_json::Value {
+        json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": kid,
+                "alg": "HS256",
+                "k": BASE64_URL_SAFE_NO_PAD.encode(secret)
+            }]
+        })
+    }
+
+    fn auth_service(
+        options: IngressAuthOptions,
+    ) -> Auth<impl Service<Request<()>, Response = Response<String>, Error = Infallible> + Clone>
+    {
+        let jwks_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(jwks_file.path(), jwk_set("test-key", SECRET).to_string()).unwrap();
+        auth_service_with_jwks(options, jwks_file.path())
+    }
+
+    fn auth_service_with_jwks(
+        options: IngressAuthOptions,
+        jwks_file: &Path,
+    ) -> Auth<impl Service<Request<()>, Response = Response<String>, Error = Infallible> + Clone>
+    {
+        let jwks = ReloadingJwks::new(jwks_file).unwrap();
+
+        Auth {
+            inner: tower::service_fn(|req: Request<()>| async move {
+                let principal = req
+                    .headers()
+                    .get("x-restate-principal")
+                    .map(|value| value.to_str().unwrap().to_owned())
+                    .unwrap_or_default();
+                Ok::<_, Infallible>(Response::new(principal))
+            }),
+            authenticator: Some(Arc::new(Authenticator::new(options, Some(jwks)).unwrap())),
+        }
+    }
+
+    fn request(path: &str, token: Option<&str>) -> Request<()> {
+        let mut builder = Request::get(path).header("x-restate-principal", "mallory");
+        if let Some(token) = token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        builder.body(()).unwrap()
+    }
+
+    fn jwt(claims: serde_json::Value) -> String {
+        jwt_signed_by("test-key", SECRET, claims)
+    }
+
+    fn jwt_signed_by(kid: &str, secret: &[u8], claims: serde_json::Value) -> String {
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some(kid.to_owned());
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(secret)).unwrap()
+    }
+
+    fn expiration() -> u64 {
+        jsonwebtoken::get_current_timestamp() + 60
+    }
+
+    #[restate_core::test]
+    async fn api_key() {
+        let service = auth_service(IngressAuthOptions {
+            api_keys: vec![ApiKeyOptions {
+                principal: "alice".to_owned(),
+                key: "alice-key".to_owned(),
+            }],
+            ..IngressAuthOptions::default()
+        });
+
+        let response = service
+            .clone()
+            .oneshot(request("/Greeter/greet", Some("alice-key")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), "alice");
+
+        let response = service
+            .oneshot(request("/Greeter/greet", Some("bob-key")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.headers().contains_key(WWW_AUTHENTICATE));
+    }
+
+    #[restate_core::test]
+    async fn jwt_token() {
+        let service = auth_service(IngressAuthOptions {
+            jwt_issuer: Some("https://issuer.example.com".to_owned()),
+            ..IngressAuthOptions::default()
+        });
+
+        let response = service
+            .clone()
+            .oneshot(request(
+                "/Greeter/greet",
+                Some(&jwt(json!({
+                    "sub": "bob",
+                    "iss": "https://issuer.example.com",
+                    "exp": expiration()
+                }))),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), "bob");
+
+        let response = service
+            .clone()
+            .oneshot(request(
+                "/Greeter/greet",
+                Some(&jwt(json!({
+                    "sub": "bob",
+                    "iss": "https://other.example.com",
+                    "exp": expiration()
+                }))),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = service
+            .oneshot(request("/Greeter/greet", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[restate_core::test]
+    async fn jwt_algorithm_must_match_the_key() {
+        let service = auth_service(IngressAuthOptions::default());
+
+        let mut header = Header::new(Algorithm::HS512);
+        header.kid = Some("test-key".to_owned());
+        let token = jsonwebtoken::encode(
+            &header,
+            &json!({"sub": "bob", "exp": expiration()}),
+            &EncodingKey::from_secret(SECRET),
+        )
+        .unwrap();
+
+        let response = service
+            .oneshot(request("/Greeter/greet", Some(&token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[restate_core::test]
+    async fn jwks_is_reloaded_on_unknown_key() {
+        let jwks_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(jwks_file.path(), jwk_set("test-key", SECRET).to_string()).unwrap();
+        let service = auth_service_with_jwks(IngressAuthOptions::default(), jwks_file.path());
+
+        let token = jwt_signed_by(
+            "rotated-key",
+            b"rotated-secret",
+            json!({"sub": "bob", "exp": expiration()}),
+        );
+        let response = service
+            .clone()
+            .oneshot(request("/Greeter/greet", Some(&token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Rotate the key, making sure the modification time changes
+        tokio::time::sleep(JWKS_UNKNOWN_KID_CHECK_INTERVAL).await;
+        std::fs::write(
+            jwks_file.path(),
+            jwk_set("rotated-key", b"rotated-secret").to_string(),
+        )
+        .unwrap();
+
+        let response = service
+            .oneshot(request("/Greeter/greet", Some(&token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), "bob");
+    }
+
+    #[restate_core::test]
+    async fn health_is_unauthenticated() {
+        let response = auth_service(IngressAuthOptions::default())
+            .oneshot(request("/restate/health", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // The principal sent by the client is discarded
+        assert_eq!(response.body(), "");
+    }
+}
@@ -8,5 +8,6 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+pub mod auth;
 pub mod load_shed;
 pub mod tracing_context_extractor;
@@ -18,12 +18,17 @@ mod server;
 pub use server::{HyperServerIngress, IngressServerError, StartSignal};
 
 use bytes::Bytes;
+use futures::Stream;
+use futures::future::BoxFuture;
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
 
-use partition_processor_rpc_client::{AttachInvocationResponse, GetInvocationOutputResponse};
+use partition_processor_rpc_client::{
+    AttachInvocationResponse, DescribeInvocationResponse, GetInvocationOutputResponse,
+    GetInvocationProgressResponse,
+};
 use restate_types::identifiers::InvocationId;
-use restate_types::invocation::{InvocationQuery, InvocationRequest, InvocationResponse};
+use restate_types::invocation::{Header, InvocationQuery, InvocationRequest, InvocationResponse};
 use restate_types::journal_v2::Signal;
 use restate_types::net::partition_processor::{InvocationOutput, SubmittedInvocationNotification};
 
@@ -60,6 +65,19 @@ pub trait RequestDispatcher {
         invocation_request: InvocationRequest,
     ) -> impl Future<Output = Result<SubmittedInvocationNotification, RequestDispatcherError>> + Send;
 
+    /// Send a batch of invocations, waiting for the [`SubmittedInvocationNotification`] of each one.
+    /// The returned stream yields the result of each invocation, together with its index in the
+    /// batch, as soon as it's available.
+    fn send_batch(
+        &self,
+        invocation_requests: Vec<InvocationRequest>,
+    ) -> impl Stream<
+        Item = (
+            usize,
+            Result<SubmittedInvocationNotification, RequestDispatcherError>,
+        ),
+    > + Send;
+
     /// Call: append invocation and wait for its response
     fn call(
         &self,
@@ -78,6 +96,24 @@ pub trait RequestDispatcher {
         invocation_query: InvocationQuery,
     ) -> impl Future<Output = Result<GetInvocationOutputResponse, RequestDispatcherError>> + Send;
 
+    /// Get the invocation progress, used to stream its status transitions.
+    fn get_invocation_progress(
+        &self,
+        invocation_query: InvocationQuery,
+    ) -> impl Future<Output = Result<GetInvocationProgressResponse, RequestDispatcherError>> + Send;
+
+    /// Describe the invocation using the given query, without blocking.
+    fn describe_invocation(
+        &self,
+        invocation_query: InvocationQuery,
+    ) -> impl Future<Output = Result<DescribeInvocationResponse, RequestDispatcherError>> + Send;
+
+    /// Cancel the invocation.
+    fn cancel_invocation(
+        &self,
+        invocation_id: InvocationId,
+    ) -> impl Future<Output = Result<(), RequestDispatcherError>> + Send;
+
     /// Send invocation response (for awakeables).
     /// **NOTE:** This works only for targeting invocations using Journal Table V1/Service Protocol <= V3.
     fn send_invocation_response(
@@ -92,6 +128,35 @@ pub trait RequestDispatcher {
         target_invocation: InvocationId,
         signal: Signal,
     ) -> impl Future<Output = Result<(), RequestDispatcherError>> + Send;
+
+    /// Dispatch a verified webhook delivery to the subscriptions consuming from the webhook.
+    /// Returns the number of subscriptions the delivery was dispatched to.
+    fn dispatch_webhook_delivery(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> impl Future<Output = Result<usize, RequestDispatcherError>> + Send;
+}
+
+/// Delivery received on a webhook subscription source, whose signature was verified by the ingress.
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    /// Name of the webhook, as configured in the `IngressOptions`.
+    pub webhook: String,
+    /// Unique id of the delivery, retries of the same delivery carry the same id.
+    pub id: String,
+    /// Key of the target, used by subscriptions targeting virtual objects and workflows.
+    pub key: Option<String>,
+    pub headers: Vec<Header>,
+    pub payload: Bytes,
+}
+
+/// Dispatches the webhook deliveries to the running webhook subscriptions. This is implemented
+/// by the worker, owning the subscriptions lifecycle.
+pub trait WebhookDispatcher: Send + Sync + 'static {
+    fn dispatch(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> BoxFuture<'_, Result<usize, RequestDispatcherError>>;
 }
 
 // Contains some mocks we use in unit tests in this crate
@@ -108,10 +173,11 @@ mod mocks {
     };
     use restate_types::schema::service::test_util::MockServiceMetadataResolver;
     use restate_types::schema::service::{
-        HandlerMetadata, ServiceMetadata, ServiceMetadataResolver,
+        HandlerMetadata, ServiceMetadata, ServiceMetadataResolver, ServiceProtobufSchemas,
     };
     use serde::{Deserialize, Serialize};
     use serde_json::Value;
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     use super::*;
@@ -130,6 +196,7 @@ mod mocks {
     pub(crate) struct MockSchemas(
         pub(crate) MockServiceMetadataResolver,
         pub(crate) MockInvocationTargetResolver,
+        pub(crate) HashMap<String, ServiceProtobufSchemas>,
     );
 
     impl MockSchemas {
@@ -150,6 +217,7 @@ mod mocks {
                     output_description: "any".to_string(),
                     input_json_schema: None,
                     output_json_schema: None,
+                    retry_policy: None,
                 }],
                 ty: invocation_target_metadata.target_ty.into(),
                 documentation: None,
@@ -159,8 +227,12 @@ mod mocks {
                 public: invocation_target_metadata.public,
                 idempotency_retention: DEFAULT_IDEMPOTENCY_RETENTION.into(),
                 workflow_completion_retention: None,
+                journal_retention: None,
                 inactivity_timeout: None,
                 abort_timeout: None,
+                retry_policy: None,
+                dead_letter_queue: false,
+                cors: None,
             });
             self.1
                 .add(service_name, [(handler_name, invocation_target_metadata)]);
@@ -175,6 +247,15 @@ mod mocks {
             self.add_service_and_target(service_name, handler_name, invocation_target_metadata);
             self
         }
+
+        pub fn with_protobuf(
+            mut self,
+            service_name: &str,
+            protobuf: ServiceProtobufSchemas,
+        ) -> Self {
+            self.2.insert(service_name.to_owned(), protobuf);
+            self
+        }
     }
 
     impl ServiceMetadataResolver for MockSchemas {
@@ -193,6 +274,13 @@ mod mocks {
             self.0.resolve_latest_service_type(service_name)
         }
 
+        fn resolve_latest_service_protobuf(
+            &self,
+            service_name: impl AsRef<str>,
+        ) -> Option<ServiceProtobufSchemas> {
+            self.2.get(service_name.as_ref()).cloned()
+        }
+
         fn list_services(&self) -> Vec<ServiceMetadata> {
             self.0.list_services()
         }
@@ -237,6 +325,18 @@ mod mocks {
             MockRequestDispatcher::send(self, invocation_request)
         }
 
+        fn send_batch(
+            &self,
+            invocation_requests: Vec<InvocationRequest>,
+        ) -> impl Stream<
+            Item = (
+                usize,
+                Result<SubmittedInvocationNotification, RequestDispatcherError>,
+            ),
+        > + Send {
+            MockRequestDispatcher::send_batch(self, invocation_requests)
+        }
+
         fn call(
             &self,
             invocation_request: InvocationRequest,
@@ -260,6 +360,29 @@ mod mocks {
             MockRequestDispatcher::get_invocation_output(self, invocation_query)
         }
 
+        fn get_invocation_progress(
+            &self,
+            invocation_query: InvocationQuery,
+        ) -> impl Future<Output = Result<GetInvocationProgressResponse, RequestDispatcherError>> + Send
+        {
+            MockRequestDispatcher::get_invocation_progress(self, invocation_query)
+        }
+
+        fn describe_invocation(
+            &self,
+            invocation_query: InvocationQuery,
+        ) -> impl Future<Output = Result<DescribeInvocationResponse, RequestDispatcherError>> + Send
+        {
+            MockRequestDispatcher::describe_invocation(self, invocation_query)
+        }
+
+        fn cancel_invocation(
+            &self,
+            invocation_id: InvocationId,
+        ) -> impl Future<Output = Result<(), RequestDispatcherError>> + Send {
+            MockRequestDispatcher::cancel_invocation(self, invocation_id)
+        }
+
         fn send_invocation_response(
             &self,
             invocation_response: InvocationResponse,
@@ -274,5 +397,12 @@ mod mocks {
         ) -> impl Future<Output = Result<(), RequestDispatcherError>> + Send {
             MockRequestDispatcher::send_signal(self, target_invocation, signal)
         }
+
+        fn dispatch_webhook_delivery(
+            &self,
+            delivery: WebhookDelivery,
+        ) -> impl Future<Output = Result<usize, RequestDispatcherError>> + Send {
+            MockRequestDispatcher::dispatch_webhook_delivery(self, delivery)
+        }
     }
 }
@@ -17,9 +17,13 @@ pub const INGRESS_REQUESTS: &str = "restate.ingress.requests.total";
 pub const REQUEST_ADMITTED: &str = "admitted";
 pub const REQUEST_COMPLETED: &str = "completed";
 pub const REQUEST_DENIED_THROTTLE: &str = "throttled";
+pub const REQUEST_RATE_LIMITED: &str = "rate_limited";
 
 pub const INGRESS_REQUEST_DURATION: &str = "restate.ingress.request_duration.seconds";
 
+pub const INGRESS_INPUT_VALIDATION_FAILURES: &str =
+    "restate.ingress.input_validation_failures.total";
+
 pub(crate) fn describe_metrics() {
     describe_counter!(
         INGRESS_REQUESTS,
@@ -31,4 +35,9 @@ pub(crate) fn describe_metrics() {
         Unit::Seconds,
         "Total latency of Ingress request processing in seconds"
     );
+    describe_counter!(
+        INGRESS_INPUT_VALIDATION_FAILURES,
+        Unit::Count,
+        "Number of ingress requests rejected because the body doesn't match the input JSON schema of the handler"
+    );
 }
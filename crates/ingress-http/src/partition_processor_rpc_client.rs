@@ -16,15 +16,16 @@ use restate_core::network::{NetworkSender, RpcReplyError, Swimlane};
 use restate_core::network::{Networking, TransportConnect};
 use restate_core::partitions::PartitionRouting;
 use restate_types::identifiers::{
-    InvocationId, PartitionId, PartitionProcessorRpcRequestId, WithPartitionKey,
+    InvocationId, PartitionId, PartitionKey, PartitionProcessorRpcRequestId, WithPartitionKey,
 };
 use restate_types::invocation::{InvocationQuery, InvocationRequest, InvocationResponse};
 use restate_types::journal_v2::Signal;
 use restate_types::live::Live;
 use restate_types::net::partition_processor::{
-    AppendInvocationReplyOn, GetInvocationOutputResponseMode, InvocationOutput,
-    PartitionProcessorRpcError, PartitionProcessorRpcRequest, PartitionProcessorRpcRequestInner,
-    PartitionProcessorRpcResponse, SubmittedInvocationNotification,
+    AppendInvocationReplyOn, GetInvocationOutputResponseMode, InvocationDescription,
+    InvocationOutput, InvocationProgress, PartitionProcessorRpcError, PartitionProcessorRpcRequest,
+    PartitionProcessorRpcRequestInner, PartitionProcessorRpcResponse,
+    SubmittedInvocationNotification,
 };
 use restate_types::partition_table::{FindPartition, PartitionTable, PartitionTableError};
 
@@ -125,6 +126,18 @@ pub enum GetInvocationOutputResponse {
     Ready(InvocationOutput),
 }
 
+#[derive(Debug, Clone)]
+pub enum GetInvocationProgressResponse {
+    NotFound,
+    Progress(InvocationProgress),
+}
+
+#[derive(Debug, Clone)]
+pub enum DescribeInvocationResponse {
+    NotFound,
+    Description(InvocationDescription),
+}
+
 pub struct PartitionProcessorRpcClient<C> {
     networking: Networking<C>,
     partition_table: Live<PartitionTable>,
@@ -153,6 +166,17 @@ impl<C> PartitionProcessorRpcClient<C> {
             partition_routing,
         }
     }
+
+    /// Returns the partition currently owning the given partition key.
+    pub fn find_partition_id(
+        &self,
+        partition_key: PartitionKey,
+    ) -> Result<PartitionId, PartitionProcessorRpcClientError> {
+        Ok(self
+            .partition_table
+            .pinned()
+            .find_partition_id(partition_key)?)
+    }
 }
 
 impl<C> PartitionProcessorRpcClient<C>
@@ -187,6 +211,36 @@ where
         Ok(submit_notification)
     }
 
+    /// Append the invocations to the log, waiting for their submit notifications. All the
+    /// invocations must belong to the same partition.
+    ///
+    /// The notifications are returned in the same order of the invocation requests.
+    pub async fn append_invocations_and_wait_submit_notifications(
+        &self,
+        request_id: PartitionProcessorRpcRequestId,
+        invocation_requests: Vec<InvocationRequest>,
+    ) -> Result<Vec<SubmittedInvocationNotification>, PartitionProcessorRpcClientError> {
+        let len = invocation_requests.len();
+        let response = self
+            .resolve_partition_id_and_send(
+                request_id,
+                PartitionProcessorRpcRequestInner::AppendInvocations(invocation_requests),
+            )
+            .await?;
+
+        let_assert!(
+            PartitionProcessorRpcResponse::SubmittedBatch(submit_notifications) = response,
+            "Expecting PartitionProcessorRpcResponse::SubmittedBatch"
+        );
+        debug_assert_eq!(
+            len,
+            submit_notifications.len(),
+            "Conflicting submit notifications received"
+        );
+
+        Ok(submit_notifications)
+    }
+
     /// Append the invocation and wait for its output.
     pub async fn append_invocation_and_wait_output(
         &self,
@@ -276,6 +330,78 @@ where
         })
     }
 
+    /// Get the progress of the invocation, without blocking.
+    pub async fn get_invocation_progress(
+        &self,
+        request_id: PartitionProcessorRpcRequestId,
+        invocation_query: InvocationQuery,
+    ) -> Result<GetInvocationProgressResponse, PartitionProcessorRpcClientError> {
+        let response = self
+            .resolve_partition_id_and_send(
+                request_id,
+                PartitionProcessorRpcRequestInner::GetInvocationProgress(invocation_query),
+            )
+            .await?;
+
+        Ok(match response {
+            PartitionProcessorRpcResponse::NotFound => GetInvocationProgressResponse::NotFound,
+            PartitionProcessorRpcResponse::Progress(progress) => {
+                GetInvocationProgressResponse::Progress(progress)
+            }
+            _ => {
+                panic!(
+                    "Expecting either PartitionProcessorRpcResponse::Progress or PartitionProcessorRpcResponse::NotFound"
+                )
+            }
+        })
+    }
+
+    /// Describe the invocation, without blocking.
+    pub async fn describe_invocation(
+        &self,
+        request_id: PartitionProcessorRpcRequestId,
+        invocation_query: InvocationQuery,
+    ) -> Result<DescribeInvocationResponse, PartitionProcessorRpcClientError> {
+        let response = self
+            .resolve_partition_id_and_send(
+                request_id,
+                PartitionProcessorRpcRequestInner::DescribeInvocation(invocation_query),
+            )
+            .await?;
+
+        Ok(match response {
+            PartitionProcessorRpcResponse::NotFound => DescribeInvocationResponse::NotFound,
+            PartitionProcessorRpcResponse::Description(description) => {
+                DescribeInvocationResponse::Description(description)
+            }
+            _ => {
+                panic!(
+                    "Expecting either PartitionProcessorRpcResponse::Description or PartitionProcessorRpcResponse::NotFound"
+                )
+            }
+        })
+    }
+
+    pub async fn append_invocation_cancellation(
+        &self,
+        request_id: PartitionProcessorRpcRequestId,
+        invocation_id: InvocationId,
+    ) -> Result<(), PartitionProcessorRpcClientError> {
+        let response = self
+            .resolve_partition_id_and_send(
+                request_id,
+                PartitionProcessorRpcRequestInner::CancelInvocation(invocation_id),
+            )
+            .await?;
+
+        let_assert!(
+            PartitionProcessorRpcResponse::Appended = response,
+            "Expecting PartitionProcessorRpcResponse::Appended"
+        );
+
+        Ok(())
+    }
+
     pub async fn append_invocation_response(
         &self,
         request_id: PartitionProcessorRpcRequestId,
@@ -322,10 +448,7 @@ where
         request_id: PartitionProcessorRpcRequestId,
         inner_request: PartitionProcessorRpcRequestInner,
     ) -> Result<PartitionProcessorRpcResponse, PartitionProcessorRpcClientError> {
-        let partition_id = self
-            .partition_table
-            .pinned()
-            .find_partition_id(inner_request.partition_key())?;
+        let partition_id = self.find_partition_id(inner_request.partition_key())?;
 
         let node_id = self
             .partition_routing
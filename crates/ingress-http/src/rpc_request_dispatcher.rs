@@ -9,28 +9,41 @@
 // by the Apache License, Version 2.0.
 
 use anyhow::anyhow;
+use futures::{Stream, StreamExt, stream};
+use std::collections::HashMap;
 use std::future::Future;
+use std::mem;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{Instrument, debug_span, trace};
 
 use restate_core::network::TransportConnect;
-use restate_types::identifiers::{InvocationId, PartitionProcessorRpcRequestId, WithInvocationId};
+use restate_types::identifiers::{
+    InvocationId, PartitionId, PartitionProcessorRpcRequestId, WithInvocationId, WithPartitionKey,
+};
 use restate_types::invocation::{InvocationQuery, InvocationRequest, InvocationResponse};
 use restate_types::journal_v2::Signal;
 use restate_types::net::partition_processor::{InvocationOutput, SubmittedInvocationNotification};
 use restate_types::retries::RetryPolicy;
 
 use crate::partition_processor_rpc_client::{
-    AttachInvocationResponse, GetInvocationOutputResponse,
+    AttachInvocationResponse, DescribeInvocationResponse, GetInvocationOutputResponse,
+    GetInvocationProgressResponse,
 };
 use crate::partition_processor_rpc_client::{
     PartitionProcessorRpcClient, PartitionProcessorRpcClientError,
 };
-use crate::{RequestDispatcher, RequestDispatcherError};
+use crate::{RequestDispatcher, RequestDispatcherError, WebhookDelivery, WebhookDispatcher};
+
+/// Maximum number of invocations appended with a single rpc, when sending a batch.
+const BATCH_APPEND_CHUNK_SIZE: usize = 500;
+/// Maximum number of in-flight appends per partition, when sending a batch.
+const BATCH_APPENDS_PER_PARTITION: usize = 4;
 
 pub struct RpcRequestDispatcher<C> {
     partition_processor_rpc_client: PartitionProcessorRpcClient<C>,
     retry_policy: RetryPolicy,
+    webhook_dispatcher: Option<Arc<dyn WebhookDispatcher>>,
 }
 
 impl<T: Clone> Clone for RpcRequestDispatcher<T> {
@@ -38,6 +51,7 @@ impl<T: Clone> Clone for RpcRequestDispatcher<T> {
         RpcRequestDispatcher {
             partition_processor_rpc_client: self.partition_processor_rpc_client.clone(),
             retry_policy: self.retry_policy.clone(),
+            webhook_dispatcher: self.webhook_dispatcher.clone(),
         }
     }
 }
@@ -48,9 +62,20 @@ impl<C> RpcRequestDispatcher<C> {
             partition_processor_rpc_client,
             // TODO figure out how to tune this?
             retry_policy: RetryPolicy::fixed_delay(Duration::from_millis(50), None),
+            webhook_dispatcher: None,
         }
     }
 
+    /// Webhook deliveries are dispatched through the worker running on the same node, without it
+    /// the webhooks are unavailable.
+    pub fn with_webhook_dispatcher(
+        mut self,
+        webhook_dispatcher: Arc<dyn WebhookDispatcher>,
+    ) -> Self {
+        self.webhook_dispatcher = Some(webhook_dispatcher);
+        self
+    }
+
     async fn execute_rpc<Fn, Fut, T>(
         &self,
         is_idempotent: bool,
@@ -79,6 +104,54 @@ impl<C> RpcRequestDispatcher<C> {
     }
 }
 
+impl<C> RpcRequestDispatcher<C>
+where
+    C: TransportConnect,
+{
+    /// Appends the invocations of the same partition with a single rpc, returning the result of
+    /// each one together with its index in the batch.
+    async fn send_chunk(
+        &self,
+        partition_id: PartitionId,
+        chunk: Vec<(usize, InvocationRequest)>,
+    ) -> Vec<(
+        usize,
+        Result<SubmittedInvocationNotification, RequestDispatcherError>,
+    )> {
+        let request_id = PartitionProcessorRpcRequestId::default();
+        let (indexes, invocation_requests): (Vec<_>, Vec<_>) = chunk.into_iter().unzip();
+        let is_idempotent = invocation_requests
+            .iter()
+            .all(InvocationRequest::is_idempotent);
+
+        match self
+            .execute_rpc(is_idempotent, || {
+                self.partition_processor_rpc_client
+                    .append_invocations_and_wait_submit_notifications(
+                        request_id,
+                        invocation_requests.clone(),
+                    )
+            })
+            .instrument(
+                debug_span!("send invocations", %request_id, %partition_id, size = indexes.len()),
+            )
+            .await
+        {
+            Ok(notifications) => indexes
+                .into_iter()
+                .zip(notifications.into_iter().map(Ok))
+                .collect(),
+            Err(err) => {
+                let err = err.to_string();
+                indexes
+                    .into_iter()
+                    .map(|index| (index, Err(anyhow!("{err}").into())))
+                    .collect()
+            }
+        }
+    }
+}
+
 impl<C> RequestDispatcher for RpcRequestDispatcher<C>
 where
     C: TransportConnect,
@@ -100,6 +173,63 @@ where
         .await
     }
 
+    fn send_batch(
+        &self,
+        invocation_requests: Vec<InvocationRequest>,
+    ) -> impl Stream<
+        Item = (
+            usize,
+            Result<SubmittedInvocationNotification, RequestDispatcherError>,
+        ),
+    > + Send {
+        // Group the invocations per partition, so that each partition gets its own pipeline of
+        // appends and a slow partition doesn't hold back the others. Invocations of the same
+        // partition are appended together, in chunks.
+        let mut partitions: HashMap<PartitionId, Vec<(usize, InvocationRequest)>> = HashMap::new();
+        // Invocations whose partition cannot be resolved yet are sent one by one, sending them
+        // will retry the resolution.
+        let mut unresolved = vec![];
+        for (index, invocation_request) in invocation_requests.into_iter().enumerate() {
+            match self
+                .partition_processor_rpc_client
+                .find_partition_id(invocation_request.partition_key())
+            {
+                Ok(partition_id) => partitions
+                    .entry(partition_id)
+                    .or_default()
+                    .push((index, invocation_request)),
+                Err(_) => unresolved.push((index, invocation_request)),
+            }
+        }
+        trace!("Sending batch to {} partitions", partitions.len());
+
+        let unresolved = stream::iter(unresolved)
+            .then(|(index, invocation_request)| async move {
+                (index, self.send(invocation_request).await)
+            })
+            .boxed();
+
+        stream::select_all(
+            partitions
+                .into_iter()
+                .map(|(partition_id, mut invocation_requests)| {
+                    let mut chunks = vec![];
+                    while invocation_requests.len() > BATCH_APPEND_CHUNK_SIZE {
+                        let rest = invocation_requests.split_off(BATCH_APPEND_CHUNK_SIZE);
+                        chunks.push(mem::replace(&mut invocation_requests, rest));
+                    }
+                    chunks.push(invocation_requests);
+
+                    stream::iter(chunks)
+                        .map(move |chunk| self.send_chunk(partition_id, chunk))
+                        .buffer_unordered(BATCH_APPENDS_PER_PARTITION)
+                        .flat_map(stream::iter)
+                        .boxed()
+                })
+                .chain(std::iter::once(unresolved)),
+        )
+    }
+
     async fn call(
         &self,
         invocation_request: InvocationRequest,
@@ -140,6 +270,45 @@ where
         .await
     }
 
+    async fn get_invocation_progress(
+        &self,
+        invocation_query: InvocationQuery,
+    ) -> Result<GetInvocationProgressResponse, RequestDispatcherError> {
+        let request_id = PartitionProcessorRpcRequestId::default();
+        self.execute_rpc(true, || {
+            self.partition_processor_rpc_client
+                .get_invocation_progress(request_id, invocation_query.clone())
+        })
+        .instrument(debug_span!("get invocation progress", %request_id, invocation_id = %invocation_query.to_invocation_id()))
+        .await
+    }
+
+    async fn describe_invocation(
+        &self,
+        invocation_query: InvocationQuery,
+    ) -> Result<DescribeInvocationResponse, RequestDispatcherError> {
+        let request_id = PartitionProcessorRpcRequestId::default();
+        self.execute_rpc(true, || {
+            self.partition_processor_rpc_client
+                .describe_invocation(request_id, invocation_query.clone())
+        })
+        .instrument(debug_span!("describe invocation", %request_id, invocation_id = %invocation_query.to_invocation_id()))
+        .await
+    }
+
+    async fn cancel_invocation(
+        &self,
+        invocation_id: InvocationId,
+    ) -> Result<(), RequestDispatcherError> {
+        let request_id = PartitionProcessorRpcRequestId::default();
+        self.execute_rpc(true, || {
+            self.partition_processor_rpc_client
+                .append_invocation_cancellation(request_id, invocation_id)
+        })
+        .instrument(debug_span!("cancel invocation", %request_id, %invocation_id))
+        .await
+    }
+
     async fn send_invocation_response(
         &self,
         invocation_response: InvocationResponse,
@@ -166,4 +335,20 @@ where
             .instrument(debug_span!("send invocation response", %request_id, invocation_id = %target_invocation))
             .await
     }
+
+    async fn dispatch_webhook_delivery(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> Result<usize, RequestDispatcherError> {
+        let Some(webhook_dispatcher) = &self.webhook_dispatcher else {
+            return Err(
+                anyhow!("webhooks are unavailable, this node doesn't run the worker role").into(),
+            );
+        };
+        let webhook_span = debug_span!("dispatch webhook delivery", webhook = %delivery.webhook, delivery_id = %delivery.id);
+        webhook_dispatcher
+            .dispatch(delivery)
+            .instrument(webhook_span)
+            .await
+    }
 }
@@ -10,15 +10,18 @@
 
 use super::*;
 
-use crate::handler::Handler;
+use crate::handler::{Handler, ResponseBody};
 use codederror::CodedError;
 use http::{Request, Response};
-use http_body_util::Full;
 use hyper::body::Incoming;
 use hyper_util::rt::TokioIo;
 use hyper_util::server::conn::auto;
+use restate_core::network::tls::{ReloadingTlsAcceptor, TlsError};
 use restate_core::{TaskCenter, TaskKind, cancellation_watcher};
-use restate_types::config::IngressOptions;
+use restate_types::config::{
+    IngressAuthOptions, IngressCorsOptions, IngressGrpcOptions, IngressOptions,
+    IngressRateLimitOptions, TlsOptions,
+};
 use restate_types::health::HealthStatus;
 use restate_types::live::Live;
 use restate_types::protobuf::common::IngressStatus;
@@ -27,12 +30,14 @@ use restate_types::schema::service::ServiceMetadataResolver;
 use std::convert::Infallible;
 use std::future::Future;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::oneshot;
+use tokio_util::either::Either;
 use tower::{ServiceBuilder, ServiceExt};
 use tower_http::cors::CorsLayer;
 use tower_http::normalize_path::NormalizePathLayer;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 pub type StartSignal = oneshot::Receiver<SocketAddr>;
 
@@ -47,6 +52,12 @@ pub enum IngressServerError {
         #[source]
         source: std::io::Error,
     },
+    #[error("failed configuring the ingress authentication: {0}")]
+    #[code(unknown)]
+    Auth(#[from] layers::auth::AuthError),
+    #[error("failed configuring the ingress TLS: {0}")]
+    #[code(unknown)]
+    Tls(#[from] TlsError),
     #[error("error while running ingress http server: {0}")]
     #[code(unknown)]
     Running(#[from] hyper::Error),
@@ -55,6 +66,11 @@ pub enum IngressServerError {
 pub struct HyperServerIngress<Schemas, Dispatcher> {
     listening_addr: SocketAddr,
     concurrency_limit: usize,
+    auth: Option<IngressAuthOptions>,
+    cors: Option<IngressCorsOptions>,
+    rate_limit: Option<IngressRateLimitOptions>,
+    grpc: Option<IngressGrpcOptions>,
+    tls: Option<TlsOptions>,
 
     // Parameters to build the layers
     schemas: Live<Schemas>,
@@ -77,13 +93,18 @@ where
         health: HealthStatus<IngressStatus>,
     ) -> HyperServerIngress<Schemas, Dispatcher> {
         crate::metric_definitions::describe_metrics();
-        let (hyper_ingress_server, _) = HyperServerIngress::new(
+        let (mut hyper_ingress_server, _) = HyperServerIngress::new(
             ingress_options.bind_address,
             ingress_options.concurrent_api_requests_limit(),
             schemas,
             dispatcher,
             health,
         );
+        hyper_ingress_server.auth = ingress_options.auth.clone();
+        hyper_ingress_server.cors = ingress_options.cors.clone();
+        hyper_ingress_server.rate_limit = ingress_options.rate_limit.clone();
+        hyper_ingress_server.grpc = ingress_options.grpc.clone();
+        hyper_ingress_server.tls = ingress_options.tls.clone();
 
         hyper_ingress_server
     }
@@ -107,6 +128,11 @@ where
         let ingress = Self {
             listening_addr,
             concurrency_limit,
+            auth: None,
+            cors: None,
+            rate_limit: None,
+            grpc: None,
+            tls: None,
             schemas,
             dispatcher,
             health,
@@ -120,12 +146,24 @@ where
         let HyperServerIngress {
             listening_addr,
             concurrency_limit,
+            auth,
+            cors,
+            rate_limit,
+            grpc,
+            tls,
             schemas,
             dispatcher,
             health,
             start_signal_tx,
         } = self;
 
+        let auth_layer =
+            layers::auth::AuthLayer::new(auth.as_ref()).map_err(IngressServerError::Auth)?;
+        let tls_acceptor = tls
+            .map(|tls| ReloadingTlsAcceptor::new(tls).map(Arc::new))
+            .transpose()
+            .map_err(IngressServerError::Tls)?;
+
         // We create a TcpListener and bind it
         let listener =
             TcpListener::bind(listening_addr)
@@ -142,12 +180,21 @@ where
             })?;
 
         // Prepare the handler
+        let has_cors_policy = cors.is_some();
+        let handler = Handler::new(schemas, dispatcher)
+            .with_cors(cors)
+            .with_rate_limit(rate_limit)
+            .with_grpc(grpc);
         let service = ServiceBuilder::new()
             .layer(NormalizePathLayer::trim_trailing_slash())
             .layer(layers::load_shed::LoadShedLayer::new(concurrency_limit))
-            .layer(CorsLayer::very_permissive())
+            // Without a configured policy, any origin is allowed. Otherwise, the policy is resolved
+            // by the handler, as it can be overridden per service.
+            .option_layer((!has_cors_policy).then(CorsLayer::very_permissive))
+            .option_layer(has_cors_policy.then(|| crate::handler::CorsLayer::new(handler.clone())))
+            .layer(auth_layer)
             .layer(layers::tracing_context_extractor::HttpTraceContextExtractorLayer)
-            .service(Handler::new(schemas, dispatcher));
+            .service(handler);
 
         info!(
             net.host.addr = %local_addr.ip(),
@@ -167,7 +214,7 @@ where
             tokio::select! {
                 res = listener.accept() => {
                     let (stream, remote_peer) = res?;
-                    Self::handle_connection(stream, remote_peer, service.clone())?;
+                    Self::handle_connection(stream, remote_peer, tls_acceptor.clone(), service.clone())?;
                 }
                   _ = &mut shutdown => {
                     return Ok(());
@@ -179,13 +226,14 @@ where
     fn handle_connection<T, F>(
         stream: TcpStream,
         remote_peer: SocketAddr,
+        tls_acceptor: Option<Arc<ReloadingTlsAcceptor>>,
         handler: T,
     ) -> anyhow::Result<()>
     where
         F: Send,
         T: tower::Service<
                 Request<Incoming>,
-                Response = Response<Full<Bytes>>,
+                Response = Response<ResponseBody>,
                 Error = Infallible,
                 Future = F,
             > + Clone
@@ -193,7 +241,6 @@ where
             + 'static,
     {
         let connect_info = ConnectInfo::new(remote_peer);
-        let io = TokioIo::new(stream);
         let handler = hyper_util::service::TowerToHyperService::new(handler.map_request(
             move |mut req: Request<Incoming>| {
                 req.extensions_mut().insert(connect_info);
@@ -203,6 +250,18 @@ where
 
         // Spawn a tokio task to serve the connection
         TaskCenter::spawn(TaskKind::Ingress, "ingress", async move {
+            let stream = match tls_acceptor {
+                Some(tls_acceptor) => match tls_acceptor.accept(stream).await {
+                    Ok((stream, _)) => Either::Right(stream),
+                    Err(err) => {
+                        debug!(?remote_peer, "TLS handshake failed: {err}");
+                        return Ok(());
+                    }
+                },
+                None => Either::Left(stream),
+            };
+            let io = TokioIo::new(stream);
+
             let shutdown = cancellation_watcher();
             let auto_connection = auto::Builder::new(TaskCenterExecutor);
             let serve_connection_fut = auto_connection.serve_connection(io, handler);
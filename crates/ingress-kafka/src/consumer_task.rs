@@ -12,7 +12,9 @@ use std::collections::HashMap;
 use std::fmt;
 use std::sync::{Arc, OnceLock, Weak};
 
-use crate::dispatcher::{DispatchKafkaEvent, KafkaIngressDispatcher, KafkaIngressEvent};
+use crate::dispatcher::{
+    DeduplicationId, DispatchIngressEvent, EventOrigin, IngressDispatcher, IngressEvent,
+};
 use crate::metric_definitions::KAFKA_INGRESS_REQUESTS;
 use base64::Engine;
 use bytes::Bytes;
@@ -29,9 +31,7 @@ use restate_types::invocation::Header;
 use restate_types::live::Live;
 use restate_types::message::MessageIndex;
 use restate_types::schema::Schema;
-use restate_types::schema::subscriptions::{
-    EventInvocationTargetTemplate, EventReceiverServiceType, Sink, Subscription,
-};
+use restate_types::schema::subscriptions::Subscription;
 use tokio::sync::{mpsc, oneshot};
 use tracing::{Instrument, debug, info, info_span, warn};
 
@@ -76,25 +76,12 @@ impl fmt::Display for KafkaDeduplicationId {
     }
 }
 
-impl KafkaDeduplicationId {
-    pub(crate) fn requires_proxying(subscription: &Subscription) -> bool {
-        // Service event receiver requires proxying because we don't want to scatter deduplication ids (kafka topic/partition offsets) in all the Restate partitions.
-        matches!(
-            subscription.sink(),
-            Sink::DeprecatedService {
-                ty: EventReceiverServiceType::Service,
-                ..
-            } | Sink::Invocation {
-                event_invocation_target_template: EventInvocationTargetTemplate::Service { .. }
-            },
-        )
-    }
-}
+impl DeduplicationId for KafkaDeduplicationId {}
 
 #[derive(Clone)]
 pub struct MessageSender {
     subscription: Subscription,
-    dispatcher: KafkaIngressDispatcher,
+    dispatcher: IngressDispatcher,
     schema: Live<Schema>,
 
     subscription_id: String,
@@ -104,7 +91,7 @@ pub struct MessageSender {
 impl MessageSender {
     pub fn new(
         subscription: Subscription,
-        dispatcher: KafkaIngressDispatcher,
+        dispatcher: IngressDispatcher,
         schema: Live<Schema>,
     ) -> Self {
         Self {
@@ -147,7 +134,7 @@ impl MessageSender {
 
         let (deduplication_id, deduplication_index) =
             Self::generate_deduplication_id(consumer_group_id, &msg);
-        let req = KafkaIngressEvent::new(
+        let req = IngressEvent::new(
             &self.subscription,
             self.schema.pinned(),
             key,
@@ -155,10 +142,12 @@ impl MessageSender {
             deduplication_id,
             deduplication_index,
             headers,
-            consumer_group_id,
-            msg.topic(),
-            msg.partition(),
-            msg.offset(),
+            EventOrigin::Kafka {
+                consumer_group: consumer_group_id,
+                topic: msg.topic(),
+                partition: msg.partition(),
+                offset: msg.offset(),
+            },
         )
         .map_err(|cause| Error::Event {
             topic: msg.topic().to_string(),
@@ -170,7 +159,7 @@ impl MessageSender {
         self.ingress_request_counter.increment(1);
 
         self.dispatcher
-            .dispatch_kafka_event(req)
+            .dispatch_ingress_event(req)
             .instrument(ingress_span)
             .await
             .map_err(|_| Error::IngressDispatcherClosed)?;
@@ -8,7 +8,6 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use crate::consumer_task::KafkaDeduplicationId;
 use bytes::Bytes;
 use opentelemetry::propagation::{Extractor, TextMapPropagator};
 use opentelemetry::trace::{Span, SpanContext, TraceContextExt};
@@ -31,34 +30,59 @@ use restate_types::schema::subscriptions::{
 use restate_types::{GenerationalNodeId, live};
 use restate_wal_protocol::{Command, Destination, Envelope, Header, Source};
 use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
 use std::sync::Arc;
 use tracing::debug;
 
+/// Identifies an ordered stream of events of a subscription source, e.g. a Kafka topic partition.
+///
+/// Together with the [`MessageIndex`] of each event, it's used by the partition processors to
+/// discard the events they have already seen, see [`DedupInformation::ingress`].
+pub trait DeduplicationId: fmt::Display + Hash {}
+
+/// Where an event comes from, used to tag the ingress span.
+#[derive(Debug, Clone, Copy)]
+pub enum EventOrigin<'a> {
+    Kafka {
+        consumer_group: &'a str,
+        topic: &'a str,
+        partition: i32,
+        offset: i64,
+    },
+    File {
+        path: &'a str,
+        offset: u64,
+    },
+    Webhook {
+        name: &'a str,
+        delivery_id: &'a str,
+    },
+}
+
 #[derive(Debug)]
-pub struct KafkaIngressEvent {
+pub struct IngressEvent {
     service_invocation: ServiceInvocation,
-    deduplication_id: KafkaDeduplicationId,
-    deduplication_index: MessageIndex,
+    deduplication: Option<(String, MessageIndex)>,
     proxying_partition_key: Option<PartitionKey>,
 }
 
-impl KafkaIngressEvent {
+impl IngressEvent {
+    /// Event of an ordered stream, deduplicated by the partition processors using the
+    /// [`DeduplicationId`] of the stream and the index of the event.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         subscription: &Subscription,
         schema: live::Pinned<Schema>,
         key: Bytes,
         payload: Bytes,
-        deduplication_id: KafkaDeduplicationId,
+        deduplication_id: impl DeduplicationId,
         deduplication_index: MessageIndex,
         headers: Vec<restate_types::invocation::Header>,
-        consumer_group_id: &str,
-        topic: &str,
-        partition: i32,
-        offset: i64,
+        origin: EventOrigin<'_>,
     ) -> Result<Self, anyhow::Error> {
         // Check if we need to proxy or not
-        let proxying_partition_key = if KafkaDeduplicationId::requires_proxying(subscription) {
+        let proxying_partition_key = if requires_proxying(subscription) {
             Some(partitioner::HashPartitioner::compute_partition_key(
                 &deduplication_id,
             ))
@@ -66,6 +90,57 @@ impl KafkaIngressEvent {
             None
         };
 
+        Ok(IngressEvent {
+            service_invocation: Self::service_invocation(
+                subscription,
+                schema,
+                key,
+                payload,
+                None,
+                headers,
+                origin,
+            )?,
+            deduplication: Some((deduplication_id.to_string(), deduplication_index)),
+            proxying_partition_key,
+        })
+    }
+
+    /// Event which is not part of an ordered stream, deduplicated using the given idempotency key.
+    /// The partition processors discard the events with the same key until the idempotency
+    /// retention of the target handler expires.
+    pub fn with_idempotency_key(
+        subscription: &Subscription,
+        schema: live::Pinned<Schema>,
+        key: Bytes,
+        payload: Bytes,
+        idempotency_key: String,
+        headers: Vec<restate_types::invocation::Header>,
+        origin: EventOrigin<'_>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(IngressEvent {
+            service_invocation: Self::service_invocation(
+                subscription,
+                schema,
+                key,
+                payload,
+                Some(idempotency_key),
+                headers,
+                origin,
+            )?,
+            deduplication: None,
+            proxying_partition_key: None,
+        })
+    }
+
+    fn service_invocation(
+        subscription: &Subscription,
+        schema: live::Pinned<Schema>,
+        key: Bytes,
+        payload: Bytes,
+        idempotency_key: Option<String>,
+        headers: Vec<restate_types::invocation::Header>,
+        origin: EventOrigin<'_>,
+    ) -> Result<ServiceInvocation, anyhow::Error> {
         let invocation_target = match subscription.sink() {
             Sink::DeprecatedService { name, handler, ty } => match ty {
                 EventReceiverServiceType::VirtualObject => InvocationTarget::virtual_object(
@@ -99,9 +174,7 @@ impl KafkaIngressEvent {
                 } => InvocationTarget::virtual_object(
                     name.clone(),
                     std::str::from_utf8(&key)
-                        .map_err(|e| {
-                            anyhow::anyhow!("The Kafka record key must be valid UTF-8: {e}")
-                        })?
+                        .map_err(|e| anyhow::anyhow!("The event key must be valid UTF-8: {e}"))?
                         .to_owned(),
                     handler.clone(),
                     *handler_ty,
@@ -121,35 +194,42 @@ impl KafkaIngressEvent {
                     *handler_ty,
                 ),
             },
+            Sink::Kafka { .. } => {
+                anyhow::bail!("Events cannot be ingested into a Kafka sink");
+            }
         };
 
-        // For workflows, we need to set the retention here
-        let invocation_retention = if invocation_target.invocation_target_ty()
-            == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow)
-        {
-            schema
-                .resolve_latest_invocation_target(
-                    invocation_target.service_name(),
-                    invocation_target.handler_name(),
-                )
-                .and_then(|target| target.compute_retention(false))
+        let invocation_target_meta = schema.resolve_latest_invocation_target(
+            invocation_target.service_name(),
+            invocation_target.handler_name(),
+        );
+
+        // The workflow id already deduplicates the workflow runs
+        let is_workflow_run = invocation_target.invocation_target_ty()
+            == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow);
+        let idempotency_key = idempotency_key.filter(|_| !is_workflow_run);
+
+        // For workflows and idempotent invocations, we need to set the retention here
+        let invocation_retention = if is_workflow_run || idempotency_key.is_some() {
+            invocation_target_meta
+                .as_ref()
+                .and_then(|target| target.compute_retention(idempotency_key.is_some()))
         } else {
             None
         };
+        let journal_retention = invocation_target_meta
+            .as_ref()
+            .and_then(|target| target.journal_retention);
+        // Dead-letter the event if either the subscription or the target service asks for it
+        let dead_letter_queue = subscription.dead_letter_queue()
+            || invocation_target_meta.is_some_and(|target| target.dead_letter_queue);
 
         // Time to generate invocation id
-        let invocation_id = InvocationId::generate(&invocation_target, None);
+        let invocation_id = InvocationId::generate(&invocation_target, idempotency_key.as_deref());
 
         // Figure out tracing span
-        let ingress_span_context = prepare_tracing_span(
-            &invocation_id,
-            &invocation_target,
-            &headers,
-            consumer_group_id,
-            topic,
-            partition as i64,
-            offset,
-        );
+        let ingress_span_context =
+            prepare_tracing_span(&invocation_id, &invocation_target, &headers, origin);
 
         // Finally generate service invocation
         let mut service_invocation = ServiceInvocation::initialize(
@@ -161,13 +241,11 @@ impl KafkaIngressEvent {
         service_invocation.argument = payload;
         service_invocation.headers = headers;
         service_invocation.completion_retention_duration = invocation_retention;
+        service_invocation.journal_retention_duration = journal_retention;
+        service_invocation.dead_letter_queue = dead_letter_queue;
+        service_invocation.idempotency_key = idempotency_key.map(Into::into);
 
-        Ok(KafkaIngressEvent {
-            service_invocation,
-            deduplication_id,
-            deduplication_index,
-            proxying_partition_key,
-        })
+        Ok(service_invocation)
     }
 }
 
@@ -179,46 +257,40 @@ pub enum IngressDispatchError {
     PartitionRoutingError(#[from] PartitionTableError),
 }
 
-/// Dispatches a request from kafka ingress to bifrost
-pub trait DispatchKafkaEvent {
-    fn dispatch_kafka_event(
+/// Dispatches an event of a subscription source to bifrost
+pub trait DispatchIngressEvent {
+    fn dispatch_ingress_event(
         &self,
-        event: KafkaIngressEvent,
+        event: IngressEvent,
     ) -> impl std::future::Future<Output = Result<(), IngressDispatchError>> + Send;
 }
 
 #[derive(Clone)]
-pub(crate) struct KafkaIngressDispatcher {
+pub struct IngressDispatcher {
     bifrost: Bifrost,
 }
 
-impl KafkaIngressDispatcher {
-    pub(crate) fn new(bifrost: Bifrost) -> Self {
+impl IngressDispatcher {
+    pub fn new(bifrost: Bifrost) -> Self {
         Self { bifrost }
     }
 }
 
-impl DispatchKafkaEvent for KafkaIngressDispatcher {
-    async fn dispatch_kafka_event(
+impl DispatchIngressEvent for IngressDispatcher {
+    async fn dispatch_ingress_event(
         &self,
-        ingress_request: KafkaIngressEvent,
+        ingress_request: IngressEvent,
     ) -> Result<(), IngressDispatchError> {
-        let KafkaIngressEvent {
+        let IngressEvent {
             service_invocation: inner,
-            deduplication_id,
-            deduplication_index,
+            deduplication,
             proxying_partition_key,
         } = ingress_request;
 
         let partition_key = proxying_partition_key.unwrap_or_else(|| inner.partition_key());
 
-        let envelope = wrap_service_invocation_in_envelope(
-            partition_key,
-            inner,
-            my_node_id(),
-            deduplication_id.to_string(),
-            deduplication_index,
-        );
+        let envelope =
+            wrap_service_invocation_in_envelope(partition_key, inner, my_node_id(), deduplication);
         let (log_id, lsn) =
             restate_bifrost::append_to_bifrost(&self.bifrost, Arc::new(envelope)).await?;
 
@@ -235,8 +307,7 @@ fn wrap_service_invocation_in_envelope(
     partition_key: PartitionKey,
     service_invocation: ServiceInvocation,
     from_node_id: GenerationalNodeId,
-    deduplication_source: String,
-    deduplication_index: MessageIndex,
+    deduplication: Option<(String, MessageIndex)>,
 ) -> Envelope {
     let header = Header {
         source: Source::Ingress {
@@ -245,23 +316,33 @@ fn wrap_service_invocation_in_envelope(
         },
         dest: Destination::Processor {
             partition_key,
-            dedup: Some(DedupInformation::ingress(
-                deduplication_source,
-                deduplication_index,
-            )),
+            dedup: deduplication.map(|(deduplication_source, deduplication_index)| {
+                DedupInformation::ingress(deduplication_source, deduplication_index)
+            }),
         },
     };
 
     Envelope::new(header, Command::ProxyThrough(service_invocation))
 }
+
+fn requires_proxying(subscription: &Subscription) -> bool {
+    // Service event receiver requires proxying because we don't want to scatter deduplication ids (e.g. kafka topic/partition offsets) in all the Restate partitions.
+    matches!(
+        subscription.sink(),
+        Sink::DeprecatedService {
+            ty: EventReceiverServiceType::Service,
+            ..
+        } | Sink::Invocation {
+            event_invocation_target_template: EventInvocationTargetTemplate::Service { .. }
+        },
+    )
+}
+
 pub(crate) fn prepare_tracing_span(
     invocation_id: &InvocationId,
     invocation_target: &InvocationTarget,
     headers: &[restate_types::invocation::Header],
-    consumer_group_name: &str,
-    topic: &str,
-    partition: i64,
-    offset: i64,
+    origin: EventOrigin<'_>,
 ) -> SpanContext {
     let tracing_context = TraceContextPropagator::new().extract(&HeaderExtractor(headers));
     let inbound_span = tracing_context.span();
@@ -272,20 +353,55 @@ pub(crate) fn prepare_tracing_span(
         SpanRelation::None
     };
 
-    let span = restate_tracing_instrumentation::info_invocation_span!(
-        relation = relation,
-        prefix = "ingress_kafka",
-        id = invocation_id,
-        target = invocation_target,
-        tags = (
-            messaging.system = "kafka",
-            messaging.consumer.group.name = consumer_group_name.to_owned(),
-            messaging.operation.type = "process",
-            messaging.kafka.offset = offset,
-            messaging.source.partition.id = partition,
-            messaging.source.name = topic.to_owned()
-        )
-    );
+    let span = match origin {
+        EventOrigin::Kafka {
+            consumer_group,
+            topic,
+            partition,
+            offset,
+        } => restate_tracing_instrumentation::info_invocation_span!(
+            relation = relation,
+            prefix = "ingress_kafka",
+            id = invocation_id,
+            target = invocation_target,
+            tags = (
+                messaging.system = "kafka",
+                messaging.consumer.group.name = consumer_group.to_owned(),
+                messaging.operation.type = "process",
+                messaging.kafka.offset = offset,
+                messaging.source.partition.id = partition as i64,
+                messaging.source.name = topic.to_owned()
+            )
+        ),
+        EventOrigin::File { path, offset } => {
+            restate_tracing_instrumentation::info_invocation_span!(
+                relation = relation,
+                prefix = "ingress_file",
+                id = invocation_id,
+                target = invocation_target,
+                tags = (
+                    messaging.system = "file",
+                    messaging.operation.type = "process",
+                    messaging.file.offset = offset as i64,
+                    messaging.source.name = path.to_owned()
+                )
+            )
+        }
+        EventOrigin::Webhook { name, delivery_id } => {
+            restate_tracing_instrumentation::info_invocation_span!(
+                relation = relation,
+                prefix = "ingress_webhook",
+                id = invocation_id,
+                target = invocation_target,
+                tags = (
+                    messaging.system = "webhook",
+                    messaging.operation.type = "process",
+                    messaging.message.id = delivery_id.to_owned(),
+                    messaging.source.name = name.to_owned()
+                )
+            )
+        }
+    };
 
     span.span_context().clone()
 }
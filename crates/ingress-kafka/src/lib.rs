@@ -15,6 +15,10 @@ mod subscription_controller;
 
 use tokio::sync::mpsc;
 
+pub use dispatcher::{
+    DeduplicationId, DispatchIngressEvent, EventOrigin, IngressDispatchError, IngressDispatcher,
+    IngressEvent,
+};
 pub use subscription_controller::{Command, Error, Service};
 
 pub type SubscriptionCommandSender = mpsc::Sender<Command>;
@@ -12,7 +12,7 @@ use super::consumer_task::MessageSender;
 use super::*;
 use std::collections::HashSet;
 
-use crate::dispatcher::KafkaIngressDispatcher;
+use crate::dispatcher::IngressDispatcher;
 use crate::subscription_controller::task_orchestrator::TaskOrchestrator;
 use anyhow::Context;
 use rdkafka::error::KafkaError;
@@ -44,7 +44,7 @@ pub enum Error {
 // For simplicity of the current implementation, this currently lives in this module
 // In future versions, we should either pull this out in a separate process, or generify it and move it to the worker, or an ad-hoc module
 pub struct Service {
-    dispatcher: KafkaIngressDispatcher,
+    dispatcher: IngressDispatcher,
     schema: Live<Schema>,
 
     commands_tx: SubscriptionCommandSender,
@@ -57,7 +57,7 @@ impl Service {
         let (commands_tx, commands_rx) = mpsc::channel(10);
 
         Service {
-            dispatcher: KafkaIngressDispatcher::new(bifrost),
+            dispatcher: IngressDispatcher::new(bifrost),
             schema,
             commands_tx,
             commands_rx,
@@ -121,7 +121,14 @@ impl Service {
     ) -> anyhow::Result<()> {
         let mut client_config = rdkafka::ClientConfig::new();
 
-        let Source::Kafka { cluster, topic, .. } = subscription.source();
+        let Source::Kafka { cluster, topic, .. } = subscription.source() else {
+            warn!(
+                "Ignoring subscription {} as its source '{}' is not a Kafka topic",
+                subscription.id(),
+                subscription.source()
+            );
+            return Ok(());
+        };
 
         // Copy cluster options and subscription metadata into client_config
         let cluster_options = options
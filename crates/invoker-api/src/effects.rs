@@ -11,7 +11,7 @@
 use restate_types::deployment::PinnedDeployment;
 use restate_types::errors::InvocationError;
 use restate_types::identifiers::InvocationId;
-use restate_types::invocation::InvocationEpoch;
+use restate_types::invocation::{InvocationEpoch, KafkaSink, OnMaxAttempts};
 use restate_types::journal::EntryIndex;
 use restate_types::journal::enriched::EnrichedRawEntry;
 use restate_types::journal_v2;
@@ -53,6 +53,14 @@ pub enum EffectKind {
     },
     /// This is sent always after [`Self::JournalEntry`] with `OutputStreamEntry`(s).
     End,
-    /// This is sent when the invoker exhausted all its attempts to make progress on the specific invocation.
+    /// Like [`Self::End`], when the output of the invocation must be published to the given Kafka sinks.
+    EndAndPublish { sinks: Vec<KafkaSink> },
+    /// This is sent when the invoker cannot make progress on the specific invocation because of a non-retryable error.
     Failed(InvocationError),
+    /// This is sent when the invoker exhausted all the attempts allowed by the retry policy
+    /// to make progress on the specific invocation.
+    RetriesExhausted {
+        error: InvocationError,
+        on_max_attempts: OnMaxAttempts,
+    },
 }
@@ -62,12 +62,15 @@ pub use input_command::InvokerHandle;
 use restate_invoker_api::invocation_reader::InvocationReader;
 use restate_service_client::{AssumeRoleCacheMode, ServiceClient};
 use restate_types::deployment::PinnedDeployment;
-use restate_types::invocation::{InvocationEpoch, InvocationTarget};
+use restate_types::invocation::{
+    InvocationEpoch, InvocationTarget, KafkaSink, OnMaxAttemptsAction,
+};
 use restate_types::journal_v2;
 use restate_types::journal_v2::raw::{RawCommand, RawEntry, RawEntryHeader, RawNotification};
 use restate_types::journal_v2::{CommandIndex, EntryMetadata, NotificationId};
-use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::invocation_target::{InvocationRetryPolicy, InvocationTargetResolver};
 use restate_types::schema::service::ServiceMetadataResolver;
+use restate_types::schema::subscriptions::SubscriptionResolver;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Notification {
@@ -94,6 +97,21 @@ trait InvocationTaskRunner<SR> {
         input_journal: InvokeInputJournal,
         task_pool: &mut JoinSet<()>,
     ) -> AbortHandle;
+
+    /// Retry policy override for the given target, if any.
+    /// When `None`, the retry policy from [`InvokerOptions`] is used, otherwise it fills the
+    /// fields left unset by the override.
+    fn resolve_retry_policy(
+        &self,
+        _invocation_target: &InvocationTarget,
+    ) -> Option<InvocationRetryPolicy> {
+        None
+    }
+
+    /// Kafka sinks where the output of the given target is published.
+    fn resolve_kafka_sinks(&self, _invocation_target: &InvocationTarget) -> Vec<KafkaSink> {
+        vec![]
+    }
 }
 
 struct DefaultInvocationTaskRunner<EE, Schemas> {
@@ -109,6 +127,7 @@ where
     Schemas: DeploymentResolver
         + ServiceMetadataResolver
         + InvocationTargetResolver
+        + SubscriptionResolver
         + Clone
         + Send
         + Sync
@@ -154,6 +173,26 @@ where
             )
             .expect("to spawn invocation task")
     }
+
+    fn resolve_retry_policy(
+        &self,
+        invocation_target: &InvocationTarget,
+    ) -> Option<InvocationRetryPolicy> {
+        self.schemas
+            .pinned()
+            .resolve_latest_invocation_target(
+                invocation_target.service_name(),
+                invocation_target.handler_name(),
+            )
+            .and_then(|invocation_target_metadata| invocation_target_metadata.retry_policy)
+    }
+
+    fn resolve_kafka_sinks(&self, invocation_target: &InvocationTarget) -> Vec<KafkaSink> {
+        self.schemas.pinned().resolve_kafka_sinks(
+            invocation_target.service_name(),
+            invocation_target.handler_name(),
+        )
+    }
 }
 
 // -- Service implementation
@@ -250,6 +289,7 @@ where
     Schemas: DeploymentResolver
         + ServiceMetadataResolver
         + InvocationTargetResolver
+        + SubscriptionResolver
         + Clone
         + Send
         + Sync
@@ -440,7 +480,7 @@ where
                         self.handle_invocation_task_closed(partition, invocation_id, invocation_epoch).await
                     },
                     InvocationTaskOutputInner::Failed(e) => {
-                        self.handle_invocation_task_failed(partition, invocation_id, invocation_epoch, e).await
+                        self.handle_invocation_task_failed(options, partition, invocation_id, invocation_epoch, e).await
                     },
                     InvocationTaskOutputInner::Suspended(indexes) => {
                         self.handle_invocation_task_suspended(partition, invocation_id, invocation_epoch, indexes).await
@@ -567,6 +607,11 @@ where
                 .invocation_state_machine_manager
                 .partition_storage_reader(partition)
                 .expect("partition is registered");
+            let retry_policy = self
+                .invocation_task_runner
+                .resolve_retry_policy(&invocation_target)
+                .map(|retry_policy| retry_policy.resolve(&options.retry_policy))
+                .unwrap_or_else(|| options.retry_policy.clone());
             self.quota.reserve_slot();
             self.start_invocation_task(
                 options,
@@ -574,11 +619,7 @@ where
                 storage_reader.clone(),
                 invocation_id,
                 journal,
-                InvocationStateMachine::create(
-                    invocation_target,
-                    invocation_epoch,
-                    options.retry_policy.clone(),
-                ),
+                InvocationStateMachine::create(invocation_target, invocation_epoch, retry_policy),
             )
         } else {
             trace!(
@@ -958,11 +999,18 @@ where
                 "Invocation task closed correctly");
             self.quota.unreserve_slot();
             self.status_store.on_end(&partition, &invocation_id);
+            let sinks = self
+                .invocation_task_runner
+                .resolve_kafka_sinks(&ism.invocation_target);
             let _ = sender
                 .send(Effect {
                     invocation_id,
                     invocation_epoch,
-                    kind: EffectKind::End,
+                    kind: if sinks.is_empty() {
+                        EffectKind::End
+                    } else {
+                        EffectKind::EndAndPublish { sinks }
+                    },
                 })
                 .await;
         } else {
@@ -1067,6 +1115,7 @@ where
     )]
     async fn handle_invocation_task_failed(
         &mut self,
+        options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
         invocation_epoch: InvocationEpoch,
@@ -1077,7 +1126,7 @@ where
             .remove_invocation_with_epoch(partition, &invocation_id, invocation_epoch)
         {
             debug_assert_eq!(invocation_epoch, ism.invocation_epoch);
-            self.handle_error_event(partition, invocation_id, error, ism)
+            self.handle_error_event(options, partition, invocation_id, error, ism)
                 .await;
         } else {
             // If no state machine, this might be a result for an aborted invocation.
@@ -1160,6 +1209,7 @@ where
 
     async fn handle_error_event(
         &mut self,
+        options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
         error: InvokerError,
@@ -1211,6 +1261,36 @@ where
                 self.retry_timers
                     .sleep_until(next_retry_at, (partition, invocation_id, epoch));
             }
+            None if error.is_transient() => {
+                counter!(INVOKER_INVOCATION_TASKS,
+                    "status" => TASK_OP_FAILED,
+                    "transient" => "true"
+                )
+                .increment(1);
+                let on_max_attempts = options.on_max_attempts.clone();
+                warn_it!(
+                    error,
+                    restate.invocation.id = %invocation_id,
+                    restate.invocation.target = %ism.invocation_target,
+                    "Error when executing the invocation, exhausted all the retry attempts. Applying the on max attempts action {}.",
+                    OnMaxAttemptsAction::from(&on_max_attempts));
+                self.quota.unreserve_slot();
+                self.status_store.on_end(&partition, &invocation_id);
+
+                let _ = self
+                    .invocation_state_machine_manager
+                    .resolve_partition_sender(partition)
+                    .expect("Partition should be registered")
+                    .send(Effect {
+                        invocation_id,
+                        invocation_epoch: ism.invocation_epoch,
+                        kind: EffectKind::RetriesExhausted {
+                            error: error.into_invocation_error(),
+                            on_max_attempts,
+                        },
+                    })
+                    .await;
+            }
             _ => {
                 counter!(INVOKER_INVOCATION_TASKS,
                     "status" => TASK_OP_FAILED,
@@ -1351,8 +1431,8 @@ mod tests {
     use restate_service_protocol_v4::entry_codec::ServiceProtocolV4Codec;
     use restate_test_util::{check, let_assert};
     use restate_types::config::InvokerOptionsBuilder;
-    use restate_types::identifiers::{LeaderEpoch, PartitionId, ServiceRevision};
-    use restate_types::invocation::ServiceType;
+    use restate_types::identifiers::{LeaderEpoch, PartitionId, ServiceRevision, SubscriptionId};
+    use restate_types::invocation::{OnMaxAttempts, ServiceType};
     use restate_types::journal::enriched::EnrichedEntryHeader;
     use restate_types::journal::raw::RawEntry;
     use restate_types::journal_v2::{Command, OutputCommand, OutputResult};
@@ -1362,6 +1442,7 @@ mod tests {
     use restate_types::schema::deployment::Deployment;
     use restate_types::schema::invocation_target::InvocationTargetMetadata;
     use restate_types::schema::service::ServiceMetadata;
+    use restate_types::schema::subscriptions::{ListSubscriptionFilter, Subscription};
 
     use crate::error::{InvokerError, SdkInvocationErrorV2};
     use crate::quota::InvokerConcurrencyQuota;
@@ -1512,6 +1593,38 @@ mod tests {
         }
     }
 
+    // Just pending, with a retry policy override for every target
+    struct RetryPolicyOverride(InvocationRetryPolicy);
+
+    impl<SR> InvocationTaskRunner<SR> for RetryPolicyOverride
+    where
+        SR: InvocationReader + Clone + Send + Sync + 'static,
+    {
+        fn start_invocation_task(
+            &self,
+            _options: &InvokerOptions,
+            _partition: PartitionLeaderEpoch,
+            _invocation_id: InvocationId,
+            _invocation_epoch: InvocationEpoch,
+            _invocation_target: InvocationTarget,
+            _retry_count_since_last_stored_entry: u32,
+            _storage_reader: SR,
+            _invoker_tx: mpsc::UnboundedSender<InvocationTaskOutput>,
+            _invoker_rx: mpsc::UnboundedReceiver<Notification>,
+            _input_journal: InvokeInputJournal,
+            task_pool: &mut JoinSet<()>,
+        ) -> AbortHandle {
+            task_pool.spawn(pending())
+        }
+
+        fn resolve_retry_policy(
+            &self,
+            _invocation_target: &InvocationTarget,
+        ) -> Option<InvocationRetryPolicy> {
+            Some(self.0.clone())
+        }
+    }
+
     #[derive(Debug, Clone, Default)]
     struct MockSchemas;
 
@@ -1564,6 +1677,20 @@ mod tests {
         }
     }
 
+    impl SubscriptionResolver for MockSchemas {
+        fn get_subscription(&self, _: SubscriptionId) -> Option<Subscription> {
+            None
+        }
+
+        fn list_subscriptions(&self, _: &[ListSubscriptionFilter]) -> Vec<Subscription> {
+            vec![]
+        }
+
+        fn resolve_kafka_sinks(&self, _: &str, _: &str) -> Vec<KafkaSink> {
+            vec![]
+        }
+    }
+
     #[test(restate_core::test)]
     async fn input_order_is_maintained() {
         let invoker_options = InvokerOptionsBuilder::default()
@@ -1807,6 +1934,7 @@ mod tests {
         // Handle error coming after the abort (this should be noop)
         service_inner
             .handle_invocation_task_failed(
+                &invoker_options,
                 MOCK_PARTITION,
                 invocation_id,
                 0,
@@ -1860,6 +1988,7 @@ mod tests {
         // Also handle error on epoch 0 should have no effect
         service_inner
             .handle_invocation_task_failed(
+                &InvokerOptions::default(),
                 MOCK_PARTITION,
                 invocation_id,
                 0,
@@ -2096,4 +2225,137 @@ mod tests {
         assert_eq!(id, invocation_id);
         assert_eq!(target, invocation_target);
     }
+
+    #[test(restate_core::test)]
+    async fn retries_exhausted_applies_on_max_attempts() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(1)))
+            .on_max_attempts(OnMaxAttempts::Pause)
+            .build()
+            .unwrap();
+        let invocation_id = InvocationId::mock_random();
+
+        let (_, _status_tx, mut service_inner) = ServiceInner::mock((), None);
+        let mut partition_rx = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner.handle_invoke(
+            &invoker_options,
+            MOCK_PARTITION,
+            invocation_id,
+            0,
+            InvocationTarget::mock_virtual_object(),
+            InvokeInputJournal::NoCachedJournal,
+        );
+
+        // First failure is retried
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvokerError::SdkV2(SdkInvocationErrorV2::unknown()),
+            )
+            .await;
+        service_inner.handle_retry_timer_fired(&invoker_options, MOCK_PARTITION, invocation_id, 0);
+        assert!(partition_rx.try_recv().is_err());
+
+        // Second failure exhausts the retry attempts
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvokerError::SdkV2(SdkInvocationErrorV2::unknown()),
+            )
+            .await;
+
+        let effect = partition_rx.recv().await.unwrap();
+        assert_eq!(effect.invocation_id, invocation_id);
+        let_assert!(
+            EffectKind::RetriesExhausted {
+                on_max_attempts,
+                ..
+            } = effect.kind
+        );
+        assert_eq!(on_max_attempts, OnMaxAttempts::Pause);
+        assert!(
+            service_inner
+                .invocation_state_machine_manager
+                .resolve_invocation(MOCK_PARTITION, &invocation_id)
+                .is_none()
+        );
+    }
+
+    #[test(restate_core::test)]
+    async fn retry_policy_override_changes_retry_timing() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::exponential(
+                Duration::from_millis(50),
+                2.0,
+                Some(1),
+                None,
+            ))
+            .on_max_attempts(OnMaxAttempts::Pause)
+            .build()
+            .unwrap();
+        let invocation_id = InvocationId::mock_random();
+
+        // Only the initial interval is overridden, the max attempts come from the invoker options
+        let (_, _status_tx, mut service_inner) = ServiceInner::mock(
+            RetryPolicyOverride(InvocationRetryPolicy::Exponential {
+                initial_interval: Some(Duration::from_secs(60 * 60).into()),
+                factor: None,
+                max_attempts: None,
+                max_interval: None,
+            }),
+            None,
+        );
+        let mut partition_rx = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner.handle_invoke(
+            &invoker_options,
+            MOCK_PARTITION,
+            invocation_id,
+            0,
+            InvocationTarget::mock_virtual_object(),
+            InvokeInputJournal::NoCachedJournal,
+        );
+
+        // First failure is retried after the overridden interval
+        let failed_at = SystemTime::now();
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvokerError::SdkV2(SdkInvocationErrorV2::unknown()),
+            )
+            .await;
+        let next_retry_at = service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id)
+            .unwrap()
+            .next_retry_at()
+            .unwrap();
+        assert!(next_retry_at >= failed_at + Duration::from_secs(60 * 60));
+
+        // Second failure exhausts the retry attempts of the invoker options
+        service_inner.handle_retry_timer_fired(&invoker_options, MOCK_PARTITION, invocation_id, 0);
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvokerError::SdkV2(SdkInvocationErrorV2::unknown()),
+            )
+            .await;
+
+        let effect = partition_rx.recv().await.unwrap();
+        assert_eq!(effect.invocation_id, invocation_id);
+        let_assert!(EffectKind::RetriesExhausted { .. } = effect.kind);
+    }
 }
@@ -22,6 +22,7 @@ use tracing::info;
 
 pub mod cluster;
 pub mod node;
+pub mod tls;
 
 /// Used to store marker files of "used" ports to avoid conflicts
 ///
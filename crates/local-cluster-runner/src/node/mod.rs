@@ -8,7 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use crate::random_socket_address;
+use crate::{random_socket_address, tls};
 use anyhow::bail;
 use arc_swap::ArcSwapOption;
 use enumset::EnumSet;
@@ -383,8 +383,9 @@ impl Node {
 
         if self.config().has_role(Role::Admin) {
             info!(
-                "To connect to node {} using restate CLI:\nexport RESTATE_ADMIN_URL=http://{admin_address}",
-                base_config.node_name()
+                "To connect to node {} using restate CLI:\nexport RESTATE_ADMIN_URL={}://{admin_address}",
+                base_config.node_name(),
+                tls::scheme(base_config.admin.tls.as_ref())
             );
         }
 
@@ -748,7 +749,15 @@ impl StartedNode {
     /// Check to see if the admin address is healthy. Returns false if this node has no admin role.
     pub async fn admin_healthy(&self) -> bool {
         if let Some(address) = self.admin_address() {
-            match reqwest::get(format!("http://{address}/health")).await {
+            let tls = self.config().admin.tls.as_ref();
+            let Ok(client) = tls::http_client(tls) else {
+                return false;
+            };
+            match client
+                .get(format!("{}://{address}/health", tls::scheme(tls)))
+                .send()
+                .await
+            {
                 Ok(resp) => resp.status().is_success(),
                 Err(_) => false,
             }
@@ -760,7 +769,15 @@ impl StartedNode {
     /// Check to see if the ingress address is healthy. Returns false if this node has no ingress role.
     pub async fn ingress_healthy(&self) -> bool {
         if let Some(address) = self.ingress_address() {
-            match reqwest::get(format!("http://{address}/restate/health")).await {
+            let tls = self.config().ingress.tls.as_ref();
+            let Ok(client) = tls::http_client(tls) else {
+                return false;
+            };
+            match client
+                .get(format!("{}://{address}/restate/health", tls::scheme(tls)))
+                .send()
+                .await
+            {
                 Ok(resp) => resp.status().is_success(),
                 Err(_) => false,
             }
@@ -0,0 +1,82 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fs;
+use std::path::Path;
+
+use rcgen::{BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+use restate_types::config::TlsOptions;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SelfSignedError {
+    #[error("Failed to generate certificate: {0}")]
+    Generate(#[from] rcgen::Error),
+    #[error("Failed to write certificate: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// Generates a self-signed CA and a certificate issued by it for `localhost` and `127.0.0.1`,
+/// writing them into `dir`. The returned options use the same CA to verify the client
+/// certificates, so the node certificate can also be used as client certificate.
+pub fn generate_self_signed(dir: &Path) -> Result<TlsOptions, SelfSignedError> {
+    fs::create_dir_all(dir)?;
+
+    let ca_key = KeyPair::generate()?;
+    let mut ca_params = CertificateParams::new(Vec::<String>::new())?;
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, "local-cluster-ca");
+    let ca = ca_params.self_signed(&ca_key)?;
+
+    let key = KeyPair::generate()?;
+    let mut params = CertificateParams::new(vec!["localhost".to_owned(), "127.0.0.1".to_owned()])?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, "local-cluster");
+    params.extended_key_usages = vec![
+        ExtendedKeyUsagePurpose::ServerAuth,
+        ExtendedKeyUsagePurpose::ClientAuth,
+    ];
+    let certificate = params.signed_by(&key, &ca, &ca_key)?;
+
+    let options = TlsOptions {
+        cert_file: dir.join("node.pem"),
+        key_file: dir.join("node.key"),
+        ca_file: Some(dir.join("ca.pem")),
+        client_certificate_optional: false,
+    };
+    fs::write(&options.cert_file, certificate.pem())?;
+    fs::write(&options.key_file, key.serialize_pem())?;
+    fs::write(dir.join("ca.pem"), ca.pem())?;
+
+    Ok(options)
+}
+
+/// Http client trusting the CA of the given options and presenting their certificate, or a
+/// plain client if TLS is not configured.
+pub(crate) fn http_client(tls: Option<&TlsOptions>) -> anyhow::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(tls) = tls {
+        if let Some(ca_file) = &tls.ca_file {
+            builder =
+                builder.add_root_certificate(reqwest::Certificate::from_pem(&fs::read(ca_file)?)?);
+        }
+        let mut identity = fs::read(&tls.cert_file)?;
+        identity.extend(fs::read(&tls.key_file)?);
+        builder = builder.identity(reqwest::Identity::from_pem(&identity)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Scheme of the http servers using the given TLS options.
+pub(crate) fn scheme(tls: Option<&TlsOptions>) -> &'static str {
+    if tls.is_some() { "https" } else { "http" }
+}
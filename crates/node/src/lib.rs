@@ -296,6 +296,7 @@ impl Node {
                 metadata.updateable_schema(),
                 metadata.updateable_partition_table(),
                 partition_routing_refresher.partition_routing(),
+                worker_role.as_ref().map(|role| role.webhook_dispatcher()),
             ))
         } else {
             None
@@ -77,6 +77,7 @@ impl<T: TransportConnect> AdminRole<T> {
         let retry_policy = RetryPolicy::exponential(Duration::from_millis(100), 2.0, Some(4), None);
         let client =
             ServiceClient::from_options(&config.common.service_client, AssumeRoleCacheMode::None)?;
+        let request_identity_keys = client.request_identity_jwks();
         let service_discovery = ServiceDiscovery::new(retry_policy, client);
 
         let query_context = if let Some(query_context) = local_query_context {
@@ -105,7 +106,8 @@ impl<T: TransportConnect> AdminRole<T> {
             config.ingress.clone(),
             service_discovery,
         )
-        .with_query_context(query_context);
+        .with_query_context(query_context)
+        .with_request_identity_keys(request_identity_keys);
 
         let controller = if config.admin.is_cluster_controller_enabled() {
             Some(
@@ -8,11 +8,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::Arc;
+
 use restate_core::network::{Networking, TransportConnect};
 use restate_core::partitions::PartitionRouting;
-use restate_ingress_http::HyperServerIngress;
 use restate_ingress_http::partition_processor_rpc_client::PartitionProcessorRpcClient;
 use restate_ingress_http::rpc_request_dispatcher::RpcRequestDispatcher;
+use restate_ingress_http::{HyperServerIngress, WebhookDispatcher};
 use restate_types::config::IngressOptions;
 use restate_types::health::HealthStatus;
 use restate_types::live::{BoxLiveLoad, Live};
@@ -34,12 +36,16 @@ impl<T: TransportConnect> IngressRole<T> {
         schema: Live<Schema>,
         partition_table: Live<PartitionTable>,
         partition_routing: PartitionRouting,
+        webhook_dispatcher: Option<Arc<dyn WebhookDispatcher>>,
     ) -> Self {
-        let dispatcher = RpcRequestDispatcher::new(PartitionProcessorRpcClient::new(
+        let mut dispatcher = RpcRequestDispatcher::new(PartitionProcessorRpcClient::new(
             networking,
             partition_table,
             partition_routing,
         ));
+        if let Some(webhook_dispatcher) = webhook_dispatcher {
+            dispatcher = dispatcher.with_webhook_dispatcher(webhook_dispatcher);
+        }
         let ingress_http = HyperServerIngress::from_options(
             ingress_options.live_load(),
             dispatcher,
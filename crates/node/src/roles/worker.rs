@@ -8,6 +8,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::Arc;
+
 use codederror::CodedError;
 
 use restate_bifrost::Bifrost;
@@ -19,6 +21,7 @@ use restate_core::partitions::PartitionRouting;
 use restate_core::worker_api::ProcessorsManagerHandle;
 use restate_core::{Metadata, MetadataKind, cancellation_watcher};
 use restate_core::{ShutdownError, TaskKind};
+use restate_ingress_http::WebhookDispatcher;
 use restate_metadata_server::MetadataStoreClient;
 use restate_storage_query_datafusion::context::QueryContext;
 use restate_types::Version;
@@ -103,6 +106,10 @@ impl WorkerRole {
         self.worker.storage_query_context()
     }
 
+    pub fn webhook_dispatcher(&self) -> Arc<dyn WebhookDispatcher> {
+        self.worker.webhook_dispatcher()
+    }
+
     pub async fn start(self) -> anyhow::Result<()> {
         // todo: only run subscriptions on node 0 once being distributed
         TaskCenter::spawn_child(
@@ -0,0 +1,138 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::keys::{KeyKind, TableKey, define_table_key};
+use crate::owned_iter::OwnedIterator;
+use crate::protobuf_types::PartitionStoreProtobufValue;
+use crate::scan::TableScan;
+use crate::{PartitionStore, TableKind};
+use crate::{PartitionStoreTransaction, StorageAccess};
+use futures::Stream;
+use futures_util::stream;
+use restate_storage_api::Result;
+use restate_storage_api::dead_letter_table::{
+    DeadLetter, DeadLetterTable, ReadOnlyDeadLetterTable,
+};
+use restate_types::identifiers::{InvocationId, InvocationUuid, PartitionKey, WithPartitionKey};
+use std::ops::RangeInclusive;
+
+define_table_key!(
+    TableKind::DeadLetter,
+    KeyKind::DeadLetter,
+    DeadLetterKey(
+        partition_key: PartitionKey,
+        invocation_uuid: InvocationUuid
+    )
+);
+
+impl PartitionStoreProtobufValue for DeadLetter {
+    type ProtobufType = crate::protobuf_types::v1::DeadLetter;
+}
+
+fn create_key(invocation_id: &InvocationId) -> DeadLetterKey {
+    DeadLetterKey::default()
+        .partition_key(invocation_id.partition_key())
+        .invocation_uuid(invocation_id.invocation_uuid())
+}
+
+fn get_dead_letter<S: StorageAccess>(
+    storage: &mut S,
+    invocation_id: &InvocationId,
+) -> Result<Option<DeadLetter>> {
+    storage.get_value(create_key(invocation_id))
+}
+
+fn all_dead_letters<S: StorageAccess>(
+    storage: &S,
+    range: RangeInclusive<PartitionKey>,
+) -> Result<impl Stream<Item = Result<(InvocationId, DeadLetter)>> + Send + use<'_, S>> {
+    let iter =
+        storage.iterator_from(TableScan::FullScanPartitionKeyRange::<DeadLetterKey>(range))?;
+    Ok(stream::iter(OwnedIterator::new(iter).map(
+        |(mut k, mut v)| {
+            let key = DeadLetterKey::deserialize_from(&mut k)?;
+            let dead_letter = DeadLetter::decode(&mut v)?;
+
+            Ok((
+                InvocationId::from_parts(
+                    *key.partition_key_ok_or()?,
+                    *key.invocation_uuid_ok_or()?,
+                ),
+                dead_letter,
+            ))
+        },
+    )))
+}
+
+fn put_dead_letter<S: StorageAccess>(
+    storage: &mut S,
+    invocation_id: &InvocationId,
+    dead_letter: &DeadLetter,
+) -> Result<()> {
+    storage.put_kv(create_key(invocation_id), dead_letter)
+}
+
+fn delete_dead_letter<S: StorageAccess>(
+    storage: &mut S,
+    invocation_id: &InvocationId,
+) -> Result<()> {
+    let key = create_key(invocation_id);
+    storage.delete_key(&key)
+}
+
+impl ReadOnlyDeadLetterTable for PartitionStore {
+    async fn get_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> Result<Option<DeadLetter>> {
+        self.assert_partition_key(invocation_id)?;
+        get_dead_letter(self, invocation_id)
+    }
+
+    fn all_dead_letters(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<(InvocationId, DeadLetter)>> + Send> {
+        all_dead_letters(self, range)
+    }
+}
+
+impl ReadOnlyDeadLetterTable for PartitionStoreTransaction<'_> {
+    async fn get_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> Result<Option<DeadLetter>> {
+        self.assert_partition_key(invocation_id)?;
+        get_dead_letter(self, invocation_id)
+    }
+
+    fn all_dead_letters(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<(InvocationId, DeadLetter)>> + Send> {
+        all_dead_letters(self, range)
+    }
+}
+
+impl DeadLetterTable for PartitionStoreTransaction<'_> {
+    async fn put_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+        dead_letter: &DeadLetter,
+    ) -> Result<()> {
+        self.assert_partition_key(invocation_id)?;
+        put_dead_letter(self, invocation_id, dead_letter)
+    }
+
+    async fn delete_dead_letter(&mut self, invocation_id: &InvocationId) -> Result<()> {
+        self.assert_partition_key(invocation_id)?;
+        delete_dead_letter(self, invocation_id)
+    }
+}
@@ -8,8 +8,11 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeSet;
+
 use restate_storage_api::Result;
 use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
+use restate_storage_api::outbox_table::InheritedOutboxMessages;
 use restate_types::identifiers::PartitionId;
 use restate_types::logs::Lsn;
 use restate_types::message::MessageIndex;
@@ -32,11 +35,29 @@ impl PartitionStoreProtobufValue for SequenceNumber {
     type ProtobufType = crate::protobuf_types::v1::SequenceNumber;
 }
 
+#[derive(Debug, Clone, derive_more::From, derive_more::Into)]
+pub(crate) struct InheritedOutbox(pub(crate) Vec<InheritedOutboxMessages>);
+
+impl PartitionStoreProtobufValue for InheritedOutbox {
+    type ProtobufType = crate::protobuf_types::v1::InheritedOutbox;
+}
+
+#[derive(Debug, Clone, derive_more::From, derive_more::Into)]
+pub(crate) struct StateTtlServices(pub(crate) BTreeSet<String>);
+
+impl PartitionStoreProtobufValue for StateTtlServices {
+    type ProtobufType = crate::protobuf_types::v1::StateTtlServices;
+}
+
 mod fsm_variable {
     pub(crate) const INBOX_SEQ_NUMBER: u64 = 0;
     pub(crate) const OUTBOX_SEQ_NUMBER: u64 = 1;
 
     pub(crate) const APPLIED_LSN: u64 = 2;
+
+    pub(crate) const INHERITED_OUTBOX: u64 = 3;
+
+    pub(crate) const STATE_TTL_SERVICES: u64 = 4;
 }
 
 fn get<T: PartitionStoreProtobufValue, S: StorageAccess>(
@@ -80,6 +101,16 @@ impl ReadOnlyFsmTable for PartitionStore {
         get::<SequenceNumber, _>(self, self.partition_id(), fsm_variable::APPLIED_LSN)
             .map(|opt| opt.map(|seq_number| Lsn::from(u64::from(seq_number))))
     }
+
+    async fn get_inherited_outbox_messages(&mut self) -> Result<Vec<InheritedOutboxMessages>> {
+        get::<InheritedOutbox, _>(self, self.partition_id(), fsm_variable::INHERITED_OUTBOX)
+            .map(|opt| opt.map(Into::into).unwrap_or_default())
+    }
+
+    async fn get_state_ttl_services(&mut self) -> Result<BTreeSet<String>> {
+        get::<StateTtlServices, _>(self, self.partition_id(), fsm_variable::STATE_TTL_SERVICES)
+            .map(|opt| opt.map(Into::into).unwrap_or_default())
+    }
 }
 
 impl ReadOnlyFsmTable for PartitionStoreTransaction<'_> {
@@ -97,6 +128,16 @@ impl ReadOnlyFsmTable for PartitionStoreTransaction<'_> {
         get::<SequenceNumber, _>(self, self.partition_id(), fsm_variable::APPLIED_LSN)
             .map(|opt| opt.map(|seq_number| Lsn::from(u64::from(seq_number))))
     }
+
+    async fn get_inherited_outbox_messages(&mut self) -> Result<Vec<InheritedOutboxMessages>> {
+        get::<InheritedOutbox, _>(self, self.partition_id(), fsm_variable::INHERITED_OUTBOX)
+            .map(|opt| opt.map(Into::into).unwrap_or_default())
+    }
+
+    async fn get_state_ttl_services(&mut self) -> Result<BTreeSet<String>> {
+        get::<StateTtlServices, _>(self, self.partition_id(), fsm_variable::STATE_TTL_SERVICES)
+            .map(|opt| opt.map(Into::into).unwrap_or_default())
+    }
 }
 
 impl FsmTable for PartitionStoreTransaction<'_> {
@@ -126,4 +167,25 @@ impl FsmTable for PartitionStoreTransaction<'_> {
             &SequenceNumber::from(seq_number),
         )
     }
+
+    async fn put_inherited_outbox_messages(
+        &mut self,
+        inherited: Vec<InheritedOutboxMessages>,
+    ) -> Result<()> {
+        put(
+            self,
+            self.partition_id(),
+            fsm_variable::INHERITED_OUTBOX,
+            &InheritedOutbox::from(inherited),
+        )
+    }
+
+    async fn put_state_ttl_services(&mut self, services: BTreeSet<String>) -> Result<()> {
+        put(
+            self,
+            self.partition_id(),
+            fsm_variable::STATE_TTL_SERVICES,
+            &StateTtlServices::from(services),
+        )
+    }
 }
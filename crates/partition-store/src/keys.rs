@@ -26,6 +26,7 @@ use strum::EnumIter;
     Debug, Copy, Clone, Eq, PartialEq, EnumIter, derive_more::Display, strum::VariantArray,
 )]
 pub enum KeyKind {
+    DeadLetter,
     Deduplication,
     Fsm,
     Idempotency,
@@ -39,8 +40,10 @@ pub enum KeyKind {
     Outbox,
     ServiceStatus,
     State,
+    StateMetadata,
     Timers,
     Promise,
+    Schedule,
 }
 
 impl KeyKind {
@@ -69,6 +72,7 @@ impl KeyKind {
         // NOTE: do not use &[0xff, 0xff] as key byte prefix, ever!
         // We should always be able to +1 the those bytes when interpreted as u16
         match self {
+            KeyKind::DeadLetter => b"dl",
             KeyKind::Deduplication => b"de",
             KeyKind::Fsm => b"fs",
             KeyKind::Idempotency => b"ip",
@@ -82,8 +86,10 @@ impl KeyKind {
             KeyKind::Outbox => b"ob",
             KeyKind::ServiceStatus => b"ss",
             KeyKind::State => b"st",
+            KeyKind::StateMetadata => b"sm",
             KeyKind::Timers => b"ti",
             KeyKind::Promise => b"pr",
+            KeyKind::Schedule => b"sc",
         }
     }
 
@@ -97,6 +103,7 @@ impl KeyKind {
     /// ```
     pub const fn from_bytes(bytes: &[u8; Self::SERIALIZED_LENGTH]) -> Option<Self> {
         match bytes {
+            b"dl" => Some(KeyKind::DeadLetter),
             b"de" => Some(KeyKind::Deduplication),
             b"fs" => Some(KeyKind::Fsm),
             b"ip" => Some(KeyKind::Idempotency),
@@ -110,8 +117,10 @@ impl KeyKind {
             b"ob" => Some(KeyKind::Outbox),
             b"ss" => Some(KeyKind::ServiceStatus),
             b"st" => Some(KeyKind::State),
+            b"sm" => Some(KeyKind::StateMetadata),
             b"ti" => Some(KeyKind::Timers),
             b"pr" => Some(KeyKind::Promise),
+            b"sc" => Some(KeyKind::Schedule),
             _ => None,
         }
     }
@@ -329,7 +338,7 @@ pub(crate) use define_table_key;
 use restate_storage_api::StorageError;
 use restate_storage_api::deduplication_table::ProducerId;
 use restate_storage_api::timer_table::TimerKeyKind;
-use restate_types::identifiers::InvocationUuid;
+use restate_types::identifiers::{InvocationUuid, ScheduleId};
 use restate_types::journal_v2::{CompletionId, NotificationId, SignalIndex};
 
 pub(crate) trait KeyCodec: Sized {
@@ -474,6 +483,24 @@ impl KeyCodec for InvocationUuid {
     }
 }
 
+impl KeyCodec for ScheduleId {
+    fn encode<B: BufMut>(&self, target: &mut B) {
+        // ulids are encoded in big-endian order, hence the binary order is the same of ScheduleId
+        target.put_slice(&self.to_bytes());
+    }
+
+    fn decode<B: Buf>(source: &mut B) -> crate::Result<Self> {
+        if source.remaining() < mem::size_of::<u128>() {
+            return Err(StorageError::DataIntegrityError);
+        }
+        Ok(ScheduleId::from(source.get_u128()))
+    }
+
+    fn serialized_length(&self) -> usize {
+        mem::size_of::<u128>()
+    }
+}
+
 impl KeyCodec for ProducerId {
     fn encode<B: BufMut>(&self, target: &mut B) {
         match self {
@@ -541,6 +568,10 @@ impl KeyCodec for TimerKeyKind {
                 target.put_u8(3);
                 invocation_uuid.encode(target);
             }
+            TimerKeyKind::FireSchedule { schedule_id } => {
+                target.put_u8(4);
+                schedule_id.encode(target);
+            }
         }
     }
 
@@ -572,6 +603,10 @@ impl KeyCodec for TimerKeyKind {
                 let invocation_uuid = InvocationUuid::decode(source)?;
                 TimerKeyKind::NeoInvoke { invocation_uuid }
             }
+            4 => {
+                let schedule_id = ScheduleId::decode(source)?;
+                TimerKeyKind::FireSchedule { schedule_id }
+            }
             i => {
                 return Err(StorageError::Generic(anyhow!(
                     "Unknown discriminator for TimerKind: '{}'",
@@ -599,6 +634,7 @@ impl KeyCodec for TimerKeyKind {
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => {
                 KeyCodec::serialized_length(invocation_uuid)
             }
+            TimerKeyKind::FireSchedule { schedule_id } => KeyCodec::serialized_length(schedule_id),
         }
     }
 }
@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+pub mod dead_letter_table;
 pub mod deduplication_table;
 pub mod fsm_table;
 pub mod idempotency_table;
@@ -22,7 +23,9 @@ mod partition_store;
 mod partition_store_manager;
 pub mod promise_table;
 mod protobuf_types;
+mod repartition;
 pub mod scan;
+pub mod schedule_table;
 pub mod service_status_table;
 pub mod snapshots;
 pub mod state_table;
@@ -135,12 +135,14 @@ pub enum TableKind {
     Inbox,
     Journal,
     Promise,
+    DeadLetter,
+    Schedule,
 }
 
 impl TableKind {
     pub const fn key_kinds(self) -> &'static [KeyKind] {
         match self {
-            Self::State => &[KeyKind::State],
+            Self::State => &[KeyKind::State, KeyKind::StateMetadata],
             Self::InvocationStatus => &[KeyKind::InvocationStatusV1, KeyKind::InvocationStatus],
             Self::ServiceStatus => &[KeyKind::ServiceStatus],
             Self::Idempotency => &[KeyKind::Idempotency],
@@ -157,6 +159,8 @@ impl TableKind {
                 KeyKind::JournalV2NotificationIdToNotificationIndex,
             ],
             Self::Promise => &[KeyKind::Promise],
+            Self::DeadLetter => &[KeyKind::DeadLetter],
+            Self::Schedule => &[KeyKind::Schedule],
         }
     }
 
@@ -19,11 +19,13 @@ use tracing::{debug, info, warn};
 
 use crate::PartitionStore;
 use crate::cf_options;
+use crate::repartition;
 use crate::snapshots::LocalPartitionSnapshot;
 use restate_core::worker_api::SnapshotError;
 use restate_rocksdb::{
     CfName, CfPrefixPattern, DbName, DbSpecBuilder, RocksDb, RocksDbManager, RocksError,
 };
+use restate_storage_api::StorageError;
 use restate_types::config::{RocksDbOptions, StorageOptions};
 use restate_types::identifiers::{PartitionId, PartitionKey, SnapshotId};
 use restate_types::live::LiveLoad;
@@ -203,6 +205,81 @@ impl PartitionStoreManager {
         Ok(partition_store)
     }
 
+    /// Creates the partition store of a partition split or merged from `parents`, out of the
+    /// latest snapshots of the parents. Each snapshot is imported into a temporary column family
+    /// from which the data within the partition key range is copied.
+    ///
+    /// A partial store left behind by an interrupted bootstrap is dropped first. The store is
+    /// complete once its applied LSN is set.
+    pub async fn open_partition_store_from_parents(
+        &self,
+        partition_id: PartitionId,
+        partition_key_range: RangeInclusive<PartitionKey>,
+        parents: Vec<(PartitionId, LocalPartitionSnapshot)>,
+        opts: &RocksDbOptions,
+    ) -> restate_storage_api::Result<PartitionStore> {
+        let mut guard = self.lookup.lock().await;
+        if guard.live.contains_key(&partition_id) {
+            warn!(
+                %partition_id,
+                "The partition store is already open, refusing to bootstrap it from its parents"
+            );
+            return Err(StorageError::Generic(RocksError::AlreadyOpen.into()));
+        }
+
+        let cf_name = cf_for_partition(partition_id);
+        self.drop_cf_if_exists(&cf_name)?;
+        self.rocksdb
+            .open_cf(cf_name.clone(), opts)
+            .await
+            .map_err(|err| StorageError::Generic(err.into()))?;
+        let mut partition_store = PartitionStore::new(
+            self.rocksdb.clone(),
+            cf_name,
+            partition_id,
+            partition_key_range,
+        );
+
+        for (parent_id, snapshot) in parents {
+            let parent_cf_name = CfName::from(format!(
+                "{PARTITION_CF_PREFIX}{partition_id}-parent-{parent_id}"
+            ));
+            self.drop_cf_if_exists(&parent_cf_name)?;
+
+            let mut import_metadata = ExportImportFilesMetaData::default();
+            import_metadata.set_db_comparator_name(snapshot.db_comparator_name.as_str());
+            import_metadata.set_files(&snapshot.files);
+
+            info!(
+                %partition_id,
+                %parent_id,
+                min_lsn = %snapshot.min_applied_lsn,
+                path = ?snapshot.base_dir,
+                "Importing parent partition snapshot"
+            );
+            self.rocksdb
+                .import_cf(parent_cf_name.clone(), opts, import_metadata)
+                .await
+                .map_err(|err| StorageError::Generic(err.into()))?;
+
+            let mut parent_store = PartitionStore::new(
+                self.rocksdb.clone(),
+                parent_cf_name.clone(),
+                parent_id,
+                snapshot.key_range,
+            );
+            repartition::copy_from_parent(&mut parent_store, &mut partition_store).await?;
+            drop(parent_store);
+
+            self.drop_cf_if_exists(&parent_cf_name)?;
+        }
+
+        repartition::complete(&mut partition_store).await?;
+        guard.live.insert(partition_id, partition_store.clone());
+
+        Ok(partition_store)
+    }
+
     pub async fn export_partition_snapshot(
         &self,
         partition_id: PartitionId,
@@ -234,6 +311,20 @@ impl PartitionStoreManager {
     }
 }
 
+impl PartitionStoreManager {
+    fn drop_cf_if_exists(&self, cf_name: &CfName) -> restate_storage_api::Result<()> {
+        if self.rocksdb.inner().cf_handle(cf_name).is_some() {
+            debug!(%cf_name, "Dropping column family");
+            self.rocksdb
+                .inner()
+                .as_raw_db()
+                .drop_cf(cf_name)
+                .map_err(|err| StorageError::Generic(err.into()))?;
+        }
+        Ok(())
+    }
+}
+
 fn cf_for_partition(partition_id: PartitionId) -> CfName {
     CfName::from(format!("{PARTITION_CF_PREFIX}{partition_id}"))
 }
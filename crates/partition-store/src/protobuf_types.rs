@@ -142,25 +142,26 @@ pub mod v1 {
         };
         use crate::protobuf_types::v1::journal_entry::{CompletionResult, Kind, completion_result};
         use crate::protobuf_types::v1::outbox_message::{
-            NotifySignal, OutboxCancel, OutboxKill, OutboxServiceInvocation,
+            NotifySignal, OutboxCancel, OutboxKafkaRecord, OutboxKill, OutboxServiceInvocation,
             OutboxServiceInvocationResponse,
         };
         use crate::protobuf_types::v1::service_invocation_response_sink::{
             Ingress, PartitionProcessor, ResponseSink,
         };
         use crate::protobuf_types::v1::{
-            BackgroundCallResolutionResult, DedupSequenceNumber, Duration, EnrichedEntryHeader,
-            Entry, EntryResult, EpochSequenceNumber, Header, IdempotencyId, IdempotencyMetadata,
-            InboxEntry, InvocationId, InvocationResolutionResult, InvocationStatus,
-            InvocationStatusV2, InvocationTarget, InvocationV2Lite, JournalCompletionTarget,
-            JournalEntry, JournalEntryIndex, JournalMeta, KvPair, OutboxMessage, Promise,
-            ResponseResult, SequenceNumber, ServiceId, ServiceInvocation,
-            ServiceInvocationResponseSink, Source, SpanContext, SpanRelation, StateMutation,
+            BackgroundCallResolutionResult, DeadLetter, DedupSequenceNumber, Duration,
+            EnrichedEntryHeader, Entry, EntryResult, EpochSequenceNumber, Header, IdempotencyId,
+            IdempotencyMetadata, InboxEntry, InheritedOutbox, InvocationId,
+            InvocationResolutionResult, InvocationStatus, InvocationStatusV2, InvocationTarget,
+            InvocationV2Lite, JournalCompletionTarget, JournalEntry, JournalEntryIndex,
+            JournalMeta, KvPair, OutboxMessage, Promise, ResponseResult, Schedule, ScheduleStatus,
+            SequenceNumber, ServiceId, ServiceInvocation, ServiceInvocationResponseSink, Source,
+            SpanContext, SpanRelation, StateEntryMetadata, StateMutation, StateTtlServices,
             SubmitNotificationSink, Timer, VirtualObjectStatus, enriched_entry_header, entry,
-            entry_result, inbox_entry, invocation_resolution_result, invocation_status,
-            invocation_status_v2, invocation_target, journal_entry, outbox_message, promise,
-            response_result, source, span_relation, submit_notification_sink, timer,
-            virtual_object_status,
+            entry_result, inbox_entry, inherited_outbox, invocation_resolution_result,
+            invocation_status, invocation_status_v2, invocation_target, journal_entry,
+            outbox_message, promise, response_result, source, span_relation,
+            submit_notification_sink, timer, virtual_object_status,
         };
         use restate_storage_api::StorageError;
         use restate_types::errors::{IdDecodeError, InvocationError};
@@ -410,6 +411,7 @@ pub mod v1 {
                     headers,
                     execution_time,
                     completion_retention_duration,
+                    journal_retention_duration,
                     idempotency_key,
                     inbox_sequence_number,
                     journal_length,
@@ -423,6 +425,8 @@ pub mod v1 {
                     waiting_for_signal_names,
                     result,
                     hotfix_apply_cancellation_after_deployment_is_pinned,
+                    retries_exhausted_action,
+                    dead_letter_queue,
                 } = value;
 
                 let invocation_target = expect_or_fail!(invocation_target)?.try_into()?;
@@ -450,6 +454,13 @@ pub mod v1 {
                     .into_iter()
                     .map(|h| restate_types::invocation::Header::try_from(h))
                     .collect::<Result<Vec<_>, ConversionError>>()?;
+                let retries_exhausted_action = retries_exhausted_action
+                    .map(|action| {
+                        action
+                            .parse::<restate_types::invocation::OnMaxAttemptsAction>()
+                            .map_err(ConversionError::invalid_data)
+                    })
+                    .transpose()?;
 
                 match status.try_into().unwrap_or_default() {
                     invocation_status_v2::Status::Scheduled => {
@@ -469,7 +480,12 @@ pub mod v1 {
                                             completion_retention_duration
                                                 .unwrap_or_default()
                                                 .try_into()?,
+                                        journal_retention_duration:
+                                            journal_retention_duration
+                                                .unwrap_or_default()
+                                                .try_into()?,
                                         idempotency_key: idempotency_key.map(ByteString::from),
+                                        dead_letter_queue,
                                     },
                             },
                         ))
@@ -492,7 +508,12 @@ pub mod v1 {
                                             completion_retention_duration
                                                 .unwrap_or_default()
                                                 .try_into()?,
+                                        journal_retention_duration:
+                                            journal_retention_duration
+                                                .unwrap_or_default()
+                                                .try_into()?,
                                         idempotency_key: idempotency_key.map(ByteString::from),
+                                        dead_letter_queue,
                                     },
                             },
                         ))
@@ -516,12 +537,50 @@ pub mod v1 {
                                 completion_retention_duration: completion_retention_duration
                                     .unwrap_or_default()
                                     .try_into()?,
+                                journal_retention_duration: journal_retention_duration
+                                    .unwrap_or_default()
+                                    .try_into()?,
                                 idempotency_key: idempotency_key.map(ByteString::from),
                                 hotfix_apply_cancellation_after_deployment_is_pinned,
                                 current_invocation_epoch,
                                 completion_range_epoch_map: CompletionRangeEpochMap::from_trim_points(
                                     trim_points.into_iter().map(|trim_point|(trim_point.completion_id, trim_point.invocation_epoch))
                                 ),
+                                retries_exhausted_action,
+                                dead_letter_queue,
+                            },
+                        ))
+                    }
+                    invocation_status_v2::Status::Paused => {
+                        Ok(restate_storage_api::invocation_status_table::InvocationStatus::Paused(
+                            restate_storage_api::invocation_status_table::InFlightInvocationMetadata {
+                                response_sinks,
+                                timestamps,
+                                invocation_target,
+                                journal_metadata: restate_storage_api::invocation_status_table::JournalMetadata {
+                                    length: journal_length,
+                                    commands,
+                                    span_context: expect_or_fail!(span_context)?.try_into()?,
+                                },
+                                pinned_deployment: derive_pinned_deployment(
+                                    deployment_id,
+                                    service_protocol_version,
+                                )?,
+                                source,
+                                completion_retention_duration: completion_retention_duration
+                                    .unwrap_or_default()
+                                    .try_into()?,
+                                journal_retention_duration: journal_retention_duration
+                                    .unwrap_or_default()
+                                    .try_into()?,
+                                idempotency_key: idempotency_key.map(ByteString::from),
+                                hotfix_apply_cancellation_after_deployment_is_pinned,
+                                current_invocation_epoch,
+                                completion_range_epoch_map: CompletionRangeEpochMap::from_trim_points(
+                                    trim_points.into_iter().map(|trim_point|(trim_point.completion_id, trim_point.invocation_epoch))
+                                ),
+                                retries_exhausted_action,
+                                dead_letter_queue,
                             },
                         ))
                     }
@@ -544,12 +603,17 @@ pub mod v1 {
                                 completion_retention_duration: completion_retention_duration
                                     .unwrap_or_default()
                                     .try_into()?,
+                                journal_retention_duration: journal_retention_duration
+                                    .unwrap_or_default()
+                                    .try_into()?,
                                 idempotency_key: idempotency_key.map(ByteString::from),
                                 hotfix_apply_cancellation_after_deployment_is_pinned,
                                 current_invocation_epoch,
                                 completion_range_epoch_map: CompletionRangeEpochMap::from_trim_points(
                                     trim_points.into_iter().map(|trim_point|(trim_point.completion_id, trim_point.invocation_epoch))
                                 ),
+                                retries_exhausted_action,
+                                dead_letter_queue,
                             },
                             waiting_for_notifications: waiting_for_completions
                                 .into_iter()
@@ -570,17 +634,32 @@ pub mod v1 {
                         },
                     ),
                     invocation_status_v2::Status::Completed => {
+                        let span_context: restate_types::invocation::ServiceInvocationSpanContext =
+                            expect_or_fail!(span_context)?.try_into()?;
                         Ok(restate_storage_api::invocation_status_table::InvocationStatus::Completed(
                             restate_storage_api::invocation_status_table::CompletedInvocation {
                                 timestamps,
                                 invocation_target,
-                                span_context: expect_or_fail!(span_context)?.try_into()?,
+                                journal_metadata: restate_storage_api::invocation_status_table::JournalMetadata {
+                                    length: journal_length,
+                                    commands,
+                                    span_context: span_context.clone(),
+                                },
+                                pinned_deployment: derive_pinned_deployment(
+                                    deployment_id,
+                                    service_protocol_version,
+                                )?,
+                                span_context,
                                 source,
                                 idempotency_key: idempotency_key.map(ByteString::from),
                                 response_result: expect_or_fail!(result)?.try_into()?,
                                 completion_retention_duration: completion_retention_duration
                                     .unwrap_or_default()
                                     .try_into()?,
+                                journal_retention_duration: journal_retention_duration
+                                    .unwrap_or_default()
+                                    .try_into()?,
+                                retries_exhausted_action,
                             },
                         ))
                     }
@@ -608,7 +687,9 @@ pub mod v1 {
                                     headers,
                                     execution_time,
                                     completion_retention_duration,
+                                    journal_retention_duration,
                                     idempotency_key,
+                                    dead_letter_queue,
                                 },
                         },
                     ) => InvocationStatusV2 {
@@ -639,6 +720,7 @@ pub mod v1 {
                         headers: headers.into_iter().map(Into::into).collect(),
                         execution_time: execution_time.map(|t| t.as_u64()),
                         completion_retention_duration: Some(completion_retention_duration.into()),
+                        journal_retention_duration: Some(journal_retention_duration.into()),
                         idempotency_key: idempotency_key.map(|key| key.to_string()),
                         inbox_sequence_number: None,
                         journal_length: 0,
@@ -652,6 +734,8 @@ pub mod v1 {
                         waiting_for_signal_indexes: vec![],
                         waiting_for_signal_names: vec![],
                         result: None,
+                        retries_exhausted_action: None,
+                        dead_letter_queue,
                     },
                     restate_storage_api::invocation_status_table::InvocationStatus::Inboxed(
                         restate_storage_api::invocation_status_table::InboxedInvocation {
@@ -666,7 +750,9 @@ pub mod v1 {
                                     headers,
                                     execution_time,
                                     completion_retention_duration,
+                                    journal_retention_duration,
                                     idempotency_key,
+                                    dead_letter_queue,
                                 },
                             inbox_sequence_number,
                         },
@@ -698,6 +784,7 @@ pub mod v1 {
                         headers: headers.into_iter().map(Into::into).collect(),
                         execution_time: execution_time.map(|t| t.as_u64()),
                         completion_retention_duration: Some(completion_retention_duration.into()),
+                        journal_retention_duration: Some(journal_retention_duration.into()),
                         idempotency_key: idempotency_key.map(|key| key.to_string()),
                         inbox_sequence_number: Some(inbox_sequence_number),
                         journal_length: 0,
@@ -711,6 +798,8 @@ pub mod v1 {
                         waiting_for_signal_indexes: vec![],
                         waiting_for_signal_names: vec![],
                         result: None,
+                        retries_exhausted_action: None,
+                        dead_letter_queue,
                     },
                     restate_storage_api::invocation_status_table::InvocationStatus::Invoked(
                         restate_storage_api::invocation_status_table::InFlightInvocationMetadata {
@@ -721,8 +810,9 @@ pub mod v1 {
                             timestamps,
                             source,
                             completion_retention_duration,
+                            journal_retention_duration,
                             idempotency_key,
-                            hotfix_apply_cancellation_after_deployment_is_pinned, current_invocation_epoch, completion_range_epoch_map
+                            hotfix_apply_cancellation_after_deployment_is_pinned, current_invocation_epoch, completion_range_epoch_map, retries_exhausted_action, dead_letter_queue
                         },
                     ) => {
                         let (deployment_id, service_protocol_version) = match pinned_deployment {
@@ -767,6 +857,7 @@ pub mod v1 {
                             completion_retention_duration: Some(
                                 completion_retention_duration.into(),
                             ),
+                            journal_retention_duration: Some(journal_retention_duration.into()),
                             idempotency_key: idempotency_key.map(|key| key.to_string()),
                             inbox_sequence_number: None,
                             journal_length: journal_metadata.length,
@@ -783,6 +874,85 @@ pub mod v1 {
                                 completion_id,
                                 invocation_epoch,
                             }).collect(),
+                            retries_exhausted_action: retries_exhausted_action.map(|action| action.to_string()),
+                            dead_letter_queue,
+                        }
+                    }
+                    restate_storage_api::invocation_status_table::InvocationStatus::Paused(
+                        restate_storage_api::invocation_status_table::InFlightInvocationMetadata {
+                            invocation_target,
+                            journal_metadata,
+                            pinned_deployment,
+                            response_sinks,
+                            timestamps,
+                            source,
+                            completion_retention_duration,
+                            journal_retention_duration,
+                            idempotency_key,
+                            hotfix_apply_cancellation_after_deployment_is_pinned, current_invocation_epoch, completion_range_epoch_map, retries_exhausted_action, dead_letter_queue
+                        },
+                    ) => {
+                        let (deployment_id, service_protocol_version) = match pinned_deployment {
+                            None => (None, None),
+                            Some(pinned_deployment) => (
+                                Some(pinned_deployment.deployment_id.to_string()),
+                                Some(pinned_deployment.service_protocol_version.as_repr()),
+                            ),
+                        };
+
+                        InvocationStatusV2 {
+                            status: invocation_status_v2::Status::Paused.into(),
+                            invocation_target: Some(invocation_target.into()),
+                            source: Some(source.into()),
+                            span_context: Some(journal_metadata.span_context.into()),
+                            // SAFETY: We're only mapping data types here
+                            creation_time: unsafe { timestamps.creation_time() }.as_u64(),
+                            modification_time: unsafe { timestamps.modification_time() }.as_u64(),
+                            inboxed_transition_time: unsafe {
+                                timestamps.inboxed_transition_time()
+                            }
+                            .map(|t| t.as_u64()),
+                            scheduled_transition_time: unsafe {
+                                timestamps.scheduled_transition_time()
+                            }
+                            .map(|t| t.as_u64()),
+                            running_transition_time: unsafe {
+                                timestamps.running_transition_time()
+                            }
+                            .map(|t| t.as_u64()),
+                            completed_transition_time: unsafe {
+                                timestamps.completed_transition_time()
+                            }
+                            .map(|t| t.as_u64()),
+                            response_sinks: response_sinks
+                                .into_iter()
+                                .map(|s| ServiceInvocationResponseSink::from(Some(s)))
+                                .collect(),
+                            argument: None,
+                            headers: vec![],
+                            execution_time: None,
+                            completion_retention_duration: Some(
+                                completion_retention_duration.into(),
+                            ),
+                            journal_retention_duration: Some(journal_retention_duration.into()),
+                            idempotency_key: idempotency_key.map(|key| key.to_string()),
+                            inbox_sequence_number: None,
+                            journal_length: journal_metadata.length,
+                            commands: journal_metadata.commands,
+                            deployment_id,
+                            service_protocol_version,
+                            waiting_for_completions: vec![],
+                            waiting_for_signal_indexes: vec![],
+                            waiting_for_signal_names: vec![],
+                            result: None,
+                            hotfix_apply_cancellation_after_deployment_is_pinned,
+                            current_invocation_epoch,
+                            trim_points: completion_range_epoch_map.into_trim_points_iter().into_iter().map(|(completion_id, invocation_epoch)| JournalTrimPoint {
+                                completion_id,
+                                invocation_epoch,
+                            }).collect(),
+                            retries_exhausted_action: retries_exhausted_action.map(|action| action.to_string()),
+                            dead_letter_queue,
                         }
                     }
                     restate_storage_api::invocation_status_table::InvocationStatus::Suspended {
@@ -795,7 +965,8 @@ pub mod v1 {
                                 timestamps,
                                 source,
                                 completion_retention_duration,
-                                idempotency_key, hotfix_apply_cancellation_after_deployment_is_pinned, current_invocation_epoch, completion_range_epoch_map,
+                                journal_retention_duration,
+                                idempotency_key, hotfix_apply_cancellation_after_deployment_is_pinned, current_invocation_epoch, completion_range_epoch_map, retries_exhausted_action, dead_letter_queue,
                             },
                         waiting_for_notifications,
                     } => {
@@ -858,6 +1029,7 @@ pub mod v1 {
                             completion_retention_duration: Some(
                                 completion_retention_duration.into(),
                             ),
+                            journal_retention_duration: Some(journal_retention_duration.into()),
                             idempotency_key: idempotency_key.map(|key| key.to_string()),
                             inbox_sequence_number: None,
                             journal_length: journal_metadata.length,
@@ -874,6 +1046,8 @@ pub mod v1 {
                                 completion_id,
                                 invocation_epoch,
                             }).collect(),
+                            retries_exhausted_action: retries_exhausted_action.map(|action| action.to_string()),
+                            dead_letter_queue,
                         }
                     }
                     restate_storage_api::invocation_status_table::InvocationStatus::Completed(
@@ -885,8 +1059,21 @@ pub mod v1 {
                             timestamps,
                             response_result,
                             completion_retention_duration,
+                            journal_retention_duration,
+                            journal_metadata,
+                            pinned_deployment,
+                            retries_exhausted_action,
                         },
-                    ) => InvocationStatusV2 {
+                    ) => {
+                        let (deployment_id, service_protocol_version) = match pinned_deployment {
+                            None => (None, None),
+                            Some(pinned_deployment) => (
+                                Some(pinned_deployment.deployment_id.to_string()),
+                                Some(pinned_deployment.service_protocol_version.as_repr()),
+                            ),
+                        };
+
+                        InvocationStatusV2 {
                         status: invocation_status_v2::Status::Completed.into(),
                         invocation_target: Some(invocation_target.into()),
                         source: Some(source.into()),
@@ -911,12 +1098,13 @@ pub mod v1 {
                         headers: vec![],
                         execution_time: None,
                         completion_retention_duration: Some(completion_retention_duration.into()),
+                        journal_retention_duration: Some(journal_retention_duration.into()),
                         idempotency_key: idempotency_key.map(|key| key.to_string()),
                         inbox_sequence_number: None,
-                        journal_length: 0,
-                        commands: 0,
-                        deployment_id: None,
-                        service_protocol_version: None,
+                        journal_length: journal_metadata.length,
+                        commands: journal_metadata.commands,
+                        deployment_id,
+                        service_protocol_version,
                         hotfix_apply_cancellation_after_deployment_is_pinned: false,
                         current_invocation_epoch: 0,
                         trim_points: vec![],
@@ -924,7 +1112,11 @@ pub mod v1 {
                         waiting_for_signal_indexes: vec![],
                         waiting_for_signal_names: vec![],
                         result: Some(response_result.into()),
-                    },
+                        retries_exhausted_action: retries_exhausted_action
+                            .map(|action| action.to_string()),
+                        dead_letter_queue: false,
+                    }
+                    }
                     restate_storage_api::invocation_status_table::InvocationStatus::Free => {
                         panic!("Unexpected serialization of Free status. This is a bug of the invocation status table")
                     }
@@ -956,6 +1148,9 @@ pub mod v1 {
                     invocation_status_v2::Status::Suspended => {
                         restate_storage_api::invocation_status_table::InvocationStatusDiscriminants::Suspended
                     }
+                    invocation_status_v2::Status::Paused => {
+                        restate_storage_api::invocation_status_table::InvocationStatusDiscriminants::Paused
+                    }
                     invocation_status_v2::Status::Completed => {
                         restate_storage_api::invocation_status_table::InvocationStatusDiscriminants::Completed
                     }
@@ -1079,6 +1274,11 @@ pub mod v1 {
                             "Unexpected conversion to old InvocationStatus when using Scheduled variant. This is a bug in the table implementation."
                         )
                     }
+                    restate_storage_api::invocation_status_table::InvocationStatus::Paused(_) => {
+                        panic!(
+                            "Unexpected conversion to old InvocationStatus when using Paused variant. This is a bug in the table implementation."
+                        )
+                    }
                 };
 
                 InvocationStatus {
@@ -1176,10 +1376,13 @@ pub mod v1 {
                             ),
                         source,
                         completion_retention_duration: completion_retention_time,
+                        journal_retention_duration: std::time::Duration::ZERO,
                         idempotency_key,
                         hotfix_apply_cancellation_after_deployment_is_pinned: false,
                         current_invocation_epoch: 0,
                         completion_range_epoch_map: Default::default(),
+                        retries_exhausted_action: None,
+                        dead_letter_queue: false,
                     },
                 )
             }
@@ -1295,10 +1498,13 @@ pub mod v1 {
                             ),
                         source: caller,
                         completion_retention_duration: completion_retention_time,
+                        journal_retention_duration: std::time::Duration::ZERO,
                         idempotency_key,
                         hotfix_apply_cancellation_after_deployment_is_pinned: false,
                         current_invocation_epoch: 0,
                         completion_range_epoch_map: Default::default(),
+                        retries_exhausted_action: None,
+                        dead_letter_queue: false,
                     },
                     waiting_for_completed_entries,
                 ))
@@ -1422,7 +1628,9 @@ pub mod v1 {
                         execution_time,
                         idempotency_key,
                         completion_retention_duration: completion_retention_time,
+                        journal_retention_duration: std::time::Duration::ZERO,
                         invocation_target,
+                        dead_letter_queue: false,
                     },
                 })
             }
@@ -1444,7 +1652,10 @@ pub mod v1 {
                             headers,
                             execution_time,
                             completion_retention_duration: completion_retention_time,
+                            // We don't store this in the old invocation status table
+                            journal_retention_duration: _,
                             idempotency_key,
+                            dead_letter_queue: _,
                         },
                     inbox_sequence_number,
                 } = value;
@@ -1511,6 +1722,13 @@ pub mod v1 {
                         // The value Duration::MAX here disables the new cleaner task business logic.
                         // Look at crates/worker/src/partition/cleaner.rs for more details.
                         completion_retention_duration: std::time::Duration::MAX,
+                        journal_retention_duration: std::time::Duration::ZERO,
+                        journal_metadata:
+                            restate_storage_api::invocation_status_table::JournalMetadata::initialize(
+                                Default::default(),
+                            ),
+                        pinned_deployment: None,
+                        retries_exhausted_action: None,
                     },
                 )
             }
@@ -1530,6 +1748,11 @@ pub mod v1 {
                     completion_retention_duration: _,
                     // The old invocation status table doesn't support span context on Completed
                     span_context: _,
+                    // The old invocation status table doesn't support journal retention
+                    journal_retention_duration: _,
+                    journal_metadata: _,
+                    pinned_deployment: _,
+                    retries_exhausted_action: _,
                 } = value;
 
                 Completed {
@@ -1716,7 +1939,9 @@ pub mod v1 {
                     execution_time,
                     idempotency_key,
                     completion_retention_time,
+                    journal_retention_duration,
                     submit_notification_sink,
+                    dead_letter_queue,
                 } = value;
 
                 let invocation_id = restate_types::identifiers::InvocationId::try_from(
@@ -1756,6 +1981,10 @@ pub mod v1 {
                     .map(std::time::Duration::try_from)
                     .transpose()?;
 
+                let journal_retention_duration = journal_retention_duration
+                    .map(std::time::Duration::try_from)
+                    .transpose()?;
+
                 let idempotency_key = idempotency_key.map(ByteString::from);
 
                 let submit_notification_sink = submit_notification_sink
@@ -1772,7 +2001,9 @@ pub mod v1 {
                     headers,
                     execution_time,
                     completion_retention_duration: completion_retention_time,
+                    journal_retention_duration,
                     idempotency_key,
+                    dead_letter_queue,
                     submit_notification_sink: submit_notification_sink,
                 })
             }
@@ -1798,8 +2029,12 @@ pub mod v1 {
                     completion_retention_time: value
                         .completion_retention_duration
                         .map(Duration::from),
+                    journal_retention_duration: value
+                        .journal_retention_duration
+                        .map(Duration::from),
                     idempotency_key: value.idempotency_key.map(|s| s.to_string()),
                     submit_notification_sink: value.submit_notification_sink.map(Into::into),
+                    dead_letter_queue: value.dead_letter_queue,
                 }
             }
         }
@@ -3330,6 +3565,25 @@ pub mod v1 {
                             ),
                         },
                     ),
+                    outbox_message::OutboxMessage::KafkaRecord(OutboxKafkaRecord {
+                        invocation_id,
+                        cluster,
+                        topic,
+                        key,
+                        response_result,
+                    }) => restate_storage_api::outbox_table::OutboxMessage::KafkaRecord(
+                        restate_storage_api::outbox_table::KafkaRecord {
+                            invocation_id: restate_types::identifiers::InvocationId::try_from(
+                                expect_or_fail!(invocation_id)?,
+                            )?,
+                            cluster,
+                            topic,
+                            key,
+                            result: restate_types::invocation::ResponseResult::try_from(
+                                expect_or_fail!(response_result)?,
+                            )?,
+                        },
+                    ),
                 };
 
                 Ok(result)
@@ -3438,6 +3692,15 @@ pub mod v1 {
                             }),
                         })
                     }
+                    restate_storage_api::outbox_table::OutboxMessage::KafkaRecord(kafka_record) => {
+                        outbox_message::OutboxMessage::KafkaRecord(OutboxKafkaRecord {
+                            invocation_id: Some(InvocationId::from(kafka_record.invocation_id)),
+                            cluster: kafka_record.cluster,
+                            topic: kafka_record.topic,
+                            key: kafka_record.key,
+                            response_result: Some(ResponseResult::from(kafka_record.result)),
+                        })
+                    }
                 };
 
                 OutboxMessage {
@@ -3529,6 +3792,14 @@ pub mod v1 {
                                 )?,
                             )
                         }
+                        timer::Value::FireSchedule(fire_schedule) => {
+                            restate_storage_api::timer_table::Timer::FireSchedule(
+                                restate_types::identifiers::ScheduleId::from_slice(
+                                    &fire_schedule.schedule_id,
+                                )
+                                .map_err(ConversionError::invalid_data)?,
+                            )
+                        }
                     },
                 )
             }
@@ -3558,6 +3829,11 @@ pub mod v1 {
                         ) => timer::Value::CleanInvocationStatus(timer::CleanInvocationStatus {
                             invocation_id: Some(InvocationId::from(invocation_id)),
                         }),
+                        restate_storage_api::timer_table::Timer::FireSchedule(schedule_id) => {
+                            timer::Value::FireSchedule(timer::FireSchedule {
+                                schedule_id: Bytes::copy_from_slice(&schedule_id.to_bytes()),
+                            })
+                        }
                     }),
                 }
             }
@@ -3672,6 +3948,143 @@ pub mod v1 {
             }
         }
 
+        impl From<restate_storage_api::dead_letter_table::DeadLetter> for DeadLetter {
+            fn from(value: restate_storage_api::dead_letter_table::DeadLetter) -> Self {
+                DeadLetter {
+                    invocation_target: Some(value.invocation_target.into()),
+                    source: Some(value.source.into()),
+                    headers: value.headers.into_iter().map(Into::into).collect(),
+                    argument: value.argument,
+                    failure: Some(response_result::ResponseFailure {
+                        failure_code: value.failure.code().into(),
+                        failure_message: Bytes::copy_from_slice(value.failure.message().as_ref()),
+                    }),
+                    failed_at: value.failed_at.as_u64(),
+                    idempotency_key: value.idempotency_key.map(|key| key.to_string()),
+                    completion_retention_duration: Some(value.completion_retention_duration.into()),
+                    journal_retention_duration: Some(value.journal_retention_duration.into()),
+                }
+            }
+        }
+
+        impl TryFrom<DeadLetter> for restate_storage_api::dead_letter_table::DeadLetter {
+            type Error = ConversionError;
+
+            fn try_from(value: DeadLetter) -> Result<Self, ConversionError> {
+                let failure = value
+                    .failure
+                    .ok_or(ConversionError::missing_field("failure"))?;
+
+                Ok(restate_storage_api::dead_letter_table::DeadLetter {
+                    invocation_target: value
+                        .invocation_target
+                        .ok_or(ConversionError::missing_field("invocation_target"))?
+                        .try_into()?,
+                    source: value
+                        .source
+                        .ok_or(ConversionError::missing_field("source"))?
+                        .try_into()?,
+                    headers: value
+                        .headers
+                        .into_iter()
+                        .map(restate_types::invocation::Header::try_from)
+                        .collect::<Result<Vec<_>, ConversionError>>()?,
+                    argument: value.argument,
+                    failure: InvocationError::new(
+                        failure.failure_code,
+                        ByteString::try_from(failure.failure_message)
+                            .map_err(ConversionError::invalid_data)?,
+                    ),
+                    failed_at: MillisSinceEpoch::new(value.failed_at),
+                    idempotency_key: value.idempotency_key.map(ByteString::from),
+                    completion_retention_duration: value
+                        .completion_retention_duration
+                        .unwrap_or_default()
+                        .try_into()?,
+                    journal_retention_duration: value
+                        .journal_retention_duration
+                        .unwrap_or_default()
+                        .try_into()?,
+                })
+            }
+        }
+
+        impl From<restate_types::schedule::Schedule> for Schedule {
+            fn from(value: restate_types::schedule::Schedule) -> Self {
+                Schedule {
+                    id: Bytes::copy_from_slice(&value.id.to_bytes()),
+                    cron: value.cron.to_string(),
+                    invocation_target: Some(value.invocation_target.into()),
+                    argument: value.argument,
+                    catch_up: value.catch_up.to_string(),
+                }
+            }
+        }
+
+        impl TryFrom<Schedule> for restate_types::schedule::Schedule {
+            type Error = ConversionError;
+
+            fn try_from(value: Schedule) -> Result<Self, ConversionError> {
+                Ok(restate_types::schedule::Schedule {
+                    id: restate_types::identifiers::ScheduleId::from_slice(&value.id)
+                        .map_err(ConversionError::invalid_data)?,
+                    cron: value.cron.parse().map_err(ConversionError::invalid_data)?,
+                    invocation_target: value
+                        .invocation_target
+                        .ok_or(ConversionError::missing_field("invocation_target"))?
+                        .try_into()?,
+                    argument: value.argument,
+                    catch_up: value
+                        .catch_up
+                        .parse()
+                        .map_err(ConversionError::invalid_data)?,
+                })
+            }
+        }
+
+        impl From<restate_storage_api::schedule_table::ScheduleStatus> for ScheduleStatus {
+            fn from(value: restate_storage_api::schedule_table::ScheduleStatus) -> Self {
+                ScheduleStatus {
+                    schedule: Some(value.schedule.into()),
+                    next_fire_at: value.next_fire_at.map(|t| t.as_u64()),
+                    last_fired_at: value.last_fired_at.map(|t| t.as_u64()),
+                }
+            }
+        }
+
+        impl TryFrom<ScheduleStatus> for restate_storage_api::schedule_table::ScheduleStatus {
+            type Error = ConversionError;
+
+            fn try_from(value: ScheduleStatus) -> Result<Self, ConversionError> {
+                Ok(restate_storage_api::schedule_table::ScheduleStatus {
+                    schedule: value
+                        .schedule
+                        .ok_or(ConversionError::missing_field("schedule"))?
+                        .try_into()?,
+                    next_fire_at: value.next_fire_at.map(MillisSinceEpoch::new),
+                    last_fired_at: value.last_fired_at.map(MillisSinceEpoch::new),
+                })
+            }
+        }
+
+        impl From<restate_storage_api::state_table::StateEntryMetadata> for StateEntryMetadata {
+            fn from(value: restate_storage_api::state_table::StateEntryMetadata) -> Self {
+                StateEntryMetadata {
+                    version: value.version,
+                    modified_at: value.modified_at.as_u64(),
+                }
+            }
+        }
+
+        impl From<StateEntryMetadata> for restate_storage_api::state_table::StateEntryMetadata {
+            fn from(value: StateEntryMetadata) -> Self {
+                restate_storage_api::state_table::StateEntryMetadata {
+                    version: value.version,
+                    modified_at: MillisSinceEpoch::new(value.modified_at),
+                }
+            }
+        }
+
         impl From<restate_storage_api::promise_table::Promise> for Promise {
             fn from(value: restate_storage_api::promise_table::Promise) -> Self {
                 match value.state {
@@ -3736,6 +4149,59 @@ pub mod v1 {
             }
         }
 
+        impl From<crate::fsm_table::InheritedOutbox> for InheritedOutbox {
+            fn from(value: crate::fsm_table::InheritedOutbox) -> Self {
+                InheritedOutbox {
+                    messages: value
+                        .0
+                        .into_iter()
+                        .map(|messages| inherited_outbox::Messages {
+                            first_index: messages.first_index,
+                            last_index: messages.last_index,
+                            producer_partition_id: messages.producer_id.into(),
+                            producer_first_seq_number: messages.producer_first_seq_number,
+                        })
+                        .collect(),
+                }
+            }
+        }
+
+        impl TryFrom<InheritedOutbox> for crate::fsm_table::InheritedOutbox {
+            type Error = ConversionError;
+
+            fn try_from(value: InheritedOutbox) -> Result<Self, ConversionError> {
+                value
+                    .messages
+                    .into_iter()
+                    .map(|messages| {
+                        Ok(restate_storage_api::outbox_table::InheritedOutboxMessages {
+                            first_index: messages.first_index,
+                            last_index: messages.last_index,
+                            producer_id: u16::try_from(messages.producer_partition_id)
+                                .map_err(ConversionError::invalid_data)?
+                                .into(),
+                            producer_first_seq_number: messages.producer_first_seq_number,
+                        })
+                    })
+                    .collect::<Result<_, ConversionError>>()
+                    .map(Self)
+            }
+        }
+
+        impl From<crate::fsm_table::StateTtlServices> for StateTtlServices {
+            fn from(value: crate::fsm_table::StateTtlServices) -> Self {
+                StateTtlServices {
+                    services: value.0.into_iter().collect(),
+                }
+            }
+        }
+
+        impl From<StateTtlServices> for crate::fsm_table::StateTtlServices {
+            fn from(value: StateTtlServices) -> Self {
+                Self(value.services.into_iter().collect())
+            }
+        }
+
         impl From<crate::journal_table_v2::JournalEntryIndex> for JournalEntryIndex {
             fn from(value: crate::journal_table_v2::JournalEntryIndex) -> Self {
                 Self {
@@ -0,0 +1,208 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Copies the state of a split or merged partition into the partition store of its children.
+//!
+//! Tables keyed by partition key are copied verbatim for the key range of the child. Tables keyed
+//! by partition id (timers, outbox, deduplication and the fsm variables) are rewritten under the
+//! partition id of the child. The outbox messages keep the producer id and the sequence numbers of
+//! the partition which produced them, see [`InheritedOutboxMessages`].
+
+use futures::TryStreamExt;
+use tracing::debug;
+
+use restate_storage_api::deduplication_table::{
+    DedupSequenceNumber, DeduplicationTable, ProducerId, ReadOnlyDeduplicationTable,
+};
+use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
+use restate_storage_api::outbox_table::{
+    InheritedOutboxMessages, OutboxTable, ReadOnlyOutboxTable,
+};
+use restate_storage_api::timer_table::TimerTable;
+use restate_storage_api::{Result, Transaction};
+use restate_types::identifiers::{PartitionId, WithPartitionKey};
+use restate_types::logs::Lsn;
+
+use crate::dead_letter_table::DeadLetterKey;
+use crate::idempotency_table::IdempotencyKey;
+use crate::inbox_table::InboxKey;
+use crate::invocation_status_table::{InvocationStatusKey, InvocationStatusKeyV1};
+use crate::keys::TableKey;
+use crate::promise_table::PromiseKey;
+use crate::schedule_table::ScheduleKey;
+use crate::service_status_table::ServiceStatusKey;
+use crate::state_table::{StateKey, StateMetadataKey};
+use crate::{PartitionStore, StorageAccess, TableScan, journal_table, journal_table_v2};
+
+/// Copies the state of `parent` belonging to the key range of `child`.
+pub(crate) async fn copy_from_parent(
+    parent: &mut PartitionStore,
+    child: &mut PartitionStore,
+) -> Result<()> {
+    debug!(
+        parent = %parent.partition_id(),
+        child = %child.partition_id(),
+        "Copying partition state from parent"
+    );
+
+    copy_partition_key_range::<StateKey>(parent, child)?;
+    copy_partition_key_range::<StateMetadataKey>(parent, child)?;
+    copy_partition_key_range::<InvocationStatusKeyV1>(parent, child)?;
+    copy_partition_key_range::<InvocationStatusKey>(parent, child)?;
+    copy_partition_key_range::<ServiceStatusKey>(parent, child)?;
+    copy_partition_key_range::<IdempotencyKey>(parent, child)?;
+    copy_partition_key_range::<InboxKey>(parent, child)?;
+    copy_partition_key_range::<journal_table::JournalKey>(parent, child)?;
+    copy_partition_key_range::<journal_table_v2::JournalKey>(parent, child)?;
+    copy_partition_key_range::<journal_table_v2::JournalCompletionIdToCommandIndexKey>(
+        parent, child,
+    )?;
+    copy_partition_key_range::<journal_table_v2::JournalNotificationIdToNotificationIndexKey>(
+        parent, child,
+    )?;
+    copy_partition_key_range::<PromiseKey>(parent, child)?;
+    copy_partition_key_range::<DeadLetterKey>(parent, child)?;
+    copy_partition_key_range::<ScheduleKey>(parent, child)?;
+
+    let timers: Vec<_> = parent
+        .next_timers_greater_than(None, usize::MAX)?
+        .try_collect()
+        .await?;
+    // the deduplication information of the parent's own proposals is tied to its leader epochs
+    let dedup_sequence_numbers: Vec<_> = parent
+        .get_all_sequence_numbers()?
+        .try_filter(|dedup| std::future::ready(dedup.producer_id != ProducerId::self_producer()))
+        .try_collect()
+        .await?;
+    let parent_inbox_seq_number = parent.get_inbox_seq_number().await?;
+
+    // the outbox is shipped by a single child, which takes over the parent's key range start.
+    // The parent might have shipped some of the messages before its log got sealed, hence the
+    // child ships them as the partition that produced them, for the receivers to drop duplicates.
+    let mut outbox = Vec::new();
+    if child.contains_partition_key(*parent.partition_key_range().start()) {
+        let parent_inherited = parent.get_inherited_outbox_messages().await?;
+        if let Some(mut seq_number) = parent.get_outbox_head_seq_number().await? {
+            while let Some((next_seq_number, message)) =
+                parent.get_next_outbox_message(seq_number).await?
+            {
+                let producer = parent_inherited
+                    .iter()
+                    .find_map(|inherited| inherited.producer_of(next_seq_number))
+                    .unwrap_or((parent.partition_id(), next_seq_number));
+                outbox.push((producer, message));
+                seq_number = next_seq_number + 1;
+            }
+        }
+    }
+
+    let key_range = child.partition_key_range().clone();
+    let mut txn = child.transaction();
+
+    for (timer_key, timer) in timers {
+        if key_range.contains(&timer.partition_key()) {
+            txn.put_timer(&timer_key, &timer).await?;
+        }
+    }
+
+    for dedup in dedup_sequence_numbers {
+        let sequence_number = match (
+            txn.get_dedup_sequence_number(&dedup.producer_id).await?,
+            dedup.sequence_number,
+        ) {
+            (Some(DedupSequenceNumber::Sn(current)), DedupSequenceNumber::Sn(parent)) => {
+                DedupSequenceNumber::Sn(current.max(parent))
+            }
+            (Some(DedupSequenceNumber::Esn(current)), DedupSequenceNumber::Esn(parent))
+                if current > parent =>
+            {
+                DedupSequenceNumber::Esn(current)
+            }
+            (_, parent) => parent,
+        };
+        txn.put_dedup_seq_number(dedup.producer_id, &sequence_number)
+            .await?;
+    }
+
+    let inbox_seq_number = txn.get_inbox_seq_number().await?;
+    txn.put_inbox_seq_number(inbox_seq_number.max(parent_inbox_seq_number))
+        .await?;
+
+    let mut outbox_seq_number = txn.get_outbox_seq_number().await?;
+    let mut inherited = txn.get_inherited_outbox_messages().await?;
+    for ((producer_id, producer_seq_number), message) in outbox {
+        txn.put_outbox_message(outbox_seq_number, &message).await?;
+        add_inherited_message(
+            &mut inherited,
+            outbox_seq_number,
+            producer_id,
+            producer_seq_number,
+        );
+        outbox_seq_number += 1;
+    }
+    txn.put_outbox_seq_number(outbox_seq_number).await?;
+    txn.put_inherited_outbox_messages(inherited).await?;
+
+    txn.commit().await
+}
+
+/// Marks the partition store as fully bootstrapped, the child starts reading its log from the
+/// beginning.
+pub(crate) async fn complete(child: &mut PartitionStore) -> Result<()> {
+    let mut txn = child.transaction();
+    txn.put_applied_lsn(Lsn::INVALID).await?;
+    txn.commit().await?;
+
+    child.flush_memtables(true).await
+}
+
+/// Records that the outbox message at `index` has been produced by `producer_id` with the given
+/// sequence number, extending the last range when the message follows it.
+fn add_inherited_message(
+    inherited: &mut Vec<InheritedOutboxMessages>,
+    index: u64,
+    producer_id: PartitionId,
+    producer_seq_number: u64,
+) {
+    match inherited.last_mut() {
+        Some(last)
+            if last.last_index + 1 == index
+                && producer_seq_number
+                    .checked_sub(1)
+                    .is_some_and(|previous_seq_number| {
+                        last.producer_of(last.last_index)
+                            == Some((producer_id, previous_seq_number))
+                    }) =>
+        {
+            last.last_index = index;
+        }
+        _ => inherited.push(InheritedOutboxMessages {
+            first_index: index,
+            last_index: index,
+            producer_id,
+            producer_first_seq_number: producer_seq_number,
+        }),
+    }
+}
+
+fn copy_partition_key_range<K: TableKey>(
+    parent: &PartitionStore,
+    child: &mut PartitionStore,
+) -> Result<()> {
+    let scan = TableScan::FullScanPartitionKeyRange::<K>(child.partition_key_range().clone());
+    let mut iterator = StorageAccess::iterator_from(parent, scan)?;
+
+    while let Some((key, value)) = iterator.item() {
+        child.put_cf(K::TABLE, key, value)?;
+        iterator.next();
+    }
+
+    Ok(())
+}
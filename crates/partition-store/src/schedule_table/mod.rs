@@ -0,0 +1,107 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::keys::{KeyKind, TableKey, define_table_key};
+use crate::owned_iter::OwnedIterator;
+use crate::protobuf_types::PartitionStoreProtobufValue;
+use crate::scan::TableScan;
+use crate::{PartitionStore, TableKind};
+use crate::{PartitionStoreTransaction, StorageAccess};
+use futures::Stream;
+use futures_util::stream;
+use restate_storage_api::Result;
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus, ScheduleTable};
+use restate_types::identifiers::{PartitionKey, ScheduleId, WithPartitionKey};
+use std::ops::RangeInclusive;
+
+define_table_key!(
+    TableKind::Schedule,
+    KeyKind::Schedule,
+    ScheduleKey(
+        partition_key: PartitionKey,
+        schedule_id: ScheduleId
+    )
+);
+
+impl PartitionStoreProtobufValue for ScheduleStatus {
+    type ProtobufType = crate::protobuf_types::v1::ScheduleStatus;
+}
+
+fn create_key(schedule_id: &ScheduleId) -> ScheduleKey {
+    ScheduleKey::default()
+        .partition_key(schedule_id.partition_key())
+        .schedule_id(*schedule_id)
+}
+
+fn get_schedule<S: StorageAccess>(
+    storage: &mut S,
+    schedule_id: &ScheduleId,
+) -> Result<Option<ScheduleStatus>> {
+    storage.get_value(create_key(schedule_id))
+}
+
+fn all_schedules<S: StorageAccess>(
+    storage: &S,
+    range: RangeInclusive<PartitionKey>,
+) -> Result<impl Stream<Item = Result<ScheduleStatus>> + Send + use<'_, S>> {
+    let iter = storage.iterator_from(TableScan::FullScanPartitionKeyRange::<ScheduleKey>(range))?;
+    Ok(stream::iter(
+        OwnedIterator::new(iter).map(|(_, mut v)| ScheduleStatus::decode(&mut v)),
+    ))
+}
+
+fn put_schedule<S: StorageAccess>(storage: &mut S, schedule_status: &ScheduleStatus) -> Result<()> {
+    storage.put_kv(create_key(&schedule_status.schedule.id), schedule_status)
+}
+
+fn delete_schedule<S: StorageAccess>(storage: &mut S, schedule_id: &ScheduleId) -> Result<()> {
+    let key = create_key(schedule_id);
+    storage.delete_key(&key)
+}
+
+impl ReadOnlyScheduleTable for PartitionStore {
+    async fn get_schedule(&mut self, schedule_id: &ScheduleId) -> Result<Option<ScheduleStatus>> {
+        self.assert_partition_key(schedule_id)?;
+        get_schedule(self, schedule_id)
+    }
+
+    fn all_schedules(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<ScheduleStatus>> + Send> {
+        all_schedules(self, range)
+    }
+}
+
+impl ReadOnlyScheduleTable for PartitionStoreTransaction<'_> {
+    async fn get_schedule(&mut self, schedule_id: &ScheduleId) -> Result<Option<ScheduleStatus>> {
+        self.assert_partition_key(schedule_id)?;
+        get_schedule(self, schedule_id)
+    }
+
+    fn all_schedules(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<ScheduleStatus>> + Send> {
+        all_schedules(self, range)
+    }
+}
+
+impl ScheduleTable for PartitionStoreTransaction<'_> {
+    async fn put_schedule(&mut self, schedule_status: &ScheduleStatus) -> Result<()> {
+        self.assert_partition_key(&schedule_status.schedule)?;
+        put_schedule(self, schedule_status)
+    }
+
+    async fn delete_schedule(&mut self, schedule_id: &ScheduleId) -> Result<()> {
+        self.assert_partition_key(schedule_id)?;
+        delete_schedule(self, schedule_id)
+    }
+}
@@ -11,6 +11,7 @@
 use crate::TableKind::State;
 use crate::keys::{KeyKind, TableKey, define_table_key};
 use crate::owned_iter::OwnedIterator;
+use crate::protobuf_types::PartitionStoreProtobufValue;
 use crate::{PartitionStore, PartitionStoreTransaction, StorageAccess};
 use crate::{TableScan, TableScanIterationDecision};
 use bytes::Bytes;
@@ -18,7 +19,7 @@ use bytestring::ByteString;
 use futures::Stream;
 use futures_util::stream;
 use restate_rocksdb::RocksDbPerfGuard;
-use restate_storage_api::state_table::{ReadOnlyStateTable, StateTable};
+use restate_storage_api::state_table::{ReadOnlyStateTable, StateEntryMetadata, StateTable};
 use restate_storage_api::{Result, StorageError};
 use restate_types::identifiers::{PartitionKey, ServiceId, WithPartitionKey};
 use std::ops::RangeInclusive;
@@ -34,6 +35,33 @@ define_table_key!(
     )
 );
 
+define_table_key!(
+    State,
+    KeyKind::StateMetadata,
+    StateMetadataKey(
+        partition_key: PartitionKey,
+        service_name: ByteString,
+        service_key: ByteString,
+        state_key: Bytes
+    )
+);
+
+impl PartitionStoreProtobufValue for StateEntryMetadata {
+    type ProtobufType = crate::protobuf_types::v1::StateEntryMetadata;
+}
+
+#[inline]
+fn write_state_metadata_key(
+    service_id: &ServiceId,
+    state_key: impl AsRef<[u8]>,
+) -> StateMetadataKey {
+    StateMetadataKey::default()
+        .partition_key(service_id.partition_key())
+        .service_name(service_id.service_name.clone())
+        .service_key(service_id.key.clone())
+        .state_key(state_key.as_ref().to_vec().into())
+}
+
 #[inline]
 fn write_state_entry_key(service_id: &ServiceId, state_key: impl AsRef<[u8]>) -> StateKey {
     StateKey::default()
@@ -63,13 +91,25 @@ fn put_user_state<S: StorageAccess>(
     storage.put_kv_raw(key, state_value.as_ref())
 }
 
+fn put_user_state_metadata<S: StorageAccess>(
+    storage: &mut S,
+    service_id: &ServiceId,
+    state_key: impl AsRef<[u8]>,
+    metadata: StateEntryMetadata,
+) -> Result<()> {
+    let key = write_state_metadata_key(service_id, state_key);
+    storage.put_kv(key, &metadata)
+}
+
 fn delete_user_state<S: StorageAccess>(
     storage: &mut S,
     service_id: &ServiceId,
     state_key: impl AsRef<[u8]>,
 ) -> Result<()> {
-    let key = write_state_entry_key(service_id, state_key);
-    storage.delete_key(&key)
+    let key = write_state_entry_key(service_id, &state_key);
+    storage.delete_key(&key)?;
+    let metadata_key = write_state_metadata_key(service_id, state_key);
+    storage.delete_key(&metadata_key)
 }
 
 fn delete_all_user_state<S: StorageAccess>(storage: &mut S, service_id: &ServiceId) -> Result<()> {
@@ -88,6 +128,21 @@ fn delete_all_user_state<S: StorageAccess>(storage: &mut S, service_id: &Service
         storage.delete_cf(State, &key)?;
     }
 
+    let metadata_prefix_key = StateMetadataKey::default()
+        .partition_key(service_id.partition_key())
+        .service_name(service_id.service_name.clone())
+        .service_key(service_id.key.clone());
+
+    let metadata_keys = storage.for_each_key_value_in_place(
+        TableScan::SinglePartitionKeyPrefix(service_id.partition_key(), metadata_prefix_key),
+        |k, _| TableScanIterationDecision::Emit(Ok(Bytes::copy_from_slice(k))),
+    )?;
+
+    for k in metadata_keys {
+        let key = k?;
+        storage.delete_cf(State, &key)?;
+    }
+
     Ok(())
 }
 
@@ -138,6 +193,37 @@ fn get_all_user_states<S: StorageAccess>(
     )))
 }
 
+fn get_user_state_metadata<S: StorageAccess>(
+    storage: &mut S,
+    service_id: &ServiceId,
+    state_key: impl AsRef<[u8]>,
+) -> Result<Option<StateEntryMetadata>> {
+    storage.get_value(write_state_metadata_key(service_id, state_key))
+}
+
+fn get_all_user_state_metadata<S: StorageAccess>(
+    storage: &S,
+    range: RangeInclusive<PartitionKey>,
+) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, StateEntryMetadata)>> + Send + use<'_, S>>
+{
+    let iter = storage.iterator_from(TableScan::FullScanPartitionKeyRange::<StateMetadataKey>(
+        range,
+    ));
+    Ok(stream::iter(OwnedIterator::new(iter?).map(
+        |(mut key, mut value)| {
+            let row_key = StateMetadataKey::deserialize_from(&mut key)?;
+            let (partition_key, service_name, service_key, state_key) =
+                row_key.into_inner_ok_or()?;
+
+            Ok((
+                ServiceId::from_parts(partition_key, service_name, service_key),
+                state_key,
+                StateEntryMetadata::decode(&mut value)?,
+            ))
+        },
+    )))
+}
+
 impl ReadOnlyStateTable for PartitionStore {
     async fn get_user_state(
         &mut self,
@@ -170,6 +256,22 @@ impl ReadOnlyStateTable for PartitionStore {
     ) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, Bytes)>> + Send> {
         get_all_user_states(self, range)
     }
+
+    async fn get_user_state_metadata(
+        &mut self,
+        service_id: &ServiceId,
+        state_key: impl AsRef<[u8]> + Send,
+    ) -> Result<Option<StateEntryMetadata>> {
+        self.assert_partition_key(service_id)?;
+        get_user_state_metadata(self, service_id, state_key)
+    }
+
+    fn get_all_user_state_metadata_in_range(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, StateEntryMetadata)>> + Send> {
+        get_all_user_state_metadata(self, range)
+    }
 }
 
 impl ReadOnlyStateTable for PartitionStoreTransaction<'_> {
@@ -204,6 +306,22 @@ impl ReadOnlyStateTable for PartitionStoreTransaction<'_> {
     ) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, Bytes)>> + Send> {
         get_all_user_states(self, range)
     }
+
+    async fn get_user_state_metadata(
+        &mut self,
+        service_id: &ServiceId,
+        state_key: impl AsRef<[u8]> + Send,
+    ) -> Result<Option<StateEntryMetadata>> {
+        self.assert_partition_key(service_id)?;
+        get_user_state_metadata(self, service_id, state_key)
+    }
+
+    fn get_all_user_state_metadata_in_range(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, StateEntryMetadata)>> + Send> {
+        get_all_user_state_metadata(self, range)
+    }
 }
 
 impl StateTable for PartitionStoreTransaction<'_> {
@@ -217,6 +335,16 @@ impl StateTable for PartitionStoreTransaction<'_> {
         put_user_state(self, service_id, state_key, state_value)
     }
 
+    async fn put_user_state_metadata(
+        &mut self,
+        service_id: &ServiceId,
+        state_key: impl AsRef<[u8]>,
+        metadata: StateEntryMetadata,
+    ) -> Result<()> {
+        self.assert_partition_key(service_id)?;
+        put_user_state_metadata(self, service_id, state_key, metadata)
+    }
+
     async fn delete_user_state(
         &mut self,
         service_id: &ServiceId,
@@ -0,0 +1,95 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::storage_test_environment;
+
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use restate_storage_api::Transaction;
+use restate_storage_api::dead_letter_table::{
+    DeadLetter, DeadLetterTable, ReadOnlyDeadLetterTable,
+};
+use restate_types::errors::InvocationError;
+use restate_types::identifiers::{InvocationId, InvocationUuid, PartitionKey, SubscriptionId};
+use restate_types::invocation::{Header, InvocationTarget, Source};
+use restate_types::time::MillisSinceEpoch;
+use std::time::Duration;
+
+const FIXTURE_INVOCATION_1: InvocationUuid = InvocationUuid::from_u128(12345678900001);
+const FIXTURE_INVOCATION_2: InvocationUuid = InvocationUuid::from_u128(12345678900002);
+
+fn dead_letter(handler: &str) -> DeadLetter {
+    DeadLetter {
+        invocation_target: InvocationTarget::service("my-service", handler),
+        source: Source::Subscription(SubscriptionId::new()),
+        headers: vec![Header::new("content-type", "application/json")],
+        argument: Bytes::from_static(b"{}"),
+        failure: InvocationError::internal("boom"),
+        failed_at: MillisSinceEpoch::new(1000),
+        idempotency_key: Some("my-key".into()),
+        completion_retention_duration: Duration::from_secs(60),
+        journal_retention_duration: Duration::ZERO,
+    }
+}
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_dead_letter() {
+    let mut rocksdb = storage_test_environment().await;
+
+    let invocation_id_1 = InvocationId::from_parts(10, FIXTURE_INVOCATION_1);
+    let invocation_id_2 = InvocationId::from_parts(10, FIXTURE_INVOCATION_2);
+    let dead_letter_1 = dead_letter("my-handler");
+    let dead_letter_2 = dead_letter("my-handler-2");
+
+    // Fill in some data
+    let mut txn = rocksdb.transaction();
+    txn.put_dead_letter(&invocation_id_1, &dead_letter_1)
+        .await
+        .unwrap();
+    txn.put_dead_letter(&invocation_id_2, &dead_letter_2)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    // Query
+    assert_eq!(
+        rocksdb.get_dead_letter(&invocation_id_1).await.unwrap(),
+        Some(dead_letter_1.clone())
+    );
+    assert_eq!(
+        rocksdb.get_dead_letter(&invocation_id_2).await.unwrap(),
+        Some(dead_letter_2.clone())
+    );
+    assert_eq!(
+        rocksdb
+            .all_dead_letters(0..=PartitionKey::MAX)
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap(),
+        vec![
+            (invocation_id_1, dead_letter_1),
+            (invocation_id_2, dead_letter_2.clone())
+        ]
+    );
+
+    // Delete and query afterwards
+    let mut txn = rocksdb.transaction();
+    txn.delete_dead_letter(&invocation_id_1).await.unwrap();
+    txn.commit().await.unwrap();
+    assert_eq!(
+        rocksdb.get_dead_letter(&invocation_id_1).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        rocksdb.get_dead_letter(&invocation_id_2).await.unwrap(),
+        Some(dead_letter_2)
+    );
+}
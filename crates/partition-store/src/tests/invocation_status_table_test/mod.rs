@@ -27,7 +27,8 @@ use restate_storage_api::invocation_status_table::{
 };
 use restate_types::identifiers::{InvocationId, PartitionProcessorRpcRequestId, WithPartitionKey};
 use restate_types::invocation::{
-    InvocationTarget, ServiceInvocationSpanContext, Source, VirtualObjectHandlerType,
+    InvocationTarget, OnMaxAttemptsAction, ServiceInvocationSpanContext, Source,
+    VirtualObjectHandlerType,
 };
 use restate_types::time::MillisSinceEpoch;
 
@@ -73,6 +74,15 @@ const INVOCATION_TARGET_4: InvocationTarget = InvocationTarget::VirtualObject {
 static INVOCATION_ID_4: LazyLock<InvocationId> =
     LazyLock::new(|| InvocationId::mock_generate(&INVOCATION_TARGET_4));
 
+const INVOCATION_TARGET_5: InvocationTarget = InvocationTarget::VirtualObject {
+    name: ByteString::from_static("abc"),
+    key: ByteString::from_static("5"),
+    handler: ByteString::from_static("myhandler"),
+    handler_ty: VirtualObjectHandlerType::Exclusive,
+};
+static INVOCATION_ID_5: LazyLock<InvocationId> =
+    LazyLock::new(|| InvocationId::mock_generate(&INVOCATION_TARGET_5));
+
 static RPC_REQUEST_ID: LazyLock<PartitionProcessorRpcRequestId> =
     LazyLock::new(PartitionProcessorRpcRequestId::new);
 
@@ -85,10 +95,13 @@ fn invoked_status(invocation_target: InvocationTarget) -> InvocationStatus {
         timestamps: StatusTimestamps::init(MillisSinceEpoch::new(0)),
         source: Source::Ingress(*RPC_REQUEST_ID),
         completion_retention_duration: Duration::ZERO,
+        journal_retention_duration: Duration::ZERO,
         idempotency_key: None,
         hotfix_apply_cancellation_after_deployment_is_pinned: false,
         current_invocation_epoch: 1,
         completion_range_epoch_map: CompletionRangeEpochMap::from_trim_points([(5, 1)]),
+        retries_exhausted_action: None,
+        dead_letter_queue: false,
     })
 }
 
@@ -102,15 +115,37 @@ fn suspended_status(invocation_target: InvocationTarget) -> InvocationStatus {
             timestamps: StatusTimestamps::init(MillisSinceEpoch::new(0)),
             source: Source::Ingress(*RPC_REQUEST_ID),
             completion_retention_duration: Duration::ZERO,
+            journal_retention_duration: Duration::ZERO,
             idempotency_key: None,
             hotfix_apply_cancellation_after_deployment_is_pinned: false,
             current_invocation_epoch: 1,
             completion_range_epoch_map: CompletionRangeEpochMap::from_trim_points([(5, 1)]),
+            retries_exhausted_action: None,
+            dead_letter_queue: false,
         },
         waiting_for_notifications: HashSet::default(),
     }
 }
 
+fn paused_status(invocation_target: InvocationTarget) -> InvocationStatus {
+    InvocationStatus::Paused(InFlightInvocationMetadata {
+        invocation_target,
+        journal_metadata: JournalMetadata::initialize(ServiceInvocationSpanContext::empty()),
+        pinned_deployment: None,
+        response_sinks: HashSet::new(),
+        timestamps: StatusTimestamps::init(MillisSinceEpoch::new(0)),
+        source: Source::Ingress(*RPC_REQUEST_ID),
+        completion_retention_duration: Duration::ZERO,
+        journal_retention_duration: Duration::ZERO,
+        idempotency_key: None,
+        hotfix_apply_cancellation_after_deployment_is_pinned: false,
+        current_invocation_epoch: 1,
+        completion_range_epoch_map: CompletionRangeEpochMap::from_trim_points([(5, 1)]),
+        retries_exhausted_action: Some(OnMaxAttemptsAction::Pause),
+        dead_letter_queue: false,
+    })
+}
+
 async fn populate_data<T: InvocationStatusTable>(txn: &mut T) {
     txn.put_invocation_status(
         &INVOCATION_ID_1,
@@ -139,6 +174,13 @@ async fn populate_data<T: InvocationStatusTable>(txn: &mut T) {
     )
     .await
     .unwrap();
+
+    txn.put_invocation_status(
+        &INVOCATION_ID_5,
+        &paused_status(INVOCATION_TARGET_5.clone()),
+    )
+    .await
+    .unwrap();
 }
 
 async fn verify_point_lookups<T: InvocationStatusTable>(txn: &mut T) {
@@ -148,6 +190,12 @@ async fn verify_point_lookups<T: InvocationStatusTable>(txn: &mut T) {
             .expect("should not fail"),
         invoked_status(INVOCATION_TARGET_1.clone())
     );
+    assert_eq!(
+        txn.get_invocation_status(&INVOCATION_ID_5)
+            .await
+            .expect("should not fail"),
+        paused_status(INVOCATION_TARGET_5.clone())
+    );
 }
 
 async fn verify_all_svc_with_status_invoked<T: InvocationStatusTable>(txn: &mut T) {
@@ -27,6 +27,7 @@ use restate_types::invocation::{InvocationTarget, ServiceInvocation, Source};
 use restate_types::live::Constant;
 use restate_types::state_mut::ExternalStateMutation;
 
+mod dead_letter_table_test;
 mod idempotency_table_test;
 mod inbox_table_test;
 mod invocation_status_table_test;
@@ -34,6 +35,8 @@ mod journal_table_test;
 mod journal_table_v2_test;
 mod outbox_table_test;
 mod promise_table_test;
+mod repartition_test;
+mod schedule_table_test;
 mod snapshots_test;
 mod state_table_test;
 mod timer_table_test;
@@ -93,7 +96,9 @@ pub(crate) fn mock_service_invocation(service_id: ServiceId) -> ServiceInvocatio
         headers: vec![],
         execution_time: None,
         completion_retention_duration: None,
+        journal_retention_duration: None,
         idempotency_key: None,
+        dead_letter_queue: false,
         submit_notification_sink: None,
     }
 }
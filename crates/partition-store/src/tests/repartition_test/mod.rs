@@ -0,0 +1,371 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use futures::TryStreamExt;
+use tempfile::tempdir;
+
+use restate_storage_api::Transaction;
+use restate_storage_api::deduplication_table::{
+    DedupSequenceNumber, DeduplicationTable, EpochSequenceNumber, ProducerId,
+    ReadOnlyDeduplicationTable,
+};
+use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
+use restate_storage_api::inbox_table::{InboxEntry, InboxTable, ReadOnlyInboxTable};
+use restate_storage_api::outbox_table::{
+    InheritedOutboxMessages, OutboxMessage, OutboxTable, ReadOnlyOutboxTable,
+};
+use restate_storage_api::state_table::{ReadOnlyStateTable, StateTable};
+use restate_storage_api::timer_table::{Timer, TimerKey, TimerKeyKind, TimerTable};
+use restate_types::config::StorageOptions;
+use restate_types::identifiers::{
+    InvocationId, InvocationUuid, LeaderEpoch, PartitionId, PartitionKey, ServiceId, SnapshotId,
+};
+use restate_types::logs::{Lsn, SequenceNumber};
+
+use super::{mock_service_invocation, storage_test_environment_with_manager};
+use crate::snapshots::LocalPartitionSnapshot;
+use crate::{OpenMode, PartitionStore, PartitionStoreManager};
+
+const SPLIT_KEY: PartitionKey = PartitionKey::MAX / 2;
+const LEFT_KEY: PartitionKey = 10;
+const RIGHT_KEY: PartitionKey = PartitionKey::MAX - 10;
+const OTHER_PRODUCER: ProducerId = ProducerId::Partition(PartitionId::new_unchecked(7));
+
+fn service_id(partition_key: PartitionKey) -> ServiceId {
+    ServiceId::with_partition_key(partition_key, "svc", partition_key.to_string())
+}
+
+fn timer_key(invocation_id: InvocationId) -> TimerKey {
+    TimerKey {
+        kind: TimerKeyKind::CompleteJournalEntry {
+            invocation_uuid: invocation_id.invocation_uuid(),
+            journal_index: 1,
+        },
+        timestamp: 0,
+    }
+}
+
+fn invocation_id(partition_key: PartitionKey) -> InvocationId {
+    InvocationId::from_parts(
+        partition_key,
+        InvocationUuid::from_u128(u128::from(partition_key)),
+    )
+}
+
+/// Populates the parent with one entry per table for each of the given partition keys, and returns
+/// the outbox messages, starting at `outbox_head`. `seq_number` is used both as the inbox sequence
+/// number and as the deduplication sequence number of another partition.
+async fn populate_parent(
+    parent: &mut PartitionStore,
+    partition_keys: &[PartitionKey],
+    outbox_head: u64,
+    seq_number: u64,
+) -> Vec<OutboxMessage> {
+    let mut txn = parent.transaction();
+    let mut outbox = Vec::new();
+    let mut outbox_seq_number = outbox_head;
+    for partition_key in partition_keys {
+        let service_id = service_id(*partition_key);
+        let invocation_id = invocation_id(*partition_key);
+
+        txn.put_user_state(&service_id, b"key", b"value")
+            .await
+            .unwrap();
+        txn.put_timer(
+            &timer_key(invocation_id),
+            &Timer::CompleteJournalEntry(invocation_id, 1, 0),
+        )
+        .await
+        .unwrap();
+        txn.put_inbox_entry(
+            *partition_key,
+            &InboxEntry::Invocation(service_id.clone(), invocation_id),
+        )
+        .await
+        .unwrap();
+        let message = OutboxMessage::ServiceInvocation(mock_service_invocation(service_id));
+        txn.put_outbox_message(outbox_seq_number, &message)
+            .await
+            .unwrap();
+        outbox.push(message);
+        outbox_seq_number += 1;
+    }
+
+    txn.put_dedup_seq_number(OTHER_PRODUCER, &DedupSequenceNumber::Sn(seq_number))
+        .await
+        .unwrap();
+    txn.put_dedup_seq_number(
+        ProducerId::self_producer(),
+        &DedupSequenceNumber::Esn(EpochSequenceNumber::new(LeaderEpoch::INITIAL)),
+    )
+    .await
+    .unwrap();
+    txn.put_inbox_seq_number(seq_number).await.unwrap();
+    txn.put_outbox_seq_number(outbox_seq_number).await.unwrap();
+    txn.put_applied_lsn(Lsn::new(100)).await.unwrap();
+    txn.commit().await.unwrap();
+
+    outbox
+}
+
+async fn snapshot(
+    parent: &mut PartitionStore,
+    snapshots_dir: &Path,
+) -> (PartitionId, LocalPartitionSnapshot) {
+    let snapshot = parent
+        .create_snapshot(snapshots_dir, None, SnapshotId::new())
+        .await
+        .unwrap();
+    (parent.partition_id(), snapshot)
+}
+
+async fn create_child(
+    manager: &PartitionStoreManager,
+    partition_id: u16,
+    key_range: RangeInclusive<PartitionKey>,
+    parents: Vec<(PartitionId, LocalPartitionSnapshot)>,
+) -> PartitionStore {
+    manager
+        .open_partition_store_from_parents(
+            PartitionId::from(partition_id),
+            key_range,
+            parents,
+            &StorageOptions::default().rocksdb,
+        )
+        .await
+        .unwrap()
+}
+
+async fn outbox(child: &mut PartitionStore) -> Vec<(u64, OutboxMessage)> {
+    let mut messages = Vec::new();
+    if let Some(mut seq_number) = child.get_outbox_head_seq_number().await.unwrap() {
+        while let Some((next_seq_number, message)) =
+            child.get_next_outbox_message(seq_number).await.unwrap()
+        {
+            messages.push((next_seq_number, message));
+            seq_number = next_seq_number + 1;
+        }
+    }
+    messages
+}
+
+async fn assert_owns_keys(
+    child: &mut PartitionStore,
+    owned: &[PartitionKey],
+    not_owned: &[PartitionKey],
+) {
+    for partition_key in owned {
+        assert_eq!(
+            child
+                .get_user_state(&service_id(*partition_key), b"key")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some(b"value".as_slice())
+        );
+        assert!(
+            child
+                .peek_inbox(&service_id(*partition_key))
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+    for partition_key in not_owned {
+        assert!(
+            child
+                .get_user_state(&service_id(*partition_key), b"key")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            child
+                .peek_inbox(&service_id(*partition_key))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    let timers: Vec<_> = child
+        .next_timers_greater_than(None, usize::MAX)
+        .unwrap()
+        .map_ok(|(timer_key, _)| timer_key)
+        .try_collect()
+        .await
+        .unwrap();
+    let expected_timers: Vec<_> = owned
+        .iter()
+        .map(|partition_key| timer_key(invocation_id(*partition_key)))
+        .collect();
+    assert_eq!(timers, expected_timers);
+
+    // the store is complete, the child reads its new log from the beginning
+    assert_eq!(child.get_applied_lsn().await.unwrap(), Some(Lsn::INVALID));
+}
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn split_partition() {
+    let (manager, mut parent) = storage_test_environment_with_manager().await;
+    let parent_outbox = populate_parent(&mut parent, &[LEFT_KEY, RIGHT_KEY], 3, 42).await;
+
+    // the snapshot files are moved when imported, each child needs its own snapshot
+    let snapshots_dir = tempdir().unwrap();
+    let left_snapshot = snapshot(&mut parent, snapshots_dir.path()).await;
+    let right_snapshot = snapshot(&mut parent, snapshots_dir.path()).await;
+
+    let mut left = create_child(&manager, 1, 0..=SPLIT_KEY - 1, vec![left_snapshot]).await;
+    let mut right = create_child(
+        &manager,
+        2,
+        SPLIT_KEY..=PartitionKey::MAX - 1,
+        vec![right_snapshot],
+    )
+    .await;
+
+    assert_owns_keys(&mut left, &[LEFT_KEY], &[RIGHT_KEY]).await;
+    assert_owns_keys(&mut right, &[RIGHT_KEY], &[LEFT_KEY]).await;
+
+    for child in [&mut left, &mut right] {
+        // the deduplication information of the parent's own proposals is not inherited
+        assert_eq!(
+            child
+                .get_all_sequence_numbers()
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            child
+                .get_dedup_sequence_number(&OTHER_PRODUCER)
+                .await
+                .unwrap(),
+            Some(DedupSequenceNumber::Sn(42))
+        );
+        assert_eq!(child.get_inbox_seq_number().await.unwrap(), 42);
+    }
+
+    // the whole outbox is shipped by the left child, as the parent that produced it
+    assert_eq!(
+        outbox(&mut left).await,
+        parent_outbox
+            .into_iter()
+            .enumerate()
+            .map(|(index, message)| (index as u64, message))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(left.get_outbox_seq_number().await.unwrap(), 2);
+    assert_eq!(
+        left.get_inherited_outbox_messages().await.unwrap(),
+        vec![InheritedOutboxMessages {
+            first_index: 0,
+            last_index: 1,
+            producer_id: PartitionId::MIN,
+            producer_first_seq_number: 3,
+        }]
+    );
+
+    assert!(outbox(&mut right).await.is_empty());
+    assert_eq!(right.get_outbox_seq_number().await.unwrap(), 0);
+    assert!(
+        right
+            .get_inherited_outbox_messages()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn merge_partitions() {
+    let (manager, _) = storage_test_environment_with_manager().await;
+    let opts = StorageOptions::default().rocksdb;
+    let mut left_parent = manager
+        .open_partition_store(
+            PartitionId::from(1),
+            0..=SPLIT_KEY - 1,
+            OpenMode::CreateIfMissing,
+            &opts,
+        )
+        .await
+        .unwrap();
+    let mut right_parent = manager
+        .open_partition_store(
+            PartitionId::from(2),
+            SPLIT_KEY..=PartitionKey::MAX - 1,
+            OpenMode::CreateIfMissing,
+            &opts,
+        )
+        .await
+        .unwrap();
+    let mut parents_outbox = populate_parent(&mut left_parent, &[LEFT_KEY], 5, 42).await;
+    parents_outbox.extend(populate_parent(&mut right_parent, &[RIGHT_KEY], 0, 17).await);
+
+    let snapshots_dir = tempdir().unwrap();
+    let parents = vec![
+        snapshot(&mut left_parent, snapshots_dir.path()).await,
+        snapshot(&mut right_parent, snapshots_dir.path()).await,
+    ];
+    let mut merged = create_child(&manager, 3, 0..=PartitionKey::MAX - 1, parents).await;
+
+    assert_owns_keys(&mut merged, &[LEFT_KEY, RIGHT_KEY], &[]).await;
+
+    // the highest sequence numbers of both parents are kept
+    assert_eq!(
+        merged
+            .get_dedup_sequence_number(&OTHER_PRODUCER)
+            .await
+            .unwrap(),
+        Some(DedupSequenceNumber::Sn(42))
+    );
+    assert!(
+        merged
+            .get_dedup_sequence_number(&ProducerId::self_producer())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(merged.get_inbox_seq_number().await.unwrap(), 42);
+
+    // each parent's outbox keeps the producer id and sequence numbers of its parent
+    assert_eq!(
+        outbox(&mut merged).await,
+        parents_outbox
+            .into_iter()
+            .enumerate()
+            .map(|(index, message)| (index as u64, message))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(merged.get_outbox_seq_number().await.unwrap(), 2);
+    assert_eq!(
+        merged.get_inherited_outbox_messages().await.unwrap(),
+        vec![
+            InheritedOutboxMessages {
+                first_index: 0,
+                last_index: 0,
+                producer_id: PartitionId::from(1),
+                producer_first_seq_number: 5,
+            },
+            InheritedOutboxMessages {
+                first_index: 1,
+                last_index: 1,
+                producer_id: PartitionId::from(2),
+                producer_first_seq_number: 0,
+            },
+        ]
+    );
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::storage_test_environment;
+
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use restate_storage_api::Transaction;
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus, ScheduleTable};
+use restate_types::identifiers::{PartitionKey, ScheduleId};
+use restate_types::invocation::InvocationTarget;
+use restate_types::schedule::{CatchUpPolicy, Schedule};
+use restate_types::time::MillisSinceEpoch;
+
+fn schedule_status(id: ScheduleId, catch_up: CatchUpPolicy) -> ScheduleStatus {
+    ScheduleStatus {
+        schedule: Schedule {
+            id,
+            cron: "*/5 * * * *".parse().unwrap(),
+            invocation_target: InvocationTarget::virtual_object(
+                "my-object",
+                "my-key",
+                "my-handler",
+                Default::default(),
+            ),
+            argument: Bytes::from_static(b"{}"),
+            catch_up,
+        },
+        next_fire_at: Some(MillisSinceEpoch::new(300_000)),
+        last_fired_at: None,
+    }
+}
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_schedule() {
+    let mut rocksdb = storage_test_environment().await;
+
+    let schedule_1 = schedule_status(ScheduleId::from(1), CatchUpPolicy::Skip);
+    let schedule_2 = schedule_status(ScheduleId::from(2), CatchUpPolicy::All);
+
+    // Fill in some data
+    let mut txn = rocksdb.transaction();
+    txn.put_schedule(&schedule_1).await.unwrap();
+    txn.put_schedule(&schedule_2).await.unwrap();
+    txn.commit().await.unwrap();
+
+    // Query
+    assert_eq!(
+        rocksdb.get_schedule(&schedule_1.schedule.id).await.unwrap(),
+        Some(schedule_1.clone())
+    );
+    let mut all_schedules = rocksdb
+        .all_schedules(0..=PartitionKey::MAX)
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    all_schedules.sort_by_key(|status| status.schedule.id);
+    assert_eq!(all_schedules, vec![schedule_1.clone(), schedule_2.clone()]);
+
+    // Delete and query afterwards
+    let mut txn = rocksdb.transaction();
+    txn.delete_schedule(&schedule_1.schedule.id).await.unwrap();
+    txn.commit().await.unwrap();
+    assert_eq!(
+        rocksdb.get_schedule(&schedule_1.schedule.id).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        rocksdb.get_schedule(&schedule_2.schedule.id).await.unwrap(),
+        Some(schedule_2)
+    );
+}
@@ -12,9 +12,11 @@ use super::{assert_stream_eq, storage_test_environment};
 
 use crate::PartitionStore;
 use bytes::Bytes;
+use futures::StreamExt;
 use restate_storage_api::Transaction;
-use restate_storage_api::state_table::{ReadOnlyStateTable, StateTable};
-use restate_types::identifiers::ServiceId;
+use restate_storage_api::state_table::{ReadOnlyStateTable, StateEntryMetadata, StateTable};
+use restate_types::identifiers::{PartitionKey, ServiceId};
+use restate_types::time::MillisSinceEpoch;
 
 async fn populate_data<T: StateTable>(table: &mut T) {
     table
@@ -151,3 +153,85 @@ async fn test_delete_all() {
         .is_some()
     );
 }
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_state_metadata() {
+    let mut rocksdb = storage_test_environment().await;
+    let service_id = ServiceId::with_partition_key(1337, "svc-1", "key-1");
+
+    let mut txn = rocksdb.transaction();
+    populate_data(&mut txn).await;
+    for (service_id, state_key, version) in [
+        (service_id.clone(), b"k1", 1),
+        (service_id.clone(), b"k2", 1),
+        (
+            ServiceId::with_partition_key(1337, "svc-1", "key-2"),
+            b"k2",
+            1,
+        ),
+        (service_id.clone(), b"k1", 2),
+    ] {
+        txn.put_user_state_metadata(
+            &service_id,
+            &Bytes::from_static(state_key),
+            StateEntryMetadata {
+                version,
+                modified_at: MillisSinceEpoch::new(version),
+            },
+        )
+        .await
+        .unwrap();
+    }
+    txn.commit().await.expect("should not fail");
+
+    let mut txn = rocksdb.transaction();
+    let metadata = txn
+        .get_user_state_metadata(&service_id, &Bytes::from_static(b"k1"))
+        .await
+        .unwrap()
+        .expect("metadata must be present");
+    assert_eq!(metadata.version, 2);
+    assert_eq!(metadata.modified_at, MillisSinceEpoch::new(2));
+
+    let all_metadata: Vec<_> = txn
+        .get_all_user_state_metadata_in_range(PartitionKey::MIN..=PartitionKey::MAX)
+        .unwrap()
+        .map(|entry| {
+            let (service_id, state_key, metadata) = entry.unwrap();
+            (service_id.key, state_key, metadata.version)
+        })
+        .collect()
+        .await;
+    assert_eq!(
+        all_metadata,
+        vec![
+            ("key-1".into(), Bytes::from_static(b"k1"), 2),
+            ("key-1".into(), Bytes::from_static(b"k2"), 1),
+            ("key-2".into(), Bytes::from_static(b"k2"), 1),
+        ]
+    );
+
+    // Deleting the state deletes its metadata
+    txn.delete_user_state(&service_id, &Bytes::from_static(b"k1"))
+        .await
+        .unwrap();
+    txn.delete_all_user_state(&ServiceId::with_partition_key(1337, "svc-1", "key-2"))
+        .await
+        .unwrap();
+    txn.commit().await.expect("should not fail");
+
+    let mut txn = rocksdb.transaction();
+    assert!(
+        txn.get_user_state_metadata(&service_id, &Bytes::from_static(b"k1"))
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        txn.get_all_user_state_metadata_in_range(PartitionKey::MIN..=PartitionKey::MAX)
+            .unwrap()
+            .count()
+            .await,
+        1
+    );
+}
@@ -14,7 +14,7 @@ use futures_util::stream;
 use restate_rocksdb::RocksDbPerfGuard;
 use restate_storage_api::timer_table::{Timer, TimerKey, TimerKeyKind, TimerTable};
 use restate_storage_api::{Result, StorageError};
-use restate_types::identifiers::{InvocationUuid, PartitionId};
+use restate_types::identifiers::{InvocationUuid, PartitionId, ScheduleId};
 
 use crate::TableKind::Timers;
 use crate::TableScanIterationDecision::Emit;
@@ -114,6 +114,15 @@ fn exclusive_start_key_range(
                     },
                 }
             }
+            TimerKeyKind::FireSchedule { schedule_id } => {
+                let incremented_schedule_id = increment_schedule_id(schedule_id);
+                TimerKey {
+                    timestamp: timer_key.timestamp,
+                    kind: TimerKeyKind::FireSchedule {
+                        schedule_id: incremented_schedule_id,
+                    },
+                }
+            }
         };
 
         let lower_bound = write_timer_key(partition_id, &next_timer_key);
@@ -137,6 +146,14 @@ fn increment_invocation_uuid(invocation_uuid: InvocationUuid) -> InvocationUuid
     )
 }
 
+fn increment_schedule_id(schedule_id: ScheduleId) -> ScheduleId {
+    ScheduleId::from(
+        u128::from_be_bytes(schedule_id.to_bytes())
+            .checked_add(1)
+            .expect("schedule_id should be smaller than u128::MAX"),
+    )
+}
+
 fn add_timer<S: StorageAccess>(
     storage: &mut S,
     partition_id: PartitionId,
@@ -295,6 +312,21 @@ mod tests {
         assert_eq!(got, key);
     }
 
+    #[test]
+    fn round_trip_fire_schedule() {
+        let key = TimerKey {
+            kind: TimerKeyKind::FireSchedule {
+                schedule_id: ScheduleId::from(12345678900001),
+            },
+            timestamp: 87654321,
+        };
+
+        let key_bytes = write_timer_key(PartitionId::from(1337), &key).serialize();
+        let got = timer_key_from_key_slice(&key_bytes).expect("should not fail");
+
+        assert_eq!(got, key);
+    }
+
     #[test]
     fn test_lexicographical_sorting_by_timestamp() {
         let kinds = [
@@ -311,6 +343,9 @@ mod tests {
             TimerKeyKind::NeoInvoke {
                 invocation_uuid: FIXTURE_INVOCATION,
             },
+            TimerKeyKind::FireSchedule {
+                schedule_id: ScheduleId::from(12345678900001),
+            },
         ];
 
         for first_kind in &kinds {
@@ -523,6 +558,9 @@ mod tests {
                         invocation_uuid: InvocationUuid::mock_random(),
                     }
                 }
+                TimerKeyKindDiscriminants::FireSchedule => TimerKeyKind::FireSchedule {
+                    schedule_id: ScheduleId::new(),
+                },
             }
         };
 
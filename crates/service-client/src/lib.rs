@@ -14,6 +14,9 @@ use crate::lambda::LambdaClient;
 pub use crate::http::HttpError;
 pub use crate::lambda::AssumeRoleCacheMode;
 use crate::request_identity::SignRequest;
+use crate::request_identity::keyring::Keyring;
+pub use crate::request_identity::keyring::{JsonWebKey, JsonWebKeySet};
+use crate::secrets::SecretResolver;
 use ::http::Version;
 use arc_swap::ArcSwapOption;
 use bytes::Bytes;
@@ -37,8 +40,11 @@ mod http;
 mod lambda;
 mod proxy;
 mod request_identity;
+mod secrets;
 mod utils;
 
+pub use crate::secrets::SecretsError;
+
 pub type ResponseBody = http_body_util::Either<hyper::body::Incoming, Full<Bytes>>;
 
 #[derive(Debug, Clone)]
@@ -48,19 +54,22 @@ pub struct ServiceClient {
     http: HttpClient,
     lambda: LambdaClient,
     // this can be changed to re-read periodically if necessary
-    request_identity_key: Arc<ArcSwapOption<request_identity::v1::SigningKey>>,
+    request_identity_keyring: Arc<ArcSwapOption<Keyring>>,
+    secrets: Arc<SecretResolver>,
 }
 
 impl ServiceClient {
     pub(crate) fn new(
         http: HttpClient,
         lambda: LambdaClient,
-        request_identity_key: Arc<ArcSwapOption<request_identity::v1::SigningKey>>,
+        request_identity_keyring: Arc<ArcSwapOption<Keyring>>,
+        secrets: Arc<SecretResolver>,
     ) -> Self {
         Self {
             http,
             lambda,
-            request_identity_key,
+            request_identity_keyring,
+            secrets,
         }
     }
 
@@ -68,14 +77,21 @@ impl ServiceClient {
         options: &ServiceClientOptions,
         assume_role_cache_mode: AssumeRoleCacheMode,
     ) -> Result<Self, BuildError> {
-        let request_identity_key = if let Some(request_identity_private_key_pem_file) =
+        let request_identity_keyring = if let Some(request_identity_private_key_pem_file) =
             options.request_identity_private_key_pem_file.clone()
         {
-            Arc::new(ArcSwapOption::from_pointee(
-                request_identity::v1::SigningKey::from_pem_file(
-                    request_identity_private_key_pem_file,
-                )?,
-            ))
+            Arc::new(ArcSwapOption::from_pointee(Keyring::from_pem_files(
+                request_identity_private_key_pem_file,
+                options
+                    .request_identity_grace_period_key_pem_files
+                    .iter()
+                    .cloned(),
+            )?))
+        } else if !options
+            .request_identity_grace_period_key_pem_files
+            .is_empty()
+        {
+            return Err(BuildError::MissingActiveRequestIdentityKey);
         } else {
             Arc::new(ArcSwapOption::empty())
         };
@@ -83,7 +99,11 @@ impl ServiceClient {
         Ok(Self::new(
             HttpClient::from_options(&options.http),
             LambdaClient::from_options(&options.lambda, assume_role_cache_mode),
-            request_identity_key,
+            request_identity_keyring,
+            Arc::new(SecretResolver::new(
+                options.secrets_file.clone(),
+                options.secrets_env_prefix.clone(),
+            )?),
         ))
     }
 }
@@ -92,6 +112,12 @@ impl ServiceClient {
 pub enum BuildError {
     #[error("Failed to read request identity private key: {0}")]
     SigningPrivateKeyReadError(#[from] request_identity::v1::SigningPrivateKeyReadError),
+    #[error(
+        "Request identity grace period keys are configured without an active key, set 'request-identity-private-key-pem-file'"
+    )]
+    MissingActiveRequestIdentityKey,
+    #[error("Failed to read deployment secrets: {0}")]
+    Secrets(#[from] SecretsError),
 }
 
 impl ServiceClient {
@@ -105,12 +131,17 @@ impl ServiceClient {
     {
         let (mut parts, body) = req.into_parts();
 
-        let request_identity_key = self.request_identity_key.load();
+        parts.headers = match self.secrets.resolve_headers(parts.headers) {
+            Ok(headers) => headers,
+            Err(err) => return future::ready(Err(err.into())).right_future(),
+        };
+
+        let request_identity_keyring = self.request_identity_keyring.load();
 
-        let signer = if let Some(request_identity_key) = request_identity_key.as_deref() {
+        let signer = if let Some(keyring) = request_identity_keyring.as_deref() {
             Some(request_identity::v1::Signer::new(
                 parts.path.path(),
-                request_identity_key,
+                keyring.active(),
             ))
         } else {
             None // will use null signing scheme
@@ -161,6 +192,18 @@ impl ServiceClient {
     }
 }
 
+impl ServiceClient {
+    /// The public keys of the request identity keyring, which service endpoints can use to
+    /// verify the requests. Empty if request identity signing is disabled.
+    pub fn request_identity_jwks(&self) -> JsonWebKeySet {
+        self.request_identity_keyring
+            .load()
+            .as_deref()
+            .map(Keyring::jwks)
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ServiceClientError {
     #[error("error when calling '{0}': {1}")]
@@ -169,6 +212,8 @@ pub enum ServiceClientError {
     Lambda(LambdaARN, #[source] lambda::LambdaError),
     #[error(transparent)]
     IdentityV1(#[from] <request_identity::v1::Signer<'static, 'static> as SignRequest>::Error),
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
 }
 
 impl ServiceClientError {
@@ -179,6 +224,8 @@ impl ServiceClientError {
             ServiceClientError::Http(_, http_error) => http_error.is_retryable(),
             ServiceClientError::Lambda(_, lambda_error) => lambda_error.is_retryable(),
             ServiceClientError::IdentityV1(_) => false, // this really should never happen
+            // the secret might be added to the secrets file in the meantime
+            ServiceClientError::Secrets(_) => true,
         }
     }
 }
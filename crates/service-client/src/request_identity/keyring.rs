@@ -0,0 +1,198 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+
+use super::v1::{SigningKey, SigningPrivateKeyReadError};
+
+/// The request identity keys: the active key signs the requests, while the grace period keys are
+/// only published, so that service endpoints accept them before and after a rotation.
+#[derive(Debug)]
+pub(crate) struct Keyring {
+    active: SigningKey,
+    grace_period: Vec<SigningKey>,
+}
+
+impl Keyring {
+    pub(crate) fn from_pem_files(
+        active_pem_file: PathBuf,
+        grace_period_pem_files: impl IntoIterator<Item = PathBuf>,
+    ) -> Result<Self, SigningPrivateKeyReadError> {
+        Ok(Self {
+            active: SigningKey::from_pem_file(active_pem_file)?,
+            grace_period: grace_period_pem_files
+                .into_iter()
+                .map(SigningKey::from_pem_file)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub(crate) fn active(&self) -> &SigningKey {
+        &self.active
+    }
+
+    /// The public keys of the keyring, starting with the active one.
+    pub(crate) fn jwks(&self) -> JsonWebKeySet {
+        let mut keys: Vec<JsonWebKey> = Vec::with_capacity(1 + self.grace_period.len());
+        for key in std::iter::once(&self.active).chain(&self.grace_period) {
+            // the same key might be listed twice while being rotated
+            if keys.iter().all(|jwk| jwk.kid != key.kid()) {
+                keys.push(JsonWebKey::ed25519(key));
+            }
+        }
+        JsonWebKeySet { keys }
+    }
+}
+
+/// Public keys used to sign the request identity JWTs, in the JSON Web Key Set format of
+/// [RFC 7517](https://www.rfc-editor.org/rfc/rfc7517#section-5).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonWebKeySet {
+    pub keys: Vec<JsonWebKey>,
+}
+
+/// An ed25519 public key, as specified by [RFC 8037](https://www.rfc-editor.org/rfc/rfc8037).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonWebKey {
+    pub kty: String,
+    pub crv: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub usage: String,
+    pub kid: String,
+    /// The base64url encoded public key.
+    pub x: String,
+}
+
+impl JsonWebKey {
+    fn ed25519(key: &SigningKey) -> Self {
+        Self {
+            kty: "OKP".to_owned(),
+            crv: "Ed25519".to_owned(),
+            alg: "EdDSA".to_owned(),
+            usage: "sig".to_owned(),
+            kid: key.kid().to_owned(),
+            x: URL_SAFE_NO_PAD.encode(key.public_key()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    use hyper::HeaderMap;
+    use ring::rand::SystemRandom;
+    use ring::signature::Ed25519KeyPair;
+
+    use crate::request_identity::SignRequest;
+    use crate::request_identity::v1::Signer;
+
+    #[derive(serde::Deserialize)]
+    struct Claims {
+        aud: String,
+    }
+
+    fn generate_key(dir: &Path, name: &str) -> PathBuf {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let path = dir.join(format!("{name}.pem"));
+        std::fs::write(
+            &path,
+            pem::encode(&pem::Pem::new("PRIVATE KEY", pkcs8.as_ref())),
+        )
+        .unwrap();
+        path
+    }
+
+    fn sign(keyring: &Keyring, path: &str) -> String {
+        let headers = Signer::new(path, keyring.active())
+            .insert_identity(HeaderMap::new())
+            .unwrap();
+        headers["x-restate-jwt-v1"].to_str().unwrap().to_owned()
+    }
+
+    /// Verifies the jwt like a service endpoint would, picking the key by the `kid` header.
+    fn verify(jwks: &JsonWebKeySet, jwt: &str, path: &str) -> Result<(), String> {
+        let kid = jsonwebtoken::decode_header(jwt)
+            .unwrap()
+            .kid
+            .expect("kid must be present");
+        let jwk = jwks
+            .keys
+            .iter()
+            .find(|jwk| jwk.kid == kid)
+            .ok_or_else(|| format!("unknown key {kid}"))?;
+        let decoding_key = jsonwebtoken::DecodingKey::from_ed_components(&jwk.x).unwrap();
+
+        let mut validate = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::EdDSA);
+        validate.required_spec_claims =
+            HashSet::from(["aud".into(), "exp".into(), "iat".into(), "nbf".into()]);
+        validate.set_audience(&[path]);
+
+        let decoded = jsonwebtoken::decode::<Claims>(jwt, &decoding_key, &validate)
+            .map_err(|err| err.to_string())?;
+        assert_eq!(decoded.claims.aud, path);
+        Ok(())
+    }
+
+    #[test]
+    fn jwks_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = generate_key(dir.path(), "key");
+
+        let keyring = Keyring::from_pem_files(key.clone(), [key]).unwrap();
+        let jwks = serde_json::to_value(keyring.jwks()).unwrap();
+
+        let keys = jwks["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0]["kty"], "OKP");
+        assert_eq!(keys[0]["crv"], "Ed25519");
+        assert_eq!(keys[0]["use"], "sig");
+        assert_eq!(keys[0]["kid"], keyring.active().kid());
+    }
+
+    #[test]
+    fn rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_key = generate_key(dir.path(), "old");
+        let new_key = generate_key(dir.path(), "new");
+
+        // before the rotation, the new key is announced as grace period key
+        let before = Keyring::from_pem_files(old_key.clone(), [new_key.clone()]).unwrap();
+        let old_jwt = sign(&before, "/invoke/Greeter/greet");
+        verify(&before.jwks(), &old_jwt, "/invoke/Greeter/greet").unwrap();
+
+        // after the rotation, the old key remains as grace period key
+        let after = Keyring::from_pem_files(new_key.clone(), [old_key]).unwrap();
+        let new_jwt = sign(&after, "/invoke/Greeter/greet");
+        assert_ne!(
+            jsonwebtoken::decode_header(&old_jwt).unwrap().kid,
+            jsonwebtoken::decode_header(&new_jwt).unwrap().kid
+        );
+
+        // endpoints that fetched the keys before the rotation accept the new signatures, and
+        // endpoints that fetch them after the rotation still accept the in-flight old ones
+        verify(&before.jwks(), &new_jwt, "/invoke/Greeter/greet").unwrap();
+        verify(&after.jwks(), &new_jwt, "/invoke/Greeter/greet").unwrap();
+        verify(&after.jwks(), &old_jwt, "/invoke/Greeter/greet").unwrap();
+
+        // once the old key is dropped, its signatures are rejected
+        let dropped = Keyring::from_pem_files(new_key, []).unwrap();
+        assert!(verify(&dropped.jwks(), &old_jwt, "/invoke/Greeter/greet").is_err());
+        verify(&dropped.jwks(), &new_jwt, "/invoke/Greeter/greet").unwrap();
+    }
+}
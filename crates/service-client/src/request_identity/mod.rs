@@ -12,6 +12,7 @@ use hyper::header::HeaderName;
 
 use hyper::HeaderMap;
 
+pub(crate) mod keyring;
 pub(crate) mod v1;
 
 const SCHEME_HEADER: HeaderName = HeaderName::from_static("x-restate-signature-scheme");
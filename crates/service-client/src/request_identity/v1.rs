@@ -23,6 +23,7 @@ use tracing::info;
 pub(crate) struct SigningKey {
     header: jsonwebtoken::Header,
     key: jsonwebtoken::EncodingKey,
+    public_key: Vec<u8>,
 }
 
 impl Debug for SigningKey {
@@ -66,8 +67,21 @@ impl SigningKey {
                 ..Default::default()
             },
             key,
+            public_key: keypair.public_key().as_ref().to_vec(),
         })
     }
+
+    pub(crate) fn kid(&self) -> &str {
+        self.header
+            .kid
+            .as_deref()
+            .expect("signing keys always have a kid")
+    }
+
+    /// The raw ed25519 public key.
+    pub(crate) fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,345 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Resolution of the secret references in the deployment headers, such as
+//! `Bearer ${secret:greeter-token}` or `${env:GREETER_API_KEY}`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use hyper::HeaderMap;
+use hyper::header::{HeaderValue, InvalidHeaderValue};
+use tracing::{info, warn};
+
+const SECRET_PREFIX: &str = "secret:";
+const ENV_PREFIX: &str = "env:";
+/// How often the modification time of the secrets file is checked.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsError {
+    #[error("failed to read secrets file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("failed to parse secrets file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("header references the secret '{0}', which is not defined in the secrets file")]
+    UnknownSecret(String),
+    #[error("header references the environment variable '{0}', which is not set")]
+    UnknownEnv(String),
+    #[error(
+        "header references the environment variable '{0}', which doesn't have the configured secrets environment prefix"
+    )]
+    EnvNotAllowed(String),
+    #[error("header contains the invalid secret reference '{0}'")]
+    InvalidReference(String),
+    #[error("header value resolved from secrets is invalid: {0}")]
+    InvalidValue(#[from] InvalidHeaderValue),
+}
+
+/// Resolves the secret references in the request headers. The modification time of the secrets
+/// file is checked every [`RELOAD_CHECK_INTERVAL`], and the file is re-read when it changed, so
+/// secrets can be rotated without restarting. Environment variables can only be referenced if
+/// they have the configured prefix.
+#[derive(Debug)]
+pub(crate) struct SecretResolver {
+    path: Option<PathBuf>,
+    env_prefix: Option<String>,
+    check_interval: Duration,
+    state: Mutex<SecretsState>,
+}
+
+#[derive(Debug)]
+struct SecretsState {
+    secrets: Arc<HashMap<String, String>>,
+    modified: Option<SystemTime>,
+    next_check: Instant,
+}
+
+impl SecretResolver {
+    pub(crate) fn new(
+        path: Option<PathBuf>,
+        env_prefix: Option<String>,
+    ) -> Result<Self, SecretsError> {
+        let mut state = SecretsState {
+            secrets: Arc::default(),
+            modified: None,
+            next_check: Instant::now() + RELOAD_CHECK_INTERVAL,
+        };
+        if let Some(path) = &path {
+            state.modified = modified_time(path);
+            state.secrets = Arc::new(read_secrets(path)?);
+            info!(
+                path = %path.display(),
+                "Loaded {} deployment secrets",
+                state.secrets.len()
+            );
+        }
+
+        Ok(Self {
+            path,
+            env_prefix,
+            check_interval: RELOAD_CHECK_INTERVAL,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Replaces the secret references in the header values with the secrets they reference.
+    pub(crate) fn resolve_headers(
+        &self,
+        mut headers: HeaderMap,
+    ) -> Result<HeaderMap, SecretsError> {
+        if !headers
+            .values()
+            .any(|value| value.as_bytes().windows(2).any(|w| w == b"${"))
+        {
+            return Ok(headers);
+        }
+
+        let secrets = self.secrets();
+        for value in headers.values_mut() {
+            if let Ok(raw) = value.to_str() {
+                if raw.contains("${") {
+                    let mut resolved =
+                        HeaderValue::try_from(resolve(raw, &secrets, self.env_prefix.as_deref())?)?;
+                    resolved.set_sensitive(true);
+                    *value = resolved;
+                }
+            }
+        }
+        Ok(headers)
+    }
+
+    fn secrets(&self) -> Arc<HashMap<String, String>> {
+        let mut state = self.state.lock().unwrap();
+        let Some(path) = &self.path else {
+            return Arc::clone(&state.secrets);
+        };
+        let now = Instant::now();
+        if now < state.next_check {
+            return Arc::clone(&state.secrets);
+        }
+        state.next_check = now + self.check_interval;
+
+        let modified = modified_time(path);
+        if modified != state.modified {
+            match read_secrets(path) {
+                Ok(secrets) => {
+                    info!(
+                        path = %path.display(),
+                        "Reloaded {} deployment secrets",
+                        secrets.len()
+                    );
+                    state.secrets = Arc::new(secrets);
+                    state.modified = modified;
+                }
+                Err(err) => {
+                    // keep the previous secrets, the file might be in the middle of being written
+                    warn!("Failed to reload deployment secrets, keeping the previous ones: {err}");
+                }
+            }
+        }
+        Arc::clone(&state.secrets)
+    }
+}
+
+fn resolve(
+    raw: &str,
+    secrets: &HashMap<String, String>,
+    env_prefix: Option<&str>,
+) -> Result<String, SecretsError> {
+    let mut resolved = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find("${") {
+        resolved.push_str(&rest[..start]);
+        let reference = &rest[start + 2..];
+        let end = reference
+            .find('}')
+            .ok_or_else(|| SecretsError::InvalidReference(rest[start..].to_owned()))?;
+        let reference = &reference[..end];
+
+        if let Some(name) = reference.strip_prefix(SECRET_PREFIX) {
+            resolved.push_str(
+                secrets
+                    .get(name)
+                    .ok_or_else(|| SecretsError::UnknownSecret(name.to_owned()))?,
+            );
+        } else if let Some(name) = reference.strip_prefix(ENV_PREFIX) {
+            if !env_prefix.is_some_and(|prefix| name.starts_with(prefix)) {
+                return Err(SecretsError::EnvNotAllowed(name.to_owned()));
+            }
+            resolved.push_str(
+                &std::env::var(name).map_err(|_| SecretsError::UnknownEnv(name.to_owned()))?,
+            );
+        } else {
+            return Err(SecretsError::InvalidReference(format!("${{{reference}}}")));
+        }
+
+        rest = &rest[start + 2 + end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+fn read_secrets(path: &Path) -> Result<HashMap<String, String>, SecretsError> {
+    let contents = std::fs::read(path).map_err(|source| SecretsError::Read {
+        path: path.to_owned(),
+        source,
+    })?;
+    serde_json::from_slice(&contents).map_err(|source| SecretsError::Parse {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+    use std::time::Duration;
+
+    use hyper::header::AUTHORIZATION;
+
+    fn write_secrets(path: &Path, contents: &str, modified: SystemTime) {
+        std::fs::write(path, contents).unwrap();
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn headers(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn resolve_references() {
+        const ENV: Option<&str> = Some("RESTATE_TEST_DEPLOYMENT_SECRET");
+        let secrets = HashMap::from([("token".to_owned(), "s3cr3t".to_owned())]);
+        // SAFETY: the variable is only used by this test
+        unsafe { std::env::set_var("RESTATE_TEST_DEPLOYMENT_SECRET", "from-env") };
+
+        assert_eq!(resolve("plain", &secrets, ENV).unwrap(), "plain");
+        assert_eq!(
+            resolve("Bearer ${secret:token}", &secrets, ENV).unwrap(),
+            "Bearer s3cr3t"
+        );
+        assert_eq!(
+            resolve(
+                "${env:RESTATE_TEST_DEPLOYMENT_SECRET}-${secret:token}",
+                &secrets,
+                ENV
+            )
+            .unwrap(),
+            "from-env-s3cr3t"
+        );
+        assert!(matches!(
+            resolve("${secret:unknown}", &secrets, ENV),
+            Err(SecretsError::UnknownSecret(name)) if name == "unknown"
+        ));
+        assert!(matches!(
+            resolve("${env:RESTATE_TEST_DEPLOYMENT_SECRET_UNSET}", &secrets, ENV),
+            Err(SecretsError::UnknownEnv(_))
+        ));
+        assert!(matches!(
+            resolve("${token}", &secrets, ENV),
+            Err(SecretsError::InvalidReference(_))
+        ));
+        assert!(matches!(
+            resolve("Bearer ${secret:token", &secrets, ENV),
+            Err(SecretsError::InvalidReference(_))
+        ));
+    }
+
+    #[test]
+    fn reject_env_references_without_prefix() {
+        let secrets = HashMap::new();
+        // SAFETY: the variable is only used by this test
+        unsafe { std::env::set_var("RESTATE_TEST_OTHER_SECRET", "from-env") };
+
+        assert!(matches!(
+            resolve("${env:RESTATE_TEST_OTHER_SECRET}", &secrets, None),
+            Err(SecretsError::EnvNotAllowed(name)) if name == "RESTATE_TEST_OTHER_SECRET"
+        ));
+        assert!(matches!(
+            resolve(
+                "${env:RESTATE_TEST_OTHER_SECRET}",
+                &secrets,
+                Some("RESTATE_TEST_DEPLOYMENT_")
+            ),
+            Err(SecretsError::EnvNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn reload_secrets_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.json");
+        let now = SystemTime::now();
+        write_secrets(&path, r#"{"token": "old"}"#, now);
+
+        let mut resolver = SecretResolver::new(Some(path.clone()), None).unwrap();
+        resolver.check_interval = Duration::ZERO;
+        resolver.state.get_mut().unwrap().next_check = Instant::now();
+        let resolved = resolver
+            .resolve_headers(headers("Bearer ${secret:token}"))
+            .unwrap();
+        assert_eq!(resolved[AUTHORIZATION], "Bearer old");
+        assert!(resolved[AUTHORIZATION].is_sensitive());
+
+        write_secrets(&path, r#"{"token": "new"}"#, now + Duration::from_secs(1));
+        let resolved = resolver
+            .resolve_headers(headers("Bearer ${secret:token}"))
+            .unwrap();
+        assert_eq!(resolved[AUTHORIZATION], "Bearer new");
+
+        // a broken file keeps the previous secrets
+        write_secrets(&path, "{", now + Duration::from_secs(2));
+        let resolved = resolver
+            .resolve_headers(headers("Bearer ${secret:token}"))
+            .unwrap();
+        assert_eq!(resolved[AUTHORIZATION], "Bearer new");
+    }
+
+    #[test]
+    fn throttle_secrets_file_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.json");
+        let now = SystemTime::now();
+        write_secrets(&path, r#"{"token": "old"}"#, now);
+
+        let resolver = SecretResolver::new(Some(path.clone()), None).unwrap();
+        write_secrets(&path, r#"{"token": "new"}"#, now + Duration::from_secs(1));
+
+        // the file is checked again only after the check interval
+        let resolved = resolver
+            .resolve_headers(headers("Bearer ${secret:token}"))
+            .unwrap();
+        assert_eq!(resolved[AUTHORIZATION], "Bearer old");
+    }
+}
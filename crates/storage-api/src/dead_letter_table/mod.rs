@@ -0,0 +1,62 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::Result;
+
+use bytes::Bytes;
+use bytestring::ByteString;
+use futures_util::Stream;
+use restate_types::errors::InvocationError;
+use restate_types::identifiers::{InvocationId, PartitionKey};
+use restate_types::invocation::{Header, InvocationTarget, Source};
+use restate_types::time::MillisSinceEpoch;
+use std::future::Future;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Request of a terminally failed invocation, retained so it can be redriven or discarded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub invocation_target: InvocationTarget,
+    pub source: Source,
+    pub headers: Vec<Header>,
+    pub argument: Bytes,
+    pub failure: InvocationError,
+    pub failed_at: MillisSinceEpoch,
+    /// Idempotency key and retention of the failed invocation, applied again when redriving it.
+    pub idempotency_key: Option<ByteString>,
+    pub completion_retention_duration: Duration,
+    pub journal_retention_duration: Duration,
+}
+
+pub trait ReadOnlyDeadLetterTable {
+    fn get_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> impl Future<Output = Result<Option<DeadLetter>>> + Send;
+
+    fn all_dead_letters(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<(InvocationId, DeadLetter)>> + Send>;
+}
+
+pub trait DeadLetterTable: ReadOnlyDeadLetterTable {
+    fn put_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+        dead_letter: &DeadLetter,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    fn delete_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
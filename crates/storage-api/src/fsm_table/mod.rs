@@ -9,8 +9,10 @@
 // by the Apache License, Version 2.0.
 
 use crate::Result;
+use crate::outbox_table::InheritedOutboxMessages;
 use restate_types::logs::Lsn;
 use restate_types::message::MessageIndex;
+use std::collections::BTreeSet;
 use std::future::Future;
 
 pub trait ReadOnlyFsmTable {
@@ -19,6 +21,15 @@ pub trait ReadOnlyFsmTable {
     fn get_outbox_seq_number(&mut self) -> impl Future<Output = Result<MessageIndex>> + Send + '_;
 
     fn get_applied_lsn(&mut self) -> impl Future<Output = Result<Option<Lsn>>> + Send + '_;
+
+    fn get_inherited_outbox_messages(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<InheritedOutboxMessages>>> + Send + '_;
+
+    /// Services with a state TTL, whose state entries' writes are tracked to expire them.
+    fn get_state_ttl_services(
+        &mut self,
+    ) -> impl Future<Output = Result<BTreeSet<String>>> + Send + '_;
 }
 
 pub trait FsmTable: ReadOnlyFsmTable {
@@ -33,4 +44,14 @@ pub trait FsmTable: ReadOnlyFsmTable {
         &mut self,
         seq_number: MessageIndex,
     ) -> impl Future<Output = Result<()>> + Send;
+
+    fn put_inherited_outbox_messages(
+        &mut self,
+        inherited: Vec<InheritedOutboxMessages>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    fn put_state_ttl_services(
+        &mut self,
+        services: BTreeSet<String>,
+    ) -> impl Future<Output = Result<()>> + Send;
 }
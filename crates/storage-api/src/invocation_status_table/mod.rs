@@ -16,11 +16,14 @@ use rangemap::RangeInclusiveMap;
 use restate_types::deployment::PinnedDeployment;
 use restate_types::identifiers::{InvocationId, PartitionKey};
 use restate_types::invocation::{
-    Header, InvocationEpoch, InvocationInput, InvocationTarget, ResponseResult, ServiceInvocation,
-    ServiceInvocationResponseSink, ServiceInvocationSpanContext, Source,
+    Header, InvocationEpoch, InvocationInput, InvocationTarget, OnMaxAttemptsAction,
+    ResponseResult, ServiceInvocation, ServiceInvocationResponseSink, ServiceInvocationSpanContext,
+    Source,
 };
 use restate_types::journal_v2::{CompletionId, EntryIndex, NotificationId};
+use restate_types::service_protocol::ServiceProtocolVersion;
 use restate_types::time::MillisSinceEpoch;
+use std::cmp;
 use std::collections::HashSet;
 use std::future::Future;
 use std::ops::RangeInclusive;
@@ -191,6 +194,8 @@ pub enum InvocationStatus {
         metadata: InFlightInvocationMetadata,
         waiting_for_notifications: HashSet<NotificationId>,
     },
+    /// Invocation was paused by the user, and won't be dispatched to the invoker until resumed
+    Paused(InFlightInvocationMetadata),
     Completed(CompletedInvocation),
     /// Service instance is currently not invoked
     #[default]
@@ -205,6 +210,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.invocation_target),
             InvocationStatus::Invoked(metadata) => Some(&metadata.invocation_target),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.invocation_target),
+            InvocationStatus::Paused(metadata) => Some(&metadata.invocation_target),
             InvocationStatus::Completed(completed) => Some(&completed.invocation_target),
             _ => None,
         }
@@ -217,6 +223,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.source),
             InvocationStatus::Invoked(metadata) => Some(&metadata.source),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.source),
+            InvocationStatus::Paused(metadata) => Some(&metadata.source),
             InvocationStatus::Completed(completed) => Some(&completed.source),
             _ => None,
         }
@@ -238,6 +245,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => metadata.metadata.idempotency_key.as_ref(),
             InvocationStatus::Invoked(metadata) => metadata.idempotency_key.as_ref(),
             InvocationStatus::Suspended { metadata, .. } => metadata.idempotency_key.as_ref(),
+            InvocationStatus::Paused(metadata) => metadata.idempotency_key.as_ref(),
             InvocationStatus::Completed(completed) => completed.idempotency_key.as_ref(),
             _ => None,
         }
@@ -248,6 +256,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata.journal_metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata.journal_metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata.journal_metadata),
             _ => None,
         }
     }
@@ -257,6 +266,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(&metadata.journal_metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.journal_metadata),
+            InvocationStatus::Paused(metadata) => Some(&metadata.journal_metadata),
             _ => None,
         }
     }
@@ -266,6 +276,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(&mut metadata.journal_metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(&mut metadata.journal_metadata),
+            InvocationStatus::Paused(metadata) => Some(&mut metadata.journal_metadata),
             _ => None,
         }
     }
@@ -275,6 +286,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata),
             _ => None,
         }
     }
@@ -284,6 +296,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata),
             _ => None,
         }
     }
@@ -293,6 +306,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata),
             _ => None,
         }
     }
@@ -306,6 +320,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&mut metadata.metadata.response_sinks),
             InvocationStatus::Invoked(metadata) => Some(&mut metadata.response_sinks),
             InvocationStatus::Suspended { metadata, .. } => Some(&mut metadata.response_sinks),
+            InvocationStatus::Paused(metadata) => Some(&mut metadata.response_sinks),
             _ => None,
         }
     }
@@ -317,6 +332,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.response_sinks),
             InvocationStatus::Invoked(metadata) => Some(&metadata.response_sinks),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.response_sinks),
+            InvocationStatus::Paused(metadata) => Some(&metadata.response_sinks),
             _ => None,
         }
     }
@@ -328,6 +344,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.timestamps),
             InvocationStatus::Invoked(metadata) => Some(&metadata.timestamps),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.timestamps),
+            InvocationStatus::Paused(metadata) => Some(&metadata.timestamps),
             InvocationStatus::Completed(completed) => Some(&completed.timestamps),
             _ => None,
         }
@@ -340,6 +357,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&mut metadata.metadata.timestamps),
             InvocationStatus::Invoked(metadata) => Some(&mut metadata.timestamps),
             InvocationStatus::Suspended { metadata, .. } => Some(&mut metadata.timestamps),
+            InvocationStatus::Paused(metadata) => Some(&mut metadata.timestamps),
             InvocationStatus::Completed(completed) => Some(&mut completed.timestamps),
             _ => None,
         }
@@ -352,6 +370,7 @@ pub enum InvocationStatusDiscriminants {
     Inboxed,
     Invoked,
     Suspended,
+    Paused,
     Killed,
     Completed,
 }
@@ -401,7 +420,11 @@ pub struct PreFlightInvocationMetadata {
     pub execution_time: Option<MillisSinceEpoch>,
     /// If zero, the invocation completion will not be retained.
     pub completion_retention_duration: Duration,
+    /// If zero, the journal will be dropped once the invocation completes.
+    pub journal_retention_duration: Duration,
     pub idempotency_key: Option<ByteString>,
+    /// If true, the request is stored in the dead letter queue when the invocation terminally fails.
+    pub dead_letter_queue: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -431,7 +454,11 @@ impl PreFlightInvocationMetadata {
             completion_retention_duration: service_invocation
                 .completion_retention_duration
                 .unwrap_or_default(),
+            journal_retention_duration: service_invocation
+                .journal_retention_duration
+                .unwrap_or_default(),
             idempotency_key: service_invocation.idempotency_key,
+            dead_letter_queue: service_invocation.dead_letter_queue,
         }
     }
 }
@@ -543,11 +570,17 @@ pub struct InFlightInvocationMetadata {
     pub source: Source,
     /// If zero, the invocation completion will not be retained.
     pub completion_retention_duration: Duration,
+    /// If zero, the journal will be dropped once the invocation completes.
+    pub journal_retention_duration: Duration,
     pub idempotency_key: Option<ByteString>,
     // TODO remove this when we remove protocol <= v3
     pub hotfix_apply_cancellation_after_deployment_is_pinned: bool,
     pub current_invocation_epoch: InvocationEpoch,
     pub completion_range_epoch_map: CompletionRangeEpochMap,
+    /// Action applied the last time the invoker exhausted the retries of this invocation, if any.
+    pub retries_exhausted_action: Option<OnMaxAttemptsAction>,
+    /// If true, the request is stored in the dead letter queue when the invocation terminally fails.
+    pub dead_letter_queue: bool,
 }
 
 impl InFlightInvocationMetadata {
@@ -570,10 +603,14 @@ impl InFlightInvocationMetadata {
                 source: pre_flight_invocation_metadata.source,
                 completion_retention_duration: pre_flight_invocation_metadata
                     .completion_retention_duration,
+                journal_retention_duration: pre_flight_invocation_metadata
+                    .journal_retention_duration,
                 idempotency_key: pre_flight_invocation_metadata.idempotency_key,
                 hotfix_apply_cancellation_after_deployment_is_pinned: false,
                 current_invocation_epoch: 0,
                 completion_range_epoch_map: Default::default(),
+                retries_exhausted_action: None,
+                dead_letter_queue: pre_flight_invocation_metadata.dead_letter_queue,
             },
             InvocationInput {
                 argument: pre_flight_invocation_metadata.argument,
@@ -588,6 +625,28 @@ impl InFlightInvocationMetadata {
         Self::from_pre_flight_invocation_metadata(inboxed_invocation.metadata)
     }
 
+    /// The journal can be retained only when using the journal table v2, that is with service protocol >= V4.
+    pub fn should_retain_journal(&self) -> bool {
+        !self.journal_retention_duration.is_zero()
+            && self
+                .pinned_deployment
+                .as_ref()
+                .is_some_and(|pd| pd.service_protocol_version >= ServiceProtocolVersion::V4)
+    }
+
+    /// Retention of the completed status. When the journal is retained, the completed status
+    /// is retained at least as long as the journal.
+    pub fn effective_completion_retention_duration(&self) -> Duration {
+        if self.should_retain_journal() {
+            cmp::max(
+                self.completion_retention_duration,
+                self.journal_retention_duration,
+            )
+        } else {
+            self.completion_retention_duration
+        }
+    }
+
     pub fn set_pinned_deployment(&mut self, pinned_deployment: PinnedDeployment) {
         debug_assert_eq!(
             self.pinned_deployment, None,
@@ -607,6 +666,14 @@ pub struct CompletedInvocation {
     pub timestamps: StatusTimestamps,
     pub response_result: ResponseResult,
     pub completion_retention_duration: Duration,
+    /// Retention of the journal, zero if the journal was not retained.
+    pub journal_retention_duration: Duration,
+    /// Metadata of the retained journal. The journal length is zero if the journal was not retained.
+    pub journal_metadata: JournalMetadata,
+    /// Deployment used by the invocation, if any.
+    pub pinned_deployment: Option<PinnedDeployment>,
+    /// Action applied the last time the invoker exhausted the retries of this invocation, if any.
+    pub retries_exhausted_action: Option<OnMaxAttemptsAction>,
 }
 
 impl CompletedInvocation {
@@ -618,6 +685,26 @@ impl CompletedInvocation {
             .timestamps
             .record_completed_transition_time();
 
+        let completion_retention_duration =
+            in_flight_invocation_metadata.effective_completion_retention_duration();
+        let (journal_metadata, journal_retention_duration) =
+            if in_flight_invocation_metadata.should_retain_journal() {
+                (
+                    in_flight_invocation_metadata.journal_metadata.clone(),
+                    in_flight_invocation_metadata.journal_retention_duration,
+                )
+            } else {
+                (
+                    JournalMetadata::initialize(
+                        in_flight_invocation_metadata
+                            .journal_metadata
+                            .span_context
+                            .clone(),
+                    ),
+                    Duration::ZERO,
+                )
+            };
+
         Self {
             invocation_target: in_flight_invocation_metadata.invocation_target,
             span_context: in_flight_invocation_metadata.journal_metadata.span_context,
@@ -625,11 +712,19 @@ impl CompletedInvocation {
             idempotency_key: in_flight_invocation_metadata.idempotency_key,
             timestamps: in_flight_invocation_metadata.timestamps,
             response_result,
-            completion_retention_duration: in_flight_invocation_metadata
-                .completion_retention_duration,
+            completion_retention_duration,
+            journal_retention_duration,
+            journal_metadata,
+            pinned_deployment: in_flight_invocation_metadata.pinned_deployment,
+            retries_exhausted_action: in_flight_invocation_metadata.retries_exhausted_action,
         }
     }
 
+    /// Returns true if the journal of this invocation was retained after completion.
+    pub fn has_retained_journal(&self) -> bool {
+        self.journal_metadata.length > 0
+    }
+
     /// Expiration time of the [`InvocationStatus::Completed`], if any.
     ///
     /// # Safety
@@ -699,10 +794,13 @@ mod test_util {
                 timestamps: StatusTimestamps::now(),
                 source: Source::Ingress(PartitionProcessorRpcRequestId::default()),
                 completion_retention_duration: Duration::ZERO,
+                journal_retention_duration: Duration::ZERO,
                 idempotency_key: None,
                 hotfix_apply_cancellation_after_deployment_is_pinned: false,
                 current_invocation_epoch: 0,
                 completion_range_epoch_map: Default::default(),
+                retries_exhausted_action: None,
+                dead_letter_queue: false,
             }
         }
     }
@@ -726,6 +824,12 @@ mod test_util {
                 timestamps,
                 response_result: ResponseResult::Success(Bytes::from_static(b"123")),
                 completion_retention_duration: Duration::from_secs(60 * 60),
+                journal_retention_duration: Duration::ZERO,
+                journal_metadata: JournalMetadata::initialize(
+                    ServiceInvocationSpanContext::default(),
+                ),
+                pinned_deployment: None,
+                retries_exhausted_action: None,
             }
         }
 
@@ -743,6 +847,12 @@ mod test_util {
                 timestamps: StatusTimestamps::now(),
                 response_result: ResponseResult::Success(Bytes::from_static(b"123")),
                 completion_retention_duration: Duration::from_secs(60 * 60),
+                journal_retention_duration: Duration::ZERO,
+                journal_metadata: JournalMetadata::initialize(
+                    ServiceInvocationSpanContext::default(),
+                ),
+                pinned_deployment: None,
+                retries_exhausted_action: None,
             }
         }
     }
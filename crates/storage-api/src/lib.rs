@@ -25,6 +25,7 @@ pub enum StorageError {
 
 pub type Result<T> = std::result::Result<T, StorageError>;
 
+pub mod dead_letter_table;
 pub mod deduplication_table;
 pub mod fsm_table;
 pub mod idempotency_table;
@@ -34,6 +35,7 @@ pub mod journal_table;
 pub mod journal_table_v2;
 pub mod outbox_table;
 pub mod promise_table;
+pub mod schedule_table;
 pub mod service_status_table;
 pub mod state_table;
 pub mod timer_table;
@@ -85,6 +87,8 @@ pub trait Transaction:
     + timer_table::TimerTable
     + idempotency_table::IdempotencyTable
     + promise_table::PromiseTable
+    + dead_letter_table::DeadLetterTable
+    + schedule_table::ScheduleTable
     + Send
 {
     fn commit(self) -> impl Future<Output = Result<()>> + Send;
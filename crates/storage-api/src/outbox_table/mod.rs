@@ -9,10 +9,11 @@
 // by the Apache License, Version 2.0.
 
 use crate::Result;
-use restate_types::identifiers::{PartitionKey, WithPartitionKey};
+use bytes::Bytes;
+use restate_types::identifiers::{InvocationId, PartitionId, PartitionKey, WithPartitionKey};
 use restate_types::invocation::{
     AttachInvocationRequest, InvocationResponse, InvocationTermination, NotifySignalRequest,
-    ServiceInvocation,
+    ResponseResult, ServiceInvocation,
 };
 use std::future::Future;
 use std::ops::RangeInclusive;
@@ -34,6 +35,49 @@ pub enum OutboxMessage {
 
     /// Notify signal request
     NotifySignal(NotifySignalRequest),
+
+    /// Output of an invocation to publish to a Kafka topic
+    KafkaRecord(KafkaRecord),
+}
+
+/// Record published to Kafka by the leader of the partition, see
+/// [`restate_types::invocation::KafkaSink`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KafkaRecord {
+    /// Invocation that produced the record.
+    pub invocation_id: InvocationId,
+    pub cluster: String,
+    pub topic: String,
+    pub key: Option<Bytes>,
+    pub result: ResponseResult,
+}
+
+/// Range of outbox messages inherited from a partition which has been split or merged. They are
+/// shipped with the producer id and the sequence numbers of the partition that produced them, so
+/// that the receivers drop those which that partition shipped before its log was sealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InheritedOutboxMessages {
+    /// Index in the outbox of the first inherited message.
+    pub first_index: u64,
+    /// Index in the outbox of the last inherited message.
+    pub last_index: u64,
+    pub producer_id: PartitionId,
+    /// Sequence number of the first inherited message in the outbox of the producer.
+    pub producer_first_seq_number: u64,
+}
+
+impl InheritedOutboxMessages {
+    /// Returns the producer and its sequence number of the message at `index`, if inherited.
+    pub fn producer_of(&self, index: u64) -> Option<(PartitionId, u64)> {
+        (self.first_index..=self.last_index)
+            .contains(&index)
+            .then(|| {
+                (
+                    self.producer_id,
+                    self.producer_first_seq_number + (index - self.first_index),
+                )
+            })
+    }
 }
 
 impl WithPartitionKey for OutboxMessage {
@@ -44,6 +88,7 @@ impl WithPartitionKey for OutboxMessage {
             OutboxMessage::InvocationTermination(it) => it.invocation_id.partition_key(),
             OutboxMessage::AttachInvocation(ai) => ai.partition_key(),
             OutboxMessage::NotifySignal(sig) => sig.partition_key(),
+            OutboxMessage::KafkaRecord(record) => record.invocation_id.partition_key(),
         }
     }
 }
@@ -0,0 +1,51 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::Result;
+
+use futures_util::Stream;
+use restate_types::identifiers::{PartitionKey, ScheduleId};
+use restate_types::schedule::Schedule;
+use restate_types::time::MillisSinceEpoch;
+use std::future::Future;
+use std::ops::RangeInclusive;
+
+/// Schedule owned by this partition, together with its firing state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleStatus {
+    pub schedule: Schedule,
+    /// Wake up time of the registered schedule timer, if any occurrence is left.
+    pub next_fire_at: Option<MillisSinceEpoch>,
+    pub last_fired_at: Option<MillisSinceEpoch>,
+}
+
+pub trait ReadOnlyScheduleTable {
+    fn get_schedule(
+        &mut self,
+        schedule_id: &ScheduleId,
+    ) -> impl Future<Output = Result<Option<ScheduleStatus>>> + Send;
+
+    fn all_schedules(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<ScheduleStatus>> + Send>;
+}
+
+pub trait ScheduleTable: ReadOnlyScheduleTable {
+    fn put_schedule(
+        &mut self,
+        schedule_status: &ScheduleStatus,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    fn delete_schedule(
+        &mut self,
+        schedule_id: &ScheduleId,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
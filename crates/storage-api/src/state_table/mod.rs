@@ -12,9 +12,21 @@ use crate::Result;
 use bytes::Bytes;
 use futures_util::Stream;
 use restate_types::identifiers::{PartitionKey, ServiceId};
+use restate_types::time::MillisSinceEpoch;
 use std::future::Future;
 use std::ops::RangeInclusive;
 
+/// Metadata of a state entry, written next to it for the services with a state TTL and used to
+/// expire their state. It is deleted together with the entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateEntryMetadata {
+    /// LSN of the command which last wrote the entry.
+    pub version: u64,
+    /// Creation time of the record of the command which last wrote the entry, the same on all
+    /// the replicas.
+    pub modified_at: MillisSinceEpoch,
+}
+
 pub trait ReadOnlyStateTable {
     fn get_user_state(
         &mut self,
@@ -35,6 +47,17 @@ pub trait ReadOnlyStateTable {
         &self,
         range: RangeInclusive<PartitionKey>,
     ) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, Bytes)>> + Send>;
+
+    fn get_user_state_metadata(
+        &mut self,
+        service_id: &ServiceId,
+        state_key: impl AsRef<[u8]> + Send,
+    ) -> impl Future<Output = Result<Option<StateEntryMetadata>>> + Send;
+
+    fn get_all_user_state_metadata_in_range(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = Result<(ServiceId, Bytes, StateEntryMetadata)>> + Send>;
 }
 
 pub trait StateTable: ReadOnlyStateTable {
@@ -45,6 +68,13 @@ pub trait StateTable: ReadOnlyStateTable {
         state_value: impl AsRef<[u8]> + Send,
     ) -> impl Future<Output = Result<()>> + Send;
 
+    fn put_user_state_metadata(
+        &mut self,
+        service_id: &ServiceId,
+        state_key: impl AsRef<[u8]> + Send,
+        metadata: StateEntryMetadata,
+    ) -> impl Future<Output = Result<()>> + Send;
+
     fn delete_user_state(
         &mut self,
         service_id: &ServiceId,
@@ -83,12 +83,14 @@ const SYS_INVOCATION_VIEW: &str = "CREATE VIEW sys_invocation as SELECT
                 WHEN ss.status = 'scheduled' THEN 'scheduled'
                 WHEN ss.status = 'completed' THEN 'completed'
                 WHEN ss.status = 'suspended' THEN 'suspended'
+                WHEN ss.status = 'paused' THEN 'paused'
                 WHEN sis.in_flight THEN 'running'
                 WHEN ss.status = 'invoked' AND retry_count > 0 THEN 'backing-off'
                 ELSE 'ready'
             END, 'LargeUtf8') AS status,
             ss.completion_result,
-            ss.completion_failure
+            ss.completion_failure,
+            ss.retries_exhausted_action
         FROM sys_invocation_status ss
         LEFT JOIN sys_invocation_state sis ON ss.id = sis.id";
 
@@ -203,6 +205,18 @@ where
             self.local_partition_store_manager.clone(),
             &self.remote_scanner_manager,
         )?;
+        crate::dead_letter::register_self(
+            ctx,
+            self.partition_selector.clone(),
+            self.local_partition_store_manager.clone(),
+            &self.remote_scanner_manager,
+        )?;
+        crate::schedule::register_self(
+            ctx,
+            self.partition_selector.clone(),
+            self.local_partition_store_manager.clone(),
+            &self.remote_scanner_manager,
+        )?;
 
         ctx.datafusion_context.sql(SYS_INVOCATION_VIEW).await?;
 
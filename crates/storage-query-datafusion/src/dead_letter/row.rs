@@ -0,0 +1,77 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::schema::SysDeadLetterBuilder;
+
+use crate::table_util::format_using;
+use restate_storage_api::dead_letter_table::DeadLetter;
+use restate_types::identifiers::{InvocationId, WithPartitionKey};
+use restate_types::invocation::Source;
+
+#[inline]
+pub(crate) fn append_dead_letter_row(
+    builder: &mut SysDeadLetterBuilder,
+    output: &mut String,
+    invocation_id: InvocationId,
+    dead_letter: DeadLetter,
+) {
+    let mut row = builder.row();
+    row.partition_key(invocation_id.partition_key());
+
+    if row.is_id_defined() {
+        row.id(format_using(output, &invocation_id));
+    }
+
+    let invocation_target = dead_letter.invocation_target;
+    row.target_service_name(invocation_target.service_name());
+    if let Some(key) = invocation_target.key() {
+        row.target_service_key(key);
+    }
+    row.target_handler_name(invocation_target.handler_name());
+    if row.is_target_defined() {
+        row.target(format_using(output, &invocation_target));
+    }
+
+    match dead_letter.source {
+        Source::Service(invocation_id, _) => {
+            row.invoked_by("service");
+            if row.is_invoked_by_id_defined() {
+                row.invoked_by_id(format_using(output, &invocation_id));
+            }
+        }
+        Source::Ingress(_) => {
+            row.invoked_by("ingress");
+        }
+        Source::Internal => {
+            row.invoked_by("restate");
+        }
+        Source::Subscription(sub_id) => {
+            row.invoked_by("subscription");
+            if row.is_invoked_by_subscription_id_defined() {
+                row.invoked_by_subscription_id(format_using(output, &sub_id));
+            }
+        }
+    }
+
+    if row.is_payload_utf8_defined() {
+        if let Ok(str) = std::str::from_utf8(&dead_letter.argument) {
+            row.payload_utf8(str);
+        }
+    }
+    if row.is_payload_defined() {
+        row.payload(&dead_letter.argument);
+    }
+
+    row.failure_code(dead_letter.failure.code().into());
+    if row.is_failure_message_defined() {
+        row.failure_message(dead_letter.failure.message());
+    }
+    row.failed_at(dead_letter.failed_at.as_u64() as i64);
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+#![allow(dead_code)]
+
+use crate::table_macro::*;
+
+use datafusion::arrow::datatypes::DataType;
+
+define_sort_order!(sys_dead_letter(partition_key, id));
+
+define_table!(sys_dead_letter(
+    /// Internal column that is used for partitioning the services invocations. Can be ignored.
+    partition_key: DataType::UInt64,
+
+    /// [Invocation ID](/operate/invocation#invocation-identifier) of the failed invocation.
+    id: DataType::LargeUtf8,
+
+    /// Invocation Target. Format for plain services: `ServiceName/HandlerName`, e.g.
+    /// `Greeter/greet`. Format for virtual objects/workflows: `VirtualObjectName/Key/HandlerName`,
+    /// e.g. `Greeter/Francesco/greet`.
+    target: DataType::LargeUtf8,
+
+    /// The name of the invoked service.
+    target_service_name: DataType::LargeUtf8,
+
+    /// The key of the virtual object or the workflow ID. Null for regular services.
+    target_service_key: DataType::LargeUtf8,
+
+    /// The invoked handler.
+    target_handler_name: DataType::LargeUtf8,
+
+    /// Either:
+    /// * `ingress` if the invocation was created externally.
+    /// * `service` if the invocation was created by another Restate service.
+    /// * `subscription` if the invocation was created by a subscription (e.g. Kafka).
+    invoked_by: DataType::LargeUtf8,
+
+    /// The caller [Invocation ID](/operate/invocation#invocation-identifier) if `invoked_by = 'service'`.
+    invoked_by_id: DataType::LargeUtf8,
+
+    /// The subscription id if `invoked_by = 'subscription'`.
+    invoked_by_subscription_id: DataType::LargeUtf8,
+
+    /// Only contains meaningful values when the request payload is `utf8`, e.g. JSON.
+    payload_utf8: DataType::LargeUtf8,
+
+    /// A binary, uninterpreted representation of the request payload. You can use the more specific column
+    /// `payload_utf8` if the payload is a string.
+    payload: DataType::LargeBinary,
+
+    /// The error code of the terminal failure.
+    failure_code: DataType::UInt32,
+
+    /// The error message of the terminal failure.
+    failure_message: DataType::LargeUtf8,
+
+    /// Timestamp indicating when the invocation failed and the request was dead-lettered.
+    failed_at: TimestampMillisecond,
+));
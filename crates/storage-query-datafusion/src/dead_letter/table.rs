@@ -0,0 +1,78 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use futures::Stream;
+
+use restate_partition_store::{PartitionStore, PartitionStoreManager};
+use restate_storage_api::StorageError;
+use restate_storage_api::dead_letter_table::{DeadLetter, ReadOnlyDeadLetterTable};
+use restate_types::identifiers::{InvocationId, PartitionKey};
+
+use super::row::append_dead_letter_row;
+use super::schema::{SysDeadLetterBuilder, sys_dead_letter_sort_order};
+use crate::context::{QueryContext, SelectPartitions};
+use crate::partition_filter::FirstMatchingPartitionKeyExtractor;
+use crate::partition_store_scanner::{LocalPartitionsScanner, ScanLocalPartition};
+use crate::remote_query_scanner_manager::RemoteScannerManager;
+use crate::table_providers::{PartitionedTableProvider, ScanPartition};
+
+const NAME: &str = "sys_dead_letter";
+
+pub(crate) fn register_self(
+    ctx: &QueryContext,
+    partition_selector: impl SelectPartitions,
+    local_partition_store_manager: Option<PartitionStoreManager>,
+    remote_scanner_manager: &RemoteScannerManager,
+) -> datafusion::common::Result<()> {
+    let local_scanner = local_partition_store_manager.map(|partition_store_manager| {
+        Arc::new(LocalPartitionsScanner::new(
+            partition_store_manager,
+            DeadLetterScanner,
+        )) as Arc<dyn ScanPartition>
+    });
+    let table = PartitionedTableProvider::new(
+        partition_selector,
+        SysDeadLetterBuilder::schema(),
+        sys_dead_letter_sort_order(),
+        remote_scanner_manager.create_distributed_scanner(NAME, local_scanner),
+        FirstMatchingPartitionKeyExtractor::default()
+            .with_service_key("target_service_key")
+            .with_invocation_id("id"),
+    );
+    ctx.register_partitioned_table(NAME, Arc::new(table))
+}
+
+#[derive(Clone, Debug)]
+struct DeadLetterScanner;
+
+impl ScanLocalPartition for DeadLetterScanner {
+    type Builder = SysDeadLetterBuilder;
+    type Item = (InvocationId, DeadLetter);
+
+    fn scan_partition_store(
+        partition_store: &PartitionStore,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = restate_storage_api::Result<Self::Item>> + Send, StorageError>
+    {
+        partition_store.all_dead_letters(range)
+    }
+
+    fn append_row(
+        row_builder: &mut Self::Builder,
+        string_buffer: &mut String,
+        (invocation_id, dead_letter): Self::Item,
+    ) {
+        append_dead_letter_row(row_builder, string_buffer, invocation_id, dead_letter);
+    }
+}
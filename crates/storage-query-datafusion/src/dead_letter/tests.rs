@@ -0,0 +1,79 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::mocks::*;
+use crate::row;
+use bytes::Bytes;
+use datafusion::arrow::array::{LargeStringArray, UInt32Array};
+use datafusion::arrow::record_batch::RecordBatch;
+use futures::StreamExt;
+use googletest::all;
+use googletest::prelude::{assert_that, eq};
+use restate_storage_api::Transaction;
+use restate_storage_api::dead_letter_table::{DeadLetter, DeadLetterTable};
+use restate_types::errors::InvocationError;
+use restate_types::identifiers::{InvocationId, SubscriptionId};
+use restate_types::invocation::{InvocationTarget, Source};
+use restate_types::time::MillisSinceEpoch;
+use std::time::Duration;
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_dead_letter() {
+    let mut engine = MockQueryEngine::create().await;
+
+    let invocation_target = InvocationTarget::mock_virtual_object();
+    let invocation_id = InvocationId::mock_generate(&invocation_target);
+    let subscription_id = SubscriptionId::new();
+
+    let mut tx = engine.partition_store().transaction();
+    tx.put_dead_letter(
+        &invocation_id,
+        &DeadLetter {
+            invocation_target: invocation_target.clone(),
+            source: Source::Subscription(subscription_id),
+            headers: vec![],
+            argument: Bytes::from_static(b"{\"name\":\"Francesco\"}"),
+            failure: InvocationError::new(500u16, "boom"),
+            failed_at: MillisSinceEpoch::new(1000),
+            idempotency_key: None,
+            completion_retention_duration: Duration::ZERO,
+            journal_retention_duration: Duration::ZERO,
+        },
+    )
+    .await
+    .unwrap();
+    tx.commit().await.unwrap();
+
+    let records = engine
+        .execute("SELECT * FROM sys_dead_letter")
+        .await
+        .unwrap()
+        .collect::<Vec<Result<RecordBatch, _>>>()
+        .await
+        .remove(0)
+        .unwrap();
+
+    assert_that!(
+        records,
+        all!(row!(
+            0,
+            {
+                "id" => LargeStringArray: eq(invocation_id.to_string()),
+                "target_service_name" => LargeStringArray: eq(invocation_target.service_name().to_string()),
+                "target_handler_name" => LargeStringArray: eq(invocation_target.handler_name().to_string()),
+                "invoked_by" => LargeStringArray: eq("subscription"),
+                "invoked_by_subscription_id" => LargeStringArray: eq(subscription_id.to_string()),
+                "payload_utf8" => LargeStringArray: eq("{\"name\":\"Francesco\"}"),
+                "failure_code" => UInt32Array: eq(500),
+                "failure_message" => LargeStringArray: eq("boom"),
+            }
+        ))
+    );
+}
@@ -83,6 +83,10 @@ pub(crate) fn append_invocation_status_row(
             row.status("suspended");
             fill_in_flight_invocation_metadata(&mut row, output, metadata);
         }
+        InvocationStatus::Paused(metadata) => {
+            row.status("paused");
+            fill_in_flight_invocation_metadata(&mut row, output, metadata);
+        }
         InvocationStatus::Free => {
             row.status("free");
         }
@@ -93,6 +97,11 @@ pub(crate) fn append_invocation_status_row(
             // We fill the span context only for the new table, as the old table will contain always the empty value
             fill_span_context(&mut row, output, &completed.span_context);
 
+            if completed.has_retained_journal() {
+                row.journal_size(completed.journal_metadata.length);
+                row.journal_commands_size(completed.journal_metadata.commands);
+            }
+
             match completed.response_result {
                 ResponseResult::Success(_) => {
                     row.completion_result("success");
@@ -102,6 +111,9 @@ pub(crate) fn append_invocation_status_row(
                     row.completion_failure(format_using(output, &failure));
                 }
             }
+            if let Some(action) = completed.retries_exhausted_action {
+                row.retries_exhausted_action(format_using(output, &action));
+            }
         }
     };
 }
@@ -112,6 +124,9 @@ fn fill_in_flight_invocation_metadata(
     meta: InFlightInvocationMetadata,
 ) {
     // journal_metadata and stats are filled by other functions
+    if let Some(action) = meta.retries_exhausted_action {
+        row.retries_exhausted_action(format_using(output, &action));
+    }
     if let Some(pinned_deployment) = meta.pinned_deployment {
         row.pinned_deployment_id(pinned_deployment.deployment_id.to_string());
         row.pinned_service_protocol_version(
@@ -23,7 +23,7 @@ define_table!(sys_invocation_status(
     /// [Invocation ID](/operate/invocation#invocation-identifier).
     id: DataType::LargeUtf8,
 
-    /// Either `inboxed` or `scheduled` or `invoked` or `suspended` or `completed`
+    /// Either `inboxed` or `scheduled` or `invoked` or `suspended` or `paused` or `completed`
     status: DataType::LargeUtf8,
 
     /// If `status = 'completed'`, this contains either `success` or `failure`
@@ -32,6 +32,10 @@ define_table!(sys_invocation_status(
     /// If `status = 'completed' AND completion_result = 'failure'`, this contains the error cause
     completion_failure: DataType::LargeUtf8,
 
+    /// If the invoker exhausted the retries of this invocation, the action that was applied,
+    /// either `kill` or `pause` or `dead_letter`. See the `on-max-attempts` invoker option.
+    retries_exhausted_action: DataType::LargeUtf8,
+
     /// Invocation Target. Format for plain services: `ServiceName/HandlerName`, e.g.
     /// `Greeter/greet`. Format for virtual objects/workflows: `VirtualObjectName/Key/HandlerName`,
     /// e.g. `Greeter/Francesco/greet`.
@@ -84,6 +88,7 @@ define_table!(sys_invocation_status(
     trace_id: DataType::LargeUtf8,
 
     /// The number of journal entries durably logged for this invocation.
+    /// For completed invocations, this is set only when the journal is retained.
     journal_size: DataType::UInt32,
 
     /// The number of commands generated by this invocation, stored in the journal.
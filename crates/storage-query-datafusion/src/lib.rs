@@ -13,6 +13,7 @@ pub mod context;
 
 pub mod remote_query_scanner_server;
 
+mod dead_letter;
 mod deployment;
 mod idempotency;
 mod inbox;
@@ -28,6 +29,7 @@ mod partition_state;
 mod partition_store_scanner;
 mod physical_optimizer;
 mod promise;
+mod schedule;
 mod service;
 mod state;
 #[cfg(feature = "table_docs")]
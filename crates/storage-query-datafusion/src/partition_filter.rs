@@ -12,7 +12,7 @@ use anyhow::Context;
 use datafusion::common::ScalarValue;
 use datafusion::logical_expr::{BinaryExpr, Expr, Operator, col};
 use restate_types::identifiers::partitioner::HashPartitioner;
-use restate_types::identifiers::{InvocationId, PartitionKey, WithPartitionKey};
+use restate_types::identifiers::{InvocationId, PartitionKey, ScheduleId, WithPartitionKey};
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
 
@@ -51,6 +51,15 @@ impl FirstMatchingPartitionKeyExtractor {
         self.append(e)
     }
 
+    pub fn with_schedule_id(self, column_name: impl Into<String>) -> Self {
+        let e = MatchingColumnExtractor::new(column_name, |column_value: &str| {
+            let schedule_id =
+                ScheduleId::from_str(column_value).context("non valid schedule id")?;
+            Ok(schedule_id.partition_key())
+        });
+        self.append(e)
+    }
+
     pub fn append(mut self, extractor: impl PartitionKeyExtractor) -> Self {
         self.extractors.push(Box::new(extractor));
         self
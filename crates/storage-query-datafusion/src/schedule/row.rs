@@ -0,0 +1,65 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::schema::SysScheduleBuilder;
+
+use crate::table_util::format_using;
+use restate_storage_api::schedule_table::ScheduleStatus;
+use restate_types::identifiers::{TimestampAwareId, WithPartitionKey};
+
+#[inline]
+pub(crate) fn append_schedule_row(
+    builder: &mut SysScheduleBuilder,
+    output: &mut String,
+    schedule_status: ScheduleStatus,
+) {
+    let schedule = schedule_status.schedule;
+
+    let mut row = builder.row();
+    row.partition_key(schedule.id.partition_key());
+
+    if row.is_id_defined() {
+        row.id(format_using(output, &schedule.id));
+    }
+    if row.is_cron_defined() {
+        row.cron(format_using(output, &schedule.cron));
+    }
+
+    let invocation_target = schedule.invocation_target;
+    row.target_service_name(invocation_target.service_name());
+    if let Some(key) = invocation_target.key() {
+        row.target_service_key(key);
+    }
+    row.target_handler_name(invocation_target.handler_name());
+    if row.is_target_defined() {
+        row.target(format_using(output, &invocation_target));
+    }
+
+    if row.is_catch_up_defined() {
+        row.catch_up(format_using(output, &schedule.catch_up));
+    }
+
+    if row.is_payload_utf8_defined() {
+        if let Ok(str) = std::str::from_utf8(&schedule.argument) {
+            row.payload_utf8(str);
+        }
+    }
+    if row.is_payload_defined() {
+        row.payload(&schedule.argument);
+    }
+
+    row.created_at(schedule.id.timestamp().as_u64() as i64);
+    if let Some(next_fire_at) = schedule_status.next_fire_at {
+        row.next_fire_at(next_fire_at.as_u64() as i64);
+    }
+    if let Some(last_fired_at) = schedule_status.last_fired_at {
+        row.last_fired_at(last_fired_at.as_u64() as i64);
+    }
+}
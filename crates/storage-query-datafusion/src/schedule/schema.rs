@@ -0,0 +1,62 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+#![allow(dead_code)]
+
+use crate::table_macro::*;
+
+use datafusion::arrow::datatypes::DataType;
+
+define_sort_order!(sys_schedule(partition_key, id));
+
+define_table!(sys_schedule(
+    /// Internal column that is used for partitioning the schedules. Can be ignored.
+    partition_key: DataType::UInt64,
+
+    /// Schedule ID.
+    id: DataType::LargeUtf8,
+
+    /// Cron expression of the schedule, evaluated in UTC.
+    cron: DataType::LargeUtf8,
+
+    /// Invocation Target. Format for plain services: `ServiceName/HandlerName`, e.g.
+    /// `Greeter/greet`. Format for virtual objects/workflows: `VirtualObjectName/Key/HandlerName`,
+    /// e.g. `Greeter/Francesco/greet`.
+    target: DataType::LargeUtf8,
+
+    /// The name of the invoked service.
+    target_service_name: DataType::LargeUtf8,
+
+    /// The key of the virtual object. Null for regular services.
+    target_service_key: DataType::LargeUtf8,
+
+    /// The invoked handler.
+    target_handler_name: DataType::LargeUtf8,
+
+    /// What to do with the runs missed while the schedule could not fire. Either `skip`,
+    /// `run-once` or `all`.
+    catch_up: DataType::LargeUtf8,
+
+    /// Only contains meaningful values when the request payload is `utf8`, e.g. JSON.
+    payload_utf8: DataType::LargeUtf8,
+
+    /// A binary, uninterpreted representation of the request payload. You can use the more specific column
+    /// `payload_utf8` if the payload is a string.
+    payload: DataType::LargeBinary,
+
+    /// Timestamp indicating when the schedule was created.
+    created_at: TimestampMillisecond,
+
+    /// Timestamp of the next run. Null if the cron expression has no further occurrences.
+    next_fire_at: TimestampMillisecond,
+
+    /// Timestamp indicating when the schedule last fired.
+    last_fired_at: TimestampMillisecond,
+));
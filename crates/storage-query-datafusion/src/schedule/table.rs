@@ -0,0 +1,76 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use futures::Stream;
+
+use restate_partition_store::{PartitionStore, PartitionStoreManager};
+use restate_storage_api::StorageError;
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus};
+use restate_types::identifiers::PartitionKey;
+
+use super::row::append_schedule_row;
+use super::schema::{SysScheduleBuilder, sys_schedule_sort_order};
+use crate::context::{QueryContext, SelectPartitions};
+use crate::partition_filter::FirstMatchingPartitionKeyExtractor;
+use crate::partition_store_scanner::{LocalPartitionsScanner, ScanLocalPartition};
+use crate::remote_query_scanner_manager::RemoteScannerManager;
+use crate::table_providers::{PartitionedTableProvider, ScanPartition};
+
+const NAME: &str = "sys_schedule";
+
+pub(crate) fn register_self(
+    ctx: &QueryContext,
+    partition_selector: impl SelectPartitions,
+    local_partition_store_manager: Option<PartitionStoreManager>,
+    remote_scanner_manager: &RemoteScannerManager,
+) -> datafusion::common::Result<()> {
+    let local_scanner = local_partition_store_manager.map(|partition_store_manager| {
+        Arc::new(LocalPartitionsScanner::new(
+            partition_store_manager,
+            ScheduleScanner,
+        )) as Arc<dyn ScanPartition>
+    });
+    let table = PartitionedTableProvider::new(
+        partition_selector,
+        SysScheduleBuilder::schema(),
+        sys_schedule_sort_order(),
+        remote_scanner_manager.create_distributed_scanner(NAME, local_scanner),
+        FirstMatchingPartitionKeyExtractor::default().with_schedule_id("id"),
+    );
+    ctx.register_partitioned_table(NAME, Arc::new(table))
+}
+
+#[derive(Clone, Debug)]
+struct ScheduleScanner;
+
+impl ScanLocalPartition for ScheduleScanner {
+    type Builder = SysScheduleBuilder;
+    type Item = ScheduleStatus;
+
+    fn scan_partition_store(
+        partition_store: &PartitionStore,
+        range: RangeInclusive<PartitionKey>,
+    ) -> Result<impl Stream<Item = restate_storage_api::Result<Self::Item>> + Send, StorageError>
+    {
+        partition_store.all_schedules(range)
+    }
+
+    fn append_row(
+        row_builder: &mut Self::Builder,
+        string_buffer: &mut String,
+        schedule_status: Self::Item,
+    ) {
+        append_schedule_row(row_builder, string_buffer, schedule_status);
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::mocks::*;
+use crate::row;
+use bytes::Bytes;
+use datafusion::arrow::array::LargeStringArray;
+use datafusion::arrow::record_batch::RecordBatch;
+use futures::StreamExt;
+use googletest::all;
+use googletest::prelude::{assert_that, eq};
+use restate_storage_api::Transaction;
+use restate_storage_api::schedule_table::{ScheduleStatus, ScheduleTable};
+use restate_types::identifiers::ScheduleId;
+use restate_types::invocation::InvocationTarget;
+use restate_types::schedule::{CatchUpPolicy, Schedule};
+use restate_types::time::MillisSinceEpoch;
+
+#[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_schedule() {
+    let mut engine = MockQueryEngine::create().await;
+
+    let invocation_target = InvocationTarget::mock_virtual_object();
+    let schedule_id = ScheduleId::new();
+
+    let mut tx = engine.partition_store().transaction();
+    tx.put_schedule(&ScheduleStatus {
+        schedule: Schedule {
+            id: schedule_id,
+            cron: "*/5 * * * *".parse().unwrap(),
+            invocation_target: invocation_target.clone(),
+            argument: Bytes::from_static(b"{\"name\":\"Francesco\"}"),
+            catch_up: CatchUpPolicy::All,
+        },
+        next_fire_at: Some(MillisSinceEpoch::new(300_000)),
+        last_fired_at: None,
+    })
+    .await
+    .unwrap();
+    tx.commit().await.unwrap();
+
+    let records = engine
+        .execute("SELECT * FROM sys_schedule")
+        .await
+        .unwrap()
+        .collect::<Vec<Result<RecordBatch, _>>>()
+        .await
+        .remove(0)
+        .unwrap();
+
+    assert_that!(
+        records,
+        all!(row!(
+            0,
+            {
+                "id" => LargeStringArray: eq(schedule_id.to_string()),
+                "cron" => LargeStringArray: eq("*/5 * * * *"),
+                "target_service_name" => LargeStringArray: eq(invocation_target.service_name().to_string()),
+                "target_service_key" => LargeStringArray: eq(invocation_target.key().unwrap().to_string()),
+                "target_handler_name" => LargeStringArray: eq(invocation_target.handler_name().to_string()),
+                "catch_up" => LargeStringArray: eq("all"),
+                "payload_utf8" => LargeStringArray: eq("{\"name\":\"Francesco\"}"),
+            }
+        ))
+    );
+}
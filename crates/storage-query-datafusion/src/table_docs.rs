@@ -9,8 +9,8 @@
 // by the Apache License, Version 2.0.
 
 use crate::{
-    deployment, idempotency, inbox, invocation_state, invocation_status, journal,
-    keyed_service_status, promise, service, state,
+    dead_letter, deployment, idempotency, inbox, invocation_state, invocation_status, journal,
+    keyed_service_status, promise, schedule, service, state,
 };
 use std::borrow::Cow;
 
@@ -24,6 +24,8 @@ pub const ALL_TABLE_DOCS: &[StaticTableDocs] = &[
     inbox::schema::TABLE_DOCS,
     idempotency::schema::TABLE_DOCS,
     promise::schema::TABLE_DOCS,
+    dead_letter::schema::TABLE_DOCS,
+    schedule::schema::TABLE_DOCS,
     service::schema::TABLE_DOCS,
     deployment::schema::TABLE_DOCS,
 ];
@@ -211,7 +213,7 @@ pub fn sys_invocation_table_docs() -> OwnedTableDocs {
         TableColumn {
             name: "status",
             column_type: "Utf8",
-            description: "Either `pending` or `scheduled` or `ready` or `running` or `backing-off` or `suspended` or `completed`.",
+            description: "Either `pending` or `scheduled` or `ready` or `running` or `backing-off` or `suspended` or `paused` or `completed`.",
         },
         sys_invocation_status
             .remove("completion_result")
@@ -219,6 +221,9 @@ pub fn sys_invocation_table_docs() -> OwnedTableDocs {
         sys_invocation_status
             .remove("completion_failure")
             .expect("completion_failure should exist"),
+        sys_invocation_status
+            .remove("retries_exhausted_action")
+            .expect("retries_exhausted_action should exist"),
     ];
 
     OwnedTableDocs {
@@ -24,7 +24,7 @@ use crate::{
 };
 
 use super::{
-    QueryEngineOptions, print_warning_deprecated_config_option,
+    AdminAuthOptions, QueryEngineOptions, TlsOptions, print_warning_deprecated_config_option,
     print_warning_deprecated_value_using_default,
 };
 
@@ -109,6 +109,19 @@ pub struct AdminOptions {
     /// Disable serving the Restate Web UI on the admin port. Default is `false`.
     pub disable_web_ui: bool,
 
+    /// # Authentication
+    ///
+    /// If set, admin API requests must be authenticated, and are authorized based on the role of
+    /// the authenticated principal. By default, the admin API accepts unauthenticated requests.
+    pub auth: Option<AdminAuthOptions>,
+
+    /// # TLS
+    ///
+    /// If set, the admin API only accepts TLS connections, using the configured certificate. The
+    /// client certificates verified with the configured CA can be used to authenticate, see the
+    /// `auth` options. Set `client-certificate-optional` to accept bearer tokens as well.
+    pub tls: Option<TlsOptions>,
+
     #[cfg(any(test, feature = "test-util"))]
     pub disable_cluster_controller: bool,
 }
@@ -154,7 +167,7 @@ impl AdminOptions {
         if self.advertised_admin_endpoint.is_none() {
             self.advertised_admin_endpoint = Some(
                 Uri::builder()
-                    .scheme("http")
+                    .scheme(if self.tls.is_some() { "https" } else { "http" })
                     .authority(bind_address)
                     .path_and_query("/")
                     .build()
@@ -181,6 +194,8 @@ impl Default for AdminOptions {
             #[cfg(any(test, feature = "test-util"))]
             disable_cluster_controller: false,
             disable_web_ui: false,
+            auth: None,
+            tls: None,
             log_tail_update_interval: Duration::from_secs(5 * 60).into(),
         }
     }
@@ -228,6 +243,8 @@ impl From<AdminOptionsShadow> for AdminOptions {
             log_tail_update_interval: value.log_tail_update_interval,
             default_partition_replication: partition_replication,
             disable_web_ui: value.disable_web_ui,
+            auth: value.auth,
+            tls: value.tls,
             #[cfg(any(test, feature = "test-util"))]
             disable_cluster_controller: value.disable_cluster_controller,
         }
@@ -272,6 +289,10 @@ struct AdminOptionsShadow {
 
     disable_web_ui: bool,
 
+    auth: Option<AdminAuthOptions>,
+
+    tls: Option<TlsOptions>,
+
     #[cfg(any(test, feature = "test-util"))]
     disable_cluster_controller: bool,
 }
@@ -0,0 +1,80 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+
+/// # Admin authentication options
+///
+/// When configured, every admin API request, except health checks, must be authenticated either
+/// with an `Authorization: Bearer <token>` header carrying one of the configured tokens, or with
+/// one of the configured client certificates. The role of the authenticated principal decides
+/// which operations it can perform.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename = "AdminAuthOptions"))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct AdminAuthOptions {
+    /// # Bearer tokens
+    ///
+    /// Static bearer tokens, each identifying a principal with a role.
+    pub tokens: Vec<AdminTokenOptions>,
+
+    /// # Client certificates
+    ///
+    /// Subjects of the client certificates accepted by the admin API, each with a role. Requires
+    /// the admin API to be served over TLS with client certificate verification.
+    pub client_certificates: Vec<AdminClientCertificateOptions>,
+}
+
+/// # Admin role
+///
+/// Roles are ordered, each one granting the permissions of the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum AdminRole {
+    /// Read-only access to the deployments, services and subscriptions.
+    Viewer,
+    /// Viewer permissions, plus managing invocations and dead letters, and the query API.
+    Operator,
+    /// Full access, including registering deployments, modifying services and state, managing
+    /// subscriptions and the metadata store.
+    Admin,
+}
+
+/// # Admin bearer token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct AdminTokenOptions {
+    /// Principal identified by this token.
+    pub principal: String,
+
+    /// The secret token, sent by the clients as bearer token.
+    pub token: String,
+
+    /// Role granted to the principal.
+    pub role: AdminRole,
+}
+
+/// # Admin client certificate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct AdminClientCertificateOptions {
+    /// Subject distinguished name of the certificate in the RFC 4514 string form, that is starting
+    /// from the most specific attribute, for example `CN=operator,O=Example`.
+    pub subject: String,
+
+    /// Role granted to the certificate holder.
+    pub role: AdminRole,
+}
@@ -501,7 +501,40 @@ pub struct ServiceClientOptions {
     ///
     /// This file is currently only read on client creation, but this may change in future.
     /// Parsed public keys will be logged at INFO level in the same format that SDKs expect.
+    ///
+    /// This is the active key of the request identity keyring: it is the only key used to sign
+    /// requests, and its key id is sent in the `kid` header of the JWTs.
     pub request_identity_private_key_pem_file: Option<PathBuf>,
+
+    /// # Request identity grace period key PEM files
+    ///
+    /// Paths to ed25519 private key PEM files, in the same format as the active key, which are
+    /// part of the request identity keyring without being used to sign requests. Their public keys
+    /// are published alongside the active one on the `/keys` endpoint of the admin API.
+    ///
+    /// To rotate the active key without a flag day, first add the new key here and wait for the
+    /// service endpoints to pick it up, then make it the active key and keep the old one here
+    /// until the requests signed with it have expired.
+    #[serde(default)]
+    pub request_identity_grace_period_key_pem_files: Vec<PathBuf>,
+
+    /// # Secrets file
+    ///
+    /// A path to a JSON file, such as "/var/secrets/deployments.json", containing an object that
+    /// maps secret names to their values. Deployment headers can reference these secrets with
+    /// `${secret:<name>}`, for example `Authorization: Bearer ${secret:greeter-token}`.
+    /// References are resolved when sending each request, and the file is checked for changes
+    /// every 10 seconds and re-read, so secrets can be rotated without re-registering the
+    /// deployments.
+    pub secrets_file: Option<PathBuf>,
+
+    /// # Secrets environment variable prefix
+    ///
+    /// Prefix of the environment variables that deployment headers can reference with
+    /// `${env:<name>}`, such as "RESTATE_DEPLOYMENT_SECRET_". References to variables without this
+    /// prefix are rejected, so registering a deployment can't exfiltrate the environment of the
+    /// Restate servers. If unset, environment variables can't be referenced.
+    pub secrets_env_prefix: Option<String>,
 }
 
 /// # Log format
@@ -15,7 +15,10 @@ use http::Uri;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Semaphore;
 
-use super::KafkaClusterOptions;
+use super::{
+    IngressAuthOptions, IngressCorsOptions, IngressGrpcOptions, IngressRateLimitOptions,
+    KafkaClusterOptions, TlsOptions, WebhookOptions,
+};
 
 /// # Ingress options
 #[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
@@ -38,6 +41,41 @@ pub struct IngressOptions {
 
     kafka_clusters: Vec<KafkaClusterOptions>,
 
+    /// # Webhooks
+    ///
+    /// Webhook sources that subscriptions can consume from, using the `webhook://<name>` source URI.
+    /// Signed deliveries are accepted on `/restate/webhook/<name>`.
+    webhooks: Vec<WebhookOptions>,
+
+    /// # Authentication
+    ///
+    /// If set, ingress requests must be authenticated, and invocations are authorized using the
+    /// configured allow rules. By default, the ingress accepts unauthenticated requests.
+    pub auth: Option<IngressAuthOptions>,
+
+    /// # CORS
+    ///
+    /// Cross-Origin Resource Sharing policy of the ingress. If not set, requests from any origin
+    /// are allowed.
+    pub cors: Option<IngressCorsOptions>,
+
+    /// # Rate limit
+    ///
+    /// Per-key rate limits of the ingress requests. If not set, requests are only limited by the
+    /// concurrency limit.
+    pub rate_limit: Option<IngressRateLimitOptions>,
+
+    /// # gRPC
+    ///
+    /// If set, the ingress also serves the gRPC and Connect protocols for the services declaring
+    /// protobuf schemas.
+    pub grpc: Option<IngressGrpcOptions>,
+
+    /// # TLS
+    ///
+    /// If set, the ingress only accepts TLS connections, using the configured certificate.
+    pub tls: Option<TlsOptions>,
+
     /// # Experimental feature to run the ingress independent of the worker role
     ///
     /// This feature is experimental and should be used with caution. It enables the HTTP ingress
@@ -73,6 +111,10 @@ impl IngressOptions {
             .collect()
     }
 
+    pub fn get_webhook(&self, name: &str) -> Option<&WebhookOptions> {
+        self.webhooks.iter().find(|w| w.name == name)
+    }
+
     pub fn concurrent_api_requests_limit(&self) -> usize {
         std::cmp::min(
             self.concurrent_api_requests_limit
@@ -94,7 +136,7 @@ impl IngressOptions {
         if self.advertised_ingress_endpoint.is_none() {
             self.advertised_ingress_endpoint = Some(
                 Uri::builder()
-                    .scheme("http")
+                    .scheme(if self.tls.is_some() { "https" } else { "http" })
                     .authority(bind_address)
                     .path_and_query("/")
                     .build()
@@ -111,6 +153,12 @@ impl Default for IngressOptions {
             // max is limited by Tower's LoadShedLayer.
             concurrent_api_requests_limit: None,
             kafka_clusters: Default::default(),
+            webhooks: Default::default(),
+            auth: None,
+            cors: None,
+            rate_limit: None,
+            grpc: None,
+            tls: None,
             experimental_feature_enable_separate_ingress_role: false,
             advertised_ingress_endpoint: None,
         }
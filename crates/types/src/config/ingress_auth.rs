@@ -0,0 +1,189 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Matches any service, handler or principal in the allow rules.
+pub const WILDCARD: &str = "*";
+
+/// # Ingress authentication options
+///
+/// When configured, every ingress request, except health checks and webhook deliveries, must carry
+/// an `Authorization: Bearer <token>` header, where the token is either one of the configured API
+/// keys or a JWT signed by one of the keys of the configured JWKS.
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename = "IngressAuthOptions"))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct IngressAuthOptions {
+    /// # JWKS file
+    ///
+    /// Path to a local JSON Web Key Set file, containing the keys used to verify the signature of
+    /// JWT bearer tokens. Tokens must carry the `kid` header when the set contains more than one key.
+    /// The file is reloaded when it changes, or when a token is signed by an unknown key.
+    pub jwks_file: Option<PathBuf>,
+
+    /// # JWT algorithm
+    ///
+    /// Signing algorithm, e.g. `RS256`, of the JWTs signed by keys of the JWKS which don't declare
+    /// their `alg`. Tokens whose header declares a different algorithm than the one of the signing
+    /// key are rejected.
+    pub jwt_algorithm: Option<String>,
+
+    /// # JWT issuer
+    ///
+    /// If set, the `iss` claim of the JWTs must match this value.
+    pub jwt_issuer: Option<String>,
+
+    /// # JWT audience
+    ///
+    /// If set, the `aud` claim of the JWTs must contain this value.
+    pub jwt_audience: Option<String>,
+
+    /// # JWT principal claim
+    ///
+    /// Claim of the JWTs identifying the principal.
+    pub jwt_principal_claim: String,
+
+    /// # API keys
+    ///
+    /// Static API keys, each identifying a principal.
+    pub api_keys: Vec<ApiKeyOptions>,
+
+    /// # Allow rules
+    ///
+    /// Rules granting principals access to services and handlers. Invocations are allowed if at
+    /// least one rule matches. If no rule is configured, every authenticated principal can invoke
+    /// every public service.
+    pub allow: Vec<IngressAllowRule>,
+
+    /// # Principal header
+    ///
+    /// Header used to pass the verified principal to the invoked handlers. Any value of this header
+    /// sent by the clients is discarded.
+    pub principal_header: String,
+}
+
+impl Default for IngressAuthOptions {
+    fn default() -> Self {
+        Self {
+            jwks_file: None,
+            jwt_algorithm: None,
+            jwt_issuer: None,
+            jwt_audience: None,
+            jwt_principal_claim: "sub".to_owned(),
+            api_keys: Vec::new(),
+            allow: Vec::new(),
+            principal_header: "x-restate-principal".to_owned(),
+        }
+    }
+}
+
+impl IngressAuthOptions {
+    /// Returns true if the principal is allowed to invoke the given handler. When `handler` is
+    /// `None`, only the rules covering every handler of the service are considered.
+    pub fn is_allowed(&self, principal: &str, service: &str, handler: Option<&str>) -> bool {
+        self.allow.is_empty()
+            || self
+                .allow
+                .iter()
+                .any(|rule| rule.matches(principal, service, handler))
+    }
+}
+
+/// # API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ApiKeyOptions {
+    /// Principal identified by this key.
+    pub principal: String,
+
+    /// The secret key, sent by the clients as bearer token.
+    pub key: String,
+}
+
+/// # Allow rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressAllowRule {
+    /// Service name, or `*` to match every service.
+    pub service: String,
+
+    /// Handler name, or `*`. If not set, the rule matches every handler of the service.
+    #[serde(default)]
+    pub handler: Option<String>,
+
+    /// Principals granted access, `*` matches every authenticated principal.
+    pub principals: Vec<String>,
+}
+
+impl IngressAllowRule {
+    fn matches(&self, principal: &str, service: &str, handler: Option<&str>) -> bool {
+        let handler_matches = match (self.handler.as_deref(), handler) {
+            (None, _) | (Some(WILDCARD), _) => true,
+            (Some(rule_handler), Some(handler)) => rule_handler == handler,
+            (Some(_), None) => false,
+        };
+
+        (self.service == WILDCARD || self.service == service)
+            && handler_matches
+            && self
+                .principals
+                .iter()
+                .any(|p| p == WILDCARD || p == principal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(service: &str, handler: Option<&str>, principals: &[&str]) -> IngressAllowRule {
+        IngressAllowRule {
+            service: service.to_owned(),
+            handler: handler.map(ToOwned::to_owned),
+            principals: principals.iter().map(|p| (*p).to_owned()).collect(),
+        }
+    }
+
+    #[test]
+    fn allow_rules() {
+        let options = IngressAuthOptions {
+            allow: vec![
+                rule("Greeter", None, &["alice"]),
+                rule("Counter", Some("get"), &[WILDCARD]),
+                rule(WILDCARD, Some("admin"), &["bob"]),
+            ],
+            ..IngressAuthOptions::default()
+        };
+
+        assert!(options.is_allowed("alice", "Greeter", Some("greet")));
+        assert!(options.is_allowed("alice", "Greeter", None));
+        assert!(!options.is_allowed("bob", "Greeter", Some("greet")));
+
+        assert!(options.is_allowed("bob", "Counter", Some("get")));
+        assert!(!options.is_allowed("alice", "Counter", Some("add")));
+        assert!(!options.is_allowed("alice", "Counter", None));
+
+        assert!(options.is_allowed("bob", "Counter", Some("admin")));
+        assert!(!options.is_allowed("alice", "Other", Some("admin")));
+    }
+
+    #[test]
+    fn no_rules_allow_everything() {
+        assert!(IngressAuthOptions::default().is_allowed("anyone", "Greeter", Some("greet")));
+    }
+}
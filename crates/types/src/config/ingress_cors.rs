@@ -0,0 +1,83 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// # Ingress CORS options
+///
+/// Cross-Origin Resource Sharing policy applied to the ingress requests. The allowed origins,
+/// methods, headers and credentials can be overridden per service through the admin API.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename = "IngressCorsOptions"))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct IngressCorsOptions {
+    /// # Allowed origins
+    ///
+    /// Origins allowed to send requests to the ingress, for example `https://example.com`.
+    /// `*` allows any origin.
+    pub allowed_origins: Vec<String>,
+
+    /// # Allowed methods
+    ///
+    /// Methods allowed in cross-origin requests.
+    pub allowed_methods: Vec<String>,
+
+    /// # Allowed headers
+    ///
+    /// Headers allowed in cross-origin requests. `*` allows any header.
+    pub allowed_headers: Vec<String>,
+
+    /// # Exposed headers
+    ///
+    /// Response headers exposed to the browser scripts.
+    pub exposed_headers: Vec<String>,
+
+    /// # Allow credentials
+    ///
+    /// If true, browsers are allowed to send credentials, such as cookies or the authorization
+    /// header, with cross-origin requests. Requires an explicit list of allowed origins, as
+    /// credentials can't be allowed for any origin.
+    pub allow_credentials: bool,
+
+    /// # Max age
+    ///
+    /// How long browsers can cache the result of a preflight request.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub max_age: Option<humantime::Duration>,
+}
+
+impl IngressCorsOptions {
+    /// Whether credentials are allowed together with the `*` origin, which would let any website
+    /// send credentialed requests to the ingress.
+    pub fn allows_credentials_from_any_origin(&self) -> bool {
+        self.allow_credentials && self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+}
+
+impl Default for IngressCorsOptions {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_owned()],
+            allowed_methods: vec!["GET".to_owned(), "POST".to_owned(), "DELETE".to_owned()],
+            allowed_headers: vec!["*".to_owned()],
+            exposed_headers: vec![],
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+
+/// # Ingress gRPC options
+///
+/// Serves the unary gRPC and Connect protocols for the services declaring protobuf schemas. The
+/// requests are routed on the `/<package>.<Service>/<Method>` paths by their content type:
+/// `application/grpc` for gRPC, and `application/proto` with the `Connect-Protocol-Version`
+/// header for Connect. The key of virtual objects and workflows is read from the
+/// `x-restate-key` metadata.
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename = "IngressGrpcOptions"))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct IngressGrpcOptions {
+    /// # Reflection
+    ///
+    /// Serve the gRPC server reflection service, used by tools such as `grpcurl` to discover the
+    /// services and their schemas.
+    pub reflection: bool,
+}
+
+impl Default for IngressGrpcOptions {
+    fn default() -> Self {
+        Self { reflection: true }
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::num::NonZeroU32;
+
+use serde::{Deserialize, Serialize};
+
+/// # Ingress rate limit options
+///
+/// Token bucket rate limits applied to the ingress requests. Each distinct value of the configured
+/// key gets its own bucket, while requests without a value for the key share a single bucket.
+/// Requests exceeding the limit are rejected with `429 Too Many Requests`.
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename = "IngressRateLimitOptions"))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct IngressRateLimitOptions {
+    /// # Key
+    ///
+    /// What the requests are grouped by when applying the limits.
+    pub key: RateLimitKey,
+
+    /// # Requests per second
+    ///
+    /// Rate at which the tokens of each bucket are refilled. If not set, only the service limits
+    /// are applied.
+    pub requests_per_second: Option<NonZeroU32>,
+
+    /// # Burst
+    ///
+    /// Capacity of each bucket. Defaults to the requests per second.
+    pub burst: Option<NonZeroU32>,
+
+    /// # Service limits
+    ///
+    /// Limits overriding the global one for the requests to the given services.
+    pub services: Vec<ServiceRateLimitOptions>,
+}
+
+impl Default for IngressRateLimitOptions {
+    fn default() -> Self {
+        Self {
+            key: RateLimitKey::Principal,
+            requests_per_second: None,
+            burst: None,
+            services: Vec::new(),
+        }
+    }
+}
+
+/// # Rate limit key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RateLimitKey {
+    /// Principal authenticated through the API keys or the JWTs, see the ingress `auth` options.
+    Principal,
+    /// Value of the given request header.
+    Header { name: String },
+    /// Key of the invoked virtual object or workflow.
+    ObjectKey,
+}
+
+/// # Service rate limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ServiceRateLimitOptions {
+    /// Name of the service.
+    pub service: String,
+
+    /// Rate at which the tokens of each bucket are refilled.
+    pub requests_per_second: NonZeroU32,
+
+    /// Capacity of each bucket. Defaults to the requests per second.
+    #[serde(default)]
+    pub burst: Option<NonZeroU32>,
+}
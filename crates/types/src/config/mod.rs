@@ -12,6 +12,7 @@ mod util;
 use enumset::EnumSet;
 pub use util::*;
 mod admin;
+mod admin_auth;
 mod aws;
 mod bifrost;
 #[cfg(feature = "clap")]
@@ -19,6 +20,10 @@ mod cli_option_overrides;
 mod common;
 mod http;
 mod ingress;
+mod ingress_auth;
+mod ingress_cors;
+mod ingress_grpc;
+mod ingress_rate_limit;
 mod kafka;
 mod log_server;
 mod metadata_server;
@@ -26,9 +31,12 @@ mod networking;
 mod object_store;
 mod query_engine;
 mod rocksdb;
+mod tls;
+mod webhook;
 mod worker;
 
 pub use admin::*;
+pub use admin_auth::*;
 pub use aws::*;
 pub use bifrost::*;
 #[cfg(feature = "clap")]
@@ -36,6 +44,10 @@ pub use cli_option_overrides::*;
 pub use common::*;
 pub use http::*;
 pub use ingress::*;
+pub use ingress_auth::*;
+pub use ingress_cors::*;
+pub use ingress_grpc::*;
+pub use ingress_rate_limit::*;
 pub use kafka::*;
 pub use log_server::*;
 pub use metadata_server::*;
@@ -43,6 +55,8 @@ pub use networking::*;
 pub use object_store::*;
 pub use query_engine::*;
 pub use rocksdb::*;
+pub use tls::*;
+pub use webhook::*;
 pub use worker::*;
 
 use std::fs;
@@ -253,6 +267,15 @@ impl Configuration {
             return Err(InvalidConfigurationError::ForceNodeIdZero);
         }
 
+        if self
+            .ingress
+            .cors
+            .as_ref()
+            .is_some_and(IngressCorsOptions::allows_credentials_from_any_origin)
+        {
+            return Err(InvalidConfigurationError::CorsCredentialsWithAnyOrigin);
+        }
+
         if self.common.node_name.is_none() {
             // If the node name is not set, we will fallback to use hostname as the node name.
             // So to avoid changing hostname to make data loss, we must validate the directory's entry.
@@ -296,6 +319,10 @@ pub enum InvalidConfigurationError {
     DeriveBindAddress(String),
     #[error("node-name is required: {0}")]
     RequiredNodeName(String),
+    #[error(
+        "ingress.cors.allow-credentials requires an explicit list of allowed origins, it can't be combined with the '*' origin"
+    )]
+    CorsCredentialsWithAnyOrigin,
 }
 
 /// Used to deserialize the [`Configuration`] in backwards compatible way which allows to specify
@@ -430,6 +457,28 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_configuration_validate_cors_credentials() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Configuration::default();
+        config.common.base_dir = Some(temp_dir.path().to_path_buf());
+        config.ingress.cors = Some(IngressCorsOptions {
+            allow_credentials: true,
+            ..IngressCorsOptions::default()
+        });
+        assert!(matches!(
+            config.validate(),
+            Err(InvalidConfigurationError::CorsCredentialsWithAnyOrigin)
+        ));
+
+        config.ingress.cors = Some(IngressCorsOptions {
+            allowed_origins: vec!["https://example.com".to_owned()],
+            allow_credentials: true,
+            ..IngressCorsOptions::default()
+        });
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_configuration_validate_base_dir_one_subdir() {
         let mut config = Configuration::default();
@@ -13,6 +13,7 @@ use std::time::Duration;
 
 use restate_serde_util::NonZeroByteCount;
 
+use super::TlsOptions;
 use crate::retries::RetryPolicy;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -74,6 +75,16 @@ pub struct NetworkingOptions {
     /// If network latency is high, it's recommended to set this to a higher value.
     /// Maximum theoretical value is 2^31-1 (2 GiB - 1), but we will sanitize this value to 500 MiB.
     data_stream_window_size: NonZeroByteCount,
+
+    /// # TLS
+    ///
+    /// If set, the node only accepts TLS connections on its RPC port, using the configured
+    /// certificate. Connections to `https` node addresses verify the peer certificate with the CA
+    /// file, or with the system roots if unset, and present the configured certificate. Setting
+    /// the CA file therefore enables mutual TLS between the nodes of the cluster.
+    ///
+    /// The advertised address of the node must use the `https` scheme.
+    pub tls: Option<TlsOptions>,
 }
 
 impl NetworkingOptions {
@@ -107,6 +118,7 @@ impl Default for NetworkingOptions {
             data_stream_window_size: NonZeroByteCount::new(
                 NonZeroUsize::new(2 * 1024 * 1024).expect("Non zero number"),
             ),
+            tls: None,
         }
     }
 }
@@ -0,0 +1,48 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// # TLS options
+///
+/// Certificate and private key used to terminate TLS. The files are checked for changes
+/// periodically and reloaded, so certificates can be rotated without restarting the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TlsOptions {
+    /// # Certificate file
+    ///
+    /// PEM file containing the certificate chain, starting with the server certificate.
+    pub cert_file: PathBuf,
+
+    /// # Private key file
+    ///
+    /// PEM file containing the private key of the certificate.
+    pub key_file: PathBuf,
+
+    /// # CA file
+    ///
+    /// PEM file containing the CA certificates used to verify the client certificates. If set,
+    /// clients must present a certificate signed by one of these CAs, unless
+    /// `client-certificate-optional` is set.
+    #[serde(default)]
+    pub ca_file: Option<PathBuf>,
+
+    /// # Optional client certificate
+    ///
+    /// If set, clients without a certificate are accepted as well, only the presented
+    /// certificates are verified with the CA file. Set it on the admin API to accept both the
+    /// clients authenticating with a certificate and the ones authenticating with a bearer token.
+    #[serde(default)]
+    pub client_certificate_optional: bool,
+}
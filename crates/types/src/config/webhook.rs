@@ -0,0 +1,48 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// # Webhook options
+///
+/// Configuration options of a webhook subscription source. Deliveries must be signed following
+/// the [Standard Webhooks](https://www.standardwebhooks.com/) specification.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct WebhookOptions {
+    /// Webhook name (Used to identify subscriptions).
+    pub name: String,
+
+    /// # Signing secret
+    ///
+    /// Base64 encoded secret used to verify the `webhook-signature` header of the deliveries.
+    /// The `whsec_` prefix, if present, is ignored.
+    pub signing_secret: String,
+
+    /// # Timestamp tolerance
+    ///
+    /// Maximum allowed difference between the `webhook-timestamp` header of a delivery and the
+    /// local clock. Deliveries outside of this window are rejected to prevent replay attacks.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde(default = "default_timestamp_tolerance")]
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub timestamp_tolerance: humantime::Duration,
+}
+
+fn default_timestamp_tolerance() -> humantime::Duration {
+    Duration::from_secs(5 * 60).into()
+}
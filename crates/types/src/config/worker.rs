@@ -18,6 +18,7 @@ use tracing::warn;
 
 use super::{CommonOptions, ObjectStoreOptions, RocksDbOptions, RocksDbOptionsBuilder};
 use crate::identifiers::PartitionId;
+use crate::invocation::OnMaxAttempts;
 use crate::retries::RetryPolicy;
 use restate_serde_util::NonZeroByteCount;
 
@@ -111,6 +112,12 @@ pub struct InvokerOptions {
     /// Retry policy to use for all the invocations handled by this invoker.
     pub retry_policy: RetryPolicy,
 
+    /// # On max attempts
+    ///
+    /// What to do when an invocation exhausts the attempts allowed by the retry policy.
+    /// This has no effect if the retry policy retries indefinitely.
+    pub on_max_attempts: OnMaxAttempts,
+
     /// # Inactivity timeout
     ///
     /// This timer guards against stalled service/handler invocations. Once it expires,
@@ -208,6 +215,7 @@ impl Default for InvokerOptions {
                 None,
                 Some(Duration::from_secs(10)),
             ),
+            on_max_attempts: OnMaxAttempts::default(),
             in_memory_queue_length_limit: NonZeroUsize::new(66_049).unwrap(),
             inactivity_timeout: Duration::from_secs(60).into(),
             abort_timeout: Duration::from_secs(60).into(),
@@ -396,6 +404,31 @@ pub struct SnapshotsOptions {
     /// Default: `None` - automatic snapshots are disabled
     pub snapshot_interval_num_records: Option<NonZeroU64>,
 
+    /// # Number of retained snapshots
+    ///
+    /// Number of most recent snapshots to retain per partition. Older snapshots are deleted from
+    /// the repository in the background after a new snapshot has been created, once the log of
+    /// the partition has been trimmed past them. The latest snapshot is never deleted.
+    ///
+    /// If a retention period is set as well, snapshots are retained as long as either setting
+    /// retains them.
+    ///
+    /// Default: `None` - snapshots are retained indefinitely
+    pub retention_num_snapshots: Option<NonZeroUsize>,
+
+    /// # Snapshot retention period
+    ///
+    /// Snapshots newer than this period are retained. Older snapshots are deleted from the
+    /// repository in the background after a new snapshot has been created, once the log of the
+    /// partition has been trimmed past them. The latest snapshot is never deleted.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    ///
+    /// Default: `None` - snapshots are retained indefinitely
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub retention_period: Option<humantime::Duration>,
+
     #[serde(flatten)]
     pub object_store: ObjectStoreOptions,
 
@@ -410,6 +443,8 @@ impl Default for SnapshotsOptions {
         Self {
             destination: None,
             snapshot_interval_num_records: None,
+            retention_num_snapshots: None,
+            retention_period: None,
             object_store: Default::default(),
             object_store_retry_policy: Self::default_retry_policy(),
         }
@@ -426,6 +461,11 @@ impl SnapshotsOptions {
         )
     }
 
+    /// Whether old snapshots should be deleted from the repository.
+    pub fn has_retention(&self) -> bool {
+        self.retention_num_snapshots.is_some() || self.retention_period.is_some()
+    }
+
     pub fn snapshots_base_dir(&self) -> PathBuf {
         super::data_dir("db-snapshots")
     }
@@ -47,6 +47,7 @@ prefixed_ids! {
         Awakeable("prom"),
         Signal("sign"),
         Snapshot("snap"),
+        Schedule("sch"),
     }
 }
 
@@ -288,6 +288,11 @@ impl InvocationUuid {
         debug_assert!(id != 0);
         InvocationUuid(id)
     }
+
+    /// Generate a random [`InvocationUuid`], like the ones used for regular invocations.
+    pub fn generate_random() -> Self {
+        InvocationUuid(Ulid::new().into())
+    }
 }
 
 impl Display for InvocationUuid {
@@ -977,6 +982,13 @@ ulid_backed_id!(Deployment @with_resource_id);
 ulid_backed_id!(Subscription @with_resource_id);
 ulid_backed_id!(PartitionProcessorRpcRequest);
 ulid_backed_id!(Snapshot @with_resource_id);
+ulid_backed_id!(Schedule @with_resource_id);
+
+impl WithPartitionKey for ScheduleId {
+    fn partition_key(&self) -> PartitionKey {
+        partitioner::HashPartitioner::compute_partition_key(self.0)
+    }
+}
 
 #[derive(
     Debug, Clone, PartialEq, Eq, serde_with::SerializeDisplay, serde_with::DeserializeFromStr,
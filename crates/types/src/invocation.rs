@@ -291,6 +291,14 @@ pub struct InvocationRequestHeader {
 
     /// Retention duration of the completed status. If none, the completed status is not retained.
     pub completion_retention_duration: Option<Duration>,
+
+    /// Retention duration of the journal, once the invocation completes. If none, the journal is not retained.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal_retention_duration: Option<Duration>,
+
+    /// If true, the request is stored in the dead letter queue when the invocation terminally fails.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dead_letter_queue: bool,
 }
 
 impl InvocationRequestHeader {
@@ -303,6 +311,8 @@ impl InvocationRequestHeader {
             idempotency_key: None,
             execution_time: None,
             completion_retention_duration: None,
+            journal_retention_duration: None,
+            dead_letter_queue: false,
         }
     }
 
@@ -366,7 +376,11 @@ pub struct ServiceInvocation {
     /// Time when the request should be executed
     pub execution_time: Option<MillisSinceEpoch>,
     pub completion_retention_duration: Option<Duration>,
+    /// Retention duration of the journal, once the invocation completes. If none, the journal is not retained.
+    pub journal_retention_duration: Option<Duration>,
     pub idempotency_key: Option<ByteString>,
+    /// If true, the request is stored in the dead letter queue when the invocation terminally fails.
+    pub dead_letter_queue: bool,
 
     // Where to send the response, if any
     pub response_sink: Option<ServiceInvocationResponseSink>,
@@ -398,7 +412,9 @@ impl ServiceInvocation {
             headers: request.header.headers,
             execution_time: request.header.execution_time,
             completion_retention_duration: request.header.completion_retention_duration,
+            journal_retention_duration: request.header.journal_retention_duration,
             idempotency_key: request.header.idempotency_key,
+            dead_letter_queue: request.header.dead_letter_queue,
             response_sink: None,
             submit_notification_sink: None,
         }
@@ -419,7 +435,9 @@ impl ServiceInvocation {
             headers: vec![],
             execution_time: None,
             completion_retention_duration: None,
+            journal_retention_duration: None,
             idempotency_key: None,
+            dead_letter_queue: false,
             submit_notification_sink: None,
         }
     }
@@ -584,6 +602,44 @@ impl ServiceInvocationResponseSink {
     }
 }
 
+/// Kafka topic where the output of an invocation is published, resolved from the subscriptions
+/// to the output of the invoked handler.
+///
+/// See [`crate::schema::subscriptions::Sink::Kafka`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct KafkaSink {
+    /// Name of the cluster, as configured in the Kafka cluster options.
+    pub cluster: String,
+    pub topic: String,
+    /// Template of the record key. The placeholders `{invocation_id}`, `{service}`, `{handler}`
+    /// and `{key}` are replaced with the respective values of the completed invocation.
+    /// If none, the record is published without a key.
+    pub key_template: Option<String>,
+}
+
+impl KafkaSink {
+    /// Renders the record key of the output of the given invocation.
+    pub fn render_key(
+        &self,
+        invocation_id: &InvocationId,
+        invocation_target: &InvocationTarget,
+    ) -> Option<String> {
+        self.key_template.as_ref().map(|key_template| {
+            key_template
+                .replace("{invocation_id}", &invocation_id.to_string())
+                .replace("{service}", invocation_target.service_name())
+                .replace("{handler}", invocation_target.handler_name())
+                .replace(
+                    "{key}",
+                    &invocation_target
+                        .key()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                )
+        })
+    }
+}
+
 /// Source of an invocation
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Source {
@@ -868,6 +924,89 @@ pub struct PurgeInvocationRequest {
     pub invocation_id: InvocationId,
 }
 
+/// Message to pause an invocation.
+///
+/// A paused invocation is not dispatched to the invoker until it's resumed.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PauseInvocationRequest {
+    pub invocation_id: InvocationId,
+}
+
+/// Message to resume a paused invocation.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResumeInvocationRequest {
+    pub invocation_id: InvocationId,
+}
+
+/// Message to re-drive an entry of the dead letter queue.
+///
+/// The stored request is submitted again as a new invocation, and the entry is removed.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RedriveDeadLetterRequest {
+    /// Id of the invocation that was dead-lettered.
+    pub invocation_id: InvocationId,
+}
+
+/// Message to discard an entry of the dead letter queue.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DiscardDeadLetterRequest {
+    /// Id of the invocation that was dead-lettered.
+    pub invocation_id: InvocationId,
+}
+
+/// Behaviour when an invocation exhausts the attempts allowed by its retry policy.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Eq,
+    PartialEq,
+    strum::EnumDiscriminants,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum_discriminants(
+    name(OnMaxAttemptsAction),
+    derive(strum::Display, strum::EnumString, strum::IntoStaticStr),
+    strum(serialize_all = "snake_case")
+)]
+#[serde(
+    tag = "type",
+    rename_all = "kebab-case",
+    rename_all_fields = "kebab-case"
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum OnMaxAttempts {
+    /// # Kill
+    ///
+    /// Fail the invocation, completing it with the last error.
+    #[default]
+    Kill,
+    /// # Pause
+    ///
+    /// Pause the invocation. It can be resumed manually, starting a new series of attempts.
+    Pause,
+    /// # Dead letter
+    ///
+    /// Fail the invocation, and send its input to the given service handler.
+    /// The dead letter handler receives the same body and headers of the failed invocation.
+    /// It takes precedence over the dead letter queue, where the request is not stored.
+    DeadLetter { service: String, handler: String },
+}
+
+/// Message to restart a completed invocation as a new invocation.
+///
+/// The new invocation starts from a copy of the retained journal of the completed invocation,
+/// up to the given entry index, and is executed on the latest deployment.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RestartAsNewInvocationRequest {
+    pub invocation_id: InvocationId,
+    /// Must have the same partition key of `invocation_id`.
+    pub new_invocation_id: InvocationId,
+    /// Index of the last journal entry to copy. The input entry, at index 0, is always copied.
+    pub copy_prefix_up_to_index_included: EntryIndex,
+}
+
 // A hack to allow spancontext to be serialized.
 // Details in https://github.com/open-telemetry/opentelemetry-rust/issues/576#issuecomment-1253396100
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -1047,7 +1186,11 @@ mod serde_hacks {
         pub headers: Vec<Header>,
         pub execution_time: Option<MillisSinceEpoch>,
         pub completion_retention_duration: Option<Duration>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub journal_retention_duration: Option<Duration>,
         pub idempotency_key: Option<ByteString>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        pub dead_letter_queue: bool,
         pub response_sink: Option<ServiceInvocationResponseSink>,
         pub submit_notification_sink: Option<SubmitNotificationSink>,
 
@@ -1075,7 +1218,9 @@ mod serde_hacks {
                 headers,
                 execution_time,
                 completion_retention_duration,
+                journal_retention_duration,
                 idempotency_key,
+                dead_letter_queue,
                 response_sink,
                 submit_notification_sink,
                 source_ingress_rpc_id,
@@ -1089,7 +1234,9 @@ mod serde_hacks {
                 headers,
                 execution_time,
                 completion_retention_duration,
+                journal_retention_duration,
                 idempotency_key,
+                dead_letter_queue,
                 response_sink: response_sink.map(Into::into),
                 submit_notification_sink: submit_notification_sink.map(Into::into),
                 source: match source {
@@ -1115,7 +1262,9 @@ mod serde_hacks {
                 headers,
                 execution_time,
                 completion_retention_duration,
+                journal_retention_duration,
                 idempotency_key,
+                dead_letter_queue,
                 response_sink,
                 submit_notification_sink,
             }: super::ServiceInvocation,
@@ -1134,7 +1283,9 @@ mod serde_hacks {
                 headers,
                 execution_time,
                 completion_retention_duration,
+                journal_retention_duration,
                 idempotency_key,
+                dead_letter_queue,
                 response_sink: response_sink.map(Into::into),
                 submit_notification_sink: submit_notification_sink.map(Into::into),
                 source_ingress_rpc_id,
@@ -1363,6 +1514,7 @@ mod mocks {
                 headers: vec![],
                 execution_time: None,
                 completion_retention_duration: None,
+                journal_retention_duration: None,
                 idempotency_key: None,
                 submit_notification_sink: None,
             }
@@ -1432,4 +1584,35 @@ mod tests {
             assert_eq!(old_response.result, result);
         }
     }
+
+    #[test]
+    fn kafka_sink_render_key() {
+        let invocation_target = InvocationTarget::virtual_object(
+            "Counter",
+            "my-key",
+            "add",
+            VirtualObjectHandlerType::Exclusive,
+        );
+        let invocation_id = InvocationId::mock_random();
+        let kafka_sink = KafkaSink {
+            cluster: "my-cluster".to_owned(),
+            topic: "my-topic".to_owned(),
+            key_template: Some("{service}/{handler}/{key}/{invocation_id}".to_owned()),
+        };
+
+        assert_eq!(
+            kafka_sink
+                .render_key(&invocation_id, &invocation_target)
+                .unwrap(),
+            format!("Counter/add/my-key/{invocation_id}")
+        );
+        assert!(
+            KafkaSink {
+                key_template: None,
+                ..kafka_sink
+            }
+            .render_key(&invocation_id, &invocation_target)
+            .is_none()
+        );
+    }
 }
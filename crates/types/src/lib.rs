@@ -43,6 +43,7 @@ pub mod protobuf;
 pub mod replicated_loglet;
 pub mod replication;
 pub mod retries;
+pub mod schedule;
 pub mod schema;
 pub mod service_discovery;
 pub mod service_protocol;
@@ -12,8 +12,8 @@ use std::num::NonZeroU32;
 use std::ops::Deref;
 
 use super::metadata::{
-    Chain, LogletConfig, LogletParams, Logs, LogsConfiguration, LookupIndex, MaybeSegment,
-    ProviderKind, SegmentIndex,
+    Chain, ChainState, LogletConfig, LogletParams, Logs, LogsConfiguration, LookupIndex,
+    MaybeSegment, ProviderKind, SegmentIndex,
 };
 use super::{LogId, Lsn};
 use crate::Version;
@@ -179,6 +179,26 @@ impl ChainBuilder<'_> {
         self.inner.chain = remaining;
     }
 
+    /// Permanently seals the chain, no segments can be appended afterwards. The tail segment
+    /// must have been sealed at `tail_lsn` prior to this call.
+    pub fn seal(&mut self, tail_lsn: Lsn) -> Result<(), BuilderError> {
+        if self.inner.state.is_sealed() {
+            return Err(BuilderError::ChainPermanentlySealed(self.log_id));
+        }
+
+        self.inner.state = ChainState::Sealed { tail_lsn };
+        *self.modified = true;
+        Ok(())
+    }
+
+    /// Reopens a permanently sealed chain, so that segments can be appended to it again.
+    pub fn reopen(&mut self) {
+        if self.inner.state.is_sealed() {
+            self.inner.state = ChainState::Open;
+            *self.modified = true;
+        }
+    }
+
     /// `base_lsn` must be higher than all previous base_lsns.
     /// If `base_lsn` is identical to the tail segment, the new segment will **replace**
     /// the last segment but will still acquire a higher segment index.
@@ -701,6 +701,14 @@ impl Chain {
             .expect("Chain must have at least one segment")
     }
 
+    /// Returns the tail LSN if the chain is permanently sealed, `None` if it can still grow.
+    pub fn sealed_tail_lsn(&self) -> Option<Lsn> {
+        match self.state {
+            ChainState::Open => None,
+            ChainState::Sealed { tail_lsn } => Some(tail_lsn),
+        }
+    }
+
     #[track_caller]
     pub fn head(&self) -> Segment<'_> {
         let mut iter = self.chain.iter();
@@ -20,6 +20,7 @@ pub mod keys {
     pub static PARTITION_TABLE_KEY: ByteString = ByteString::from_static("partition_table");
     pub static SCHEMA_INFORMATION_KEY: ByteString = ByteString::from_static("schema_registry");
     // end todo
+    pub static SCHEDULES_KEY: ByteString = ByteString::from_static("schedules");
 
     pub static PARTITION_PROCESSOR_EPOCH_PREFIX: &str = "pp_epoch";
     pub fn partition_processor_epoch_key(partition_id: PartitionId) -> ByteString {
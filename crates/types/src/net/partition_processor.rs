@@ -67,6 +67,12 @@ pub enum PartitionProcessorRpcRequestInner {
     GetInvocationOutput(InvocationQuery, GetInvocationOutputResponseMode),
     AppendInvocationResponse(InvocationResponse),
     AppendSignal(InvocationId, Signal),
+    GetInvocationProgress(InvocationQuery),
+    DescribeInvocation(InvocationQuery),
+    CancelInvocation(InvocationId),
+    /// Appends a batch of invocations of the same partition, replying with
+    /// [`PartitionProcessorRpcResponse::SubmittedBatch`] once all of them have been submitted.
+    AppendInvocations(Vec<InvocationRequest>),
 }
 
 impl WithPartitionKey for PartitionProcessorRpcRequestInner {
@@ -76,6 +82,14 @@ impl WithPartitionKey for PartitionProcessorRpcRequestInner {
             PartitionProcessorRpcRequestInner::GetInvocationOutput(iq, _) => iq.partition_key(),
             PartitionProcessorRpcRequestInner::AppendInvocationResponse(ir) => ir.partition_key(),
             PartitionProcessorRpcRequestInner::AppendSignal(si, _) => si.partition_key(),
+            PartitionProcessorRpcRequestInner::GetInvocationProgress(iq) => iq.partition_key(),
+            PartitionProcessorRpcRequestInner::DescribeInvocation(iq) => iq.partition_key(),
+            PartitionProcessorRpcRequestInner::CancelInvocation(id) => id.partition_key(),
+            PartitionProcessorRpcRequestInner::AppendInvocations(invocation_requests) => {
+                invocation_requests
+                    .first()
+                    .map_or(PartitionKey::MIN, WithPartitionKey::partition_key)
+            }
         }
     }
 }
@@ -114,6 +128,48 @@ pub enum PartitionProcessorRpcResponse {
     NotSupported,
     Submitted(SubmittedInvocationNotification),
     Output(InvocationOutput),
+    Progress(InvocationProgress),
+    Description(InvocationDescription),
+    /// Submit notifications of a batch of invocations, in the order of the request.
+    SubmittedBatch(Vec<SubmittedInvocationNotification>),
+}
+
+/// Description of the invocation resolved from an [`InvocationQuery`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvocationDescription {
+    pub invocation_id: InvocationId,
+    pub invocation_target: InvocationTarget,
+    pub progress: InvocationProgress,
+    /// Output of the invocation, when it completed successfully.
+    pub output: Option<Bytes>,
+}
+
+/// Progress of an invocation, as observed by the leader of its partition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvocationProgress {
+    /// The invocation is waiting for its execution time.
+    Scheduled {
+        execution_time: Option<MillisSinceEpoch>,
+    },
+    /// The invocation is waiting in the inbox of its virtual object.
+    Inboxed,
+    /// The invocation is being executed, `attempt` starts from 1 once the invoker picks it up.
+    Invoked {
+        attempt: u32,
+    },
+    /// The invocation is waiting for a completion or a signal.
+    Suspended,
+    /// The invocation was paused and won't be executed until resumed.
+    Paused,
+    /// The last attempt failed, and the invocation will be retried.
+    Retrying {
+        retry_count: u32,
+        last_failure: Option<InvocationError>,
+        next_retry_at: Option<MillisSinceEpoch>,
+    },
+    Completed {
+        failure: Option<InvocationError>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
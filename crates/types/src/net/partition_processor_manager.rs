@@ -16,6 +16,7 @@ use crate::identifiers::{PartitionId, SnapshotId};
 use crate::logs::{LogId, Lsn};
 use crate::net::{ServiceTag, define_service, define_unary_message};
 use crate::net::{default_wire_codec, define_rpc};
+use crate::time::MillisSinceEpoch;
 
 pub struct PartitionManagerService;
 
@@ -100,4 +101,56 @@ pub struct Snapshot {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SnapshotError {
     SnapshotCreationFailed(String),
+    RepositoryNotConfigured,
+    RepositoryError(String),
+}
+
+define_rpc! {
+    @request = ListSnapshotsRequest,
+    @response = ListSnapshotsResponse,
+    @service = PartitionManagerService,
+}
+
+default_wire_codec!(ListSnapshotsRequest);
+default_wire_codec!(ListSnapshotsResponse);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListSnapshotsRequest {
+    pub partition_id: PartitionId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListSnapshotsResponse {
+    /// Snapshots ordered from the newest to the oldest.
+    pub result: Result<Vec<SnapshotInfo>, SnapshotError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub snapshot_id: SnapshotId,
+    pub min_applied_lsn: Lsn,
+    pub created_at: MillisSinceEpoch,
+    pub node_name: String,
+    /// Whether this is the latest snapshot of the partition.
+    pub latest: bool,
+}
+
+define_rpc! {
+    @request = DeleteSnapshotRequest,
+    @response = DeleteSnapshotResponse,
+    @service = PartitionManagerService,
+}
+
+default_wire_codec!(DeleteSnapshotRequest);
+default_wire_codec!(DeleteSnapshotResponse);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteSnapshotRequest {
+    pub partition_id: PartitionId,
+    pub snapshot_id: SnapshotId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteSnapshotResponse {
+    pub result: Result<(), SnapshotError>,
 }
@@ -86,6 +86,8 @@ pub struct PartitionTable {
     partition_key_index: BTreeMap<PartitionKey, PartitionId>,
 
     replication: PartitionReplication,
+    // Nodes drained by the operator, which should not lead any partition
+    drained_nodes: NodeSet,
 }
 
 impl Default for PartitionTable {
@@ -95,6 +97,7 @@ impl Default for PartitionTable {
             partitions: BTreeMap::default(),
             partition_key_index: BTreeMap::default(),
             replication: PartitionReplication::Limit(ReplicationProperty::new_unchecked(1)),
+            drained_nodes: NodeSet::default(),
         }
     }
 }
@@ -164,6 +167,12 @@ impl PartitionTable {
         &self.replication
     }
 
+    /// Nodes which are drained, and hence are not picked as leader of any partition while
+    /// another node can take over.
+    pub fn drained_nodes(&self) -> &NodeSet {
+        &self.drained_nodes
+    }
+
     pub fn into_builder(self) -> PartitionTableBuilder {
         self.into()
     }
@@ -251,6 +260,30 @@ impl PartialEq for PartitionPlacement {
     }
 }
 
+/// Placement constraints of a partition set by the operator. The scheduler honors them over its
+/// own placement decisions.
+#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlacementConstraints {
+    /// Node which should run the leader of the partition while it is alive.
+    #[serde(default)]
+    pub preferred_leader: Option<PlainNodeId>,
+    /// Nodes the partition processors are restricted to. If empty, the processors can run on any
+    /// worker node.
+    #[serde(default)]
+    pub pinned_nodes: NodeSet,
+}
+
+impl PlacementConstraints {
+    pub fn is_empty(&self) -> bool {
+        self.preferred_leader.is_none() && self.pinned_nodes.is_empty()
+    }
+
+    /// Whether the constraints allow running a partition processor on the given node.
+    pub fn allows(&self, node_id: PlainNodeId) -> bool {
+        self.pinned_nodes.is_empty() || self.pinned_nodes.contains(node_id)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct DbName(SmartString);
 
@@ -275,6 +308,12 @@ pub struct Partition {
     pub key_range: RangeInclusive<PartitionKey>,
     #[serde(default)]
     pub placement: PartitionPlacement,
+    /// Partitions this partition has been split from or merged from. The partition store of
+    /// a partition with parents is bootstrapped from the snapshots of its parents.
+    #[serde(default)]
+    pub parents: Vec<PartitionId>,
+    #[serde(default)]
+    pub placement_constraints: PlacementConstraints,
     log_id: Option<LogId>,
     db_name: Option<DbName>,
     cf_name: Option<CfName>,
@@ -286,12 +325,32 @@ impl Partition {
             partition_id,
             key_range,
             placement: PartitionPlacement::default(),
+            parents: Vec::new(),
+            placement_constraints: PlacementConstraints::default(),
             log_id: None,
             db_name: None,
             cf_name: None,
         }
     }
 
+    /// Creates a partition derived from the given parents, running where the first parent runs.
+    fn with_parents(
+        partition_id: PartitionId,
+        key_range: RangeInclusive<PartitionKey>,
+        parents: &[&Partition],
+    ) -> Self {
+        Self {
+            partition_id,
+            key_range,
+            placement: parents[0].placement.clone(),
+            parents: parents.iter().map(|parent| parent.partition_id).collect(),
+            placement_constraints: parents[0].placement_constraints.clone(),
+            log_id: None,
+            db_name: parents[0].db_name.clone(),
+            cf_name: None,
+        }
+    }
+
     pub fn log_id(&self) -> LogId {
         self.log_id
             .unwrap_or_else(|| LogId::from(self.partition_id))
@@ -317,6 +376,15 @@ pub enum BuilderError {
     Duplicate(PartitionId),
     #[error("partition table has reached its limits")]
     LimitReached,
+    #[error("partition '{0}' does not exist")]
+    UnknownPartition(PartitionId),
+    #[error("cannot split partition '{partition_id}' at partition key '{split_key}'")]
+    InvalidSplitKey {
+        partition_id: PartitionId,
+        split_key: PartitionKey,
+    },
+    #[error("partitions '{0}' and '{1}' are not adjacent")]
+    NotAdjacent(PartitionId, PartitionId),
 }
 
 #[derive(Debug, Default)]
@@ -365,6 +433,17 @@ impl PartitionTableBuilder {
         &self.inner.replication
     }
 
+    /// Marks the node as drained or undrains it. Returns whether the drained state changed.
+    pub fn set_drained(&mut self, node_id: PlainNodeId, drained: bool) -> bool {
+        let changed = if drained {
+            self.inner.drained_nodes.insert(node_id)
+        } else {
+            self.inner.drained_nodes.remove(node_id)
+        };
+        self.modified |= changed;
+        changed
+    }
+
     /// Adds a new partition to the partition table. The newly added partition must exist and must
     /// not intersect with any other partition. Otherwise, this operation fails.
     pub fn add_partition(&mut self, partition: Partition) -> Result<(), BuilderError> {
@@ -410,6 +489,123 @@ impl PartitionTableBuilder {
         }
     }
 
+    /// Replaces the partition with two partitions covering its key range, the right one
+    /// starting at `split_key`. If no split key is given, the key range is split in half.
+    ///
+    /// Returns the ids of the left and the right partition.
+    pub fn split_partition(
+        &mut self,
+        partition_id: PartitionId,
+        split_key: Option<PartitionKey>,
+    ) -> Result<(PartitionId, PartitionId), BuilderError> {
+        let parent = self
+            .inner
+            .partitions
+            .get(&partition_id)
+            .ok_or(BuilderError::UnknownPartition(partition_id))?
+            .clone();
+        let start = *parent.key_range.start();
+        let end = *parent.key_range.end();
+
+        let split_key = split_key.unwrap_or((start + (end - start) / 2).saturating_add(1));
+        if split_key <= start || split_key > end {
+            return Err(BuilderError::InvalidSplitKey {
+                partition_id,
+                split_key,
+            });
+        }
+
+        let left_id = self.next_partition_id()?;
+        let right_id = left_id.next();
+        if right_id == left_id {
+            return Err(BuilderError::LimitReached);
+        }
+
+        self.remove_partition(&partition_id);
+        self.add_partition(Partition::with_parents(
+            left_id,
+            start..=split_key - 1,
+            &[&parent],
+        ))?;
+        self.add_partition(Partition::with_parents(
+            right_id,
+            split_key..=end,
+            &[&parent],
+        ))?;
+
+        Ok((left_id, right_id))
+    }
+
+    /// Replaces two adjacent partitions with a single partition covering both key ranges.
+    ///
+    /// Returns the id of the merged partition.
+    pub fn merge_partitions(
+        &mut self,
+        left: PartitionId,
+        right: PartitionId,
+    ) -> Result<PartitionId, BuilderError> {
+        let left_partition = self
+            .inner
+            .partitions
+            .get(&left)
+            .ok_or(BuilderError::UnknownPartition(left))?
+            .clone();
+        let right_partition = self
+            .inner
+            .partitions
+            .get(&right)
+            .ok_or(BuilderError::UnknownPartition(right))?
+            .clone();
+
+        if left_partition.key_range.end().checked_add(1) != Some(*right_partition.key_range.start())
+        {
+            return Err(BuilderError::NotAdjacent(left, right));
+        }
+
+        let partition_id = self.next_partition_id()?;
+
+        self.remove_partition(&left);
+        self.remove_partition(&right);
+        self.add_partition(Partition::with_parents(
+            partition_id,
+            *left_partition.key_range.start()..=*right_partition.key_range.end(),
+            &[&left_partition, &right_partition],
+        ))?;
+
+        Ok(partition_id)
+    }
+
+    /// Replaces the placement constraints of the partition.
+    pub fn set_placement_constraints(
+        &mut self,
+        partition_id: PartitionId,
+        placement_constraints: PlacementConstraints,
+    ) -> Result<(), BuilderError> {
+        let partition = self
+            .inner
+            .partitions
+            .get_mut(&partition_id)
+            .ok_or(BuilderError::UnknownPartition(partition_id))?;
+
+        if partition.placement_constraints != placement_constraints {
+            partition.placement_constraints = placement_constraints;
+            self.modified = true;
+        }
+
+        Ok(())
+    }
+
+    /// Partition ids are never reused, so that new partitions get their own log.
+    fn next_partition_id(&self) -> Result<PartitionId, BuilderError> {
+        match self.inner.partitions.keys().next_back() {
+            Some(partition_id) if *partition_id == PartitionId::MAX => {
+                Err(BuilderError::LimitReached)
+            }
+            Some(partition_id) => Ok(partition_id.next()),
+            None => Ok(PartitionId::MIN),
+        }
+    }
+
     pub fn for_each<F>(&mut self, mut modify: F)
     where
         F: FnMut(&PartitionId, &mut PartitionPlacement),
@@ -462,6 +658,10 @@ pub struct PartitionShadow {
     pub cf_name: Option<CfName>,
     #[serde(default)]
     pub placement: PartitionPlacement,
+    #[serde(default)]
+    pub parents: Vec<PartitionId>,
+    #[serde(default)]
+    pub placement_constraints: PlacementConstraints,
 }
 
 /// Serialization helper which handles the deserialization of the current and older
@@ -479,6 +679,8 @@ struct PartitionTableShadow {
     partitions: Option<BTreeMap<PartitionId, PartitionShadow>>,
 
     replication: Option<PartitionReplication>,
+    #[serde(default)]
+    drained_nodes: NodeSet,
 }
 
 impl From<PartitionTable> for PartitionTableShadow {
@@ -498,6 +700,8 @@ impl From<PartitionTable> for PartitionTableShadow {
                             cf_name: partition.cf_name,
                             db_name: partition.db_name,
                             placement: partition.placement,
+                            parents: partition.parents,
+                            placement_constraints: partition.placement_constraints,
                         };
 
                         (partition_id, partition_shadow)
@@ -505,6 +709,7 @@ impl From<PartitionTable> for PartitionTableShadow {
                     .collect(),
             ),
             replication: Some(value.replication),
+            drained_nodes: value.drained_nodes,
         }
     }
 }
@@ -518,6 +723,7 @@ impl TryFrom<PartitionTableShadow> for PartitionTable {
         builder.set_partition_replication(value.replication.unwrap_or(
             PartitionReplication::Limit(ReplicationProperty::new_unchecked(1)),
         ));
+        builder.inner.drained_nodes = value.drained_nodes;
 
         match value.partitions {
             Some(partitions) => {
@@ -527,6 +733,8 @@ impl TryFrom<PartitionTableShadow> for PartitionTable {
                         log_id: partition_shadow.log_id,
                         key_range: partition_shadow.key_range,
                         placement: partition_shadow.placement,
+                        parents: partition_shadow.parents,
+                        placement_constraints: partition_shadow.placement_constraints,
                         db_name: partition_shadow.db_name,
                         cf_name: partition_shadow.cf_name,
                     };
@@ -612,7 +820,7 @@ mod tests {
     use crate::identifiers::{PartitionId, PartitionKey};
     use crate::partition_table::{
         EqualSizedPartitionPartitioner, FindPartition, Partition, PartitionTable,
-        PartitionTableBuilder,
+        PartitionTableBuilder, PlacementConstraints,
     };
     use crate::storage::StorageCodec;
     use crate::{PlainNodeId, Version, flexbuffers_storage_encode_decode};
@@ -721,6 +929,170 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn split_partition() -> googletest::Result<()> {
+        let mut builder = PartitionTableBuilder::new(Version::INVALID);
+        builder.with_equally_sized_partitions(2)?;
+        builder.for_each(|_, placement| placement.set_leader(PlainNodeId::new(1)));
+
+        let (left, right) = builder.split_partition(PartitionId::from(0), None)?;
+        assert_eq!(left, PartitionId::from(2));
+        assert_eq!(right, PartitionId::from(3));
+
+        let partition_table = builder.build();
+        assert_eq!(partition_table.num_partitions(), 3);
+        assert!(!partition_table.contains_partition(&PartitionId::from(0)));
+
+        let left = partition_table.get_partition(&left).unwrap();
+        let right = partition_table.get_partition(&right).unwrap();
+        assert_eq!(left.key_range, 0..=(1 << 62) - 1);
+        assert_eq!(right.key_range, (1 << 62)..=(1 << 63) - 1);
+        assert_eq!(left.parents, vec![PartitionId::from(0)]);
+        assert_eq!(right.placement.leader(), Some(PlainNodeId::new(1)));
+
+        assert_eq!(partition_table.find_partition_id(0)?, left.partition_id);
+        assert_eq!(
+            partition_table.find_partition_id(1 << 62)?,
+            right.partition_id
+        );
+        assert_eq!(
+            partition_table.find_partition_id(1 << 63)?,
+            PartitionId::from(1)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_partition_at_invalid_key() -> googletest::Result<()> {
+        let mut builder = PartitionTableBuilder::new(Version::INVALID);
+        builder.add_partition(Partition::new(PartitionId::from(0), 0..=1024))?;
+
+        assert!(
+            builder
+                .split_partition(PartitionId::from(0), Some(0))
+                .is_err()
+        );
+        assert!(
+            builder
+                .split_partition(PartitionId::from(0), Some(1025))
+                .is_err()
+        );
+        assert!(builder.split_partition(PartitionId::from(1), None).is_err());
+        assert_eq!(builder.num_partitions(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_partitions() -> googletest::Result<()> {
+        let mut builder = PartitionTableBuilder::new(Version::INVALID);
+        builder.add_partition(Partition::new(PartitionId::from(0), 0..=1024))?;
+        builder.add_partition(Partition::new(PartitionId::from(1), 1025..=2048))?;
+        builder.add_partition(Partition::new(PartitionId::from(2), 4096..=8192))?;
+
+        assert!(
+            builder
+                .merge_partitions(PartitionId::from(1), PartitionId::from(2))
+                .is_err()
+        );
+        assert!(
+            builder
+                .merge_partitions(PartitionId::from(1), PartitionId::from(0))
+                .is_err()
+        );
+
+        let merged = builder.merge_partitions(PartitionId::from(0), PartitionId::from(1))?;
+        assert_eq!(merged, PartitionId::from(3));
+
+        let partition_table = builder.build();
+        assert_eq!(partition_table.num_partitions(), 2);
+
+        let merged = partition_table.get_partition(&merged).unwrap();
+        assert_eq!(merged.key_range, 0..=2048);
+        assert_eq!(
+            merged.parents,
+            vec![PartitionId::from(0), PartitionId::from(1)]
+        );
+        assert_eq!(
+            partition_table.find_partition_id(1024)?,
+            merged.partition_id
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn placement_constraints() -> googletest::Result<()> {
+        let mut builder = PartitionTableBuilder::new(Version::INVALID);
+        builder.with_equally_sized_partitions(2)?;
+        let partition_table = builder.build();
+
+        let constraints = PlacementConstraints {
+            preferred_leader: Some(PlainNodeId::from(2)),
+            pinned_nodes: [1, 2].into(),
+        };
+        assert!(constraints.allows(PlainNodeId::from(1)));
+        assert!(!constraints.allows(PlainNodeId::from(3)));
+
+        let mut builder = partition_table.into_builder();
+        builder.set_placement_constraints(PartitionId::from(1), constraints.clone())?;
+        assert!(
+            builder
+                .set_placement_constraints(PartitionId::from(2), constraints.clone())
+                .is_err()
+        );
+        let partition_table = builder.build();
+
+        let mut buf = BytesMut::default();
+        StorageCodec::encode(&partition_table, &mut buf)?;
+        let partition_table = StorageCodec::decode::<PartitionTable, _>(&mut buf)?;
+
+        let partition = partition_table
+            .get_partition(&PartitionId::from(1))
+            .unwrap();
+        assert_eq!(partition.placement_constraints, constraints);
+
+        // the children of a split partition keep its placement constraints
+        let mut builder = partition_table.into_builder();
+        let (left, _) = builder.split_partition(PartitionId::from(1), None)?;
+        let partition_table = builder.build();
+        assert_eq!(
+            partition_table
+                .get_partition(&left)
+                .unwrap()
+                .placement_constraints,
+            constraints
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn drained_nodes() -> googletest::Result<()> {
+        let mut builder = PartitionTableBuilder::new(Version::INVALID);
+        builder.with_equally_sized_partitions(2)?;
+        assert!(builder.set_drained(PlainNodeId::from(1), true));
+        assert!(!builder.set_drained(PlainNodeId::from(1), true));
+        let partition_table = builder.build();
+
+        let mut buf = BytesMut::default();
+        StorageCodec::encode(&partition_table, &mut buf)?;
+        let partition_table = StorageCodec::decode::<PartitionTable, _>(&mut buf)?;
+        assert!(
+            partition_table
+                .drained_nodes()
+                .contains(PlainNodeId::from(1))
+        );
+
+        let mut builder = partition_table.into_builder();
+        assert!(builder.set_drained(PlainNodeId::from(1), false));
+        let partition_table = builder.build_if_modified().expect("modified");
+        assert!(partition_table.drained_nodes().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_placement_equal() {
         let placement_1 = PartitionPlacement::from_iter([
@@ -0,0 +1,371 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recurring invocations driven by cron expressions.
+//!
+//! Schedules are registered through the admin API and stored in the metadata store under
+//! [`SCHEDULES_KEY`](crate::metadata_store::keys::SCHEDULES_KEY). The partition owning the
+//! [`ScheduleId`] materializes each schedule as a timer, and fires the target invocation
+//! every time the timer expires.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+
+use crate::identifiers::{InvocationId, PartitionKey, ScheduleId, WithPartitionKey};
+use crate::invocation::InvocationTarget;
+use crate::time::MillisSinceEpoch;
+use crate::{Version, Versioned, flexbuffers_storage_encode_decode};
+
+/// Maximum number of missed runs executed at once with [`CatchUpPolicy::All`].
+pub const MAX_CATCH_UP_RUNS: usize = 100;
+
+/// Cron expression, either in the 5 fields (`min hour day-of-month month day-of-week`) or in
+/// the 6/7 fields format with leading seconds and trailing year.
+///
+/// Expressions are evaluated in UTC.
+#[derive(Clone, serde_with::SerializeDisplay, serde_with::DeserializeFromStr)]
+pub struct CronExpression {
+    expression: String,
+    schedule: cron::Schedule,
+}
+
+impl CronExpression {
+    /// Returns the first occurrence strictly after the given time, if any.
+    pub fn next_after(&self, time: MillisSinceEpoch) -> Option<MillisSinceEpoch> {
+        let after = DateTime::<Utc>::from_timestamp_millis(time.as_u64() as i64)?;
+        self.schedule
+            .after(&after)
+            .next()
+            .map(|next| MillisSinceEpoch::new(next.timestamp_millis() as u64))
+    }
+
+    /// Returns the last occurrence at or before the given time, if any.
+    pub fn last_until(&self, time: MillisSinceEpoch) -> Option<MillisSinceEpoch> {
+        // Iterating backwards yields the occurrences strictly before the start time, and the
+        // occurrences have at most the precision of a second.
+        let until = DateTime::<Utc>::from_timestamp_millis(time.as_u64() as i64 + 1)?;
+        self.schedule
+            .after(&until)
+            .next_back()
+            .map(|last| MillisSinceEpoch::new(last.timestamp_millis() as u64))
+    }
+}
+
+impl FromStr for CronExpression {
+    type Err = cron::error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim().to_owned();
+        let schedule = if expression.split_whitespace().count() == 5 {
+            // The cron crate requires the seconds field
+            cron::Schedule::from_str(&format!("0 {expression}"))?
+        } else {
+            cron::Schedule::from_str(&expression)?
+        };
+
+        Ok(Self {
+            expression,
+            schedule,
+        })
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl fmt::Debug for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CronExpression")
+            .field(&self.expression)
+            .finish()
+    }
+}
+
+impl PartialEq for CronExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.expression == other.expression
+    }
+}
+
+impl Eq for CronExpression {}
+
+/// What to do with the runs missed while the schedule timer could not fire,
+/// e.g. because the partition had no leader.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CatchUpPolicy {
+    /// # Skip
+    ///
+    /// Drop the missed runs, and run only when the timer fires before the next occurrence.
+    Skip,
+    /// # Run once
+    ///
+    /// Run once for all the missed occurrences.
+    #[default]
+    RunOnce,
+    /// # All
+    ///
+    /// Run once for every missed occurrence, up to 100 runs.
+    All,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Schedule {
+    pub id: ScheduleId,
+    pub cron: CronExpression,
+    /// Invocation target of the runs. Keyed targets always use the same key.
+    pub invocation_target: InvocationTarget,
+    pub argument: Bytes,
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+}
+
+impl Schedule {
+    /// Id of the invocation running the occurrence at `fire_time`.
+    ///
+    /// The id is deterministic, so the same occurrence is never run twice.
+    pub fn run_invocation_id(&self, fire_time: MillisSinceEpoch) -> InvocationId {
+        InvocationId::generate(
+            &self.invocation_target,
+            Some(&format!("{}-{}", self.id, fire_time.as_u64())),
+        )
+    }
+
+    /// Returns the occurrences to run when the timer due at `due` fires at `now`,
+    /// according to the [`CatchUpPolicy`].
+    pub fn runs_to_fire(
+        &self,
+        due: MillisSinceEpoch,
+        now: MillisSinceEpoch,
+    ) -> Vec<MillisSinceEpoch> {
+        let is_missed = |fire_time: &MillisSinceEpoch| *fire_time <= now;
+
+        match self.catch_up {
+            CatchUpPolicy::Skip if self.cron.next_after(due).is_some_and(|t| is_missed(&t)) => {
+                vec![]
+            }
+            CatchUpPolicy::Skip => vec![due],
+            CatchUpPolicy::RunOnce => vec![
+                self.cron
+                    .last_until(now)
+                    .filter(|last| *last > due)
+                    .unwrap_or(due),
+            ],
+            CatchUpPolicy::All => {
+                let mut missed = vec![due];
+                let mut next = self.cron.next_after(due);
+                while let Some(fire_time) = next.filter(is_missed) {
+                    if missed.len() >= MAX_CATCH_UP_RUNS {
+                        break;
+                    }
+                    missed.push(fire_time);
+                    next = self.cron.next_after(fire_time);
+                }
+                missed
+            }
+        }
+    }
+}
+
+impl WithPartitionKey for Schedule {
+    fn partition_key(&self) -> PartitionKey {
+        self.id.partition_key()
+    }
+}
+
+/// Fire the schedule timer due at `due`.
+///
+/// Proposed by the leader when the schedule timer expires, `fired_at` is the leader time
+/// used to evaluate the [`CatchUpPolicy`], so the runs are the same on all the replicas.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FireScheduleRequest {
+    pub schedule_id: ScheduleId,
+    pub due: MillisSinceEpoch,
+    pub fired_at: MillisSinceEpoch,
+}
+
+impl WithPartitionKey for FireScheduleRequest {
+    fn partition_key(&self) -> PartitionKey {
+        self.schedule_id.partition_key()
+    }
+}
+
+/// All the registered schedules, stored in the metadata store.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Schedules {
+    version: Version,
+    schedules: BTreeMap<ScheduleId, Schedule>,
+}
+
+impl Default for Schedules {
+    fn default() -> Self {
+        Self {
+            version: Version::INVALID,
+            schedules: BTreeMap::default(),
+        }
+    }
+}
+
+impl Schedules {
+    pub fn get(&self, schedule_id: &ScheduleId) -> Option<&Schedule> {
+        self.schedules.get(schedule_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Schedule> {
+        self.schedules.values()
+    }
+
+    pub fn insert(&mut self, schedule: Schedule) {
+        self.schedules.insert(schedule.id, schedule);
+        self.version = self.version.next();
+    }
+
+    pub fn remove(&mut self, schedule_id: &ScheduleId) -> Option<Schedule> {
+        let removed = self.schedules.remove(schedule_id);
+        if removed.is_some() {
+            self.version = self.version.next();
+        }
+        removed
+    }
+}
+
+impl Versioned for Schedules {
+    fn version(&self) -> Version {
+        self.version
+    }
+}
+
+flexbuffers_storage_encode_decode!(Schedules);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(cron: &str, catch_up: CatchUpPolicy) -> Schedule {
+        Schedule {
+            id: ScheduleId::new(),
+            cron: cron.parse().unwrap(),
+            invocation_target: InvocationTarget::mock_service(),
+            argument: Bytes::new(),
+            catch_up,
+        }
+    }
+
+    const MINUTE: u64 = 60 * 1000;
+
+    #[test]
+    fn five_fields_expression() {
+        let cron: CronExpression = "*/5 * * * *".parse().unwrap();
+
+        assert_eq!(cron.to_string(), "*/5 * * * *");
+        assert_eq!(
+            cron.next_after(MillisSinceEpoch::new(MINUTE)),
+            Some(MillisSinceEpoch::new(5 * MINUTE))
+        );
+        assert_eq!(
+            cron.next_after(MillisSinceEpoch::new(5 * MINUTE)),
+            Some(MillisSinceEpoch::new(10 * MINUTE))
+        );
+    }
+
+    #[test]
+    fn invalid_expression() {
+        assert!("every five minutes".parse::<CronExpression>().is_err());
+    }
+
+    #[test]
+    fn fire_on_time() {
+        let due = MillisSinceEpoch::new(5 * MINUTE);
+        let now = MillisSinceEpoch::new(5 * MINUTE + 10);
+
+        for catch_up in [
+            CatchUpPolicy::Skip,
+            CatchUpPolicy::RunOnce,
+            CatchUpPolicy::All,
+        ] {
+            assert_eq!(
+                schedule("*/5 * * * *", catch_up).runs_to_fire(due, now),
+                vec![due]
+            );
+        }
+    }
+
+    #[test]
+    fn catch_up_missed_runs() {
+        let due = MillisSinceEpoch::new(5 * MINUTE);
+        let now = MillisSinceEpoch::new(17 * MINUTE);
+
+        assert_eq!(
+            schedule("*/5 * * * *", CatchUpPolicy::Skip).runs_to_fire(due, now),
+            vec![]
+        );
+        assert_eq!(
+            schedule("*/5 * * * *", CatchUpPolicy::RunOnce).runs_to_fire(due, now),
+            vec![MillisSinceEpoch::new(15 * MINUTE)]
+        );
+        assert_eq!(
+            schedule("*/5 * * * *", CatchUpPolicy::All).runs_to_fire(due, now),
+            vec![
+                MillisSinceEpoch::new(5 * MINUTE),
+                MillisSinceEpoch::new(10 * MINUTE),
+                MillisSinceEpoch::new(15 * MINUTE)
+            ]
+        );
+    }
+
+    #[test]
+    fn catch_up_all_is_capped() {
+        let due = MillisSinceEpoch::new(MINUTE);
+        let now = MillisSinceEpoch::new(1000 * MINUTE);
+
+        assert_eq!(
+            schedule("* * * * *", CatchUpPolicy::All)
+                .runs_to_fire(due, now)
+                .len(),
+            MAX_CATCH_UP_RUNS
+        );
+    }
+
+    #[test]
+    fn run_once_after_long_downtime() {
+        let due = MillisSinceEpoch::new(1000);
+        // A year of missed runs, one every second
+        let now = MillisSinceEpoch::new(365 * 24 * 60 * MINUTE + 500);
+
+        assert_eq!(
+            schedule("* * * * * *", CatchUpPolicy::RunOnce).runs_to_fire(due, now),
+            vec![MillisSinceEpoch::new(365 * 24 * 60 * MINUTE)]
+        );
+        assert_eq!(
+            schedule("* * * * * *", CatchUpPolicy::Skip).runs_to_fire(due, now),
+            vec![]
+        );
+    }
+}
@@ -9,12 +9,15 @@
 // by the Apache License, Version 2.0.
 
 use super::Schema;
+use crate::config::InvokerOptions;
 use crate::invocation::InvocationTargetType;
+use crate::retries::RetryPolicy;
 
 use bytes::Bytes;
 use bytestring::ByteString;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::time::Duration;
 use std::{cmp, fmt};
@@ -29,6 +32,20 @@ pub struct InvocationTargetMetadata {
     pub completion_retention: Option<Duration>,
     /// Retention timer that should be used only if an idempotency key is set. See [`InvocationTargetMetadata::compute_retention`] for more details.
     pub idempotency_retention: Duration,
+    /// Retention timer for the journal of completed invocations. When set, the journal is kept
+    /// together with the completed invocation, and can be used to restart the invocation from a given entry.
+    #[serde(default)]
+    pub journal_retention: Option<Duration>,
+    /// Retry policy overriding the invoker default, if any.
+    /// This is the most specific policy between the handler and the service retry policy.
+    #[serde(default)]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+    /// If true, the request is stored in the dead letter queue when the invocation terminally fails.
+    #[serde(default)]
+    pub dead_letter_queue: bool,
+    /// If true, the ingress validates the request body against the JSON schema of the input rules.
+    #[serde(default)]
+    pub validate_input: bool,
     pub target_ty: InvocationTargetType,
     pub input_rules: InputRules,
     pub output_rules: OutputRules,
@@ -47,6 +64,87 @@ impl InvocationTargetMetadata {
     }
 }
 
+/// Retry policy overriding the retry policy of the invoker for the invocations of a service or handler.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", rename_all_fields = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum InvocationRetryPolicy {
+    /// # Policy
+    ///
+    /// Retry policy replacing the retry policy of the invoker.
+    Policy(RetryPolicy),
+    /// # Exponential
+    ///
+    /// Exponential retry policy declared by the service endpoint.
+    /// The unset fields are taken from the retry policy of the invoker.
+    Exponential {
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "serde_with::As::<Option<serde_with::DisplayFromStr>>"
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+        initial_interval: Option<humantime::Duration>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        factor: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_attempts: Option<NonZeroUsize>,
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "serde_with::As::<Option<serde_with::DisplayFromStr>>"
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+        max_interval: Option<humantime::Duration>,
+    },
+}
+
+impl InvocationRetryPolicy {
+    /// Returns the retry policy to use, given the retry policy of the invoker.
+    ///
+    /// When the invoker doesn't retry, the unset fields of an exponential policy are taken from the
+    /// default invoker retry policy.
+    pub fn resolve(&self, invoker_retry_policy: &RetryPolicy) -> RetryPolicy {
+        match self {
+            InvocationRetryPolicy::Policy(retry_policy) => retry_policy.clone(),
+            InvocationRetryPolicy::Exponential {
+                initial_interval,
+                factor,
+                max_attempts,
+                max_interval,
+            } => {
+                let (
+                    default_initial_interval,
+                    default_factor,
+                    default_max_attempts,
+                    default_max_interval,
+                ) = match invoker_retry_policy {
+                    RetryPolicy::None => {
+                        return self.resolve(&InvokerOptions::default().retry_policy);
+                    }
+                    RetryPolicy::FixedDelay {
+                        interval,
+                        max_attempts,
+                    } => (*interval, 1.0, *max_attempts, None),
+                    RetryPolicy::Exponential {
+                        initial_interval,
+                        factor,
+                        max_attempts,
+                        max_interval,
+                    } => (*initial_interval, *factor, *max_attempts, *max_interval),
+                };
+
+                RetryPolicy::Exponential {
+                    initial_interval: initial_interval.unwrap_or(default_initial_interval),
+                    factor: factor.unwrap_or(default_factor),
+                    max_attempts: max_attempts.or(default_max_attempts),
+                    max_interval: max_interval.or(default_max_interval),
+                }
+            }
+        }
+    }
+}
+
 /// This API resolves invocation targets.
 pub trait InvocationTargetResolver {
     /// Returns None if the service handler doesn't exist, Some(basic_service_metadata) otherwise.
@@ -114,9 +212,13 @@ impl InputRules {
     }
 
     pub fn json_schema(&self) -> Option<serde_json::Value> {
+        self.json_schema_ref().cloned()
+    }
+
+    pub fn json_schema_ref(&self) -> Option<&serde_json::Value> {
         for rule in &self.input_validation_rules {
             if let InputValidationRule::JsonValue { schema, .. } = rule {
-                return schema.clone();
+                return schema.as_ref();
             }
         }
         None
@@ -188,8 +290,8 @@ pub enum InputValidationRule {
     JsonValue {
         // Can use wildcards
         content_type: InputContentType,
-        // Right now we don't use this schema for anything except printing,
-        // so no need to use a more specialized type (we validate the schema is valid inside the schema registry updater)
+        // Used for printing, and by the ingress to validate the input when enabled for the handler.
+        // We validate the schema is valid inside the schema registry updater
         schema: Option<serde_json::Value>,
     },
 }
@@ -494,6 +596,10 @@ pub mod test_util {
                 public: true,
                 idempotency_retention: DEFAULT_IDEMPOTENCY_RETENTION,
                 completion_retention: None,
+                journal_retention: None,
+                retry_policy: None,
+                dead_letter_queue: false,
+                validate_input: false,
                 target_ty: invocation_target_type,
                 input_rules: Default::default(),
                 output_rules: Default::default(),
@@ -697,4 +803,43 @@ mod tests {
         assert_eq!(input_rules.infer_content_type(false), None);
         assert_eq!(input_rules.infer_content_type(true), None);
     }
+
+    #[test]
+    fn retry_policy_defaults_from_invoker() {
+        let retry_policy = InvocationRetryPolicy::Exponential {
+            initial_interval: None,
+            factor: Some(3.0),
+            max_attempts: None,
+            max_interval: None,
+        };
+
+        assert_eq!(
+            retry_policy.resolve(&RetryPolicy::exponential(
+                Duration::from_millis(100),
+                2.0,
+                Some(10),
+                Some(Duration::from_secs(5)),
+            )),
+            RetryPolicy::exponential(
+                Duration::from_millis(100),
+                3.0,
+                Some(10),
+                Some(Duration::from_secs(5)),
+            )
+        );
+        assert_eq!(
+            retry_policy.resolve(&RetryPolicy::fixed_delay(Duration::from_secs(1), Some(3))),
+            RetryPolicy::exponential(Duration::from_secs(1), 3.0, Some(3), None)
+        );
+        assert_eq!(
+            retry_policy.resolve(&RetryPolicy::None),
+            retry_policy.resolve(&InvokerOptions::default().retry_policy)
+        );
+
+        let fixed_delay = RetryPolicy::fixed_delay(Duration::from_secs(1), Some(3));
+        assert_eq!(
+            InvocationRetryPolicy::Policy(fixed_delay.clone()).resolve(&RetryPolicy::None),
+            fixed_delay
+        );
+    }
 }
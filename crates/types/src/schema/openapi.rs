@@ -740,6 +740,29 @@ fn restate_error_json_schema() -> Value {
             "stacktrace": {
                 "type": "string",
                 "title": "Stacktrace of the error"
+            },
+            "violations": {
+                "type": "array",
+                "title": "Input JSON schema violations",
+                "description": "Present when the request body doesn't match the input JSON schema of the handler",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "instancePath": {
+                            "type": "string",
+                            "title": "JSON pointer to the invalid value of the request body"
+                        },
+                        "schemaPath": {
+                            "type": "string",
+                            "title": "JSON pointer to the violated keyword of the schema"
+                        },
+                        "message": {
+                            "type": "string",
+                            "title": "Violation message"
+                        }
+                    },
+                    "required": ["instancePath", "schemaPath", "message"]
+                }
             }
         },
         "required": ["message"],
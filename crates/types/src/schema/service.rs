@@ -9,7 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use super::Schema;
-use super::invocation_target::InvocationTargetMetadata;
+use super::invocation_target::{InvocationRetryPolicy, InvocationTargetMetadata};
 use crate::config::Configuration;
 use crate::identifiers::{DeploymentId, ServiceRevision};
 use crate::invocation::{
@@ -17,6 +17,7 @@ use crate::invocation::{
 };
 use crate::schema::openapi::ServiceOpenAPI;
 use arc_swap::ArcSwapOption;
+use bytes::Bytes;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_with::serde_as;
@@ -83,6 +84,18 @@ pub struct ServiceMetadata {
     #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
     pub workflow_completion_retention: Option<humantime::Duration>,
 
+    /// # Journal retention
+    ///
+    /// The retention duration of the journal of completed invocations.
+    /// When set, the journal can be used to restart completed invocations from a given entry.
+    #[serde(
+        with = "serde_with::As::<Option<serde_with::DisplayFromStr>>",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub journal_retention: Option<humantime::Duration>,
+
     /// # Inactivity timeout
     ///
     /// This timer guards against stalled service/handler invocations. Once it expires,
@@ -123,6 +136,92 @@ pub struct ServiceMetadata {
     )]
     #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
     pub abort_timeout: Option<humantime::Duration>,
+
+    /// # Retry policy
+    ///
+    /// Retry policy to use for the invocations of this service.
+    ///
+    /// This overrides the default retry policy set in invoker options,
+    /// and can be further overridden by the retry policy of the single handlers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+
+    /// # Dead letter queue
+    ///
+    /// If true, the requests of invocations of this service that terminally fail are stored in the dead letter queue.
+    /// Dead letters can be inspected through the `sys_dead_letter` table, and redriven or discarded through the admin API.
+    /// Killed and canceled invocations are not stored, nor are the requests sent to a dead letter handler by the retry policy.
+    #[serde(default)]
+    pub dead_letter_queue: bool,
+
+    /// # CORS policy
+    ///
+    /// Overrides of the ingress CORS policy for the handlers of this service.
+    /// The overrides apply only when a CORS policy is configured in the ingress options.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors: Option<ServiceCorsPolicy>,
+
+    /// # Input validation
+    ///
+    /// If true, the ingress validates the request bodies against the input JSON schemas of the handlers,
+    /// rejecting the invalid ones before creating the invocation.
+    /// Handlers can override this through the `ingress.validate-input` handler metadata.
+    #[serde(default)]
+    pub validate_input: bool,
+
+    /// # State TTL
+    ///
+    /// Time to live of the state entries of this virtual object or workflow, counted from their last write.
+    /// Expired entries are deleted in the background, while no invocation is running for their key.
+    /// Entries written before the TTL was set don't expire until they're written again.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde(
+        with = "serde_with::As::<Option<serde_with::DisplayFromStr>>",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub state_ttl: Option<humantime::Duration>,
+}
+
+/// # Service CORS policy
+///
+/// Fields which are not set fall back to the CORS policy configured in the ingress options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ServiceCorsPolicy {
+    /// # Allowed origins
+    ///
+    /// Origins allowed to invoke the service, `*` allows any origin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_origins: Option<Vec<String>>,
+
+    /// # Allowed methods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_methods: Option<Vec<String>>,
+
+    /// # Allowed headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_headers: Option<Vec<String>>,
+
+    /// # Allow credentials
+    ///
+    /// Enabling credentials requires setting the allowed origins as well, without `*`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_credentials: Option<bool>,
+}
+
+impl ServiceCorsPolicy {
+    /// Whether the policy enables credentials without an explicit list of allowed origins, either
+    /// by falling back to the origins of the ingress options or by allowing `*`.
+    pub fn allows_credentials_from_unlisted_origins(&self) -> bool {
+        self.allow_credentials == Some(true)
+            && self
+                .allowed_origins
+                .as_ref()
+                .is_none_or(|origins| origins.iter().any(|origin| origin == "*"))
+    }
 }
 
 // This type is used only for exposing the handler metadata, and not internally. See [ServiceAndHandlerType].
@@ -191,6 +290,14 @@ pub struct HandlerMetadata {
     /// JSON Schema of the handler output
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_json_schema: Option<serde_json::Value>,
+
+    /// # Retry policy
+    ///
+    /// Retry policy to use for the invocations of this handler.
+    ///
+    /// This overrides both the retry policy of the service and the default retry policy set in invoker options.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<InvocationRetryPolicy>,
 }
 
 /// This API will return services registered by the user.
@@ -204,6 +311,38 @@ pub trait ServiceMetadataResolver {
 
     fn resolve_latest_service_type(&self, service_name: impl AsRef<str>) -> Option<ServiceType>;
 
+    /// Returns the latest revision of the service, if it exists.
+    fn resolve_latest_service_revision(
+        &self,
+        service_name: impl AsRef<str>,
+    ) -> Option<ServiceRevision> {
+        self.resolve_latest_service(service_name)
+            .map(|service| service.revision)
+    }
+
+    fn resolve_latest_service_cors(
+        &self,
+        service_name: impl AsRef<str>,
+    ) -> Option<ServiceCorsPolicy> {
+        self.resolve_latest_service(service_name)
+            .and_then(|service| service.cors)
+    }
+
+    /// Returns the TTL of the state entries of the service, if any.
+    fn resolve_latest_service_state_ttl(&self, service_name: impl AsRef<str>) -> Option<Duration> {
+        self.resolve_latest_service(service_name)
+            .and_then(|service| service.state_ttl)
+            .map(Into::into)
+    }
+
+    /// Returns the protobuf schemas of the service, if it declares any.
+    fn resolve_latest_service_protobuf(
+        &self,
+        _service_name: impl AsRef<str>,
+    ) -> Option<ServiceProtobufSchemas> {
+        None
+    }
+
     fn list_services(&self) -> Vec<ServiceMetadata>;
 }
 
@@ -214,6 +353,51 @@ pub struct HandlerSchemas {
     pub documentation: Option<String>,
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
+    /// Retry policy override declared for this handler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+    /// Protobuf schemas of the handler, if the service declares a protobuf descriptor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protobuf: Option<ProtobufHandlerSchemas>,
+}
+
+/// Service metadata key of the base64 encoded, serialized `google.protobuf.FileDescriptorSet`
+/// declaring the protobuf schemas of a service. The set must contain a gRPC service with the same
+/// fully qualified name of the Restate service, whose methods are matched to the handlers by name.
+pub const PROTOBUF_DESCRIPTOR_SET_METADATA_KEY: &str = "protobuf.descriptor-set";
+
+/// Handler metadata key overriding the [`ServiceSchemas::validate_input`] setting of the service
+/// for a single handler, either `true` or `false`.
+pub const VALIDATE_INPUT_METADATA_KEY: &str = "ingress.validate-input";
+
+/// Protobuf schemas of a handler, making it callable through the gRPC and Connect protocols.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtobufHandlerSchemas {
+    /// Name of the gRPC method, as declared in the service descriptor.
+    pub method: String,
+    /// Fully qualified name of the input message.
+    pub input_type: String,
+    /// Fully qualified name of the output message.
+    pub output_type: String,
+}
+
+/// Protobuf schemas of a service, see [`PROTOBUF_DESCRIPTOR_SET_METADATA_KEY`].
+#[derive(Debug, Clone)]
+pub struct ServiceProtobufSchemas {
+    /// Serialized `google.protobuf.FileDescriptorSet` of the service.
+    pub file_descriptor_set: Bytes,
+    /// Protobuf schemas of the handlers, by handler name.
+    pub handlers: HashMap<String, ProtobufHandlerSchemas>,
+}
+
+impl ServiceProtobufSchemas {
+    /// Resolves the handler serving the given gRPC method.
+    pub fn resolve_method(&self, method: &str) -> Option<(&str, &ProtobufHandlerSchemas)> {
+        self.handlers
+            .iter()
+            .find(|(_, schemas)| schemas.method == method)
+            .map(|(handler, schemas)| (handler.as_str(), schemas))
+    }
 }
 
 impl HandlerSchemas {
@@ -227,6 +411,7 @@ impl HandlerSchemas {
             output_description: self.target_meta.output_rules.to_string(),
             input_json_schema: self.target_meta.input_rules.json_schema(),
             output_json_schema: self.target_meta.output_rules.json_schema(),
+            retry_policy: self.retry_policy.clone(),
         }
     }
 }
@@ -239,12 +424,27 @@ pub struct ServiceSchemas {
     pub location: ServiceLocation,
     pub idempotency_retention: Duration,
     pub workflow_completion_retention: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal_retention: Option<Duration>,
     pub inactivity_timeout: Option<Duration>,
     pub abort_timeout: Option<Duration>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+    #[serde(default)]
+    pub dead_letter_queue: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors: Option<ServiceCorsPolicy>,
+    #[serde(default)]
+    pub validate_input: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_ttl: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub documentation: Option<String>,
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
+    /// Decoded value of the [`PROTOBUF_DESCRIPTOR_SET_METADATA_KEY`] metadata, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protobuf_file_descriptor_set: Option<Bytes>,
 
     /// This is a cache for the computed value of ServiceOpenAPI
     #[serde(skip)]
@@ -268,11 +468,28 @@ impl ServiceSchemas {
             public: self.location.public,
             idempotency_retention: self.idempotency_retention.into(),
             workflow_completion_retention: self.workflow_completion_retention.map(Into::into),
+            journal_retention: self.journal_retention.map(Into::into),
             inactivity_timeout: self.inactivity_timeout.map(Into::into),
             abort_timeout: self.abort_timeout.map(Into::into),
+            retry_policy: self.retry_policy.clone(),
+            dead_letter_queue: self.dead_letter_queue,
+            cors: self.cors.clone(),
+            validate_input: self.validate_input,
+            state_ttl: self.state_ttl.map(Into::into),
         }
     }
 
+    pub fn protobuf(&self) -> Option<ServiceProtobufSchemas> {
+        Some(ServiceProtobufSchemas {
+            file_descriptor_set: self.protobuf_file_descriptor_set.clone()?,
+            handlers: self
+                .handlers
+                .iter()
+                .filter_map(|(name, handler)| Some((name.clone(), handler.protobuf.clone()?)))
+                .collect(),
+        })
+    }
+
     pub fn openapi_spec(&self, name: &str) -> serde_json::Value {
         let service_openapi = {
             let cached_openapi = self.service_openapi_cache.load();
@@ -331,6 +548,42 @@ impl ServiceMetadataResolver for Schema {
         self.use_service_schema(service_name.as_ref(), |service_schemas| service_schemas.ty)
     }
 
+    fn resolve_latest_service_revision(
+        &self,
+        service_name: impl AsRef<str>,
+    ) -> Option<ServiceRevision> {
+        self.use_service_schema(service_name.as_ref(), |service_schemas| {
+            service_schemas.revision
+        })
+    }
+
+    fn resolve_latest_service_cors(
+        &self,
+        service_name: impl AsRef<str>,
+    ) -> Option<ServiceCorsPolicy> {
+        self.use_service_schema(service_name.as_ref(), |service_schemas| {
+            service_schemas.cors.clone()
+        })
+        .flatten()
+    }
+
+    fn resolve_latest_service_state_ttl(&self, service_name: impl AsRef<str>) -> Option<Duration> {
+        self.use_service_schema(service_name.as_ref(), |service_schemas| {
+            service_schemas.state_ttl
+        })
+        .flatten()
+    }
+
+    fn resolve_latest_service_protobuf(
+        &self,
+        service_name: impl AsRef<str>,
+    ) -> Option<ServiceProtobufSchemas> {
+        self.use_service_schema(service_name.as_ref(), |service_schemas| {
+            service_schemas.protobuf()
+        })
+        .flatten()
+    }
+
     fn list_services(&self) -> Vec<ServiceMetadata> {
         self.services
             .iter()
@@ -398,6 +651,7 @@ pub mod test_util {
                         output_description: "any".to_string(),
                         input_json_schema: None,
                         output_json_schema: None,
+                        retry_policy: None,
                     })
                     .collect(),
                 ty: ServiceType::Service,
@@ -408,8 +662,14 @@ pub mod test_util {
                 public: true,
                 idempotency_retention: Duration::from_secs(60).into(),
                 workflow_completion_retention: None,
+                journal_retention: None,
                 inactivity_timeout: None,
                 abort_timeout: None,
+                retry_policy: None,
+                dead_letter_queue: false,
+                cors: None,
+                validate_input: false,
+                state_ttl: None,
             }
         }
 
@@ -430,6 +690,7 @@ pub mod test_util {
                         output_description: "any".to_string(),
                         input_json_schema: None,
                         output_json_schema: None,
+                        retry_policy: None,
                     })
                     .collect(),
                 ty: ServiceType::VirtualObject,
@@ -440,8 +701,14 @@ pub mod test_util {
                 public: true,
                 idempotency_retention: Duration::from_secs(60).into(),
                 workflow_completion_retention: None,
+                journal_retention: None,
                 inactivity_timeout: None,
                 abort_timeout: None,
+                retry_policy: None,
+                dead_letter_queue: false,
+                cors: None,
+                validate_input: false,
+                state_ttl: None,
             }
         }
     }
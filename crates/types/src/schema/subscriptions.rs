@@ -19,12 +19,28 @@ use super::Schema;
 use crate::config::IngressOptions;
 use crate::errors::GenericError;
 use crate::identifiers::SubscriptionId;
-use crate::invocation::{VirtualObjectHandlerType, WorkflowHandlerType};
+use crate::invocation::{KafkaSink, VirtualObjectHandlerType, WorkflowHandlerType};
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Source {
-    Kafka { cluster: String, topic: String },
+    Kafka {
+        cluster: String,
+        topic: String,
+    },
+    /// NDJSON file, or directory of NDJSON files, tailed by the worker.
+    File {
+        path: String,
+    },
+    /// Webhook configured in the [`IngressOptions`], receiving signed deliveries on the ingress.
+    Webhook {
+        name: String,
+    },
+    /// Output of the completed invocations of a service handler, see [`Sink::Kafka`].
+    Service {
+        name: String,
+        handler: String,
+    },
 }
 
 impl fmt::Display for Source {
@@ -33,6 +49,15 @@ impl fmt::Display for Source {
             Source::Kafka { cluster, topic, .. } => {
                 write!(f, "kafka://{cluster}/{topic}")
             }
+            Source::File { path } => {
+                write!(f, "file://localhost{path}")
+            }
+            Source::Webhook { name } => {
+                write!(f, "webhook://{name}")
+            }
+            Source::Service { name, handler } => {
+                write!(f, "service://{name}/{handler}")
+            }
         }
     }
 }
@@ -52,6 +77,9 @@ pub enum EventReceiverServiceType {
     Service,
 }
 
+/// Subscription option holding the template of the key of the records published to a [`Sink::Kafka`].
+pub const KAFKA_SINK_KEY_TEMPLATE_OPTION: &str = "key_template";
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Sink {
@@ -65,6 +93,17 @@ pub enum Sink {
     Invocation {
         event_invocation_target_template: EventInvocationTargetTemplate,
     },
+    /// Kafka topic where the output of the invocations of a [`Source::Service`] is published.
+    ///
+    /// Records are published exactly once, in Kafka transactions: consumers must read the topic
+    /// with `isolation.level=read_committed`. The key template is configured through the
+    /// [`KAFKA_SINK_KEY_TEMPLATE_OPTION`] subscription option.
+    Kafka {
+        cluster: String,
+        topic: String,
+        /// Template of the record key, see [`KafkaSink::key_template`].
+        key_template: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -106,6 +145,9 @@ impl fmt::Display for Sink {
             } => {
                 write!(f, "service://{name}/{handler}")
             }
+            Sink::Kafka { cluster, topic, .. } => {
+                write!(f, "kafka://{cluster}/{topic}")
+            }
         }
     }
 }
@@ -123,6 +165,9 @@ pub struct Subscription {
     source: Source,
     sink: Sink,
     metadata: HashMap<String, String>,
+    /// If true, the events whose invocation terminally fails are stored in the dead letter queue.
+    #[serde(default)]
+    dead_letter_queue: bool,
 }
 
 impl Subscription {
@@ -131,12 +176,14 @@ impl Subscription {
         source: Source,
         sink: Sink,
         metadata: HashMap<String, String>,
+        dead_letter_queue: bool,
     ) -> Self {
         Self {
             id,
             source,
             sink,
             metadata,
+            dead_letter_queue,
         }
     }
 
@@ -159,6 +206,10 @@ impl Subscription {
     pub fn metadata_mut(&mut self) -> &mut HashMap<String, String> {
         &mut self.metadata
     }
+
+    pub fn dead_letter_queue(&self) -> bool {
+        self.dead_letter_queue
+    }
 }
 
 pub enum ListSubscriptionFilter {
@@ -179,6 +230,9 @@ pub trait SubscriptionResolver {
     fn get_subscription(&self, id: SubscriptionId) -> Option<Subscription>;
 
     fn list_subscriptions(&self, filters: &[ListSubscriptionFilter]) -> Vec<Subscription>;
+
+    /// Kafka sinks where the output of the invocations of the given handler is published.
+    fn resolve_kafka_sinks(&self, service_name: &str, handler_name: &str) -> Vec<KafkaSink>;
 }
 
 impl SubscriptionResolver for Schema {
@@ -200,6 +254,27 @@ impl SubscriptionResolver for Schema {
             .cloned()
             .collect()
     }
+
+    fn resolve_kafka_sinks(&self, service_name: &str, handler_name: &str) -> Vec<KafkaSink> {
+        self.subscriptions
+            .values()
+            .filter_map(|sub| match (&sub.source, &sub.sink) {
+                (
+                    Source::Service { name, handler },
+                    Sink::Kafka {
+                        cluster,
+                        topic,
+                        key_template,
+                    },
+                ) if name == service_name && handler == handler_name => Some(KafkaSink {
+                    cluster: cluster.clone(),
+                    topic: topic.clone(),
+                    key_template: key_template.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 pub trait SubscriptionValidator {
@@ -219,8 +294,53 @@ impl SubscriptionValidator for IngressOptions {
     type Error = ValidationError;
 
     fn validate(&self, mut subscription: Subscription) -> Result<Subscription, Self::Error> {
+        if matches!(subscription.sink(), Sink::Kafka { .. })
+            && !matches!(subscription.source(), Source::Service { .. })
+        {
+            return Err(ValidationError {
+                name: "sink",
+                reason: "only the output of a service can be published to a Kafka sink",
+            });
+        }
+
+        let cluster = match subscription.source() {
+            Source::Kafka { cluster, .. } => cluster,
+            Source::File { path } => {
+                if !std::path::Path::new(path).is_absolute() {
+                    return Err(ValidationError {
+                        name: "source",
+                        reason: "the path in the file source URI must be absolute",
+                    });
+                }
+                return Ok(subscription);
+            }
+            Source::Webhook { name } => {
+                if self.get_webhook(name).is_none() {
+                    return Err(ValidationError {
+                        name: "source",
+                        reason: "specified webhook in the source URI does not exist. Make sure it is defined in the IngressOptions",
+                    });
+                }
+                return Ok(subscription);
+            }
+            Source::Service { .. } => {
+                let Sink::Kafka { cluster, .. } = subscription.sink() else {
+                    return Err(ValidationError {
+                        name: "sink",
+                        reason: "the output of a service can only be published to a Kafka sink",
+                    });
+                };
+                if self.get_kafka_cluster(cluster).is_none() {
+                    return Err(ValidationError {
+                        name: "sink",
+                        reason: "specified cluster in the sink URI does not exist. Make sure it is defined in the KafkaOptions",
+                    });
+                }
+                return Ok(subscription);
+            }
+        };
+
         // Retrieve the cluster option and merge them with subscription metadata
-        let Source::Kafka { cluster, .. } = subscription.source();
         let cluster_options = &self.get_kafka_cluster(cluster).ok_or(ValidationError {
             name: "source",
             reason: "specified cluster in the source URI does not exist. Make sure it is defined in the KafkaOptions",
@@ -290,6 +410,7 @@ pub mod mocks {
                     },
                 },
                 metadata: Default::default(),
+                dead_letter_queue: false,
             }
         }
     }
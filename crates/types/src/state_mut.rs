@@ -8,15 +8,16 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
 
 use base64::Engine;
 use bytes::Bytes;
 use serde_with::serde_as;
 use sha2::{Digest, Sha256};
 
-use crate::identifiers::ServiceId;
+use crate::identifiers::{PartitionKey, ServiceId, WithPartitionKey};
 
 #[serde_as]
 /// ExternalStateMutation
@@ -31,6 +32,33 @@ pub struct ExternalStateMutation {
     pub state: HashMap<Bytes, Bytes>,
 }
 
+/// Request to delete a state entry whose TTL expired.
+///
+/// Proposed by the leader, the entry is deleted only if it was not written again since,
+/// i.e. if its write `version`, the LSN of the last write, is still the same.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExpireStateRequest {
+    pub service_id: ServiceId,
+    pub state_key: Bytes,
+    pub version: u64,
+}
+
+/// Services with a state TTL.
+///
+/// Proposed by the leader when they change, so that all the replicas of the partition track the
+/// writes of the state entries of these services, and only of these services.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StateTtlServices {
+    pub partition_key_range: RangeInclusive<PartitionKey>,
+    pub services: BTreeSet<String>,
+}
+
+impl WithPartitionKey for ExpireStateRequest {
+    fn partition_key(&self) -> PartitionKey {
+        self.service_id.partition_key()
+    }
+}
+
 /// # StateMutationVersion
 ///
 /// This type represents a user state version. This implementation hashes canonically the raw key-value
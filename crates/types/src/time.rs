@@ -180,6 +180,12 @@ impl From<SystemTime> for NanosSinceEpoch {
     }
 }
 
+impl From<NanosSinceEpoch> for MillisSinceEpoch {
+    fn from(value: NanosSinceEpoch) -> Self {
+        Self::new(value.as_u64() / 1_000_000)
+    }
+}
+
 /// # Panics
 /// If timestamp is out of range (e.g. older than UNIX_EPOCH) this conversion will panic.
 impl From<prost_types::Timestamp> for NanosSinceEpoch {
@@ -9,13 +9,18 @@
 // by the Apache License, Version 2.0.
 
 use restate_storage_api::deduplication_table::DedupInformation;
-use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey, WithPartitionKey};
+use restate_types::identifiers::{
+    LeaderEpoch, PartitionId, PartitionKey, ScheduleId, WithPartitionKey,
+};
 use restate_types::invocation::{
-    AttachInvocationRequest, GetInvocationOutputResponse, InvocationResponse,
-    InvocationTermination, NotifySignalRequest, PurgeInvocationRequest, ServiceInvocation,
+    AttachInvocationRequest, DiscardDeadLetterRequest, GetInvocationOutputResponse,
+    InvocationResponse, InvocationTermination, NotifySignalRequest, PauseInvocationRequest,
+    PurgeInvocationRequest, RedriveDeadLetterRequest, RestartAsNewInvocationRequest,
+    ResumeInvocationRequest, ServiceInvocation,
 };
 use restate_types::message::MessageIndex;
-use restate_types::state_mut::ExternalStateMutation;
+use restate_types::schedule::{FireScheduleRequest, Schedule};
+use restate_types::state_mut::{ExpireStateRequest, ExternalStateMutation, StateTtlServices};
 use restate_types::{PlainNodeId, Version, logs};
 
 use crate::control::AnnounceLeader;
@@ -137,10 +142,28 @@ pub enum Command {
     // -- Partition processor commands
     /// Manual patching of storage state
     PatchState(ExternalStateMutation),
+    /// Delete a state entry whose TTL expired
+    ExpireState(ExpireStateRequest),
+    /// Update the services whose state entries expire after a TTL
+    UpdateStateTtlServices(StateTtlServices),
     /// Terminate an ongoing invocation
     TerminateInvocation(InvocationTermination),
     /// Purge a completed invocation
     PurgeInvocation(PurgeInvocationRequest),
+    /// Pause an ongoing invocation, preventing it from being dispatched to the invoker
+    PauseInvocation(PauseInvocationRequest),
+    /// Resume a paused invocation
+    ResumeInvocation(ResumeInvocationRequest),
+    /// Restart a completed invocation as a new invocation, reusing a prefix of its retained journal
+    RestartAsNewInvocation(RestartAsNewInvocationRequest),
+    /// Submit again the request of a dead-lettered invocation
+    RedriveDeadLetter(RedriveDeadLetterRequest),
+    /// Remove an entry from the dead letter queue
+    DiscardDeadLetter(DiscardDeadLetterRequest),
+    /// Create or replace a schedule owned by this partition
+    UpsertSchedule(Schedule),
+    /// Remove a schedule owned by this partition
+    DeleteSchedule(ScheduleId),
     /// Start an invocation on this partition
     Invoke(ServiceInvocation),
     /// Truncate the message outbox up to, and including, the specified index.
@@ -157,6 +180,8 @@ pub enum Command {
     Timer(TimerKeyValue),
     /// Schedule timer
     ScheduleTimer(TimerKeyValue),
+    /// Schedule timer has fired
+    FireSchedule(FireScheduleRequest),
     /// Another partition processor is reporting a response of an invocation we requested.
     ///
     /// KINDA DEPRECATED: When Journal Table V1 is removed, this command should be used only to reply to invocations.
@@ -199,10 +224,27 @@ impl HasRecordKeys for Envelope {
                 }
             }
             Command::PatchState(mutation) => Keys::Single(mutation.service_id.partition_key()),
+            Command::ExpireState(expire) => Keys::Single(expire.partition_key()),
+            Command::UpdateStateTtlServices(update) => {
+                Keys::RangeInclusive(update.partition_key_range.clone())
+            }
             Command::TerminateInvocation(terminate) => {
                 Keys::Single(terminate.invocation_id.partition_key())
             }
             Command::PurgeInvocation(purge) => Keys::Single(purge.invocation_id.partition_key()),
+            Command::PauseInvocation(pause) => Keys::Single(pause.invocation_id.partition_key()),
+            Command::ResumeInvocation(resume) => Keys::Single(resume.invocation_id.partition_key()),
+            Command::RestartAsNewInvocation(restart) => {
+                Keys::Single(restart.invocation_id.partition_key())
+            }
+            Command::RedriveDeadLetter(redrive) => {
+                Keys::Single(redrive.invocation_id.partition_key())
+            }
+            Command::DiscardDeadLetter(discard) => {
+                Keys::Single(discard.invocation_id.partition_key())
+            }
+            Command::UpsertSchedule(schedule) => Keys::Single(schedule.partition_key()),
+            Command::DeleteSchedule(schedule_id) => Keys::Single(schedule_id.partition_key()),
             Command::Invoke(invoke) => Keys::Single(invoke.partition_key()),
             // todo: Remove this, or pass the partition key range but filter based on partition-id
             // on read if needed.
@@ -211,8 +253,9 @@ impl HasRecordKeys for Envelope {
             Command::AttachInvocation(_) => Keys::Single(self.partition_key()),
             // todo: Handle journal entries that request cross-partition invocations
             Command::InvokerEffect(effect) => Keys::Single(effect.invocation_id.partition_key()),
-            Command::Timer(timer) => Keys::Single(timer.invocation_id().partition_key()),
-            Command::ScheduleTimer(timer) => Keys::Single(timer.invocation_id().partition_key()),
+            Command::Timer(timer) => Keys::Single(timer.partition_key()),
+            Command::ScheduleTimer(timer) => Keys::Single(timer.partition_key()),
+            Command::FireSchedule(fire) => Keys::Single(fire.partition_key()),
             Command::InvocationResponse(response) => Keys::Single(response.partition_key()),
             Command::NotifySignal(sig) => Keys::Single(sig.partition_key()),
             Command::NotifyGetInvocationOutputResponse(res) => Keys::Single(res.partition_key()),
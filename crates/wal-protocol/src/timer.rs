@@ -9,7 +9,9 @@
 // by the Apache License, Version 2.0.
 
 use restate_storage_api::timer_table::{Timer, TimerKey, TimerKeyKind};
-use restate_types::identifiers::{EntryIndex, InvocationId};
+use restate_types::identifiers::{
+    EntryIndex, InvocationId, PartitionKey, ScheduleId, WithPartitionKey,
+};
 use restate_types::invocation::{InvocationEpoch, ServiceInvocation};
 use restate_types::time::MillisSinceEpoch;
 use std::borrow::Borrow;
@@ -65,6 +67,11 @@ impl TimerKeyValue {
         Self { timer_key, value }
     }
 
+    pub fn fire_schedule(wake_up_time: MillisSinceEpoch, schedule_id: ScheduleId) -> Self {
+        let (timer_key, value) = Timer::fire_schedule(wake_up_time.as_u64(), schedule_id);
+        Self { timer_key, value }
+    }
+
     pub fn into_inner(self) -> (TimerKey, Timer) {
         (self.timer_key, self.value)
     }
@@ -77,7 +84,7 @@ impl TimerKeyValue {
         &self.value
     }
 
-    pub fn invocation_id(&self) -> InvocationId {
+    pub fn invocation_id(&self) -> Option<InvocationId> {
         self.value.invocation_id()
     }
 
@@ -86,6 +93,12 @@ impl TimerKeyValue {
     }
 }
 
+impl WithPartitionKey for TimerKeyValue {
+    fn partition_key(&self) -> PartitionKey {
+        self.value.partition_key()
+    }
+}
+
 impl Hash for TimerKeyValue {
     fn hash<H: Hasher>(&self, state: &mut H) {
         Hash::hash(&self.timer_key, state);
@@ -138,6 +151,9 @@ impl fmt::Display for TimerKeyDisplay<'_> {
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => {
                 write!(f, "Clean invocation status '{invocation_uuid}'")
             }
+            TimerKeyKind::FireSchedule { schedule_id } => {
+                write!(f, "Fire schedule '{schedule_id}'")
+            }
         }
     }
 }
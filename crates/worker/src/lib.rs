@@ -21,6 +21,7 @@ mod subscription_integration;
 
 use codederror::CodedError;
 use restate_core::TaskCenter;
+use std::sync::Arc;
 use std::time::Duration;
 
 use restate_bifrost::Bifrost;
@@ -30,6 +31,7 @@ use restate_core::network::TransportConnect;
 use restate_core::partitions::PartitionRouting;
 use restate_core::worker_api::ProcessorsManagerHandle;
 use restate_core::{Metadata, TaskKind};
+use restate_ingress_http::WebhookDispatcher;
 use restate_ingress_kafka::Service as IngressKafkaService;
 use restate_invoker_impl::InvokerHandle as InvokerChannelServiceHandle;
 use restate_metadata_server::MetadataStoreClient;
@@ -136,6 +138,8 @@ impl Worker {
         let subscription_controller_handle = SubscriptionControllerHandle::new(
             config.ingress.clone(),
             ingress_kafka.create_command_sender(),
+            bifrost.clone(),
+            schema.clone(),
         );
 
         let snapshots_options = &config.worker.snapshots;
@@ -146,6 +150,11 @@ impl Worker {
                 "Periodic snapshot interval set without a specified snapshot destination"
             )));
         }
+        if snapshots_options.has_retention() && snapshots_options.destination.is_none() {
+            return Err(BuildError::SnapshotRepository(anyhow::anyhow!(
+                "Snapshot retention set without a specified snapshot destination"
+            )));
+        }
 
         let partition_processor_manager = PartitionProcessorManager::new(
             health_status,
@@ -203,6 +212,11 @@ impl Worker {
         self.subscription_controller_handle.clone()
     }
 
+    /// Dispatcher of the deliveries received by the ingress for the webhook subscriptions.
+    pub fn webhook_dispatcher(&self) -> Arc<dyn WebhookDispatcher> {
+        Arc::new(self.subscription_controller_handle.webhook_source().clone())
+    }
+
     pub fn storage_query_context(&self) -> &QueryContext {
         &self.storage_query_context
     }
@@ -47,6 +47,8 @@ pub const PARTITION_RECORD_COMMITTED_TO_READ_LATENCY_SECONDS: &str =
 // to calculate read rates
 pub const PARTITION_RECORD_READ_COUNT: &str = "restate.partition.record_read_count";
 
+pub const PARTITION_KAFKA_EGRESS_FAILED: &str = "restate.partition.kafka_egress.failed.total";
+
 pub(crate) fn describe_metrics() {
     describe_histogram!(
         PARTITION_APPLY_COMMAND,
@@ -165,4 +167,10 @@ pub(crate) fn describe_metrics() {
         Unit::Count,
         "Number of read records from bifrost",
     );
+
+    describe_counter!(
+        PARTITION_KAFKA_EGRESS_FAILED,
+        Unit::Count,
+        "Number of failed attempts to publish outbox records to Kafka",
+    );
 }
@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::{BTreeSet, HashMap};
 use std::ops::RangeInclusive;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
@@ -22,9 +23,12 @@ use restate_core::{Metadata, cancellation_watcher};
 use restate_storage_api::invocation_status_table::{
     InvocationStatus, ReadOnlyInvocationStatusTable,
 };
+use restate_storage_api::state_table::ReadOnlyStateTable;
 use restate_types::identifiers::WithPartitionKey;
 use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey};
 use restate_types::invocation::PurgeInvocationRequest;
+use restate_types::schema::service::ServiceMetadataResolver;
+use restate_types::state_mut::{ExpireStateRequest, StateTtlServices};
 use restate_wal_protocol::{Command, Destination, Envelope, Header, Source};
 
 pub(super) struct Cleaner<Storage> {
@@ -38,7 +42,7 @@ pub(super) struct Cleaner<Storage> {
 
 impl<Storage> Cleaner<Storage>
 where
-    Storage: ReadOnlyInvocationStatusTable + Send + Sync + 'static,
+    Storage: ReadOnlyInvocationStatusTable + ReadOnlyStateTable + Send + Sync + 'static,
 {
     pub(super) fn new(
         partition_id: PartitionId,
@@ -81,6 +85,7 @@ where
 
         let mut interval = tokio::time::interval(cleanup_interval);
         interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut state_ttl_services = None;
 
         loop {
             tokio::select! {
@@ -88,6 +93,13 @@ where
                     if let Err(e) = Self::do_cleanup(&storage, &bifrost, partition_key_range.clone(), &bifrost_envelope_source).await {
                         warn!("Error when trying to cleanup completed invocations: {e:?}");
                     }
+                    let schema = Metadata::with_current(|m| m.schema_snapshot());
+                    if let Err(e) = Self::do_update_state_ttl_services(&bifrost, partition_key_range.clone(), &bifrost_envelope_source, schema.as_ref(), &mut state_ttl_services).await {
+                        warn!("Error when trying to update the services with state TTL: {e:?}");
+                    }
+                    if let Err(e) = Self::do_state_cleanup(&storage, &bifrost, partition_key_range.clone(), &bifrost_envelope_source, schema.as_ref()).await {
+                        warn!("Error when trying to cleanup expired state: {e:?}");
+                    }
                 },
                 _ = cancellation_watcher() => {
                     break;
@@ -158,12 +170,120 @@ where
 
         Ok(())
     }
+
+    /// Proposes the services with a state TTL whenever they differ from the last proposed ones,
+    /// so that the partition processors track the state writes of those services only.
+    pub(super) async fn do_update_state_ttl_services(
+        bifrost: &Bifrost,
+        partition_key_range: RangeInclusive<PartitionKey>,
+        bifrost_envelope_source: &Source,
+        schema: &impl ServiceMetadataResolver,
+        last_proposed: &mut Option<BTreeSet<String>>,
+    ) -> anyhow::Result<()> {
+        let services: BTreeSet<_> = schema
+            .list_services()
+            .into_iter()
+            .filter(|service| service.state_ttl.is_some())
+            .map(|service| service.name)
+            .collect();
+        if last_proposed.as_ref() == Some(&services) {
+            return Ok(());
+        }
+
+        debug!("Updating the services with state TTL to {services:?}");
+        restate_bifrost::append_to_bifrost(
+            bifrost,
+            Arc::new(Envelope {
+                header: Header {
+                    source: bifrost_envelope_source.clone(),
+                    dest: Destination::Processor {
+                        partition_key: *partition_key_range.start(),
+                        dedup: None,
+                    },
+                },
+                command: Command::UpdateStateTtlServices(StateTtlServices {
+                    partition_key_range,
+                    services: services.clone(),
+                }),
+            }),
+        )
+        .await
+        .context("Cannot append to bifrost")?;
+        *last_proposed = Some(services);
+
+        Ok(())
+    }
+
+    pub(super) async fn do_state_cleanup(
+        storage: &Storage,
+        bifrost: &Bifrost,
+        partition_key_range: RangeInclusive<PartitionKey>,
+        bifrost_envelope_source: &Source,
+        schema: &impl ServiceMetadataResolver,
+    ) -> anyhow::Result<()> {
+        debug!("Executing expired state cleanup");
+
+        let mut state_ttls = HashMap::new();
+        let state_metadata_stream =
+            storage.get_all_user_state_metadata_in_range(partition_key_range)?;
+        tokio::pin!(state_metadata_stream);
+
+        while let Some((service_id, state_key, metadata)) = state_metadata_stream
+            .next()
+            .await
+            .transpose()
+            .context("Cannot read the next item of the state table")?
+        {
+            let state_ttl = *state_ttls
+                .entry(service_id.service_name.clone())
+                .or_insert_with(|| {
+                    schema.resolve_latest_service_state_ttl(&service_id.service_name)
+                });
+            let Some(state_ttl) = state_ttl else {
+                continue;
+            };
+
+            // The modification time is the creation time of the record which wrote the entry,
+            // comparing it with the local time is fine because only the leader runs this cleaner code.
+            let Some(expiration_time) =
+                SystemTime::from(metadata.modified_at).checked_add(state_ttl)
+            else {
+                // If sum overflow, then the expiration time lies far enough in the future
+                continue;
+            };
+
+            if SystemTime::now() >= expiration_time {
+                restate_bifrost::append_to_bifrost(
+                    bifrost,
+                    Arc::new(Envelope {
+                        header: Header {
+                            source: bifrost_envelope_source.clone(),
+                            dest: Destination::Processor {
+                                partition_key: service_id.partition_key(),
+                                dedup: None,
+                            },
+                        },
+                        command: Command::ExpireState(ExpireStateRequest {
+                            service_id,
+                            state_key,
+                            version: metadata.version,
+                        }),
+                    }),
+                )
+                .await
+                .context("Cannot append to bifrost")?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use bytes::Bytes;
     use futures::{Stream, stream};
     use googletest::prelude::*;
     use restate_core::{Metadata, TaskCenter, TaskKind, TestCoreEnvBuilder};
@@ -172,16 +292,23 @@ mod tests {
         CompletedInvocation, InFlightInvocationMetadata, InvocationStatus,
         InvokedInvocationStatusLite,
     };
+    use restate_storage_api::state_table::StateEntryMetadata;
     use restate_types::Version;
-    use restate_types::identifiers::{InvocationId, InvocationUuid};
+    use restate_types::identifiers::{InvocationId, InvocationUuid, ServiceId};
     use restate_types::partition_table::{FindPartition, PartitionTable};
+    use restate_types::schema::service::ServiceMetadata;
+    use restate_types::schema::service::test_util::MockServiceMetadataResolver;
+    use restate_types::time::MillisSinceEpoch;
     use std::future::Future;
     use test_log::test;
 
     #[allow(dead_code)]
-    struct MockInvocationStatusReader(Vec<(InvocationId, InvocationStatus)>);
+    struct MockStorage(
+        Vec<(InvocationId, InvocationStatus)>,
+        Vec<(ServiceId, Bytes, StateEntryMetadata)>,
+    );
 
-    impl ReadOnlyInvocationStatusTable for MockInvocationStatusReader {
+    impl ReadOnlyInvocationStatusTable for MockStorage {
         fn get_invocation_status(
             &mut self,
             _: &InvocationId,
@@ -213,6 +340,71 @@ mod tests {
         }
     }
 
+    impl ReadOnlyStateTable for MockStorage {
+        fn get_user_state(
+            &mut self,
+            _: &ServiceId,
+            _: impl AsRef<[u8]> + Send,
+        ) -> impl Future<Output = restate_storage_api::Result<Option<Bytes>>> + Send {
+            todo!();
+            #[allow(unreachable_code)]
+            std::future::pending()
+        }
+
+        fn get_all_user_states_for_service(
+            &mut self,
+            _: &ServiceId,
+        ) -> restate_storage_api::Result<
+            impl Stream<Item = restate_storage_api::Result<(Bytes, Bytes)>> + Send,
+        > {
+            todo!();
+            #[allow(unreachable_code)]
+            Ok(stream::empty())
+        }
+
+        fn get_all_user_states(
+            &self,
+        ) -> restate_storage_api::Result<
+            impl Stream<Item = restate_storage_api::Result<(ServiceId, Bytes, Bytes)>> + Send,
+        > {
+            todo!();
+            #[allow(unreachable_code)]
+            Ok(stream::empty())
+        }
+
+        fn get_all_user_states_in_range(
+            &self,
+            _: RangeInclusive<PartitionKey>,
+        ) -> restate_storage_api::Result<
+            impl Stream<Item = restate_storage_api::Result<(ServiceId, Bytes, Bytes)>> + Send,
+        > {
+            todo!();
+            #[allow(unreachable_code)]
+            Ok(stream::empty())
+        }
+
+        fn get_user_state_metadata(
+            &mut self,
+            _: &ServiceId,
+            _: impl AsRef<[u8]> + Send,
+        ) -> impl Future<Output = restate_storage_api::Result<Option<StateEntryMetadata>>> + Send
+        {
+            todo!();
+            #[allow(unreachable_code)]
+            std::future::pending()
+        }
+
+        fn get_all_user_state_metadata_in_range(
+            &self,
+            _: RangeInclusive<PartitionKey>,
+        ) -> restate_storage_api::Result<
+            impl Stream<Item = restate_storage_api::Result<(ServiceId, Bytes, StateEntryMetadata)>>
+            + Send,
+        > {
+            Ok(stream::iter(self.1.clone()).map(Ok))
+        }
+    }
+
     // Start paused makes sure the timer is immediately fired
     #[test(restate_core::test(start_paused = true))]
     pub async fn cleanup_works() {
@@ -234,31 +426,34 @@ mod tests {
         let not_completed_invocation =
             InvocationId::from_parts(PartitionKey::MIN, InvocationUuid::mock_random());
 
-        let mock_storage = MockInvocationStatusReader(vec![
-            (
-                expired_invocation,
-                InvocationStatus::Completed(CompletedInvocation {
-                    completion_retention_duration: Duration::ZERO,
-                    ..CompletedInvocation::mock_neo()
-                }),
-            ),
-            (
-                not_expired_invocation_1,
-                InvocationStatus::Completed(CompletedInvocation {
-                    completion_retention_duration: Duration::MAX,
-                    ..CompletedInvocation::mock_neo()
-                }),
-            ),
-            (
-                not_expired_invocation_2,
-                // Old status invocations are still processed with the cleanup timer in the PP
-                InvocationStatus::Completed(CompletedInvocation::mock_old()),
-            ),
-            (
-                not_completed_invocation,
-                InvocationStatus::Invoked(InFlightInvocationMetadata::mock()),
-            ),
-        ]);
+        let mock_storage = MockStorage(
+            vec![
+                (
+                    expired_invocation,
+                    InvocationStatus::Completed(CompletedInvocation {
+                        completion_retention_duration: Duration::ZERO,
+                        ..CompletedInvocation::mock_neo()
+                    }),
+                ),
+                (
+                    not_expired_invocation_1,
+                    InvocationStatus::Completed(CompletedInvocation {
+                        completion_retention_duration: Duration::MAX,
+                        ..CompletedInvocation::mock_neo()
+                    }),
+                ),
+                (
+                    not_expired_invocation_2,
+                    // Old status invocations are still processed with the cleanup timer in the PP
+                    InvocationStatus::Completed(CompletedInvocation::mock_old()),
+                ),
+                (
+                    not_completed_invocation,
+                    InvocationStatus::Invoked(InFlightInvocationMetadata::mock()),
+                ),
+            ],
+            vec![],
+        );
 
         TaskCenter::spawn(
             TaskKind::Cleaner,
@@ -300,4 +495,147 @@ mod tests {
         );
         assert_that!(log_entries, empty());
     }
+
+    #[test(restate_core::test)]
+    pub async fn state_cleanup_works() {
+        let env = TestCoreEnvBuilder::with_incoming_only_connector()
+            .set_partition_table(PartitionTable::with_equally_sized_partitions(
+                Version::MIN,
+                1,
+            ))
+            .build()
+            .await;
+        let bifrost = Bifrost::init_in_memory(env.metadata_writer).await;
+
+        let mut schema = MockServiceMetadataResolver::default();
+        schema.add(ServiceMetadata {
+            state_ttl: Some(Duration::from_secs(60).into()),
+            ..ServiceMetadata::mock_virtual_object("ExpiringObject", ["greet"])
+        });
+        schema.add(ServiceMetadata::mock_virtual_object("Object", ["greet"]));
+
+        let expired_state = ServiceId::with_partition_key(PartitionKey::MIN, "ExpiringObject", "a");
+        let not_expired_state =
+            ServiceId::with_partition_key(PartitionKey::MIN, "ExpiringObject", "b");
+        let no_ttl_state = ServiceId::with_partition_key(PartitionKey::MIN, "Object", "a");
+
+        let long_ago = MillisSinceEpoch::new(
+            MillisSinceEpoch::now().as_u64() - Duration::from_secs(120).as_millis() as u64,
+        );
+        let mock_storage = MockStorage(
+            vec![],
+            vec![
+                (
+                    expired_state.clone(),
+                    Bytes::from_static(b"key"),
+                    StateEntryMetadata {
+                        version: 3,
+                        modified_at: long_ago,
+                    },
+                ),
+                (
+                    not_expired_state,
+                    Bytes::from_static(b"key"),
+                    StateEntryMetadata {
+                        version: 1,
+                        modified_at: MillisSinceEpoch::now(),
+                    },
+                ),
+                (
+                    no_ttl_state,
+                    Bytes::from_static(b"key"),
+                    StateEntryMetadata {
+                        version: 1,
+                        modified_at: long_ago,
+                    },
+                ),
+            ],
+        );
+
+        Cleaner::do_state_cleanup(
+            &mock_storage,
+            &bifrost,
+            RangeInclusive::new(PartitionKey::MIN, PartitionKey::MAX),
+            &Source::ControlPlane {},
+            &schema,
+        )
+        .await
+        .unwrap();
+
+        let partition_id = Metadata::with_current(|m| {
+            m.partition_table_snapshot()
+                .find_partition_id(expired_state.partition_key())
+        })
+        .unwrap();
+
+        let mut log_entries = bifrost.read_all(partition_id.into()).await.unwrap();
+        let bifrost_message = log_entries
+            .remove(0)
+            .try_decode::<Envelope>()
+            .unwrap()
+            .unwrap();
+
+        assert_that!(
+            bifrost_message.command,
+            pat!(Command::ExpireState(pat!(ExpireStateRequest {
+                service_id: eq(expired_state),
+                state_key: eq(Bytes::from_static(b"key")),
+                version: eq(3)
+            })))
+        );
+        assert_that!(log_entries, empty());
+    }
+
+    #[test(restate_core::test)]
+    pub async fn state_ttl_services_are_proposed_on_change() {
+        let env = TestCoreEnvBuilder::with_incoming_only_connector()
+            .set_partition_table(PartitionTable::with_equally_sized_partitions(
+                Version::MIN,
+                1,
+            ))
+            .build()
+            .await;
+        let bifrost = Bifrost::init_in_memory(env.metadata_writer).await;
+
+        let mut schema = MockServiceMetadataResolver::default();
+        schema.add(ServiceMetadata {
+            state_ttl: Some(Duration::from_secs(60).into()),
+            ..ServiceMetadata::mock_virtual_object("ExpiringObject", ["greet"])
+        });
+        schema.add(ServiceMetadata::mock_virtual_object("Object", ["greet"]));
+
+        let mut last_proposed = None;
+        for _ in 0..2 {
+            Cleaner::<MockStorage>::do_update_state_ttl_services(
+                &bifrost,
+                RangeInclusive::new(PartitionKey::MIN, PartitionKey::MAX),
+                &Source::ControlPlane {},
+                &schema,
+                &mut last_proposed,
+            )
+            .await
+            .unwrap();
+        }
+
+        let partition_id = Metadata::with_current(|m| {
+            m.partition_table_snapshot()
+                .find_partition_id(PartitionKey::MIN)
+        })
+        .unwrap();
+
+        let mut log_entries = bifrost.read_all(partition_id.into()).await.unwrap();
+        let bifrost_message = log_entries
+            .remove(0)
+            .try_decode::<Envelope>()
+            .unwrap()
+            .unwrap();
+
+        assert_that!(
+            bifrost_message.command,
+            pat!(Command::UpdateStateTtlServices(pat!(StateTtlServices {
+                services: eq(BTreeSet::from(["ExpiringObject".to_owned()]))
+            })))
+        );
+        assert_that!(log_entries, empty());
+    }
 }
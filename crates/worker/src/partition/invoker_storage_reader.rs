@@ -89,11 +89,16 @@ where
                 unsafe { invoked_status.timestamps.modification_time() },
             );
 
-            let journal_stream = if invoked_status
-                .pinned_deployment
-                .is_some_and(|p| p.service_protocol_version >= ServiceProtocolVersion::V4)
-            {
+            let read_journal_table_v2 = match invoked_status.pinned_deployment {
                 // If pinned service protocol version exists and >= V4, we need to read from Journal Table V2!
+                Some(p) => p.service_protocol_version >= ServiceProtocolVersion::V4,
+                // Not pinned yet, but the journal might already live in Journal Table V2,
+                // e.g. when the invocation was restarted as new from a retained journal.
+                // Only Journal Table V2 tracks the number of commands.
+                None => invoked_status.journal_metadata.commands > 0,
+            };
+
+            let journal_stream = if read_journal_table_v2 {
                 journal_table_v2::ReadOnlyJournalTable::get_journal(
                     &mut self.txn,
                     *invocation_id,
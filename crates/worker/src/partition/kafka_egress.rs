@@ -0,0 +1,462 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use bytes::Bytes;
+use futures::never::Never;
+use metrics::counter;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::error::KafkaError;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+use rdkafka::types::RDKafkaErrorCode;
+use rdkafka::util::Timeout;
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use tokio::sync::{Notify, watch};
+use tracing::{debug, warn};
+
+use restate_storage_api::outbox_table::{KafkaRecord, OutboxMessage};
+use restate_types::config::Configuration;
+use restate_types::identifiers::{LeaderEpoch, PartitionId};
+use restate_types::invocation::ResponseResult;
+use restate_types::message::MessageIndex;
+use restate_types::retries::RetryPolicy;
+
+use crate::metric_definitions::{PARTITION_KAFKA_EGRESS_FAILED, PARTITION_LABEL};
+use crate::partition::shuffle::OutboxReader;
+
+const INVOCATION_ID_HEADER: &str = "x-restate-id";
+const DEDUPLICATION_ID_HEADER: &str = "x-restate-deduplication-id";
+const ERROR_CODE_HEADER: &str = "x-restate-error-code";
+const ERROR_MESSAGE_HEADER: &str = "x-restate-error-message";
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MESSAGE_TIMEOUT_MS: &str = "30000";
+
+/// Partition of the sink topic whose offset, in the consumer group of the egress, records the
+/// publishing progress on that topic.
+const PROGRESS_PARTITION: i32 = 0;
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum KafkaEgressError {
+    #[error(transparent)]
+    OutboxReader(#[from] crate::partition::shuffle::OutboxReaderError),
+    #[error(
+        "the Kafka topic '{topic}' is published by the leader epoch {committed_epoch}, newer than this leader epoch {leader_epoch}"
+    )]
+    Superseded {
+        topic: String,
+        leader_epoch: LeaderEpoch,
+        committed_epoch: LeaderEpoch,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+enum PublishError {
+    #[error(transparent)]
+    Kafka(#[from] KafkaError),
+    #[error(transparent)]
+    Superseded(KafkaEgressError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl PublishError {
+    /// Whether the producer is not usable anymore, e.g. because it's fenced by another producer.
+    fn is_fatal(&self) -> bool {
+        match self {
+            PublishError::Kafka(KafkaError::Transaction(err)) => err.is_fatal(),
+            PublishError::Kafka(err) => matches!(
+                err.rdkafka_error_code(),
+                Some(
+                    RDKafkaErrorCode::Fatal
+                        | RDKafkaErrorCode::ProducerFenced
+                        | RDKafkaErrorCode::InvalidProducerEpoch
+                )
+            ),
+            PublishError::Superseded(_) | PublishError::Other(_) => false,
+        }
+    }
+}
+
+/// Publishes the [`KafkaRecord`]s of the outbox of a partition, exactly once.
+///
+/// The egress consumes the outbox independently of the shuffle, so that an unavailable Kafka
+/// cluster doesn't hold back the messages to the other partitions. It reports the index of the
+/// last published outbox message, and the shuffle truncates the outbox only up to it.
+///
+/// Every record is published in a Kafka transaction, which also commits the outbox sequence number
+/// of the record as the offset of the sink topic in the consumer group `restate-kafka-egress-<partition>`,
+/// tagged with the leader epoch. A new leader resumes after the committed sequence number, and the
+/// transactional id `restate-kafka-egress-<partition>` fences the producers of the previous leaders,
+/// so consumers reading with `isolation.level=read_committed` see each record once. A leader
+/// finding progress committed by a newer leader epoch stops publishing.
+///
+/// Records are never dropped: the egress retries publishing a record until it succeeds, counting
+/// the failed attempts in the `restate.partition.kafka_egress.failed.total` metric.
+pub(super) struct KafkaEgress<OR> {
+    partition_id: PartitionId,
+    leader_epoch: LeaderEpoch,
+    outbox_reader: OR,
+    // Producers are lazily created, one per cluster
+    producers: HashMap<String, ClusterProducer>,
+    retry_policy: RetryPolicy,
+    // Notified for every new outbox message
+    new_message: Arc<Notify>,
+    handled_tx: watch::Sender<Option<MessageIndex>>,
+}
+
+/// Transactional producer of a Kafka cluster.
+struct ClusterProducer {
+    producer: FutureProducer,
+    // Reads the progress committed by the transactions, in the consumer group of the egress
+    progress_consumer: Arc<BaseConsumer>,
+    // Next outbox sequence number to publish, per topic
+    next_seq_numbers: HashMap<String, MessageIndex>,
+}
+
+impl<OR> KafkaEgress<OR>
+where
+    OR: OutboxReader,
+{
+    /// Returns the egress, and the receiver of the index of the last published outbox message.
+    pub(super) fn new(
+        partition_id: PartitionId,
+        leader_epoch: LeaderEpoch,
+        outbox_reader: OR,
+        new_message: Arc<Notify>,
+    ) -> (Self, watch::Receiver<Option<MessageIndex>>) {
+        let (handled_tx, handled_rx) = watch::channel(None);
+        (
+            Self {
+                partition_id,
+                leader_epoch,
+                outbox_reader,
+                producers: Default::default(),
+                retry_policy: RetryPolicy::exponential(
+                    Duration::from_millis(100),
+                    2.0,
+                    None,
+                    Some(Duration::from_secs(10)),
+                ),
+                new_message,
+                handled_tx,
+            },
+            handled_rx,
+        )
+    }
+
+    pub(super) async fn run(mut self) -> Result<Never, KafkaEgressError> {
+        let mut next_seq_number = 0;
+        loop {
+            let Some((seq_number, message)) =
+                self.outbox_reader.get_next_message(next_seq_number).await?
+            else {
+                self.new_message.notified().await;
+                continue;
+            };
+
+            if let OutboxMessage::KafkaRecord(record) = message {
+                // Block on the record until it's published, the outbox is not truncated past it
+                let mut retry_iter = self.retry_policy.clone().into_iter();
+                loop {
+                    let err = match self.publish(seq_number, &record).await {
+                        Ok(()) => break,
+                        Err(PublishError::Superseded(err)) => return Err(err),
+                        Err(err) => err,
+                    };
+
+                    let retry_in = retry_iter.next().unwrap_or(Duration::from_secs(10));
+                    warn!(
+                        restate.invocation.id = %record.invocation_id,
+                        restate.outbox.seq = seq_number,
+                        "Failed publishing the response to the Kafka topic '{}' of cluster '{}', retrying in {}: {err:#}",
+                        record.topic,
+                        record.cluster,
+                        humantime::format_duration(retry_in),
+                    );
+                    counter!(
+                        PARTITION_KAFKA_EGRESS_FAILED,
+                        PARTITION_LABEL => self.partition_id.to_string()
+                    )
+                    .increment(1);
+                    if err.is_fatal() {
+                        // Start over with a new producer, resuming after the committed progress
+                        self.producers.remove(&record.cluster);
+                    }
+                    tokio::time::sleep(retry_in).await;
+                }
+            }
+
+            next_seq_number = seq_number + 1;
+            self.handled_tx.send_replace(Some(seq_number));
+        }
+    }
+
+    async fn publish(
+        &mut self,
+        seq_number: MessageIndex,
+        record: &KafkaRecord,
+    ) -> Result<(), PublishError> {
+        let leader_epoch = self.leader_epoch;
+        let group_id = self.group_id();
+        let cluster_producer = self.producer(&record.cluster).await?;
+
+        let next_seq_number = match cluster_producer.next_seq_numbers.get(&record.topic) {
+            Some(next_seq_number) => *next_seq_number,
+            None => {
+                let progress_consumer = Arc::clone(&cluster_producer.progress_consumer);
+                let topic = record.topic.clone();
+                let next_seq_number =
+                    blocking(move || read_progress(&progress_consumer, &topic, leader_epoch))
+                        .await?;
+                cluster_producer
+                    .next_seq_numbers
+                    .insert(record.topic.clone(), next_seq_number);
+                next_seq_number
+            }
+        };
+        if seq_number < next_seq_number {
+            debug!(
+                restate.invocation.id = %record.invocation_id,
+                restate.outbox.seq = seq_number,
+                "Response already published to the Kafka topic '{}'",
+                record.topic
+            );
+            return Ok(());
+        }
+
+        let producer = cluster_producer.producer.clone();
+        let group_metadata = cluster_producer
+            .progress_consumer
+            .group_metadata()
+            .with_context(|| format!("missing metadata of the consumer group '{group_id}'"))?;
+        let mut progress = TopicPartitionList::new();
+        progress
+            .add_partition_offset(
+                &record.topic,
+                PROGRESS_PARTITION,
+                Offset::Offset(
+                    i64::try_from(seq_number + 1).context("outbox sequence number overflow")?,
+                ),
+            )
+            .map_err(PublishError::Kafka)?;
+        if let Some(mut progress) = progress.find_partition(&record.topic, PROGRESS_PARTITION) {
+            progress.set_metadata(u64::from(leader_epoch).to_string());
+        }
+
+        blocking({
+            let producer = producer.clone();
+            move || producer.begin_transaction().map_err(PublishError::Kafka)
+        })
+        .await?;
+
+        let result = async {
+            self.send(&producer, seq_number, record).await?;
+
+            let producer = producer.clone();
+            blocking(move || {
+                producer.send_offsets_to_transaction(
+                    &progress,
+                    &group_metadata,
+                    TRANSACTION_TIMEOUT,
+                )?;
+                producer.commit_transaction(TRANSACTION_TIMEOUT)
+            })
+            .await
+            .map_err(PublishError::Kafka)
+        }
+        .await;
+
+        if let Err(err) = result {
+            if !err.is_fatal() {
+                let producer = producer.clone();
+                if let Err(abort_err) =
+                    blocking(move || producer.abort_transaction(TRANSACTION_TIMEOUT)).await
+                {
+                    warn!("Failed aborting the Kafka transaction: {abort_err}");
+                    // The producer state is unknown, start over with a new producer
+                    self.producers.remove(&record.cluster);
+                }
+            }
+            return Err(err);
+        }
+
+        if let Some(cluster_producer) = self.producers.get_mut(&record.cluster) {
+            cluster_producer
+                .next_seq_numbers
+                .insert(record.topic.clone(), seq_number + 1);
+        }
+        debug!(
+            restate.invocation.id = %record.invocation_id,
+            restate.outbox.seq = seq_number,
+            "Published response to the Kafka topic '{}'",
+            record.topic
+        );
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        producer: &FutureProducer,
+        seq_number: MessageIndex,
+        record: &KafkaRecord,
+    ) -> Result<(), PublishError> {
+        let invocation_id = record.invocation_id.to_string();
+        let deduplication_id = format!("{}-{}", self.partition_id, seq_number);
+        let mut headers = OwnedHeaders::new()
+            .insert(Header {
+                key: INVOCATION_ID_HEADER,
+                value: Some(&invocation_id),
+            })
+            .insert(Header {
+                key: DEDUPLICATION_ID_HEADER,
+                value: Some(&deduplication_id),
+            });
+        let payload = match &record.result {
+            ResponseResult::Success(output) => output.clone(),
+            ResponseResult::Failure(err) => {
+                headers = headers
+                    .insert(Header {
+                        key: ERROR_CODE_HEADER,
+                        value: Some(&err.code().to_string()),
+                    })
+                    .insert(Header {
+                        key: ERROR_MESSAGE_HEADER,
+                        value: Some(err.message()),
+                    });
+                Bytes::new()
+            }
+        };
+
+        let mut future_record = FutureRecord::<[u8], [u8]>::to(&record.topic)
+            .payload(payload.as_ref())
+            .headers(headers);
+        future_record.key = record.key.as_deref();
+
+        producer
+            .send(future_record, Timeout::After(SEND_TIMEOUT))
+            .await
+            .map_err(|(err, _)| PublishError::Kafka(err))?;
+        Ok(())
+    }
+
+    fn group_id(&self) -> String {
+        format!("restate-kafka-egress-{}", self.partition_id)
+    }
+
+    async fn producer(&mut self, cluster: &str) -> Result<&mut ClusterProducer, PublishError> {
+        if !self.producers.contains_key(cluster) {
+            let cluster_producer = self.create_producer(cluster).await?;
+            self.producers.insert(cluster.to_owned(), cluster_producer);
+        }
+        Ok(self
+            .producers
+            .get_mut(cluster)
+            .expect("producer was just created"))
+    }
+
+    async fn create_producer(&self, cluster: &str) -> Result<ClusterProducer, PublishError> {
+        let mut client_config = {
+            let configuration = Configuration::pinned();
+            let cluster_options = configuration
+                .ingress
+                .get_kafka_cluster(cluster)
+                .with_context(|| format!("the Kafka cluster '{cluster}' is not configured"))?;
+
+            let mut client_config = ClientConfig::new();
+            client_config.set("metadata.broker.list", cluster_options.brokers.join(","));
+            for (k, v) in &cluster_options.additional_options {
+                client_config.set(k, v);
+            }
+            client_config
+        };
+        let group_id = self.group_id();
+
+        let progress_consumer: BaseConsumer = client_config
+            .clone()
+            .set("group.id", &group_id)
+            .set("enable.auto.commit", "false")
+            .set("isolation.level", "read_committed")
+            .create()
+            .with_context(|| {
+                format!("cannot create the progress consumer for Kafka cluster '{cluster}'")
+            })?;
+
+        // Bound the delivery of a record, so that its transaction is eventually aborted and retried
+        client_config.set("message.timeout.ms", DEFAULT_MESSAGE_TIMEOUT_MS);
+        // The transactional id is the same for all the leaders of the partition, so that
+        // initializing the transactions fences the producers of the previous leaders, and aborts
+        // their pending transactions
+        client_config.set("transactional.id", &group_id);
+        client_config.set("enable.idempotence", "true");
+        let producer: FutureProducer = client_config
+            .create()
+            .with_context(|| format!("cannot create producer for Kafka cluster '{cluster}'"))?;
+
+        blocking({
+            let producer = producer.clone();
+            move || producer.init_transactions(TRANSACTION_TIMEOUT)
+        })
+        .await?;
+
+        Ok(ClusterProducer {
+            producer,
+            progress_consumer: Arc::new(progress_consumer),
+            next_seq_numbers: Default::default(),
+        })
+    }
+}
+
+/// Returns the next outbox sequence number to publish to the topic, as committed by the last
+/// transaction.
+fn read_progress(
+    progress_consumer: &BaseConsumer,
+    topic: &str,
+    leader_epoch: LeaderEpoch,
+) -> Result<MessageIndex, PublishError> {
+    let mut partitions = TopicPartitionList::new();
+    partitions.add_partition(topic, PROGRESS_PARTITION);
+    let committed = progress_consumer.committed_offsets(partitions, TRANSACTION_TIMEOUT)?;
+
+    let Some(progress) = committed.find_partition(topic, PROGRESS_PARTITION) else {
+        return Ok(0);
+    };
+    if let Ok(committed_epoch) = progress.metadata().parse::<u64>() {
+        let committed_epoch = LeaderEpoch::from(committed_epoch);
+        if committed_epoch > leader_epoch {
+            return Err(PublishError::Superseded(KafkaEgressError::Superseded {
+                topic: topic.to_owned(),
+                leader_epoch,
+                committed_epoch,
+            }));
+        }
+    }
+    match progress.offset() {
+        Offset::Offset(offset) => Ok(u64::try_from(offset).unwrap_or_default()),
+        _ => Ok(0),
+    }
+}
+
+/// Runs a blocking call of the Kafka client.
+async fn blocking<T, E>(f: impl FnOnce() -> Result<T, E> + Send + 'static) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .expect("Kafka client call not to panic")
+}
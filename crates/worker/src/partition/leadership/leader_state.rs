@@ -23,6 +23,7 @@ use restate_bifrost::CommitToken;
 use restate_core::network::{Oneshot, Reciprocal};
 use restate_core::{TaskCenter, TaskHandle, TaskId};
 use restate_partition_store::PartitionStore;
+use restate_storage_api::timer_table::Timer;
 use restate_types::identifiers::{
     InvocationId, LeaderEpoch, PartitionId, PartitionKey, PartitionProcessorRpcRequestId,
     WithPartitionKey,
@@ -31,6 +32,7 @@ use restate_types::net::partition_processor::{
     InvocationOutput, PartitionProcessorRpcError, PartitionProcessorRpcResponse,
     SubmittedInvocationNotification,
 };
+use restate_types::schedule::FireScheduleRequest;
 use restate_types::time::MillisSinceEpoch;
 use restate_wal_protocol::Command;
 use restate_wal_protocol::timer::TimerKeyValue;
@@ -38,6 +40,7 @@ use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
 use std::future;
 use std::future::Future;
+use std::mem;
 use std::pin::Pin;
 use std::task::{Context, Poll, ready};
 use std::time::{Duration, SystemTime};
@@ -49,6 +52,13 @@ const BATCH_READY_UP_TO: usize = 10;
 type RpcReciprocal =
     Reciprocal<Oneshot<Result<PartitionProcessorRpcResponse, PartitionProcessorRpcError>>>;
 
+/// Batch of proposed commands, answered once every command sent its submit notification.
+struct AwaitingRpcBatch {
+    reciprocal: RpcReciprocal,
+    notifications: Vec<Option<SubmittedInvocationNotification>>,
+    pending: usize,
+}
+
 pub struct LeaderState {
     partition_id: PartitionId,
     pub leader_epoch: LeaderEpoch,
@@ -65,6 +75,10 @@ pub struct LeaderState {
     self_proposer: SelfProposer,
 
     awaiting_rpc_actions: HashMap<PartitionProcessorRpcRequestId, RpcReciprocal>,
+    awaiting_rpc_batches: HashMap<PartitionProcessorRpcRequestId, AwaitingRpcBatch>,
+    // Maps the request id of every command of a batch to the batch and its position
+    awaiting_rpc_batch_items:
+        HashMap<PartitionProcessorRpcRequestId, (PartitionProcessorRpcRequestId, usize)>,
     awaiting_rpc_self_propose: FuturesUnordered<SelfAppendFuture>,
 
     invoker_stream: InvokerStream,
@@ -98,6 +112,8 @@ impl LeaderState {
             timer_service: Box::pin(timer_service),
             self_proposer,
             awaiting_rpc_actions: Default::default(),
+            awaiting_rpc_batches: Default::default(),
+            awaiting_rpc_batch_items: Default::default(),
             awaiting_rpc_self_propose: Default::default(),
             invoker_stream: invoker_rx,
             shuffle_stream: ReceiverStream::new(shuffle_rx),
@@ -213,6 +229,18 @@ impl LeaderState {
                 self.partition_id,
             )))
         }
+        for (request_id, batch) in self.awaiting_rpc_batches.drain() {
+            trace!(
+                %request_id,
+                "Failing batch rpc because I lost leadership",
+            );
+            batch
+                .reciprocal
+                .send(Err(PartitionProcessorRpcError::LostLeadership(
+                    self.partition_id,
+                )))
+        }
+        self.awaiting_rpc_batch_items.clear();
         for fut in self.awaiting_rpc_self_propose.iter_mut() {
             fut.fail_with_lost_leadership(self.partition_id);
         }
@@ -245,9 +273,18 @@ impl LeaderState {
                         .await?;
                 }
                 ActionEffect::Timer(timer) => {
-                    self.self_proposer
-                        .propose(timer.invocation_id().partition_key(), Command::Timer(timer))
-                        .await?;
+                    let partition_key = timer.partition_key();
+                    let command = if let Timer::FireSchedule(schedule_id) = timer.value() {
+                        // The catch-up of missed runs depends on when the timer actually fired
+                        Command::FireSchedule(FireScheduleRequest {
+                            schedule_id: *schedule_id,
+                            due: timer.wake_up_time(),
+                            fired_at: MillisSinceEpoch::now(),
+                        })
+                    } else {
+                        Command::Timer(timer)
+                    };
+                    self.self_proposer.propose(partition_key, command).await?;
                 }
                 ActionEffect::ScheduleCleanupTimer(invocation_id, duration) => {
                     self.self_proposer
@@ -299,6 +336,77 @@ impl LeaderState {
         }
     }
 
+    pub async fn handle_rpc_batch_proposal_commands(
+        &mut self,
+        request_id: PartitionProcessorRpcRequestId,
+        reciprocal: RpcReciprocal,
+        commands: Vec<(PartitionProcessorRpcRequestId, PartitionKey, Command)>,
+    ) {
+        if let Some(batch) = self.awaiting_rpc_batches.get_mut(&request_id) {
+            // Someone already proposed this batch, replace the reciprocal and fail the old one
+            let old_reciprocal = mem::replace(&mut batch.reciprocal, reciprocal);
+            trace!(%request_id, "Replacing batch rpc with newer request");
+            old_reciprocal.send(Err(PartitionProcessorRpcError::Internal(
+                "retried".to_string(),
+            )));
+            return;
+        }
+
+        if commands.is_empty() {
+            reciprocal.send(Ok(PartitionProcessorRpcResponse::SubmittedBatch(vec![])));
+            return;
+        }
+
+        let mut item_ids = Vec::with_capacity(commands.len());
+        let mut proposals = Vec::with_capacity(commands.len());
+        for (item_request_id, partition_key, cmd) in commands {
+            item_ids.push(item_request_id);
+            proposals.push((partition_key, cmd));
+        }
+
+        if let Err(e) = self.self_proposer.propose_many(proposals).await {
+            reciprocal.send(Err(PartitionProcessorRpcError::Internal(e.to_string())));
+            return;
+        }
+
+        for (idx, item_request_id) in item_ids.iter().enumerate() {
+            self.awaiting_rpc_batch_items
+                .insert(*item_request_id, (request_id, idx));
+        }
+        self.awaiting_rpc_batches.insert(
+            request_id,
+            AwaitingRpcBatch {
+                reciprocal,
+                notifications: vec![None; item_ids.len()],
+                pending: item_ids.len(),
+            },
+        );
+    }
+
+    fn complete_batch_item(
+        &mut self,
+        batch_id: PartitionProcessorRpcRequestId,
+        idx: usize,
+        notification: SubmittedInvocationNotification,
+    ) {
+        let Entry::Occupied(mut o) = self.awaiting_rpc_batches.entry(batch_id) else {
+            return;
+        };
+
+        let batch = o.get_mut();
+        if batch.notifications[idx].replace(notification).is_none() {
+            batch.pending -= 1;
+        }
+        if batch.pending == 0 {
+            let batch = o.remove();
+            batch
+                .reciprocal
+                .send(Ok(PartitionProcessorRpcResponse::SubmittedBatch(
+                    batch.notifications.into_iter().flatten().collect(),
+                )));
+        }
+    }
+
     pub async fn self_propose_and_respond_asynchronously(
         &mut self,
         partition_key: PartitionKey,
@@ -425,14 +533,17 @@ impl LeaderState {
                 is_new_invocation,
                 ..
             } => {
+                let notification = SubmittedInvocationNotification {
+                    request_id,
+                    execution_time,
+                    is_new_invocation,
+                };
                 if let Some(response_tx) = self.awaiting_rpc_actions.remove(&request_id) {
-                    response_tx.send(Ok(PartitionProcessorRpcResponse::Submitted(
-                        SubmittedInvocationNotification {
-                            request_id,
-                            execution_time,
-                            is_new_invocation,
-                        },
-                    )));
+                    response_tx.send(Ok(PartitionProcessorRpcResponse::Submitted(notification)));
+                } else if let Some((batch_id, idx)) =
+                    self.awaiting_rpc_batch_items.remove(&request_id)
+                {
+                    self.complete_batch_item(batch_id, idx, notification);
                 }
             }
             Action::ScheduleInvocationStatusCleanup {
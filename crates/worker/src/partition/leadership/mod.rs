@@ -29,6 +29,7 @@ use restate_errors::NotRunningError;
 use restate_invoker_api::InvokeInputJournal;
 use restate_partition_store::PartitionStore;
 use restate_storage_api::deduplication_table::EpochSequenceNumber;
+use restate_storage_api::fsm_table::ReadOnlyFsmTable;
 use restate_storage_api::invocation_status_table::{
     InvokedInvocationStatusLite, ReadOnlyInvocationStatusTable,
 };
@@ -344,8 +345,10 @@ where
                 ShuffleMetadata::new(
                     self.partition_processor_metadata.partition_id,
                     *leader_epoch,
+                    partition_store.get_inherited_outbox_messages().await?,
                 ),
                 OutboxReader::from(partition_store.clone()),
+                OutboxReader::from(partition_store.clone()),
                 shuffle_tx,
                 self.channel_size,
                 self.bifrost.clone(),
@@ -534,6 +537,32 @@ where
         }
     }
 
+    /// Proposes a batch of commands, each one replying through the [`Action`] of its own request
+    /// id. The reciprocal is completed once all of them replied.
+    ///
+    /// [`Action`]: crate::partition::state_machine::Action
+    pub async fn handle_rpc_batch_proposal_commands(
+        &mut self,
+        request_id: PartitionProcessorRpcRequestId,
+        reciprocal: Reciprocal<
+            Oneshot<Result<PartitionProcessorRpcResponse, PartitionProcessorRpcError>>,
+        >,
+        commands: Vec<(PartitionProcessorRpcRequestId, PartitionKey, Command)>,
+    ) {
+        match &mut self.state {
+            State::Follower | State::Candidate { .. } => {
+                reciprocal.send(Err(PartitionProcessorRpcError::NotLeader(
+                    self.partition_processor_metadata.partition_id,
+                )))
+            }
+            State::Leader(leader_state) => {
+                leader_state
+                    .handle_rpc_batch_proposal_commands(request_id, reciprocal, commands)
+                    .await;
+            }
+        }
+    }
+
     /// Self propose to this partition, and register the reciprocal to respond asynchronously.
     pub async fn self_propose_and_respond_asynchronously(
         &mut self,
@@ -74,6 +74,30 @@ impl SelfProposer {
         Ok(())
     }
 
+    /// Proposes the commands, which are appended together as long as the appender keeps up.
+    pub async fn propose_many(
+        &mut self,
+        commands: impl IntoIterator<Item = (PartitionKey, Command)>,
+    ) -> Result<(), Error> {
+        let envelopes: Vec<_> = commands
+            .into_iter()
+            .map(|(partition_key, cmd)| {
+                Arc::new(Envelope::new(self.create_header(partition_key), cmd))
+            })
+            .collect();
+
+        // The appender can't reserve more slots than its queue size at once
+        for chunk in envelopes.chunks(BIFROST_QUEUE_SIZE) {
+            self.bifrost_appender
+                .sender()
+                .enqueue_many(chunk.iter().cloned())
+                .await
+                .map_err(|_| Error::SelfProposer)?;
+        }
+
+        Ok(())
+    }
+
     pub async fn propose_with_notification(
         &mut self,
         partition_key: PartitionKey,
@@ -26,6 +26,8 @@ use restate_bifrost::Bifrost;
 use restate_bifrost::loglet::FindTailOptions;
 use restate_core::network::{Oneshot, Reciprocal, ServiceMessage, Verdict};
 use restate_core::{ShutdownError, cancellation_watcher};
+use restate_invoker_api::StatusHandle;
+use restate_invoker_impl::ChannelStatusReader;
 use restate_partition_store::{PartitionStore, PartitionStoreTransaction};
 use restate_storage_api::deduplication_table::{
     DedupInformation, DedupSequenceNumber, DeduplicationTable, ProducerId,
@@ -34,7 +36,7 @@ use restate_storage_api::deduplication_table::{
 use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
 use restate_storage_api::idempotency_table::ReadOnlyIdempotencyTable;
 use restate_storage_api::invocation_status_table::{
-    InvocationStatus, ReadOnlyInvocationStatusTable,
+    CompletedInvocation, InvocationStatus, ReadOnlyInvocationStatusTable,
 };
 use restate_storage_api::outbox_table::ReadOnlyOutboxTable;
 use restate_storage_api::service_status_table::{
@@ -44,21 +46,23 @@ use restate_storage_api::{StorageError, Transaction};
 use restate_types::cluster::cluster_state::{PartitionProcessorStatus, ReplayStatus, RunMode};
 use restate_types::config::WorkerOptions;
 use restate_types::identifiers::{
-    LeaderEpoch, PartitionId, PartitionKey, PartitionProcessorRpcRequestId, WithPartitionKey,
+    InvocationId, LeaderEpoch, PartitionId, PartitionKey, PartitionProcessorRpcRequestId,
+    WithInvocationId, WithPartitionKey,
 };
 use restate_types::invocation;
 use restate_types::invocation::{
     AttachInvocationRequest, InvocationQuery, InvocationTarget, InvocationTargetType,
-    NotifySignalRequest, ResponseResult, ServiceInvocation, ServiceInvocationResponseSink,
-    SubmitNotificationSink, WorkflowHandlerType,
+    InvocationTermination, NotifySignalRequest, ResponseResult, ServiceInvocation,
+    ServiceInvocationResponseSink, SubmitNotificationSink, WorkflowHandlerType,
 };
 use restate_types::logs::MatchKeyQuery;
 use restate_types::logs::{KeyFilter, LogId, Lsn, SequenceNumber};
 use restate_types::net::RpcRequest;
 use restate_types::net::partition_processor::{
     AppendInvocationReplyOn, GetInvocationOutputResponseMode, IngressResponseResult,
-    InvocationOutput, PartitionLeaderService, PartitionProcessorRpcError,
-    PartitionProcessorRpcRequest, PartitionProcessorRpcRequestInner, PartitionProcessorRpcResponse,
+    InvocationDescription, InvocationOutput, InvocationProgress, PartitionLeaderService,
+    PartitionProcessorRpcError, PartitionProcessorRpcRequest, PartitionProcessorRpcRequestInner,
+    PartitionProcessorRpcResponse,
 };
 use restate_types::storage::StorageDecodeError;
 use restate_types::time::MillisSinceEpoch;
@@ -77,6 +81,7 @@ use crate::partition::state_machine::{ActionCollector, StateMachine};
 
 mod cleaner;
 pub mod invoker_storage_reader;
+mod kafka_egress;
 mod leadership;
 pub mod shuffle;
 pub mod snapshots;
@@ -101,6 +106,7 @@ pub(super) struct PartitionProcessorBuilder<InvokerInputSender> {
 
     status: PartitionProcessorStatus,
     invoker_tx: InvokerInputSender,
+    invoker_status_reader: ChannelStatusReader,
     control_rx: mpsc::Receiver<PartitionProcessorControlCommand>,
     network_svc_rx: mpsc::Receiver<ServiceMessage<PartitionLeaderService>>,
     status_watch_tx: watch::Sender<PartitionProcessorStatus>,
@@ -121,6 +127,7 @@ where
         network_svc_rx: mpsc::Receiver<ServiceMessage<PartitionLeaderService>>,
         status_watch_tx: watch::Sender<PartitionProcessorStatus>,
         invoker_tx: InvokerInputSender,
+        invoker_status_reader: ChannelStatusReader,
     ) -> Self {
         Self {
             partition_id,
@@ -131,6 +138,7 @@ where
             channel_size: options.internal_queue_length(),
             max_command_batch_size: options.max_command_batch_size(),
             invoker_tx,
+            invoker_status_reader,
             control_rx,
             network_svc_rx,
             status_watch_tx,
@@ -150,6 +158,7 @@ where
             channel_size,
             max_command_batch_size,
             invoker_tx,
+            invoker_status_reader,
             control_rx,
             network_svc_rx: rpc_rx,
             status_watch_tx,
@@ -185,6 +194,7 @@ where
             partition_id,
             partition_key_range,
             leadership_state,
+            invoker_status_reader,
             state_machine,
             max_command_batch_size,
             partition_store,
@@ -203,6 +213,7 @@ where
         let inbox_seq_number = partition_store.get_inbox_seq_number().await?;
         let outbox_seq_number = partition_store.get_outbox_seq_number().await?;
         let outbox_head_seq_number = partition_store.get_outbox_head_seq_number().await?;
+        let state_ttl_services = partition_store.get_state_ttl_services().await?;
 
         let state_machine = StateMachine::new(
             inbox_seq_number,
@@ -210,7 +221,8 @@ where
             outbox_head_seq_number,
             partition_key_range,
             EnumSet::empty(),
-        );
+        )
+        .with_state_ttl_services(state_ttl_services);
 
         Ok(state_machine)
     }
@@ -220,6 +232,7 @@ pub struct PartitionProcessor<InvokerSender> {
     partition_id: PartitionId,
     partition_key_range: RangeInclusive<PartitionKey>,
     leadership_state: LeadershipState<InvokerSender>,
+    invoker_status_reader: ChannelStatusReader,
     state_machine: StateMachine,
     bifrost: Bifrost,
     control_rx: mpsc::Receiver<PartitionProcessorControlCommand>,
@@ -253,7 +266,8 @@ pub enum ProcessorError {
     Other(#[from] anyhow::Error),
 }
 
-type LsnEnvelope = (Lsn, Arc<Envelope>);
+/// Envelope read from the log, with its LSN and the creation time of its record.
+type LsnEnvelope = (Lsn, MillisSinceEpoch, Arc<Envelope>);
 
 impl<InvokerSender> PartitionProcessor<InvokerSender>
 where
@@ -384,12 +398,16 @@ where
                     trace!(?entry, "Read entry");
                     let lsn = entry.sequence_number();
                     if entry.is_data_record() {
-                        entry.as_record().inspect(|record| {
-                            record_write_to_read_latencty.record(record.created_at().elapsed());
-                        });
+                        let created_at = entry
+                            .as_record()
+                            .map(|record| {
+                                record_write_to_read_latencty.record(record.created_at().elapsed());
+                                MillisSinceEpoch::from(record.created_at())
+                            })
+                            .expect("data record is present");
                         entry
                             .try_decode_arc::<Envelope>()
-                            .map(|envelope| Ok((lsn, envelope?)))
+                            .map(|envelope| Ok((lsn, created_at, envelope?)))
                             .expect("data record is present")
                     } else {
                         Err(ProcessorError::TrimGapEncountered {
@@ -402,7 +420,7 @@ where
                 }
                 Err(err) => Err(ProcessorError::from(err)),
             })
-            .try_take_while(|(_, envelope)| {
+            .try_take_while(|(_, _, envelope)| {
                 // a catch-all safety net if all lower layers didn't filter this record out. This
                 // could happen for old records that didn't store `Keys` in the log store.
                 //
@@ -464,13 +482,14 @@ where
                     // clear buffers used when applying the next record
                     action_collector.clear();
 
-                    for (lsn, envelope) in command_buffer.drain(..) {
+                    for (lsn, created_at, envelope) in command_buffer.drain(..) {
                         let command_start = Instant::now();
 
                         trace!(%lsn, "Processing bifrost record for '{}': {:?}", envelope.command.name(), envelope.header);
 
                         let leadership_change = self.apply_record(
                             lsn,
+                            created_at,
                             envelope,
                             &mut transaction,
                             &mut action_collector).await?;
@@ -692,17 +711,73 @@ where
                     )
                     .await;
             }
+            PartitionProcessorRpcRequestInner::GetInvocationProgress(invocation_query) => {
+                response_tx.send(
+                    self.handle_rpc_get_invocation_progress(invocation_query, partition_store)
+                        .await
+                        .map_err(|err| PartitionProcessorRpcError::Internal(err.to_string())),
+                );
+            }
+            PartitionProcessorRpcRequestInner::DescribeInvocation(invocation_query) => {
+                response_tx.send(
+                    self.handle_rpc_describe_invocation(invocation_query, partition_store)
+                        .await
+                        .map_err(|err| PartitionProcessorRpcError::Internal(err.to_string())),
+                );
+            }
+            PartitionProcessorRpcRequestInner::CancelInvocation(invocation_id) => {
+                self.leadership_state
+                    .self_propose_and_respond_asynchronously(
+                        invocation_id.partition_key(),
+                        Command::TerminateInvocation(InvocationTermination::cancel(invocation_id)),
+                        response_tx,
+                    )
+                    .await;
+            }
+            PartitionProcessorRpcRequestInner::AppendInvocations(invocation_requests) => {
+                if let Some(invocation_request) = invocation_requests
+                    .iter()
+                    .find(|request| !self.partition_key_range.contains(&request.partition_key()))
+                {
+                    response_tx.send(Err(PartitionProcessorRpcError::Internal(format!(
+                        "invocation {} doesn't belong to partition {}",
+                        invocation_request.invocation_id(),
+                        self.partition_id
+                    ))));
+                    return;
+                }
+
+                // Each invocation gets its own request id, to correlate its submit notification
+                let commands = invocation_requests
+                    .into_iter()
+                    .map(|invocation_request| {
+                        let request_id = PartitionProcessorRpcRequestId::new();
+                        let mut service_invocation = ServiceInvocation::from_request(
+                            invocation_request,
+                            invocation::Source::ingress(request_id),
+                        );
+                        service_invocation.submit_notification_sink =
+                            Some(SubmitNotificationSink::Ingress { request_id });
+                        (
+                            request_id,
+                            service_invocation.partition_key(),
+                            Command::Invoke(service_invocation),
+                        )
+                    })
+                    .collect();
+
+                self.leadership_state
+                    .handle_rpc_batch_proposal_commands(request_id, response_tx, commands)
+                    .await
+            }
         };
     }
 
-    async fn handle_rpc_get_invocation_output(
-        &self,
-        request_id: PartitionProcessorRpcRequestId,
+    async fn resolve_invocation_query(
         invocation_query: InvocationQuery,
         partition_store: &mut PartitionStore,
-    ) -> Result<PartitionProcessorRpcResponse, StorageError> {
-        // We can handle this immediately by querying the partition store, no need to go through proposals
-        let invocation_id = match invocation_query {
+    ) -> Result<InvocationId, StorageError> {
+        Ok(match invocation_query {
             InvocationQuery::Invocation(iid) => iid,
             ref q @ InvocationQuery::Workflow(ref sid) => {
                 // TODO We need this query for backward compatibility, remove when we remove the idempotency table
@@ -724,7 +799,124 @@ where
                     }
                 }
             }
+        })
+    }
+
+    async fn handle_rpc_get_invocation_progress(
+        &self,
+        invocation_query: InvocationQuery,
+        partition_store: &mut PartitionStore,
+    ) -> Result<PartitionProcessorRpcResponse, StorageError> {
+        let invocation_id =
+            Self::resolve_invocation_query(invocation_query, partition_store).await?;
+
+        let invocation_status = partition_store
+            .get_invocation_status(&invocation_id)
+            .await?;
+        Ok(
+            match self
+                .invocation_progress(&invocation_id, &invocation_status)
+                .await
+            {
+                Some(progress) => PartitionProcessorRpcResponse::Progress(progress),
+                None => PartitionProcessorRpcResponse::NotFound,
+            },
+        )
+    }
+
+    async fn handle_rpc_describe_invocation(
+        &self,
+        invocation_query: InvocationQuery,
+        partition_store: &mut PartitionStore,
+    ) -> Result<PartitionProcessorRpcResponse, StorageError> {
+        let invocation_id =
+            Self::resolve_invocation_query(invocation_query, partition_store).await?;
+
+        let invocation_status = partition_store
+            .get_invocation_status(&invocation_id)
+            .await?;
+        let (Some(progress), Some(invocation_target)) = (
+            self.invocation_progress(&invocation_id, &invocation_status)
+                .await,
+            invocation_status.invocation_target().cloned(),
+        ) else {
+            return Ok(PartitionProcessorRpcResponse::NotFound);
         };
+        let output = match invocation_status {
+            InvocationStatus::Completed(CompletedInvocation {
+                response_result: ResponseResult::Success(output),
+                ..
+            }) => Some(output),
+            _ => None,
+        };
+
+        Ok(PartitionProcessorRpcResponse::Description(
+            InvocationDescription {
+                invocation_id,
+                invocation_target,
+                progress,
+                output,
+            },
+        ))
+    }
+
+    /// Progress of the invocation with the given status, `None` if the invocation doesn't exist.
+    async fn invocation_progress(
+        &self,
+        invocation_id: &InvocationId,
+        invocation_status: &InvocationStatus,
+    ) -> Option<InvocationProgress> {
+        Some(match invocation_status {
+            InvocationStatus::Free => return None,
+            InvocationStatus::Scheduled(scheduled) => InvocationProgress::Scheduled {
+                execution_time: scheduled.metadata.execution_time,
+            },
+            InvocationStatus::Inboxed(_) => InvocationProgress::Inboxed,
+            InvocationStatus::Suspended { .. } => InvocationProgress::Suspended,
+            InvocationStatus::Paused(_) => InvocationProgress::Paused,
+            InvocationStatus::Completed(completed) => InvocationProgress::Completed {
+                failure: match &completed.response_result {
+                    ResponseResult::Success(_) => None,
+                    ResponseResult::Failure(err) => Some(err.clone()),
+                },
+            },
+            InvocationStatus::Invoked(_) => {
+                // The invoker knows whether the invocation is running or backing off
+                let partition_key = invocation_id.partition_key();
+                let report = self
+                    .invoker_status_reader
+                    .read_status(partition_key..=partition_key)
+                    .await
+                    .find(|report| report.invocation_id() == invocation_id);
+                match report {
+                    Some(report) if report.next_retry_at().is_some() => {
+                        InvocationProgress::Retrying {
+                            retry_count: u32::try_from(report.retry_count()).unwrap_or(u32::MAX),
+                            last_failure: report
+                                .last_retry_attempt_failure()
+                                .map(|failure| failure.err.clone()),
+                            next_retry_at: report.next_retry_at().map(MillisSinceEpoch::from),
+                        }
+                    }
+                    report => InvocationProgress::Invoked {
+                        attempt: report
+                            .map(|report| u32::try_from(report.retry_count()).unwrap_or(u32::MAX))
+                            .unwrap_or_default(),
+                    },
+                }
+            }
+        })
+    }
+
+    async fn handle_rpc_get_invocation_output(
+        &self,
+        request_id: PartitionProcessorRpcRequestId,
+        invocation_query: InvocationQuery,
+        partition_store: &mut PartitionStore,
+    ) -> Result<PartitionProcessorRpcResponse, StorageError> {
+        // We can handle this immediately by querying the partition store, no need to go through proposals
+        let invocation_id =
+            Self::resolve_invocation_query(invocation_query, partition_store).await?;
 
         let invocation_status = partition_store
             .get_invocation_status(&invocation_id)
@@ -766,6 +958,7 @@ where
     async fn apply_record<'a, 'b: 'a>(
         &mut self,
         lsn: Lsn,
+        record_created_at: MillisSinceEpoch,
         envelope: Arc<Envelope>,
         transaction: &mut PartitionStoreTransaction<'b>,
         action_collector: &mut ActionCollector,
@@ -818,6 +1011,8 @@ where
                 self.state_machine
                     .apply(
                         envelope.command,
+                        lsn,
+                        record_created_at,
                         transaction,
                         action_collector,
                         self.leadership_state.is_leader(),
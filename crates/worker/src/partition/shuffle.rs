@@ -12,17 +12,18 @@ use std::future::Future;
 use std::sync::Arc;
 
 use async_channel::{TryRecvError, TrySendError};
-use tokio::sync::mpsc;
+use tokio::sync::{Notify, mpsc};
 use tracing::debug;
 
 use restate_bifrost::Bifrost;
 use restate_core::{Metadata, cancellation_watcher};
 use restate_storage_api::deduplication_table::DedupInformation;
-use restate_storage_api::outbox_table::OutboxMessage;
+use restate_storage_api::outbox_table::{InheritedOutboxMessages, OutboxMessage};
 use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey, WithPartitionKey};
 use restate_types::message::MessageIndex;
 use restate_wal_protocol::{Destination, Envelope, Header, Source};
 
+use crate::partition::kafka_egress::KafkaEgress;
 use crate::partition::shuffle::state_machine::StateMachine;
 use crate::partition::types::OutboxMessageExt;
 
@@ -54,15 +55,17 @@ impl OutboxTruncation {
     }
 }
 
+/// Returns `None` for the messages which are not shuffled to a partition, see
+/// [`OutboxMessageExt::to_command`].
 pub(crate) fn wrap_outbox_message_in_envelope(
     message: OutboxMessage,
     seq_number: MessageIndex,
     shuffle_metadata: &ShuffleMetadata,
-) -> Envelope {
-    Envelope::new(
-        create_header(message.partition_key(), seq_number, shuffle_metadata),
-        message.to_command(),
-    )
+) -> Option<Envelope> {
+    let header = create_header(message.partition_key(), seq_number, shuffle_metadata);
+    message
+        .to_command()
+        .map(|command| Envelope::new(header, command))
 }
 
 fn create_header(
@@ -81,10 +84,7 @@ fn create_header(
         },
         dest: Destination::Processor {
             partition_key: dest_partition_key,
-            dedup: Some(DedupInformation::cross_partition(
-                shuffle_metadata.partition_id,
-                seq_number,
-            )),
+            dedup: Some(shuffle_metadata.dedup_information(seq_number)),
         },
     }
 }
@@ -110,17 +110,26 @@ pub(crate) struct HintSender {
 
     // receiver to pop the oldest messages from the hint channel
     rx: async_channel::Receiver<NewOutboxMessage>,
+
+    // wakes up the Kafka egress
+    new_message: Arc<Notify>,
 }
 
 impl HintSender {
     fn new(
         tx: async_channel::Sender<NewOutboxMessage>,
         rx: async_channel::Receiver<NewOutboxMessage>,
+        new_message: Arc<Notify>,
     ) -> Self {
-        Self { tx, rx }
+        Self {
+            tx,
+            rx,
+            new_message,
+        }
     }
 
     pub(crate) fn send(&self, mut outbox_message: NewOutboxMessage) {
+        self.new_message.notify_one();
         loop {
             let result = self.tx.try_send(outbox_message);
 
@@ -149,19 +158,36 @@ impl HintSender {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub(crate) struct ShuffleMetadata {
     partition_id: PartitionId,
     leader_epoch: LeaderEpoch,
+    inherited_outbox: Vec<InheritedOutboxMessages>,
 }
 
 impl ShuffleMetadata {
-    pub(crate) fn new(partition_id: PartitionId, leader_epoch: LeaderEpoch) -> Self {
+    pub(crate) fn new(
+        partition_id: PartitionId,
+        leader_epoch: LeaderEpoch,
+        inherited_outbox: Vec<InheritedOutboxMessages>,
+    ) -> Self {
         ShuffleMetadata {
             partition_id,
             leader_epoch,
+            inherited_outbox,
         }
     }
+
+    /// Messages inherited from a split or merged partition are deduplicated as the messages of the
+    /// partition which produced them.
+    fn dedup_information(&self, seq_number: MessageIndex) -> DedupInformation {
+        let (producer_id, producer_seq_number) = self
+            .inherited_outbox
+            .iter()
+            .find_map(|inherited| inherited.producer_of(seq_number))
+            .unwrap_or((self.partition_id, seq_number));
+        DedupInformation::cross_partition(producer_id, producer_seq_number)
+    }
 }
 
 pub(super) struct Shuffle<OR> {
@@ -169,6 +195,9 @@ pub(super) struct Shuffle<OR> {
 
     outbox_reader: OR,
 
+    // the Kafka egress consumes the outbox independently
+    kafka_egress_outbox_reader: OR,
+
     bifrost: Bifrost,
 
     // used to tell partition processor about outbox truncations
@@ -178,6 +207,9 @@ pub(super) struct Shuffle<OR> {
 
     // used to create the senders into the shuffle
     hint_tx: async_channel::Sender<NewOutboxMessage>,
+
+    // used to wake up the Kafka egress on new outbox messages
+    new_message: Arc<Notify>,
 }
 
 impl<OR> Shuffle<OR>
@@ -187,6 +219,7 @@ where
     pub(super) fn new(
         metadata: ShuffleMetadata,
         outbox_reader: OR,
+        kafka_egress_outbox_reader: OR,
         truncation_tx: mpsc::Sender<OutboxTruncation>,
         channel_size: usize,
         bifrost: Bifrost,
@@ -196,15 +229,21 @@ where
         Self {
             metadata,
             outbox_reader,
+            kafka_egress_outbox_reader,
             truncation_tx,
             hint_rx,
             hint_tx,
             bifrost,
+            new_message: Arc::new(Notify::new()),
         }
     }
 
     pub(super) fn create_hint_sender(&self) -> HintSender {
-        HintSender::new(self.hint_tx.clone(), self.hint_rx.clone())
+        HintSender::new(
+            self.hint_tx.clone(),
+            self.hint_rx.clone(),
+            Arc::clone(&self.new_message),
+        )
     }
 
     pub(super) async fn run(self) -> anyhow::Result<()> {
@@ -212,21 +251,35 @@ where
             metadata,
             mut hint_rx,
             outbox_reader,
+            kafka_egress_outbox_reader,
             truncation_tx,
             bifrost,
+            new_message,
             ..
         } = self;
 
         let node_id = Metadata::with_current(|m| m.my_node_id());
         debug!(restate.node = %node_id, restate.partition.id = %metadata.partition_id, "Running shuffle");
 
+        // Kafka records don't go through Bifrost, they're published by the egress
+        let (kafka_egress, mut kafka_egress_handled_rx) = KafkaEgress::new(
+            metadata.partition_id,
+            metadata.leader_epoch,
+            kafka_egress_outbox_reader,
+            new_message,
+        );
+        let kafka_egress = kafka_egress.run();
+        tokio::pin!(kafka_egress);
+
         let state_machine = StateMachine::new(
-            metadata,
             outbox_reader,
-            move |msg| {
+            move |seq_number, message| {
                 let bifrost = bifrost.clone();
+                let envelope = wrap_outbox_message_in_envelope(message, seq_number, &metadata);
                 async move {
-                    restate_bifrost::append_to_bifrost(&bifrost, Arc::new(msg)).await?;
+                    if let Some(envelope) = envelope {
+                        restate_bifrost::append_to_bifrost(&bifrost, Arc::new(envelope)).await?;
+                    }
                     Ok(())
                 }
             },
@@ -235,18 +288,31 @@ where
 
         tokio::pin!(state_machine);
 
+        let mut last_shuffled_message_index = None;
         loop {
             tokio::select! {
                 shuffled_message_index = state_machine.as_mut().shuffle_next_message() => {
-                    let shuffled_message_index = shuffled_message_index?;
-
-                    // this is just a hint which we can drop
-                    let _ = truncation_tx.try_send(OutboxTruncation::new(shuffled_message_index));
+                    last_shuffled_message_index = Some(shuffled_message_index?);
+                },
+                Ok(()) = kafka_egress_handled_rx.changed() => {},
+                result = &mut kafka_egress => {
+                    result?;
                 },
                 _ = cancellation_watcher() => {
                     break;
                 }
             }
+
+            // Messages can be truncated once they've been both shuffled and published by the egress
+            if let (Some(shuffled_message_index), Some(handled_message_index)) = (
+                last_shuffled_message_index,
+                *kafka_egress_handled_rx.borrow_and_update(),
+            ) {
+                // this is just a hint which we can drop
+                let _ = truncation_tx.try_send(OutboxTruncation::new(
+                    shuffled_message_index.min(handled_message_index),
+                ));
+            }
         }
 
         debug!(restate.node = %node_id, "Stopping shuffle");
@@ -265,12 +331,9 @@ mod state_machine {
 
     use restate_storage_api::outbox_table::OutboxMessage;
     use restate_types::message::MessageIndex;
-    use restate_wal_protocol::Envelope;
 
     use crate::partition::shuffle;
-    use crate::partition::shuffle::{
-        NewOutboxMessage, OutboxReaderError, ShuffleMetadata, wrap_outbox_message_in_envelope,
-    };
+    use crate::partition::shuffle::{NewOutboxMessage, OutboxReaderError};
 
     type ReadFuture<OutboxReader> = ReusableBoxFuture<
         'static,
@@ -289,7 +352,6 @@ mod state_machine {
 
     #[pin_project]
     pub(super) struct StateMachine<'a, OutboxReader, SendOp, SendFuture> {
-        metadata: ShuffleMetadata,
         current_sequence_number: MessageIndex,
         outbox_reader: Option<OutboxReader>,
         read_future: ReadFuture<OutboxReader>,
@@ -313,11 +375,10 @@ mod state_machine {
     impl<'a, OutboxReader, SendOp, SendFuture> StateMachine<'a, OutboxReader, SendOp, SendFuture>
     where
         SendFuture: Future<Output = Result<(), anyhow::Error>>,
-        SendOp: Fn(Envelope) -> SendFuture,
+        SendOp: Fn(MessageIndex, OutboxMessage) -> SendFuture,
         OutboxReader: shuffle::OutboxReader + Send + Sync + 'static,
     {
         pub(super) fn new(
-            metadata: ShuffleMetadata,
             outbox_reader: OutboxReader,
             send_operation: SendOp,
             hint_rx: &'a mut async_channel::Receiver<NewOutboxMessage>,
@@ -329,7 +390,6 @@ mod state_machine {
             let reading_future = get_next_message(outbox_reader, current_sequence_number);
 
             Self {
-                metadata,
                 current_sequence_number,
                 outbox_reader: None,
                 read_future: ReusableBoxFuture::new(reading_future),
@@ -358,12 +418,8 @@ mod state_machine {
 
                             match seq_number.cmp(this.current_sequence_number) {
                                 Ordering::Equal => {
-                                    let envelope = wrap_outbox_message_in_envelope(
-                                        message.clone(),
-                                        seq_number,
-                                        this.metadata,
-                                    );
-                                    let send_future = (this.send_operation)(envelope);
+                                    let send_future =
+                                        (this.send_operation)(seq_number, message.clone());
                                     this.state.set(State::Sending(send_future));
                                     break;
                                 }
@@ -396,9 +452,7 @@ mod state_machine {
 
                             *this.current_sequence_number = seq_number;
 
-                            let envelope =
-                                wrap_outbox_message_in_envelope(message, seq_number, this.metadata);
-                            let send_future = (this.send_operation)(envelope);
+                            let send_future = (this.send_operation)(seq_number, message);
 
                             this.state.set(State::Sending(send_future));
                         } else {
@@ -615,6 +669,7 @@ mod tests {
 
     async fn create_shuffle_env<OR: OutboxReader + Send + Sync + 'static>(
         outbox_reader: OR,
+        kafka_egress_outbox_reader: OR,
     ) -> ShuffleEnv<OR> {
         // set numbers of partitions to 1 to easily find all sent messages by the shuffle
         let env = TestCoreEnvBuilder::with_incoming_only_connector()
@@ -624,12 +679,19 @@ mod tests {
             ))
             .build()
             .await;
-        let metadata = ShuffleMetadata::new(PartitionId::from(0), LeaderEpoch::from(0));
+        let metadata = ShuffleMetadata::new(PartitionId::from(0), LeaderEpoch::from(0), Vec::new());
 
         let (truncation_tx, _truncation_rx) = mpsc::channel(1);
 
         let bifrost = Bifrost::init_in_memory(env.metadata_writer.clone()).await;
-        let shuffle = Shuffle::new(metadata, outbox_reader, truncation_tx, 1, bifrost.clone());
+        let shuffle = Shuffle::new(
+            metadata,
+            outbox_reader,
+            kafka_egress_outbox_reader,
+            truncation_tx,
+            1,
+            bifrost.clone(),
+        );
 
         ShuffleEnv {
             env,
@@ -653,7 +715,8 @@ mod tests {
             .expect("service invocation should be present");
 
         let outbox_reader = MockOutboxReader::new(42, expected_messages.clone());
-        let shuffle_env = create_shuffle_env(outbox_reader).await;
+        let shuffle_env =
+            create_shuffle_env(outbox_reader, MockOutboxReader::new(0, Vec::new())).await;
 
         let partition_id = shuffle_env.shuffle.metadata.partition_id;
         TaskCenter::spawn_child(TaskKind::Shuffle, "shuffle", shuffle_env.shuffle.run())?;
@@ -690,7 +753,8 @@ mod tests {
             .expect("service invocation should be present");
 
         let outbox_reader = MockOutboxReader::new(42, expected_messages.clone());
-        let shuffle_env = create_shuffle_env(outbox_reader).await;
+        let shuffle_env =
+            create_shuffle_env(outbox_reader, MockOutboxReader::new(0, Vec::new())).await;
 
         let partition_id = shuffle_env.shuffle.metadata.partition_id;
         TaskCenter::spawn_child(TaskKind::Shuffle, "shuffle", shuffle_env.shuffle.run())?;
@@ -723,7 +787,11 @@ mod tests {
             .expect("service invocation should be present");
 
         let mut outbox_reader = Arc::new(FailingOutboxReader::new(expected_messages.clone(), 10));
-        let shuffle_env = create_shuffle_env(Arc::clone(&outbox_reader)).await;
+        let shuffle_env = create_shuffle_env(
+            Arc::clone(&outbox_reader),
+            Arc::new(FailingOutboxReader::new(Vec::new(), MessageIndex::MAX)),
+        )
+        .await;
         let total_restarts = Arc::new(AtomicUsize::new(0));
 
         let partition_id = shuffle_env.shuffle.metadata.partition_id;
@@ -738,7 +806,7 @@ mod tests {
             let total_restarts = Arc::clone(&total_restarts);
             async move {
                 let mut shuffle = shuffle_env.shuffle;
-                let metadata = shuffle.metadata;
+                let metadata = shuffle.metadata.clone();
                 let truncation_tx = shuffle.truncation_tx.clone();
                 let mut processed_range = 0;
                 let mut num_restarts = 0;
@@ -762,8 +830,9 @@ mod tests {
                     }
 
                     shuffle = Shuffle::new(
-                        metadata,
+                        metadata.clone(),
                         Arc::clone(&outbox_reader),
+                        Arc::new(FailingOutboxReader::new(Vec::new(), MessageIndex::MAX)),
                         truncation_tx.clone(),
                         1,
                         shuffle_env.bifrost.clone(),
@@ -9,11 +9,14 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, anyhow, bail};
 use bytes::BytesMut;
+use futures::TryStreamExt;
 use object_store::path::Path as ObjectPath;
 use object_store::{MultipartUpload, ObjectStore, PutMode, PutOptions, PutPayload, UpdateVersion};
 use serde::{Deserialize, Serialize};
@@ -58,6 +61,10 @@ pub struct SnapshotRepository {
     staging_dir: PathBuf,
     /// Expected cluster name for the snapshots in this repository.
     cluster_name: String,
+    /// Number of most recent snapshots to retain per partition when pruning.
+    retention_num_snapshots: Option<NonZeroUsize>,
+    /// Snapshots newer than this period are retained when pruning.
+    retention_period: Option<Duration>,
 }
 
 /// S3 and other stores require a certain minimum size for the parts of a multipart upload. It is an
@@ -110,6 +117,14 @@ impl LatestSnapshot {
     }
 }
 
+/// A complete snapshot stored in the repository.
+#[derive(Debug)]
+pub struct StoredSnapshot {
+    pub metadata: PartitionSnapshotMetadata,
+    /// Whether the latest snapshot pointer of the partition refers to this snapshot.
+    pub is_latest: bool,
+}
+
 struct UniqueSnapshotKey {
     lsn: Lsn,
     snapshot_id: SnapshotId,
@@ -132,6 +147,15 @@ impl UniqueSnapshotKey {
             snapshot_id = self.snapshot_id
         )
     }
+
+    /// Parse the path component constructed by [`Self::padded_key`].
+    fn parse(key: &str) -> Option<Self> {
+        let (lsn, snapshot_id) = key.strip_prefix("lsn_")?.split_once('-')?;
+        Some(UniqueSnapshotKey {
+            lsn: Lsn::new(lsn.parse().ok()?),
+            snapshot_id: snapshot_id.parse().ok()?,
+        })
+    }
 }
 
 impl SnapshotRepository {
@@ -166,6 +190,8 @@ impl SnapshotRepository {
             prefix: ObjectPath::from(prefix),
             staging_dir,
             cluster_name,
+            retention_num_snapshots: snapshots_options.retention_num_snapshots,
+            retention_period: snapshots_options.retention_period.map(Into::into),
         }))
     }
 
@@ -504,13 +530,213 @@ impl SnapshotRepository {
         &self,
         partition_id: PartitionId,
     ) -> anyhow::Result<Lsn> {
+        Ok(self
+            .get_latest_snapshot(partition_id)
+            .await?
+            .map(|latest| latest.min_applied_lsn)
+            .unwrap_or(Lsn::INVALID))
+    }
+
+    /// List the complete snapshots of a partition, ordered from the newest to the oldest.
+    #[instrument(
+        level = "debug",
+        skip_all,
+        err,
+        fields(%partition_id),
+    )]
+    pub(crate) async fn list(
+        &self,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<Vec<StoredSnapshot>> {
+        let latest = self.get_latest_snapshot(partition_id).await?;
+
+        Ok(self
+            .list_snapshot_prefixes(partition_id)
+            .await?
+            .into_iter()
+            .filter_map(|(_, metadata)| metadata)
+            .map(|metadata| StoredSnapshot {
+                is_latest: latest
+                    .as_ref()
+                    .is_some_and(|latest| latest.snapshot_id == metadata.snapshot_id),
+                metadata,
+            })
+            .collect())
+    }
+
+    /// Delete a snapshot from the repository. The latest snapshot of a partition cannot be
+    /// deleted, since nodes bootstrap from it.
+    #[instrument(
+        level = "debug",
+        skip_all,
+        err,
+        fields(%partition_id, %snapshot_id),
+    )]
+    pub(crate) async fn delete(
+        &self,
+        partition_id: PartitionId,
+        snapshot_id: SnapshotId,
+    ) -> anyhow::Result<()> {
+        if self
+            .get_latest_snapshot(partition_id)
+            .await?
+            .is_some_and(|latest| latest.snapshot_id == snapshot_id)
+        {
+            bail!(
+                "Refusing to delete the latest snapshot {snapshot_id} of partition {partition_id}"
+            );
+        }
+
+        let Some((key, _)) = self
+            .list_snapshot_prefixes(partition_id)
+            .await?
+            .into_iter()
+            .find(|(key, _)| key.snapshot_id == snapshot_id)
+        else {
+            bail!("Snapshot {snapshot_id} of partition {partition_id} not found");
+        };
+
+        self.delete_snapshot_prefix(partition_id, &key).await
+    }
+
+    /// Delete the snapshots of a partition which are no longer retained by the configured
+    /// retention settings. The latest snapshot, and snapshots the log can still be replayed on
+    /// top of (at or above the trim point of the partition's log), are always retained. Partially
+    /// uploaded snapshots are deleted once they are older than the latest snapshot.
+    ///
+    /// Returns the ids of the deleted snapshots.
+    #[instrument(
+        level = "debug",
+        skip_all,
+        err,
+        fields(%partition_id),
+    )]
+    pub(crate) async fn prune(
+        &self,
+        partition_id: PartitionId,
+        trim_point: Lsn,
+    ) -> anyhow::Result<Vec<SnapshotId>> {
+        if self.retention_num_snapshots.is_none() && self.retention_period.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let Some(latest) = self.get_latest_snapshot(partition_id).await? else {
+            return Ok(Vec::new());
+        };
+
+        let retain_created_after = self
+            .retention_period
+            .and_then(|period| SystemTime::now().checked_sub(period));
+        let mut num_snapshots = 0;
+        let mut deleted = Vec::new();
+
+        for (key, metadata) in self.list_snapshot_prefixes(partition_id).await? {
+            let retained = match metadata {
+                None => key.lsn >= latest.min_applied_lsn,
+                Some(metadata) => {
+                    num_snapshots += 1;
+                    metadata.snapshot_id == latest.snapshot_id
+                        || self
+                            .retention_num_snapshots
+                            .is_some_and(|retained| num_snapshots <= retained.get())
+                        || retain_created_after
+                            .is_some_and(|created_after| *metadata.created_at > created_after)
+                }
+            } || key.lsn >= trim_point;
+
+            if !retained {
+                self.delete_snapshot_prefix(partition_id, &key).await?;
+                deleted.push(key.snapshot_id);
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    /// List the snapshot prefixes of a partition along with the snapshot metadata, ordered from
+    /// the newest to the oldest. The metadata is missing for partially uploaded snapshots.
+    async fn list_snapshot_prefixes(
+        &self,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<Vec<(UniqueSnapshotKey, Option<PartitionSnapshotMetadata>)>> {
+        let partition_prefix = self.get_partition_snapshots_prefix(partition_id);
+        let list_result = self
+            .object_store
+            .list_with_delimiter(Some(&partition_prefix))
+            .await?;
+
+        let mut snapshots = Vec::with_capacity(list_result.common_prefixes.len());
+        for prefix in list_result.common_prefixes {
+            let Some(key) = prefix.filename().and_then(UniqueSnapshotKey::parse) else {
+                debug!(%prefix, "Ignoring unknown prefix in snapshot repository");
+                continue;
+            };
+
+            let metadata = match self.object_store.get(&prefix.child("metadata.json")).await {
+                Ok(result) => Some(serde_json::from_slice(&result.bytes().await?)?),
+                Err(object_store::Error::NotFound { .. }) => None,
+                Err(e) => return Err(e.into()),
+            };
+            snapshots.push((key, metadata));
+        }
+
+        snapshots.sort_by(|(left, _), (right, _)| right.lsn.cmp(&left.lsn));
+        Ok(snapshots)
+    }
+
+    async fn delete_snapshot_prefix(
+        &self,
+        partition_id: PartitionId,
+        key: &UniqueSnapshotKey,
+    ) -> anyhow::Result<()> {
+        let snapshot_prefix = self
+            .get_partition_snapshots_prefix(partition_id)
+            .child(key.padded_key());
+
+        // Delete the metadata first, so that a partially deleted snapshot is never listed as
+        // complete; the remaining files are deleted by the next pruning attempt if we fail midway.
+        match self
+            .object_store
+            .delete(&snapshot_prefix.child("metadata.json"))
+            .await
+        {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let files: Vec<_> = self
+            .object_store
+            .list(Some(&snapshot_prefix))
+            .map_ok(|object| object.location)
+            .try_collect()
+            .await?;
+        for file in files {
+            match self.object_store.delete(&file).await {
+                Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        info!(
+            snapshot_id = %key.snapshot_id,
+            min_applied_lsn = %key.lsn,
+            "Deleted snapshot from repository"
+        );
+        Ok(())
+    }
+
+    /// Get the latest snapshot pointer of a partition, if any.
+    async fn get_latest_snapshot(
+        &self,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<Option<LatestSnapshot>> {
         let latest_path = self.get_latest_snapshot_pointer(partition_id);
 
         let latest = match self.object_store.get(&latest_path).await {
             Ok(result) => result,
             Err(object_store::Error::NotFound { .. }) => {
                 debug!("Latest snapshot data not found in repository");
-                return Ok(Lsn::INVALID);
+                return Ok(None);
             }
             Err(e) => return Err(e.into()),
         };
@@ -518,7 +744,7 @@ impl SnapshotRepository {
         let latest: LatestSnapshot = serde_json::from_slice(&latest.bytes().await?)?;
         debug!("Latest snapshot metadata: {:?}", latest);
 
-        Ok(latest.min_applied_lsn)
+        Ok(Some(latest))
     }
 
     async fn get_latest_snapshot_metadata_for_update(
@@ -700,6 +926,7 @@ mod tests {
     use object_store::ObjectStore;
     use restate_object_store_util::create_object_store_client;
     use restate_types::retries::RetryPolicy;
+    use std::num::NonZeroUsize;
     use std::time::SystemTime;
     use tempfile::TempDir;
     use tokio::io::AsyncWriteExt;
@@ -906,6 +1133,75 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_prune_snapshots() -> anyhow::Result<()> {
+        let snapshots_destination = TempDir::new()?;
+        let opts = SnapshotsOptions {
+            destination: Some(
+                Url::from_file_path(snapshots_destination.path())
+                    .unwrap()
+                    .to_string(),
+            ),
+            retention_num_snapshots: Some(NonZeroUsize::new(2).unwrap()),
+            ..SnapshotsOptions::default()
+        };
+        let repository = SnapshotRepository::create_if_configured(
+            &opts,
+            TempDir::new().unwrap().into_path(),
+            "cluster".to_owned(),
+        )
+        .await?
+        .unwrap();
+
+        let mut snapshot_ids = Vec::new();
+        for lsn in [10, 20, 30, 40] {
+            let snapshot_source = TempDir::new()?;
+            let source_dir = snapshot_source.path().to_path_buf();
+
+            let data = b"snapshot-data";
+            let mut data_file = tokio::fs::File::create(source_dir.join("data.sst")).await?;
+            data_file.write_all(data).await?;
+            data_file.shutdown().await?;
+
+            let mut snapshot = mock_snapshot_metadata(
+                "/data.sst".to_owned(),
+                source_dir.to_string_lossy().to_string(),
+                data.len(),
+            );
+            snapshot.min_applied_lsn = Lsn::new(lsn);
+            repository.put(&snapshot, source_dir).await?;
+            snapshot_ids.push(snapshot.snapshot_id);
+        }
+
+        // the snapshot at LSN 20 is retained since the log has not been trimmed past it yet
+        let deleted = repository.prune(PartitionId::MIN, Lsn::new(15)).await?;
+        assert_eq!(vec![snapshot_ids[0]], deleted);
+
+        let deleted = repository.prune(PartitionId::MIN, Lsn::new(40)).await?;
+        assert_eq!(vec![snapshot_ids[1]], deleted);
+
+        let snapshots = repository.list(PartitionId::MIN).await?;
+        assert_eq!(
+            vec![(snapshot_ids[3], true), (snapshot_ids[2], false)],
+            snapshots
+                .iter()
+                .map(|snapshot| (snapshot.metadata.snapshot_id, snapshot.is_latest))
+                .collect::<Vec<_>>()
+        );
+
+        // the latest snapshot can not be deleted
+        assert!(
+            repository
+                .delete(PartitionId::MIN, snapshot_ids[3])
+                .await
+                .is_err()
+        );
+        repository.delete(PartitionId::MIN, snapshot_ids[2]).await?;
+        assert_eq!(1, repository.list(PartitionId::MIN).await?.len());
+
+        Ok(())
+    }
+
     fn mock_snapshot_metadata(
         file_name: String,
         directory: String,
@@ -122,7 +122,9 @@ where
             headers,
             execution_time: self.execution_time,
             completion_retention_duration: Some(completion_retention_duration),
+            journal_retention_duration: None,
             idempotency_key,
+            dead_letter_queue: false,
             submit_notification_sink: None,
         };
 
@@ -49,10 +49,8 @@ where
                 "Set state"
             );
 
-            ctx.storage
-                .put_user_state(&service_id, self.entry.key, self.entry.value)
-                .await
-                .map_err(Error::Storage)?;
+            ctx.put_user_state(&service_id, self.entry.key, self.entry.value)
+                .await?;
         } else {
             warn!(
                 "Trying to process entry {} for a target that has no state",
@@ -0,0 +1,455 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::debug_if_leader;
+use crate::partition::state_machine::{CommandHandler, Error, StateMachineApplyContext};
+use restate_storage_api::dead_letter_table::DeadLetterTable;
+use restate_storage_api::fsm_table::FsmTable;
+use restate_storage_api::idempotency_table::IdempotencyTable;
+use restate_storage_api::invocation_status_table::{InvocationStatus, InvocationStatusTable};
+use restate_storage_api::journal_table::JournalTable;
+use restate_storage_api::journal_table_v2;
+use restate_storage_api::outbox_table::{OutboxMessage, OutboxTable};
+use restate_storage_api::promise_table::PromiseTable;
+use restate_storage_api::service_status_table::VirtualObjectStatusTable;
+use restate_storage_api::state_table::StateTable;
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::{
+    InvocationTargetType, ServiceInvocation, ServiceInvocationSpanContext, SpanRelation,
+    WorkflowHandlerType,
+};
+use tracing::trace;
+
+pub struct OnRedriveDeadLetterCommand {
+    pub invocation_id: InvocationId,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnRedriveDeadLetterCommand
+where
+    S: DeadLetterTable
+        + OutboxTable
+        + FsmTable
+        + InvocationStatusTable
+        + IdempotencyTable
+        + VirtualObjectStatusTable
+        + StateTable
+        + PromiseTable
+        + JournalTable
+        + journal_table_v2::JournalTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let Some(dead_letter) = ctx.storage.get_dead_letter(&self.invocation_id).await? else {
+            trace!(
+                "Received redrive command for invocation '{}' which is not in the dead letter queue. Ignoring it.",
+                self.invocation_id
+            );
+            return Ok(());
+        };
+
+        let is_idempotent = dead_letter.idempotency_key.is_some()
+            || dead_letter.invocation_target.invocation_target_ty()
+                == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow);
+        let new_invocation_id = if is_idempotent {
+            // Idempotent requests and workflow runs keep the id derived from their key, so that
+            // attaching by key finds the redriven invocation. The retained failure is purged,
+            // together with its idempotency entry, otherwise the redriven invocation would be
+            // deduplicated to it.
+            if let InvocationStatus::Completed(_) =
+                ctx.get_invocation_status(&self.invocation_id).await?
+            {
+                ctx.on_purge_invocation(self.invocation_id).await?;
+            }
+            InvocationId::generate(
+                &dead_letter.invocation_target,
+                dead_letter.idempotency_key.as_deref(),
+            )
+        } else {
+            // The new id is derived from the dead-lettered one, so redriving it is deterministic.
+            InvocationId::generate(
+                &dead_letter.invocation_target,
+                Some(&self.invocation_id.to_string()),
+            )
+        };
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.invocation.id = %self.invocation_id,
+            "Effect: Redrive dead letter as invocation {}",
+            new_invocation_id
+        );
+
+        let mut service_invocation = ServiceInvocation::initialize(
+            new_invocation_id,
+            dead_letter.invocation_target,
+            dead_letter.source,
+        );
+        service_invocation.argument = dead_letter.argument;
+        service_invocation.headers = dead_letter.headers;
+        service_invocation.idempotency_key = dead_letter.idempotency_key;
+        service_invocation.completion_retention_duration =
+            Some(dead_letter.completion_retention_duration);
+        service_invocation.journal_retention_duration =
+            Some(dead_letter.journal_retention_duration);
+        service_invocation.span_context =
+            ServiceInvocationSpanContext::start(&new_invocation_id, SpanRelation::None);
+        // If it fails again, the request should land back in the dead letter queue
+        service_invocation.dead_letter_queue = true;
+
+        ctx.handle_outgoing_message(OutboxMessage::ServiceInvocation(service_invocation))
+            .await?;
+
+        ctx.storage
+            .delete_dead_letter(&self.invocation_id)
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+pub struct OnDiscardDeadLetterCommand {
+    pub invocation_id: InvocationId,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnDiscardDeadLetterCommand
+where
+    S: DeadLetterTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.invocation.id = %self.invocation_id,
+            "Effect: Discard dead letter"
+        );
+
+        ctx.storage
+            .delete_dead_letter(&self.invocation_id)
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::partition::state_machine::Action;
+    use crate::partition::state_machine::tests::{TestEnv, fixtures};
+    use crate::partition::types::{InvokerEffect, InvokerEffectKind};
+    use bytes::Bytes;
+    use googletest::prelude::{assert_that, contains, eq, none, pat, some};
+    use restate_storage_api::dead_letter_table::{DeadLetter, ReadOnlyDeadLetterTable};
+    use restate_storage_api::invocation_status_table::{
+        InvocationStatus, ReadOnlyInvocationStatusTable,
+    };
+    use restate_storage_api::outbox_table::OutboxMessage;
+    use restate_types::errors::{CANCELED_INVOCATION_ERROR, InvocationError, codes};
+    use restate_types::identifiers::{InvocationId, PartitionProcessorRpcRequestId};
+    use restate_types::invocation::{
+        DiscardDeadLetterRequest, InvocationTarget, InvocationTermination, OnMaxAttempts,
+        RedriveDeadLetterRequest, ServiceInvocation, Source,
+    };
+    use restate_wal_protocol::Command;
+    use std::time::Duration;
+
+    async fn mock_invocation_with_dead_letter_queue(
+        test_env: &mut TestEnv,
+        idempotency_key: Option<&str>,
+    ) -> InvocationId {
+        let invocation_target = InvocationTarget::mock_service();
+        let invocation_id = InvocationId::generate(&invocation_target, idempotency_key);
+
+        let mut service_invocation = ServiceInvocation::initialize(
+            invocation_id,
+            invocation_target,
+            Source::Ingress(PartitionProcessorRpcRequestId::new()),
+        );
+        service_invocation.argument = "my-argument".into();
+        service_invocation.idempotency_key = idempotency_key.map(Into::into);
+        service_invocation.completion_retention_duration = Some(Duration::from_secs(60));
+        service_invocation.dead_letter_queue = true;
+        let _ = test_env.apply(Command::Invoke(service_invocation)).await;
+        fixtures::mock_pinned_deployment_v5(test_env, invocation_id).await;
+
+        invocation_id
+    }
+
+    async fn mock_dead_lettered_invocation(
+        test_env: &mut TestEnv,
+        idempotency_key: Option<&str>,
+    ) -> InvocationId {
+        let invocation_id = mock_invocation_with_dead_letter_queue(test_env, idempotency_key).await;
+
+        let _ = test_env
+            .apply(Command::InvokerEffect(InvokerEffect {
+                invocation_id,
+                invocation_epoch: 0,
+                kind: InvokerEffectKind::Failed(InvocationError::internal("boom")),
+            }))
+            .await;
+
+        invocation_id
+    }
+
+    #[restate_core::test]
+    async fn failed_invocation_is_dead_lettered() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_dead_lettered_invocation(&mut test_env, Some("my-key")).await;
+
+        let dead_letter = test_env
+            .storage()
+            .get_dead_letter(&invocation_id)
+            .await
+            .unwrap();
+        assert_that!(
+            dead_letter,
+            some(pat!(DeadLetter {
+                invocation_target: eq(InvocationTarget::mock_service()),
+                argument: eq(Bytes::from_static(b"my-argument")),
+                failure: eq(InvocationError::internal("boom")),
+                idempotency_key: some(eq("my-key")),
+                completion_retention_duration: eq(Duration::from_secs(60)),
+            }))
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn killed_invocation_is_not_dead_lettered() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_invocation_with_dead_letter_queue(&mut test_env, None).await;
+
+        let _ = test_env
+            .apply(Command::TerminateInvocation(InvocationTermination::kill(
+                invocation_id,
+            )))
+            .await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_dead_letter(&invocation_id)
+                .await
+                .unwrap(),
+            none()
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn canceled_invocation_is_not_dead_lettered() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_invocation_with_dead_letter_queue(&mut test_env, None).await;
+
+        let _ = test_env
+            .apply(Command::TerminateInvocation(InvocationTermination::cancel(
+                invocation_id,
+            )))
+            .await;
+        let _ = test_env
+            .apply(Command::InvokerEffect(InvokerEffect {
+                invocation_id,
+                invocation_epoch: 0,
+                kind: InvokerEffectKind::Failed(CANCELED_INVOCATION_ERROR),
+            }))
+            .await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_dead_letter(&invocation_id)
+                .await
+                .unwrap(),
+            none()
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn failure_with_aborted_code_is_dead_lettered() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_invocation_with_dead_letter_queue(&mut test_env, None).await;
+
+        // User code failing with the same code as killed and canceled invocations
+        let failure = InvocationError::new(codes::ABORTED, "conflict");
+        let _ = test_env
+            .apply(Command::InvokerEffect(InvokerEffect {
+                invocation_id,
+                invocation_epoch: 0,
+                kind: InvokerEffectKind::Failed(failure.clone()),
+            }))
+            .await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_dead_letter(&invocation_id)
+                .await
+                .unwrap(),
+            some(pat!(DeadLetter {
+                failure: eq(failure),
+            }))
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn dead_letter_handler_takes_precedence() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_invocation_with_dead_letter_queue(&mut test_env, None).await;
+
+        let _ = test_env
+            .apply(Command::InvokerEffect(InvokerEffect {
+                invocation_id,
+                invocation_epoch: 0,
+                kind: InvokerEffectKind::RetriesExhausted {
+                    error: InvocationError::internal("boom"),
+                    on_max_attempts: OnMaxAttempts::DeadLetter {
+                        service: "DeadLetters".to_owned(),
+                        handler: "handle".to_owned(),
+                    },
+                },
+            }))
+            .await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_dead_letter(&invocation_id)
+                .await
+                .unwrap(),
+            none()
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn redrive_dead_letter() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_dead_lettered_invocation(&mut test_env, None).await;
+
+        let actions = test_env
+            .apply(Command::RedriveDeadLetter(RedriveDeadLetterRequest {
+                invocation_id,
+            }))
+            .await;
+        assert_that!(
+            actions,
+            contains(pat!(Action::NewOutboxMessage {
+                message: pat!(OutboxMessage::ServiceInvocation(pat!(ServiceInvocation {
+                    invocation_id: eq(InvocationId::generate(
+                        &InvocationTarget::mock_service(),
+                        Some(&invocation_id.to_string()),
+                    )),
+                    argument: eq(Bytes::from_static(b"my-argument")),
+                    idempotency_key: none(),
+                    completion_retention_duration: some(eq(Duration::from_secs(60))),
+                    dead_letter_queue: eq(true),
+                })))
+            }))
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_dead_letter(&invocation_id)
+                .await
+                .unwrap(),
+            none()
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn redrive_idempotent_dead_letter() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_dead_lettered_invocation(&mut test_env, Some("my-key")).await;
+
+        // The failure is retained, and the idempotency key is deduplicated to it
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Completed(_))
+        );
+
+        let actions = test_env
+            .apply(Command::RedriveDeadLetter(RedriveDeadLetterRequest {
+                invocation_id,
+            }))
+            .await;
+        let service_invocation = actions
+            .into_iter()
+            .find_map(|action| match action {
+                Action::NewOutboxMessage {
+                    message: OutboxMessage::ServiceInvocation(service_invocation),
+                    ..
+                } => Some(service_invocation),
+                _ => None,
+            })
+            .expect("the redriven invocation is sent");
+
+        // The redriven invocation keeps the id derived from the idempotency key
+        assert_eq!(service_invocation.invocation_id, invocation_id);
+        assert_eq!(
+            service_invocation.idempotency_key.as_deref(),
+            Some("my-key")
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            eq(InvocationStatus::Free)
+        );
+
+        // The redriven invocation runs, instead of being deduplicated to the failure
+        let _ = test_env.apply(Command::Invoke(service_invocation)).await;
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Invoked(_))
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn discard_dead_letter() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_dead_lettered_invocation(&mut test_env, None).await;
+
+        let _ = test_env
+            .apply(Command::DiscardDeadLetter(DiscardDeadLetterRequest {
+                invocation_id,
+            }))
+            .await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_dead_letter(&invocation_id)
+                .await
+                .unwrap(),
+            none()
+        );
+
+        test_env.shutdown().await;
+    }
+}
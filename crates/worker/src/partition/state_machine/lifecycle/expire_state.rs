@@ -0,0 +1,294 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::debug_if_leader;
+use crate::partition::state_machine::{CommandHandler, Error, StateMachineApplyContext};
+use futures::TryStreamExt;
+use restate_storage_api::fsm_table::FsmTable;
+use restate_storage_api::service_status_table::{
+    ReadOnlyVirtualObjectStatusTable, VirtualObjectStatus,
+};
+use restate_storage_api::state_table::{StateEntryMetadata, StateTable};
+use restate_types::state_mut::{ExpireStateRequest, StateTtlServices};
+use tracing::trace;
+
+pub struct OnUpdateStateTtlServicesCommand {
+    pub update: StateTtlServices,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnUpdateStateTtlServicesCommand
+where
+    S: StateTable + FsmTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let StateTtlServices { services, .. } = self.update;
+
+        // State written before the TTL was enabled has no metadata, backfill it as if it was
+        // written now. This overwrites the stale metadata left behind if the TTL was enabled before.
+        let added_services: Vec<_> = services
+            .iter()
+            .filter(|service| !ctx.state_ttl_services.contains(*service))
+            .collect();
+        if !added_services.is_empty() {
+            let state_keys: Vec<_> = ctx
+                .storage
+                .get_all_user_states_in_range(ctx.partition_key_range.clone())?
+                .try_filter(|(service_id, _, _)| {
+                    std::future::ready(
+                        added_services
+                            .iter()
+                            .any(|service| **service == *service_id.service_name),
+                    )
+                })
+                .map_ok(|(service_id, state_key, _)| (service_id, state_key))
+                .try_collect()
+                .await?;
+
+            debug_if_leader!(
+                ctx.is_leader,
+                "Effect: Backfill the metadata of {} state entries of the services {:?}",
+                state_keys.len(),
+                added_services
+            );
+
+            for (service_id, state_key) in state_keys {
+                ctx.storage
+                    .put_user_state_metadata(
+                        &service_id,
+                        state_key,
+                        StateEntryMetadata {
+                            version: ctx.lsn.as_u64(),
+                            modified_at: ctx.record_created_at,
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        ctx.storage.put_state_ttl_services(services.clone()).await?;
+        *ctx.state_ttl_services = services;
+        Ok(())
+    }
+}
+
+pub struct OnExpireStateCommand {
+    pub request: ExpireStateRequest,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnExpireStateCommand
+where
+    S: StateTable + ReadOnlyVirtualObjectStatusTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let ExpireStateRequest {
+            service_id,
+            state_key,
+            version,
+        } = self.request;
+
+        // Don't pull the state from under a running handler, the cleaner will try again later
+        if let VirtualObjectStatus::Locked(invocation_id) =
+            ctx.storage.get_virtual_object_status(&service_id).await?
+        {
+            trace!(
+                "Ignoring the expiry of state key {:?} of '{}' locked by invocation '{}'",
+                state_key, service_id, invocation_id
+            );
+            return Ok(());
+        }
+
+        let metadata = ctx
+            .storage
+            .get_user_state_metadata(&service_id, &state_key)
+            .await?;
+        if metadata.is_none_or(|metadata| metadata.version != version) {
+            trace!(
+                "Ignoring the expiry of state key {:?} of '{}', written again in the meantime",
+                state_key, service_id
+            );
+            return Ok(());
+        }
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.state.key = ?state_key,
+            "Effect: Expire state of '{}'",
+            service_id
+        );
+
+        ctx.storage
+            .delete_user_state(&service_id, &state_key)
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::partition::state_machine::tests::{TestEnv, fixtures};
+    use bytes::Bytes;
+    use googletest::prelude::{assert_that, eq, none, some};
+    use restate_storage_api::state_table::ReadOnlyStateTable;
+    use restate_types::identifiers::{PartitionKey, ServiceId};
+    use restate_types::state_mut::{ExpireStateRequest, ExternalStateMutation, StateTtlServices};
+    use restate_wal_protocol::Command;
+    use std::collections::HashMap;
+
+    async fn enable_state_ttl(test_env: &mut TestEnv, service_id: &ServiceId) {
+        let _ = test_env
+            .apply(Command::UpdateStateTtlServices(StateTtlServices {
+                partition_key_range: PartitionKey::MIN..=PartitionKey::MAX,
+                services: [service_id.service_name.to_string()].into(),
+            }))
+            .await;
+    }
+
+    /// Writes the state entry and returns the LSN of the command which wrote it.
+    async fn mock_state(test_env: &mut TestEnv, service_id: &ServiceId) -> u64 {
+        let _ = test_env
+            .apply(Command::PatchState(ExternalStateMutation {
+                service_id: service_id.clone(),
+                version: None,
+                state: HashMap::from([(Bytes::from_static(b"key"), Bytes::from_static(b"value"))]),
+            }))
+            .await;
+        test_env.lsn.as_u64()
+    }
+
+    fn expire(service_id: &ServiceId, version: u64) -> Command {
+        Command::ExpireState(ExpireStateRequest {
+            service_id: service_id.clone(),
+            state_key: Bytes::from_static(b"key"),
+            version,
+        })
+    }
+
+    #[restate_core::test]
+    async fn expire_state() {
+        let mut test_env = TestEnv::create().await;
+        let service_id = ServiceId::mock_random();
+        enable_state_ttl(&mut test_env, &service_id).await;
+        let version = mock_state(&mut test_env, &service_id).await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state_metadata(&service_id, b"key")
+                .await
+                .unwrap()
+                .map(|metadata| metadata.version),
+            some(eq(version))
+        );
+
+        let _ = test_env.apply(expire(&service_id, version)).await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state(&service_id, b"key")
+                .await
+                .unwrap(),
+            none()
+        );
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn state_written_again_is_not_expired() {
+        let mut test_env = TestEnv::create().await;
+        let service_id = ServiceId::mock_random();
+        enable_state_ttl(&mut test_env, &service_id).await;
+        let version = mock_state(&mut test_env, &service_id).await;
+        mock_state(&mut test_env, &service_id).await;
+
+        let _ = test_env.apply(expire(&service_id, version)).await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state(&service_id, b"key")
+                .await
+                .unwrap(),
+            some(eq(Bytes::from_static(b"value")))
+        );
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn locked_state_is_not_expired() {
+        let mut test_env = TestEnv::create().await;
+        let service_id = ServiceId::mock_random();
+        enable_state_ttl(&mut test_env, &service_id).await;
+        let version = mock_state(&mut test_env, &service_id).await;
+        fixtures::mock_start_invocation_with_service_id(&mut test_env, service_id.clone()).await;
+
+        let _ = test_env.apply(expire(&service_id, version)).await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state(&service_id, b"key")
+                .await
+                .unwrap(),
+            some(eq(Bytes::from_static(b"value")))
+        );
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn state_written_before_enabling_ttl_is_expired() {
+        let mut test_env = TestEnv::create().await;
+        let service_id = ServiceId::mock_random();
+        mock_state(&mut test_env, &service_id).await;
+        enable_state_ttl(&mut test_env, &service_id).await;
+        let version = test_env.lsn.as_u64();
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state_metadata(&service_id, b"key")
+                .await
+                .unwrap()
+                .map(|metadata| metadata.version),
+            some(eq(version))
+        );
+
+        let _ = test_env.apply(expire(&service_id, version)).await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state(&service_id, b"key")
+                .await
+                .unwrap(),
+            none()
+        );
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn state_without_ttl_has_no_metadata() {
+        let mut test_env = TestEnv::create().await;
+        let service_id = ServiceId::mock_random();
+        mock_state(&mut test_env, &service_id).await;
+
+        assert_that!(
+            test_env
+                .storage()
+                .get_user_state_metadata(&service_id, b"key")
+                .await
+                .unwrap(),
+            none()
+        );
+        test_env.shutdown().await;
+    }
+}
@@ -9,21 +9,35 @@
 // by the Apache License, Version 2.0.
 
 mod cancel;
+mod dead_letter;
+mod expire_state;
 mod migrate_journal_table;
 mod notify_get_invocation_output_response;
 mod notify_invocation_response;
 mod notify_signal;
 mod notify_sleep_completion;
+mod pause;
 mod pinned_deployment;
+mod restart_as_new;
 mod resume;
+mod retries_exhausted;
+mod schedule;
 mod suspend;
 
 pub(super) use cancel::OnCancelCommand;
+pub(super) use dead_letter::{OnDiscardDeadLetterCommand, OnRedriveDeadLetterCommand};
+pub(super) use expire_state::{OnExpireStateCommand, OnUpdateStateTtlServicesCommand};
 pub(super) use migrate_journal_table::VerifyOrMigrateJournalTableToV2Command;
 pub(super) use notify_get_invocation_output_response::OnNotifyGetInvocationOutputResponse;
 pub(super) use notify_invocation_response::OnNotifyInvocationResponse;
 pub(super) use notify_signal::OnNotifySignalCommand;
 pub(super) use notify_sleep_completion::OnNotifySleepCompletionCommand;
+pub(super) use pause::{OnPauseCommand, OnResumePausedCommand};
 pub(super) use pinned_deployment::OnPinnedDeploymentCommand;
+pub(super) use restart_as_new::OnRestartAsNewInvocationCommand;
 pub(super) use resume::ResumeInvocationCommand;
+pub(super) use retries_exhausted::OnRetriesExhaustedCommand;
+pub(super) use schedule::{
+    OnDeleteScheduleCommand, OnFireScheduleCommand, OnUpsertScheduleCommand,
+};
 pub(super) use suspend::OnSuspendCommand;
@@ -0,0 +1,198 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::debug_if_leader;
+use crate::partition::state_machine::{CommandHandler, Error, StateMachineApplyContext};
+use restate_storage_api::invocation_status_table::{InvocationStatus, InvocationStatusTable};
+use restate_types::identifiers::InvocationId;
+use tracing::trace;
+
+pub struct OnPauseCommand {
+    pub invocation_id: InvocationId,
+    pub invocation_status: InvocationStatus,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>> for OnPauseCommand
+where
+    S: InvocationStatusTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let mut metadata = match self.invocation_status {
+            InvocationStatus::Invoked(metadata) => {
+                // Stop the current attempt, if any.
+                ctx.send_abort_invocation_to_invoker(
+                    self.invocation_id,
+                    metadata.current_invocation_epoch,
+                );
+                metadata
+            }
+            InvocationStatus::Suspended { metadata, .. } => {
+                // Once resumed, the invocation replays the journal and suspends again if needed,
+                // so there's no need to retain the notifications it's waiting for.
+                metadata
+            }
+            InvocationStatus::Paused(_) => {
+                trace!(
+                    "Received pause command for invocation '{}' which is already paused.",
+                    self.invocation_id
+                );
+                return Ok(());
+            }
+            _ => {
+                trace!(
+                    "Received pause command for invocation '{}' which is neither invoked nor suspended. Ignoring it.",
+                    self.invocation_id
+                );
+                return Ok(());
+            }
+        };
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.journal.length = metadata.journal_metadata.length,
+            "Effect: Pause invocation"
+        );
+
+        metadata.timestamps.update();
+        ctx.storage
+            .put_invocation_status(&self.invocation_id, &InvocationStatus::Paused(metadata))
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+pub struct OnResumePausedCommand {
+    pub invocation_id: InvocationId,
+    pub invocation_status: InvocationStatus,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnResumePausedCommand
+where
+    S: InvocationStatusTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let InvocationStatus::Paused(metadata) = self.invocation_status else {
+            trace!(
+                "Received resume command for invocation '{}' which is not paused. Ignoring it.",
+                self.invocation_id
+            );
+            return Ok(());
+        };
+
+        ctx.do_resume_service(self.invocation_id, metadata).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::partition::state_machine::Action;
+    use crate::partition::state_machine::tests::{TestEnv, fixtures, matchers};
+    use googletest::prelude::{all, assert_that, contains, eq, not, pat};
+    use restate_storage_api::invocation_status_table::{
+        InvocationStatus, ReadOnlyInvocationStatusTable,
+    };
+    use restate_types::invocation::{
+        InvocationTermination, PauseInvocationRequest, ResumeInvocationRequest,
+    };
+    use restate_wal_protocol::Command;
+
+    #[restate_core::test]
+    async fn pause_then_resume() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+        fixtures::mock_pinned_deployment_v5(&mut test_env, invocation_id).await;
+
+        let actions = test_env
+            .apply(Command::PauseInvocation(PauseInvocationRequest {
+                invocation_id,
+            }))
+            .await;
+        assert_that!(
+            actions,
+            contains(pat!(Action::AbortInvocation {
+                invocation_id: eq(invocation_id),
+                invocation_epoch: eq(0)
+            }))
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Paused { .. })
+        );
+
+        // Pausing twice is a no-op
+        let actions = test_env
+            .apply(Command::PauseInvocation(PauseInvocationRequest {
+                invocation_id,
+            }))
+            .await;
+        assert_that!(actions, not(contains(pat!(Action::AbortInvocation { .. }))));
+
+        let actions = test_env
+            .apply(Command::ResumeInvocation(ResumeInvocationRequest {
+                invocation_id,
+            }))
+            .await;
+        assert_that!(
+            actions,
+            contains(matchers::actions::invoke_for_id(invocation_id))
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Invoked { .. })
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn cancel_paused_invocation_resumes_it() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+        fixtures::mock_pinned_deployment_v5(&mut test_env, invocation_id).await;
+
+        test_env
+            .apply(Command::PauseInvocation(PauseInvocationRequest {
+                invocation_id,
+            }))
+            .await;
+
+        let actions = test_env
+            .apply(Command::TerminateInvocation(InvocationTermination::cancel(
+                invocation_id,
+            )))
+            .await;
+        assert_that!(
+            actions,
+            all!(
+                contains(matchers::actions::invoke_for_id(invocation_id)),
+                contains(pat!(Action::ForwardNotification { .. }))
+            )
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Invoked { .. })
+        );
+
+        test_env.shutdown().await;
+    }
+}
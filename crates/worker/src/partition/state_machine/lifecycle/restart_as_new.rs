@@ -0,0 +1,416 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::debug_if_leader;
+use crate::partition::state_machine::{CommandHandler, Error, StateMachineApplyContext};
+use futures::TryStreamExt;
+use restate_invoker_api::InvokeInputJournal;
+use restate_service_protocol_v4::entry_codec::ServiceProtocolV4Codec;
+use restate_storage_api::invocation_status_table::{
+    InFlightInvocationMetadata, InvocationStatus, InvocationStatusTable, JournalMetadata,
+    ReadOnlyInvocationStatusTable, StatusTimestamps,
+};
+use restate_storage_api::journal_table_v2;
+use restate_storage_api::service_status_table::{VirtualObjectStatus, VirtualObjectStatusTable};
+use restate_types::identifiers::{EntryIndex, InvocationId, WithPartitionKey};
+use restate_types::invocation::{
+    InvocationTargetType, ServiceInvocationSpanContext, Source, VirtualObjectHandlerType,
+    WorkflowHandlerType,
+};
+use restate_types::journal_v2::command::{Command, CommandMetadata};
+use restate_types::journal_v2::raw::RawEntry;
+use restate_types::journal_v2::{CompletionId, EntryMetadata, EntryType, NotificationId};
+use restate_types::time::MillisSinceEpoch;
+use std::collections::HashSet;
+use tracing::{trace, warn};
+
+pub struct OnRestartAsNewInvocationCommand {
+    pub invocation_id: InvocationId,
+    pub new_invocation_id: InvocationId,
+    pub copy_prefix_up_to_index_included: EntryIndex,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnRestartAsNewInvocationCommand
+where
+    S: InvocationStatusTable + journal_table_v2::JournalTable + VirtualObjectStatusTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let InvocationStatus::Completed(completed) =
+            ctx.get_invocation_status(&self.invocation_id).await?
+        else {
+            trace!(
+                "Received restart as new command for invocation '{}' which is not completed. Ignoring it.",
+                self.invocation_id
+            );
+            return Ok(());
+        };
+
+        if !completed.has_retained_journal() {
+            warn!(
+                "Cannot restart invocation '{}' as new, because its journal was not retained.",
+                self.invocation_id
+            );
+            return Ok(());
+        }
+        if self.copy_prefix_up_to_index_included >= completed.journal_metadata.length {
+            warn!(
+                "Cannot restart invocation '{}' as new from entry {}, because the journal has only {} entries.",
+                self.invocation_id,
+                self.copy_prefix_up_to_index_included,
+                completed.journal_metadata.length
+            );
+            return Ok(());
+        }
+        if self.new_invocation_id.partition_key() != self.invocation_id.partition_key() {
+            warn!(
+                "Cannot restart invocation '{}' as '{}', because the new invocation id belongs to a different partition key.",
+                self.invocation_id, self.new_invocation_id
+            );
+            return Ok(());
+        }
+        if !matches!(
+            ctx.storage
+                .get_invocation_status(&self.new_invocation_id)
+                .await?,
+            InvocationStatus::Free
+        ) {
+            warn!(
+                "Cannot restart invocation '{}' as '{}', because the new invocation id is already in use.",
+                self.invocation_id, self.new_invocation_id
+            );
+            return Ok(());
+        }
+        if completed.invocation_target.invocation_target_ty()
+            == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow)
+        {
+            warn!(
+                "Cannot restart invocation '{}' as new, because workflow runs cannot be restarted.",
+                self.invocation_id
+            );
+            return Ok(());
+        }
+
+        // Read the prefix of the journal to copy
+        let prefix_length = self.copy_prefix_up_to_index_included + 1;
+        let entries: Vec<(EntryIndex, RawEntry)> =
+            journal_table_v2::ReadOnlyJournalTable::get_journal(
+                ctx.storage,
+                self.invocation_id,
+                prefix_length,
+            )?
+            .try_collect()
+            .await?;
+
+        // Every copied command must find its completions within the prefix,
+        // otherwise the new invocation would wait on notifications that will never arrive.
+        let mut entries_to_copy = Vec::with_capacity(entries.len());
+        let mut awaited_completion_ids: HashSet<CompletionId> = HashSet::new();
+        let mut commands = 0;
+        for (_, entry) in entries {
+            let mut related_completion_ids = vec![];
+            match entry.ty() {
+                EntryType::Command(_) => {
+                    let cmd = entry.decode::<ServiceProtocolV4Codec, Command>()?;
+                    related_completion_ids = cmd.related_completion_ids();
+                    awaited_completion_ids.extend(related_completion_ids.iter().copied());
+                    commands += 1;
+                }
+                EntryType::Notification(_) => {
+                    if let Some(NotificationId::CompletionId(completion_id)) = entry
+                        .inner
+                        .try_as_notification_ref()
+                        .map(|notification| notification.id())
+                    {
+                        awaited_completion_ids.remove(&completion_id);
+                    }
+                }
+                EntryType::Event => {}
+            }
+            entries_to_copy.push((entry, related_completion_ids));
+        }
+        if !awaited_completion_ids.is_empty() {
+            warn!(
+                "Cannot restart invocation '{}' as new from entry {}, because the commands in the journal prefix are waiting for the completions {:?} which are not part of the prefix.",
+                self.invocation_id, self.copy_prefix_up_to_index_included, awaited_completion_ids
+            );
+            return Ok(());
+        }
+
+        if completed.invocation_target.invocation_target_ty()
+            == InvocationTargetType::VirtualObject(VirtualObjectHandlerType::Exclusive)
+        {
+            let keyed_service_id = completed.invocation_target.as_keyed_service_id().expect(
+                "When the handler type is Exclusive, the invocation target must have a key",
+            );
+            if let VirtualObjectStatus::Locked(locked_by) = ctx
+                .storage
+                .get_virtual_object_status(&keyed_service_id)
+                .await?
+            {
+                warn!(
+                    "Cannot restart invocation '{}' as new, because the virtual object '{}' is currently locked by invocation '{}'.",
+                    self.invocation_id, keyed_service_id, locked_by
+                );
+                return Ok(());
+            }
+
+            debug_if_leader!(
+                ctx.is_leader,
+                restate.service.id = %keyed_service_id,
+                "Locking service"
+            );
+            ctx.storage
+                .put_virtual_object_status(
+                    &keyed_service_id,
+                    &VirtualObjectStatus::Locked(self.new_invocation_id),
+                )
+                .await
+                .map_err(Error::Storage)?;
+        }
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.journal.length = prefix_length,
+            "Effect: Restart invocation {} as new",
+            self.invocation_id
+        );
+
+        // Copy the journal prefix
+        for (index, (entry, related_completion_ids)) in entries_to_copy.iter().enumerate() {
+            journal_table_v2::JournalTable::put_journal_entry(
+                ctx.storage,
+                self.new_invocation_id,
+                index as EntryIndex,
+                entry,
+                related_completion_ids,
+            )
+            .await?;
+        }
+
+        let span_context = ServiceInvocationSpanContext::start(
+            &self.new_invocation_id,
+            completed.span_context.as_linked(),
+        );
+        let now = MillisSinceEpoch::now();
+        let timestamps = StatusTimestamps::new(now, now, None, None, Some(now), None);
+
+        // The deployment is not pinned, so the new invocation runs on the latest deployment.
+        let in_flight_invocation_metadata = InFlightInvocationMetadata {
+            invocation_target: completed.invocation_target,
+            journal_metadata: JournalMetadata::new(prefix_length, commands, span_context.clone()),
+            pinned_deployment: None,
+            response_sinks: Default::default(),
+            timestamps,
+            source: Source::Internal,
+            completion_retention_duration: completed.completion_retention_duration,
+            journal_retention_duration: completed.journal_retention_duration,
+            idempotency_key: None,
+            hotfix_apply_cancellation_after_deployment_is_pinned: false,
+            current_invocation_epoch: 0,
+            completion_range_epoch_map: Default::default(),
+            retries_exhausted_action: None,
+            dead_letter_queue: false,
+        };
+
+        ctx.invoke(
+            self.new_invocation_id,
+            in_flight_invocation_metadata,
+            InvokeInputJournal::CachedJournal(
+                restate_invoker_api::JournalMetadata::new(
+                    prefix_length,
+                    span_context,
+                    None,
+                    // This is safe to do as only the leader will execute the invoker command
+                    MillisSinceEpoch::now(),
+                ),
+                entries_to_copy
+                    .into_iter()
+                    .map(|(entry, _)| {
+                        restate_invoker_api::invocation_reader::JournalEntry::JournalV2(entry)
+                    })
+                    .collect(),
+            ),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::partition::state_machine::Action;
+    use crate::partition::state_machine::tests::{TestEnv, fixtures, matchers};
+    use crate::partition::types::{InvokerEffect, InvokerEffectKind};
+    use bytes::Bytes;
+    use googletest::prelude::{assert_that, contains, eq, none, not, pat, some};
+    use restate_storage_api::invocation_status_table::{
+        InFlightInvocationMetadata, InvocationStatus, ReadOnlyInvocationStatusTable,
+    };
+    use restate_storage_api::journal_table_v2::ReadOnlyJournalTable;
+    use restate_types::identifiers::{
+        InvocationId, InvocationUuid, PartitionProcessorRpcRequestId, WithPartitionKey,
+    };
+    use restate_types::invocation::{
+        InvocationTarget, RestartAsNewInvocationRequest, ServiceInvocation, Source,
+    };
+    use restate_types::journal_v2::command::{OutputCommand, OutputResult, RunCommand};
+    use restate_types::journal_v2::notification::{RunCompletion, RunResult};
+    use restate_wal_protocol::Command;
+    use std::time::Duration;
+
+    async fn mock_completed_invocation_with_retained_journal(
+        test_env: &mut TestEnv,
+    ) -> InvocationId {
+        let invocation_target = InvocationTarget::mock_service();
+        let invocation_id = InvocationId::mock_generate(&invocation_target);
+
+        test_env
+            .apply(Command::Invoke(ServiceInvocation {
+                invocation_id,
+                invocation_target,
+                argument: Default::default(),
+                source: Source::Ingress(PartitionProcessorRpcRequestId::new()),
+                response_sink: None,
+                span_context: Default::default(),
+                headers: vec![],
+                execution_time: None,
+                completion_retention_duration: None,
+                journal_retention_duration: Some(Duration::from_secs(60 * 60)),
+                idempotency_key: None,
+                dead_letter_queue: false,
+                submit_notification_sink: None,
+            }))
+            .await;
+        fixtures::mock_pinned_deployment_v5(test_env, invocation_id).await;
+        test_env
+            .apply_multiple([
+                fixtures::invoker_entry_effect(
+                    invocation_id,
+                    RunCommand {
+                        completion_id: 1,
+                        name: Default::default(),
+                    },
+                ),
+                fixtures::invoker_entry_effect(
+                    invocation_id,
+                    RunCompletion {
+                        completion_id: 1,
+                        result: RunResult::Success(Bytes::from_static(b"123")),
+                    },
+                ),
+                fixtures::invoker_entry_effect(
+                    invocation_id,
+                    OutputCommand {
+                        result: OutputResult::Success(Bytes::default()),
+                        name: Default::default(),
+                    },
+                ),
+                Command::InvokerEffect(InvokerEffect {
+                    invocation_id,
+                    invocation_epoch: 0,
+                    kind: InvokerEffectKind::End,
+                }),
+            ])
+            .await;
+
+        invocation_id
+    }
+
+    #[restate_core::test]
+    async fn restart_as_new_copies_journal_prefix() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_completed_invocation_with_retained_journal(&mut test_env).await;
+        let new_invocation_id =
+            InvocationId::from_parts(invocation_id.partition_key(), InvocationUuid::mock_random());
+
+        let actions = test_env
+            .apply(Command::RestartAsNewInvocation(
+                RestartAsNewInvocationRequest {
+                    invocation_id,
+                    new_invocation_id,
+                    copy_prefix_up_to_index_included: 2,
+                },
+            ))
+            .await;
+        assert_that!(
+            actions,
+            contains(matchers::actions::invoke_for_id(new_invocation_id))
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&new_invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Invoked(pat!(
+                InFlightInvocationMetadata {
+                    journal_retention_duration: eq(Duration::from_secs(60 * 60)),
+                }
+            )))
+        );
+
+        // Input, run command and run completion are copied, the output is not.
+        for index in 0..3 {
+            assert_that!(
+                test_env
+                    .storage()
+                    .get_journal_entry(new_invocation_id, index)
+                    .await
+                    .unwrap(),
+                some(eq(test_env
+                    .storage()
+                    .get_journal_entry(invocation_id, index)
+                    .await
+                    .unwrap()
+                    .unwrap()))
+            );
+        }
+        assert_that!(
+            test_env
+                .storage()
+                .get_journal_entry(new_invocation_id, 3)
+                .await
+                .unwrap(),
+            none()
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn restart_as_new_rejects_prefix_with_missing_completions() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = mock_completed_invocation_with_retained_journal(&mut test_env).await;
+        let new_invocation_id =
+            InvocationId::from_parts(invocation_id.partition_key(), InvocationUuid::mock_random());
+
+        // The run command at index 1 is completed by the notification at index 2
+        let actions = test_env
+            .apply(Command::RestartAsNewInvocation(
+                RestartAsNewInvocationRequest {
+                    invocation_id,
+                    new_invocation_id,
+                    copy_prefix_up_to_index_included: 1,
+                },
+            ))
+            .await;
+        assert_that!(actions, not(contains(pat!(Action::Invoke { .. }))));
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&new_invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Free)
+        );
+
+        test_env.shutdown().await;
+    }
+}
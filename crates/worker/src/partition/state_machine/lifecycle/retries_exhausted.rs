@@ -0,0 +1,291 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::debug_if_leader;
+use crate::partition::state_machine::entries::OnJournalEntryCommand;
+use crate::partition::state_machine::{CommandHandler, Error, StateMachineApplyContext};
+use restate_storage_api::dead_letter_table::DeadLetterTable;
+use restate_storage_api::fsm_table::FsmTable;
+use restate_storage_api::inbox_table::InboxTable;
+use restate_storage_api::invocation_status_table::{InvocationStatus, InvocationStatusTable};
+use restate_storage_api::outbox_table::{OutboxMessage, OutboxTable};
+use restate_storage_api::promise_table::PromiseTable;
+use restate_storage_api::service_status_table::VirtualObjectStatusTable;
+use restate_storage_api::state_table::StateTable;
+use restate_storage_api::timer_table::TimerTable;
+use restate_storage_api::{journal_table as journal_table_v1, journal_table_v2};
+use restate_types::errors::InvocationError;
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::{
+    InvocationTarget, OnMaxAttempts, OnMaxAttemptsAction, ResponseResult, ServiceInvocation,
+    ServiceInvocationSpanContext, Source,
+};
+use restate_types::journal_v2::{Entry, Event, EventType};
+use restate_types::service_protocol::ServiceProtocolVersion;
+use std::collections::HashMap;
+use tracing::trace;
+
+/// Applies the [`OnMaxAttempts`] policy once the invoker exhausted the retries of an invocation.
+pub struct OnRetriesExhaustedCommand {
+    pub invocation_id: InvocationId,
+    pub invocation_status: InvocationStatus,
+    pub error: InvocationError,
+    pub on_max_attempts: OnMaxAttempts,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnRetriesExhaustedCommand
+where
+    S: InvocationStatusTable
+        + journal_table_v1::JournalTable
+        + journal_table_v2::JournalTable
+        + StateTable
+        + PromiseTable
+        + OutboxTable
+        + FsmTable
+        + TimerTable
+        + InboxTable
+        + VirtualObjectStatusTable
+        + DeadLetterTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let action = OnMaxAttemptsAction::from(&self.on_max_attempts);
+
+        let mut invocation_status = self.invocation_status;
+        let Some(metadata) = invocation_status.get_invocation_metadata() else {
+            trace!(
+                "Received retries exhausted for invocation '{}' which is not in-flight. Ignoring it.",
+                self.invocation_id
+            );
+            return Ok(());
+        };
+
+        // Record the event in the journal, so it shows up when introspecting the invocation.
+        // Only the journal table v2 can store events.
+        if metadata
+            .pinned_deployment
+            .as_ref()
+            .is_some_and(|pd| pd.service_protocol_version >= ServiceProtocolVersion::V4)
+        {
+            OnJournalEntryCommand::from_entry(
+                self.invocation_id,
+                invocation_status,
+                Entry::Event(retries_exhausted_event(action, &self.error)),
+            )
+            .apply(ctx)
+            .await?;
+            invocation_status = ctx.get_invocation_status(&self.invocation_id).await?;
+        }
+
+        let mut metadata = invocation_status
+            .into_invocation_metadata()
+            .expect("Must be present if status is in-flight");
+        metadata.retries_exhausted_action = Some(action);
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.invocation.id = %self.invocation_id,
+            "Effect: Retries exhausted, applying action {}",
+            action
+        );
+
+        match self.on_max_attempts {
+            OnMaxAttempts::Kill => {
+                ctx.end_invocation(
+                    self.invocation_id,
+                    metadata,
+                    Some(ResponseResult::Failure(self.error)),
+                    vec![],
+                )
+                .await
+            }
+            OnMaxAttempts::Pause => {
+                metadata.timestamps.update();
+                ctx.storage
+                    .put_invocation_status(&self.invocation_id, &InvocationStatus::Paused(metadata))
+                    .await
+                    .map_err(Error::Storage)
+            }
+            OnMaxAttempts::DeadLetter { service, handler } => {
+                let (headers, argument) = ctx.read_invocation_input(&self.invocation_id).await?;
+
+                let dead_letter_target = InvocationTarget::service(service, handler);
+                // Deterministic id, derived from the dead-lettered invocation id.
+                let dead_letter_invocation_id = InvocationId::generate(
+                    &dead_letter_target,
+                    Some(&self.invocation_id.to_string()),
+                );
+                let mut service_invocation = ServiceInvocation::initialize(
+                    dead_letter_invocation_id,
+                    dead_letter_target,
+                    Source::Service(self.invocation_id, metadata.invocation_target.clone()),
+                );
+                service_invocation.argument = argument;
+                service_invocation.headers = headers;
+                service_invocation.span_context = ServiceInvocationSpanContext::start(
+                    &dead_letter_invocation_id,
+                    metadata.journal_metadata.span_context.as_linked(),
+                );
+                ctx.handle_outgoing_message(OutboxMessage::ServiceInvocation(service_invocation))
+                    .await?;
+
+                // The dead letter handler takes precedence over the dead letter queue,
+                // the request is not stored in both.
+                metadata.dead_letter_queue = false;
+                ctx.end_invocation(
+                    self.invocation_id,
+                    metadata,
+                    Some(ResponseResult::Failure(self.error)),
+                    vec![],
+                )
+                .await
+            }
+        }
+    }
+}
+
+fn retries_exhausted_event(action: OnMaxAttemptsAction, error: &InvocationError) -> Event {
+    Event {
+        ty: EventType::Lifecycle,
+        metadata: HashMap::from([
+            ("type".to_owned(), "retries_exhausted".into()),
+            ("action".to_owned(), action.to_string().into()),
+            ("error_code".to_owned(), error.code().to_string().into()),
+            ("error_message".to_owned(), error.message().into()),
+        ]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::partition::state_machine::Action;
+    use crate::partition::state_machine::tests::{TestEnv, fixtures};
+    use crate::partition::types::{InvokerEffect, InvokerEffectKind};
+    use googletest::prelude::{assert_that, contains, eq, none, pat, some};
+    use restate_storage_api::invocation_status_table::{
+        InFlightInvocationMetadata, InvocationStatus, ReadOnlyInvocationStatusTable,
+    };
+    use restate_storage_api::outbox_table::OutboxMessage;
+    use restate_types::errors::InvocationError;
+    use restate_types::invocation::{
+        InvocationTarget, OnMaxAttempts, OnMaxAttemptsAction, ResumeInvocationRequest,
+        ServiceInvocation,
+    };
+    use restate_wal_protocol::Command;
+
+    fn retries_exhausted(
+        invocation_id: restate_types::identifiers::InvocationId,
+        on_max_attempts: OnMaxAttempts,
+    ) -> Command {
+        Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::RetriesExhausted {
+                error: InvocationError::internal("boom"),
+                on_max_attempts,
+            },
+        })
+    }
+
+    #[restate_core::test]
+    async fn pause_on_retries_exhausted() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+        fixtures::mock_pinned_deployment_v5(&mut test_env, invocation_id).await;
+
+        test_env
+            .apply(retries_exhausted(invocation_id, OnMaxAttempts::Pause))
+            .await;
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Paused(pat!(InFlightInvocationMetadata {
+                retries_exhausted_action: some(eq(OnMaxAttemptsAction::Pause))
+            })))
+        );
+
+        // Once resumed, the invocation is invoked again
+        test_env
+            .apply(Command::ResumeInvocation(ResumeInvocationRequest {
+                invocation_id,
+            }))
+            .await;
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Invoked { .. })
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn kill_on_retries_exhausted() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+        fixtures::mock_pinned_deployment_v5(&mut test_env, invocation_id).await;
+
+        test_env
+            .apply(retries_exhausted(invocation_id, OnMaxAttempts::Kill))
+            .await;
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Free)
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn dead_letter_on_retries_exhausted() {
+        let mut test_env = TestEnv::create().await;
+        let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+        fixtures::mock_pinned_deployment_v5(&mut test_env, invocation_id).await;
+
+        let actions = test_env
+            .apply(retries_exhausted(
+                invocation_id,
+                OnMaxAttempts::DeadLetter {
+                    service: "DeadLetters".to_owned(),
+                    handler: "handle".to_owned(),
+                },
+            ))
+            .await;
+        assert_that!(
+            actions,
+            contains(pat!(Action::NewOutboxMessage {
+                message: pat!(OutboxMessage::ServiceInvocation(pat!(ServiceInvocation {
+                    invocation_target: eq(InvocationTarget::service("DeadLetters", "handle")),
+                    response_sink: none()
+                })))
+            }))
+        );
+        assert_that!(
+            test_env
+                .storage()
+                .get_invocation_status(&invocation_id)
+                .await
+                .unwrap(),
+            pat!(InvocationStatus::Free)
+        );
+
+        test_env.shutdown().await;
+    }
+}
@@ -0,0 +1,380 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::debug_if_leader;
+use crate::partition::state_machine::{CommandHandler, Error, StateMachineApplyContext};
+use restate_storage_api::fsm_table::FsmTable;
+use restate_storage_api::outbox_table::{OutboxMessage, OutboxTable};
+use restate_storage_api::schedule_table::{ScheduleStatus, ScheduleTable};
+use restate_storage_api::timer_table::{TimerKey, TimerTable};
+use restate_types::identifiers::{ScheduleId, TimestampAwareId};
+use restate_types::invocation::{
+    ServiceInvocation, ServiceInvocationSpanContext, Source, SpanRelation,
+};
+use restate_types::schedule::Schedule;
+use restate_types::time::MillisSinceEpoch;
+use restate_wal_protocol::timer::TimerKeyValue;
+use tracing::trace;
+
+pub struct OnUpsertScheduleCommand {
+    pub schedule: Schedule,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnUpsertScheduleCommand
+where
+    S: ScheduleTable + TimerTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let schedule_id = self.schedule.id;
+        let previous = ctx.storage.get_schedule(&schedule_id).await?;
+
+        let mut last_fired_at = None;
+        if let Some(previous) = previous {
+            if let Some(next_fire_at) = previous.next_fire_at {
+                ctx.do_delete_timer(TimerKey::fire_schedule(next_fire_at.as_u64(), schedule_id))
+                    .await?;
+            }
+            last_fired_at = previous.last_fired_at;
+        }
+
+        // Occurrences before the creation of the schedule, or before its last run, are not missed runs.
+        let next_fire_at = self
+            .schedule
+            .cron
+            .next_after(last_fired_at.unwrap_or_else(|| schedule_id.timestamp()));
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.schedule.id = %schedule_id,
+            "Effect: Upsert schedule '{}' targeting {}, next run at {:?}",
+            self.schedule.cron,
+            self.schedule.invocation_target,
+            next_fire_at
+        );
+
+        if let Some(next_fire_at) = next_fire_at {
+            ctx.register_timer(
+                TimerKeyValue::fire_schedule(next_fire_at, schedule_id),
+                Default::default(),
+            )
+            .await?;
+        }
+
+        ctx.storage
+            .put_schedule(&ScheduleStatus {
+                schedule: self.schedule,
+                next_fire_at,
+                last_fired_at,
+            })
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+pub struct OnDeleteScheduleCommand {
+    pub schedule_id: ScheduleId,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnDeleteScheduleCommand
+where
+    S: ScheduleTable + TimerTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let Some(schedule_status) = ctx.storage.get_schedule(&self.schedule_id).await? else {
+            trace!(
+                "Received delete command for unknown schedule '{}'. Ignoring it.",
+                self.schedule_id
+            );
+            return Ok(());
+        };
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.schedule.id = %self.schedule_id,
+            "Effect: Delete schedule"
+        );
+
+        if let Some(next_fire_at) = schedule_status.next_fire_at {
+            ctx.do_delete_timer(TimerKey::fire_schedule(
+                next_fire_at.as_u64(),
+                self.schedule_id,
+            ))
+            .await?;
+        }
+
+        ctx.storage
+            .delete_schedule(&self.schedule_id)
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+pub struct OnFireScheduleCommand {
+    pub schedule_id: ScheduleId,
+    pub due: MillisSinceEpoch,
+    pub fired_at: MillisSinceEpoch,
+}
+
+impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
+    for OnFireScheduleCommand
+where
+    S: ScheduleTable + TimerTable + OutboxTable + FsmTable,
+{
+    async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
+        let Some(mut schedule_status) = ctx.storage.get_schedule(&self.schedule_id).await? else {
+            trace!(
+                "Fired timer of unknown schedule '{}'. The schedule might have been deleted previously.",
+                self.schedule_id
+            );
+            return Ok(());
+        };
+        if schedule_status.next_fire_at != Some(self.due) {
+            trace!(
+                "Fired stale timer of schedule '{}', due at {}. Ignoring it.",
+                self.schedule_id, self.due
+            );
+            return Ok(());
+        }
+
+        ctx.do_delete_timer(TimerKey::fire_schedule(self.due.as_u64(), self.schedule_id))
+            .await?;
+
+        let now = self.fired_at.max(self.due);
+        let schedule = &schedule_status.schedule;
+        for fire_time in schedule.runs_to_fire(self.due, now) {
+            let invocation_id = schedule.run_invocation_id(fire_time);
+
+            debug_if_leader!(
+                ctx.is_leader,
+                restate.schedule.id = %self.schedule_id,
+                "Effect: Run schedule occurrence at {} as invocation {}",
+                fire_time,
+                invocation_id
+            );
+
+            let mut service_invocation = ServiceInvocation::initialize(
+                invocation_id,
+                schedule.invocation_target.clone(),
+                Source::Internal,
+            );
+            service_invocation.argument = schedule.argument.clone();
+            service_invocation.span_context =
+                ServiceInvocationSpanContext::start(&invocation_id, SpanRelation::None);
+
+            ctx.handle_outgoing_message(OutboxMessage::ServiceInvocation(service_invocation))
+                .await?;
+        }
+
+        schedule_status.last_fired_at = Some(now);
+        schedule_status.next_fire_at = schedule_status.schedule.cron.next_after(now);
+        if let Some(next_fire_at) = schedule_status.next_fire_at {
+            ctx.register_timer(
+                TimerKeyValue::fire_schedule(next_fire_at, self.schedule_id),
+                Default::default(),
+            )
+            .await?;
+        }
+
+        ctx.storage
+            .put_schedule(&schedule_status)
+            .await
+            .map_err(Error::Storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::partition::state_machine::Action;
+    use crate::partition::state_machine::tests::TestEnv;
+    use bytes::Bytes;
+    use googletest::prelude::{
+        all, assert_that, contains, eq, none, not, pat, some, unordered_elements_are,
+    };
+    use restate_storage_api::outbox_table::OutboxMessage;
+    use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus};
+    use restate_storage_api::timer_table::{Timer, TimerKey};
+    use restate_types::identifiers::ScheduleId;
+    use restate_types::invocation::{InvocationTarget, ServiceInvocation};
+    use restate_types::schedule::{CatchUpPolicy, FireScheduleRequest, Schedule};
+    use restate_types::time::MillisSinceEpoch;
+    use restate_wal_protocol::Command;
+
+    const MINUTE: u64 = 60 * 1000;
+
+    fn mock_schedule(catch_up: CatchUpPolicy) -> Schedule {
+        Schedule {
+            id: ScheduleId::from_parts(MINUTE, 1),
+            cron: "*/5 * * * *".parse().unwrap(),
+            invocation_target: InvocationTarget::mock_service(),
+            argument: Bytes::from_static(b"my-argument"),
+            catch_up,
+        }
+    }
+
+    fn fire_at(schedule: &Schedule, due: u64, fired_at: u64) -> Command {
+        Command::FireSchedule(FireScheduleRequest {
+            schedule_id: schedule.id,
+            due: MillisSinceEpoch::new(due),
+            fired_at: MillisSinceEpoch::new(fired_at),
+        })
+    }
+
+    #[restate_core::test]
+    async fn upsert_schedule_registers_timer() {
+        let mut test_env = TestEnv::create().await;
+        let schedule = mock_schedule(CatchUpPolicy::RunOnce);
+
+        let actions = test_env
+            .apply(Command::UpsertSchedule(schedule.clone()))
+            .await;
+        assert_that!(
+            actions,
+            contains(pat!(Action::RegisterTimer {
+                timer_value: eq(restate_wal_protocol::timer::TimerKeyValue::fire_schedule(
+                    MillisSinceEpoch::new(5 * MINUTE),
+                    schedule.id
+                ))
+            }))
+        );
+        assert_that!(
+            test_env.storage().get_schedule(&schedule.id).await.unwrap(),
+            some(pat!(ScheduleStatus {
+                next_fire_at: some(eq(MillisSinceEpoch::new(5 * MINUTE))),
+                last_fired_at: none(),
+            }))
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn fire_schedule_on_time() {
+        let mut test_env = TestEnv::create().await;
+        let schedule = mock_schedule(CatchUpPolicy::RunOnce);
+        let _ = test_env
+            .apply(Command::UpsertSchedule(schedule.clone()))
+            .await;
+
+        let actions = test_env
+            .apply(fire_at(&schedule, 5 * MINUTE, 5 * MINUTE + 10))
+            .await;
+        assert_that!(
+            actions,
+            all!(
+                contains(pat!(Action::DeleteTimer {
+                    timer_key: eq(TimerKey::fire_schedule(5 * MINUTE, schedule.id))
+                })),
+                contains(pat!(Action::NewOutboxMessage {
+                    message: pat!(OutboxMessage::ServiceInvocation(pat!(ServiceInvocation {
+                        invocation_id: eq(
+                            schedule.run_invocation_id(MillisSinceEpoch::new(5 * MINUTE))
+                        ),
+                        argument: eq(Bytes::from_static(b"my-argument")),
+                    })))
+                })),
+                contains(pat!(Action::RegisterTimer {
+                    timer_value: eq(restate_wal_protocol::timer::TimerKeyValue::fire_schedule(
+                        MillisSinceEpoch::new(10 * MINUTE),
+                        schedule.id
+                    ))
+                }))
+            )
+        );
+
+        // Firing the same timer again has no effect
+        let actions = test_env
+            .apply(fire_at(&schedule, 5 * MINUTE, 5 * MINUTE + 20))
+            .await;
+        assert_that!(
+            actions,
+            not(contains(pat!(Action::NewOutboxMessage { .. })))
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn fire_schedule_catches_up_missed_runs() {
+        let mut test_env = TestEnv::create().await;
+        let schedule = mock_schedule(CatchUpPolicy::All);
+        let _ = test_env
+            .apply(Command::UpsertSchedule(schedule.clone()))
+            .await;
+
+        let actions = test_env
+            .apply(fire_at(&schedule, 5 * MINUTE, 12 * MINUTE))
+            .await;
+        let invocation_ids: Vec<_> = actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::NewOutboxMessage {
+                    message: OutboxMessage::ServiceInvocation(service_invocation),
+                    ..
+                } => Some(service_invocation.invocation_id),
+                _ => None,
+            })
+            .collect();
+        assert_that!(
+            invocation_ids,
+            unordered_elements_are![
+                eq(schedule.run_invocation_id(MillisSinceEpoch::new(5 * MINUTE))),
+                eq(schedule.run_invocation_id(MillisSinceEpoch::new(10 * MINUTE)))
+            ]
+        );
+        assert_that!(
+            test_env.storage().get_schedule(&schedule.id).await.unwrap(),
+            some(pat!(ScheduleStatus {
+                next_fire_at: some(eq(MillisSinceEpoch::new(15 * MINUTE))),
+                last_fired_at: some(eq(MillisSinceEpoch::new(12 * MINUTE))),
+            }))
+        );
+
+        test_env.shutdown().await;
+    }
+
+    #[restate_core::test]
+    async fn delete_schedule() {
+        let mut test_env = TestEnv::create().await;
+        let schedule = mock_schedule(CatchUpPolicy::RunOnce);
+        let _ = test_env
+            .apply(Command::UpsertSchedule(schedule.clone()))
+            .await;
+
+        let actions = test_env.apply(Command::DeleteSchedule(schedule.id)).await;
+        assert_that!(
+            actions,
+            contains(pat!(Action::DeleteTimer {
+                timer_key: eq(TimerKey::fire_schedule(5 * MINUTE, schedule.id))
+            }))
+        );
+        assert_that!(
+            test_env.storage().get_schedule(&schedule.id).await.unwrap(),
+            none()
+        );
+
+        // The timer of a deleted schedule doesn't run anything
+        let actions = test_env
+            .apply(Command::Timer(
+                restate_wal_protocol::timer::TimerKeyValue::new(
+                    TimerKey::fire_schedule(5 * MINUTE, schedule.id),
+                    Timer::FireSchedule(schedule.id),
+                ),
+            ))
+            .await;
+        assert_that!(
+            actions,
+            not(contains(pat!(Action::NewOutboxMessage { .. })))
+        );
+
+        test_env.shutdown().await;
+    }
+}
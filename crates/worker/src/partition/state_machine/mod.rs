@@ -28,6 +28,7 @@ use restate_invoker_api::InvokeInputJournal;
 use restate_service_protocol::codec::ProtobufRawEntryCodec;
 use restate_service_protocol_v4::entry_codec::ServiceProtocolV4Codec;
 use restate_storage_api::Result as StorageResult;
+use restate_storage_api::dead_letter_table::{DeadLetter, DeadLetterTable};
 use restate_storage_api::fsm_table::FsmTable;
 use restate_storage_api::idempotency_table::{IdempotencyTable, ReadOnlyIdempotencyTable};
 use restate_storage_api::inbox_table::{InboxEntry, InboxTable};
@@ -39,19 +40,20 @@ use restate_storage_api::invocation_status_table::{InvocationStatus, ScheduledIn
 use restate_storage_api::journal_table::ReadOnlyJournalTable;
 use restate_storage_api::journal_table::{JournalEntry, JournalTable};
 use restate_storage_api::journal_table_v2;
-use restate_storage_api::outbox_table::{OutboxMessage, OutboxTable};
+use restate_storage_api::outbox_table::{KafkaRecord, OutboxMessage, OutboxTable};
 use restate_storage_api::promise_table::{Promise, PromiseState, PromiseTable};
+use restate_storage_api::schedule_table::ScheduleTable;
 use restate_storage_api::service_status_table::{
     ReadOnlyVirtualObjectStatusTable, VirtualObjectStatus, VirtualObjectStatusTable,
 };
-use restate_storage_api::state_table::StateTable;
+use restate_storage_api::state_table::{StateEntryMetadata, StateTable};
 use restate_storage_api::timer_table::TimerKey;
 use restate_storage_api::timer_table::{Timer, TimerTable};
 use restate_tracing_instrumentation as instrumentation;
 use restate_types::errors::{
     ALREADY_COMPLETED_INVOCATION_ERROR, ATTACH_NOT_SUPPORTED_INVOCATION_ERROR,
-    CANCELED_INVOCATION_ERROR, GenericError, InvocationErrorCode, KILLED_INVOCATION_ERROR,
-    NOT_FOUND_INVOCATION_ERROR, NOT_READY_INVOCATION_ERROR,
+    CANCELED_INVOCATION_ERROR, GenericError, InvocationError, InvocationErrorCode,
+    KILLED_INVOCATION_ERROR, NOT_FOUND_INVOCATION_ERROR, NOT_READY_INVOCATION_ERROR,
     WORKFLOW_ALREADY_INVOKED_INVOCATION_ERROR,
 };
 use restate_types::identifiers::{
@@ -60,11 +62,11 @@ use restate_types::identifiers::{
 };
 use restate_types::identifiers::{IdempotencyId, WithPartitionKey};
 use restate_types::invocation::{
-    AttachInvocationRequest, InvocationEpoch, InvocationQuery, InvocationResponse,
+    AttachInvocationRequest, Header, InvocationEpoch, InvocationQuery, InvocationResponse,
     InvocationTarget, InvocationTargetType, InvocationTermination, JournalCompletionTarget,
-    NotifySignalRequest, ResponseResult, ServiceInvocation, ServiceInvocationResponseSink,
-    ServiceInvocationSpanContext, Source, SubmitNotificationSink, TerminationFlavor,
-    VirtualObjectHandlerType, WorkflowHandlerType,
+    KafkaSink, NotifySignalRequest, ResponseResult, ServiceInvocation,
+    ServiceInvocationResponseSink, ServiceInvocationSpanContext, Source, SubmitNotificationSink,
+    TerminationFlavor, VirtualObjectHandlerType, WorkflowHandlerType,
 };
 use restate_types::invocation::{InvocationInput, SpanRelation};
 use restate_types::journal::Completion;
@@ -77,11 +79,12 @@ use restate_types::journal::enriched::{
 use restate_types::journal::raw::{EntryHeader, RawEntryCodec, RawEntryCodecError};
 use restate_types::journal::*;
 use restate_types::journal_v2;
-use restate_types::journal_v2::command::{OutputCommand, OutputResult};
+use restate_types::journal_v2::command::{InputCommand, OutputCommand, OutputResult};
 use restate_types::journal_v2::raw::RawNotification;
 use restate_types::journal_v2::{
     CommandType, CompletionId, EntryMetadata, NotificationId, Signal, SignalResult,
 };
+use restate_types::logs::Lsn;
 use restate_types::message::MessageIndex;
 use restate_types::net::partition_processor::IngressResponseResult;
 use restate_types::service_protocol::ServiceProtocolVersion;
@@ -92,7 +95,7 @@ use restate_wal_protocol::Command;
 use restate_wal_protocol::timer::TimerKeyDisplay;
 use restate_wal_protocol::timer::TimerKeyValue;
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::ops::RangeInclusive;
@@ -113,6 +116,8 @@ pub struct StateMachine {
     outbox_seq_number: MessageIndex,
     partition_key_range: RangeInclusive<PartitionKey>,
     invoker_apply_latency: Histogram,
+    /// Services whose state entries expire after a TTL.
+    state_ttl_services: BTreeSet<String>,
 
     /// Enabled experimental features.
     experimental_features: EnumSet<ExperimentalFeature>,
@@ -204,9 +209,15 @@ impl StateMachine {
             outbox_head_seq_number,
             partition_key_range,
             invoker_apply_latency,
+            state_ttl_services: BTreeSet::default(),
             experimental_features,
         }
     }
+
+    pub fn with_state_ttl_services(mut self, state_ttl_services: BTreeSet<String>) -> Self {
+        self.state_ttl_services = state_ttl_services;
+        self
+    }
 }
 
 pub(crate) struct StateMachineApplyContext<'a, S> {
@@ -217,6 +228,11 @@ pub(crate) struct StateMachineApplyContext<'a, S> {
     outbox_head_seq_number: &'a mut Option<MessageIndex>,
     partition_key_range: RangeInclusive<PartitionKey>,
     invoker_apply_latency: &'a Histogram,
+    state_ttl_services: &'a mut BTreeSet<String>,
+    /// LSN of the applied command.
+    lsn: Lsn,
+    /// Creation time of the record of the applied command.
+    record_created_at: MillisSinceEpoch,
     #[allow(dead_code)]
     experimental_features: &'a EnumSet<ExperimentalFeature>,
     is_leader: bool,
@@ -230,6 +246,8 @@ impl StateMachine {
     pub async fn apply<TransactionType: restate_storage_api::Transaction + Send>(
         &mut self,
         command: Command,
+        lsn: Lsn,
+        record_created_at: MillisSinceEpoch,
         transaction: &mut TransactionType,
         action_collector: &mut ActionCollector,
         is_leader: bool,
@@ -247,6 +265,9 @@ impl StateMachine {
                 outbox_head_seq_number: &mut self.outbox_head_seq_number,
                 partition_key_range: self.partition_key_range.clone(),
                 invoker_apply_latency: &self.invoker_apply_latency,
+                state_ttl_services: &mut self.state_ttl_services,
+                lsn,
+                record_created_at,
                 experimental_features: &self.experimental_features,
                 is_leader,
             }
@@ -261,6 +282,33 @@ impl StateMachine {
 }
 
 impl<S> StateMachineApplyContext<'_, S> {
+    /// Writes the state entry, and its metadata if the service state expires after a TTL.
+    async fn put_user_state(
+        &mut self,
+        service_id: &ServiceId,
+        key: impl AsRef<[u8]> + Send,
+        value: impl AsRef<[u8]> + Send,
+    ) -> Result<(), Error>
+    where
+        S: StateTable,
+    {
+        let key = key.as_ref();
+        self.storage.put_user_state(service_id, key, value).await?;
+        if self.state_ttl_services.contains(&*service_id.service_name) {
+            self.storage
+                .put_user_state_metadata(
+                    service_id,
+                    key,
+                    StateEntryMetadata {
+                        version: self.lsn.as_u64(),
+                        modified_at: self.record_created_at,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn get_invocation_status(
         &mut self,
         invocation_id: &InvocationId,
@@ -341,6 +389,14 @@ impl<S> StateMachineApplyContext<'_, S> {
                     "Register cleanup invocation status timer"
                 )
             }
+            Timer::FireSchedule(_) => {
+                debug_if_leader!(
+                    self.is_leader,
+                    restate.timer.wake_up_time = %timer_value.wake_up_time(),
+                    restate.timer.key = %TimerKeyDisplay(timer_value.key()),
+                    "Register schedule timer"
+                )
+            }
         };
 
         self.storage
@@ -402,7 +458,9 @@ impl<S> StateMachineApplyContext<'_, S> {
             + VirtualObjectStatusTable
             + InboxTable
             + StateTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable
+            + ScheduleTable,
     {
         match command {
             Command::Invoke(service_invocation) => {
@@ -457,7 +515,80 @@ impl<S> StateMachineApplyContext<'_, S> {
                 self.on_purge_invocation(purge_invocation_request.invocation_id)
                     .await
             }
+            Command::PauseInvocation(pause_invocation_request) => {
+                lifecycle::OnPauseCommand {
+                    invocation_id: pause_invocation_request.invocation_id,
+                    invocation_status: self
+                        .get_invocation_status(&pause_invocation_request.invocation_id)
+                        .await?,
+                }
+                .apply(self)
+                .await
+            }
+            Command::ResumeInvocation(resume_invocation_request) => {
+                lifecycle::OnResumePausedCommand {
+                    invocation_id: resume_invocation_request.invocation_id,
+                    invocation_status: self
+                        .get_invocation_status(&resume_invocation_request.invocation_id)
+                        .await?,
+                }
+                .apply(self)
+                .await
+            }
+            Command::RestartAsNewInvocation(restart_as_new_invocation_request) => {
+                lifecycle::OnRestartAsNewInvocationCommand {
+                    invocation_id: restart_as_new_invocation_request.invocation_id,
+                    new_invocation_id: restart_as_new_invocation_request.new_invocation_id,
+                    copy_prefix_up_to_index_included: restart_as_new_invocation_request
+                        .copy_prefix_up_to_index_included,
+                }
+                .apply(self)
+                .await
+            }
+            Command::RedriveDeadLetter(redrive_dead_letter_request) => {
+                lifecycle::OnRedriveDeadLetterCommand {
+                    invocation_id: redrive_dead_letter_request.invocation_id,
+                }
+                .apply(self)
+                .await
+            }
+            Command::DiscardDeadLetter(discard_dead_letter_request) => {
+                lifecycle::OnDiscardDeadLetterCommand {
+                    invocation_id: discard_dead_letter_request.invocation_id,
+                }
+                .apply(self)
+                .await
+            }
+            Command::UpsertSchedule(schedule) => {
+                lifecycle::OnUpsertScheduleCommand { schedule }
+                    .apply(self)
+                    .await
+            }
+            Command::DeleteSchedule(schedule_id) => {
+                lifecycle::OnDeleteScheduleCommand { schedule_id }
+                    .apply(self)
+                    .await
+            }
+            Command::FireSchedule(fire_schedule_request) => {
+                lifecycle::OnFireScheduleCommand {
+                    schedule_id: fire_schedule_request.schedule_id,
+                    due: fire_schedule_request.due,
+                    fired_at: fire_schedule_request.fired_at,
+                }
+                .apply(self)
+                .await
+            }
             Command::PatchState(mutation) => self.handle_external_state_mutation(mutation).await,
+            Command::ExpireState(request) => {
+                lifecycle::OnExpireStateCommand { request }
+                    .apply(self)
+                    .await
+            }
+            Command::UpdateStateTtlServices(update) => {
+                lifecycle::OnUpdateStateTtlServicesCommand { update }
+                    .apply(self)
+                    .await
+            }
             Command::AnnounceLeader(_) => {
                 // no-op :-)
                 Ok(())
@@ -699,6 +830,7 @@ impl<S> StateMachineApplyContext<'_, S> {
         match previous_invocation_status {
             is @ InvocationStatus::Invoked { .. }
             | is @ InvocationStatus::Suspended { .. }
+            | is @ InvocationStatus::Paused(_)
             | is @ InvocationStatus::Inboxed { .. }
             | is @ InvocationStatus::Scheduled { .. } => {
                 if let Some(ref response_sink) = service_invocation.response_sink {
@@ -998,7 +1130,8 @@ impl<S> StateMachineApplyContext<'_, S> {
             + OutboxTable
             + journal_table_v2::JournalTable
             + TimerTable
-            + PromiseTable,
+            + PromiseTable
+            + DeadLetterTable,
     {
         match termination_flavor {
             TerminationFlavor::Kill => self.on_kill_invocation(invocation_id).await,
@@ -1017,7 +1150,8 @@ impl<S> StateMachineApplyContext<'_, S> {
             + OutboxTable
             + TimerTable
             + FsmTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable,
     {
         let status = self.get_invocation_status(&invocation_id).await?;
 
@@ -1026,7 +1160,7 @@ impl<S> StateMachineApplyContext<'_, S> {
                 self.kill_invoked_invocation(invocation_id, metadata)
                     .await?;
             }
-            InvocationStatus::Suspended { metadata, .. } => {
+            InvocationStatus::Suspended { metadata, .. } | InvocationStatus::Paused(metadata) => {
                 self.kill_suspended_invocation(invocation_id, metadata)
                     .await?;
             }
@@ -1077,6 +1211,34 @@ impl<S> StateMachineApplyContext<'_, S> {
     {
         let mut status = self.get_invocation_status(&invocation_id).await?;
 
+        if let InvocationStatus::Paused(_) = status {
+            // The invocation needs to run in order to execute the cancellation,
+            // so let's resume it first.
+            debug_if_leader!(
+                self.is_leader,
+                "Resuming paused invocation to process the cancellation"
+            );
+            lifecycle::ResumeInvocationCommand {
+                invocation_id,
+                invocation_status: &mut status,
+            }
+            .apply(self)
+            .await?;
+            self.storage
+                .put_invocation_status(&invocation_id, &status)
+                .await?;
+        }
+
+        // Canceled invocations are stopped on purpose, they're not dead-lettered once they end
+        if let Some(metadata) = status.get_invocation_metadata_mut() {
+            if metadata.dead_letter_queue {
+                metadata.dead_letter_queue = false;
+                self.storage
+                    .put_invocation_status(&invocation_id, &status)
+                    .await?;
+            }
+        }
+
         match status.get_invocation_metadata().and_then(|meta| {
             meta.pinned_deployment
                 .as_ref()
@@ -1172,6 +1334,9 @@ impl<S> StateMachineApplyContext<'_, S> {
                 )
                 .await?
             }
+            InvocationStatus::Paused(_) => {
+                unreachable!("Paused invocations are resumed before being cancelled")
+            }
             InvocationStatus::Completed(_) => {
                 debug!("Received cancel command for completed invocation '{invocation_id}'. To cleanup the invocation after it's been completed, use the purge invocation command.");
             }
@@ -1307,7 +1472,7 @@ impl<S> StateMachineApplyContext<'_, S> {
     async fn kill_invoked_invocation(
         &mut self,
         invocation_id: InvocationId,
-        metadata: InFlightInvocationMetadata,
+        mut metadata: InFlightInvocationMetadata,
     ) -> Result<(), Error>
     where
         S: InboxTable
@@ -1318,15 +1483,19 @@ impl<S> StateMachineApplyContext<'_, S> {
             + JournalTable
             + OutboxTable
             + FsmTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable,
     {
         self.kill_child_invocations(&invocation_id, metadata.journal_metadata.length, &metadata)
             .await?;
 
+        // Killed invocations are stopped on purpose, they're not dead-lettered
+        metadata.dead_letter_queue = false;
         self.end_invocation(
             invocation_id,
             metadata,
             Some(ResponseResult::Failure(KILLED_INVOCATION_ERROR)),
+            vec![],
         )
         .await?;
         self.do_send_abort_invocation_to_invoker(invocation_id, InvocationEpoch::MAX);
@@ -1336,7 +1505,7 @@ impl<S> StateMachineApplyContext<'_, S> {
     async fn kill_suspended_invocation(
         &mut self,
         invocation_id: InvocationId,
-        metadata: InFlightInvocationMetadata,
+        mut metadata: InFlightInvocationMetadata,
     ) -> Result<(), Error>
     where
         S: InboxTable
@@ -1347,15 +1516,19 @@ impl<S> StateMachineApplyContext<'_, S> {
             + JournalTable
             + OutboxTable
             + FsmTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable,
     {
         self.kill_child_invocations(&invocation_id, metadata.journal_metadata.length, &metadata)
             .await?;
 
+        // Killed invocations are stopped on purpose, they're not dead-lettered
+        metadata.dead_letter_queue = false;
         self.end_invocation(
             invocation_id,
             metadata,
             Some(ResponseResult::Failure(KILLED_INVOCATION_ERROR)),
+            vec![],
         )
         .await?;
         self.do_send_abort_invocation_to_invoker(invocation_id, InvocationEpoch::MAX);
@@ -1552,16 +1725,25 @@ impl<S> StateMachineApplyContext<'_, S> {
             + IdempotencyTable
             + VirtualObjectStatusTable
             + StateTable
-            + PromiseTable,
+            + PromiseTable
+            + JournalTable
+            + journal_table_v2::JournalTable,
     {
         match self.get_invocation_status(&invocation_id).await? {
             InvocationStatus::Completed(CompletedInvocation {
                 invocation_target,
                 idempotency_key,
+                journal_metadata,
                 ..
             }) => {
                 self.do_free_invocation(invocation_id).await?;
 
+                // Drop the retained journal, if any. Only journal table v2 supports retention.
+                if journal_metadata.length > 0 {
+                    self.do_drop_journal(invocation_id, journal_metadata.length, true)
+                        .await?;
+                }
+
                 // Also cleanup the associated idempotency key if any
                 if let Some(idempotency_key) = idempotency_key {
                     self.do_delete_idempotency_id(IdempotencyId::combine(
@@ -1614,8 +1796,10 @@ impl<S> StateMachineApplyContext<'_, S> {
             + TimerTable
             + PromiseTable
             + StateTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + ScheduleTable,
     {
+        let wake_up_time = timer_value.wake_up_time();
         let (key, value) = timer_value.into_inner();
         self.do_delete_timer(key).await?;
 
@@ -1657,6 +1841,17 @@ impl<S> StateMachineApplyContext<'_, S> {
                 self.on_purge_invocation(invocation_id).await
             }
             Timer::NeoInvoke(invocation_id) => self.on_neo_invoke_timer(invocation_id).await,
+            Timer::FireSchedule(schedule_id) => {
+                // The leader proposes FireSchedule for schedule timers, this is only reached
+                // if the timer was proposed as is. Consider it fired on time.
+                lifecycle::OnFireScheduleCommand {
+                    schedule_id,
+                    due: wake_up_time,
+                    fired_at: wake_up_time,
+                }
+                .apply(self)
+                .await
+            }
         }
     }
 
@@ -1722,7 +1917,8 @@ impl<S> StateMachineApplyContext<'_, S> {
             + TimerTable
             + InboxTable
             + VirtualObjectStatusTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable,
     {
         let start = Instant::now();
         let status = self
@@ -1753,7 +1949,8 @@ impl<S> StateMachineApplyContext<'_, S> {
             + TimerTable
             + InboxTable
             + VirtualObjectStatusTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable,
     {
         let is_status_invoked = matches!(invocation_status, InvocationStatus::Invoked(_));
 
@@ -1877,6 +2074,18 @@ impl<S> StateMachineApplyContext<'_, S> {
                         .into_invocation_metadata()
                         .expect("Must be present if status is invoked"),
                     None,
+                    vec![],
+                )
+                .await?;
+            }
+            InvokerEffectKind::EndAndPublish { sinks } => {
+                self.end_invocation(
+                    invocation_id,
+                    invocation_status
+                        .into_invocation_metadata()
+                        .expect("Must be present if status is invoked"),
+                    None,
+                    sinks,
                 )
                 .await?;
             }
@@ -1887,9 +2096,23 @@ impl<S> StateMachineApplyContext<'_, S> {
                         .into_invocation_metadata()
                         .expect("Must be present if status is invoked"),
                     Some(ResponseResult::Failure(e)),
+                    vec![],
                 )
                 .await?;
             }
+            InvokerEffectKind::RetriesExhausted {
+                error,
+                on_max_attempts,
+            } => {
+                lifecycle::OnRetriesExhaustedCommand {
+                    invocation_id,
+                    invocation_status,
+                    error,
+                    on_max_attempts,
+                }
+                .apply(self)
+                .await?;
+            }
         }
 
         Ok(())
@@ -1902,6 +2125,8 @@ impl<S> StateMachineApplyContext<'_, S> {
         invocation_metadata: InFlightInvocationMetadata,
         // If given, this will override any Output Entry available in the journal table
         response_result_override: Option<ResponseResult>,
+        // Kafka sinks where the output of the invocation is published
+        kafka_sinks: Vec<KafkaSink>,
     ) -> Result<(), Error>
     where
         S: InboxTable
@@ -1911,11 +2136,15 @@ impl<S> StateMachineApplyContext<'_, S> {
             + FsmTable
             + InvocationStatusTable
             + StateTable
-            + journal_table_v2::JournalTable,
+            + journal_table_v2::JournalTable
+            + DeadLetterTable,
     {
         let invocation_target = invocation_metadata.invocation_target.clone();
         let journal_length = invocation_metadata.journal_metadata.length;
-        let completion_retention_time = invocation_metadata.completion_retention_duration;
+        let completion_retention_time =
+            invocation_metadata.effective_completion_retention_duration();
+        let should_retain_journal = invocation_metadata.should_retain_journal();
+        let dead_letter_queue = invocation_metadata.dead_letter_queue;
 
         let should_remove_journal_table_v2 = invocation_metadata
             .pinned_deployment
@@ -1924,9 +2153,13 @@ impl<S> StateMachineApplyContext<'_, S> {
                 pinned_deployment.service_protocol_version >= ServiceProtocolVersion::V4
             });
 
-        // If there are any response sinks, or we need to store back the completed status,
-        //  we need to find the latest output entry
-        if !invocation_metadata.response_sinks.is_empty() || !completion_retention_time.is_zero() {
+        // If there are any response or Kafka sinks, or we need to store back the completed status,
+        //  or we might need to dead-letter the request, we need to find the latest output entry
+        if !invocation_metadata.response_sinks.is_empty()
+            || !kafka_sinks.is_empty()
+            || !completion_retention_time.is_zero()
+            || dead_letter_queue
+        {
             let response_result = if let Some(response_result) = response_result_override {
                 response_result
             } else if let Some(response_result) = self
@@ -1958,6 +2191,20 @@ impl<S> StateMachineApplyContext<'_, S> {
             )
             .await?;
 
+            // Publish the output to the Kafka sinks, the Kafka egress of the leader takes care of the delivery
+            for kafka_sink in kafka_sinks {
+                self.handle_outgoing_message(OutboxMessage::KafkaRecord(KafkaRecord {
+                    invocation_id,
+                    key: kafka_sink
+                        .render_key(&invocation_id, &invocation_metadata.invocation_target)
+                        .map(Bytes::from),
+                    cluster: kafka_sink.cluster,
+                    topic: kafka_sink.topic,
+                    result: response_result.clone(),
+                }))
+                .await?;
+            }
+
             // Notify invocation result
             self.notify_invocation_result(
                 invocation_id,
@@ -1971,6 +2218,16 @@ impl<S> StateMachineApplyContext<'_, S> {
                 },
             );
 
+            // Store the request in the dead letter queue, if needed.
+            // This must happen before dropping the journal, as we need to read the input entry.
+            // Killing and canceling an invocation clear its dead letter queue flag.
+            if let ResponseResult::Failure(failure) = &response_result {
+                if dead_letter_queue {
+                    self.do_store_dead_letter(invocation_id, &invocation_metadata, failure.clone())
+                        .await?;
+                }
+            }
+
             // Store the completed status, if needed
             if !completion_retention_time.is_zero() {
                 let completed_invocation = CompletedInvocation::from_in_flight_invocation_metadata(
@@ -1997,12 +2254,15 @@ impl<S> StateMachineApplyContext<'_, S> {
             self.do_free_invocation(invocation_id).await?;
         }
 
-        self.do_drop_journal(
-            invocation_id,
-            journal_length,
-            should_remove_journal_table_v2,
-        )
-        .await?;
+        // The retained journal is dropped together with the completed status, see on_purge_invocation
+        if !should_retain_journal {
+            self.do_drop_journal(
+                invocation_id,
+                journal_length,
+                should_remove_journal_table_v2,
+            )
+            .await?;
+        }
 
         // Consume inbox and move on
         self.consume_inbox(&invocation_target).await?;
@@ -2583,7 +2843,9 @@ impl<S> StateMachineApplyContext<'_, S> {
                         headers: request.headers,
                         execution_time: None,
                         completion_retention_duration: *completion_retention_time,
+                        journal_retention_duration: None,
                         idempotency_key: request.idempotency_key,
+                        dead_letter_queue: false,
                         submit_notification_sink: None,
                     };
 
@@ -2648,7 +2910,9 @@ impl<S> StateMachineApplyContext<'_, S> {
                     headers: request.headers,
                     execution_time: delay,
                     completion_retention_duration: *completion_retention_time,
+                    journal_retention_duration: None,
                     idempotency_key: request.idempotency_key,
+                    dead_letter_queue: false,
                     submit_notification_sink: None,
                 };
 
@@ -3032,6 +3296,10 @@ impl<S> StateMachineApplyContext<'_, S> {
             self.do_resume_service( invocation_id, metadata).await?;
                 }
             }
+            InvocationStatus::Paused(_) => {
+                // Store the completion, it will be sent to the deployment when the invocation is resumed
+                self.store_completion(invocation_id, completion).await?;
+            }
             _ => {
                 debug!(
                     restate.invocation.id = %invocation_id,
@@ -3129,6 +3397,46 @@ impl<S> StateMachineApplyContext<'_, S> {
         }
     }
 
+    /// Reads the input of the invocation, looking first in the journal table v2 and then in the old one.
+    async fn read_invocation_input(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> Result<(Vec<Header>, Bytes), Error>
+    where
+        S: ReadOnlyJournalTable + journal_table_v2::ReadOnlyJournalTable,
+    {
+        if let Some(entry) = journal_table_v2::ReadOnlyJournalTable::get_journal_entry(
+            self.storage,
+            *invocation_id,
+            0,
+        )
+        .await?
+        {
+            let InputCommand {
+                headers, payload, ..
+            } = entry.decode::<ServiceProtocolV4Codec, InputCommand>()?;
+            return Ok((headers, payload));
+        }
+
+        if let Some(JournalEntry::Entry(entry)) =
+            ReadOnlyJournalTable::get_journal_entry(self.storage, invocation_id, 0).await?
+        {
+            // The first entry must be an input entry!
+            let_assert!(
+                Entry::Input(InputEntry { headers, value }) =
+                    entry.deserialize_entry_ref::<ProtobufRawEntryCodec>()?
+            );
+            return Ok((headers, value));
+        }
+
+        // This should not happen, but we still go on with an empty input.
+        warn!(
+            "Cannot find the input entry of invocation '{}'. This indicates a bug.",
+            invocation_id
+        );
+        Ok((vec![], Bytes::new()))
+    }
+
     fn notify_invocation_result(
         &mut self,
         invocation_id: InvocationId,
@@ -3255,6 +3563,7 @@ impl<S> StateMachineApplyContext<'_, S> {
             }
             is @ InvocationStatus::Invoked(_)
             | is @ InvocationStatus::Suspended { .. }
+            | is @ InvocationStatus::Paused(_)
             | is @ InvocationStatus::Inboxed(_)
             | is @ InvocationStatus::Scheduled(_) => {
                 if attach_invocation_request.block_on_inflight {
@@ -3446,6 +3755,42 @@ impl<S> StateMachineApplyContext<'_, S> {
             .map_err(Error::Storage)
     }
 
+    async fn do_store_dead_letter(
+        &mut self,
+        invocation_id: InvocationId,
+        invocation_metadata: &InFlightInvocationMetadata,
+        failure: InvocationError,
+    ) -> Result<(), Error>
+    where
+        S: DeadLetterTable + ReadOnlyJournalTable + journal_table_v2::ReadOnlyJournalTable,
+    {
+        debug_if_leader!(
+            self.is_leader,
+            restate.invocation.id = %invocation_id,
+            "Effect: Store dead letter"
+        );
+
+        let (headers, argument) = self.read_invocation_input(&invocation_id).await?;
+        self.storage
+            .put_dead_letter(
+                &invocation_id,
+                &DeadLetter {
+                    invocation_target: invocation_metadata.invocation_target.clone(),
+                    source: invocation_metadata.source.clone(),
+                    headers,
+                    argument,
+                    failure,
+                    failed_at: self.record_created_at,
+                    idempotency_key: invocation_metadata.idempotency_key.clone(),
+                    completion_retention_duration: invocation_metadata
+                        .completion_retention_duration,
+                    journal_retention_duration: invocation_metadata.journal_retention_duration,
+                },
+            )
+            .await
+            .map_err(Error::Storage)
+    }
+
     async fn do_free_invocation(&mut self, invocation_id: InvocationId) -> Result<(), Error>
     where
         S: InvocationStatusTable,
@@ -3560,6 +3905,21 @@ impl<S> StateMachineApplyContext<'_, S> {
                     signal.id,
                 )
             }
+            OutboxMessage::KafkaRecord(KafkaRecord {
+                invocation_id,
+                cluster,
+                topic,
+                ..
+            }) => {
+                debug_if_leader!(
+                    self.is_leader,
+                    restate.invocation.id = %invocation_id,
+                    restate.outbox.seq = seq_number,
+                    "Effect: Publish response to Kafka topic '{}' of cluster '{}'",
+                    topic,
+                    cluster,
+                )
+            }
         };
 
         self.storage
@@ -3624,10 +3984,7 @@ impl<S> StateMachineApplyContext<'_, S> {
             "Effect: Set state"
         );
 
-        self.storage
-            .put_user_state(&service_id, key, value)
-            .await
-            .map_err(Error::Storage)
+        self.put_user_state(&service_id, key, value).await
     }
 
     #[tracing::instrument(
@@ -4053,7 +4410,7 @@ impl<S> StateMachineApplyContext<'_, S> {
 
         // overwrite existing key value pairs
         for (key, value) in state {
-            self.storage.put_user_state(&service_id, key, value).await?;
+            self.put_user_state(&service_id, key, value).await?;
         }
 
         Ok(())
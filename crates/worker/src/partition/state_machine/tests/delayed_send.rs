@@ -31,6 +31,7 @@ async fn send_with_delay() {
             invocation_id,
             invocation_target: invocation_target.clone(),
             response_sink: None,
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress { request_id }),
             // Doesn't matter the execution time here, just needs to be filled
             execution_time: Some(wake_up_time),
@@ -91,6 +92,7 @@ async fn send_with_delay_to_locked_virtual_object() {
             invocation_id,
             invocation_target: invocation_target.clone(),
             response_sink: None,
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress { request_id }),
             // Doesn't matter the execution time here, just needs to be filled
             execution_time: Some(wake_up_time),
@@ -177,6 +179,7 @@ async fn send_with_delay_and_idempotency_key() {
             invocation_id,
             invocation_target: invocation_target.clone(),
             idempotency_key: Some(idempotency_key.clone()),
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress {
                 request_id: request_id_1,
             }),
@@ -207,6 +210,7 @@ async fn send_with_delay_and_idempotency_key() {
             invocation_id,
             invocation_target: invocation_target.clone(),
             idempotency_key: Some(idempotency_key),
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress {
                 request_id: request_id_2,
             }),
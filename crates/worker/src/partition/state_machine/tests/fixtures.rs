@@ -136,7 +136,9 @@ pub async fn mock_start_invocation_with_invocation_target(
             headers: vec![],
             execution_time: None,
             completion_retention_duration: None,
+            journal_retention_duration: None,
             idempotency_key: None,
+            dead_letter_queue: false,
             submit_notification_sink: None,
         }))
         .await;
@@ -15,7 +15,9 @@ use restate_storage_api::idempotency_table::{
     IdempotencyMetadata, IdempotencyTable, ReadOnlyIdempotencyTable,
 };
 use restate_storage_api::inbox_table::{InboxEntry, ReadOnlyInboxTable, SequenceNumberInboxEntry};
-use restate_storage_api::invocation_status_table::{CompletedInvocation, StatusTimestamps};
+use restate_storage_api::invocation_status_table::{
+    CompletedInvocation, JournalMetadata, StatusTimestamps,
+};
 use restate_types::identifiers::{IdempotencyId, PartitionProcessorRpcRequestId};
 use restate_types::invocation::{
     AttachInvocationRequest, InvocationQuery, InvocationTarget, PurgeInvocationRequest,
@@ -237,6 +239,10 @@ async fn complete_already_completed_invocation() {
             timestamps: StatusTimestamps::now(),
             response_result: ResponseResult::Success(response_bytes.clone()),
             completion_retention_duration: Default::default(),
+            journal_retention_duration: Default::default(),
+            journal_metadata: JournalMetadata::initialize(ServiceInvocationSpanContext::default()),
+            pinned_deployment: None,
+            retries_exhausted_action: None,
         }),
     )
     .await
@@ -410,6 +416,7 @@ async fn attach_with_send_service_invocation(#[case] use_same_request_id: bool)
             invocation_target: invocation_target.clone(),
             idempotency_key: Some(idempotency_key.clone()),
             completion_retention_duration: Some(retention),
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress {
                 request_id: request_id_2,
             }),
@@ -515,6 +522,7 @@ async fn attach_inboxed_with_send_service_invocation() {
             invocation_target: invocation_target.clone(),
             idempotency_key: Some(idempotency_key.clone()),
             completion_retention_duration: Some(Duration::from_secs(60) * 60 * 24),
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress {
                 request_id: request_id_1,
             }),
@@ -558,6 +566,7 @@ async fn attach_inboxed_with_send_service_invocation() {
             invocation_target: invocation_target.clone(),
             idempotency_key: Some(idempotency_key.clone()),
             completion_retention_duration: Some(Duration::from_secs(60) * 60 * 24),
+            dead_letter_queue: false,
             submit_notification_sink: Some(SubmitNotificationSink::Ingress {
                 request_id: request_id_2,
             }),
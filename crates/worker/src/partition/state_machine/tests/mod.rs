@@ -41,7 +41,7 @@ use restate_storage_api::invocation_status_table::{
     ReadOnlyInvocationStatusTable,
 };
 use restate_storage_api::journal_table::{JournalEntry, ReadOnlyJournalTable};
-use restate_storage_api::outbox_table::OutboxTable;
+use restate_storage_api::outbox_table::{KafkaRecord, OutboxMessage, OutboxTable};
 use restate_storage_api::service_status_table::{
     ReadOnlyVirtualObjectStatusTable, VirtualObjectStatus, VirtualObjectStatusTable,
 };
@@ -54,7 +54,7 @@ use restate_types::identifiers::{
     ServiceId,
 };
 use restate_types::invocation::{
-    Header, InvocationResponse, InvocationTarget, InvocationTermination, ResponseResult,
+    Header, InvocationResponse, InvocationTarget, InvocationTermination, KafkaSink, ResponseResult,
     ServiceInvocation, ServiceInvocationResponseSink, Source, VirtualObjectHandlerType,
 };
 use restate_types::journal::enriched::EnrichedRawEntry;
@@ -63,7 +63,9 @@ use restate_types::journal::{
 };
 use restate_types::journal::{Entry, EntryType};
 use restate_types::live::Constant;
+use restate_types::logs::{Lsn, SequenceNumber};
 use restate_types::state_mut::ExternalStateMutation;
+use restate_types::time::MillisSinceEpoch;
 use std::collections::{HashMap, HashSet};
 use test_log::test;
 use tracing_subscriber::fmt::format::FmtSpan;
@@ -73,6 +75,8 @@ pub struct TestEnv {
     // TODO for the time being we use rocksdb storage because we have no mocks for storage interfaces.
     //  Perhaps we could make these tests faster by having those.
     pub storage: PartitionStore,
+    /// LSN of the last applied command.
+    pub lsn: Lsn,
 }
 
 impl TestEnv {
@@ -143,14 +147,23 @@ impl TestEnv {
         Self {
             state_machine,
             storage: rocksdb_storage,
+            lsn: Lsn::INVALID,
         }
     }
 
     pub async fn apply(&mut self, command: Command) -> Vec<Action> {
         let mut transaction = self.storage.transaction();
         let mut action_collector = ActionCollector::default();
+        self.lsn = self.lsn.next();
         self.state_machine
-            .apply(command, &mut transaction, &mut action_collector, true)
+            .apply(
+                command,
+                self.lsn,
+                MillisSinceEpoch::now(),
+                &mut transaction,
+                &mut action_collector,
+                true,
+            )
             .await
             .unwrap();
 
@@ -890,7 +903,9 @@ async fn send_ingress_response_to_multiple_targets() -> TestResult {
             headers: vec![],
             execution_time: None,
             completion_retention_duration: None,
+            journal_retention_duration: None,
             idempotency_key: None,
+            dead_letter_queue: false,
             submit_notification_sink: None,
         }))
         .await;
@@ -976,6 +991,68 @@ async fn send_ingress_response_to_multiple_targets() -> TestResult {
     Ok(())
 }
 
+#[test(restate_core::test)]
+async fn publish_output_to_kafka_sink() -> TestResult {
+    let mut test_env = TestEnv::create().await;
+    let invocation_target = InvocationTarget::mock_virtual_object();
+    let invocation_id = InvocationId::mock_generate(&invocation_target);
+
+    let _ = test_env
+        .apply(Command::Invoke(ServiceInvocation::initialize(
+            invocation_id,
+            invocation_target.clone(),
+            Source::Ingress(PartitionProcessorRpcRequestId::default()),
+        )))
+        .await;
+
+    let response_bytes = Bytes::from_static(b"123");
+    let _ = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::JournalEntry {
+                entry_index: 1,
+                entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
+                    EntryResult::Success(response_bytes.clone()),
+                )),
+            },
+        }))
+        .await;
+    let actions = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::EndAndPublish {
+                sinks: vec![KafkaSink {
+                    cluster: "my-cluster".to_owned(),
+                    topic: "my-topic".to_owned(),
+                    key_template: Some("{service}-{key}".to_owned()),
+                }],
+            },
+        }))
+        .await;
+
+    assert_that!(
+        actions,
+        contains(pat!(Action::NewOutboxMessage {
+            message: pat!(OutboxMessage::KafkaRecord(pat!(KafkaRecord {
+                invocation_id: eq(invocation_id),
+                cluster: eq("my-cluster"),
+                topic: eq("my-topic"),
+                key: some(eq(Bytes::from(format!(
+                    "{}-{}",
+                    invocation_target.service_name(),
+                    invocation_target.key().unwrap()
+                )))),
+                result: eq(ResponseResult::Success(response_bytes)),
+            })))
+        }))
+    );
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
 #[test(restate_core::test)]
 async fn truncate_outbox_from_empty() -> Result<(), Error> {
     // An outbox message with index 0 has been successfully processed, and must now be truncated
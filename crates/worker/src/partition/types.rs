@@ -24,7 +24,9 @@ pub(crate) trait OutboxMessageExt {
         result: ResponseResult,
     ) -> OutboxMessage;
 
-    fn to_command(self) -> Command;
+    /// Returns `None` for the [`OutboxMessage::KafkaRecord`]s, which are published to Kafka
+    /// instead of being proposed to a partition.
+    fn to_command(self) -> Option<Command>;
 }
 
 impl OutboxMessageExt for OutboxMessage {
@@ -44,13 +46,16 @@ impl OutboxMessageExt for OutboxMessage {
         })
     }
 
-    fn to_command(self) -> Command {
+    fn to_command(self) -> Option<Command> {
         match self {
-            OutboxMessage::ServiceInvocation(si) => Command::Invoke(si),
-            OutboxMessage::ServiceResponse(sr) => Command::InvocationResponse(sr),
-            OutboxMessage::InvocationTermination(it) => Command::TerminateInvocation(it),
-            OutboxMessage::AttachInvocation(ai) => Command::AttachInvocation(ai),
-            OutboxMessage::NotifySignal(notify_signal) => Command::NotifySignal(notify_signal),
+            OutboxMessage::ServiceInvocation(si) => Some(Command::Invoke(si)),
+            OutboxMessage::ServiceResponse(sr) => Some(Command::InvocationResponse(sr)),
+            OutboxMessage::InvocationTermination(it) => Some(Command::TerminateInvocation(it)),
+            OutboxMessage::AttachInvocation(ai) => Some(Command::AttachInvocation(ai)),
+            OutboxMessage::NotifySignal(notify_signal) => {
+                Some(Command::NotifySignal(notify_signal))
+            }
+            OutboxMessage::KafkaRecord(_) => None,
         }
     }
 }
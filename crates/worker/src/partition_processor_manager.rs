@@ -63,7 +63,9 @@ use restate_types::net::metadata::MetadataKind;
 use restate_types::net::partition_processor::PartitionLeaderService;
 use restate_types::net::partition_processor_manager::{
     ControlProcessor, ControlProcessors, CreateSnapshotRequest, CreateSnapshotResponse,
+    DeleteSnapshotRequest, DeleteSnapshotResponse, ListSnapshotsRequest, ListSnapshotsResponse,
     PartitionManagerService, ProcessorCommand, Snapshot, SnapshotError as NetSnapshotError,
+    SnapshotInfo,
 };
 use restate_types::net::{RpcRequest as _, UnaryMessage};
 use restate_types::partition_table::PartitionTable;
@@ -351,6 +353,14 @@ impl PartitionProcessorManager {
                 let request = msg.into_typed::<CreateSnapshotRequest>();
                 self.handle_create_snapshot_request(request);
             }
+            ServiceMessage::Rpc(msg) if msg.msg_type() == ListSnapshotsRequest::TYPE => {
+                let request = msg.into_typed::<ListSnapshotsRequest>();
+                self.handle_list_snapshots_request(request);
+            }
+            ServiceMessage::Rpc(msg) if msg.msg_type() == DeleteSnapshotRequest::TYPE => {
+                let request = msg.into_typed::<DeleteSnapshotRequest>();
+                self.handle_delete_snapshot_request(request);
+            }
             msg => {
                 msg.fail(Verdict::MessageUnrecognized);
             }
@@ -812,14 +822,12 @@ impl PartitionProcessorManager {
                             processor_state.stop();
                         }
                     }
-                } else if let Some(partition_key_range) = partition_table
-                    .get_partition(&partition_id)
-                    .map(|partition| &partition.key_range)
-                {
+                } else if let Some(partition) = partition_table.get_partition(&partition_id) {
                     debug!(%partition_id, "Starting new partition processor to run as {}", control_processor.command);
                     let starting_task = self.create_start_partition_processor_task(
                         partition_id,
-                        partition_key_range.clone(),
+                        partition.key_range.clone(),
+                        partition.parents.clone(),
                     );
 
                     self.asynchronous_operations
@@ -908,6 +916,8 @@ impl PartitionProcessorManager {
                     })
                     .or_insert_with(|| response.clone());
 
+                self.spawn_prune_snapshots_task(metadata.partition_id, metadata.get_log_id());
+
                 (metadata.partition_id, Ok(response))
             }
             Err(snapshot_error) => (snapshot_error.partition_id(), Err(snapshot_error)),
@@ -925,6 +935,48 @@ impl PartitionProcessorManager {
         }
     }
 
+    /// Deletes the snapshots of a partition that are no longer retained, if snapshot retention is
+    /// configured. Failures are logged and retried after the next snapshot has been created.
+    fn spawn_prune_snapshots_task(&self, partition_id: PartitionId, log_id: LogId) {
+        let Some(snapshot_repository) = self.snapshot_repository.clone() else {
+            return;
+        };
+
+        if !self
+            .updateable_config
+            .live_load()
+            .worker
+            .snapshots
+            .has_retention()
+        {
+            return;
+        }
+
+        let bifrost = self.bifrost.clone();
+        let _ = TaskCenter::spawn(TaskKind::Disposable, "prune-snapshots", async move {
+            let result = async {
+                let trim_point = bifrost.get_trim_point(log_id).await?;
+                snapshot_repository.prune(partition_id, trim_point).await
+            }
+            .await;
+
+            match result {
+                Ok(deleted) if !deleted.is_empty() => {
+                    info!(
+                        %partition_id,
+                        deleted_snapshots = deleted.len(),
+                        "Pruned obsolete snapshots from repository"
+                    );
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    warn!(%partition_id, %err, "Failed to prune obsolete snapshots");
+                }
+            }
+            Ok(())
+        });
+    }
+
     fn trigger_periodic_partition_snapshots(&mut self) {
         let Some(snapshot_repository) = self.snapshot_repository.clone() else {
             return;
@@ -1120,6 +1172,7 @@ impl PartitionProcessorManager {
         &mut self,
         partition_id: PartitionId,
         key_range: RangeInclusive<PartitionKey>,
+        parents: Vec<PartitionId>,
     ) -> SpawnPartitionProcessorTask {
         // the name is also used as thread names for the corresponding tokio runtimes, let's keep
         // it short.
@@ -1132,6 +1185,7 @@ impl PartitionProcessorManager {
             task_name.clone(),
             partition_id,
             key_range,
+            parents,
             self.updateable_config.clone(),
             self.bifrost.clone(),
             self.partition_store_manager.clone(),
@@ -1198,6 +1252,56 @@ impl PartitionProcessorManager {
             };
         });
     }
+
+    fn handle_list_snapshots_request(&self, request: Incoming<Rpc<ListSnapshotsRequest>>) {
+        let (reciprocal, body) = request.split();
+        let snapshot_repository = self.snapshot_repository.clone();
+        tokio::spawn(async move {
+            let Some(snapshot_repository) = snapshot_repository else {
+                reciprocal.send(ListSnapshotsResponse {
+                    result: Err(NetSnapshotError::RepositoryNotConfigured),
+                });
+                return;
+            };
+
+            let result = snapshot_repository
+                .list(body.partition_id)
+                .await
+                .map(|snapshots| {
+                    snapshots
+                        .into_iter()
+                        .map(|snapshot| SnapshotInfo {
+                            snapshot_id: snapshot.metadata.snapshot_id,
+                            min_applied_lsn: snapshot.metadata.min_applied_lsn,
+                            created_at: (*snapshot.metadata.created_at).into(),
+                            node_name: snapshot.metadata.node_name,
+                            latest: snapshot.is_latest,
+                        })
+                        .collect()
+                })
+                .map_err(|err| NetSnapshotError::RepositoryError(err.to_string()));
+            reciprocal.send(ListSnapshotsResponse { result });
+        });
+    }
+
+    fn handle_delete_snapshot_request(&self, request: Incoming<Rpc<DeleteSnapshotRequest>>) {
+        let (reciprocal, body) = request.split();
+        let snapshot_repository = self.snapshot_repository.clone();
+        tokio::spawn(async move {
+            let Some(snapshot_repository) = snapshot_repository else {
+                reciprocal.send(DeleteSnapshotResponse {
+                    result: Err(NetSnapshotError::RepositoryNotConfigured),
+                });
+                return;
+            };
+
+            let result = snapshot_repository
+                .delete(body.partition_id, body.snapshot_id)
+                .await
+                .map_err(|err| NetSnapshotError::RepositoryError(err.to_string()));
+            reciprocal.send(DeleteSnapshotResponse { result });
+        });
+    }
 }
 
 struct AsynchronousEvent {
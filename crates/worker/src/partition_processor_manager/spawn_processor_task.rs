@@ -12,6 +12,7 @@ use std::ops::RangeInclusive;
 use std::sync::Arc;
 use std::time::Duration;
 
+use anyhow::{anyhow, bail};
 use tokio::sync::{mpsc, watch};
 use tracing::{debug, info, instrument, warn};
 
@@ -21,13 +22,14 @@ use restate_invoker_impl::Service as InvokerService;
 use restate_partition_store::snapshots::LocalPartitionSnapshot;
 use restate_partition_store::{OpenMode, PartitionStore, PartitionStoreManager};
 use restate_service_protocol::codec::ProtobufRawEntryCodec;
+use restate_storage_api::fsm_table::ReadOnlyFsmTable;
 use restate_types::SharedString;
 use restate_types::cluster::cluster_state::PartitionProcessorStatus;
 use restate_types::config::{Configuration, WorkerOptions};
 use restate_types::identifiers::{PartitionId, PartitionKey};
 use restate_types::live::Live;
 use restate_types::live::LiveLoadExt;
-use restate_types::logs::Lsn;
+use restate_types::logs::{Lsn, SequenceNumber};
 use restate_types::schema::Schema;
 
 use crate::PartitionProcessorBuilder;
@@ -41,6 +43,7 @@ pub struct SpawnPartitionProcessorTask {
     task_name: SharedString,
     partition_id: PartitionId,
     key_range: RangeInclusive<PartitionKey>,
+    parents: Vec<PartitionId>,
     configuration: Live<Configuration>,
     bifrost: Bifrost,
     partition_store_manager: PartitionStoreManager,
@@ -54,6 +57,7 @@ impl SpawnPartitionProcessorTask {
         task_name: SharedString,
         partition_id: PartitionId,
         key_range: RangeInclusive<PartitionKey>,
+        parents: Vec<PartitionId>,
         configuration: Live<Configuration>,
         bifrost: Bifrost,
         partition_store_manager: PartitionStoreManager,
@@ -64,6 +68,7 @@ impl SpawnPartitionProcessorTask {
             task_name,
             partition_id,
             key_range,
+            parents,
             configuration,
             bifrost,
             partition_store_manager,
@@ -89,6 +94,7 @@ impl SpawnPartitionProcessorTask {
             task_name,
             partition_id,
             key_range,
+            parents,
             configuration,
             bifrost,
             partition_store_manager,
@@ -127,6 +133,7 @@ impl SpawnPartitionProcessorTask {
             net_rx,
             watch_tx,
             invoker.handle(),
+            status_reader.clone(),
         );
 
         let invoker_name = Arc::from(format!("invoker-{partition_id}"));
@@ -143,6 +150,7 @@ impl SpawnPartitionProcessorTask {
                 move || async move {
                     let partition_store = open_partition_store(
                         partition_id,
+                        &parents,
                         partition_store_manager,
                         snapshot_repository,
                         fast_forward_lsn,
@@ -185,6 +193,7 @@ impl SpawnPartitionProcessorTask {
 
 async fn open_partition_store(
     partition_id: PartitionId,
+    parents: &[PartitionId],
     partition_store_manager: PartitionStoreManager,
     snapshot_repository: Option<SnapshotRepository>,
     fast_forward_lsn: Option<Lsn>,
@@ -197,19 +206,37 @@ async fn open_partition_store(
 
     if partition_store_exists && fast_forward_lsn.is_none() {
         // We have an initialized partition store, and no fast-forward target - go on and open it.
-        Ok(partition_store_manager
+        let mut partition_store = partition_store_manager
             .open_partition_store(
                 partition_id,
-                key_range,
+                key_range.clone(),
                 OpenMode::OpenExisting,
                 &options.storage.rocksdb,
             )
-            .await?)
+            .await?;
+
+        if !parents.is_empty() && partition_store.get_applied_lsn().await?.is_none() {
+            // The bootstrap from the parent partitions was interrupted, start over
+            info!(%partition_id, "Dropping partially bootstrapped partition store");
+            partition_store_manager.drop_partition(partition_id).await;
+            return bootstrap_from_parents(
+                partition_id,
+                parents,
+                partition_store_manager,
+                snapshot_repository.as_ref(),
+                options,
+                key_range,
+            )
+            .await;
+        }
+
+        Ok(partition_store)
     } else {
         // We either don't have an existing local partition store initialized - or we have a
         // fast-forward LSN target for the local state (probably due to seeing a log trim-gap).
         Ok(create_or_recreate_store(
             partition_id,
+            parents,
             partition_store_manager,
             snapshot_repository,
             fast_forward_lsn,
@@ -227,6 +254,7 @@ async fn open_partition_store(
 /// LSN greater than the fast-forward target.
 async fn create_or_recreate_store(
     partition_id: PartitionId,
+    parents: &[PartitionId],
     partition_store_manager: PartitionStoreManager,
     snapshot_repository: Option<SnapshotRepository>,
     fast_forward_lsn: Option<Lsn>,
@@ -250,6 +278,17 @@ async fn create_or_recreate_store(
     };
 
     Ok(match (snapshot, fast_forward_lsn) {
+        (None, None) if !parents.is_empty() => {
+            bootstrap_from_parents(
+                partition_id,
+                parents,
+                partition_store_manager,
+                snapshot_repository.as_ref(),
+                options,
+                key_range,
+            )
+            .await?
+        }
         (None, None) => {
             debug!(%partition_id, "No snapshot found to bootstrap partition, creating new store");
             partition_store_manager
@@ -345,6 +384,80 @@ async fn create_or_recreate_store(
     })
 }
 
+/// Creates the partition store of a split or merged partition from the latest snapshots of its
+/// parents. The snapshots must cover the whole, sealed, log of the parents.
+async fn bootstrap_from_parents(
+    partition_id: PartitionId,
+    parents: &[PartitionId],
+    partition_store_manager: PartitionStoreManager,
+    snapshot_repository: Option<&SnapshotRepository>,
+    options: &WorkerOptions,
+    key_range: RangeInclusive<PartitionKey>,
+) -> anyhow::Result<PartitionStore> {
+    let Some(repository) = snapshot_repository else {
+        bail!(
+            "Partition {partition_id} was split or merged from {parents:?}; bootstrapping it \
+            requires a snapshot repository"
+        );
+    };
+
+    let logs = Metadata::with_current(|m| m.logs_snapshot());
+    let mut snapshots = Vec::with_capacity(parents.len());
+    for parent_id in parents {
+        let snapshot = repository
+            .get_latest(*parent_id)
+            .await?
+            .ok_or_else(|| anyhow!("No snapshot found for parent partition {parent_id}"))?;
+
+        let sealed_tail = logs
+            .chain(&snapshot.log_id)
+            .and_then(|chain| chain.sealed_tail_lsn())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Log {} of parent partition {parent_id} is not sealed",
+                    snapshot.log_id
+                )
+            })?;
+        if snapshot.min_applied_lsn < sealed_tail.prev() {
+            bail!(
+                "The latest snapshot of parent partition {parent_id} is at LSN {}, but its log \
+                has been sealed at {sealed_tail}",
+                snapshot.min_applied_lsn
+            );
+        }
+
+        snapshots.push((*parent_id, snapshot));
+    }
+
+    info!(%partition_id, ?parents, "Bootstrapping partition store from the parent partitions");
+    let snapshot_paths: Vec<_> = snapshots
+        .iter()
+        .map(|(_, snapshot)| snapshot.base_dir.clone())
+        .collect();
+
+    let partition_store = partition_store_manager
+        .open_partition_store_from_parents(
+            partition_id,
+            key_range,
+            snapshots,
+            &options.storage.rocksdb,
+        )
+        .await?;
+
+    for snapshot_path in snapshot_paths {
+        if let Err(err) = tokio::fs::remove_dir_all(&snapshot_path).await {
+            warn!(
+                %partition_id,
+                snapshot_path = %snapshot_path.display(),
+                %err,
+                "Failed to remove local snapshot directory, continuing with startup",
+            );
+        }
+    }
+
+    Ok(partition_store)
+}
+
 async fn import_snapshot(
     partition_id: PartitionId,
     key_range: RangeInclusive<PartitionKey>,
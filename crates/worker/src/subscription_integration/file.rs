@@ -0,0 +1,455 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use bytes::Bytes;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tracing::{debug, info, warn};
+
+use restate_bifrost::Bifrost;
+use restate_core::{TaskCenter, TaskId, TaskKind, cancellation_watcher, my_node_id};
+use restate_ingress_kafka::{
+    DeduplicationId, DispatchIngressEvent, EventOrigin, IngressDispatcher, IngressEvent,
+};
+use restate_types::PlainNodeId;
+use restate_types::config::data_dir;
+use restate_types::identifiers::SubscriptionId;
+use restate_types::invocation::Header;
+use restate_types::live::Live;
+use restate_types::schema::Schema;
+use restate_types::schema::subscriptions::{Source, Subscription};
+
+use super::SubscriptionSource;
+use crate::WorkerHandleError;
+
+const OFFSETS_DIR: &str = "subscriptions";
+const NDJSON_EXTENSIONS: [&str; 2] = ["ndjson", "jsonl"];
+/// Subscription option to select the top level field of each line to use as key.
+const KEY_FIELD_OPTION: &str = "key.field";
+/// Subscription option to configure how often the files are checked for new lines.
+const POLL_INTERVAL_OPTION: &str = "poll.interval";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Local NDJSON files, or directories of NDJSON files, tailed by every worker node.
+///
+/// Each line is an event. The offset of the last dispatched line of each file is persisted in the
+/// node data directory, and the line offset is used as deduplication index, so lines re-read
+/// after a restart are discarded by the partition processors. Malformed lines, e.g. lines without
+/// the configured key field, are logged and skipped.
+#[derive(Clone)]
+pub(crate) struct FileSource {
+    dispatcher: IngressDispatcher,
+    schema: Live<Schema>,
+    tasks: Arc<Mutex<HashMap<SubscriptionId, TaskId>>>,
+}
+
+impl fmt::Debug for FileSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileSource")
+            .field("tasks", &self.tasks)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileSource {
+    pub(crate) fn new(bifrost: Bifrost, schema: Live<Schema>) -> Self {
+        Self {
+            dispatcher: IngressDispatcher::new(bifrost),
+            schema,
+            tasks: Default::default(),
+        }
+    }
+
+    fn start(&self, subscription: Subscription) -> Result<(), WorkerHandleError> {
+        let subscription_id = subscription.id();
+        let tail = FileTail::new(subscription, self.dispatcher.clone(), self.schema.clone());
+
+        let mut tasks = self.tasks.lock();
+        if let Some(task_id) = tasks.remove(&subscription_id) {
+            TaskCenter::cancel_task(task_id);
+        }
+        let task_id = TaskCenter::spawn(TaskKind::SystemService, "file-subscription", tail.run())
+            .map_err(|_| WorkerHandleError::Unreachable)?;
+        tasks.insert(subscription_id, task_id);
+        Ok(())
+    }
+
+    fn stop(&self, subscription_id: SubscriptionId) {
+        if let Some(task_id) = self.tasks.lock().remove(&subscription_id) {
+            TaskCenter::cancel_task(task_id);
+        }
+    }
+}
+
+impl SubscriptionSource for FileSource {
+    async fn start_subscription(
+        &self,
+        subscription: Subscription,
+    ) -> Result<(), WorkerHandleError> {
+        self.start(subscription)
+    }
+
+    async fn stop_subscription(&self, id: SubscriptionId) -> Result<(), WorkerHandleError> {
+        self.stop(id);
+        Ok(())
+    }
+
+    async fn update_subscriptions(
+        &self,
+        subscriptions: Vec<Subscription>,
+    ) -> Result<(), WorkerHandleError> {
+        let mut running_subscriptions: HashSet<_> = self.tasks.lock().keys().copied().collect();
+
+        for subscription in subscriptions {
+            if !running_subscriptions.remove(&subscription.id()) {
+                self.start(subscription)?;
+            }
+        }
+
+        for subscription_id in running_subscriptions {
+            self.stop(subscription_id);
+        }
+        Ok(())
+    }
+}
+
+/// Identifies a generation of a file tailed by a node. The generation is bumped when the file is
+/// truncated, as the line offsets start over.
+#[derive(Debug, Hash)]
+struct FileDeduplicationId<'a> {
+    subscription: SubscriptionId,
+    node: PlainNodeId,
+    path: &'a str,
+    generation: u32,
+}
+
+impl fmt::Display for FileDeduplicationId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{}-{}",
+            self.subscription, self.node, self.path, self.generation
+        )
+    }
+}
+
+impl DeduplicationId for FileDeduplicationId<'_> {}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct FileOffset {
+    generation: u32,
+    offset: u64,
+}
+
+/// Offsets of the files tailed by a subscription, persisted in the node data directory.
+struct Offsets {
+    path: PathBuf,
+    files: HashMap<String, FileOffset>,
+}
+
+impl Offsets {
+    async fn load(subscription_id: SubscriptionId) -> anyhow::Result<Self> {
+        let dir = data_dir(OFFSETS_DIR);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("cannot create directory {}", dir.display()))?;
+        let path = dir.join(format!("{subscription_id}.json"));
+
+        let files = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("cannot parse offsets file {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("cannot read offsets file {}", path.display()));
+            }
+        };
+
+        Ok(Self { path, files })
+    }
+
+    async fn persist(&self) -> anyhow::Result<()> {
+        // Write and rename, so a crash never leaves a partially written file behind
+        let tmp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec(&self.files)?)
+            .await
+            .with_context(|| format!("cannot write offsets file {}", tmp_path.display()))?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .with_context(|| format!("cannot write offsets file {}", self.path.display()))
+    }
+}
+
+struct FileTail {
+    subscription: Subscription,
+    dispatcher: IngressDispatcher,
+    schema: Live<Schema>,
+    path: PathBuf,
+    key_field: Option<String>,
+    poll_interval: Duration,
+}
+
+impl FileTail {
+    fn new(
+        subscription: Subscription,
+        dispatcher: IngressDispatcher,
+        schema: Live<Schema>,
+    ) -> Self {
+        let Source::File { path } = subscription.source() else {
+            panic!("FileTail only supports file subscriptions");
+        };
+        let path = PathBuf::from(path);
+        let key_field = subscription.metadata().get(KEY_FIELD_OPTION).cloned();
+        let poll_interval = subscription
+            .metadata()
+            .get(POLL_INTERVAL_OPTION)
+            .and_then(|interval| match interval.parse::<humantime::Duration>() {
+                Ok(interval) => Some(interval.into()),
+                Err(err) => {
+                    warn!(
+                        "Bad option '{POLL_INTERVAL_OPTION}' for subscription {}, using the default: {err}",
+                        subscription.id()
+                    );
+                    None
+                }
+            })
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+
+        Self {
+            subscription,
+            dispatcher,
+            schema,
+            path,
+            key_field,
+            poll_interval,
+        }
+    }
+
+    async fn run(self) -> anyhow::Result<()> {
+        let mut offsets = Offsets::load(self.subscription.id()).await?;
+        info!(
+            restate.subscription.id = %self.subscription.id(),
+            "Tailing {}",
+            self.path.display()
+        );
+
+        let mut shutdown = std::pin::pin!(cancellation_watcher());
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => {
+                    return Ok(());
+                }
+                _ = interval.tick() => {
+                    if let Err(err) = self.poll(&mut offsets).await {
+                        warn!(
+                            restate.subscription.id = %self.subscription.id(),
+                            "Error when tailing {}, retrying: {err:#}",
+                            self.path.display()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    async fn poll(&self, offsets: &mut Offsets) -> anyhow::Result<()> {
+        for file in self.list_files().await? {
+            let file_path = file.to_string_lossy().into_owned();
+            let mut file_offset = offsets.files.get(&file_path).copied().unwrap_or_default();
+
+            let len = tokio::fs::metadata(&file).await?.len();
+            if len < file_offset.offset {
+                debug!("File {file_path} was truncated, starting over");
+                file_offset = FileOffset {
+                    generation: file_offset.generation + 1,
+                    offset: 0,
+                };
+            } else if len == file_offset.offset {
+                continue;
+            }
+
+            let lines = read_complete_lines(&file, file_offset.offset).await?;
+            for (offset, line) in lines {
+                let next_offset = offset + line.len() as u64 + 1;
+                match self.ingress_event(&file_path, file_offset.generation, offset, line) {
+                    Ok(Some(event)) => self.dispatcher.dispatch_ingress_event(event).await?,
+                    Ok(None) => {}
+                    Err(err) => {
+                        // Retrying won't fix the line, skip it so it doesn't block the file
+                        warn!(
+                            restate.subscription.id = %self.subscription.id(),
+                            "Skipping line: {err:#}"
+                        );
+                    }
+                }
+                file_offset.offset = next_offset;
+            }
+
+            offsets.files.insert(file_path, file_offset);
+            offsets.persist().await?;
+        }
+        Ok(())
+    }
+
+    async fn list_files(&self) -> anyhow::Result<Vec<PathBuf>> {
+        if !tokio::fs::metadata(&self.path).await?.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = vec![];
+        let mut entries = tokio::fs::read_dir(&self.path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| NDJSON_EXTENSIONS.contains(&ext))
+            {
+                files.push(path);
+            }
+        }
+        // Files are read in lexicographic order, e.g. to follow rotated files with increasing suffixes
+        files.sort();
+        Ok(files)
+    }
+
+    /// Returns the event to dispatch for the given line, if any. Fails if the line is malformed.
+    fn ingress_event(
+        &self,
+        file_path: &str,
+        generation: u32,
+        offset: u64,
+        line: Bytes,
+    ) -> anyhow::Result<Option<IngressEvent>> {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+
+        let key = match &self.key_field {
+            Some(key_field) => extract_key(&line, key_field).with_context(|| {
+                format!("cannot extract the key of the line at offset {offset} of {file_path}")
+            })?,
+            None => Bytes::new(),
+        };
+        let headers = vec![
+            Header::new("file.path", file_path),
+            Header::new("file.offset", offset.to_string()),
+            Header::new(
+                "restate.subscription.id",
+                self.subscription.id().to_string(),
+            ),
+        ];
+
+        let event = IngressEvent::new(
+            &self.subscription,
+            self.schema.pinned(),
+            key,
+            line,
+            FileDeduplicationId {
+                subscription: self.subscription.id(),
+                node: my_node_id().as_plain(),
+                path: file_path,
+                generation,
+            },
+            offset,
+            headers,
+            EventOrigin::File {
+                path: file_path,
+                offset,
+            },
+        )
+        .with_context(|| format!("bad line at offset {offset} of {file_path}"))?;
+
+        Ok(Some(event))
+    }
+}
+
+/// Reads the lines starting from the given offset, skipping the last line if not terminated yet.
+/// Returns the lines, without the trailing newline, together with their start offset.
+async fn read_complete_lines(path: &Path, offset: u64) -> std::io::Result<Vec<(u64, Bytes)>> {
+    let mut file = tokio::fs::File::open(path).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+    let mut reader = BufReader::new(file);
+
+    let mut lines = vec![];
+    let mut line_offset = offset;
+    loop {
+        let mut buf = vec![];
+        let read = reader.read_until(b'\n', &mut buf).await?;
+        if read == 0 || buf.last() != Some(&b'\n') {
+            // EOF, or the writer didn't complete the line yet
+            return Ok(lines);
+        }
+        buf.pop();
+        lines.push((line_offset, Bytes::from(buf)));
+        line_offset += read as u64;
+    }
+}
+
+fn extract_key(line: &[u8], key_field: &str) -> anyhow::Result<Bytes> {
+    let value: serde_json::Value = serde_json::from_slice(line)?;
+    match value.get(key_field) {
+        Some(serde_json::Value::String(key)) => Ok(Bytes::from(key.clone())),
+        Some(serde_json::Value::Number(key)) => Ok(Bytes::from(key.to_string())),
+        _ => anyhow::bail!("the field '{key_field}' is missing or is not a string or a number"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn read_complete_lines_skips_partial_line() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"{\"a\":1}\n{\"a\":2}\n{\"a\":").unwrap();
+
+        let lines = read_complete_lines(file.path(), 0).await.unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                (0, Bytes::from_static(b"{\"a\":1}")),
+                (8, Bytes::from_static(b"{\"a\":2}"))
+            ]
+        );
+
+        // Complete the last line and resume from the last offset
+        file.write_all(b"3}\n").unwrap();
+        let lines = read_complete_lines(file.path(), 16).await.unwrap();
+        assert_eq!(lines, vec![(16, Bytes::from_static(b"{\"a\":3}"))]);
+    }
+
+    #[test]
+    fn extract_key_from_line() {
+        assert_eq!(
+            extract_key(br#"{"user":"alice","amount":10}"#, "user").unwrap(),
+            Bytes::from_static(b"alice")
+        );
+        assert_eq!(
+            extract_key(br#"{"user":42}"#, "user").unwrap(),
+            Bytes::from_static(b"42")
+        );
+        assert!(extract_key(br#"{"amount":10}"#, "user").is_err());
+    }
+}
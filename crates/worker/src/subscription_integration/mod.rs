@@ -0,0 +1,134 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod file;
+mod kafka;
+mod webhook;
+
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use restate_bifrost::Bifrost;
+use restate_ingress_kafka::SubscriptionCommandSender;
+use restate_types::config::IngressOptions;
+use restate_types::identifiers::SubscriptionId;
+use restate_types::live::Live;
+use restate_types::schema::Schema;
+use restate_types::schema::subscriptions::{Source, Subscription, SubscriptionValidator};
+
+use crate::{SubscriptionController, WorkerHandleError};
+
+pub(crate) use file::FileSource;
+pub(crate) use kafka::KafkaSource;
+pub(crate) use webhook::WebhookSource;
+
+/// A source of events feeding the subscriptions. Each implementation runs only the subscriptions
+/// of one kind of [`Source`], the [`SubscriptionControllerHandle`] takes care of routing them.
+pub(crate) trait SubscriptionSource {
+    fn start_subscription(
+        &self,
+        subscription: Subscription,
+    ) -> impl Future<Output = Result<(), WorkerHandleError>> + Send;
+
+    /// Stops the subscription, if running. This is a no-op for unknown subscriptions.
+    fn stop_subscription(
+        &self,
+        id: SubscriptionId,
+    ) -> impl Future<Output = Result<(), WorkerHandleError>> + Send;
+
+    /// Runs only the provided set of subscriptions, which are all handled by this source.
+    fn update_subscriptions(
+        &self,
+        subscriptions: Vec<Subscription>,
+    ) -> impl Future<Output = Result<(), WorkerHandleError>> + Send;
+}
+
+#[derive(Debug, Clone)]
+pub struct SubscriptionControllerHandle {
+    ingress_options: Arc<IngressOptions>,
+    kafka: KafkaSource,
+    file: FileSource,
+    webhook: WebhookSource,
+}
+
+impl SubscriptionControllerHandle {
+    pub(crate) fn new(
+        ingress_options: IngressOptions,
+        commands_tx: SubscriptionCommandSender,
+        bifrost: Bifrost,
+        schema: Live<Schema>,
+    ) -> Self {
+        Self {
+            ingress_options: Arc::new(ingress_options),
+            kafka: KafkaSource::new(commands_tx),
+            file: FileSource::new(bifrost.clone(), schema.clone()),
+            webhook: WebhookSource::new(bifrost, schema),
+        }
+    }
+
+    pub(crate) fn webhook_source(&self) -> &WebhookSource {
+        &self.webhook
+    }
+}
+
+impl SubscriptionValidator for SubscriptionControllerHandle {
+    type Error = <IngressOptions as SubscriptionValidator>::Error;
+
+    fn validate(&self, subscription: Subscription) -> Result<Subscription, Self::Error> {
+        SubscriptionValidator::validate(self.ingress_options.deref(), subscription)
+    }
+}
+
+impl SubscriptionController for SubscriptionControllerHandle {
+    async fn start_subscription(
+        &self,
+        subscription: Subscription,
+    ) -> Result<(), WorkerHandleError> {
+        match subscription.source() {
+            Source::Kafka { .. } => self.kafka.start_subscription(subscription).await,
+            Source::File { .. } => self.file.start_subscription(subscription).await,
+            Source::Webhook { .. } => self.webhook.start_subscription(subscription).await,
+            // Published by the partition processors, there's nothing to consume
+            Source::Service { .. } => Ok(()),
+        }
+    }
+
+    async fn stop_subscription(&self, id: SubscriptionId) -> Result<(), WorkerHandleError> {
+        // We don't know the source of the subscription anymore, stopping is a no-op for the
+        // sources not running it.
+        self.kafka.stop_subscription(id).await?;
+        self.file.stop_subscription(id).await?;
+        self.webhook.stop_subscription(id).await
+    }
+
+    async fn update_subscriptions(
+        &self,
+        subscriptions: Vec<Subscription>,
+    ) -> Result<(), WorkerHandleError> {
+        let mut kafka_subscriptions = vec![];
+        let mut file_subscriptions = vec![];
+        let mut webhook_subscriptions = vec![];
+        for subscription in subscriptions {
+            match subscription.source() {
+                Source::Kafka { .. } => kafka_subscriptions.push(subscription),
+                Source::File { .. } => file_subscriptions.push(subscription),
+                Source::Webhook { .. } => webhook_subscriptions.push(subscription),
+                Source::Service { .. } => {}
+            }
+        }
+
+        self.kafka.update_subscriptions(kafka_subscriptions).await?;
+        self.file.update_subscriptions(file_subscriptions).await?;
+        self.webhook
+            .update_subscriptions(webhook_subscriptions)
+            .await
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use parking_lot::RwLock;
+use tracing::debug;
+
+use restate_bifrost::Bifrost;
+use restate_ingress_http::{RequestDispatcherError, WebhookDelivery, WebhookDispatcher};
+use restate_ingress_kafka::{DispatchIngressEvent, EventOrigin, IngressDispatcher, IngressEvent};
+use restate_types::identifiers::SubscriptionId;
+use restate_types::live::Live;
+use restate_types::schema::Schema;
+use restate_types::schema::subscriptions::{Source, Subscription};
+
+use super::SubscriptionSource;
+use crate::WorkerHandleError;
+
+/// Webhooks receiving signed deliveries on the ingress, which verifies them and pushes them to
+/// this source through the [`WebhookDispatcher`] interface.
+///
+/// Deliveries don't have a total order, hence each delivery is submitted with an idempotency key
+/// derived from the delivery id: retries of the same delivery are discarded by the partition
+/// processors, until the idempotency retention of the target handler expires.
+#[derive(Clone)]
+pub(crate) struct WebhookSource {
+    dispatcher: IngressDispatcher,
+    schema: Live<Schema>,
+    subscriptions: Arc<RwLock<HashMap<SubscriptionId, Subscription>>>,
+}
+
+impl fmt::Debug for WebhookSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookSource")
+            .field("subscriptions", &self.subscriptions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebhookSource {
+    pub(crate) fn new(bifrost: Bifrost, schema: Live<Schema>) -> Self {
+        Self {
+            dispatcher: IngressDispatcher::new(bifrost),
+            schema,
+            subscriptions: Default::default(),
+        }
+    }
+
+    async fn dispatch_delivery(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> Result<usize, RequestDispatcherError> {
+        let subscriptions: Vec<_> = self
+            .subscriptions
+            .read()
+            .values()
+            .filter(|subscription| {
+                matches!(subscription.source(), Source::Webhook { name } if *name == delivery.webhook)
+            })
+            .cloned()
+            .collect();
+
+        for subscription in &subscriptions {
+            let mut headers = delivery.headers.clone();
+            headers.push(restate_types::invocation::Header::new(
+                "restate.subscription.id",
+                subscription.id().to_string(),
+            ));
+
+            let event = IngressEvent::with_idempotency_key(
+                subscription,
+                self.schema.pinned(),
+                delivery.key.clone().map(Bytes::from).unwrap_or_default(),
+                delivery.payload.clone(),
+                format!("{}-{}", subscription.id(), delivery.id),
+                headers,
+                EventOrigin::Webhook {
+                    name: &delivery.webhook,
+                    delivery_id: &delivery.id,
+                },
+            )?;
+            self.dispatcher
+                .dispatch_ingress_event(event)
+                .await
+                .map_err(anyhow::Error::from)?;
+
+            debug!(
+                restate.subscription.id = %subscription.id(),
+                "Dispatched webhook delivery {}",
+                delivery.id
+            );
+        }
+
+        Ok(subscriptions.len())
+    }
+}
+
+impl WebhookDispatcher for WebhookSource {
+    fn dispatch(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> BoxFuture<'_, Result<usize, RequestDispatcherError>> {
+        self.dispatch_delivery(delivery).boxed()
+    }
+}
+
+impl SubscriptionSource for WebhookSource {
+    async fn start_subscription(
+        &self,
+        subscription: Subscription,
+    ) -> Result<(), WorkerHandleError> {
+        self.subscriptions
+            .write()
+            .insert(subscription.id(), subscription);
+        Ok(())
+    }
+
+    async fn stop_subscription(&self, id: SubscriptionId) -> Result<(), WorkerHandleError> {
+        self.subscriptions.write().remove(&id);
+        Ok(())
+    }
+
+    async fn update_subscriptions(
+        &self,
+        subscriptions: Vec<Subscription>,
+    ) -> Result<(), WorkerHandleError> {
+        *self.subscriptions.write() = subscriptions
+            .into_iter()
+            .map(|subscription| (subscription.id(), subscription))
+            .collect();
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use rand::seq::IndexedRandom;
 use regex::Regex;
 use restate_core::{TaskCenter, TaskKind, cancellation_token};
 use restate_local_cluster_runner::cluster::StartedCluster;
+use restate_local_cluster_runner::tls::generate_self_signed;
 use restate_local_cluster_runner::{
     cluster::Cluster,
     node::{BinarySource, Node},
@@ -289,3 +290,51 @@ async fn cluster_chaos_test() -> googletest::Result<()> {
 
     Ok(())
 }
+
+#[test_log::test(restate_core::test)]
+async fn tls_cluster() -> googletest::Result<()> {
+    let certificates = tempfile::tempdir()?;
+    let tls = generate_self_signed(certificates.path())?;
+
+    let mut base_config = Configuration::default();
+    base_config.common.default_num_partitions = 1;
+    base_config.admin.tls = Some(tls.clone());
+    base_config.ingress.tls = Some(tls.clone());
+
+    let nodes = Node::new_test_nodes(
+        base_config,
+        BinarySource::CargoTest,
+        enum_set!(Role::Admin | Role::MetadataServer | Role::Worker | Role::LogServer),
+        1,
+        false,
+    );
+
+    let cluster = Cluster::builder()
+        .cluster_name("tls-cluster")
+        .nodes(nodes)
+        .temp_base_dir()
+        .build()
+        .start()
+        .await?;
+
+    cluster.wait_healthy(Duration::from_secs(30)).await?;
+
+    let admin_address = cluster.nodes[0]
+        .admin_address()
+        .expect("node to have the admin role");
+
+    // without a client certificate, the handshake is rejected
+    let ca = reqwest::Certificate::from_pem(&std::fs::read(tls.ca_file.as_ref().unwrap())?)?;
+    let client = reqwest::Client::builder()
+        .add_root_certificate(ca)
+        .build()?;
+    assert!(
+        client
+            .get(format!("https://{admin_address}/health"))
+            .send()
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::Duration;
+
+use anyhow::bail;
+use cling::prelude::*;
+use itertools::Itertools;
+use tokio::time::Instant;
+
+use restate_cli_util::c_println;
+use restate_core::protobuf::cluster_ctrl_svc::{
+    ClusterStateRequest, DrainNodeRequest, new_cluster_ctrl_client,
+};
+use restate_types::PlainNodeId;
+use restate_types::nodes_config::Role;
+use restate_types::protobuf::cluster::{RunMode, node_state};
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "drain_node")]
+pub struct DrainNodeOpts {
+    /// The node to drain
+    #[arg()]
+    node_id: PlainNodeId,
+
+    /// How long to wait for the leaderships to be handed off
+    #[arg(long, default_value = "60s")]
+    timeout: humantime::Duration,
+}
+
+async fn drain_node(connection: &ConnectionInfo, opts: &DrainNodeOpts) -> anyhow::Result<()> {
+    let request = DrainNodeRequest {
+        node_id: u32::from(opts.node_id),
+    };
+
+    let response = connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel).drain_node(request).await
+        })
+        .await?
+        .into_inner();
+
+    if response.partition_ids.is_empty() {
+        c_println!("Node {} does not lead any partition", opts.node_id);
+        return Ok(());
+    }
+
+    c_println!(
+        "Moving the leadership of partitions [{}] away from node {}",
+        response.partition_ids.iter().join(","),
+        opts.node_id
+    );
+
+    let deadline = Instant::now() + *opts.timeout;
+    loop {
+        let led_partitions = led_partitions(connection, opts.node_id).await?;
+        if led_partitions.is_empty() {
+            break;
+        }
+
+        if Instant::now() >= deadline {
+            bail!(
+                "Node {} still leads partitions [{}] after {}",
+                opts.node_id,
+                led_partitions.iter().join(","),
+                opts.timeout
+            );
+        }
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    c_println!(
+        "Node {} no longer leads any partition and can be shut down",
+        opts.node_id
+    );
+
+    Ok(())
+}
+
+/// Returns the partitions for which the node runs the effective leader.
+async fn led_partitions(
+    connection: &ConnectionInfo,
+    node_id: PlainNodeId,
+) -> anyhow::Result<Vec<u32>> {
+    let cluster_state = connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel)
+                .get_cluster_state(ClusterStateRequest::default())
+                .await
+        })
+        .await?
+        .into_inner()
+        .cluster_state
+        .ok_or_else(|| anyhow::anyhow!("no cluster state returned"))?;
+
+    let led_partitions = cluster_state
+        .nodes
+        .get(&u32::from(node_id))
+        .and_then(|node_state| match &node_state.state {
+            Some(node_state::State::Alive(alive_node)) => Some(
+                alive_node
+                    .partitions
+                    .iter()
+                    .filter(|(_, status)| status.effective_mode() == RunMode::Leader)
+                    .map(|(partition_id, _)| *partition_id)
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    Ok(led_partitions)
+}
@@ -9,8 +9,10 @@
 // by the Apache License, Version 2.0.
 
 pub mod disable_node_checker;
+mod drain;
 pub mod list_nodes;
 mod remove_nodes;
+mod undrain;
 
 use cling::prelude::*;
 
@@ -22,4 +24,10 @@ pub enum Nodes {
     /// certain that the specified nodes are no longer part of any node sets, not members of the
     /// metadata cluster nor required to run partition processors.
     Remove(remove_nodes::RemoveNodesOpts),
+    /// Hands off the leadership of all partitions led by the given node to other nodes, and
+    /// waits until the node no longer leads any partition. Use this command before shutting
+    /// down a node for maintenance. The node does not lead partitions until it is undrained.
+    Drain(drain::DrainNodeOpts),
+    /// Lets a drained node lead partitions again.
+    Undrain(undrain::UndrainNodeOpts),
 }
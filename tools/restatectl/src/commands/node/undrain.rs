@@ -0,0 +1,42 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_core::protobuf::cluster_ctrl_svc::{UndrainNodeRequest, new_cluster_ctrl_client};
+use restate_types::PlainNodeId;
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "undrain_node")]
+pub struct UndrainNodeOpts {
+    /// The node to undrain
+    #[arg()]
+    node_id: PlainNodeId,
+}
+
+async fn undrain_node(connection: &ConnectionInfo, opts: &UndrainNodeOpts) -> anyhow::Result<()> {
+    let request = UndrainNodeRequest {
+        node_id: u32::from(opts.node_id),
+    };
+
+    connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel).undrain_node(request).await
+        })
+        .await?;
+
+    c_println!("Node {} can lead partitions again", opts.node_id);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_core::protobuf::cluster_ctrl_svc::{MergePartitionsRequest, new_cluster_ctrl_client};
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "merge_partitions")]
+pub struct MergePartitionsOpts {
+    /// The partition covering the lower partition keys
+    #[arg()]
+    left: u16,
+
+    /// The partition covering the partition keys right after the left partition
+    #[arg()]
+    right: u16,
+}
+
+async fn merge_partitions(
+    connection: &ConnectionInfo,
+    opts: &MergePartitionsOpts,
+) -> anyhow::Result<()> {
+    confirm_or_exit(&format!(
+        "Merge partitions {} and {}? Their logs will be sealed and replaced by the log of a new partition.",
+        opts.left, opts.right
+    ))?;
+
+    let request = MergePartitionsRequest {
+        left: u32::from(opts.left),
+        right: u32::from(opts.right),
+    };
+
+    let response = connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel)
+                .merge_partitions(request)
+                .await
+        })
+        .await?
+        .into_inner();
+
+    c_println!(
+        "Partitions {} and {} merged into partition {}",
+        opts.left,
+        opts.right,
+        response.partition_id
+    );
+
+    Ok(())
+}
@@ -10,6 +10,10 @@
 
 mod gen_metadata;
 pub mod list;
+mod merge;
+mod move_leader;
+mod pin;
+mod split;
 
 use cling::prelude::*;
 
@@ -20,4 +24,12 @@ pub enum Partitions {
     List(list::ListPartitionsOpts),
     /// Prints a generated partition table in JSON format
     GenerateMetadata(gen_metadata::GeneratePartitionTableOpts),
+    /// Split a partition into two partitions covering its key range
+    Split(split::SplitPartitionOpts),
+    /// Merge two partitions with adjacent key ranges into a single partition
+    Merge(merge::MergePartitionsOpts),
+    /// Move the leader of a partition to the given node
+    MoveLeader(move_leader::MoveLeaderOpts),
+    /// Restrict the partition processors of a partition to the given nodes
+    Pin(pin::PinPartitionOpts),
 }
@@ -0,0 +1,51 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_core::protobuf::cluster_ctrl_svc::{MoveLeaderRequest, new_cluster_ctrl_client};
+use restate_types::PlainNodeId;
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "move_leader")]
+pub struct MoveLeaderOpts {
+    /// The partition id whose leader to move
+    #[arg()]
+    partition_id: u16,
+
+    /// The node which should run the leader of the partition
+    #[arg(long)]
+    to: PlainNodeId,
+}
+
+async fn move_leader(connection: &ConnectionInfo, opts: &MoveLeaderOpts) -> anyhow::Result<()> {
+    let request = MoveLeaderRequest {
+        partition_id: u32::from(opts.partition_id),
+        node_id: u32::from(opts.to),
+    };
+
+    connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel).move_leader(request).await
+        })
+        .await?;
+
+    c_println!(
+        "Node {} is now the preferred leader of partition {}, the leadership will move shortly",
+        opts.to,
+        opts.partition_id
+    );
+
+    Ok(())
+}
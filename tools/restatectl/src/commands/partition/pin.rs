@@ -0,0 +1,71 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use cling::prelude::*;
+use itertools::Itertools;
+
+use restate_cli_util::c_println;
+use restate_core::protobuf::cluster_ctrl_svc::{PinPartitionRequest, new_cluster_ctrl_client};
+use restate_types::PlainNodeId;
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "pin_partition")]
+pub struct PinPartitionOpts {
+    /// The partition id to pin
+    #[arg()]
+    partition_id: u16,
+
+    /// The nodes the partition processors are restricted to. Specify multiple nodes as a
+    /// comma-separated list or specify the option multiple times.
+    #[arg(
+        long,
+        visible_alias = "node",
+        value_delimiter = ',',
+        required_unless_present = "clear"
+    )]
+    nodes: Vec<PlainNodeId>,
+
+    /// Remove all placement constraints of the partition, including its preferred leader
+    #[arg(long, conflicts_with = "nodes")]
+    clear: bool,
+}
+
+async fn pin_partition(connection: &ConnectionInfo, opts: &PinPartitionOpts) -> anyhow::Result<()> {
+    let request = PinPartitionRequest {
+        partition_id: u32::from(opts.partition_id),
+        node_ids: opts.nodes.iter().copied().map(u32::from).collect(),
+    };
+
+    connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel)
+                .pin_partition(request)
+                .await
+        })
+        .await?;
+
+    if opts.clear {
+        c_println!(
+            "Removed the placement constraints of partition {}",
+            opts.partition_id
+        );
+    } else {
+        c_println!(
+            "Pinned partition {} to nodes [{}]",
+            opts.partition_id,
+            opts.nodes.iter().join(",")
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_core::protobuf::cluster_ctrl_svc::{SplitPartitionRequest, new_cluster_ctrl_client};
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "split_partition")]
+pub struct SplitPartitionOpts {
+    /// The partition id to split
+    #[arg()]
+    partition_id: u16,
+
+    /// First partition key of the second partition, defaults to the middle of the key range
+    #[arg(long)]
+    at: Option<u64>,
+}
+
+async fn split_partition(
+    connection: &ConnectionInfo,
+    opts: &SplitPartitionOpts,
+) -> anyhow::Result<()> {
+    confirm_or_exit(&format!(
+        "Split partition {}? Its log will be sealed and replaced by the logs of two new partitions.",
+        opts.partition_id
+    ))?;
+
+    let request = SplitPartitionRequest {
+        partition_id: u32::from(opts.partition_id),
+        split_key: opts.at,
+    };
+
+    let response = connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel)
+                .split_partition(request)
+                .await
+        })
+        .await?
+        .into_inner();
+
+    c_println!(
+        "Partition {} split into partitions {}",
+        opts.partition_id,
+        response
+            .partition_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_core::protobuf::cluster_ctrl_svc::{
+    DeletePartitionSnapshotRequest, new_cluster_ctrl_client,
+};
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[clap(visible_alias = "delete")]
+#[cling(run = "delete_snapshot")]
+pub struct DeleteSnapshotOpts {
+    /// The partition id the snapshot belongs to
+    #[arg()]
+    partition_id: u16,
+
+    /// The id of the snapshot to delete, e.g. "snap_..."; the latest snapshot cannot be deleted
+    #[arg()]
+    snapshot_id: String,
+}
+
+async fn delete_snapshot(
+    connection: &ConnectionInfo,
+    opts: &DeleteSnapshotOpts,
+) -> anyhow::Result<()> {
+    confirm_or_exit(&format!(
+        "Delete snapshot {} of partition {} from the snapshot repository?",
+        opts.snapshot_id, opts.partition_id
+    ))?;
+
+    let request = DeletePartitionSnapshotRequest {
+        partition_id: u32::from(opts.partition_id),
+        snapshot_id: opts.snapshot_id.clone(),
+    };
+
+    connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel)
+                .delete_partition_snapshot(request)
+                .await
+        })
+        .await?;
+
+    c_println!(
+        "Deleted snapshot {} of partition {}",
+        opts.snapshot_id,
+        opts.partition_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2023 - 2025 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local};
+use cling::prelude::*;
+
+use restate_cli_util::_comfy_table::{Cell, Color, Table};
+use restate_cli_util::c_println;
+use restate_cli_util::ui::console::StyledTable;
+use restate_cli_util::ui::{Tense, timestamp_as_human_duration};
+use restate_core::protobuf::cluster_ctrl_svc::{
+    ListPartitionSnapshotsRequest, new_cluster_ctrl_client,
+};
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[clap(visible_alias = "list")]
+#[cling(run = "list_snapshots")]
+pub struct ListSnapshotsOpts {
+    /// The partition id to list the snapshots of
+    #[arg()]
+    partition_id: u16,
+}
+
+async fn list_snapshots(
+    connection: &ConnectionInfo,
+    opts: &ListSnapshotsOpts,
+) -> anyhow::Result<()> {
+    let request = ListPartitionSnapshotsRequest {
+        partition_id: u32::from(opts.partition_id),
+    };
+
+    let response = connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel)
+                .list_partition_snapshots(request)
+                .await
+        })
+        .await?
+        .into_inner();
+
+    if response.snapshots.is_empty() {
+        c_println!("No snapshots found for partition {}", opts.partition_id);
+        return Ok(());
+    }
+
+    let mut snapshots_table = Table::new_styled();
+    snapshots_table.set_styled_header(vec![
+        "SNAPSHOT-ID",
+        "MIN-APPLIED-LSN",
+        "CREATED",
+        "NODE",
+        "LATEST",
+    ]);
+
+    for snapshot in response.snapshots {
+        let created_at: DateTime<Local> =
+            (SystemTime::UNIX_EPOCH + Duration::from_millis(snapshot.created_at_millis)).into();
+
+        snapshots_table.add_row(vec![
+            Cell::new(snapshot.snapshot_id),
+            Cell::new(snapshot.min_applied_lsn),
+            Cell::new(timestamp_as_human_duration(created_at, Tense::Past)),
+            Cell::new(snapshot.node_name),
+            if snapshot.latest {
+                Cell::new("yes").fg(Color::Green)
+            } else {
+                Cell::new("")
+            },
+        ]);
+    }
+
+    c_println!("{}", snapshots_table);
+
+    Ok(())
+}
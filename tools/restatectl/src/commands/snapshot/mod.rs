@@ -9,6 +9,8 @@
 // by the Apache License, Version 2.0.
 
 mod create_snapshot;
+mod delete_snapshot;
+mod list_snapshots;
 
 use cling::prelude::*;
 
@@ -16,4 +18,8 @@ use cling::prelude::*;
 pub enum Snapshot {
     /// Create.
     CreateSnapshot(create_snapshot::CreateSnapshotOpts),
+    /// List the snapshots of a partition in the snapshot repository
+    ListSnapshots(list_snapshots::ListSnapshotsOpts),
+    /// Delete a snapshot of a partition from the snapshot repository
+    DeleteSnapshot(delete_snapshot::DeleteSnapshotOpts),
 }